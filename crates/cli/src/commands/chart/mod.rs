@@ -1,9 +1,14 @@
+mod report;
+mod svg;
+
 use crate::execute::Execute;
 use anyhow::{anyhow, Result};
 use clap::Args;
-use rain_orderbook_common::fuzz::{FuzzRunner, FuzzRunnerContext};
+use rain_orderbook_common::fuzz::{ChartData, FuzzRunner, FuzzRunnerContext};
+use report::{render_html, ChartReport, MetricReport};
 use std::fs::read_to_string;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use tracing::info;
 
 #[derive(Args, Clone)]
@@ -14,6 +19,24 @@ pub struct Chart {
         help = "Path to the .rain file specifying the order"
     )]
     dotrain_file: PathBuf,
+
+    #[arg(
+        short = 'o',
+        long,
+        default_value = "charts",
+        help = "Directory the SVG plots and report.html are written to"
+    )]
+    output_dir: PathBuf,
+
+    #[arg(long, help = "Also convert every SVG plot to PNG")]
+    png: bool,
+
+    #[arg(
+        long,
+        default_value = "rsvg-convert",
+        help = "Command used to convert SVG to PNG, called as <command> -o <png> <svg>"
+    )]
+    svg_converter: String,
 }
 
 impl Execute for Chart {
@@ -23,11 +46,112 @@ impl Execute for Chart {
         let mut context = FuzzRunnerContext::new(&dotrain, None, None)?;
         let chart_data = fuzzer.make_chart_data(&mut context).await?;
 
-        info!("{:#?}", chart_data);
+        std::fs::create_dir_all(&self.output_dir)?;
+        let reports = self.render_charts(&chart_data)?;
+
+        let report_path = self.output_dir.join("report.html");
+        let title = self
+            .dotrain_file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        std::fs::write(&report_path, render_html(&title, &reports))?;
+        info!(
+            "Rendered {} charts to {}",
+            reports.len(),
+            report_path.display()
+        );
+        Ok(())
+    }
+}
+
+impl Chart {
+    fn render_charts(&self, chart_data: &ChartData) -> Result<Vec<ChartReport>> {
+        let mut keys: Vec<&String> = chart_data.charts.keys().collect();
+        keys.sort();
+
+        let mut reports = vec![];
+        for key in keys {
+            let chart = &chart_data.charts[key];
+            let data = &chart_data
+                .scenarios_data
+                .get(&chart.scenario.key)
+                .ok_or_else(|| anyhow!("No fuzz results for scenario {}", chart.scenario.key))?
+                .data;
+
+            let metrics = chart
+                .metrics
+                .iter()
+                .flatten()
+                .map(|metric| MetricReport::new(metric, data))
+                .collect::<Result<Vec<_>>>()
+                .map_err(|e| anyhow!("Chart {}: {}", key, e))?;
+
+            let mut plots = vec![];
+            for (i, plot) in chart.plots.iter().flatten().enumerate() {
+                let svg = svg::render_plot(plot, data)
+                    .map_err(|e| anyhow!("Chart {} plot {}: {}", key, i + 1, e))?;
+                let svg_path = self
+                    .output_dir
+                    .join(format!("{}-{}.svg", file_stem(key), i + 1));
+                std::fs::write(&svg_path, &svg)?;
+                if self.png {
+                    self.convert_to_png(&svg_path)?;
+                }
+                plots.push(svg);
+            }
+
+            reports.push(ChartReport {
+                key: key.clone(),
+                scenario: chart.scenario.key.clone(),
+                runs: data.rows.len(),
+                metrics,
+                plots,
+            });
+        }
+        Ok(reports)
+    }
+
+    fn convert_to_png(&self, svg_path: &Path) -> Result<()> {
+        let png_path = svg_path.with_extension("png");
+        let status = Command::new(&self.svg_converter)
+            .arg("-o")
+            .arg(&png_path)
+            .arg(svg_path)
+            .status()
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to run {} to convert {}: {}",
+                    self.svg_converter,
+                    svg_path.display(),
+                    e
+                )
+            })?;
+        if !status.success() {
+            return Err(anyhow!(
+                "{} failed to convert {} ({})",
+                self.svg_converter,
+                svg_path.display(),
+                status
+            ));
+        }
         Ok(())
     }
 }
 
+/// Chart keys are YAML keys, keep file names portable
+fn file_stem(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,8 +233,8 @@ charts:
         marks:
           - type: dot
             options:
-              x: 1
-              y: 2
+              x: 0.0
+              y: 0.1
               r: 3
               fill: "red"
               stroke: "blue"
@@ -118,15 +242,15 @@ charts:
                 type: hexbin
                 content:
                   outputs:
-                    x: 1
-                    y: 2
+                    x: 0.0
+                    y: 0.1
                     r: 3
                     z: 4
                     stroke: "green"
                     fill: "blue"
                   options:
-                    x: 1
-                    y: 2
+                    x: 0.0
+                    y: 0.1
                     bin-width: 10
         x:
           label: Test x label
@@ -147,22 +271,22 @@ charts:
     metrics:
       - label: Metric One
         description: Description for metric one
-        value: 42
+        value: 0.1
         precision: 0
       - label: Metric Two
         description: Description for metric two
         unit-prefix: "k"
-        value: 3.14
+        value: 0.1
         precision: 2
       - label: Metric Three
         description: Description for metric three
         unit-suffix: "ms"
-        value: 100
+        value: 0.0
         precision: 1
       - label: Metric Four
         description: Description for metric four
         unit-prefix: "%"
-        value: 87
+        value: 0.1
         precision: 0
 "#;
 
@@ -207,8 +331,12 @@ io: fixed-io;
         let mut temp_file = NamedTempFile::new().unwrap();
         temp_file.write_all(dotrain.as_bytes()).unwrap();
 
+        let output_dir = tempfile::tempdir().unwrap();
         let chart_cmd = Chart {
             dotrain_file: temp_file.path().to_path_buf(),
+            output_dir: output_dir.path().to_path_buf(),
+            png: false,
+            svg_converter: "rsvg-convert".to_string(),
         };
 
         let result = chart_cmd.execute().await;
@@ -217,12 +345,25 @@ io: fixed-io;
             "Expected execution to succeed, but it failed: {:?}",
             result.err()
         );
+        assert!(output_dir.path().join("flare-chart-1.svg").exists());
+        let report = std::fs::read_to_string(output_dir.path().join("report.html")).unwrap();
+        assert!(report.contains("<h2>flare-chart</h2>"));
+        assert!(report.contains("Metric One"));
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("flare-chart"), "flare-chart");
+        assert_eq!(file_stem("a/b c"), "a-b-c");
     }
 
     #[tokio::test]
     async fn test_chart_execute_file_not_found() {
         let chart_cmd = Chart {
             dotrain_file: PathBuf::from("non_existent_file.rain"),
+            output_dir: PathBuf::from("charts"),
+            png: false,
+            svg_converter: "rsvg-convert".to_string(),
         };
 
         let result = chart_cmd.execute().await;
@@ -268,8 +409,12 @@ charts:
         let mut temp_file = NamedTempFile::new().unwrap();
         temp_file.write_all(dotrain.as_bytes()).unwrap();
 
+        let output_dir = tempfile::tempdir().unwrap();
         let chart_cmd = Chart {
             dotrain_file: temp_file.path().to_path_buf(),
+            output_dir: output_dir.path().to_path_buf(),
+            png: false,
+            svg_converter: "rsvg-convert".to_string(),
         };
 
         let result = chart_cmd.execute().await;
@@ -316,8 +461,12 @@ charts:
         let mut temp_file = NamedTempFile::new().unwrap();
         temp_file.write_all(dotrain.as_bytes()).unwrap();
 
+        let output_dir = tempfile::tempdir().unwrap();
         let chart_cmd = Chart {
             dotrain_file: temp_file.path().to_path_buf(),
+            output_dir: output_dir.path().to_path_buf(),
+            png: false,
+            svg_converter: "rsvg-convert".to_string(),
         };
 
         let result = chart_cmd.execute().await;
@@ -0,0 +1,185 @@
+use super::svg::{column, escape, format_number};
+use anyhow::Result;
+use rain_orderbook_app_settings::chart::MetricCfg;
+use rain_orderbook_common::fuzz::RainEvalResultsTable;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricReport {
+    pub label: String,
+    pub description: Option<String>,
+    pub value: String,
+}
+
+impl MetricReport {
+    /// A single run shows its value, several runs show the mean and range
+    pub fn new(metric: &MetricCfg, table: &RainEvalResultsTable) -> Result<Self> {
+        let values: Vec<f64> = column(table, &metric.value)?
+            .into_iter()
+            .flatten()
+            .collect();
+        let format = |value: f64| {
+            let number = match metric.precision {
+                Some(precision) => format!("{:.*}", precision as usize, value),
+                None => format_number(value),
+            };
+            format!(
+                "{}{}{}",
+                metric.unit_prefix.as_deref().unwrap_or_default(),
+                number,
+                metric.unit_suffix.as_deref().unwrap_or_default()
+            )
+        };
+
+        let min = values.iter().copied().reduce(f64::min);
+        let max = values.iter().copied().reduce(f64::max);
+        let value = match (min, max) {
+            (Some(min), Some(max)) if min == max => format(min),
+            (Some(min), Some(max)) => format!(
+                "{} ({} – {})",
+                format(values.iter().sum::<f64>() / values.len() as f64),
+                format(min),
+                format(max)
+            ),
+            _ => "-".to_string(),
+        };
+
+        Ok(Self {
+            label: metric.label.clone(),
+            description: metric.description.clone(),
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartReport {
+    pub key: String,
+    pub scenario: String,
+    pub runs: usize,
+    pub metrics: Vec<MetricReport>,
+    pub plots: Vec<String>,
+}
+
+/// Self-contained HTML page with every chart's metrics and inline SVG plots
+pub fn render_html(title: &str, charts: &[ChartReport]) -> String {
+    let mut html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #111; }}
+section {{ margin-bottom: 3rem; }}
+.meta {{ color: #555; }}
+.metrics {{ display: flex; flex-wrap: wrap; gap: 1rem; margin: 1rem 0; }}
+.metric {{ border: 1px solid #ddd; border-radius: 0.5rem; padding: 0.75rem 1rem; min-width: 10rem; }}
+.metric .value {{ font-size: 1.5rem; font-weight: bold; }}
+.metric .description {{ color: #555; font-size: 0.85rem; }}
+.plots {{ display: flex; flex-wrap: wrap; gap: 1rem; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+"#,
+        title = escape(title)
+    );
+
+    for chart in charts {
+        html.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<p class=\"meta\">Scenario {}, {} runs</p>\n",
+            escape(&chart.key),
+            escape(&chart.scenario),
+            chart.runs
+        ));
+        if !chart.metrics.is_empty() {
+            html.push_str("<div class=\"metrics\">\n");
+            for metric in &chart.metrics {
+                html.push_str(&format!(
+                    "<div class=\"metric\"><div>{}</div><div class=\"value\">{}</div>",
+                    escape(&metric.label),
+                    escape(&metric.value)
+                ));
+                if let Some(description) = &metric.description {
+                    html.push_str(&format!(
+                        "<div class=\"description\">{}</div>",
+                        escape(description)
+                    ));
+                }
+                html.push_str("</div>\n");
+            }
+            html.push_str("</div>\n");
+        }
+        if !chart.plots.is_empty() {
+            html.push_str("<div class=\"plots\">\n");
+            for plot in &chart.plots {
+                html.push_str(plot);
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    fn metric(precision: Option<u8>) -> MetricCfg {
+        MetricCfg {
+            label: "Ratio".to_string(),
+            description: Some("io <ratio>".to_string()),
+            unit_prefix: Some("$".to_string()),
+            unit_suffix: Some("k".to_string()),
+            value: "0.0".to_string(),
+            precision,
+        }
+    }
+
+    fn table(values: &[u64]) -> RainEvalResultsTable {
+        RainEvalResultsTable {
+            column_names: vec!["0.0".to_string()],
+            rows: values.iter().map(|v| vec![U256::from(*v)]).collect(),
+        }
+    }
+
+    #[test]
+    fn test_metric_report() {
+        let report = MetricReport::new(&metric(Some(2)), &table(&[3, 3])).unwrap();
+        assert_eq!(report.value, "$3.00k");
+
+        let report = MetricReport::new(&metric(None), &table(&[1, 2, 6])).unwrap();
+        assert_eq!(report.value, "$3k ($1k – $6k)");
+
+        let report = MetricReport::new(&metric(None), &table(&[])).unwrap();
+        assert_eq!(report.value, "-");
+
+        let mut missing = metric(None);
+        missing.value = "1.0".to_string();
+        assert!(MetricReport::new(&missing, &table(&[1])).is_err());
+    }
+
+    #[test]
+    fn test_render_html() {
+        let html = render_html(
+            "order.rain",
+            &[ChartReport {
+                key: "my-chart".to_string(),
+                scenario: "base".to_string(),
+                runs: 3,
+                metrics: vec![MetricReport::new(&metric(None), &table(&[4])).unwrap()],
+                plots: vec!["<svg></svg>\n".to_string()],
+            }],
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>my-chart</h2>"));
+        assert!(html.contains("Scenario base, 3 runs"));
+        assert!(html.contains("<div class=\"value\">$4k</div>"));
+        assert!(html.contains("io &lt;ratio&gt;"));
+        assert!(html.contains("<svg></svg>"));
+    }
+}
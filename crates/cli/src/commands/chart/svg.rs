@@ -0,0 +1,853 @@
+use alloy::primitives::{B256, U256};
+use anyhow::{anyhow, Result};
+use rain_math_float::Float;
+use rain_orderbook_app_settings::plot_source::{
+    AxisOptionsCfg, BinXTransformCfg, DotOptionsCfg, HexBinTransformCfg, LineOptionsCfg, MarkCfg,
+    PlotCfg, RectYOptionsCfg, TransformCfg,
+};
+use rain_orderbook_common::fuzz::RainEvalResultsTable;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 400.0;
+const TITLE_HEIGHT: f64 = 24.0;
+const SUBTITLE_HEIGHT: f64 = 18.0;
+const TICK_COUNT: f64 = 6.0;
+const DEFAULT_COLOR: &str = "black";
+const DEFAULT_DOT_RADIUS: f64 = 3.0;
+const DEFAULT_BIN_WIDTH: f64 = 20.0;
+const COUNT_COLOR_LOW: (u8, u8, u8) = (0xde, 0xeb, 0xf7);
+const COUNT_COLOR_HIGH: (u8, u8, u8) = (0x08, 0x30, 0x6b);
+
+/// Reads a stack column as numbers, runs whose value can't be shown as a
+/// decimal are `None`
+pub fn column(table: &RainEvalResultsTable, name: &str) -> Result<Vec<Option<f64>>> {
+    let index = table
+        .column_names
+        .iter()
+        .position(|column| column == name)
+        .ok_or_else(|| anyhow!("Plot references unknown stack column {}", name))?;
+    Ok(table
+        .rows
+        .iter()
+        .map(|row| row.get(index).copied().and_then(stack_value))
+        .collect())
+}
+
+/// Stack items are raw floats
+fn stack_value(value: U256) -> Option<f64> {
+    Float::from_raw(B256::from(value))
+        .format()
+        .ok()?
+        .parse::<f64>()
+        .ok()
+}
+
+/// Formats a number without trailing zeros, switching to exponent notation
+/// for very large or small magnitudes
+pub fn format_number(value: f64) -> String {
+    let abs = value.abs();
+    if abs != 0.0 && !(1e-4..1e9).contains(&abs) {
+        return format!("{value:e}");
+    }
+    let formatted = format!("{value:.6}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+pub fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// A mark resolved against the fuzz results, in data coordinates
+enum Layer {
+    Dots {
+        points: Vec<(f64, f64)>,
+        r: f64,
+        fill: String,
+        stroke: String,
+    },
+    Line {
+        points: Vec<(f64, f64)>,
+        stroke: String,
+    },
+    Rects {
+        rects: Vec<[f64; 4]>,
+        fill: String,
+        stroke: String,
+    },
+    HexBin {
+        points: Vec<(f64, f64)>,
+        bin_width: f64,
+        r: Option<f64>,
+        fill: Option<String>,
+        stroke: String,
+    },
+}
+
+impl Layer {
+    fn extent(&self) -> Vec<(f64, f64)> {
+        match self {
+            Layer::Dots { points, .. }
+            | Layer::Line { points, .. }
+            | Layer::HexBin { points, .. } => points.clone(),
+            Layer::Rects { rects, .. } => rects
+                .iter()
+                .flat_map(|[x0, x1, y0, y1]| [(*x0, *y0), (*x1, *y1)])
+                .collect(),
+        }
+    }
+}
+
+fn zip_points(xs: Vec<Option<f64>>, ys: Vec<Option<f64>>) -> Vec<(f64, f64)> {
+    xs.into_iter()
+        .zip(ys)
+        .filter_map(|(x, y)| Some((x?, y?)))
+        .collect()
+}
+
+fn require<'a>(
+    channel: &'a Option<String>,
+    mark: &'static str,
+    name: &'static str,
+) -> Result<&'a str> {
+    channel
+        .as_deref()
+        .ok_or_else(|| anyhow!("{} mark is missing its {} channel", mark, name))
+}
+
+fn dot_layer(dot: &DotOptionsCfg, table: &RainEvalResultsTable) -> Result<Layer> {
+    match &dot.transform {
+        Some(TransformCfg::HexBin(hexbin)) => hexbin_layer(hexbin, &dot.x, &dot.y, table),
+        Some(TransformCfg::BinX(binx)) => binx_layer(binx, &dot.x, table),
+        None => {
+            let xs = column(table, require(&dot.x, "dot", "x")?)?;
+            let ys = column(table, require(&dot.y, "dot", "y")?)?;
+            Ok(Layer::Dots {
+                points: zip_points(xs, ys),
+                r: dot.r.map(f64::from).unwrap_or(DEFAULT_DOT_RADIUS),
+                fill: dot.fill.clone().unwrap_or_else(|| "none".to_string()),
+                stroke: dot.stroke.clone().unwrap_or_else(|| {
+                    if dot.fill.is_some() {
+                        "none".to_string()
+                    } else {
+                        DEFAULT_COLOR.to_string()
+                    }
+                }),
+            })
+        }
+    }
+}
+
+fn line_layer(line: &LineOptionsCfg, table: &RainEvalResultsTable) -> Result<Layer> {
+    match &line.transform {
+        Some(TransformCfg::HexBin(hexbin)) => hexbin_layer(hexbin, &line.x, &line.y, table),
+        Some(TransformCfg::BinX(binx)) => binx_layer(binx, &line.x, table),
+        None => {
+            let xs = column(table, require(&line.x, "line", "x")?)?;
+            let ys = column(table, require(&line.y, "line", "y")?)?;
+            Ok(Layer::Line {
+                points: zip_points(xs, ys),
+                stroke: line
+                    .stroke
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_COLOR.to_string()),
+            })
+        }
+    }
+}
+
+fn rect_y_layer(rect: &RectYOptionsCfg, table: &RainEvalResultsTable) -> Result<Layer> {
+    match &rect.transform {
+        Some(TransformCfg::BinX(binx)) => binx_layer(binx, &rect.x0, table),
+        Some(TransformCfg::HexBin(hexbin)) => hexbin_layer(hexbin, &rect.x0, &rect.y1, table),
+        None => {
+            let x0 = column(table, require(&rect.x0, "rectY", "x0")?)?;
+            let x1 = column(table, require(&rect.x1, "rectY", "x1")?)?;
+            let y1 = column(table, require(&rect.y1, "rectY", "y1")?)?;
+            let y0 = match &rect.y0 {
+                Some(y0) => column(table, y0)?,
+                None => vec![Some(0.0); y1.len()],
+            };
+            let rects = x0
+                .into_iter()
+                .zip(x1)
+                .zip(y0.into_iter().zip(y1))
+                .filter_map(|((x0, x1), (y0, y1))| Some([x0?, x1?, y0?, y1?]))
+                .collect();
+            Ok(Layer::Rects {
+                rects,
+                fill: DEFAULT_COLOR.to_string(),
+                stroke: "none".to_string(),
+            })
+        }
+    }
+}
+
+fn hexbin_layer(
+    hexbin: &HexBinTransformCfg,
+    mark_x: &Option<String>,
+    mark_y: &Option<String>,
+    table: &RainEvalResultsTable,
+) -> Result<Layer> {
+    let x = hexbin.options.x.as_ref().or(mark_x.as_ref()).cloned();
+    let y = hexbin.options.y.as_ref().or(mark_y.as_ref()).cloned();
+    let xs = column(table, require(&x, "hexbin", "x")?)?;
+    let ys = column(table, require(&y, "hexbin", "y")?)?;
+
+    let outputs = &hexbin.outputs;
+    Ok(Layer::HexBin {
+        points: zip_points(xs, ys),
+        bin_width: hexbin
+            .options
+            .bin_width
+            .map(f64::from)
+            .unwrap_or(DEFAULT_BIN_WIDTH),
+        r: outputs.r.map(f64::from),
+        fill: outputs.fill.clone(),
+        stroke: outputs
+            .stroke
+            .clone()
+            .filter(|stroke| stroke != "count")
+            .unwrap_or_else(|| "none".to_string()),
+    })
+}
+
+fn binx_layer(
+    binx: &BinXTransformCfg,
+    mark_x: &Option<String>,
+    table: &RainEvalResultsTable,
+) -> Result<Layer> {
+    if let Some(reducer) = binx.outputs.y.as_ref().filter(|y| *y != "count") {
+        return Err(anyhow!(
+            "Unsupported reducer {}, only count is supported",
+            reducer
+        ));
+    }
+    let x = binx.options.x.as_ref().or(mark_x.as_ref()).cloned();
+    let xs: Vec<f64> = column(table, require(&x, "binX", "x")?)?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let mut rects = vec![];
+    if let (Some(min), Some(max)) = (
+        xs.iter().copied().reduce(f64::min),
+        xs.iter().copied().reduce(f64::max),
+    ) {
+        // Sturges' rule, the default of the webapp's plotting library
+        let thresholds = binx
+            .options
+            .thresholds
+            .map(|t| t.max(1) as usize)
+            .unwrap_or_else(|| (xs.len() as f64).log2().ceil() as usize + 1);
+        let (min, max) = if min == max {
+            (min - 0.5, max + 0.5)
+        } else {
+            (min, max)
+        };
+        let step = (max - min) / thresholds as f64;
+
+        let mut counts = vec![0usize; thresholds];
+        for x in &xs {
+            let bin = (((x - min) / step) as usize).min(thresholds - 1);
+            counts[bin] += 1;
+        }
+        rects = counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let x0 = min + step * i as f64;
+                [x0, x0 + step, 0.0, *count as f64]
+            })
+            .collect();
+    }
+
+    Ok(Layer::Rects {
+        rects,
+        fill: binx
+            .outputs
+            .fill
+            .clone()
+            .unwrap_or_else(|| DEFAULT_COLOR.to_string()),
+        stroke: binx
+            .outputs
+            .stroke
+            .clone()
+            .unwrap_or_else(|| "white".to_string()),
+    })
+}
+
+struct Scale {
+    domain: (f64, f64),
+    range: (f64, f64),
+}
+
+impl Scale {
+    fn new(values: impl Iterator<Item = f64>, range: (f64, f64)) -> Self {
+        let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+        let domain = if !min.is_finite() || !max.is_finite() {
+            (0.0, 1.0)
+        } else if min == max {
+            let pad = if min == 0.0 { 1.0 } else { min.abs() * 0.1 };
+            (min - pad, max + pad)
+        } else {
+            (min, max)
+        };
+        Self { domain, range }
+    }
+
+    fn apply(&self, value: f64) -> f64 {
+        let (d0, d1) = self.domain;
+        let (r0, r1) = self.range;
+        r0 + (value - d0) / (d1 - d0) * (r1 - r0)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        let (min, max) = self.domain;
+        let raw = (max - min) / TICK_COUNT;
+        let power = 10f64.powf(raw.log10().floor());
+        let step = match raw / power {
+            f if f < 1.5 => power,
+            f if f < 3.5 => 2.0 * power,
+            f if f < 7.5 => 5.0 * power,
+            _ => 10.0 * power,
+        };
+        let mut ticks = vec![];
+        let mut tick = (min / step).ceil() * step;
+        while tick <= max + step * 1e-9 {
+            ticks.push(tick);
+            tick += step;
+        }
+        ticks
+    }
+}
+
+struct Frame {
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+    inset: f64,
+}
+
+impl Frame {
+    fn new(plot: &PlotCfg) -> Self {
+        let margin = |specific: Option<u32>, default: f64| {
+            specific.or(plot.margin).map(f64::from).unwrap_or(default)
+        };
+        let header = plot.title.as_ref().map_or(0.0, |_| TITLE_HEIGHT)
+            + plot.subtitle.as_ref().map_or(0.0, |_| SUBTITLE_HEIGHT);
+        Self {
+            left: margin(plot.margin_left, 50.0),
+            right: WIDTH - margin(plot.margin_right, 20.0),
+            top: header + margin(plot.margin_top, 20.0),
+            bottom: HEIGHT - margin(plot.margin_bottom, 40.0),
+            inset: plot.inset.map(f64::from).unwrap_or(0.0),
+        }
+    }
+}
+
+/// Renders a plot of the fuzz results as a standalone SVG document
+pub fn render_plot(plot: &PlotCfg, table: &RainEvalResultsTable) -> Result<String> {
+    let layers = plot
+        .marks
+        .iter()
+        .map(|mark| match mark {
+            MarkCfg::Dot(dot) => dot_layer(dot, table),
+            MarkCfg::Line(line) => line_layer(line, table),
+            MarkCfg::RectY(rect) => rect_y_layer(rect, table),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let frame = Frame::new(plot);
+    let extent: Vec<(f64, f64)> = layers.iter().flat_map(Layer::extent).collect();
+    let x = Scale::new(
+        extent.iter().map(|(x, _)| *x),
+        (frame.left + frame.inset, frame.right - frame.inset),
+    );
+    let y = Scale::new(
+        extent.iter().map(|(_, y)| *y),
+        (frame.bottom - frame.inset, frame.top + frame.inset),
+    );
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"system-ui, sans-serif\" font-size=\"10\">"
+    );
+    let _ = writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>");
+
+    let mut baseline = 0.0;
+    if let Some(title) = &plot.title {
+        baseline += TITLE_HEIGHT;
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" font-size=\"16\" font-weight=\"bold\">{}</text>",
+            frame.left,
+            baseline - 6.0,
+            escape(title)
+        );
+    }
+    if let Some(subtitle) = &plot.subtitle {
+        baseline += SUBTITLE_HEIGHT;
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" font-size=\"12\" fill=\"#555\">{}</text>",
+            frame.left,
+            baseline - 4.0,
+            escape(subtitle)
+        );
+    }
+
+    write_x_axis(&mut svg, &x, &frame, plot.x.as_ref());
+    write_y_axis(&mut svg, &y, &frame, plot.y.as_ref());
+    for layer in &layers {
+        write_layer(&mut svg, layer, &x, &y);
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+fn text_anchor(label_anchor: Option<&str>, default: &'static str) -> &'static str {
+    match label_anchor {
+        Some("start") | Some("left") | Some("bottom") => "start",
+        Some("middle") | Some("center") => "middle",
+        Some("end") | Some("right") | Some("top") => "end",
+        _ => default,
+    }
+}
+
+fn write_x_axis(svg: &mut String, x: &Scale, frame: &Frame, options: Option<&AxisOptionsCfg>) {
+    let top = options.and_then(|o| o.anchor.as_deref()) == Some("top");
+    let axis_y = if top { frame.top } else { frame.bottom };
+    let tick_dir = if top { -1.0 } else { 1.0 };
+
+    let _ = writeln!(
+        svg,
+        "<g class=\"x-axis\"><line x1=\"{:.2}\" x2=\"{:.2}\" y1=\"{axis_y:.2}\" y2=\"{axis_y:.2}\" stroke=\"currentColor\"/>",
+        frame.left, frame.right
+    );
+    for tick in x.ticks() {
+        let px = x.apply(tick);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{px:.2}\" x2=\"{px:.2}\" y1=\"{axis_y:.2}\" y2=\"{:.2}\" stroke=\"currentColor\"/><text x=\"{px:.2}\" y=\"{:.2}\" text-anchor=\"middle\">{}</text>",
+            axis_y + 6.0 * tick_dir,
+            axis_y + if top { -9.0 } else { 17.0 },
+            format_number(tick)
+        );
+    }
+
+    if let Some(label) = options.and_then(|o| o.label.as_ref()) {
+        let anchor = text_anchor(options.and_then(|o| o.label_anchor.as_deref()), "end");
+        let label_x = match anchor {
+            "start" => frame.left,
+            "middle" => (frame.left + frame.right) / 2.0,
+            _ => frame.right,
+        };
+        let label = match options.and_then(|o| o.label_arrow.as_deref()) {
+            Some("none") => label.clone(),
+            _ => format!("{label} →"),
+        };
+        let _ = writeln!(
+            svg,
+            "<text x=\"{label_x:.2}\" y=\"{:.2}\" text-anchor=\"{anchor}\" font-weight=\"bold\">{}</text>",
+            axis_y + if top { -22.0 } else { 32.0 },
+            escape(&label)
+        );
+    }
+    svg.push_str("</g>\n");
+}
+
+fn write_y_axis(svg: &mut String, y: &Scale, frame: &Frame, options: Option<&AxisOptionsCfg>) {
+    let right = options.and_then(|o| o.anchor.as_deref()) == Some("right");
+    let axis_x = if right { frame.right } else { frame.left };
+    let tick_dir = if right { 1.0 } else { -1.0 };
+
+    let _ = writeln!(
+        svg,
+        "<g class=\"y-axis\"><line x1=\"{axis_x:.2}\" x2=\"{axis_x:.2}\" y1=\"{:.2}\" y2=\"{:.2}\" stroke=\"currentColor\"/>",
+        frame.top, frame.bottom
+    );
+    for tick in y.ticks() {
+        let py = y.apply(tick);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{axis_x:.2}\" x2=\"{:.2}\" y1=\"{py:.2}\" y2=\"{py:.2}\" stroke=\"currentColor\"/><text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"{}\">{}</text>",
+            axis_x + 6.0 * tick_dir,
+            axis_x + 9.0 * tick_dir,
+            py + 3.0,
+            if right { "start" } else { "end" },
+            format_number(tick)
+        );
+    }
+
+    if let Some(label) = options.and_then(|o| o.label.as_ref()) {
+        let anchor = text_anchor(options.and_then(|o| o.label_anchor.as_deref()), "start");
+        let label_y = match anchor {
+            "middle" => (frame.top + frame.bottom) / 2.0,
+            "end" => frame.bottom,
+            _ => frame.top - 8.0,
+        };
+        let label = match options.and_then(|o| o.label_arrow.as_deref()) {
+            Some("none") => label.clone(),
+            _ => format!("↑ {label}"),
+        };
+        let _ = writeln!(
+            svg,
+            "<text x=\"{axis_x:.2}\" y=\"{label_y:.2}\" text-anchor=\"{}\" font-weight=\"bold\">{}</text>",
+            if right { "end" } else { "start" },
+            escape(&label)
+        );
+    }
+    svg.push_str("</g>\n");
+}
+
+fn write_layer(svg: &mut String, layer: &Layer, x: &Scale, y: &Scale) {
+    match layer {
+        Layer::Dots {
+            points,
+            r,
+            fill,
+            stroke,
+        } => {
+            let _ = writeln!(
+                svg,
+                "<g fill=\"{}\" stroke=\"{}\">",
+                escape(fill),
+                escape(stroke)
+            );
+            for (px, py) in points {
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{r}\"/>",
+                    x.apply(*px),
+                    y.apply(*py)
+                );
+            }
+            svg.push_str("</g>\n");
+        }
+        Layer::Line { points, stroke } => {
+            let path = points
+                .iter()
+                .map(|(px, py)| format!("{:.2},{:.2}", x.apply(*px), y.apply(*py)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{path}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+                escape(stroke)
+            );
+        }
+        Layer::Rects {
+            rects,
+            fill,
+            stroke,
+        } => {
+            let _ = writeln!(
+                svg,
+                "<g fill=\"{}\" stroke=\"{}\">",
+                escape(fill),
+                escape(stroke)
+            );
+            for [x0, x1, y0, y1] in rects {
+                let (left, right) = (x.apply(*x0), x.apply(*x1));
+                let (top, bottom) = (y.apply(y0.max(*y1)), y.apply(y0.min(*y1)));
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\"/>",
+                    left.min(right),
+                    top,
+                    (right - left).abs(),
+                    bottom - top
+                );
+            }
+            svg.push_str("</g>\n");
+        }
+        Layer::HexBin {
+            points,
+            bin_width,
+            r,
+            fill,
+            stroke,
+        } => write_hexbins(svg, points, *bin_width, *r, fill.as_deref(), stroke, x, y),
+    }
+}
+
+/// Bins points into a pointy-top hexagonal grid in pixel space, `bin_width`
+/// apart horizontally like the webapp's hexbin transform
+#[allow(clippy::too_many_arguments)]
+fn write_hexbins(
+    svg: &mut String,
+    points: &[(f64, f64)],
+    bin_width: f64,
+    r: Option<f64>,
+    fill: Option<&str>,
+    stroke: &str,
+    x: &Scale,
+    y: &Scale,
+) {
+    let radius = bin_width / 3f64.sqrt();
+    let row_height = radius * 1.5;
+
+    let mut bins: BTreeMap<(i64, i64), usize> = BTreeMap::new();
+    for (px, py) in points {
+        let (px, py) = (x.apply(*px), y.apply(*py));
+        let row = (py / row_height).round() as i64;
+        let offset = if row % 2 == 0 { 0.0 } else { bin_width / 2.0 };
+        let col = ((px - offset) / bin_width).round() as i64;
+        *bins.entry((col, row)).or_default() += 1;
+    }
+    let max = bins.values().copied().max().unwrap_or(1) as f64;
+
+    let _ = writeln!(svg, "<g stroke=\"{}\">", escape(stroke));
+    for ((col, row), count) in &bins {
+        let offset = if row % 2 == 0 { 0.0 } else { bin_width / 2.0 };
+        let cx = *col as f64 * bin_width + offset;
+        let cy = *row as f64 * row_height;
+        let share = *count as f64 / max;
+        let color = match fill {
+            Some("count") | None => count_color(share),
+            Some(color) => escape(color),
+        };
+
+        match r {
+            Some(r) => {
+                let _ = writeln!(
+                    svg,
+                    "<circle cx=\"{cx:.2}\" cy=\"{cy:.2}\" r=\"{:.2}\" fill=\"{color}\"><title>{count}</title></circle>",
+                    r * share.sqrt()
+                );
+            }
+            None => {
+                let corners = (0..6)
+                    .map(|i| {
+                        let angle =
+                            std::f64::consts::PI / 3.0 * i as f64 + std::f64::consts::PI / 6.0;
+                        format!(
+                            "{:.2},{:.2}",
+                            cx + radius * angle.cos(),
+                            cy + radius * angle.sin()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let _ = writeln!(
+                    svg,
+                    "<polygon points=\"{corners}\" fill=\"{color}\"><title>{count}</title></polygon>"
+                );
+            }
+        }
+    }
+    svg.push_str("</g>\n");
+}
+
+fn count_color(share: f64) -> String {
+    let mix = |low: u8, high: u8| (low as f64 + (high as f64 - low as f64) * share).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        mix(COUNT_COLOR_LOW.0, COUNT_COLOR_HIGH.0),
+        mix(COUNT_COLOR_LOW.1, COUNT_COLOR_HIGH.1),
+        mix(COUNT_COLOR_LOW.2, COUNT_COLOR_HIGH.2)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_app_settings::plot_source::{
+        BinXOptionsCfg, HexBinOptionsCfg, TransformOutputsCfg,
+    };
+
+    fn table() -> RainEvalResultsTable {
+        RainEvalResultsTable {
+            column_names: vec!["0.0".to_string(), "0.1".to_string()],
+            rows: (1..=4u64)
+                .map(|i| vec![U256::from(i), U256::from(i * 10)])
+                .collect(),
+        }
+    }
+
+    fn plot(marks: Vec<MarkCfg>) -> PlotCfg {
+        PlotCfg {
+            title: Some("Ratio <by> amount".to_string()),
+            subtitle: None,
+            marks,
+            x: Some(AxisOptionsCfg {
+                label: Some("amount".to_string()),
+                anchor: None,
+                label_anchor: None,
+                label_arrow: None,
+            }),
+            y: None,
+            margin: None,
+            margin_left: None,
+            margin_right: None,
+            margin_top: None,
+            margin_bottom: None,
+            inset: None,
+        }
+    }
+
+    fn dot(x: &str, y: &str) -> MarkCfg {
+        MarkCfg::Dot(DotOptionsCfg {
+            x: Some(x.to_string()),
+            y: Some(y.to_string()),
+            r: None,
+            fill: None,
+            stroke: None,
+            transform: None,
+        })
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0.0), "0");
+        assert_eq!(format_number(1.5), "1.5");
+        assert_eq!(format_number(20.0), "20");
+        assert_eq!(format_number(-0.25), "-0.25");
+        assert_eq!(format_number(1e12), "1e12");
+    }
+
+    #[test]
+    fn test_column() {
+        assert_eq!(
+            column(&table(), "0.1").unwrap(),
+            vec![Some(10.0), Some(20.0), Some(30.0), Some(40.0)]
+        );
+        assert_eq!(
+            column(&table(), "0.9").unwrap_err().to_string(),
+            "Plot references unknown stack column 0.9"
+        );
+    }
+
+    #[test]
+    fn test_scale_ticks() {
+        let scale = Scale::new([0.0, 10.0].into_iter(), (0.0, 100.0));
+        assert_eq!(scale.ticks(), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert_eq!(scale.apply(5.0), 50.0);
+
+        let flat = Scale::new([3.0, 3.0].into_iter(), (0.0, 100.0));
+        assert!(flat.domain.0 < 3.0 && flat.domain.1 > 3.0);
+    }
+
+    #[test]
+    fn test_render_dot_plot() {
+        let svg = render_plot(&plot(vec![dot("0.0", "0.1")]), &table()).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("Ratio &lt;by&gt; amount"));
+        assert!(svg.contains("amount →"));
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_render_transforms() {
+        let binx = MarkCfg::RectY(RectYOptionsCfg {
+            x0: None,
+            x1: None,
+            y0: None,
+            y1: None,
+            transform: Some(TransformCfg::BinX(BinXTransformCfg {
+                outputs: TransformOutputsCfg {
+                    x: None,
+                    y: Some("count".to_string()),
+                    r: None,
+                    z: None,
+                    stroke: None,
+                    fill: None,
+                },
+                options: BinXOptionsCfg {
+                    x: Some("0.1".to_string()),
+                    thresholds: Some(2),
+                },
+            })),
+        });
+        let svg = render_plot(&plot(vec![binx]), &table()).unwrap();
+        assert_eq!(svg.matches("<rect x=").count(), 2);
+
+        let hexbin = MarkCfg::Dot(DotOptionsCfg {
+            x: None,
+            y: None,
+            r: None,
+            fill: None,
+            stroke: None,
+            transform: Some(TransformCfg::HexBin(HexBinTransformCfg {
+                outputs: TransformOutputsCfg {
+                    x: None,
+                    y: None,
+                    r: None,
+                    z: None,
+                    stroke: None,
+                    fill: Some("count".to_string()),
+                },
+                options: HexBinOptionsCfg {
+                    x: Some("0.0".to_string()),
+                    y: Some("0.1".to_string()),
+                    bin_width: Some(10),
+                },
+            })),
+        });
+        let svg = render_plot(&plot(vec![hexbin]), &table()).unwrap();
+        assert!(svg.contains("<polygon"));
+
+        let unsupported = MarkCfg::RectY(RectYOptionsCfg {
+            x0: None,
+            x1: None,
+            y0: None,
+            y1: None,
+            transform: Some(TransformCfg::BinX(BinXTransformCfg {
+                outputs: TransformOutputsCfg {
+                    x: None,
+                    y: Some("sum".to_string()),
+                    r: None,
+                    z: None,
+                    stroke: None,
+                    fill: None,
+                },
+                options: BinXOptionsCfg {
+                    x: Some("0.1".to_string()),
+                    thresholds: None,
+                },
+            })),
+        });
+        assert_eq!(
+            render_plot(&plot(vec![unsupported]), &table())
+                .unwrap_err()
+                .to_string(),
+            "Unsupported reducer sum, only count is supported"
+        );
+    }
+
+    #[test]
+    fn test_render_missing_channel() {
+        let line = MarkCfg::Line(LineOptionsCfg {
+            x: Some("0.0".to_string()),
+            y: None,
+            r: None,
+            fill: None,
+            stroke: None,
+            transform: None,
+        });
+        assert_eq!(
+            render_plot(&plot(vec![line]), &table())
+                .unwrap_err()
+                .to_string(),
+            "line mark is missing its y channel"
+        );
+    }
+}
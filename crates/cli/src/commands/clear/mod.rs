@@ -0,0 +1,34 @@
+mod scan;
+
+use crate::execute::Execute;
+use anyhow::Result;
+use clap::Parser;
+use scan::CliClearScanArgs;
+
+#[derive(Parser)]
+pub enum Clear {
+    #[command(
+        about = "Find resting orders whose ratios cross and build clear3 calldata",
+        alias = "sc"
+    )]
+    Scan(CliClearScanArgs),
+}
+
+impl Execute for Clear {
+    async fn execute(&self) -> Result<()> {
+        match self {
+            Clear::Scan(scan) => scan.execute().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_command() {
+        Clear::command().debug_assert();
+    }
+}
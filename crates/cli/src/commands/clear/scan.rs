@@ -0,0 +1,193 @@
+use crate::execute::Execute;
+use alloy::hex::encode_prefixed;
+use alloy::primitives::{Address, B256};
+use anyhow::Result;
+use clap::Args;
+use comfy_table::Table;
+use rain_orderbook_common::clear::{find_clear_opportunities, ClearOpportunity};
+use rain_orderbook_common::raindex_client::orders::{GetOrdersFilters, RaindexOrder};
+use rain_orderbook_common::raindex_client::{ChainIds, RaindexClient};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tracing::info;
+
+const ORDERS_PAGE_SIZE: u16 = 100;
+
+#[derive(Args, Clone)]
+pub struct CliClearScanArgs {
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML describing networks, orderbooks and subgraphs"
+    )]
+    settings_file: PathBuf,
+
+    #[arg(short = 'c', long, help = "Chain ID to scan")]
+    chain_id: u32,
+
+    #[arg(
+        long = "orderbook",
+        help = "Only scan orders in these orderbooks (repeatable)"
+    )]
+    orderbooks: Vec<Address>,
+
+    #[arg(
+        long = "owner",
+        help = "Only scan orders from these owners (repeatable)"
+    )]
+    owners: Vec<Address>,
+
+    #[arg(
+        long,
+        default_value = "0x0000000000000000000000000000000000000000000000000000000000000000",
+        help = "Clearer's vault ID credited with alice's bounty"
+    )]
+    alice_bounty_vault_id: B256,
+
+    #[arg(
+        long,
+        default_value = "0x0000000000000000000000000000000000000000000000000000000000000000",
+        help = "Clearer's vault ID credited with bob's bounty"
+    )]
+    bob_bounty_vault_id: B256,
+
+    #[arg(long, help = "Quote at this block instead of the latest")]
+    block_number: Option<u64>,
+
+    #[arg(long, help = "Number of quotes per multicall batch")]
+    chunk_size: Option<u32>,
+
+    #[arg(
+        long,
+        help = "Local DB path, required when the settings configure local-db syncs"
+    )]
+    local_db_path: Option<PathBuf>,
+
+    #[arg(long, help = "Print opportunities as JSON, including calldata")]
+    json: bool,
+}
+
+impl Execute for CliClearScanArgs {
+    async fn execute(&self) -> Result<()> {
+        let yaml = std::fs::read_to_string(&self.settings_file)?;
+        let client = RaindexClient::new(vec![yaml], None, self.local_db_path.clone()).await?;
+
+        let orders = self.fetch_active_orders(&client).await?;
+        info!("Quoting {} active orders", orders.len());
+
+        let opportunities = find_clear_opportunities(
+            &orders,
+            self.alice_bounty_vault_id,
+            self.bob_bounty_vault_id,
+            self.block_number,
+            self.chunk_size,
+        )
+        .await?;
+
+        if self.json {
+            let values = opportunities
+                .iter()
+                .map(opportunity_json)
+                .collect::<Result<Vec<Value>>>()?;
+            println!("{}", serde_json::to_string_pretty(&values)?);
+        } else if opportunities.is_empty() {
+            info!("No crossing orders found");
+        } else {
+            info!("\n{}", build_table(&opportunities)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl CliClearScanArgs {
+    async fn fetch_active_orders(&self, client: &RaindexClient) -> Result<Vec<RaindexOrder>> {
+        let filters = GetOrdersFilters {
+            owners: self.owners.clone(),
+            active: Some(true),
+            orderbook_addresses: if self.orderbooks.is_empty() {
+                None
+            } else {
+                Some(self.orderbooks.clone())
+            },
+            ..Default::default()
+        };
+
+        let mut orders = vec![];
+        let mut page = 1;
+        loop {
+            let result = client
+                .get_orders(
+                    Some(ChainIds(vec![self.chain_id])),
+                    Some(filters.clone()),
+                    Some(page),
+                    Some(ORDERS_PAGE_SIZE),
+                )
+                .await?;
+            let fetched = result.orders().len();
+            orders.extend_from_slice(result.orders());
+            if fetched < ORDERS_PAGE_SIZE as usize || orders.len() >= result.total_count() as usize
+            {
+                break;
+            }
+            page += 1;
+        }
+        Ok(orders)
+    }
+}
+
+fn opportunity_json(opportunity: &ClearOpportunity) -> Result<Value> {
+    Ok(json!({
+        "orderbook": opportunity.orderbook,
+        "aliceOrderHash": opportunity.alice_order_hash,
+        "bobOrderHash": opportunity.bob_order_hash,
+        "aliceInput": opportunity.alice_input.format()?,
+        "aliceOutput": opportunity.alice_output.format()?,
+        "bobInput": opportunity.bob_input.format()?,
+        "bobOutput": opportunity.bob_output.format()?,
+        "aliceBounty": opportunity.alice_bounty.format()?,
+        "aliceBountyToken": opportunity.alice_bounty_token(),
+        "bobBounty": opportunity.bob_bounty.format()?,
+        "bobBountyToken": opportunity.bob_bounty_token(),
+        "ratioProduct": opportunity.ratio_product.format()?,
+        "calldata": encode_prefixed(&opportunity.calldata),
+    }))
+}
+
+fn build_table(opportunities: &[ClearOpportunity]) -> Result<Table> {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Rank",
+            "Orderbook",
+            "Alice Order",
+            "Bob Order",
+            "Ratio Product",
+            "Alice Bounty",
+            "Bob Bounty",
+        ]);
+
+    for (rank, opportunity) in opportunities.iter().enumerate() {
+        table.add_row(vec![
+            (rank + 1).to_string(),
+            opportunity.orderbook.to_string(),
+            opportunity.alice_order_hash.to_string(),
+            opportunity.bob_order_hash.to_string(),
+            opportunity.ratio_product.format()?,
+            format!(
+                "{} {}",
+                opportunity.alice_bounty.format()?,
+                opportunity.alice_bounty_token()
+            ),
+            format!(
+                "{} {}",
+                opportunity.bob_bounty.format()?,
+                opportunity.bob_bounty_token()
+            ),
+        ]);
+    }
+
+    Ok(table)
+}
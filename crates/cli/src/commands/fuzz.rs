@@ -0,0 +1,275 @@
+use crate::execute::Execute;
+use alloy::primitives::B256;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use comfy_table::Table;
+use rain_orderbook_app_settings::scenario::ScenarioCfg;
+use rain_orderbook_common::fuzz::{FuzzRunner, FuzzRunnerContext, InvariantReport};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct Fuzz {
+    #[arg(
+        short = 'f',
+        long,
+        help = "Path to the .rain file specifying the order"
+    )]
+    dotrain_file: PathBuf,
+
+    #[arg(
+        short = 's',
+        long = "scenario",
+        help = "Only fuzz these scenarios (repeatable), defaults to every scenario with invariants"
+    )]
+    scenarios: Vec<String>,
+
+    #[arg(long, help = "Seed for the random elided bindings")]
+    seed: Option<B256>,
+
+    #[arg(long, value_name = "PATH", help = "Write the reports as JSON")]
+    json: Option<PathBuf>,
+}
+
+impl Execute for Fuzz {
+    async fn execute(&self) -> Result<()> {
+        let dotrain = read_to_string(self.dotrain_file.clone()).map_err(|e| anyhow!(e))?;
+        let mut runner = FuzzRunner::new(None)?;
+        let mut context = FuzzRunnerContext::new(&dotrain, None, self.seed.map(|seed| seed.0))?;
+
+        let scenarios = select_scenarios(context.dotrain_yaml.get_scenarios()?, &self.scenarios)?;
+        let mut reports = vec![];
+        for scenario in &scenarios {
+            info!("Fuzzing scenario {}", scenario.key);
+            reports.push(runner.check_invariants(&mut context, scenario).await?);
+        }
+
+        if let Some(path) = &self.json {
+            std::fs::write(path, serde_json::to_string_pretty(&reports)?)?;
+        }
+        info!("\n{}", build_table(&reports));
+
+        let violations: usize = reports.iter().map(|r| r.violations.len()).sum();
+        if violations > 0 {
+            return Err(anyhow!("{} invariant violations found", violations));
+        }
+        Ok(())
+    }
+}
+
+fn select_scenarios(
+    mut all: HashMap<String, ScenarioCfg>,
+    names: &[String],
+) -> Result<Vec<ScenarioCfg>> {
+    if names.is_empty() {
+        let mut scenarios: Vec<ScenarioCfg> = all
+            .into_values()
+            .filter(|scenario| !scenario.invariants.is_empty())
+            .collect();
+        if scenarios.is_empty() {
+            return Err(anyhow!("No scenarios declare invariants"));
+        }
+        scenarios.sort_by(|a, b| a.key.cmp(&b.key));
+        return Ok(scenarios);
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            let scenario = all
+                .remove(name)
+                .ok_or_else(|| anyhow!("Scenario {} is not configured", name))?;
+            if scenario.invariants.is_empty() {
+                return Err(anyhow!("Scenario {} declares no invariants", name));
+            }
+            Ok(scenario)
+        })
+        .collect()
+}
+
+fn build_table(reports: &[InvariantReport]) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Scenario", "Invariant", "Result", "Counterexample"]);
+
+    for report in reports {
+        for invariant in &report.invariants {
+            let violation = report
+                .violations
+                .iter()
+                .find(|v| v.invariant == invariant.key);
+            let (result, counterexample) = match violation {
+                None => (format!("passed ({} runs)", report.runs), String::new()),
+                Some(violation) => {
+                    let mut lines = vec![format!(
+                        "block {}, value {}",
+                        violation.counterexample.block_number, violation.counterexample.value
+                    )];
+                    lines.extend(
+                        violation
+                            .counterexample
+                            .bindings
+                            .iter()
+                            .map(|(k, v)| format!("{k}: {v}")),
+                    );
+                    if let Some(previous) = &violation.previous {
+                        lines.push(format!(
+                            "after value {} with {}",
+                            previous.value,
+                            previous
+                                .bindings
+                                .iter()
+                                .map(|(k, v)| format!("{k}: {v}"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ));
+                    }
+                    (
+                        format!(
+                            "failed {} of {} runs (shrunk {} times)",
+                            violation.failed_runs, report.runs, violation.shrink_steps
+                        ),
+                        lines.join("\n"),
+                    )
+                }
+            };
+            table.add_row(vec![
+                report.scenario.clone(),
+                format!("{}: {}", invariant.key, invariant.check),
+                result,
+                counterexample,
+            ]);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rain_orderbook_app_settings::invariant::{InvariantCfg, InvariantCheckCfg, InvariantOpCfg};
+    use rain_orderbook_common::fuzz::{
+        InvariantCounterexample, InvariantSummary, InvariantViolation,
+    };
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        fuzz: Fuzz,
+    }
+
+    fn scenarios(keys: &[(&str, bool)]) -> HashMap<String, ScenarioCfg> {
+        keys.iter()
+            .map(|(key, with_invariants)| {
+                let invariants = if *with_invariants {
+                    vec![InvariantCfg {
+                        key: "positive".to_string(),
+                        value: "0.0".to_string(),
+                        check: InvariantCheckCfg::Compare {
+                            op: InvariantOpCfg::Gt,
+                            bound: "0".to_string(),
+                        },
+                    }]
+                } else {
+                    vec![]
+                };
+                (
+                    key.to_string(),
+                    ScenarioCfg {
+                        key: key.to_string(),
+                        invariants,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_scenarios() {
+        let all =
+            select_scenarios(scenarios(&[("b", true), ("a", true), ("c", false)]), &[]).unwrap();
+        assert_eq!(
+            all.iter().map(|s| s.key.as_str()).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        let some =
+            select_scenarios(scenarios(&[("a", true), ("b", true)]), &["b".to_string()]).unwrap();
+        assert_eq!(some.len(), 1);
+        assert_eq!(some[0].key, "b");
+
+        let err = select_scenarios(scenarios(&[("a", false)]), &["a".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Scenario a declares no invariants");
+
+        let err = select_scenarios(scenarios(&[("a", true)]), &["c".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Scenario c is not configured");
+
+        let err = select_scenarios(scenarios(&[("a", false)]), &[]).unwrap_err();
+        assert_eq!(err.to_string(), "No scenarios declare invariants");
+    }
+
+    #[test]
+    fn test_build_table_matches_violations_by_key() {
+        let summary = |key: &str| InvariantSummary {
+            key: key.to_string(),
+            check: "0.0 <= 1".to_string(),
+        };
+        let report = InvariantReport {
+            scenario: "a".to_string(),
+            runs: 10,
+            invariants: vec![summary("first"), summary("second")],
+            violations: vec![InvariantViolation {
+                invariant: "second".to_string(),
+                check: "0.0 <= 1".to_string(),
+                failed_runs: 3,
+                counterexample: InvariantCounterexample {
+                    block_number: 1,
+                    bindings: Default::default(),
+                    value: "2".to_string(),
+                },
+                previous: None,
+                shrink_steps: 0,
+            }],
+        };
+
+        let table = build_table(&[report]);
+        let rows = table
+            .row_iter()
+            .map(|row| {
+                row.cell_iter()
+                    .map(|cell| cell.content())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows[0][1], "first: 0.0 <= 1");
+        assert_eq!(rows[0][2], "passed (10 runs)");
+        assert_eq!(rows[1][1], "second: 0.0 <= 1");
+        assert_eq!(rows[1][2], "failed 3 of 10 runs (shrunk 0 times)");
+    }
+
+    #[test]
+    fn test_fuzz_args() {
+        let cli = Cli::parse_from(["fuzz", "-f", "order.rain", "-s", "a", "--scenario", "b"]);
+        assert_eq!(cli.fuzz.dotrain_file, PathBuf::from("order.rain"));
+        assert_eq!(cli.fuzz.scenarios, vec!["a", "b"]);
+        assert_eq!(cli.fuzz.seed, None);
+        assert_eq!(cli.fuzz.json, None);
+    }
+
+    #[tokio::test]
+    async fn test_fuzz_execute_file_not_found() {
+        let cmd = Fuzz {
+            dotrain_file: PathBuf::from("non_existent_file.rain"),
+            scenarios: vec![],
+            seed: None,
+            json: None,
+        };
+        assert!(cmd.execute().await.is_err());
+    }
+}
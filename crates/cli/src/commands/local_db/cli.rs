@@ -1,6 +1,7 @@
 use super::pipeline::runner::ProducerRunReport;
 use crate::commands::local_db::pipeline::runner::ProducerRunner;
-use anyhow::Result;
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use rain_orderbook_common::local_db::pipeline::runner::TargetFailure;
 use std::io::{self, Write};
@@ -45,6 +46,19 @@ pub struct RunPipeline {
         help = "Emit per-stage status updates from the pipeline as it runs"
     )]
     pub debug_status: bool,
+
+    #[clap(
+        long,
+        help = "Seed orderbooks without a local DB from their subgraph instead of replaying logs from the deployment block"
+    )]
+    pub bootstrap_from_subgraph: bool,
+
+    #[clap(
+        long,
+        help = "Sign the produced manifest with the hex private key stored in this environment variable",
+        value_name = "ENV"
+    )]
+    pub manifest_signer_key_env: Option<String>,
 }
 
 impl RunPipeline {
@@ -57,17 +71,28 @@ impl RunPipeline {
             out_root,
             release_base_url,
             debug_status,
+            bootstrap_from_subgraph,
+            manifest_signer_key_env,
         } = self;
 
+        let manifest_signer = manifest_signer_key_env
+            .map(|env_var| load_manifest_signer(&env_var))
+            .transpose()?;
+
         std::fs::create_dir_all(&out_root)?;
 
-        let runner = ProducerRunner::new(
+        let mut runner = ProducerRunner::new(
             settings_yaml,
             out_root,
             release_base_url,
             api_token,
+            bootstrap_from_subgraph,
             debug_status.into(),
         )?;
+        if let Some(signer) = manifest_signer {
+            println!("Signing manifest as publisher {:#x}", signer.address());
+            runner = runner.with_manifest_signer(signer);
+        }
         let report = runner.run().await?;
 
         render_report(&report);
@@ -83,6 +108,14 @@ impl RunPipeline {
     }
 }
 
+fn load_manifest_signer(env_var: &str) -> Result<PrivateKeySigner> {
+    let key = std::env::var(env_var)
+        .map_err(|_| anyhow!("environment variable {} is not set", env_var))?;
+    key.trim()
+        .parse()
+        .map_err(|e| anyhow!("invalid manifest signing key in {}: {}", env_var, e))
+}
+
 fn render_report(report: &ProducerRunReport) {
     let mut stdout = io::stdout();
     let _ = render_report_to(report, &mut stdout);
@@ -175,7 +208,7 @@ mod tests {
     use crate::commands::local_db::pipeline::runner::export::ExportMetadata;
 
     use super::*;
-    use alloy::primitives::{address, Address};
+    use alloy::primitives::{address, Address, B256};
     use rain_orderbook_common::local_db::pipeline::engine::SyncInputs;
     use rain_orderbook_common::local_db::pipeline::runner::utils::RunnerTarget;
     use rain_orderbook_common::local_db::pipeline::runner::{
@@ -199,6 +232,32 @@ mod tests {
             "https://example.com/releases",
         ]);
         assert_eq!(command.out_root, PathBuf::from("./local-db"));
+        assert_eq!(command.manifest_signer_key_env, None);
+        assert!(!command.bootstrap_from_subgraph);
+    }
+
+    #[test]
+    fn manifest_signer_is_loaded_from_env() {
+        let command = RunPipeline::parse_from([
+            "sync",
+            "--settings-yaml",
+            "test",
+            "--api-token",
+            "token",
+            "--release-base-url",
+            "https://example.com/releases",
+            "--manifest-signer-key-env",
+            "LOCAL_DB_TEST_MANIFEST_SIGNER_KEY",
+        ]);
+        let env_var = command.manifest_signer_key_env.expect("flag parsed");
+        std::env::set_var(&env_var, format!("0x{}", "07".repeat(32)));
+
+        let signer = load_manifest_signer(&env_var).expect("signer loads");
+        let expected = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x07)).unwrap();
+        assert_eq!(signer.address(), expected.address());
+
+        let err = load_manifest_signer("LOCAL_DB_TEST_MANIFEST_SIGNER_KEY_UNSET").unwrap_err();
+        assert!(err.to_string().contains("is not set"));
     }
 
     fn sample_success_and_export(chain_id: u32) -> (TargetSuccess, ExportMetadata) {
@@ -218,6 +277,7 @@ mod tests {
             metadata_rpcs: Vec::new(),
             cfg: sync_config,
             dump_str: None,
+            delta_dump_str: None,
             block_number_threshold: 10000,
             manifest_end_block: 1,
         };
@@ -234,15 +294,19 @@ mod tests {
             target_block: 400,
             fetched_logs: 123,
             decoded_events: 456,
+            rolled_back_to: None,
+            events: vec![],
         };
         let export = ExportMetadata {
             dump_path: PathBuf::from(format!(
                 "./local-db/{}/{}-{}.sql.gz",
                 chain_id, chain_id, runner_target.inputs.ob_id.orderbook_address
             )),
+            dump_sha256: B256::ZERO,
             end_block: 400,
             end_block_hash: "0xdeadbeef".to_string(),
             end_block_time_ms: 1_700_000_000,
+            start_block: None,
         };
 
         (TargetSuccess { outcome }, export)
@@ -0,0 +1,107 @@
+use super::executor::RusqliteExecutor;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rain_orderbook_app_settings::local_db_manifest::DB_SCHEMA_VERSION;
+use rain_orderbook_common::local_db::migrations::{migrate, MigrationReport, MIGRATIONS};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Parser)]
+#[command(about = "Migrate a local SQLite database to a newer schema version in place")]
+pub struct MigrateDb {
+    #[clap(
+        long,
+        help = "Path to the SQLite database to migrate",
+        value_name = "PATH"
+    )]
+    pub db_path: PathBuf,
+
+    #[clap(
+        long,
+        help = "Schema version to migrate to, defaults to the current schema version",
+        value_name = "VERSION",
+        default_value_t = DB_SCHEMA_VERSION
+    )]
+    pub target_version: u32,
+
+    #[clap(
+        long,
+        help = "List the migrations that would run without applying them"
+    )]
+    pub dry_run: bool,
+}
+
+impl MigrateDb {
+    pub async fn execute(self) -> Result<()> {
+        if !self.db_path.exists() {
+            return Err(anyhow!("database not found at {}", self.db_path.display()));
+        }
+
+        let executor = RusqliteExecutor::new(&self.db_path);
+        let report = migrate(&executor, MIGRATIONS, self.target_version, self.dry_run)
+            .await
+            .map_err(|err| anyhow!(err.to_readable_msg()))?;
+
+        println!("{}", render_report(&report));
+        Ok(())
+    }
+}
+
+fn render_report(report: &MigrationReport) -> String {
+    if report.migrations.is_empty() {
+        return format!(
+            "Database is already at schema version {}",
+            report.to_version
+        );
+    }
+
+    let action = if report.dry_run {
+        "Would apply"
+    } else {
+        "Applied"
+    };
+    let mut lines = vec![format!(
+        "{} {} migration(s) from schema version {} to {}:",
+        action,
+        report.migrations.len(),
+        report.from_version,
+        report.to_version
+    )];
+    lines.extend(
+        report
+            .migrations
+            .iter()
+            .map(|migration| format!("  v{} {}", migration.version, migration.name)),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_common::local_db::migrations::AppliedMigration;
+
+    #[test]
+    fn render_report_lists_migrations() {
+        let mut report = MigrationReport {
+            from_version: 2,
+            to_version: 2,
+            migrations: vec![],
+            dry_run: false,
+        };
+        assert_eq!(
+            render_report(&report),
+            "Database is already at schema version 2"
+        );
+
+        report.to_version = 3;
+        report.dry_run = true;
+        report.migrations.push(AppliedMigration {
+            version: 3,
+            name: "add_index".to_string(),
+        });
+        assert_eq!(
+            render_report(&report),
+            "Would apply 1 migration(s) from schema version 2 to 3:\n  v3 add_index"
+        );
+    }
+}
@@ -1,22 +1,27 @@
 pub mod cli;
 pub mod executor;
+pub mod migrate;
 pub mod pipeline;
 
 use anyhow::Result;
 use clap::Subcommand;
 use cli::RunPipeline;
+use migrate::MigrateDb;
 
 #[derive(Subcommand)]
 #[command(about = "Local database operations")]
 pub enum LocalDbCommands {
     #[command(name = "sync")]
     Sync(RunPipeline),
+    #[command(name = "migrate")]
+    Migrate(MigrateDb),
 }
 
 impl LocalDbCommands {
     pub async fn execute(self) -> Result<()> {
         match self {
             LocalDbCommands::Sync(cmd) => cmd.execute().await,
+            LocalDbCommands::Migrate(cmd) => cmd.execute().await,
         }
     }
 }
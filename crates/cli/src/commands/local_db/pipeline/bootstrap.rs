@@ -127,6 +127,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: None,
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -162,6 +163,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: None,
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -196,6 +198,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -235,6 +238,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -272,6 +276,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: None,
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: 1,
             deployment_block: 1,
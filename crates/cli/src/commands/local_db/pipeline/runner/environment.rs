@@ -1,6 +1,7 @@
 use rain_orderbook_common::local_db::pipeline::{
     adapters::{
-        apply::DefaultApplyPipeline, events::DefaultEventsPipeline, tokens::DefaultTokensPipeline,
+        apply::DefaultApplyPipeline, events::DefaultEventsPipeline,
+        subgraph::SubgraphBootstrapAdapter, tokens::DefaultTokensPipeline,
         window::DefaultWindowPipeline,
     },
     runner::{
@@ -10,18 +11,27 @@ use rain_orderbook_common::local_db::pipeline::{
         utils::RunnerTarget,
     },
 };
+use std::collections::HashMap;
 use std::sync::Arc;
+use url::Url;
 
 use crate::commands::local_db::pipeline::{
     bootstrap::ProducerBootstrapAdapter,
     status::{DebugStatus, ProducerStatusBus},
 };
 
+/// Producer environment fetching logs from HyperRPC, at the `hyperrpc_urls`
+/// template configured for a target's network or at its built-in URL.
+///
+/// Orderbooks with an entry in `subgraph_urls` are seeded from that subgraph
+/// when they have not been synced yet.
 pub fn default_environment(
     hypersync_token: String,
+    hyperrpc_urls: HashMap<String, String>,
+    subgraph_urls: HashMap<String, Url>,
     debug_status: DebugStatus,
 ) -> RunnerEnvironment<
-    ProducerBootstrapAdapter,
+    SubgraphBootstrapAdapter<ProducerBootstrapAdapter>,
     DefaultWindowPipeline,
     DefaultEventsPipeline,
     DefaultTokensPipeline,
@@ -32,10 +42,15 @@ pub fn default_environment(
         default_manifest_fetcher(),
         default_dump_downloader(),
         Arc::new(move |target: &RunnerTarget| {
-            let events = DefaultEventsPipeline::with_hyperrpc(
-                target.inputs.ob_id.chain_id,
-                hypersync_token.clone(),
-            )?;
+            let chain_id = target.inputs.ob_id.chain_id;
+            let events = match hyperrpc_urls.get(&target.network_key) {
+                Some(url_template) => DefaultEventsPipeline::with_hyperrpc_url(
+                    chain_id,
+                    url_template,
+                    hypersync_token.clone(),
+                )?,
+                None => DefaultEventsPipeline::with_hyperrpc(chain_id, hypersync_token.clone())?,
+            };
             let tokens = DefaultTokensPipeline::new(target.inputs.metadata_rpcs.clone())?;
             let status = ProducerStatusBus::new(
                 debug_status,
@@ -44,7 +59,10 @@ pub fn default_environment(
             );
 
             Ok(EnginePipelines::new(
-                ProducerBootstrapAdapter::new(),
+                SubgraphBootstrapAdapter::new(
+                    ProducerBootstrapAdapter::new(),
+                    subgraph_urls.get(&target.orderbook_key).cloned(),
+                ),
                 DefaultWindowPipeline::new(),
                 events,
                 tokens,
@@ -64,7 +82,6 @@ mod tests {
     use rain_orderbook_common::local_db::pipeline::{FinalityConfig, SyncConfig, WindowOverrides};
     use rain_orderbook_common::local_db::{LocalDbError, OrderbookIdentifier};
     use rain_orderbook_common::rpc_client::RpcClientError;
-    use url::Url;
 
     fn sample_target(chain_id: u32) -> RunnerTarget {
         let fetch = FetchConfig::new(1, 1, 1, 1, 0, 0).expect("fetch config");
@@ -85,6 +102,7 @@ mod tests {
                     window_overrides: WindowOverrides::default(),
                 },
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 10000,
                 manifest_end_block: 1,
             },
@@ -93,7 +111,12 @@ mod tests {
 
     #[test]
     fn build_engine_configures_hyperrpc_for_supported_chain() {
-        let env = default_environment("super-secret-token".to_string(), DebugStatus::Disabled);
+        let env = default_environment(
+            "super-secret-token".to_string(),
+            HashMap::new(),
+            HashMap::new(),
+            DebugStatus::Disabled,
+        );
         let target = sample_target(42161);
         let engine = env.build_engine(&target).expect("engine available");
 
@@ -110,7 +133,12 @@ mod tests {
 
     #[test]
     fn build_engine_rejects_unsupported_chain() {
-        let env = default_environment("token".to_string(), DebugStatus::Disabled);
+        let env = default_environment(
+            "token".to_string(),
+            HashMap::new(),
+            HashMap::new(),
+            DebugStatus::Disabled,
+        );
         let target = sample_target(1);
         match env.build_engine(&target) {
             Err(LocalDbError::Rpc(RpcClientError::UnsupportedChainId { chain_id })) => {
@@ -120,4 +148,59 @@ mod tests {
             Ok(_) => panic!("expected unsupported chain to fail"),
         }
     }
+
+    #[test]
+    fn build_engine_uses_configured_hyperrpc_url() {
+        let env = default_environment(
+            "token".to_string(),
+            HashMap::from([(
+                "anvil".to_string(),
+                "https://{chain-id}.rpc.hypersync.xyz/{api-token}".to_string(),
+            )]),
+            HashMap::new(),
+            DebugStatus::Disabled,
+        );
+        let engine = env
+            .build_engine(&sample_target(1))
+            .expect("engine available");
+
+        let events_debug = format!("{:?}", engine.events);
+        assert!(
+            events_debug.contains("https://1.rpc.hypersync.xyz"),
+            "expected configured HyperRPC URL in debug repr: {events_debug}"
+        );
+    }
+
+    #[test]
+    fn build_engine_bootstraps_from_configured_subgraph() {
+        let env = default_environment(
+            "token".to_string(),
+            HashMap::new(),
+            HashMap::from([(
+                "test-book".to_string(),
+                Url::parse("https://subgraph.example/test-book").unwrap(),
+            )]),
+            DebugStatus::Disabled,
+        );
+        let engine = env
+            .build_engine(&sample_target(42161))
+            .expect("engine available");
+
+        let bootstrap_debug = format!("{:?}", engine.bootstrap);
+        assert!(
+            bootstrap_debug.contains("subgraph.example"),
+            "expected subgraph client in debug repr: {bootstrap_debug}"
+        );
+
+        let env = default_environment(
+            "token".to_string(),
+            HashMap::new(),
+            HashMap::new(),
+            DebugStatus::Disabled,
+        );
+        let engine = env
+            .build_engine(&sample_target(42161))
+            .expect("engine available");
+        assert!(format!("{:?}", engine.bootstrap).contains("client: None"));
+    }
 }
@@ -1,8 +1,9 @@
 use crate::commands::local_db::executor::RusqliteExecutor;
-use alloy::primitives::hex::encode_prefixed;
+use alloy::primitives::{hex::encode_prefixed, B256};
 use flate2::write::GzEncoder;
 use flate2::Compression;
-use rain_orderbook_common::local_db::export::{export_data_only, ExportError};
+use rain_orderbook_common::local_db::export::{export_data_only, export_delta, ExportError};
+use rain_orderbook_common::local_db::pipeline::runner::remotes::dump_sha256;
 use rain_orderbook_common::local_db::pipeline::runner::utils::RunnerTarget;
 use rain_orderbook_common::local_db::pipeline::SyncOutcome;
 use rain_orderbook_common::local_db::query::fetch_target_watermark::{
@@ -17,29 +18,47 @@ use tokio::fs::create_dir_all;
 #[derive(Debug)]
 pub struct ExportMetadata {
     pub dump_path: PathBuf,
+    /// SHA-256 of the compressed dump written to `dump_path`.
+    pub dump_sha256: B256,
+    /// Block the dump continues from when it is a delta on top of the
+    /// previously published manifest entry, `None` for a full snapshot.
+    pub start_block: Option<u64>,
     pub end_block: u64,
     pub end_block_hash: String,
     pub end_block_time_ms: u64,
 }
 
+/// Exports the synced orderbook as a full snapshot, or as a delta covering
+/// `(start_block, target_block]` when `start_block` is given.
 pub(super) async fn export_dump(
     executor: &RusqliteExecutor,
     target: &RunnerTarget,
     outcome: &SyncOutcome,
     out_root: &Path,
+    start_block: Option<u64>,
 ) -> Result<Option<ExportMetadata>, LocalDbError> {
-    let dump_sql = match export_data_only(executor, &target.inputs.ob_id).await? {
-        Some(sql) => sql,
-        None => return Ok(None),
+    let ob_id = &target.inputs.ob_id;
+    let dump_sql = match start_block {
+        Some(start_block) if outcome.target_block <= start_block => None,
+        Some(start_block) => {
+            export_delta(executor, ob_id, start_block, outcome.target_block).await?
+        }
+        None => export_data_only(executor, ob_id).await?,
+    };
+    let Some(dump_sql) = dump_sql else {
+        return Ok(None);
     };
 
-    let chain_folder = out_root.join(target.inputs.ob_id.chain_id.to_string());
+    let chain_folder = out_root.join(ob_id.chain_id.to_string());
     create_dir_all(&chain_folder).await?;
 
-    let filename = format!(
-        "{}-{}.sql.gz",
-        target.inputs.ob_id.chain_id, target.inputs.ob_id.orderbook_address
-    );
+    let filename = match start_block {
+        Some(start_block) => format!(
+            "{}-{}-{}-{}.sql.gz",
+            ob_id.chain_id, ob_id.orderbook_address, start_block, outcome.target_block
+        ),
+        None => format!("{}-{}.sql.gz", ob_id.chain_id, ob_id.orderbook_address),
+    };
     let dump_path = chain_folder.join(filename);
 
     let compressed = tokio::task::spawn_blocking(move || -> Result<Vec<u8>, std::io::Error> {
@@ -49,19 +68,22 @@ pub(super) async fn export_dump(
     })
     .await??;
 
+    let dump_sha256 = dump_sha256(&compressed);
     tokio::fs::write(&dump_path, compressed).await?;
 
-    let watermark_stmt = fetch_target_watermark_stmt(&target.inputs.ob_id);
+    let watermark_stmt = fetch_target_watermark_stmt(ob_id);
     let rows: Vec<TargetWatermarkRow> = executor.query_json(&watermark_stmt).await?;
     let row = rows.into_iter().next().ok_or_else(|| {
         LocalDbError::from(ExportError::MissingTargetWatermark {
-            chain_id: target.inputs.ob_id.chain_id,
-            orderbook_address: target.inputs.ob_id.orderbook_address,
+            chain_id: ob_id.chain_id,
+            orderbook_address: ob_id.orderbook_address,
         })
     })?;
 
     Ok(Some(ExportMetadata {
         dump_path,
+        dump_sha256,
+        start_block,
         end_block: outcome.target_block,
         end_block_hash: encode_prefixed(&row.last_hash),
         end_block_time_ms: row.updated_at,
@@ -151,6 +173,7 @@ mod tests {
                 metadata_rpcs: Vec::new(),
                 cfg: sync_config,
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 10000,
                 manifest_end_block: 1,
             },
@@ -162,9 +185,11 @@ mod tests {
             target_block: 1000,
             fetched_logs: 1,
             decoded_events: 1,
+            rolled_back_to: None,
+            events: vec![],
         };
 
-        let metadata = export_dump(&executor, &target, &outcome, temp_dir.path())
+        let metadata = export_dump(&executor, &target, &outcome, temp_dir.path(), None)
             .await
             .expect("export succeeds")
             .expect("dump produced");
@@ -192,6 +217,7 @@ mod tests {
         );
 
         let gz_bytes = fs::read(&metadata.dump_path).await.expect("read dump file");
+        assert_eq!(metadata.dump_sha256, dump_sha256(&gz_bytes));
         let mut decoder = GzDecoder::new(Cursor::new(gz_bytes));
         let mut sql = String::new();
         decoder.read_to_string(&mut sql).expect("decode gzip");
@@ -204,6 +230,98 @@ mod tests {
         assert!(sql.ends_with("COMMIT;\n"));
     }
 
+    #[tokio::test]
+    async fn export_dump_writes_delta_since_start_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("orderbook.sqlite");
+        let conn = Connection::open(&db_path).expect("open sqlite db");
+        conn.execute_batch(
+            rain_orderbook_common::local_db::query::create_tables::CREATE_TABLES_SQL,
+        )
+        .expect("create tables");
+
+        let chain_id = 8453u32;
+        let orderbook_address = address!("0x0000000000000000000000000000000000000def");
+        let orderbook_str = encode_prefixed(orderbook_address);
+        for (tx, block) in [("0xoldtx", 900i64), ("0xnewtx", 950i64)] {
+            conn.execute(
+                "INSERT INTO raw_events (chain_id, orderbook_address, transaction_hash, log_index, block_number, block_timestamp, address, topics, data, raw_json) VALUES (?1, ?2, ?3, 0, ?4, 1, 'event_address', '[]', '0x00', '{}');",
+                params![chain_id as i64, orderbook_str.as_str(), tx, block],
+            )
+            .expect("insert raw_events row");
+        }
+        conn.execute(
+            "INSERT INTO target_watermarks (chain_id, orderbook_address, last_block, last_hash, updated_at) VALUES (?1, ?2, 1000, '0xbeef', 1700000000000);",
+            params![chain_id as i64, orderbook_str.as_str()],
+        )
+        .expect("insert watermark row");
+        drop(conn);
+
+        let executor = RusqliteExecutor::new(&db_path);
+        let ob_id = OrderbookIdentifier {
+            chain_id,
+            orderbook_address,
+        };
+        let target = RunnerTarget {
+            orderbook_key: "delta".to_string(),
+            manifest_url: Url::parse("https://example.com/manifest.yaml").unwrap(),
+            network_key: "base".to_string(),
+            inputs: SyncInputs {
+                ob_id: ob_id.clone(),
+                metadata_rpcs: Vec::new(),
+                cfg: SyncConfig {
+                    deployment_block: 1,
+                    fetch: FetchConfig::new(1, 1, 1, 1, 0, 0).expect("fetch config"),
+                    finality: FinalityConfig { depth: 1 },
+                    window_overrides: WindowOverrides::default(),
+                },
+                dump_str: None,
+                delta_dump_str: None,
+                block_number_threshold: 10000,
+                manifest_end_block: 1,
+            },
+        };
+        let outcome = SyncOutcome {
+            ob_id,
+            start_block: 901,
+            target_block: 1000,
+            fetched_logs: 1,
+            decoded_events: 1,
+            rolled_back_to: None,
+            events: vec![],
+        };
+
+        let metadata = export_dump(&executor, &target, &outcome, temp_dir.path(), Some(900))
+            .await
+            .expect("export succeeds")
+            .expect("delta produced");
+        assert_eq!(metadata.start_block, Some(900));
+        assert_eq!(metadata.end_block, 1000);
+        assert_eq!(metadata.end_block_hash, "0xbeef");
+        let expected_file = format!("{}-{}-900-1000.sql.gz", chain_id, orderbook_address);
+        assert_eq!(
+            metadata
+                .dump_path
+                .file_name()
+                .and_then(|name| name.to_str()),
+            Some(expected_file.as_str())
+        );
+
+        let gz_bytes = fs::read(&metadata.dump_path).await.expect("read dump file");
+        let mut sql = String::new();
+        GzDecoder::new(Cursor::new(gz_bytes))
+            .read_to_string(&mut sql)
+            .expect("decode gzip");
+        assert!(sql.contains("INSERT OR REPLACE INTO \"raw_events\""));
+        assert!(sql.contains("0xnewtx"));
+        assert!(!sql.contains("0xoldtx"));
+
+        let up_to_date = export_dump(&executor, &target, &outcome, temp_dir.path(), Some(1000))
+            .await
+            .expect("export succeeds");
+        assert!(up_to_date.is_none());
+    }
+
     #[tokio::test]
     async fn export_dump_returns_none_when_no_rows_are_present() {
         let temp_dir = TempDir::new().unwrap();
@@ -240,6 +358,7 @@ mod tests {
                 metadata_rpcs: Vec::new(),
                 cfg: sync_config,
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 10000,
                 manifest_end_block: 1,
             },
@@ -251,9 +370,11 @@ mod tests {
             target_block: 0,
             fetched_logs: 0,
             decoded_events: 0,
+            rolled_back_to: None,
+            events: vec![],
         };
 
-        let result = export_dump(&executor, &target, &outcome, temp_dir.path())
+        let result = export_dump(&executor, &target, &outcome, temp_dir.path(), None)
             .await
             .expect("export succeeds");
 
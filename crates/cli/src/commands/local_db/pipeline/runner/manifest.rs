@@ -1,6 +1,9 @@
 use super::export::ExportMetadata;
 use alloy::primitives::{Address, Bytes};
-use rain_orderbook_app_settings::local_db_manifest::{LocalDbManifest, ManifestOrderbook};
+use alloy::signers::{local::PrivateKeySigner, SignerSync};
+use rain_orderbook_app_settings::local_db_manifest::{
+    LocalDbManifest, ManifestDelta, ManifestOrderbook,
+};
 use rain_orderbook_app_settings::remote::manifest::ManifestMap;
 use rain_orderbook_common::local_db::pipeline::runner::utils::RunnerTarget;
 use rain_orderbook_common::local_db::pipeline::runner::TargetSuccess;
@@ -12,7 +15,8 @@ use tokio::fs;
 use url::Url;
 
 /// Builds a manifest by overlaying successful producer outcomes on top of any
-/// previous manifest entries. New dumps replace old ones; failed or skipped
+/// previous manifest entries. New snapshots replace old entries, new deltas
+/// are appended to the previous entry they continue, and failed or skipped
 /// targets keep their last known manifest entry when available.
 pub fn build_manifest(
     successes: &[TargetSuccess],
@@ -38,15 +42,51 @@ pub fn build_manifest(
                     orderbook_address: ob_id.orderbook_address,
                 })?;
 
-        let dump_url = build_dump_url(release_base_url, ob_id.chain_id, ob_id.orderbook_address)?;
         let end_block_hash = Bytes::from_str(export.end_block_hash.as_str())?;
 
-        let manifest_orderbook = ManifestOrderbook {
-            address: ob_id.orderbook_address,
-            dump_url,
-            end_block: export.end_block,
-            end_block_hash,
-            end_block_time_ms: export.end_block_time_ms,
+        let manifest_orderbook = match export.start_block {
+            Some(start_block) => {
+                let mut previous = previous_manifests
+                    .get(&runner_target.manifest_url)
+                    .and_then(|manifest| manifest.find(ob_id.chain_id, ob_id.orderbook_address))
+                    .filter(|previous| previous.head_block() == start_block)
+                    .cloned()
+                    .ok_or(LocalDbError::ManifestDeltaBaseMismatch {
+                        chain_id: ob_id.chain_id,
+                        orderbook_address: ob_id.orderbook_address,
+                        start_block,
+                    })?;
+                let delta = ManifestDelta {
+                    dump_url: build_delta_dump_url(
+                        release_base_url,
+                        ob_id.chain_id,
+                        ob_id.orderbook_address,
+                        start_block,
+                        export.end_block,
+                    )?,
+                    dump_sha256: export.dump_sha256,
+                    start_block,
+                    start_block_hash: previous.head_block_hash().clone(),
+                    end_block: export.end_block,
+                    end_block_hash,
+                    end_block_time_ms: export.end_block_time_ms,
+                };
+                previous.deltas.push(delta);
+                previous
+            }
+            None => ManifestOrderbook {
+                address: ob_id.orderbook_address,
+                dump_url: build_dump_url(
+                    release_base_url,
+                    ob_id.chain_id,
+                    ob_id.orderbook_address,
+                )?,
+                dump_sha256: export.dump_sha256,
+                end_block: export.end_block,
+                end_block_hash,
+                end_block_time_ms: export.end_block_time_ms,
+                deltas: Vec::new(),
+            },
         };
 
         push_entry(
@@ -129,15 +169,51 @@ fn build_dump_url(
     chain_id: u32,
     orderbook_address: Address,
 ) -> Result<Url, LocalDbError> {
+    build_release_url(
+        base_url,
+        format!("{}-{}.sql.gz", chain_id, orderbook_address),
+    )
+}
+
+fn build_delta_dump_url(
+    base_url: &Url,
+    chain_id: u32,
+    orderbook_address: Address,
+    start_block: u64,
+    end_block: u64,
+) -> Result<Url, LocalDbError> {
+    build_release_url(
+        base_url,
+        format!(
+            "{}-{}-{}-{}.sql.gz",
+            chain_id, orderbook_address, start_block, end_block
+        ),
+    )
+}
+
+fn build_release_url(base_url: &Url, filename: String) -> Result<Url, LocalDbError> {
     let base = base_url.as_str().trim_end_matches('/');
-    let address_str = orderbook_address.to_string();
-    let url_str = format!("{}/{}-{}.sql.gz", base, chain_id, address_str);
+    let url_str = format!("{}/{}", base, filename);
     Url::parse(&url_str).map_err(|source| LocalDbError::DumpUrlConstructionFailed {
         url: url_str,
         source,
     })
 }
 
+/// Sets the manifest signature to the EIP-191 signature of its signing
+/// payload, which clients check against the remote's configured publisher.
+pub fn sign_manifest(
+    manifest: &mut LocalDbManifest,
+    signer: &PrivateKeySigner,
+) -> Result<(), LocalDbError> {
+    let payload = manifest.signing_payload()?;
+    let signature = signer
+        .sign_message_sync(payload.as_bytes())
+        .map_err(|e| LocalDbError::CustomError(format!("Failed to sign manifest: {}", e)))?;
+    manifest.signature = Some(Bytes::copy_from_slice(&signature.as_bytes()));
+    Ok(())
+}
+
 /// Writes the manifest to disk, creating parent directories if necessary.
 pub async fn write_manifest_to_path(
     manifest: &LocalDbManifest,
@@ -155,11 +231,14 @@ pub async fn write_manifest_to_path(
 mod tests {
     use super::*;
     use crate::commands::local_db::pipeline::runner::export::ExportMetadata;
-    use alloy::primitives::address;
+    use alloy::primitives::{address, B256};
     use rain_orderbook_app_settings::local_db_manifest::{
-        LocalDbManifest, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION, MANIFEST_VERSION,
+        parse_manifest_doc, LocalDbManifest, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION,
+        MANIFEST_VERSION,
     };
+    use rain_orderbook_app_settings::yaml::load_yaml;
     use rain_orderbook_common::local_db::pipeline::engine::SyncInputs;
+    use rain_orderbook_common::local_db::pipeline::runner::remotes::verify_manifest_signature;
     use rain_orderbook_common::local_db::pipeline::{
         FinalityConfig, SyncConfig, SyncOutcome, WindowOverrides,
     };
@@ -186,6 +265,7 @@ mod tests {
                     window_overrides: WindowOverrides::default(),
                 },
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 10000,
                 manifest_end_block: 1,
             },
@@ -204,10 +284,14 @@ mod tests {
                     target_block: 1234,
                     fetched_logs: 10,
                     decoded_events: 5,
+                    rolled_back_to: None,
+                    events: vec![],
                 },
             },
             ExportMetadata {
                 dump_path: Path::new(dump_suffix).to_path_buf(),
+                dump_sha256: B256::repeat_byte(0x5a),
+                start_block: None,
                 end_block: 1234,
                 end_block_hash: "0xfeedface".to_string(),
                 end_block_time_ms: 1_700_000_000,
@@ -304,7 +388,7 @@ mod tests {
         let manifest = build_manifest(&successes, &exports, &lookup, &base_url, &HashMap::new())
             .expect("manifest build succeeds");
 
-        assert_eq!(manifest.manifest_version, 1);
+        assert_eq!(manifest.manifest_version, MANIFEST_VERSION);
         assert_eq!(manifest.db_schema_version, 2);
         assert_eq!(manifest.networks.len(), 2);
 
@@ -316,6 +400,7 @@ mod tests {
             target_a.chain_id, target_a.orderbook_address
         );
         assert_eq!(anvil.orderbooks[0].dump_url.as_str(), expected_anvil);
+        assert_eq!(anvil.orderbooks[0].dump_sha256, B256::repeat_byte(0x5a));
 
         let optimism = manifest.networks.get("optimism").expect("optimism network");
         assert_eq!(optimism.chain_id, 10);
@@ -486,7 +571,7 @@ mod tests {
         .expect("empty input succeeds");
 
         assert!(manifest.networks.is_empty());
-        assert_eq!(manifest.manifest_version, 1);
+        assert_eq!(manifest.manifest_version, MANIFEST_VERSION);
         assert_eq!(manifest.db_schema_version, 2);
     }
 
@@ -528,20 +613,25 @@ mod tests {
                         ManifestOrderbook {
                             address: target_a.orderbook_address,
                             dump_url: Url::parse("https://old.example/a.sql.gz").unwrap(),
+                            dump_sha256: B256::ZERO,
                             end_block: 100,
                             end_block_hash: Bytes::from_str("0x0aaa").unwrap(),
                             end_block_time_ms: 1_700_000_000,
+                            deltas: vec![],
                         },
                         ManifestOrderbook {
                             address: target_b.orderbook_address,
                             dump_url: Url::parse("https://old.example/b.sql.gz").unwrap(),
+                            dump_sha256: B256::ZERO,
                             end_block: 200,
                             end_block_hash: Bytes::from_str("0x0bbb").unwrap(),
                             end_block_time_ms: 1_700_000_100,
+                            deltas: vec![],
                         },
                     ],
                 },
             )]),
+            signature: None,
         };
         let manifest_url = Url::parse("https://example.com/manifest.yaml").unwrap();
         let previous_map: ManifestMap = HashMap::from([(manifest_url, previous_manifest)]);
@@ -567,6 +657,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_manifest_appends_delta_to_previous_entry() {
+        let target = OrderbookIdentifier {
+            chain_id: 42161,
+            orderbook_address: address!("0x0000000000000000000000000000000000000aa1"),
+        };
+        let lookup = HashMap::from([(
+            target.clone(),
+            sample_runner_target("anvil", target.chain_id, target.orderbook_address),
+        )]);
+
+        let (success, mut export) = sample_success(&target, "delta.sql.gz");
+        export.start_block = Some(100);
+        let successes = vec![success];
+        let exports = HashMap::from([(target.clone(), Some(export))]);
+
+        let previous_entry = ManifestOrderbook {
+            address: target.orderbook_address,
+            dump_url: Url::parse("https://old.example/a.sql.gz").unwrap(),
+            dump_sha256: B256::ZERO,
+            end_block: 100,
+            end_block_hash: Bytes::from_str("0x0aaa").unwrap(),
+            end_block_time_ms: 1_700_000_000,
+            deltas: vec![],
+        };
+        let mut previous_manifest = LocalDbManifest::new();
+        previous_manifest
+            .add_network("anvil", target.chain_id)
+            .unwrap();
+        previous_manifest
+            .push_orderbook("anvil", previous_entry.clone())
+            .unwrap();
+        let manifest_url = Url::parse("https://example.com/manifest.yaml").unwrap();
+        let previous_map: ManifestMap = HashMap::from([(manifest_url, previous_manifest)]);
+
+        let base_url = Url::parse("https://releases.example.com").unwrap();
+        let manifest = build_manifest(&successes, &exports, &lookup, &base_url, &previous_map)
+            .expect("manifest build succeeds");
+
+        let entry = &manifest.networks["anvil"].orderbooks[0];
+        assert_eq!(entry.dump_url, previous_entry.dump_url);
+        assert_eq!(entry.end_block, 100);
+        assert_eq!(entry.deltas.len(), 1);
+        let delta = &entry.deltas[0];
+        assert_eq!(
+            delta.dump_url.as_str(),
+            format!(
+                "https://releases.example.com/{}-{}-100-1234.sql.gz",
+                target.chain_id, target.orderbook_address
+            )
+        );
+        assert_eq!(delta.dump_sha256, B256::repeat_byte(0x5a));
+        assert_eq!(delta.start_block, 100);
+        assert_eq!(delta.start_block_hash, previous_entry.end_block_hash);
+        assert_eq!(delta.end_block, 1234);
+        assert_eq!(delta.end_block_hash, Bytes::from_str("0xfeedface").unwrap());
+        assert_eq!(entry.head_block(), 1234);
+
+        let err = build_manifest(&successes, &exports, &lookup, &base_url, &HashMap::new())
+            .expect_err("delta without previous entry should fail");
+        assert!(matches!(
+            err,
+            LocalDbError::ManifestDeltaBaseMismatch {
+                start_block: 100,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn build_manifest_orders_networks_alphabetically() {
         let target_devnet = OrderbookIdentifier {
@@ -666,4 +825,38 @@ mod tests {
         );
         assert!(contents.contains("anvil"), "expected network key");
     }
+
+    #[test]
+    fn sign_manifest_is_verifiable_after_yaml_roundtrip() {
+        let ob_id = OrderbookIdentifier {
+            chain_id: 42161,
+            orderbook_address: address!("0x0000000000000000000000000000000000000aa1"),
+        };
+        let mut lookup: HashMap<OrderbookIdentifier, RunnerTarget> = HashMap::new();
+        lookup.insert(
+            ob_id.clone(),
+            sample_runner_target("anvil", ob_id.chain_id, ob_id.orderbook_address),
+        );
+        let (success, export) = sample_success(&ob_id, "dump.sql.gz");
+        let mut exports = HashMap::new();
+        exports.insert(ob_id.clone(), Some(export));
+
+        let base_url = Url::parse("https://releases.example.com").unwrap();
+        let mut manifest =
+            build_manifest(&[success], &exports, &lookup, &base_url, &HashMap::new())
+                .expect("manifest build succeeds");
+
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x07)).unwrap();
+        sign_manifest(&mut manifest, &signer).expect("signing succeeds");
+
+        let yaml = manifest.to_yaml_string().expect("yaml serialization");
+        let published = parse_manifest_doc(&load_yaml(&yaml).unwrap()).expect("manifest parses");
+        let url = Url::parse("https://example.com/manifest.yaml").unwrap();
+        verify_manifest_signature(&url, &published, signer.address())
+            .expect("signature matches signer");
+        assert!(matches!(
+            verify_manifest_signature(&url, &published, Address::ZERO),
+            Err(LocalDbError::ManifestSignatureInvalid { .. })
+        ));
+    }
 }
@@ -8,10 +8,11 @@ use crate::commands::local_db::pipeline::{
     status::{DebugStatus, ProducerStatusBus},
 };
 use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
 use environment::default_environment;
 use export::export_dump;
 pub use export::ExportMetadata;
-use manifest::{build_manifest, write_manifest_to_path};
+use manifest::{build_manifest, sign_manifest, write_manifest_to_path};
 use rain_orderbook_app_settings::local_db_manifest::ManifestOrderbook;
 use rain_orderbook_common::local_db::pipeline::adapters::apply::ApplyPipeline;
 use rain_orderbook_common::local_db::pipeline::runner::{
@@ -23,7 +24,8 @@ use rain_orderbook_common::local_db::pipeline::runner::{
 use rain_orderbook_common::local_db::pipeline::{
     adapters::{
         apply::DefaultApplyPipeline, bootstrap::BootstrapPipeline, events::DefaultEventsPipeline,
-        tokens::DefaultTokensPipeline, window::DefaultWindowPipeline,
+        subgraph::SubgraphBootstrapAdapter, tokens::DefaultTokensPipeline,
+        window::DefaultWindowPipeline,
     },
     engine::SyncInputs,
     EventsPipeline, StatusBus, TokensPipeline, WindowPipeline,
@@ -36,6 +38,10 @@ use tokio::task::{JoinSet, LocalSet};
 use tracing::{error, warn};
 use url::Url;
 
+/// Deltas chained onto a base snapshot before the producer publishes a new
+/// base, bounding the downloads a fresh client needs.
+const MAX_MANIFEST_DELTAS: usize = 24;
+
 pub struct ProducerRunner<B, W, E, T, A, S> {
     settings: ParsedRunnerSettings,
     targets: Vec<RunnerTarget>,
@@ -43,6 +49,7 @@ pub struct ProducerRunner<B, W, E, T, A, S> {
     out_root: PathBuf,
     release_base_url: Url,
     manifest_output_path: PathBuf,
+    manifest_signer: Option<PrivateKeySigner>,
     environment: RunnerEnvironment<B, W, E, T, A, S>,
 }
 
@@ -78,10 +85,17 @@ where
             out_root,
             release_base_url,
             manifest_output_path,
+            manifest_signer: None,
             environment,
         })
     }
 
+    /// Signs every manifest the runner writes with `signer`.
+    pub fn with_manifest_signer(mut self, signer: PrivateKeySigner) -> Self {
+        self.manifest_signer = Some(signer);
+        self
+    }
+
     pub async fn run(&self) -> Result<ProducerRunReport, LocalDbError> {
         let manifest_map = match self
             .environment
@@ -185,7 +199,7 @@ where
             );
         }
 
-        let manifest = build_manifest(
+        let mut manifest = build_manifest(
             &report.successes,
             &exports,
             &self.target_lookup,
@@ -195,6 +209,9 @@ where
         if manifest.networks.is_empty() {
             return Ok(ProducerRunReport::from_parts(report, exports));
         }
+        if let Some(signer) = &self.manifest_signer {
+            sign_manifest(&mut manifest, signer)?;
+        }
         let manifest_path = self.manifest_output_path.clone();
         write_manifest_to_path(&manifest, manifest_path.as_path()).await?;
 
@@ -204,7 +221,7 @@ where
 
 impl
     ProducerRunner<
-        ProducerBootstrapAdapter,
+        SubgraphBootstrapAdapter<ProducerBootstrapAdapter>,
         DefaultWindowPipeline,
         DefaultEventsPipeline,
         DefaultTokensPipeline,
@@ -217,9 +234,26 @@ impl
         out_root: PathBuf,
         release_base_url: Url,
         hypersync_token: String,
+        bootstrap_from_subgraph: bool,
         debug_status: DebugStatus,
     ) -> Result<Self, LocalDbError> {
-        let environment = default_environment(hypersync_token, debug_status);
+        let settings = parse_runner_settings(&settings_yaml)?;
+        let hyperrpc_urls = settings
+            .syncs
+            .into_iter()
+            .filter_map(|(network_key, sync)| Some((network_key, sync.hyperrpc_url?)))
+            .collect();
+        let subgraph_urls = if bootstrap_from_subgraph {
+            settings
+                .orderbooks
+                .into_iter()
+                .map(|(key, orderbook)| (key, orderbook.subgraph.url.clone()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let environment =
+            default_environment(hypersync_token, hyperrpc_urls, subgraph_urls, debug_status);
         Self::with_environment(settings_yaml, out_root, release_base_url, environment)
     }
 }
@@ -286,10 +320,17 @@ where
         error,
     };
 
+    // Continue the published delta chain while it is short enough, otherwise
+    // publish a fresh base snapshot.
+    let published_head = manifest_entry
+        .as_ref()
+        .filter(|entry| entry.deltas.len() < MAX_MANIFEST_DELTAS)
+        .map(ManifestOrderbook::head_block);
+
     let inputs = match manifest_entry {
         Some(entry) => {
             let dump_sql = environment
-                .download_dump(&entry.dump_url)
+                .download_full_dump(&entry)
                 .await
                 .map_err(|error| mk_failure(TargetStage::DumpDownload, error))?;
             SyncInputs {
@@ -320,13 +361,25 @@ where
         .run(&executor, &target.inputs)
         .await
         .map_err(|error| mk_failure(TargetStage::EngineRun, error))?;
-    let exported_dump = export_dump(&executor, &target, &outcome, &out_root)
+    let start_block = delta_start_block(published_head, outcome.rolled_back_to);
+    let exported_dump = export_dump(&executor, &target, &outcome, &out_root, start_block)
         .await
         .map_err(|error| mk_failure(TargetStage::Export, error))?;
 
     Ok((TargetSuccess { outcome }, exported_dump))
 }
 
+/// Block the exported delta starts after, `None` to export a base snapshot.
+/// Deltas can only insert rows, so a rollback below the published head has to
+/// be republished as a snapshot for clients to drop the reorged-out rows.
+fn delta_start_block(published_head: Option<u64>, rolled_back_to: Option<u64>) -> Option<u64> {
+    let published_head = published_head?;
+    match rolled_back_to {
+        Some(rolled_back_to) if rolled_back_to < published_head => None,
+        _ => Some(published_head),
+    }
+}
+
 fn db_path_for_target(out_root: &Path, target: &RunnerTarget) -> Result<PathBuf, LocalDbError> {
     let chain_folder = out_root.join(target.inputs.ob_id.chain_id.to_string());
     std::fs::create_dir_all(&chain_folder)?;
@@ -348,11 +401,13 @@ mod tests {
     use async_trait::async_trait;
     use flate2::read::GzDecoder;
     use rain_orderbook_app_settings::local_db_manifest::{
-        LocalDbManifest, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION, MANIFEST_VERSION,
+        parse_manifest_doc, LocalDbManifest, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION,
+        MANIFEST_VERSION,
     };
     use rain_orderbook_app_settings::orderbook::OrderbookCfg;
     use rain_orderbook_app_settings::remote::manifest::ManifestMap;
     use rain_orderbook_app_settings::spec_version::SpecVersion;
+    use rain_orderbook_app_settings::yaml::load_yaml;
     use rain_orderbook_common::local_db::pipeline::adapters::apply::ApplyPipelineTargetInfo;
     use rain_orderbook_common::local_db::pipeline::adapters::bootstrap::{
         BootstrapConfig, BootstrapPipeline, BootstrapState,
@@ -360,6 +415,7 @@ mod tests {
     use rain_orderbook_common::local_db::pipeline::runner::environment::{
         DumpFuture, EnginePipelines, ManifestFuture,
     };
+    use rain_orderbook_common::local_db::pipeline::runner::remotes::verify_manifest_signature;
     use rain_orderbook_common::local_db::pipeline::{
         EventsPipeline, StatusBus, SyncPhase, TokensPipeline, WindowPipeline,
     };
@@ -376,6 +432,11 @@ mod tests {
     use tempfile::TempDir;
     use url::Url;
 
+    /// Watermark hash seeded by the stub bootstrap. The stub events pipeline
+    /// reports the same hash so the continuity check sees a canonical chain.
+    const SEED_WATERMARK_HASH: &str =
+        "0xfefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefe";
+
     #[derive(Clone, Default)]
     struct Telemetry {
         dump_requests: Arc<Mutex<Vec<Url>>>,
@@ -711,12 +772,12 @@ mod tests {
                 )));
                 batch.add(SqlStatement::new(format!(
                     "INSERT INTO target_watermarks (chain_id, orderbook_address, last_block, last_hash, updated_at) \
-                     VALUES ({}, '{}', {}, '0xfeedface', 1_700_000_000_000) \
+                     VALUES ({}, '{}', {}, '{}', 1_700_000_000_000) \
                      ON CONFLICT(chain_id, orderbook_address) DO UPDATE \
                      SET last_block = excluded.last_block, \
                          last_hash = excluded.last_hash, \
                          updated_at = excluded.updated_at;",
-                    ob_id.chain_id, orderbook_address, config.latest_block
+                    ob_id.chain_id, orderbook_address, config.latest_block, SEED_WATERMARK_HASH
                 )));
 
                 db.execute_batch(&batch.ensure_transaction()).await?;
@@ -816,7 +877,7 @@ mod tests {
         }
 
         async fn block_hash(&self, _block_number: u64) -> Result<B256, LocalDbError> {
-            Ok(B256::ZERO)
+            Ok(B256::from_str(SEED_WATERMARK_HASH).unwrap())
         }
     }
 
@@ -1143,12 +1204,15 @@ orderbooks:
                     orderbooks: vec![ManifestOrderbook {
                         address: orderbook_address,
                         dump_url,
+                        dump_sha256: B256::ZERO,
                         end_block: 111,
                         end_block_hash: Bytes::from_str("0xdead").unwrap(),
                         end_block_time_ms: 1000,
+                        deltas: vec![],
                     }],
                 },
             )]),
+            signature: None,
         };
         HashMap::from([(url, manifest)])
     }
@@ -1168,12 +1232,15 @@ orderbooks:
                     orderbooks: vec![ManifestOrderbook {
                         address: second_address,
                         dump_url: second_dump_url,
+                        dump_sha256: B256::ZERO,
                         end_block: 222,
                         end_block_hash: Bytes::from_str("0xbeef").unwrap(),
                         end_block_time_ms: 2000,
+                        deltas: vec![],
                     }],
                 },
             )]),
+            signature: None,
         };
         map.insert(second_url, manifest);
         map
@@ -1264,7 +1331,7 @@ orderbooks:
         };
 
         let telemetry_for_downloader = telemetry.clone();
-        let dump_downloader = Arc::new(move |url: &Url| {
+        let dump_downloader = Arc::new(move |url: &Url, _sha256: &B256| {
             let url = url.clone();
             let telemetry = telemetry_for_downloader.clone();
             Box::pin(async move {
@@ -1494,13 +1561,17 @@ orderbooks:
         let manifest_lower = manifest_contents.to_lowercase();
 
         let trimmed_base = release_base.as_str().trim_end_matches('/');
-        let success_dump = format!(
-            "{}/{}-{}.sql.gz",
+        let success_delta = format!(
+            "{}/{}-{}-111-123.sql.gz",
             trimmed_base, 42161, "0x00000000000000000000000000000000000000a1"
         );
         assert!(
-            manifest_lower.contains(&success_dump),
-            "expected manifest to contain updated dump URL for succeeded job"
+            manifest_lower.contains(&success_delta),
+            "expected manifest to contain a delta chained onto the previous entry"
+        );
+        assert!(
+            manifest_lower.contains("https://dumps.example/ok.dump.sql"),
+            "expected manifest to keep the base dump URL for succeeded job"
         );
         assert!(
             manifest_lower.contains("https://dumps.example/ok-second.dump.sql"),
@@ -1521,8 +1592,9 @@ orderbooks:
                 })
             },
         );
-        let dump_downloader =
-            Arc::new(|_url: &Url| -> DumpFuture { Box::pin(async { Ok("-- dump".to_string()) }) });
+        let dump_downloader = Arc::new(|_url: &Url, _sha256: &B256| -> DumpFuture {
+            Box::pin(async { Ok("-- dump".to_string()) })
+        });
         let behaviors = Arc::new(HashMap::new());
         let engine_builder = engine_builder_for_behaviors(telemetry.clone(), behaviors);
         let environment = RunnerEnvironment::new(manifest_fetcher, dump_downloader, engine_builder);
@@ -1561,7 +1633,7 @@ orderbooks:
             })
         };
         let telemetry_for_downloader = telemetry.clone();
-        let dump_downloader = Arc::new(move |url: &Url| {
+        let dump_downloader = Arc::new(move |url: &Url, _sha256: &B256| {
             let telemetry = telemetry_for_downloader.clone();
             let url = url.clone();
             Box::pin(async move {
@@ -1612,8 +1684,9 @@ orderbooks:
                 Box::pin(async { Ok(HashMap::new()) })
             },
         );
-        let dump_downloader =
-            Arc::new(|_url: &Url| -> DumpFuture { Box::pin(async { Ok(String::new()) }) });
+        let dump_downloader = Arc::new(|_url: &Url, _sha256: &B256| -> DumpFuture {
+            Box::pin(async { Ok(String::new()) })
+        });
         let engine_builder = Arc::new(
             |_target: &RunnerTarget| -> Result<
                 EnginePipelines<
@@ -1664,8 +1737,9 @@ orderbooks:
                 Box::pin(async move { Ok(manifest_map) })
             },
         );
-        let dump_downloader =
-            Arc::new(|_url: &Url| -> DumpFuture { Box::pin(async { Ok(String::new()) }) });
+        let dump_downloader = Arc::new(|_url: &Url, _sha256: &B256| -> DumpFuture {
+            Box::pin(async { Ok(String::new()) })
+        });
 
         let telemetry = Telemetry::default();
         let engine_builder: NoWatermarkEngineBuilder = Arc::new(move |target: &RunnerTarget| {
@@ -1804,7 +1878,7 @@ orderbooks:
         );
         assert_eq!(file_name, expected_file);
         assert_eq!(metadata.end_block, outcome.outcome.target_block);
-        assert_eq!(metadata.end_block_hash, "0xfeedface");
+        assert_eq!(metadata.end_block_hash, SEED_WATERMARK_HASH);
 
         let dump_bytes = std::fs::read(&metadata.dump_path).expect("read dump");
         let mut decoder = GzDecoder::new(&dump_bytes[..]);
@@ -1818,7 +1892,7 @@ orderbooks:
             "dump should include target_watermarks insert statements"
         );
         assert!(
-            dump_sql.contains("0xfeedface"),
+            dump_sql.contains(SEED_WATERMARK_HASH),
             "dump should preserve the watermark hash"
         );
         assert!(
@@ -1857,7 +1931,7 @@ orderbooks:
         let manifest_contents = std::fs::read_to_string(&manifest_path).expect("manifest readable");
         let manifest_lower = manifest_contents.to_lowercase();
         assert!(
-            manifest_lower.contains("manifest-version: \"1\""),
+            manifest_lower.contains("manifest-version: \"2\""),
             "manifest header missing"
         );
         let expected_base = "https://cdn.example/releases";
@@ -1891,6 +1965,39 @@ orderbooks:
         );
     }
 
+    #[tokio::test]
+    async fn run_signs_manifest_with_configured_signer() {
+        let yaml = settings_yaml_ok_only();
+        let mut behaviors = HashMap::new();
+        behaviors.insert("ok".to_string(), EngineBehavior::SuccessWithExport);
+        let (environment, _telemetry) = build_environment(HashMap::new(), behaviors);
+        let temp_dir = TempDir::new().unwrap();
+        let release_base = Url::parse("https://releases.example.com").unwrap();
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x07)).unwrap();
+        let runner = ProducerRunner::with_environment(
+            yaml,
+            temp_dir.path().to_path_buf(),
+            release_base,
+            environment,
+        )
+        .expect("runner")
+        .with_manifest_signer(signer.clone());
+
+        let report = runner.run().await.expect("run completes");
+        assert!(report.failures.is_empty());
+
+        let contents =
+            std::fs::read_to_string(temp_dir.path().join("manifest.yaml")).expect("manifest");
+        let manifest = parse_manifest_doc(&load_yaml(&contents).unwrap()).expect("parses");
+        assert!(manifest.signature.is_some());
+        verify_manifest_signature(
+            &Url::parse("https://manifests.example/a.yaml").unwrap(),
+            &manifest,
+            signer.address(),
+        )
+        .expect("manifest signed by configured signer");
+    }
+
     #[tokio::test]
     async fn run_all_successes_report_no_failures() {
         let yaml = settings_yaml_two_success();
@@ -1957,6 +2064,16 @@ orderbooks:
         assert_eq!(&second_header[..13], b"SQLite format");
     }
 
+    #[test]
+    fn delta_start_block_falls_back_to_snapshot_after_deep_rollback() {
+        assert_eq!(delta_start_block(None, None), None);
+        assert_eq!(delta_start_block(Some(111), None), Some(111));
+        assert_eq!(delta_start_block(Some(111), Some(111)), Some(111));
+        assert_eq!(delta_start_block(Some(111), Some(120)), Some(111));
+        assert_eq!(delta_start_block(Some(111), Some(110)), None);
+        assert_eq!(delta_start_block(None, Some(110)), None);
+    }
+
     #[test]
     fn db_path_for_target_builds_folder_structure() {
         let yaml = settings_yaml_ok_fail();
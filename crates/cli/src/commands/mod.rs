@@ -1,12 +1,20 @@
 mod chart;
+mod clear;
+mod fuzz;
 pub mod local_db;
+mod multicall;
 mod order;
 mod quote;
+mod registry;
 mod subgraph;
 mod trade;
+mod unit_test;
 mod vault;
+mod watch;
 mod words;
 
 pub use self::{
-    chart::Chart, order::Order, subgraph::Subgraph, trade::Trade, vault::Vault, words::Words,
+    chart::Chart, clear::Clear, fuzz::Fuzz, multicall::Multicall, order::Order, registry::Registry,
+    subgraph::Subgraph, trade::Trade, unit_test::UnitTest, vault::Vault, watch::Watch,
+    words::Words,
 };
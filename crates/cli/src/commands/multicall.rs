@@ -0,0 +1,196 @@
+use crate::{
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliTransactionArgs},
+};
+use alloy::primitives::{Address, Bytes, B256};
+use alloy::sol_types::SolCall;
+use alloy_ethers_typecast::WriteTransaction;
+use anyhow::{anyhow, Result};
+use clap::{ArgAction, Parser};
+use rain_math_float::Float;
+use rain_orderbook_bindings::OrderBook::multicallCall;
+use rain_orderbook_common::multicall::OrderbookMulticallBuilder;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::info;
+
+/// Batch deposit, withdraw, addOrder, removeOrder and takeOrders calldata
+/// into one orderbook multicall
+#[derive(Parser, Clone)]
+pub struct Multicall {
+    /// Calldata of a call to batch, repeatable, in order
+    #[arg(long = "call", value_name = "HEX")]
+    calls: Vec<Bytes>,
+
+    /// File with one calldata per line, batched after the --call calldata
+    #[arg(long, value_name = "PATH")]
+    calls_file: Option<PathBuf>,
+
+    /// Known balance of a vault before the multicall, as token:vault_id=amount,
+    /// so withdrawals from it once it is empty are rejected
+    #[arg(long = "vault-balance", value_name = "BALANCE", value_parser = parse_vault_balance)]
+    vault_balances: Vec<(Address, B256, Float)>,
+
+    /// Sign and send the multicall instead of printing its calldata
+    #[arg(long, action = ArgAction::SetTrue)]
+    send: bool,
+
+    #[clap(flatten)]
+    transaction_args: CliTransactionArgs,
+}
+
+impl Multicall {
+    fn to_builder(&self) -> Result<OrderbookMulticallBuilder> {
+        let mut builder = OrderbookMulticallBuilder::new();
+        for calldata in &self.calls {
+            builder.push_calldata(calldata)?;
+        }
+        if let Some(calls_file) = &self.calls_file {
+            let calls = read_to_string(calls_file).map_err(|e| anyhow!(e))?;
+            for line in calls.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                builder.push_calldata(&Bytes::from_str(line)?)?;
+            }
+        }
+        for (token, vault_id, balance) in &self.vault_balances {
+            builder.set_vault_balance(*token, *vault_id, *balance);
+        }
+        Ok(builder)
+    }
+}
+
+impl Execute for Multicall {
+    async fn execute(&self) -> Result<()> {
+        let calldata = self.to_builder()?.build()?;
+        if !self.send {
+            println!("{calldata}");
+            return Ok(());
+        }
+
+        let mut tx_args: TransactionArgs =
+            self.transaction_args.clone().try_into_transaction_args()?;
+        tx_args.try_fill_chain_id().await?;
+        let call = multicallCall::abi_decode(&calldata)?;
+        let params = tx_args.try_into_write_contract_parameters(call, tx_args.orderbook_address)?;
+
+        if let TransactionSigner::Unsigned { .. } = tx_args.signer {
+            return print_unsigned_transaction(&tx_args, params).await;
+        }
+
+        info!("----- Multicall -----");
+        let (client, _) = tx_args.try_into_signing_client().await?;
+        WriteTransaction::new(client, params, 4, |status| {
+            display_write_transaction_status(status);
+        })
+        .execute()
+        .await?;
+        Ok(())
+    }
+}
+
+fn parse_vault_balance(value: &str) -> Result<(Address, B256, Float), String> {
+    let invalid = || format!("Invalid vault balance {value}, expected token:vault_id=amount");
+    let (vault, amount) = value.split_once('=').ok_or_else(invalid)?;
+    let (token, vault_id) = vault.split_once(':').ok_or_else(invalid)?;
+    Ok((
+        Address::from_str(token.trim()).map_err(|e| e.to_string())?,
+        B256::from_str(vault_id.trim()).map_err(|e| e.to_string())?,
+        Float::parse(amount.trim().to_string()).map_err(|e| e.to_string())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::CliSignerArgs;
+    use alloy::primitives::{address, U256};
+    use rain_orderbook_bindings::IRaindexV6::{deposit4Call, withdraw4Call};
+    use tempfile::NamedTempFile;
+
+    const TOKEN: Address = address!("0x1111111111111111111111111111111111111111");
+
+    fn amount(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn withdraw(target_amount: &str) -> Bytes {
+        Bytes::from(
+            withdraw4Call {
+                token: TOKEN,
+                vaultId: B256::from(U256::from(1)),
+                targetAmount: amount(target_amount).get_inner(),
+                tasks: vec![],
+            }
+            .abi_encode(),
+        )
+    }
+
+    fn multicall_args(calls: Vec<Bytes>, calls_file: Option<PathBuf>) -> Multicall {
+        Multicall {
+            calls,
+            calls_file,
+            vault_balances: vec![],
+            send: false,
+            transaction_args: CliTransactionArgs {
+                orderbook_address: Address::ZERO,
+                chain_id: None,
+                rpcs: vec![],
+                signer: CliSignerArgs {
+                    derivation_index: None,
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    private_key_env: None,
+                    keystore: None,
+                    keystore_password_env: None,
+                    unsigned_from: None,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_vault_balance() {
+        let (token, vault_id, balance) = parse_vault_balance(
+            "0x1111111111111111111111111111111111111111:0x0000000000000000000000000000000000000000000000000000000000000001=2.5",
+        )
+        .unwrap();
+        assert_eq!(token, TOKEN);
+        assert_eq!(vault_id, B256::from(U256::from(1)));
+        assert!(balance.eq(amount("2.5")).unwrap());
+
+        assert_eq!(
+            parse_vault_balance("0x1111111111111111111111111111111111111111=1").unwrap_err(),
+            "Invalid vault balance 0x1111111111111111111111111111111111111111=1, expected token:vault_id=amount"
+        );
+        assert!(parse_vault_balance("0x11:0x01=1").is_err());
+    }
+
+    #[test]
+    fn test_to_builder() {
+        let deposit = Bytes::from(
+            deposit4Call {
+                token: TOKEN,
+                vaultId: B256::from(U256::from(1)),
+                depositAmount: amount("1").get_inner(),
+                tasks: vec![],
+            }
+            .abi_encode(),
+        );
+        let calls_file = NamedTempFile::new().unwrap();
+        std::fs::write(calls_file.path(), format!("\n{}\n\n", withdraw("2"))).unwrap();
+
+        let args = multicall_args(vec![deposit.clone()], Some(calls_file.path().to_path_buf()));
+        let calldata = args.to_builder().unwrap().build().unwrap();
+        assert_eq!(
+            multicallCall::abi_decode(&calldata).unwrap().data,
+            vec![deposit, withdraw("2")]
+        );
+
+        let mut args = multicall_args(vec![withdraw("2"), withdraw("2")], None);
+        args.vault_balances = vec![(TOKEN, B256::from(U256::from(1)), amount("2"))];
+        let err = args.to_builder().unwrap().build().unwrap_err();
+        assert!(err.to_string().contains("which is empty by then"));
+    }
+}
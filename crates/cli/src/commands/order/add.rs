@@ -1,5 +1,7 @@
 use crate::{
-    execute::Execute, status::display_write_transaction_status, transaction::CliTransactionArgs,
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliTransactionArgs},
 };
 use anyhow::{anyhow, Result};
 use clap::{ArgAction, Args};
@@ -7,7 +9,7 @@ use rain_orderbook_app_settings::yaml::dotrain::{DotrainYaml, DotrainYamlValidat
 use rain_orderbook_app_settings::yaml::YamlParsable;
 use rain_orderbook_common::add_order::AddOrderArgs;
 use rain_orderbook_common::dotrain::RainDocument;
-use rain_orderbook_common::transaction::TransactionArgs;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
 use std::fs::read_to_string;
 use std::path::PathBuf;
 use tracing::info;
@@ -53,31 +55,47 @@ impl CliOrderAddArgs {
 impl Execute for CliOrderAddArgs {
     async fn execute(&self) -> Result<()> {
         let add_order_args: AddOrderArgs = self.clone().to_add_order_args().await?;
-        let mut tx_args: TransactionArgs = self.transaction_args.clone().into();
-        tx_args.try_fill_chain_id().await?;
+        submit_add_order(add_order_args, &self.transaction_args, self.no_broadcast).await
+    }
+}
 
-        info!("----- Simulating Transaction -----");
+/// Simulates the add order transaction, then prints it unsigned or signs and
+/// broadcasts it unless `no_broadcast` is set
+async fn submit_add_order(
+    add_order_args: AddOrderArgs,
+    transaction_args: &CliTransactionArgs,
+    no_broadcast: bool,
+) -> Result<()> {
+    let mut tx_args: TransactionArgs = transaction_args.clone().try_into_transaction_args()?;
+    tx_args.try_fill_chain_id().await?;
+
+    info!("----- Simulating Transaction -----");
+    add_order_args
+        .simulate_execute(tx_args.clone(), None)
+        .await?;
+    info!("----- Finished Simulation Successfully -----");
+
+    if let TransactionSigner::Unsigned { .. } = tx_args.signer {
+        let params = add_order_args
+            .get_add_order_call_parameters(tx_args.clone())
+            .await?;
+        print_unsigned_transaction(&tx_args, params).await?;
+    } else if !no_broadcast {
+        info!("----- Add Order -----");
         add_order_args
-            .simulate_execute(tx_args.clone(), None)
+            .execute(tx_args, |status| {
+                display_write_transaction_status(status);
+            })
             .await?;
-        info!("----- Finished Simulation Successfully -----");
-
-        if !self.no_broadcast {
-            info!("----- Add Order -----");
-            add_order_args
-                .execute(tx_args, |status| {
-                    display_write_transaction_status(status);
-                })
-                .await?;
-        }
-
-        Ok(())
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction::CliSignerArgs;
     use alloy::primitives::{address, Address, B256, U256};
     use rain_orderbook_app_settings::spec_version::SpecVersion;
     use rain_orderbook_bindings::IRaindexV6::IOV2;
@@ -98,11 +116,17 @@ mod tests {
             deployment: "some-deployment".to_string(),
             transaction_args: CliTransactionArgs {
                 orderbook_address: Address::random(),
-                derivation_index: None,
                 chain_id: Some(123),
                 rpcs: vec!["https://some-rpc.com".to_string()],
-                max_fee_per_gas: None,
-                max_priority_fee_per_gas: None,
+                signer: CliSignerArgs {
+                    derivation_index: None,
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    private_key_env: None,
+                    keystore: None,
+                    keystore_password_env: None,
+                    unsigned_from: None,
+                },
             },
         };
 
@@ -0,0 +1,385 @@
+use crate::{
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliSignerArgs},
+};
+use alloy::primitives::Address;
+use alloy::sol_types::SolCall;
+use alloy_ethers_typecast::WriteTransaction;
+use anyhow::{anyhow, Result};
+use clap::{ArgAction, Args};
+use rain_metadata_bindings::MetaBoard::emitMetaCall;
+use rain_orderbook_bindings::{OrderBook::multicallCall, IERC20::approveCall};
+use rain_orderbook_common::deployment_builder::{
+    DeploymentAnswers, DeploymentBuilder, DeploymentCalldatas,
+};
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+#[derive(Args, Clone)]
+pub struct CliOrderDeployArgs {
+    #[arg(
+        short = 'f',
+        long = "dotrain",
+        help = "Path to the .rain file specifying the order"
+    )]
+    dotrain_file: PathBuf,
+
+    #[arg(short = 's', long, help = "Path to the settings yaml file")]
+    settings_file: Option<PathBuf>,
+
+    #[arg(short = 'e', long, help = "GUI deployment key to deploy")]
+    deployment: String,
+
+    #[arg(
+        short = 'a',
+        long,
+        help = "Path to the yaml file answering the deployment's select-tokens, fields and deposits"
+    )]
+    answers: PathBuf,
+
+    #[arg(
+        long,
+        help = "Only print approvals whose allowance from this owner differs from the deposit",
+        conflicts_with = "send"
+    )]
+    owner: Option<Address>,
+
+    /// Sign and send the approvals and the deposit and addOrder multicall
+    /// instead of printing the calldata
+    #[arg(long, action = ArgAction::SetTrue)]
+    send: bool,
+
+    #[clap(flatten)]
+    signer: CliSignerArgs,
+}
+
+impl CliOrderDeployArgs {
+    async fn to_builder(&self) -> Result<DeploymentBuilder> {
+        let dotrain = read_to_string(&self.dotrain_file).map_err(|e| anyhow!(e))?;
+        let settings = match &self.settings_file {
+            Some(settings_file) => {
+                Some(vec![read_to_string(settings_file).map_err(|e| anyhow!(e))?])
+            }
+            None => None,
+        };
+        build_deployment(dotrain, settings, &self.deployment, &self.answers).await
+    }
+}
+
+impl Execute for CliOrderDeployArgs {
+    async fn execute(&self) -> Result<()> {
+        let builder = self.to_builder().await?;
+        deploy(
+            builder,
+            &self.deployment,
+            &self.signer,
+            self.owner,
+            self.send,
+        )
+        .await
+    }
+}
+
+/// Builder for `deployment` of the dotrain with the answers file applied
+pub(crate) async fn build_deployment(
+    dotrain: String,
+    settings: Option<Vec<String>>,
+    deployment: &str,
+    answers: &Path,
+) -> Result<DeploymentBuilder> {
+    let answers = DeploymentAnswers::parse(&read_to_string(answers).map_err(|e| anyhow!(e))?)?;
+
+    let mut builder = DeploymentBuilder::new(dotrain, settings, deployment.to_string()).await?;
+    builder.apply_answers(&answers).await?;
+    Ok(builder)
+}
+
+fn to_transaction_args(
+    builder: &DeploymentBuilder,
+    deployment: &str,
+    signer: &CliSignerArgs,
+) -> Result<TransactionArgs> {
+    let gui_deployment = builder.get_current_deployment()?;
+    let orderbook = gui_deployment
+        .deployment
+        .order
+        .orderbook
+        .as_ref()
+        .ok_or(anyhow!("Deployment {} has no orderbook", deployment))?;
+    signer.clone().try_into_transaction_args(
+        orderbook.address,
+        Some(orderbook.network.chain_id as u64),
+        orderbook
+            .network
+            .rpcs
+            .iter()
+            .map(|rpc| rpc.to_string())
+            .collect(),
+    )
+}
+
+/// Prints the deployment calldata, or with `send` signs and sends the
+/// approvals, the addOrder and deposit multicall and the meta emit
+pub(crate) async fn deploy(
+    mut builder: DeploymentBuilder,
+    deployment: &str,
+    signer: &CliSignerArgs,
+    owner: Option<Address>,
+    send: bool,
+) -> Result<()> {
+    if !send {
+        let calldatas = builder.build(owner).await?;
+        println!("{}", serde_json::to_string_pretty(&calldatas)?);
+        return Ok(());
+    }
+
+    let tx_args = to_transaction_args(&builder, deployment, signer)?;
+    if let TransactionSigner::Unsigned { from } = tx_args.signer {
+        let calldatas = builder.build(Some(from)).await?;
+        return print_unsigned_deployment(&tx_args, calldatas).await;
+    }
+
+    let (client, owner) = tx_args.clone().try_into_signing_client().await?;
+    let calldatas = builder.build(Some(owner)).await?;
+
+    for approval in &calldatas.approvals {
+        info!("----- Approve {} spend -----", approval.symbol);
+        let approve_call = approveCall {
+            spender: calldatas.orderbook,
+            amount: approval.amount,
+        };
+        let params = tx_args.try_into_write_contract_parameters(approve_call, approval.token)?;
+        WriteTransaction::new(client.clone(), params, 4, |status| {
+            display_write_transaction_status(status);
+        })
+        .execute()
+        .await?;
+    }
+
+    info!("----- Add Order and Deposit -----");
+    let multicall = multicall(&calldatas);
+    let params = tx_args.try_into_write_contract_parameters(multicall, calldatas.orderbook)?;
+    WriteTransaction::new(client.clone(), params, 4, |status| {
+        display_write_transaction_status(status);
+    })
+    .execute()
+    .await?;
+
+    if let Some(emit_meta) = &calldatas.emit_meta {
+        info!("----- Emit Meta -----");
+        let params = tx_args.try_into_write_contract_parameters(
+            emitMetaCall::abi_decode(&emit_meta.calldata)?,
+            emit_meta.to,
+        )?;
+        WriteTransaction::new(client, params, 4, |status| {
+            display_write_transaction_status(status);
+        })
+        .execute()
+        .await?;
+    }
+    Ok(())
+}
+
+fn multicall(calldatas: &DeploymentCalldatas) -> multicallCall {
+    let mut data = vec![calldatas.add_order.clone()];
+    data.extend(calldatas.deposits.iter().cloned());
+    multicallCall { data }
+}
+
+/// Prints the approvals when there are any, as the multicall can't be gas
+/// estimated until they are mined.
+async fn print_unsigned_deployment(
+    tx_args: &TransactionArgs,
+    calldatas: DeploymentCalldatas,
+) -> Result<()> {
+    if !calldatas.approvals.is_empty() {
+        for approval in &calldatas.approvals {
+            let approve_call = approveCall {
+                spender: calldatas.orderbook,
+                amount: approval.amount,
+            };
+            let params =
+                tx_args.try_into_write_contract_parameters(approve_call, approval.token)?;
+            print_unsigned_transaction(tx_args, params).await?;
+        }
+        warn!("Allowances must be approved first, run again once the approvals are mined");
+        return Ok(());
+    }
+
+    let params =
+        tx_args.try_into_write_contract_parameters(multicall(&calldatas), calldatas.orderbook)?;
+    print_unsigned_transaction(tx_args, params).await?;
+
+    if let Some(emit_meta) = &calldatas.emit_meta {
+        let params = tx_args.try_into_write_contract_parameters(
+            emitMetaCall::abi_decode(&emit_meta.calldata)?,
+            emit_meta.to,
+        )?;
+        print_unsigned_transaction(tx_args, params).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, Bytes};
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
+    use tempfile::NamedTempFile;
+
+    fn dotrain() -> String {
+        format!(
+            r#"version: {version}
+gui:
+  name: Test gui
+  description: Test description
+  deployments:
+    flare:
+      name: Flare order name
+      description: Flare order description
+      deposits:
+        - token: token1
+      fields:
+        - binding: max-price
+          name: Max price
+          validation:
+            type: number
+            minimum: "1"
+networks:
+  flare:
+    rpcs:
+      - https://rpc.ankr.com/flare
+    chain-id: 14
+    currency: FLR
+rainlangs:
+  flare:
+    address: 0x1111111111111111111111111111111111111111
+    network: flare
+orderbooks:
+  flare:
+    address: 0xCEe8Cd002F151A536394E564b84076c41bBBcD4d
+    network: flare
+tokens:
+  token1:
+    address: 0x4200000000000000000000000000000000000042
+    network: flare
+    decimals: 18
+    label: Token 1
+    symbol: T1
+scenarios:
+  flare:
+    rainlang: flare
+orders:
+  flare:
+    rainlang: flare
+    orderbook: flare
+    inputs:
+      - token: token1
+    outputs:
+      - token: token1
+deployments:
+  flare:
+    scenario: flare
+    order: flare
+---
+#max-price !
+#calculate-io
+_ _: 0 0;
+#handle-io
+:;
+#handle-add-order
+:;"#,
+            version = SpecVersion::current()
+        )
+    }
+
+    fn write(content: &str) -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+        file
+    }
+
+    fn deploy_args(dotrain: &NamedTempFile, answers: &NamedTempFile) -> CliOrderDeployArgs {
+        CliOrderDeployArgs {
+            dotrain_file: dotrain.path().to_path_buf(),
+            settings_file: None,
+            deployment: "flare".to_string(),
+            answers: answers.path().to_path_buf(),
+            owner: None,
+            send: false,
+            signer: CliSignerArgs {
+                derivation_index: None,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                private_key_env: None,
+                keystore: None,
+                keystore_password_env: None,
+                unsigned_from: Some(Address::ZERO),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_to_builder() {
+        let dotrain = write(&dotrain());
+        let answers = write("fields:\n  max-price: 10\ndeposits:\n  token1: 5\n");
+        let args = deploy_args(&dotrain, &answers);
+
+        let builder = args.to_builder().await.unwrap();
+        let tx_args = to_transaction_args(&builder, &args.deployment, &args.signer).unwrap();
+        assert_eq!(
+            tx_args.orderbook_address,
+            address!("CEe8Cd002F151A536394E564b84076c41bBBcD4d")
+        );
+        assert_eq!(tx_args.chain_id, Some(14));
+        assert_eq!(tx_args.rpcs, vec!["https://rpc.ankr.com/flare".to_string()]);
+        assert_eq!(
+            tx_args.signer,
+            TransactionSigner::Unsigned {
+                from: Address::ZERO
+            }
+        );
+
+        let answers = write("fields:\n  max-price: 0\n");
+        let err = deploy_args(&dotrain, &answers)
+            .to_builder()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("too small"));
+
+        let answers = write("deposits:\n  token2: 5\n");
+        let err = deploy_args(&dotrain, &answers)
+            .to_builder()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Deposit token not found in gui config: token2"
+        );
+    }
+
+    #[test]
+    fn test_multicall() {
+        let calldatas = DeploymentCalldatas {
+            orderbook: Address::ZERO,
+            chain_id: 14,
+            rpcs: vec![],
+            approvals: vec![],
+            deposits: vec![Bytes::from(vec![2]), Bytes::from(vec![3])],
+            add_order: Bytes::from(vec![1]),
+            deposit_and_add_order: Bytes::new(),
+            emit_meta: None,
+        };
+        assert_eq!(
+            multicall(&calldatas).data,
+            vec![
+                Bytes::from(vec![1]),
+                Bytes::from(vec![2]),
+                Bytes::from(vec![3])
+            ]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 mod add;
 mod calldata;
 mod compose;
+pub(crate) mod deploy;
 mod detail;
 mod list;
 mod listorderfrontmatterkeys;
 mod orderbook_address;
+mod performance;
 mod remove;
+mod update;
 
 use crate::commands::order::orderbook_address::OrderbookAddress;
 use crate::execute::Execute;
@@ -14,11 +17,14 @@ use anyhow::Result;
 use calldata::AddOrderCalldata;
 use clap::Parser;
 use compose::Compose;
+use deploy::CliOrderDeployArgs;
 use listorderfrontmatterkeys::ListOrderFrontmatterKeys;
 
 use detail::CliOrderDetailArgs;
 use list::CliOrderListArgs;
+use performance::CliOrderPerformanceArgs;
 use remove::CliOrderRemoveArgs;
+use update::CliOrderUpdateArgs;
 
 #[derive(Parser)]
 pub enum Order {
@@ -31,9 +37,17 @@ pub enum Order {
     #[command(about = "Create an Order", alias = "add")]
     Create(CliOrderAddArgs),
 
+    #[command(about = "Deploy a GUI deployment of an Order from a yaml answers file")]
+    Deploy(CliOrderDeployArgs),
+
     #[command(about = "Remove an Order", alias = "rm")]
     Remove(CliOrderRemoveArgs),
 
+    #[command(
+        about = "Replace an Order with a new deployment, reusing its vaults, in one multicall"
+    )]
+    Update(CliOrderUpdateArgs),
+
     #[command(about = "Compose a .rain order file to Rainlang", alias = "comp")]
     Compose(Compose),
 
@@ -51,6 +65,12 @@ pub enum Order {
 
     #[command(about = "Get frontmatter keys from a dotrain file", alias = "keys")]
     ListOrderFrontmatterKeys(ListOrderFrontmatterKeys),
+
+    #[command(
+        about = "Measure an Order's volume and APY, optionally in a chosen quote token",
+        alias = "perf"
+    )]
+    Performance(CliOrderPerformanceArgs),
 }
 
 impl Execute for Order {
@@ -59,11 +79,14 @@ impl Execute for Order {
             Order::List(list) => list.execute().await,
             Order::Detail(detail) => detail.execute().await,
             Order::Create(create) => create.execute().await,
+            Order::Deploy(deploy) => deploy.execute().await,
             Order::Remove(remove) => remove.execute().await,
+            Order::Update(update) => update.execute().await,
             Order::Compose(compose) => compose.execute().await,
             Order::Calldata(calldata) => calldata.execute().await,
             Order::OrderbookAddress(orderbook_address) => orderbook_address.execute().await,
             Order::ListOrderFrontmatterKeys(keys) => keys.execute().await,
+            Order::Performance(performance) => performance.execute().await,
         }
     }
 }
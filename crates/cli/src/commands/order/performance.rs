@@ -0,0 +1,293 @@
+use crate::execute::Execute;
+use alloy::primitives::{Address, B256};
+use anyhow::Result;
+use clap::Args;
+use comfy_table::Table;
+use rain_math_float::Float;
+use rain_orderbook_common::local_db::OrderbookIdentifier;
+use rain_orderbook_common::raindex_client::RaindexClient;
+use rain_orderbook_subgraph_client::performance::{
+    DenominatedPerformance, OrderPerformance, VaultPerformance,
+};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct CliOrderPerformanceArgs {
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML describing networks, orderbooks and subgraphs"
+    )]
+    settings_file: PathBuf,
+
+    #[arg(short = 'c', long, help = "Chain ID of the order")]
+    chain_id: u32,
+
+    #[arg(short = 'o', long, help = "Address of the order's orderbook")]
+    orderbook: Address,
+
+    #[arg(short = 'H', long, help = "Hash of the order")]
+    order_hash: B256,
+
+    #[arg(
+        long,
+        help = "Denominate the order's performance in this io token instead of the one with the highest net vol"
+    )]
+    quote_token: Option<Address>,
+
+    #[arg(
+        long,
+        conflicts_with = "window",
+        help = "Unix timestamp for the start of the measured period"
+    )]
+    start: Option<u64>,
+
+    #[arg(
+        long,
+        conflicts_with = "window",
+        help = "Unix timestamp for the end of the measured period"
+    )]
+    end: Option<u64>,
+
+    #[arg(long, help = "Only measure the trailing window of this many seconds")]
+    window: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Local DB path, required when the settings configure local-db syncs"
+    )]
+    local_db_path: Option<PathBuf>,
+
+    #[arg(long, help = "Print the performance as JSON")]
+    json: bool,
+}
+
+impl Execute for CliOrderPerformanceArgs {
+    async fn execute(&self) -> Result<()> {
+        let yaml = std::fs::read_to_string(&self.settings_file)?;
+        let client = RaindexClient::new(vec![yaml], None, self.local_db_path.clone()).await?;
+
+        let order = client
+            .get_order_by_hash(
+                &OrderbookIdentifier::new(self.chain_id, self.orderbook),
+                self.order_hash,
+            )
+            .await?;
+        let (start, end) = self.timeframe();
+        let performance = order.performance(self.quote_token, start, end).await?;
+
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&performance_json(&performance)?)?
+            );
+        } else {
+            match &performance.denominated_performance {
+                Some(denominated) => info!(
+                    "Denominated in {}: APY {}, net vol {}, starting capital {}",
+                    token_label(&denominated.token.symbol, &denominated.token.address.0),
+                    denominated.apy.format()?,
+                    denominated.net_vol.format()?,
+                    denominated.starting_capital.format()?
+                ),
+                None => info!("Order performance could not be denominated in a single token"),
+            }
+            info!("\n{}", build_table(&performance)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl CliOrderPerformanceArgs {
+    fn timeframe(&self) -> (Option<u64>, Option<u64>) {
+        match self.window {
+            Some(window) => {
+                let now = chrono::Utc::now().timestamp().max(0) as u64;
+                (Some(now.saturating_sub(window)), Some(now))
+            }
+            None => (self.start, self.end),
+        }
+    }
+}
+
+fn token_label(symbol: &Option<String>, address: &str) -> String {
+    symbol.clone().unwrap_or_else(|| address.to_string())
+}
+
+fn format_optional(value: Option<Float>) -> Result<Option<String>> {
+    Ok(value.map(|v| v.format()).transpose()?)
+}
+
+fn vault_json(vault: &VaultPerformance) -> Result<Value> {
+    let apy = vault.apy_details.as_ref();
+    Ok(json!({
+        "vaultId": vault.id,
+        "token": vault.token.address.0,
+        "symbol": vault.token.symbol,
+        "totalIn": vault.vol_details.total_in.format()?,
+        "totalOut": vault.vol_details.total_out.format()?,
+        "totalVol": vault.vol_details.total_vol.format()?,
+        "netVol": vault.vol_details.net_vol.format()?,
+        "capital": format_optional(apy.map(|v| v.capital))?,
+        "apy": format_optional(apy.and_then(|v| v.apy))?,
+    }))
+}
+
+fn denominated_json(denominated: &DenominatedPerformance) -> Result<Value> {
+    Ok(json!({
+        "token": denominated.token.address.0,
+        "symbol": denominated.token.symbol,
+        "apy": denominated.apy.format()?,
+        "netVol": denominated.net_vol.format()?,
+        "startingCapital": denominated.starting_capital.format()?,
+    }))
+}
+
+fn performance_json(performance: &OrderPerformance) -> Result<Value> {
+    Ok(json!({
+        "orderHash": performance.order_hash,
+        "orderbook": performance.orderbook,
+        "startTime": performance.start_time,
+        "endTime": performance.end_time,
+        "denominatedPerformance": performance
+            .denominated_performance
+            .as_ref()
+            .map(denominated_json)
+            .transpose()?,
+        "inputs": performance
+            .inputs_vaults
+            .iter()
+            .map(vault_json)
+            .collect::<Result<Vec<Value>>>()?,
+        "outputs": performance
+            .outputs_vaults
+            .iter()
+            .map(vault_json)
+            .collect::<Result<Vec<Value>>>()?,
+    }))
+}
+
+fn build_table(performance: &OrderPerformance) -> Result<Table> {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Side",
+            "Vault ID",
+            "Token",
+            "Total In",
+            "Total Out",
+            "Net Vol",
+            "APY",
+        ]);
+
+    let sides = performance
+        .inputs_vaults
+        .iter()
+        .map(|v| ("Input", v))
+        .chain(performance.outputs_vaults.iter().map(|v| ("Output", v)));
+    for (side, vault) in sides {
+        let apy = vault.apy_details.as_ref().and_then(|v| v.apy);
+        table.add_row(vec![
+            side.to_string(),
+            vault.id.clone(),
+            token_label(&vault.token.symbol, &vault.token.address.0),
+            vault.vol_details.total_in.format()?,
+            vault.vol_details.total_out.format()?,
+            vault.vol_details.net_vol.format()?,
+            format_optional(apy)?.unwrap_or_else(|| "N/A".to_string()),
+        ]);
+    }
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_subgraph_client::performance::{apy::APYDetails, vol::VolumeDetails};
+    use rain_orderbook_subgraph_client::types::common::{SgBigInt, SgBytes, SgErc20};
+
+    fn vault(id: &str, apy: Option<Float>) -> VaultPerformance {
+        let two = Float::parse("2".to_string()).unwrap();
+        VaultPerformance {
+            id: id.to_string(),
+            token: SgErc20 {
+                id: SgBytes("0x01".to_string()),
+                address: SgBytes("0x01".to_string()),
+                name: None,
+                symbol: Some("TKN".to_string()),
+                decimals: Some(SgBigInt("18".to_string())),
+            },
+            vol_details: VolumeDetails {
+                total_in: two,
+                total_out: Float::default(),
+                total_vol: two,
+                net_vol: two,
+            },
+            apy_details: Some(APYDetails {
+                start_time: 0,
+                end_time: 1,
+                net_vol: two,
+                capital: two,
+                apy,
+                is_neg: false,
+            }),
+        }
+    }
+
+    fn performance() -> OrderPerformance {
+        OrderPerformance {
+            order_id: "0x01".to_string(),
+            order_hash: "0x02".to_string(),
+            orderbook: "0x03".to_string(),
+            denominated_performance: None,
+            start_time: 0,
+            end_time: 1,
+            inputs_vaults: vec![vault("1", Some(Float::parse("0.5".to_string()).unwrap()))],
+            outputs_vaults: vec![vault("2", None)],
+        }
+    }
+
+    #[test]
+    fn test_performance_json() {
+        let value = performance_json(&performance()).unwrap();
+        assert_eq!(value["orderHash"], "0x02");
+        assert_eq!(value["denominatedPerformance"], Value::Null);
+        assert_eq!(value["inputs"][0]["vaultId"], "1");
+        assert_eq!(value["inputs"][0]["netVol"], "2");
+        assert_eq!(value["inputs"][0]["apy"], "0.5");
+        assert_eq!(value["outputs"][0]["apy"], Value::Null);
+    }
+
+    #[test]
+    fn test_build_table() {
+        let table = build_table(&performance()).unwrap();
+        assert_eq!(table.row_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_timeframe() {
+        let mut args = CliOrderPerformanceArgs {
+            settings_file: PathBuf::new(),
+            chain_id: 1,
+            orderbook: Address::ZERO,
+            order_hash: B256::ZERO,
+            quote_token: None,
+            start: Some(10),
+            end: Some(20),
+            window: None,
+            local_db_path: None,
+            json: false,
+        };
+        assert_eq!(args.timeframe(), (Some(10), Some(20)));
+
+        args.window = Some(100);
+        let (start, end) = args.timeframe();
+        assert_eq!(end.unwrap() - start.unwrap(), 100);
+    }
+}
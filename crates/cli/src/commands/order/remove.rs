@@ -1,12 +1,15 @@
 use crate::{
-    execute::Execute, status::display_write_transaction_status, subgraph::CliSubgraphArgs,
-    transaction::CliTransactionArgs,
+    execute::Execute,
+    status::display_write_transaction_status,
+    subgraph::CliSubgraphArgs,
+    transaction::{print_unsigned_transaction, CliTransactionArgs},
 };
 use anyhow::Result;
 use clap::Args;
+use rain_orderbook_bindings::IRaindexV6::removeOrder3Call;
 use rain_orderbook_common::remove_order::RemoveOrderArgs;
 use rain_orderbook_common::subgraph::SubgraphArgs;
-use rain_orderbook_common::transaction::TransactionArgs;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
 use tracing::info;
 
 #[derive(Args, Clone)]
@@ -30,9 +33,17 @@ impl Execute for CliOrderRemoveArgs {
             .await?;
         let remove_order_args: RemoveOrderArgs = order.into();
 
-        let mut tx_args: TransactionArgs = self.transaction_args.clone().into();
+        let mut tx_args: TransactionArgs =
+            self.transaction_args.clone().try_into_transaction_args()?;
         tx_args.try_fill_chain_id().await?;
 
+        if let TransactionSigner::Unsigned { .. } = tx_args.signer {
+            let call: removeOrder3Call = remove_order_args.try_into()?;
+            let params =
+                tx_args.try_into_write_contract_parameters(call, tx_args.orderbook_address)?;
+            return print_unsigned_transaction(&tx_args, params).await;
+        }
+
         info!("----- Remove Order -----");
         remove_order_args
             .execute(tx_args, |status| {
@@ -0,0 +1,178 @@
+use crate::{
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliSignerArgs},
+};
+use alloy::primitives::{Address, B256};
+use alloy::sol_types::SolCall;
+use alloy_ethers_typecast::WriteTransaction;
+use anyhow::{anyhow, Result};
+use clap::{ArgAction, Args};
+use rain_orderbook_bindings::OrderBook::multicallCall;
+use rain_orderbook_common::local_db::OrderbookIdentifier;
+use rain_orderbook_common::raindex_client::update_orders::RaindexOrderUpdate;
+use rain_orderbook_common::raindex_client::RaindexClient;
+use rain_orderbook_common::transaction::TransactionSigner;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct CliOrderUpdateArgs {
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML describing networks, orderbooks and subgraphs"
+    )]
+    settings_file: PathBuf,
+
+    #[arg(short = 'c', long, help = "Chain ID of the order")]
+    chain_id: u32,
+
+    #[arg(short = 'o', long, help = "Address of the order's orderbook")]
+    orderbook: Address,
+
+    #[arg(short = 'H', long, help = "Hash of the order to update")]
+    order_hash: B256,
+
+    #[arg(
+        short = 'f',
+        long = "dotrain",
+        help = "Path to the .rain file specifying the new order"
+    )]
+    dotrain_file: PathBuf,
+
+    #[arg(short = 'e', long, help = "Deployment key to replace the order with")]
+    deployment: String,
+
+    #[arg(
+        long = "binding",
+        value_parser = parse_binding,
+        help = "Binding overriding the deployment's scenario, as key=value, repeatable"
+    )]
+    bindings: Vec<(String, String)>,
+
+    #[arg(
+        long,
+        help = "Local DB path, required when the settings configure local-db syncs"
+    )]
+    local_db_path: Option<PathBuf>,
+
+    /// Sign and send the remove and add order multicall instead of printing
+    /// the update
+    #[arg(long, action = ArgAction::SetTrue)]
+    send: bool,
+
+    #[clap(flatten)]
+    signer: CliSignerArgs,
+}
+
+impl Execute for CliOrderUpdateArgs {
+    async fn execute(&self) -> Result<()> {
+        let settings = read_to_string(&self.settings_file).map_err(|e| anyhow!(e))?;
+        let dotrain = read_to_string(&self.dotrain_file).map_err(|e| anyhow!(e))?;
+        let client =
+            RaindexClient::new(vec![settings.clone()], None, self.local_db_path.clone()).await?;
+
+        let order = client
+            .get_order_by_hash(
+                &OrderbookIdentifier::new(self.chain_id, self.orderbook),
+                self.order_hash,
+            )
+            .await?;
+        let update = order
+            .build_update(
+                dotrain,
+                Some(vec![settings]),
+                &self.deployment,
+                self.bindings.iter().cloned().collect::<HashMap<_, _>>(),
+            )
+            .await?;
+
+        if !self.send {
+            println!("{}", serde_json::to_string_pretty(&update)?);
+            return Ok(());
+        }
+
+        info!("----- Rainlang diff -----\n{}", update.rainlang_diff);
+        let rpcs = order
+            .get_rpc_urls()?
+            .iter()
+            .map(|rpc| rpc.to_string())
+            .collect();
+        let tx_args = self.signer.clone().try_into_transaction_args(
+            update.orderbook,
+            Some(update.chain_id as u64),
+            rpcs,
+        )?;
+        let call = multicall(&update)?;
+
+        if let TransactionSigner::Unsigned { .. } = tx_args.signer {
+            let params = tx_args.try_into_write_contract_parameters(call, update.orderbook)?;
+            return print_unsigned_transaction(&tx_args, params).await;
+        }
+
+        info!("----- Update Order -----");
+        let (client, _) = tx_args.clone().try_into_signing_client().await?;
+        let params = tx_args.try_into_write_contract_parameters(call, update.orderbook)?;
+        WriteTransaction::new(client, params, 4, |status| {
+            display_write_transaction_status(status);
+        })
+        .execute()
+        .await?;
+        Ok(())
+    }
+}
+
+fn parse_binding(binding: &str) -> Result<(String, String), String> {
+    binding
+        .split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or(format!("Invalid binding {binding}, expected key=value"))
+}
+
+fn multicall(update: &RaindexOrderUpdate) -> Result<multicallCall> {
+    Ok(multicallCall::abi_decode(&update.calldata)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Bytes;
+
+    #[test]
+    fn test_parse_binding() {
+        assert_eq!(
+            parse_binding("max-price=10").unwrap(),
+            ("max-price".to_string(), "10".to_string())
+        );
+        assert_eq!(
+            parse_binding(" key = a=b ").unwrap(),
+            ("key".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_binding("=10").unwrap_err(),
+            "Invalid binding =10, expected key=value"
+        );
+        assert_eq!(
+            parse_binding("max-price").unwrap_err(),
+            "Invalid binding max-price, expected key=value"
+        );
+    }
+
+    #[test]
+    fn test_multicall() {
+        let data = vec![Bytes::from(vec![1]), Bytes::from(vec![2])];
+        let update = RaindexOrderUpdate {
+            calldata: Bytes::from(multicallCall { data: data.clone() }.abi_encode()),
+            old_rainlang: None,
+            new_rainlang: String::new(),
+            rainlang_diff: String::new(),
+            orderbook: Address::ZERO,
+            chain_id: 1,
+        };
+        assert_eq!(multicall(&update).unwrap().data, data);
+    }
+}
@@ -0,0 +1,151 @@
+use super::CliRegistryArgs;
+use crate::commands::order::deploy::{build_deployment, deploy};
+use crate::execute::Execute;
+use crate::transaction::CliSignerArgs;
+use alloy::primitives::Address;
+use anyhow::Result;
+use clap::{ArgAction, Args};
+use rain_orderbook_common::deployment_builder::DeploymentBuilder;
+use rain_orderbook_common::registry::DotrainRegistry;
+use std::path::PathBuf;
+
+#[derive(Args, Clone)]
+pub struct CliRegistryDeployArgs {
+    #[clap(flatten)]
+    registry_args: CliRegistryArgs,
+
+    #[arg(long, help = "Key of the order in the registry")]
+    order: String,
+
+    #[arg(short = 'e', long, help = "GUI deployment key of the order")]
+    deployment: String,
+
+    #[arg(
+        short = 'a',
+        long,
+        help = "Path to the yaml file answering the deployment's select-tokens, fields and deposits"
+    )]
+    answers: PathBuf,
+
+    #[arg(
+        long,
+        help = "Only print approvals whose allowance from this owner differs from the deposit",
+        conflicts_with = "send"
+    )]
+    owner: Option<Address>,
+
+    /// Sign and send the approvals and the deposit and addOrder multicall
+    /// instead of printing the calldata
+    #[arg(long, action = ArgAction::SetTrue)]
+    send: bool,
+
+    #[clap(flatten)]
+    signer: CliSignerArgs,
+}
+
+impl CliRegistryDeployArgs {
+    async fn to_builder(&self, registry: &DotrainRegistry) -> Result<DeploymentBuilder> {
+        let dotrain = registry.order(&self.order)?.clone();
+        build_deployment(
+            dotrain,
+            registry.settings_sources(),
+            &self.deployment,
+            &self.answers,
+        )
+        .await
+    }
+}
+
+impl Execute for CliRegistryDeployArgs {
+    async fn execute(&self) -> Result<()> {
+        let registry = self.registry_args.fetch().await?;
+        let builder = self.to_builder(&registry).await?;
+        deploy(
+            builder,
+            &self.deployment,
+            &self.signer,
+            self.owner,
+            self.send,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::registry::tests::mock_registry;
+    use httpmock::MockServer;
+    use tempfile::NamedTempFile;
+
+    fn write(content: &str) -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), content).unwrap();
+        file
+    }
+
+    fn deploy_args(
+        registry_args: CliRegistryArgs,
+        order: &str,
+        answers: &NamedTempFile,
+    ) -> CliRegistryDeployArgs {
+        CliRegistryDeployArgs {
+            registry_args,
+            order: order.to_string(),
+            deployment: "flare".to_string(),
+            answers: answers.path().to_path_buf(),
+            owner: None,
+            send: false,
+            signer: CliSignerArgs {
+                derivation_index: None,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                private_key_env: None,
+                keystore: None,
+                keystore_password_env: None,
+                unsigned_from: Some(Address::ZERO),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_to_builder_composes_gui_fields() {
+        let server = MockServer::start_async().await;
+        let registry_args = mock_registry(&server);
+        let registry = registry_args.fetch().await.unwrap();
+
+        let answers = write("fields:\n  test-binding: 5\n");
+        let mut builder = deploy_args(registry_args.clone(), "valid", &answers)
+            .to_builder(&registry)
+            .await
+            .unwrap();
+        assert_eq!(
+            builder.compose_rainlang().await.unwrap(),
+            "/* 0. calculate-io */ \n_ _: 5 0;\n\n/* 1. handle-io */ \n:;"
+        );
+
+        // Unanswered fields fall back to their default
+        let answers = write("deposits:\n  token1: 0\n");
+        let mut builder = deploy_args(registry_args.clone(), "valid", &answers)
+            .to_builder(&registry)
+            .await
+            .unwrap();
+        assert_eq!(
+            builder.compose_rainlang().await.unwrap(),
+            "/* 0. calculate-io */ \n_ _: 10 0;\n\n/* 1. handle-io */ \n:;"
+        );
+
+        let answers = write("fields:\n  missing-binding: 5\n");
+        let err = deploy_args(registry_args.clone(), "valid", &answers)
+            .to_builder(&registry)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missing-binding"));
+
+        let err = deploy_args(registry_args, "missing", &answers)
+            .to_builder(&registry)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Order key not found: missing");
+    }
+}
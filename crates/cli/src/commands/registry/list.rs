@@ -0,0 +1,66 @@
+use super::CliRegistryArgs;
+use crate::execute::Execute;
+use anyhow::Result;
+use clap::Args;
+use comfy_table::Table;
+use rain_orderbook_common::registry::DotrainRegistry;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct CliRegistryListArgs {
+    #[clap(flatten)]
+    registry_args: CliRegistryArgs,
+}
+
+impl Execute for CliRegistryListArgs {
+    async fn execute(&self) -> Result<()> {
+        let registry = self.registry_args.fetch().await?;
+        info!("Settings: {}", registry.settings_url);
+        info!("\n{}", build_table(&registry));
+        Ok(())
+    }
+}
+
+fn build_table(registry: &DotrainRegistry) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Key", "Name", "Description", "URL"]);
+
+    for key in registry.order_keys() {
+        let (name, description) = match registry.order_details(&key) {
+            Ok(details) => (details.name, details.description),
+            Err(err) => ("(invalid)".to_string(), err.to_readable_msg()),
+        };
+        table.add_row(vec![
+            key.clone(),
+            name,
+            description,
+            registry.order_urls[&key].to_string(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::registry::tests::mock_registry;
+    use httpmock::MockServer;
+
+    #[tokio::test]
+    async fn test_list() {
+        let server = MockServer::start_async().await;
+        let registry_args = mock_registry(&server);
+
+        let table = build_table(&registry_args.fetch().await.unwrap()).to_string();
+        assert!(table.contains("valid"));
+        assert!(table.contains("Test gui"));
+
+        assert!(CliRegistryListArgs { registry_args }
+            .execute()
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+mod deploy;
+mod list;
+mod show;
+mod validate;
+
+use crate::execute::Execute;
+use anyhow::Result;
+use clap::{Args, Parser};
+use deploy::CliRegistryDeployArgs;
+use list::CliRegistryListArgs;
+use rain_orderbook_common::registry::DotrainRegistry;
+use show::CliRegistryShowArgs;
+use url::Url;
+use validate::CliRegistryValidateArgs;
+
+#[derive(Parser)]
+pub enum Registry {
+    #[command(about = "List the orders of a registry", alias = "ls")]
+    List(CliRegistryListArgs),
+
+    #[command(
+        about = "Show an order of a registry and its deployments",
+        alias = "view"
+    )]
+    Show(CliRegistryShowArgs),
+
+    #[command(about = "Parse every order and deployment of a registry and report errors")]
+    Validate(CliRegistryValidateArgs),
+
+    #[command(about = "Deploy an order from a registry")]
+    Deploy(CliRegistryDeployArgs),
+}
+
+impl Execute for Registry {
+    async fn execute(&self) -> Result<()> {
+        match self {
+            Registry::List(list) => list.execute().await,
+            Registry::Show(show) => show.execute().await,
+            Registry::Validate(validate) => validate.execute().await,
+            Registry::Deploy(deploy) => deploy.execute().await,
+        }
+    }
+}
+
+#[derive(Args, Clone)]
+pub struct CliRegistryArgs {
+    #[arg(
+        long = "registry",
+        help = "URL of the registry file, the settings URL followed by `key url` order lines"
+    )]
+    pub registry_url: Url,
+}
+
+impl CliRegistryArgs {
+    pub async fn fetch(&self) -> Result<DotrainRegistry> {
+        Ok(DotrainRegistry::new(self.registry_url.clone()).await?)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use httpmock::MockServer;
+    use rain_orderbook_common::test_fixtures::registry::{mock_order, mock_settings};
+
+    #[test]
+    fn verify_command() {
+        Registry::command().debug_assert();
+    }
+
+    /// Serves a registry with a valid order and one whose deployment points
+    /// at a missing order
+    pub fn mock_registry(server: &MockServer) -> CliRegistryArgs {
+        let registry = format!(
+            "{}/settings.yaml\nvalid {}/valid.rain\ninvalid {}/invalid.rain",
+            server.url(""),
+            server.url(""),
+            server.url("")
+        );
+        server.mock(|when, then| {
+            when.method("GET").path("/registry.txt");
+            then.status(200).body(registry);
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/settings.yaml");
+            then.status(200).body(mock_settings());
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/valid.rain");
+            then.status(200).body(mock_order("flare"));
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/invalid.rain");
+            then.status(200).body(mock_order("missing-order"));
+        });
+        CliRegistryArgs {
+            registry_url: Url::parse(&server.url("/registry.txt")).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let server = MockServer::start_async().await;
+        let registry = mock_registry(&server).fetch().await.unwrap();
+        assert_eq!(registry.order_keys(), vec!["invalid", "valid"]);
+
+        let err = CliRegistryArgs {
+            registry_url: Url::parse(&server.url("/missing.txt")).unwrap(),
+        }
+        .fetch()
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("HTTP 404"));
+    }
+}
@@ -0,0 +1,93 @@
+use super::CliRegistryArgs;
+use crate::execute::Execute;
+use anyhow::Result;
+use clap::{ArgAction, Args};
+use comfy_table::Table;
+use rain_orderbook_app_settings::gui::NameAndDescriptionCfg;
+use std::collections::BTreeMap;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct CliRegistryShowArgs {
+    #[clap(flatten)]
+    registry_args: CliRegistryArgs,
+
+    #[arg(long, help = "Key of the order in the registry")]
+    order: String,
+
+    /// Print the order's .rain file instead of its details
+    #[arg(long, action = ArgAction::SetTrue)]
+    dotrain: bool,
+}
+
+impl Execute for CliRegistryShowArgs {
+    async fn execute(&self) -> Result<()> {
+        let registry = self.registry_args.fetch().await?;
+        if self.dotrain {
+            println!("{}", registry.order(&self.order)?);
+            return Ok(());
+        }
+
+        let details = registry.order_details(&self.order)?;
+        info!("{}: {}", details.name, details.description);
+        if let Some(short_description) = &details.short_description {
+            info!("{}", short_description);
+        }
+        info!(
+            "\n{}",
+            build_table(&registry.deployment_details(&self.order)?)
+        );
+        Ok(())
+    }
+}
+
+fn build_table(deployments: &BTreeMap<String, NameAndDescriptionCfg>) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Deployment", "Name", "Description"]);
+
+    for (key, details) in deployments {
+        table.add_row(vec![
+            key.clone(),
+            details.name.clone(),
+            details.description.clone(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::registry::tests::mock_registry;
+    use httpmock::MockServer;
+
+    #[tokio::test]
+    async fn test_show() {
+        let server = MockServer::start_async().await;
+        let registry_args = mock_registry(&server);
+
+        let registry = registry_args.fetch().await.unwrap();
+        let table = build_table(&registry.deployment_details("valid").unwrap()).to_string();
+        assert!(table.contains("Flare order name"));
+
+        let show = CliRegistryShowArgs {
+            registry_args: registry_args.clone(),
+            order: "valid".to_string(),
+            dotrain: false,
+        };
+        assert!(show.execute().await.is_ok());
+
+        let show = CliRegistryShowArgs {
+            registry_args,
+            order: "missing".to_string(),
+            dotrain: true,
+        };
+        assert_eq!(
+            show.execute().await.unwrap_err().to_string(),
+            "Order key not found: missing"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use super::CliRegistryArgs;
+use crate::execute::Execute;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use comfy_table::Table;
+use rain_orderbook_common::registry::RegistryValidation;
+use std::path::PathBuf;
+use tracing::info;
+
+#[derive(Args, Clone)]
+pub struct CliRegistryValidateArgs {
+    #[clap(flatten)]
+    registry_args: CliRegistryArgs,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the validation report as JSON"
+    )]
+    json: Option<PathBuf>,
+}
+
+impl Execute for CliRegistryValidateArgs {
+    async fn execute(&self) -> Result<()> {
+        let registry = self.registry_args.fetch().await?;
+        let validation = registry.validate_orders().await;
+
+        if let Some(path) = &self.json {
+            std::fs::write(path, serde_json::to_string_pretty(&validation)?)?;
+        }
+        info!("\n{}", build_table(&validation));
+
+        if !validation.invalid.is_empty() {
+            return Err(anyhow!(
+                "{} of {} orders are invalid",
+                validation.invalid.len(),
+                validation.invalid.len() + validation.valid.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn build_table(validation: &RegistryValidation) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Order", "Deployment", "Result"]);
+
+    for key in validation.valid.keys() {
+        table.add_row(vec![key.clone(), String::new(), "valid".to_string()]);
+    }
+    for (key, errors) in &validation.invalid {
+        for error in errors {
+            table.add_row(vec![
+                key.clone(),
+                error.deployment.clone().unwrap_or_default(),
+                error.readable_msg.clone(),
+            ]);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::registry::tests::mock_registry;
+    use httpmock::MockServer;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_validate() {
+        let server = MockServer::start_async().await;
+        let json = NamedTempFile::new().unwrap();
+        let validate = CliRegistryValidateArgs {
+            registry_args: mock_registry(&server),
+            json: Some(json.path().to_path_buf()),
+        };
+
+        let err = validate.execute().await.unwrap_err();
+        assert_eq!(err.to_string(), "1 of 2 orders are invalid");
+
+        let report: RegistryValidation =
+            serde_json::from_str(&std::fs::read_to_string(json.path()).unwrap()).unwrap();
+        assert!(report.valid.contains_key("valid"));
+        assert_eq!(
+            report.invalid["invalid"][0].deployment,
+            Some("flare".to_string())
+        );
+
+        let table = build_table(&report).to_string();
+        assert!(table.contains("valid"));
+        assert!(table.contains("flare"));
+    }
+}
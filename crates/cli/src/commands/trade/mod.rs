@@ -1,11 +1,13 @@
 mod detail;
 mod list;
+mod replay;
 
 use crate::execute::Execute;
 use anyhow::Result;
 use clap::Parser;
 use detail::CliOrderTradeDetailArgs;
 use list::CliOrderTradesListArgs;
+use replay::CliTradeReplayArgs;
 
 #[derive(Parser)]
 pub enum Trade {
@@ -14,6 +16,9 @@ pub enum Trade {
 
     #[command(about = "List takes for an Order", alias = "ls")]
     List(CliOrderTradesListArgs),
+
+    #[command(about = "Replay trades through the interpreter on a fork and show their stacks")]
+    Replay(CliTradeReplayArgs),
 }
 
 impl Execute for Trade {
@@ -21,6 +26,7 @@ impl Execute for Trade {
         match self {
             Trade::Detail(detail) => detail.execute().await,
             Trade::List(list) => list.execute().await,
+            Trade::Replay(replay) => replay.execute().await,
         }
     }
 }
@@ -0,0 +1,427 @@
+use crate::execute::Execute;
+use alloy::primitives::{Address, B256, U256};
+use anyhow::{anyhow, Result};
+use clap::Args;
+use comfy_table::Table;
+use rain_math_float::Float;
+use rain_orderbook_common::{
+    csv::TryIntoCsv,
+    fuzz::{RainEvalResults, RainEvalResultsTable},
+    local_db::OrderbookIdentifier,
+    raindex_client::RaindexClient,
+    replays::{
+        find_trade_transactions, NewTradeReplayer, TradeReplay, TradeReplayer, TradeTransaction,
+    },
+};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::info;
+use url::Url;
+
+#[derive(Args, Clone)]
+pub struct CliTradeReplayArgs {
+    #[command(flatten)]
+    source: ReplaySource,
+
+    #[arg(
+        long,
+        requires = "from_block",
+        help = "Last block of the range (inclusive)"
+    )]
+    to_block: Option<u64>,
+
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML describing networks, orderbooks and subgraphs"
+    )]
+    settings_file: Option<PathBuf>,
+
+    #[arg(short = 'c', long, help = "Chain ID of the order")]
+    chain_id: Option<u32>,
+
+    #[arg(short = 'o', long, help = "Address of the orderbook")]
+    orderbook: Option<Address>,
+
+    #[arg(
+        short = 'r',
+        long = "rpc-url",
+        help = "RPC to fork from (repeatable), defaults to the network's RPCs when replaying an order"
+    )]
+    rpc_urls: Vec<Url>,
+
+    #[arg(
+        long,
+        help = "Local DB path, required when the settings configure local-db syncs"
+    )]
+    local_db_path: Option<PathBuf>,
+
+    #[arg(long, help = "Print the stack values as CSV, one row per stack item")]
+    csv: bool,
+}
+
+/// What to replay, only one of transactions, an order or a block range
+#[derive(Args, Clone, Debug, PartialEq)]
+#[group(required = true, multiple = false)]
+pub struct ReplaySource {
+    #[arg(long = "tx", help = "Hash of a transaction to replay (repeatable)")]
+    tx_hashes: Vec<B256>,
+
+    #[arg(
+        short = 'H',
+        long,
+        requires_all = ["settings_file", "chain_id", "orderbook"],
+        help = "Replay every trade of the order with this hash"
+    )]
+    order_hash: Option<B256>,
+
+    #[arg(
+        long,
+        requires_all = ["to_block", "orderbook"],
+        help = "Replay every take and clear on the orderbook from this block (inclusive)"
+    )]
+    from_block: Option<u64>,
+}
+
+/// A single stack item of a replayed trade, or the reason the replay failed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayRow {
+    pub tx_hash: String,
+    pub block_number: Option<u64>,
+    pub column: String,
+    pub value: String,
+    pub error: Option<String>,
+}
+
+impl Execute for CliTradeReplayArgs {
+    async fn execute(&self) -> Result<()> {
+        let (transactions, rpc_urls) = self.resolve_transactions().await?;
+        if transactions.is_empty() {
+            info!("No trades to replay");
+            return Ok(());
+        }
+        let fork_url = rpc_urls
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("--rpc-url is required to replay trades"))?;
+
+        info!("Replaying {} transactions", transactions.len());
+        let mut replayer = TradeReplayer::new(NewTradeReplayer { fork_url }).await?;
+        let replays = replayer.replay_trades(transactions).await;
+        let failed = replays.iter().filter(|r| r.result.is_err()).count();
+        let total = replays.len();
+        let rows = replay_rows(replays);
+
+        if self.csv {
+            println!("{}", rows.try_into_csv()?);
+        } else {
+            info!("\n{}", build_table(&rows));
+        }
+
+        if failed > 0 {
+            return Err(anyhow!("{} of {} replays failed", failed, total));
+        }
+        Ok(())
+    }
+}
+
+impl CliTradeReplayArgs {
+    async fn resolve_transactions(&self) -> Result<(Vec<TradeTransaction>, Vec<Url>)> {
+        if let Some(order_hash) = self.source.order_hash {
+            return self.order_transactions(order_hash).await;
+        }
+
+        if let Some(from_block) = self.source.from_block {
+            let (Some(to_block), Some(orderbook)) = (self.to_block, self.orderbook) else {
+                return Err(anyhow!(
+                    "--to-block and --orderbook are required with --from-block"
+                ));
+            };
+            if to_block < from_block {
+                return Err(anyhow!(
+                    "--to-block {} is before --from-block {}",
+                    to_block,
+                    from_block
+                ));
+            }
+            if self.rpc_urls.is_empty() {
+                return Err(anyhow!("--rpc-url is required to replay a block range"));
+            }
+            let transactions =
+                find_trade_transactions(self.rpc_urls.clone(), orderbook, from_block, to_block)
+                    .await?;
+            return Ok((transactions, self.rpc_urls.clone()));
+        }
+
+        let transactions = self
+            .source
+            .tx_hashes
+            .iter()
+            .map(|tx_hash| TradeTransaction {
+                tx_hash: *tx_hash,
+                block_number: None,
+            })
+            .collect();
+        Ok((transactions, self.rpc_urls.clone()))
+    }
+
+    /// Every trade of the order from its subgraph or the local DB, one
+    /// transaction can hold several of the order's trades
+    async fn order_transactions(
+        &self,
+        order_hash: B256,
+    ) -> Result<(Vec<TradeTransaction>, Vec<Url>)> {
+        let (Some(settings_file), Some(chain_id), Some(orderbook)) =
+            (&self.settings_file, self.chain_id, self.orderbook)
+        else {
+            return Err(anyhow!(
+                "--settings-file, --chain-id and --orderbook are required with --order-hash"
+            ));
+        };
+        let yaml = std::fs::read_to_string(settings_file)?;
+        let client = RaindexClient::new(vec![yaml], None, self.local_db_path.clone()).await?;
+        let order = client
+            .get_order_by_hash(&OrderbookIdentifier::new(chain_id, orderbook), order_hash)
+            .await?;
+
+        // the local DB returns every trade regardless of the page, so stop
+        // once a page brings nothing new
+        let mut trades = vec![];
+        let mut trade_ids = HashSet::new();
+        let mut page = 1;
+        loop {
+            let page_trades = order.get_trades_list(None, None, Some(page)).await?;
+            let count = trades.len();
+            trades.extend(
+                page_trades
+                    .into_iter()
+                    .filter(|trade| trade_ids.insert(trade.id())),
+            );
+            if trades.len() == count {
+                break;
+            }
+            page += 1;
+        }
+
+        let mut seen = HashSet::new();
+        let mut transactions: Vec<TradeTransaction> = trades
+            .iter()
+            .map(|trade| {
+                let transaction = trade.transaction();
+                TradeTransaction {
+                    tx_hash: transaction.id(),
+                    block_number: Some(transaction.block_number().saturating_to()),
+                }
+            })
+            .filter(|transaction| seen.insert(transaction.tx_hash))
+            .collect();
+        transactions.sort_by_key(|transaction| transaction.block_number);
+
+        let rpc_urls = if self.rpc_urls.is_empty() {
+            order.get_rpc_urls()?
+        } else {
+            self.rpc_urls.clone()
+        };
+        Ok((transactions, rpc_urls))
+    }
+}
+
+/// Stack items are raw floats, anything that doesn't format as one is shown
+/// as hex
+fn format_stack_value(value: U256) -> String {
+    Float::from_raw(B256::from(value))
+        .format()
+        .unwrap_or_else(|_| format!("{:#x}", value))
+}
+
+fn replay_rows(replays: Vec<TradeReplay>) -> Vec<ReplayRow> {
+    let mut rows = vec![];
+    for replay in replays {
+        match replay.result {
+            Ok(result) => rows.extend(stack_rows(
+                &replay.transaction,
+                RainEvalResults::from(vec![result]).into_flattened_table(),
+            )),
+            Err(err) => rows.push(ReplayRow {
+                tx_hash: replay.transaction.tx_hash.to_string(),
+                block_number: replay.transaction.block_number,
+                column: String::new(),
+                value: String::new(),
+                error: Some(err.to_string()),
+            }),
+        }
+    }
+    rows
+}
+
+/// A replay evaluates a single run, so its flattened table has one row
+fn stack_rows(transaction: &TradeTransaction, table: RainEvalResultsTable) -> Vec<ReplayRow> {
+    let values = table.rows.into_iter().next().unwrap_or_default();
+    table
+        .column_names
+        .into_iter()
+        .zip(values)
+        .map(|(column, value)| ReplayRow {
+            tx_hash: transaction.tx_hash.to_string(),
+            block_number: transaction.block_number,
+            column,
+            value: format_stack_value(value),
+            error: None,
+        })
+        .collect()
+}
+
+fn build_table(rows: &[ReplayRow]) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Transaction", "Block", "Stack"]);
+
+    let mut i = 0;
+    while i < rows.len() {
+        let tx_rows: Vec<&ReplayRow> = rows[i..]
+            .iter()
+            .take_while(|row| row.tx_hash == rows[i].tx_hash)
+            .collect();
+        i += tx_rows.len();
+
+        let stack = match &tx_rows[0].error {
+            Some(error) => format!("replay failed: {}", error),
+            None => tx_rows
+                .iter()
+                .map(|row| format!("{} = {}", row.column, row.value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        table.add_row(vec![
+            tx_rows[0].tx_hash.clone(),
+            tx_rows[0]
+                .block_number
+                .map(|block| block.to_string())
+                .unwrap_or_default(),
+            stack,
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rain_orderbook_common::replays::TradeReplayerError;
+    use rain_orderbook_common::rpc_client::RpcClientError;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        replay: CliTradeReplayArgs,
+    }
+
+    fn float(value: &str) -> U256 {
+        U256::from_be_bytes(Float::parse(value.to_string()).unwrap().get_inner().0)
+    }
+
+    #[test]
+    fn test_replay_args() {
+        let tx = B256::repeat_byte(1);
+        let cli = Cli::parse_from([
+            "replay",
+            "--tx",
+            &tx.to_string(),
+            "--tx",
+            &B256::repeat_byte(2).to_string(),
+            "-r",
+            "http://localhost:8545",
+        ]);
+        assert_eq!(cli.replay.source.tx_hashes, vec![tx, B256::repeat_byte(2)]);
+        assert_eq!(cli.replay.rpc_urls.len(), 1);
+
+        let cli = Cli::parse_from([
+            "replay",
+            "--from-block",
+            "10",
+            "--to-block",
+            "20",
+            "-o",
+            &Address::ZERO.to_string(),
+        ]);
+        assert_eq!(cli.replay.source.from_block, Some(10));
+        assert_eq!(cli.replay.to_block, Some(20));
+
+        assert!(Cli::try_parse_from(["replay"]).is_err());
+        assert!(
+            Cli::try_parse_from(["replay", "--tx", &tx.to_string(), "-H", &tx.to_string()])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from(["replay", "-H", &tx.to_string()]).is_err());
+        assert!(Cli::try_parse_from(["replay", "--from-block", "10"]).is_err());
+    }
+
+    #[test]
+    fn test_replay_rows_and_table() {
+        let transaction = TradeTransaction {
+            tx_hash: B256::repeat_byte(1),
+            block_number: Some(5),
+        };
+        let mut rows = stack_rows(
+            &transaction,
+            RainEvalResultsTable {
+                column_names: vec!["0.0".to_string(), "0.1".to_string()],
+                rows: vec![vec![float("1"), float("2.5")]],
+            },
+        );
+        rows.extend(replay_rows(vec![TradeReplay {
+            transaction: TradeTransaction {
+                tx_hash: B256::repeat_byte(2),
+                block_number: None,
+            },
+            result: Err(TradeReplayerError::RpcClient(RpcClientError::Config {
+                message: "unreachable".to_string(),
+            })),
+        }]));
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1].column, "0.1");
+        assert_eq!(rows[1].value, "2.5");
+        assert_eq!(rows[1].block_number, Some(5));
+        assert_eq!(rows[1].error, None);
+        assert_eq!(rows[2].tx_hash, B256::repeat_byte(2).to_string());
+        assert!(rows[2].error.is_some());
+
+        let table = build_table(&rows).to_string();
+        assert!(table.contains("0.1 = 2.5"));
+        assert!(table.contains("replay failed"));
+
+        let csv = rows.try_into_csv().unwrap();
+        assert!(csv.starts_with("tx_hash,block_number,column,value,error\n"));
+    }
+
+    #[test]
+    fn test_format_stack_value() {
+        assert_eq!(format_stack_value(float("3")), "3");
+        assert_eq!(format_stack_value(float("0.5")), "0.5");
+    }
+
+    #[tokio::test]
+    async fn test_replay_execute_without_rpc() {
+        let cmd = CliTradeReplayArgs {
+            source: ReplaySource {
+                tx_hashes: vec![B256::ZERO],
+                order_hash: None,
+                from_block: None,
+            },
+            to_block: None,
+            settings_file: None,
+            chain_id: None,
+            orderbook: None,
+            rpc_urls: vec![],
+            local_db_path: None,
+            csv: false,
+        };
+        let err = cmd.execute().await.unwrap_err();
+        assert_eq!(err.to_string(), "--rpc-url is required to replay trades");
+    }
+}
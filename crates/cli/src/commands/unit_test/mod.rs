@@ -0,0 +1,269 @@
+mod report;
+
+use crate::execute::Execute;
+use alloy::primitives::B256;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use comfy_table::Table;
+use rain_orderbook_common::unit_tests::{
+    parse_test_config, AssertionStatus, TestRunner, UnitTestRunOutcome,
+};
+use report::{TestFileReport, TestReport};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const TEST_FILE_SUFFIX: &str = ".test.rain";
+
+#[derive(Args, Clone)]
+pub struct UnitTest {
+    #[arg(
+        help = "Test files or directories searched recursively for *.test.rain files, each tested against the .rain file next to it",
+        default_value = "."
+    )]
+    paths: Vec<PathBuf>,
+
+    #[arg(long, help = "Seed for the random elided bindings of every run")]
+    seed: Option<B256>,
+
+    #[arg(
+        long,
+        help = "Fork at this block instead of the latest block of the scenario's network"
+    )]
+    fork_block: Option<u64>,
+
+    #[arg(long, value_name = "PATH", help = "Write a JUnit XML report")]
+    junit: Option<PathBuf>,
+
+    #[arg(long, value_name = "PATH", help = "Write a JSON report")]
+    json: Option<PathBuf>,
+}
+
+impl Execute for UnitTest {
+    async fn execute(&self) -> Result<()> {
+        let test_files = discover_test_files(&self.paths)?;
+        if test_files.is_empty() {
+            return Err(anyhow!("No {} files found", TEST_FILE_SUFFIX));
+        }
+
+        let mut report = TestReport::default();
+        for test_file in &test_files {
+            info!("Running {}", test_file.display());
+            report.files.push(self.run_file(test_file).await);
+        }
+
+        if let Some(path) = &self.junit {
+            std::fs::write(path, report.to_junit())?;
+        }
+        if let Some(path) = &self.json {
+            std::fs::write(path, report.to_json()?)?;
+        }
+        info!("\n{}", build_table(&report));
+
+        if !report.passed() {
+            return Err(anyhow!(
+                "{} of {} test files failed",
+                report.failed_files(),
+                report.files.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl UnitTest {
+    async fn run_file(&self, test_file: &Path) -> TestFileReport {
+        let main_file = main_dotrain_path(test_file);
+        let mut file_report = TestFileReport {
+            path: test_file.display().to_string(),
+            main_path: main_file.display().to_string(),
+            runs: vec![],
+            error: None,
+        };
+
+        match self.run_tests(test_file, &main_file).await {
+            Ok(runs) => file_report.runs = runs,
+            Err(e) => file_report.error = Some(e.to_string()),
+        }
+        file_report
+    }
+
+    async fn run_tests(
+        &self,
+        test_file: &Path,
+        main_file: &Path,
+    ) -> Result<Vec<UnitTestRunOutcome>> {
+        let test_dotrain = std::fs::read_to_string(test_file)
+            .map_err(|e| anyhow!("Failed to read {}: {}", test_file.display(), e))?;
+        let dotrain = std::fs::read_to_string(main_file)
+            .map_err(|e| anyhow!("Failed to read {}: {}", main_file.display(), e))?;
+
+        let test_config = parse_test_config(&test_dotrain)?;
+        let mut runner = TestRunner::new(
+            &dotrain,
+            &test_dotrain,
+            &test_config,
+            self.seed.map(|seed| seed.0),
+        )
+        .await?;
+        runner.fork_block_number = self.fork_block;
+
+        Ok(runner.run_all().await)
+    }
+}
+
+/// `strategy.test.rain` tests `strategy.rain` in the same directory
+fn main_dotrain_path(test_file: &Path) -> PathBuf {
+    let file_name = test_file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = file_name
+        .strip_suffix(TEST_FILE_SUFFIX)
+        .unwrap_or(&file_name);
+    test_file.with_file_name(format!("{}.rain", stem))
+}
+
+fn is_test_file(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().ends_with(TEST_FILE_SUFFIX))
+        .unwrap_or(false)
+}
+
+fn discover_test_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_test_files(path, &mut files)?;
+        } else if is_test_file(path) {
+            files.push(path.clone());
+        } else {
+            return Err(anyhow!(
+                "{} is neither a directory nor a {} file",
+                path.display(),
+                TEST_FILE_SUFFIX
+            ));
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn collect_test_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .map(|name| name.to_string_lossy().starts_with('.'))
+            .unwrap_or(false);
+
+        if path.is_dir() {
+            if !hidden && !path.ends_with("node_modules") {
+                collect_test_files(&path, files)?;
+            }
+        } else if is_test_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn build_table(report: &TestReport) -> Table {
+    let mut table = comfy_table::Table::new();
+    table
+        .load_preset(comfy_table::presets::UTF8_FULL)
+        .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
+        .set_header(vec!["Test", "Run", "Block", "Result"]);
+
+    for file in &report.files {
+        if let Some(error) = &file.error {
+            table.add_row(vec![
+                file.path.clone(),
+                "-".to_string(),
+                "-".to_string(),
+                format!("error: {}", error),
+            ]);
+            continue;
+        }
+        for run in &file.runs {
+            let result = if run.passed() {
+                format!("passed ({} assertions)", run.assertions.len())
+            } else if let Some(error) = &run.error {
+                format!("error: {}", error)
+            } else {
+                run.assertions
+                    .iter()
+                    .find(|a| a.status == AssertionStatus::Failed)
+                    .map(|a| format!("failed: {}", a.message))
+                    .unwrap_or_else(|| "failed".to_string())
+            };
+            table.add_row(vec![
+                file.path.clone(),
+                run.run.to_string(),
+                run.block_number.to_string(),
+                result,
+            ]);
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_main_dotrain_path() {
+        assert_eq!(
+            main_dotrain_path(Path::new("strategies/dca.test.rain")),
+            PathBuf::from("strategies/dca.rain")
+        );
+    }
+
+    #[test]
+    fn test_discover_test_files() {
+        let dir = TempDir::new().unwrap();
+        let nested = dir.path().join("nested");
+        let hidden = dir.path().join(".git");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(&hidden).unwrap();
+        for path in [
+            dir.path().join("a.rain"),
+            dir.path().join("a.test.rain"),
+            nested.join("b.test.rain"),
+            hidden.join("c.test.rain"),
+        ] {
+            std::fs::write(path, "").unwrap();
+        }
+
+        let files =
+            discover_test_files(&[dir.path().to_path_buf(), dir.path().join("a.test.rain")])
+                .unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("a.test.rain"), nested.join("b.test.rain")]
+        );
+
+        let err = discover_test_files(&[dir.path().join("a.rain")]).unwrap_err();
+        assert!(err.to_string().contains("is neither a directory"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_main_dotrain_is_reported() {
+        let dir = TempDir::new().unwrap();
+        let test_file = dir.path().join("orphan.test.rain");
+        std::fs::write(&test_file, "").unwrap();
+
+        let cmd = UnitTest {
+            paths: vec![test_file.clone()],
+            seed: None,
+            fork_block: None,
+            junit: None,
+            json: None,
+        };
+        let report = cmd.run_file(&test_file).await;
+        assert!(!report.passed());
+        assert!(report.error.unwrap().contains("orphan.rain"));
+    }
+}
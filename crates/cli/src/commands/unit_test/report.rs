@@ -0,0 +1,273 @@
+use rain_orderbook_common::unit_tests::{AssertionStatus, UnitTestRunOutcome};
+use serde::Serialize;
+
+/// Outcome of one `*.test.rain` file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestFileReport {
+    pub path: String,
+    pub main_path: String,
+    pub runs: Vec<UnitTestRunOutcome>,
+    /// Set when the test could not be set up, e.g. missing main dotrain
+    pub error: Option<String>,
+}
+
+impl TestFileReport {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.runs.iter().all(|run| run.passed())
+    }
+
+    fn cases(&self) -> Vec<JunitCase> {
+        if let Some(error) = &self.error {
+            return vec![JunitCase {
+                classname: self.path.clone(),
+                name: "setup".to_string(),
+                time: 0.0,
+                result: JunitResult::Error(error.clone()),
+            }];
+        }
+
+        let mut cases = Vec::new();
+        for run in &self.runs {
+            let classname = format!("{} run {}", self.path, run.run);
+            let first = cases.len();
+
+            for assertion in &run.assertions {
+                cases.push(JunitCase {
+                    classname: classname.clone(),
+                    name: assertion.message.clone(),
+                    time: 0.0,
+                    result: match assertion.status {
+                        AssertionStatus::Passed => JunitResult::Passed,
+                        AssertionStatus::Failed => JunitResult::Failure(format!(
+                            "assertion failed at block {}: {}",
+                            run.block_number, assertion.message
+                        )),
+                        AssertionStatus::Skipped => JunitResult::Skipped,
+                    },
+                });
+            }
+            if run.error.is_some() || run.assertions.is_empty() {
+                cases.push(JunitCase {
+                    classname: classname.clone(),
+                    name: format!("run {}", run.run),
+                    time: 0.0,
+                    result: match &run.error {
+                        Some(error) => JunitResult::Error(error.clone()),
+                        None => JunitResult::Passed,
+                    },
+                });
+            }
+
+            if let Some(case) = cases.get_mut(first) {
+                case.time = run.duration_secs;
+            }
+        }
+        cases
+    }
+}
+
+/// Outcome of every discovered test file
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestReport {
+    pub files: Vec<TestFileReport>,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> bool {
+        self.files.iter().all(|file| file.passed())
+    }
+
+    pub fn failed_files(&self) -> usize {
+        self.files.iter().filter(|file| !file.passed()).count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// JUnit XML with a testsuite per file and a testcase per assertion of
+    /// each run
+    pub fn to_junit(&self) -> String {
+        let suites: Vec<(&TestFileReport, Vec<JunitCase>)> =
+            self.files.iter().map(|file| (file, file.cases())).collect();
+        let all: Vec<&JunitCase> = suites.iter().flat_map(|(_, cases)| cases).collect();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"rain-orderbook\" {}>\n",
+            counts(&all)
+        ));
+        for (file, cases) in &suites {
+            let cases_ref: Vec<&JunitCase> = cases.iter().collect();
+            xml.push_str(&format!(
+                "  <testsuite name=\"{}\" {}>\n",
+                escape(&file.path),
+                counts(&cases_ref)
+            ));
+            for case in cases {
+                case.write(&mut xml);
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JunitResult {
+    Passed,
+    Failure(String),
+    Error(String),
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct JunitCase {
+    classname: String,
+    name: String,
+    time: f64,
+    result: JunitResult,
+}
+
+impl JunitCase {
+    fn write(&self, xml: &mut String) {
+        let open = format!(
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+            escape(&self.classname),
+            escape(&self.name),
+            self.time
+        );
+        match &self.result {
+            JunitResult::Passed => xml.push_str(&format!("{}/>\n", open)),
+            JunitResult::Failure(message) => xml.push_str(&format!(
+                "{}>\n      <failure message=\"{}\"/>\n    </testcase>\n",
+                open,
+                escape(message)
+            )),
+            JunitResult::Error(message) => xml.push_str(&format!(
+                "{}>\n      <error message=\"{}\"/>\n    </testcase>\n",
+                open,
+                escape(message)
+            )),
+            JunitResult::Skipped => {
+                xml.push_str(&format!("{}>\n      <skipped/>\n    </testcase>\n", open))
+            }
+        }
+    }
+}
+
+fn counts(cases: &[&JunitCase]) -> String {
+    let count = |f: fn(&JunitResult) -> bool| cases.iter().filter(|c| f(&c.result)).count();
+    format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\"",
+        cases.len(),
+        count(|r| matches!(r, JunitResult::Failure(_))),
+        count(|r| matches!(r, JunitResult::Error(_))),
+        count(|r| matches!(r, JunitResult::Skipped)),
+        cases.iter().map(|c| c.time).sum::<f64>()
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_common::unit_tests::AssertionOutcome;
+
+    fn assertion(message: &str, status: AssertionStatus) -> AssertionOutcome {
+        AssertionOutcome {
+            message: message.to_string(),
+            status,
+        }
+    }
+
+    fn report() -> TestReport {
+        TestReport {
+            files: vec![
+                TestFileReport {
+                    path: "strategies/a.test.rain".to_string(),
+                    main_path: "strategies/a.rain".to_string(),
+                    runs: vec![
+                        UnitTestRunOutcome {
+                            run: 1,
+                            block_number: 100,
+                            assertions: vec![
+                                assertion("ratio < 2", AssertionStatus::Passed),
+                                assertion("cap \"ok\"", AssertionStatus::Passed),
+                            ],
+                            error: None,
+                            duration_secs: 1.5,
+                        },
+                        UnitTestRunOutcome {
+                            run: 2,
+                            block_number: 100,
+                            assertions: vec![
+                                assertion("ratio < 2", AssertionStatus::Failed),
+                                assertion("cap \"ok\"", AssertionStatus::Skipped),
+                            ],
+                            error: None,
+                            duration_secs: 0.5,
+                        },
+                    ],
+                    error: None,
+                },
+                TestFileReport {
+                    path: "strategies/b.test.rain".to_string(),
+                    main_path: "strategies/b.rain".to_string(),
+                    runs: vec![],
+                    error: Some("main dotrain strategies/b.rain not found".to_string()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_report_status() {
+        let report = report();
+        assert!(!report.passed());
+        assert_eq!(report.failed_files(), 2);
+        assert!(TestReport::default().passed());
+    }
+
+    #[test]
+    fn test_to_junit() {
+        let xml = report().to_junit();
+        assert!(xml.contains(
+            "<testsuites name=\"rain-orderbook\" tests=\"5\" failures=\"1\" errors=\"1\" skipped=\"1\" time=\"2.000\">"
+        ));
+        assert!(xml.contains(
+            "<testsuite name=\"strategies/a.test.rain\" tests=\"4\" failures=\"1\" errors=\"0\" skipped=\"1\" time=\"2.000\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"strategies/a.test.rain run 1\" name=\"ratio &lt; 2\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains("name=\"cap &quot;ok&quot;\""));
+        assert!(xml.contains("<failure message=\"assertion failed at block 100: ratio &lt; 2\"/>"));
+        assert!(xml.contains("<error message=\"main dotrain strategies/b.rain not found\"/>"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+        assert_eq!(json["files"][0]["path"], "strategies/a.test.rain");
+        assert_eq!(
+            json["files"][0]["runs"][1]["assertions"][0]["status"],
+            "failed"
+        );
+        assert_eq!(
+            json["files"][1]["error"],
+            "main dotrain strategies/b.rain not found"
+        );
+    }
+}
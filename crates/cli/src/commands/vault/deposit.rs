@@ -1,12 +1,19 @@
 use crate::{
-    execute::Execute, status::display_write_transaction_status, transaction::CliTransactionArgs,
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliTransactionArgs},
 };
 use alloy::primitives::{Address, B256, U256};
 use anyhow::Result;
 use clap::Args;
 use rain_math_float::Float;
-use rain_orderbook_common::{deposit::DepositArgs, erc20::ERC20, transaction::TransactionArgs};
-use tracing::info;
+use rain_orderbook_bindings::{IRaindexV6::deposit4Call, IERC20::approveCall};
+use rain_orderbook_common::{
+    deposit::DepositArgs,
+    erc20::ERC20,
+    transaction::{TransactionArgs, TransactionSigner},
+};
+use tracing::{info, warn};
 
 #[derive(Args, Clone)]
 pub struct CliVaultDepositArgs {
@@ -28,7 +35,8 @@ pub struct CliVaultDepositArgs {
 
 impl Execute for CliVaultDepositArgs {
     async fn execute(&self) -> Result<()> {
-        let mut tx_args: TransactionArgs = self.transaction_args.clone().into();
+        let mut tx_args: TransactionArgs =
+            self.transaction_args.clone().try_into_transaction_args()?;
         tx_args.try_fill_chain_id().await?;
 
         let decimals = if let Some(decimals) = self.decimals {
@@ -51,6 +59,10 @@ impl Execute for CliVaultDepositArgs {
             decimals,
         };
 
+        if let TransactionSigner::Unsigned { from } = tx_args.signer {
+            return print_unsigned_deposit(&deposit_args, &tx_args, from).await;
+        }
+
         info!("----- Approve ERC20 token spend -----");
         deposit_args
             .execute_approve(tx_args.clone(), |status| {
@@ -67,3 +79,32 @@ impl Execute for CliVaultDepositArgs {
         Ok(())
     }
 }
+
+/// Prints the approval when the allowance doesn't match the deposit amount,
+/// as the deposit can't be gas estimated until the approval is mined.
+async fn print_unsigned_deposit(
+    deposit_args: &DepositArgs,
+    tx_args: &TransactionArgs,
+    from: Address,
+) -> Result<()> {
+    let allowance = deposit_args.read_allowance(from, tx_args.clone()).await?;
+    let allowance = Float::from_fixed_decimal(allowance, deposit_args.decimals)?;
+
+    if !allowance.eq(deposit_args.amount)? {
+        let approve_call = approveCall {
+            spender: tx_args.orderbook_address,
+            amount: deposit_args
+                .amount
+                .to_fixed_decimal(deposit_args.decimals)?,
+        };
+        let params =
+            tx_args.try_into_write_contract_parameters(approve_call, deposit_args.token)?;
+        warn!("Allowance must be approved first, run again once the approval is mined");
+        return print_unsigned_transaction(tx_args, params).await;
+    }
+
+    let deposit_call: deposit4Call = deposit_args.clone().try_into()?;
+    let params =
+        tx_args.try_into_write_contract_parameters(deposit_call, tx_args.orderbook_address)?;
+    print_unsigned_transaction(tx_args, params).await
+}
@@ -2,6 +2,7 @@ mod deposit;
 mod detail;
 mod list;
 mod list_balance_changes;
+mod rebalance;
 mod withdraw;
 
 use crate::execute::Execute;
@@ -11,6 +12,7 @@ use deposit::CliVaultDepositArgs;
 use detail::CliVaultDetailArgs;
 use list::CliVaultListArgs;
 use list_balance_changes::CliVaultBalanceChangesList;
+use rebalance::CliVaultRebalanceArgs;
 use withdraw::CliVaultWithdrawArgs;
 
 #[derive(Parser)]
@@ -29,6 +31,9 @@ pub enum Vault {
 
     #[command(about = "List balance changes for a Vault (i.e. deposits + withdraws)")]
     ListBalanceChanges(CliVaultBalanceChangesList),
+
+    #[command(about = "Deposit and withdraw to bring an owner's Vaults to target balances")]
+    Rebalance(CliVaultRebalanceArgs),
 }
 
 impl Execute for Vault {
@@ -39,6 +44,7 @@ impl Execute for Vault {
             Vault::List(list) => list.execute().await,
             Vault::Detail(detail) => detail.execute().await,
             Vault::ListBalanceChanges(list_balance_changes) => list_balance_changes.execute().await,
+            Vault::Rebalance(rebalance) => rebalance.execute().await,
         }
     }
 }
@@ -0,0 +1,208 @@
+use crate::{
+    execute::Execute,
+    status::display_write_transaction_status,
+    transaction::{print_unsigned_transaction, CliSignerArgs},
+};
+use alloy::primitives::Address;
+use alloy::sol_types::SolCall;
+use alloy_ethers_typecast::WriteTransaction;
+use anyhow::{anyhow, Result};
+use clap::{ArgAction, Args};
+use rain_orderbook_bindings::{OrderBook::multicallCall, IERC20::approveCall};
+use rain_orderbook_common::local_db::OrderbookIdentifier;
+use rain_orderbook_common::raindex_client::rebalance_vaults::{
+    VaultRebalancePlan, VaultRebalanceTargets,
+};
+use rain_orderbook_common::raindex_client::RaindexClient;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+#[derive(Args, Clone)]
+pub struct CliVaultRebalanceArgs {
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML describing networks, orderbooks and subgraphs"
+    )]
+    settings_file: PathBuf,
+
+    #[arg(short = 'c', long, help = "Chain ID of the orderbook")]
+    chain_id: u32,
+
+    #[arg(
+        short = 'o',
+        long,
+        help = "Address of the orderbook holding the vaults"
+    )]
+    orderbook: Address,
+
+    #[arg(long, help = "Owner of the vaults")]
+    owner: Address,
+
+    #[arg(
+        short = 't',
+        long,
+        help = "Path to the YAML of target balances or percentages, keyed by token and vault ID"
+    )]
+    targets_file: PathBuf,
+
+    #[arg(
+        long,
+        help = "Local DB path, required when the settings configure local-db syncs"
+    )]
+    local_db_path: Option<PathBuf>,
+
+    /// Sign and send the approvals and the rebalance multicall instead of
+    /// printing the plan
+    #[arg(long, action = ArgAction::SetTrue)]
+    send: bool,
+
+    #[clap(flatten)]
+    signer: CliSignerArgs,
+}
+
+impl Execute for CliVaultRebalanceArgs {
+    async fn execute(&self) -> Result<()> {
+        let settings = read_to_string(&self.settings_file).map_err(|e| anyhow!(e))?;
+        let targets = read_to_string(&self.targets_file).map_err(|e| anyhow!(e))?;
+        let client = RaindexClient::new(vec![settings], None, self.local_db_path.clone()).await?;
+
+        let plan = client
+            .plan_vault_rebalance(
+                &OrderbookIdentifier::new(self.chain_id, self.orderbook),
+                self.owner,
+                &VaultRebalanceTargets::parse(&targets)?,
+            )
+            .await?;
+        info!("\n{}", plan.summary);
+
+        let Some(call) = multicall(&plan)? else {
+            return Ok(());
+        };
+        if !self.send {
+            println!("{}", serde_json::to_string_pretty(&plan)?);
+            return Ok(());
+        }
+
+        let rpcs = client
+            .get_network_by_chain_id(self.chain_id)?
+            .rpcs
+            .iter()
+            .map(|rpc| rpc.to_string())
+            .collect();
+        let tx_args = self.signer.clone().try_into_transaction_args(
+            self.orderbook,
+            Some(self.chain_id as u64),
+            rpcs,
+        )?;
+
+        if let TransactionSigner::Unsigned { from } = tx_args.signer {
+            check_sender(from, self.owner)?;
+            return print_unsigned_rebalance(&plan, &tx_args, call).await;
+        }
+
+        let (signing_client, sender) = tx_args.clone().try_into_signing_client().await?;
+        check_sender(sender, self.owner)?;
+        for approval in &plan.approvals {
+            info!("----- Approve {} spend -----", approval.token);
+            let params = tx_args.try_into_write_contract_parameters(
+                approveCall::abi_decode(&approval.calldata)?,
+                approval.token,
+            )?;
+            WriteTransaction::new(signing_client.clone(), params, 4, |status| {
+                display_write_transaction_status(status);
+            })
+            .execute()
+            .await?;
+        }
+
+        info!("----- Rebalance vaults -----");
+        let params = tx_args.try_into_write_contract_parameters(call, self.orderbook)?;
+        WriteTransaction::new(signing_client, params, 4, |status| {
+            display_write_transaction_status(status);
+        })
+        .execute()
+        .await?;
+        Ok(())
+    }
+}
+
+/// Prints the first missing approval instead of the multicall, as the
+/// multicall can't be gas estimated until the approvals are mined.
+async fn print_unsigned_rebalance(
+    plan: &VaultRebalancePlan,
+    tx_args: &TransactionArgs,
+    call: multicallCall,
+) -> Result<()> {
+    if let Some(approval) = plan.approvals.first() {
+        let params = tx_args.try_into_write_contract_parameters(
+            approveCall::abi_decode(&approval.calldata)?,
+            approval.token,
+        )?;
+        warn!("Allowance must be approved first, run again once the approval is mined");
+        return print_unsigned_transaction(tx_args, params).await;
+    }
+
+    let params = tx_args.try_into_write_contract_parameters(call, plan.orderbook)?;
+    print_unsigned_transaction(tx_args, params).await
+}
+
+/// Withdrawals always run against the sender's vaults, so only the owner can
+/// send the rebalance.
+fn check_sender(sender: Address, owner: Address) -> Result<()> {
+    if sender != owner {
+        return Err(anyhow!(
+            "Sender {sender} is not the owner {owner} of the rebalanced vaults"
+        ));
+    }
+    Ok(())
+}
+
+fn multicall(plan: &VaultRebalancePlan) -> Result<Option<multicallCall>> {
+    Ok(plan
+        .calldata
+        .as_ref()
+        .map(|calldata| multicallCall::abi_decode(calldata))
+        .transpose()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Bytes;
+
+    fn plan(calldata: Option<Bytes>) -> VaultRebalancePlan {
+        VaultRebalancePlan {
+            orderbook: Address::ZERO,
+            chain_id: 1,
+            owner: Address::ZERO,
+            steps: vec![],
+            approvals: vec![],
+            calldata,
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_multicall() {
+        assert!(multicall(&plan(None)).unwrap().is_none());
+
+        let data = vec![Bytes::from(vec![1]), Bytes::from(vec![2])];
+        let calldata = Bytes::from(multicallCall { data: data.clone() }.abi_encode());
+        assert_eq!(
+            multicall(&plan(Some(calldata))).unwrap().unwrap().data,
+            data
+        );
+    }
+
+    #[test]
+    fn test_check_sender() {
+        let owner = Address::repeat_byte(0x11);
+        assert!(check_sender(owner, owner).is_ok());
+
+        let err = check_sender(Address::repeat_byte(0x22), owner).unwrap_err();
+        assert!(err.to_string().contains("is not the owner"));
+    }
+}
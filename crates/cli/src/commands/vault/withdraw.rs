@@ -1,10 +1,14 @@
 use crate::status::display_write_transaction_status;
-use crate::{execute::Execute, transaction::CliTransactionArgs};
+use crate::{
+    execute::Execute,
+    transaction::{print_unsigned_transaction, CliTransactionArgs},
+};
 use alloy::primitives::{Address, B256};
 use anyhow::Result;
 use clap::Args;
 use rain_math_float::{Float, FloatError};
-use rain_orderbook_common::transaction::TransactionArgs;
+use rain_orderbook_bindings::IRaindexV6::withdraw4Call;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
 use rain_orderbook_common::withdraw::WithdrawArgs;
 use tracing::info;
 
@@ -39,10 +43,18 @@ impl TryFrom<CliVaultWithdrawArgs> for WithdrawArgs {
 
 impl Execute for CliVaultWithdrawArgs {
     async fn execute(&self) -> Result<()> {
-        let mut tx_args: TransactionArgs = self.transaction_args.clone().into();
+        let mut tx_args: TransactionArgs =
+            self.transaction_args.clone().try_into_transaction_args()?;
         tx_args.try_fill_chain_id().await?;
         let withdraw_args: WithdrawArgs = self.clone().try_into()?;
 
+        if let TransactionSigner::Unsigned { .. } = tx_args.signer {
+            let call: withdraw4Call = withdraw_args.into();
+            let params =
+                tx_args.try_into_write_contract_parameters(call, tx_args.orderbook_address)?;
+            return print_unsigned_transaction(&tx_args, params).await;
+        }
+
         info!("----- Withdraw tokens from Vault -----");
         withdraw_args
             .execute(tx_args, |status| {
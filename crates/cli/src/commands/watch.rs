@@ -0,0 +1,282 @@
+use crate::execute::Execute;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use futures::StreamExt;
+use rain_orderbook_app_settings::watcher::WatcherCfg;
+use rain_orderbook_common::raindex_client::RaindexClient;
+use rain_orderbook_common::watch::{WatchNotification, WatchNotifier, WatchWebhooks, WebhookRetry};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::{info, warn};
+
+/// Notifications waiting for delivery before new ones are dropped
+const DELIVERY_QUEUE_CAPACITY: usize = 1024;
+
+#[derive(Args, Clone)]
+pub struct Watch {
+    #[arg(
+        short = 's',
+        long,
+        help = "Path to the orderbook settings YAML with local-db-sync and watchers sections"
+    )]
+    settings_file: PathBuf,
+
+    #[arg(long, help = "Local DB path the sync loop writes to")]
+    local_db_path: PathBuf,
+
+    #[arg(
+        short = 'w',
+        long = "watcher",
+        help = "Only run these watchers (repeatable), defaults to every configured watcher"
+    )]
+    watchers: Vec<String>,
+
+    #[arg(
+        long,
+        default_value_t = 3,
+        help = "Attempts per webhook delivery before it is dropped"
+    )]
+    retry_attempts: usize,
+
+    #[arg(
+        long,
+        default_value_t = 500,
+        help = "Base delay between webhook retries, doubled after each attempt"
+    )]
+    retry_delay_ms: u64,
+
+    #[arg(
+        long,
+        default_value_t = 5000,
+        help = "Delay before retrying a webhook that responded 429"
+    )]
+    rate_limit_delay_ms: u64,
+
+    #[arg(
+        long,
+        default_value_t = 60,
+        help = "How often orders watched for order-unquotable are quoted"
+    )]
+    quote_interval_secs: u64,
+}
+
+impl Execute for Watch {
+    async fn execute(&self) -> Result<()> {
+        let yaml = std::fs::read_to_string(&self.settings_file)?;
+        let client = RaindexClient::new(vec![yaml], None, Some(self.local_db_path.clone())).await?;
+
+        let watchers = select_watchers(client.get_all_watchers()?, &self.watchers)?;
+        info!(
+            "Watching with {}",
+            watchers
+                .iter()
+                .map(|w| w.key.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let mut notifier = WatchNotifier::new(
+            watchers,
+            WebhookRetry {
+                max_attempts: self.retry_attempts,
+                base_delay_ms: self.retry_delay_ms,
+                rate_limit_delay_ms: self.rate_limit_delay_ms,
+            },
+        )?;
+
+        let mut events = client.subscribe_sync_events()?;
+        notifier.skip_history().await?;
+
+        // Deliveries retry and back off, so they run apart from the loop
+        // reading the sync events
+        let webhooks = notifier.webhooks();
+        let (queue, mut pending) = mpsc::channel(DELIVERY_QUEUE_CAPACITY);
+        let delivery = tokio::spawn(async move {
+            while let Some(notification) = pending.recv().await {
+                deliver(&webhooks, &notification).await;
+            }
+        });
+
+        let check_quotes = !notifier.quoted_orders().is_empty();
+        let mut ticker =
+            tokio::time::interval(Duration::from_secs(self.quote_interval_secs.max(1)));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            let notifications = tokio::select! {
+                event = events.next() => match event {
+                    Some(event) => match notifier.notifications_for_event(&event) {
+                        Ok(notifications) => notifications,
+                        Err(e) => {
+                            warn!(
+                                "Skipping sync event of transaction {} at block {}: {}",
+                                event.transaction_hash, event.block_number, e
+                            );
+                            vec![]
+                        }
+                    },
+                    None => {
+                        delivery.abort();
+                        return Err(anyhow!("Local DB sync stopped"));
+                    }
+                },
+                _ = ticker.tick(), if check_quotes => notifier.check_quotes(&client).await,
+                _ = tokio::signal::ctrl_c() => {
+                    info!("Stopping watchers");
+                    delivery.abort();
+                    return Ok(());
+                }
+            };
+
+            for notification in notifications {
+                enqueue(&queue, notification)?;
+            }
+        }
+    }
+}
+
+/// Queues a notification for delivery, dropping it when the webhooks are too
+/// far behind
+fn enqueue(queue: &mpsc::Sender<WatchNotification>, notification: WatchNotification) -> Result<()> {
+    match queue.try_send(notification) {
+        Ok(()) => Ok(()),
+        Err(TrySendError::Full(notification)) => {
+            warn!(
+                "Delivery queue is full, dropping notification for watcher {}",
+                notification.watcher
+            );
+            Ok(())
+        }
+        Err(TrySendError::Closed(_)) => Err(anyhow!("Webhook delivery stopped")),
+    }
+}
+
+/// A webhook that keeps failing must not stop the other watchers
+async fn deliver(webhooks: &WatchWebhooks, notification: &WatchNotification) {
+    match webhooks.send(notification).await {
+        Ok(()) => info!(
+            "Notified watcher {}: {}",
+            notification.watcher,
+            serde_json::to_string(&notification.alert).unwrap_or_default()
+        ),
+        Err(e) => warn!("Failed to notify watcher {}: {}", notification.watcher, e),
+    }
+}
+
+fn select_watchers(
+    mut all: HashMap<String, WatcherCfg>,
+    names: &[String],
+) -> Result<Vec<WatcherCfg>> {
+    if all.is_empty() {
+        return Err(anyhow!("No watchers are configured in the settings"));
+    }
+    if names.is_empty() {
+        let mut watchers: Vec<WatcherCfg> = all.into_values().collect();
+        watchers.sort_by(|a, b| a.key.cmp(&b.key));
+        return Ok(watchers);
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            all.remove(name)
+                .ok_or_else(|| anyhow!("Watcher {} is not configured", name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rain_orderbook_common::watch::WatchAlert;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        watch: Watch,
+    }
+
+    fn watchers(keys: &[&str]) -> HashMap<String, WatcherCfg> {
+        keys.iter()
+            .map(|key| {
+                (
+                    key.to_string(),
+                    WatcherCfg {
+                        key: key.to_string(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_watchers() {
+        let all = select_watchers(watchers(&["b", "a"]), &[]).unwrap();
+        assert_eq!(
+            all.iter().map(|w| w.key.as_str()).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        let some = select_watchers(watchers(&["a", "b"]), &["b".to_string()]).unwrap();
+        assert_eq!(some.len(), 1);
+        assert_eq!(some[0].key, "b");
+
+        let err = select_watchers(watchers(&["a"]), &["c".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Watcher c is not configured");
+
+        let err = select_watchers(HashMap::new(), &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No watchers are configured in the settings"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_drops_when_full() {
+        let notification = || WatchNotification {
+            watcher: "alerts".to_string(),
+            chain_id: 1,
+            orderbook: Default::default(),
+            alert: WatchAlert::OrderUnquotable {
+                order_hash: Default::default(),
+                errors: vec![],
+            },
+        };
+        let (queue, mut pending) = mpsc::channel(1);
+
+        enqueue(&queue, notification()).unwrap();
+        enqueue(&queue, notification()).unwrap();
+        assert_eq!(pending.recv().await, Some(notification()));
+        assert!(pending.try_recv().is_err());
+
+        drop(pending);
+        let err = enqueue(&queue, notification()).unwrap_err();
+        assert_eq!(err.to_string(), "Webhook delivery stopped");
+    }
+
+    #[test]
+    fn test_watch_args() {
+        let cli = Cli::parse_from([
+            "watch",
+            "-s",
+            "settings.yaml",
+            "--local-db-path",
+            "local.db",
+            "-w",
+            "alerts",
+            "--watcher",
+            "vaults",
+            "--retry-attempts",
+            "5",
+        ]);
+        assert_eq!(cli.watch.settings_file, PathBuf::from("settings.yaml"));
+        assert_eq!(cli.watch.local_db_path, PathBuf::from("local.db"));
+        assert_eq!(cli.watch.watchers, vec!["alerts", "vaults"]);
+        assert_eq!(cli.watch.retry_attempts, 5);
+        assert_eq!(cli.watch.retry_delay_ms, 500);
+        assert_eq!(cli.watch.quote_interval_secs, 60);
+    }
+}
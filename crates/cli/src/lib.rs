@@ -1,4 +1,6 @@
-use crate::commands::{Chart, Order, Subgraph, Trade, Vault, Words};
+use crate::commands::{
+    Chart, Clear, Fuzz, Multicall, Order, Registry, Subgraph, Trade, UnitTest, Vault, Watch, Words,
+};
 use crate::execute::Execute;
 use anyhow::Result;
 use clap::Subcommand;
@@ -26,12 +28,28 @@ pub enum Orderbook {
     #[command(subcommand)]
     Subgraph(Subgraph),
 
+    #[command(subcommand)]
+    Clear(Clear),
+
+    #[command(subcommand)]
+    Registry(Registry),
+
     Chart(Chart),
 
     Quote(Quoter),
 
     Words(Words),
 
+    Watch(Watch),
+
+    #[command(about = "Run dotrain unit tests from *.test.rain files")]
+    Test(UnitTest),
+
+    #[command(about = "Fuzz scenarios and check their invariants")]
+    Fuzz(Fuzz),
+
+    Multicall(Multicall),
+
     #[command(name = "local-db", subcommand)]
     LocalDb(LocalDbCommands),
 }
@@ -45,7 +63,13 @@ impl Orderbook {
             Orderbook::Chart(chart) => chart.execute().await,
             Orderbook::Quote(quote) => quote.execute().await,
             Orderbook::Subgraph(subgraph) => subgraph.execute().await,
+            Orderbook::Clear(clear) => clear.execute().await,
+            Orderbook::Registry(registry) => registry.execute().await,
             Orderbook::Words(words) => words.execute().await,
+            Orderbook::Watch(watch) => watch.execute().await,
+            Orderbook::Test(test) => test.execute().await,
+            Orderbook::Fuzz(fuzz) => fuzz.execute().await,
+            Orderbook::Multicall(multicall) => multicall.execute().await,
             Orderbook::LocalDb(local_db) => local_db.execute().await,
         }
     }
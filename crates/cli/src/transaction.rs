@@ -1,12 +1,30 @@
 use alloy::primitives::Address;
+use alloy::sol_types::SolCall;
+use alloy_ethers_typecast::WriteContractParameters;
+use anyhow::Result;
 use clap::Args;
-use rain_orderbook_common::transaction::TransactionArgs;
+use rain_orderbook_common::transaction::{TransactionArgs, TransactionSigner};
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 pub struct CliTransactionArgs {
     #[arg(short, long, help = "Orderbook contract address")]
     pub orderbook_address: Address,
 
+    #[arg(short, long, help = "Chain ID of the network")]
+    pub chain_id: Option<u64>,
+
+    #[arg(short, long, help = "RPC URLs", num_args = 1..)]
+    pub rpcs: Vec<String>,
+
+    #[clap(flatten)]
+    pub signer: CliSignerArgs,
+}
+
+/// How transactions are signed and priced, for commands that take the
+/// orderbook and network from elsewhere
+#[derive(Args, Clone)]
+pub struct CliSignerArgs {
     #[arg(
         short,
         long,
@@ -15,32 +33,121 @@ pub struct CliTransactionArgs {
     )]
     pub derivation_index: Option<usize>,
 
-    #[arg(short, long, help = "Chain ID of the network")]
-    pub chain_id: Option<u64>,
-
-    #[arg(short, long, help = "RPC URLs", num_args = 1..)]
-    pub rpcs: Vec<String>,
-
     #[arg(short = 'p', long, help = "Max priority fee per gas (in wei)")]
     pub max_priority_fee_per_gas: Option<u128>,
 
     #[arg(short, long, help = "Max fee per gas (in wei)")]
     pub max_fee_per_gas: Option<u128>,
+
+    #[arg(
+        long,
+        help = "Sign with the hex private key stored in this environment variable instead of a Ledger",
+        conflicts_with_all = ["keystore", "unsigned_from"]
+    )]
+    pub private_key_env: Option<String>,
+
+    #[arg(
+        long,
+        help = "Sign with this encrypted JSON keystore file instead of a Ledger",
+        conflicts_with = "unsigned_from"
+    )]
+    pub keystore: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Environment variable holding the keystore password, prompted for when not set",
+        requires = "keystore"
+    )]
+    pub keystore_password_env: Option<String>,
+
+    #[arg(
+        long,
+        help = "Print the populated unsigned EIP-1559 transaction for this sender instead of signing and sending it"
+    )]
+    pub unsigned_from: Option<Address>,
+}
+
+impl CliTransactionArgs {
+    /// Converts into [`TransactionArgs`], prompting for the keystore password
+    /// when it isn't available from the environment.
+    pub fn try_into_transaction_args(self) -> Result<TransactionArgs> {
+        self.signer
+            .try_into_transaction_args(self.orderbook_address, self.chain_id, self.rpcs)
+    }
 }
 
 impl From<CliTransactionArgs> for TransactionArgs {
     fn from(val: CliTransactionArgs) -> Self {
+        val.signer
+            .into_transaction_args(val.orderbook_address, val.chain_id, val.rpcs)
+    }
+}
+
+impl CliSignerArgs {
+    fn into_transaction_args(
+        self,
+        orderbook_address: Address,
+        chain_id: Option<u64>,
+        rpcs: Vec<String>,
+    ) -> TransactionArgs {
+        let signer = if let Some(from) = self.unsigned_from {
+            TransactionSigner::Unsigned { from }
+        } else if let Some(env_var) = self.private_key_env {
+            TransactionSigner::PrivateKeyEnv { env_var }
+        } else if let Some(path) = self.keystore {
+            TransactionSigner::Keystore {
+                path,
+                password: self
+                    .keystore_password_env
+                    .and_then(|env_var| std::env::var(env_var).ok()),
+            }
+        } else {
+            TransactionSigner::Ledger
+        };
+
         TransactionArgs {
-            orderbook_address: val.orderbook_address,
-            derivation_index: val.derivation_index,
-            chain_id: val.chain_id,
-            rpcs: val.rpcs,
-            max_priority_fee_per_gas: val.max_priority_fee_per_gas,
-            max_fee_per_gas: val.max_fee_per_gas,
+            orderbook_address,
+            derivation_index: self.derivation_index,
+            chain_id,
+            rpcs,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+            max_fee_per_gas: self.max_fee_per_gas,
+            signer,
+        }
+    }
+
+    /// Builds [`TransactionArgs`] for `orderbook_address` on the given
+    /// network, prompting for the keystore password when it isn't available
+    /// from the environment.
+    pub fn try_into_transaction_args(
+        self,
+        orderbook_address: Address,
+        chain_id: Option<u64>,
+        rpcs: Vec<String>,
+    ) -> Result<TransactionArgs> {
+        let mut args = self.into_transaction_args(orderbook_address, chain_id, rpcs);
+        if let TransactionSigner::Keystore { path, password } = &mut args.signer {
+            if password.is_none() {
+                *password = Some(rpassword::prompt_password(format!(
+                    "Password for keystore {}: ",
+                    path.display()
+                ))?);
+            }
         }
+        Ok(args)
     }
 }
 
+/// Populates `params` for external signing and prints the transaction as JSON.
+pub async fn print_unsigned_transaction<T: SolCall + Clone>(
+    tx_args: &TransactionArgs,
+    params: WriteContractParameters<T>,
+) -> Result<()> {
+    let tx = tx_args.try_populate_unsigned_transaction(&params).await?;
+    println!("{}", serde_json::to_string_pretty(&tx)?);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,11 +156,17 @@ mod tests {
     fn test_from_cli_transaction_args() {
         let cli_args = CliTransactionArgs {
             orderbook_address: Address::ZERO,
-            derivation_index: Some(1),
             chain_id: Some(2),
             rpcs: vec!["http://localhost:8545".to_string()],
-            max_priority_fee_per_gas: Some(100),
-            max_fee_per_gas: Some(1000),
+            signer: CliSignerArgs {
+                derivation_index: Some(1),
+                max_priority_fee_per_gas: Some(100),
+                max_fee_per_gas: Some(1000),
+                private_key_env: None,
+                keystore: None,
+                keystore_password_env: None,
+                unsigned_from: None,
+            },
         };
 
         let transaction_args: TransactionArgs = cli_args.into();
@@ -64,15 +177,22 @@ mod tests {
         assert_eq!(transaction_args.rpcs, vec!["http://localhost:8545"]);
         assert_eq!(transaction_args.max_priority_fee_per_gas, Some(100));
         assert_eq!(transaction_args.max_fee_per_gas, Some(1000));
+        assert_eq!(transaction_args.signer, TransactionSigner::Ledger);
 
         let orderbook_address = Address::random();
         let cli_args = CliTransactionArgs {
             orderbook_address,
-            derivation_index: None,
             chain_id: None,
             rpcs: vec!["http://localhost:8545".to_string()],
-            max_priority_fee_per_gas: None,
-            max_fee_per_gas: None,
+            signer: CliSignerArgs {
+                derivation_index: None,
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                private_key_env: Some("PRIVATE_KEY".to_string()),
+                keystore: None,
+                keystore_password_env: None,
+                unsigned_from: None,
+            },
         };
 
         let transaction_args: TransactionArgs = cli_args.into();
@@ -83,5 +203,55 @@ mod tests {
         assert_eq!(transaction_args.rpcs, vec!["http://localhost:8545"]);
         assert_eq!(transaction_args.max_priority_fee_per_gas, None);
         assert_eq!(transaction_args.max_fee_per_gas, None);
+        assert_eq!(
+            transaction_args.signer,
+            TransactionSigner::PrivateKeyEnv {
+                env_var: "PRIVATE_KEY".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_cli_transaction_args_keystore_and_unsigned() {
+        std::env::set_var("RAIN_CLI_TEST_KEYSTORE_PASSWORD", "secret");
+        let cli_args = CliTransactionArgs {
+            orderbook_address: Address::ZERO,
+            chain_id: None,
+            rpcs: vec!["http://localhost:8545".to_string()],
+            signer: CliSignerArgs {
+                derivation_index: None,
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: None,
+                private_key_env: None,
+                keystore: Some(PathBuf::from("keystore.json")),
+                keystore_password_env: Some("RAIN_CLI_TEST_KEYSTORE_PASSWORD".to_string()),
+                unsigned_from: None,
+            },
+        };
+
+        let transaction_args = cli_args.clone().try_into_transaction_args().unwrap();
+        assert_eq!(
+            transaction_args.signer,
+            TransactionSigner::Keystore {
+                path: PathBuf::from("keystore.json"),
+                password: Some("secret".to_string()),
+            }
+        );
+
+        let from = Address::random();
+        let transaction_args: TransactionArgs = CliTransactionArgs {
+            signer: CliSignerArgs {
+                keystore: None,
+                keystore_password_env: None,
+                unsigned_from: Some(from),
+                ..cli_args.signer
+            },
+            ..cli_args
+        }
+        .into();
+        assert_eq!(
+            transaction_args.signer,
+            TransactionSigner::Unsigned { from }
+        );
     }
 }
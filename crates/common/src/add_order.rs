@@ -373,11 +373,11 @@ impl AddOrderArgs {
         transaction_args: TransactionArgs,
         transaction_status_changed: S,
     ) -> Result<(), AddOrderArgsError> {
-        let (ledger_client, _) = transaction_args.clone().try_into_ledger_client().await?;
+        let (client, _) = transaction_args.clone().try_into_signing_client().await?;
 
         let params = self.get_add_order_call_parameters(transaction_args).await?;
 
-        WriteTransaction::new(ledger_client, params, 4, transaction_status_changed)
+        WriteTransaction::new(client, params, 4, transaction_status_changed)
             .execute()
             .await?;
 
@@ -403,8 +403,7 @@ impl AddOrderArgs {
         let from_address = if let Some(v) = from {
             v.0 .0
         } else {
-            let (_, Address(FixedBytes(address))) =
-                transaction_args.clone().try_into_ledger_client().await?;
+            let Address(FixedBytes(address)) = transaction_args.try_get_sender().await?;
             address
         };
 
@@ -687,6 +686,7 @@ price: 2e18;
             bindings: HashMap::new(),
             runs: None,
             blocks: None,
+            invariants: vec![],
             rainlang: rainlang_arc.clone(),
         };
         let token1 = TokenCfg {
@@ -812,6 +812,7 @@ _ _: 0 0;
             bindings: HashMap::new(),
             runs: None,
             blocks: None,
+            invariants: vec![],
             rainlang: rainlang_arc.clone(),
         };
         let token1 = TokenCfg {
@@ -978,6 +979,7 @@ _ _: 0 0;
             bindings: HashMap::new(),
             runs: None,
             blocks: None,
+            invariants: vec![],
             rainlang: rainlang_arc.clone(),
         };
         let token1 = TokenCfg {
@@ -1324,6 +1326,7 @@ _ _: 16 52;
             bindings: HashMap::new(),
             runs: None,
             blocks: None,
+            invariants: vec![],
             rainlang: rainlang_arc.clone(),
         };
         let token1 = TokenCfg {
@@ -0,0 +1,387 @@
+use crate::raindex_client::RaindexError;
+use crate::take_orders::TakeOrderCandidate;
+use crate::utils::float::cmp_float;
+use alloy::primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy::sol_types::{SolCall, SolValue};
+use rain_math_float::Float;
+use rain_orderbook_bindings::IRaindexV6::{clear3Call, ClearConfigV2};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::ops::{Div, Mul, Sub};
+
+/// A pair of resting orders whose quoted ratios cross, together with the
+/// amounts `clear3` is expected to move and ready-to-send calldata.
+#[derive(Clone, Debug)]
+pub struct ClearOpportunity {
+    pub orderbook: Address,
+    pub alice_order_hash: B256,
+    pub bob_order_hash: B256,
+    pub alice: TakeOrderCandidate,
+    pub bob: TakeOrderCandidate,
+    /// Amount of alice's input token that alice receives.
+    pub alice_input: Float,
+    /// Amount of alice's output token that leaves alice's vault.
+    pub alice_output: Float,
+    /// Amount of bob's input token that bob receives.
+    pub bob_input: Float,
+    /// Amount of bob's output token that leaves bob's vault.
+    pub bob_output: Float,
+    /// Bounty paid in alice's output token to the clearer's alice bounty vault.
+    pub alice_bounty: Float,
+    /// Bounty paid in bob's output token to the clearer's bob bounty vault.
+    pub bob_bounty: Float,
+    /// `alice.ratio * bob.ratio`. Orders cross when this is at most one; the
+    /// lower it is the wider the spread captured by the clearer.
+    pub ratio_product: Float,
+    pub clear_config: ClearConfigV2,
+    pub calldata: Bytes,
+}
+
+impl ClearOpportunity {
+    pub fn alice_bounty_token(&self) -> Address {
+        self.alice.order.validOutputs[self.alice.output_io_index as usize].token
+    }
+
+    pub fn bob_bounty_token(&self) -> Address {
+        self.bob.order.validOutputs[self.bob.output_io_index as usize].token
+    }
+}
+
+fn input_token(candidate: &TakeOrderCandidate) -> Address {
+    candidate.order.validInputs[candidate.input_io_index as usize].token
+}
+
+fn output_token(candidate: &TakeOrderCandidate) -> Address {
+    candidate.order.validOutputs[candidate.output_io_index as usize].token
+}
+
+/// Mirrors the token checks `clear3` performs before any evaluation.
+fn is_clearable_pair(alice: &TakeOrderCandidate, bob: &TakeOrderCandidate) -> bool {
+    alice.orderbook == bob.orderbook
+        && alice.order.owner != bob.order.owner
+        && output_token(alice) == input_token(bob)
+        && input_token(alice) == output_token(bob)
+}
+
+/// Caps one side of the clear by the counterparty's output, exactly as
+/// `calculateClearStateAlice` does on-chain. Returns `(input, output)`.
+fn clamp_side(
+    max_output: Float,
+    ratio: Float,
+    counterparty_max_output: Float,
+) -> Result<(Float, Float), RaindexError> {
+    let input = max_output.mul(ratio)?;
+    if input.gt(counterparty_max_output)? {
+        let input = counterparty_max_output;
+        let output = input.div(ratio)?;
+        Ok((input, output))
+    } else {
+        Ok((input, max_output))
+    }
+}
+
+/// Evaluates whether `alice` and `bob` can be cleared against each other and,
+/// if so, how much each side moves and what bounty is left for the clearer.
+///
+/// Returns `None` for pairs the contract would reject (mismatched tokens,
+/// same owner, different orderbooks), pairs whose ratios don't cross and
+/// pairs that would leave no bounty on either side.
+pub fn evaluate_clear(
+    alice: &TakeOrderCandidate,
+    bob: &TakeOrderCandidate,
+    alice_bounty_vault_id: B256,
+    bob_bounty_vault_id: B256,
+) -> Result<Option<ClearOpportunity>, RaindexError> {
+    if !is_clearable_pair(alice, bob) {
+        return Ok(None);
+    }
+
+    let zero = Float::zero()?;
+    let one = Float::parse("1".to_string())?;
+
+    let ratio_product = alice.ratio.mul(bob.ratio)?;
+    if ratio_product.gt(one)? {
+        return Ok(None);
+    }
+
+    let (alice_input, alice_output) = clamp_side(alice.max_output, alice.ratio, bob.max_output)?;
+    let (bob_input, bob_output) = clamp_side(bob.max_output, bob.ratio, alice.max_output)?;
+
+    let alice_bounty = alice_output.sub(bob_input)?;
+    let bob_bounty = bob_output.sub(alice_input)?;
+
+    if alice_bounty.lt(zero)? || bob_bounty.lt(zero)? {
+        return Ok(None);
+    }
+    if !alice_bounty.gt(zero)? && !bob_bounty.gt(zero)? {
+        return Ok(None);
+    }
+
+    let clear_config = ClearConfigV2 {
+        aliceInputIOIndex: U256::from(alice.input_io_index),
+        aliceOutputIOIndex: U256::from(alice.output_io_index),
+        bobInputIOIndex: U256::from(bob.input_io_index),
+        bobOutputIOIndex: U256::from(bob.output_io_index),
+        aliceBountyVaultId: alice_bounty_vault_id,
+        bobBountyVaultId: bob_bounty_vault_id,
+    };
+
+    let calldata = Bytes::copy_from_slice(
+        &clear3Call {
+            aliceOrder: alice.order.clone(),
+            bobOrder: bob.order.clone(),
+            clearConfig: clear_config.clone(),
+            aliceSignedContext: alice.signed_context.clone(),
+            bobSignedContext: bob.signed_context.clone(),
+        }
+        .abi_encode(),
+    );
+
+    Ok(Some(ClearOpportunity {
+        orderbook: alice.orderbook,
+        alice_order_hash: keccak256(alice.order.abi_encode()),
+        bob_order_hash: keccak256(bob.order.abi_encode()),
+        alice: alice.clone(),
+        bob: bob.clone(),
+        alice_input,
+        alice_output,
+        bob_input,
+        bob_output,
+        alice_bounty,
+        bob_bounty,
+        ratio_product,
+        clear_config,
+        calldata,
+    }))
+}
+
+/// Evaluates every unordered pair of sides and returns the crossing ones,
+/// most crossed (lowest ratio product) first.
+///
+/// `clear3` is symmetric in alice and bob, so each pair is only evaluated once.
+pub fn match_clear_sides(
+    sides: &[TakeOrderCandidate],
+    alice_bounty_vault_id: B256,
+    bob_bounty_vault_id: B256,
+) -> Result<Vec<ClearOpportunity>, RaindexError> {
+    let mut opportunities = vec![];
+    for (i, alice) in sides.iter().enumerate() {
+        for bob in &sides[i + 1..] {
+            if let Some(opportunity) =
+                evaluate_clear(alice, bob, alice_bounty_vault_id, bob_bounty_vault_id)?
+            {
+                opportunities.push(opportunity);
+            }
+        }
+    }
+    rank_opportunities(&mut opportunities)?;
+    Ok(opportunities)
+}
+
+fn rank_opportunities(opportunities: &mut [ClearOpportunity]) -> Result<(), RaindexError> {
+    let comparison_error: RefCell<Option<RaindexError>> = RefCell::new(None);
+    opportunities.sort_by(|a, b| {
+        if comparison_error.borrow().is_some() {
+            return Ordering::Equal;
+        }
+        match cmp_float(&a.ratio_product, &b.ratio_product) {
+            Ok(ord) => ord,
+            Err(e) => {
+                *comparison_error.borrow_mut() = Some(e.into());
+                Ordering::Equal
+            }
+        }
+    });
+    if let Some(e) = comparison_error.into_inner() {
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(not(target_family = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::orders::make_basic_order;
+
+    const ORDERBOOK: Address = Address::repeat_byte(0xAA);
+
+    fn f(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn make_side(
+        owner: u8,
+        input_token: Address,
+        output_token: Address,
+        max_output: &str,
+        ratio: &str,
+    ) -> TakeOrderCandidate {
+        let mut order = make_basic_order(input_token, output_token);
+        order.owner = Address::repeat_byte(owner);
+        TakeOrderCandidate {
+            orderbook: ORDERBOOK,
+            order,
+            input_io_index: 0,
+            output_io_index: 0,
+            max_output: f(max_output),
+            ratio: f(ratio),
+            signed_context: vec![],
+        }
+    }
+
+    fn tokens() -> (Address, Address) {
+        (Address::repeat_byte(4), Address::repeat_byte(5))
+    }
+
+    #[test]
+    fn test_evaluate_clear_crossing_orders() {
+        let (a, b) = tokens();
+        // Alice sells 10 B for A at 0.5 A per B, bob sells 10 A for B at 1.5 B per A.
+        let alice = make_side(1, a, b, "10", "0.5");
+        let bob = make_side(2, b, a, "10", "1.5");
+
+        let opportunity = evaluate_clear(
+            &alice,
+            &bob,
+            B256::from(U256::from(7)),
+            B256::from(U256::from(8)),
+        )
+        .unwrap()
+        .expect("orders cross");
+
+        assert!(opportunity.alice_input.eq(f("5")).unwrap());
+        assert!(opportunity.alice_output.eq(f("10")).unwrap());
+        // Bob would want 15 B but alice only offers 10, so bob is capped.
+        assert!(opportunity.bob_input.eq(f("10")).unwrap());
+        assert!(opportunity
+            .bob_output
+            .eq(f("10").div(f("1.5")).unwrap())
+            .unwrap());
+        assert!(opportunity.alice_bounty.eq(f("0")).unwrap());
+        assert!(opportunity
+            .bob_bounty
+            .eq(f("10").div(f("1.5")).unwrap().sub(f("5")).unwrap())
+            .unwrap());
+        assert!(opportunity.ratio_product.eq(f("0.75")).unwrap());
+        assert_eq!(opportunity.alice_bounty_token(), b);
+        assert_eq!(opportunity.bob_bounty_token(), a);
+        assert_eq!(
+            opportunity.clear_config.aliceBountyVaultId,
+            B256::from(U256::from(7))
+        );
+        assert_eq!(
+            opportunity.clear_config.bobBountyVaultId,
+            B256::from(U256::from(8))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_clear_calldata_decodes() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "0.5");
+        let bob = make_side(2, b, a, "4", "1");
+
+        let opportunity = evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .unwrap();
+        let decoded = clear3Call::abi_decode(&opportunity.calldata).unwrap();
+
+        assert_eq!(decoded.aliceOrder, alice.order);
+        assert_eq!(decoded.bobOrder, bob.order);
+        assert_eq!(decoded.clearConfig, opportunity.clear_config);
+        assert_eq!(
+            opportunity.alice_order_hash,
+            keccak256(alice.order.abi_encode())
+        );
+    }
+
+    #[test]
+    fn test_evaluate_clear_not_crossing() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "2");
+        let bob = make_side(2, b, a, "10", "0.6");
+
+        assert!(evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_evaluate_clear_exact_cross_has_no_bounty() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "2");
+        let bob = make_side(2, b, a, "20", "0.5");
+
+        assert!(evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_evaluate_clear_same_owner() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "0.5");
+        let bob = make_side(1, b, a, "10", "0.5");
+
+        assert!(evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_evaluate_clear_token_mismatch() {
+        let (a, b) = tokens();
+        let c = Address::repeat_byte(6);
+        let alice = make_side(1, a, b, "10", "0.5");
+        let bob = make_side(2, c, a, "10", "0.5");
+
+        assert!(evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_evaluate_clear_different_orderbooks() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "0.5");
+        let mut bob = make_side(2, b, a, "10", "0.5");
+        bob.orderbook = Address::repeat_byte(0xBB);
+
+        assert!(evaluate_clear(&alice, &bob, B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_match_clear_sides_ranks_by_ratio_product() {
+        let (a, b) = tokens();
+        let alice = make_side(1, a, b, "10", "0.5");
+        let wide = make_side(2, b, a, "10", "0.5");
+        let narrow = make_side(3, b, a, "10", "1.5");
+        let same_direction = make_side(4, a, b, "10", "0.1");
+
+        let opportunities = match_clear_sides(
+            &[alice, narrow, wide.clone(), same_direction.clone()],
+            B256::ZERO,
+            B256::ZERO,
+        )
+        .unwrap();
+
+        // Both A->B orders cross with both B->A orders; same-direction pairs never do.
+        assert_eq!(opportunities.len(), 4);
+        for pair in opportunities.windows(2) {
+            assert!(pair[0].ratio_product.lte(pair[1].ratio_product).unwrap());
+        }
+        assert_eq!(opportunities[0].alice.order.owner, wide.order.owner);
+        assert_eq!(opportunities[0].bob.order.owner, same_direction.order.owner);
+        assert!(opportunities[0].ratio_product.eq(f("0.05")).unwrap());
+    }
+
+    #[test]
+    fn test_match_clear_sides_empty() {
+        assert!(match_clear_sides(&[], B256::ZERO, B256::ZERO)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+pub mod matching;
+
+pub use matching::{evaluate_clear, match_clear_sides, ClearOpportunity};
+
+use crate::raindex_client::order_quotes::{get_order_quotes_batch, RaindexOrderQuote};
+use crate::raindex_client::orders::RaindexOrder;
+use crate::raindex_client::RaindexError;
+use crate::take_orders::candidates::{
+    fetch_oracle_for_pair, get_orderbook_address, has_capacity, indices_in_bounds,
+};
+use crate::take_orders::TakeOrderCandidate;
+use alloy::primitives::{Address, B256};
+use rain_orderbook_bindings::IRaindexV6::{OrderV4, SignedContextV1};
+
+/// Batch-quotes every IO pair of `orders` and returns every pair of resting
+/// orders that can be settled against each other with `clear3`, ranked from
+/// most to least crossed.
+///
+/// All orders must share a chain. Bounties are credited to the caller's
+/// `alice_bounty_vault_id` / `bob_bounty_vault_id` vaults.
+pub async fn find_clear_opportunities(
+    orders: &[RaindexOrder],
+    alice_bounty_vault_id: B256,
+    bob_bounty_vault_id: B256,
+    block_number: Option<u64>,
+    chunk_size: Option<u32>,
+) -> Result<Vec<ClearOpportunity>, RaindexError> {
+    let all_quotes = get_order_quotes_batch(orders, block_number, chunk_size).await?;
+
+    let mut sides = vec![];
+    for (order, quotes) in orders.iter().zip(all_quotes) {
+        let order_v4: OrderV4 = order.try_into()?;
+        let orderbook = get_orderbook_address(order)?;
+        let oracle_url = order.oracle_url();
+
+        for quote in &quotes {
+            if !is_quoted_side(&order_v4, quote)? {
+                continue;
+            }
+            let signed_context = match &oracle_url {
+                Some(url) => {
+                    fetch_oracle_for_pair(
+                        url,
+                        &order_v4,
+                        quote.pair.input_index,
+                        quote.pair.output_index,
+                        // The counterparty order is only known after matching. As with
+                        // take orders, the oracle only prices the IO pair.
+                        Address::ZERO,
+                    )
+                    .await?
+                }
+                None => vec![],
+            };
+            if let Some(side) = build_clear_side(orderbook, &order_v4, quote, signed_context) {
+                sides.push(side);
+            }
+        }
+    }
+
+    match_clear_sides(&sides, alice_bounty_vault_id, bob_bounty_vault_id)
+}
+
+fn is_quoted_side(order: &OrderV4, quote: &RaindexOrderQuote) -> Result<bool, RaindexError> {
+    match (quote.success, &quote.data) {
+        (true, Some(data)) => Ok(indices_in_bounds(
+            order,
+            quote.pair.input_index,
+            quote.pair.output_index,
+        ) && has_capacity(data)?),
+        _ => Ok(false),
+    }
+}
+
+fn build_clear_side(
+    orderbook: Address,
+    order: &OrderV4,
+    quote: &RaindexOrderQuote,
+    signed_context: Vec<SignedContextV1>,
+) -> Option<TakeOrderCandidate> {
+    let data = quote.data.as_ref()?;
+    Some(TakeOrderCandidate {
+        orderbook,
+        order: order.clone(),
+        input_io_index: quote.pair.input_index,
+        output_io_index: quote.pair.output_index,
+        max_output: data.max_output,
+        ratio: data.ratio,
+        signed_context,
+    })
+}
+
+#[cfg(test)]
+#[cfg(not(target_family = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::orders::make_basic_order;
+    use crate::test_helpers::quotes::{make_quote, make_quote_value};
+    use rain_math_float::Float;
+
+    #[test]
+    fn test_is_quoted_side_filters_unusable_quotes() {
+        let order = make_basic_order(Address::repeat_byte(4), Address::repeat_byte(5));
+        let one = Float::parse("1".to_string()).unwrap();
+        let zero = Float::zero().unwrap();
+
+        let usable = make_quote(0, 0, Some(make_quote_value(one, one, one)), true);
+        let failed = make_quote(0, 0, None, false);
+        let empty = make_quote(0, 0, Some(make_quote_value(zero, zero, one)), true);
+        let out_of_bounds = make_quote(3, 0, Some(make_quote_value(one, one, one)), true);
+
+        assert!(is_quoted_side(&order, &usable).unwrap());
+        assert!(!is_quoted_side(&order, &failed).unwrap());
+        assert!(!is_quoted_side(&order, &empty).unwrap());
+        assert!(!is_quoted_side(&order, &out_of_bounds).unwrap());
+    }
+
+    #[test]
+    fn test_build_clear_side_uses_quote_pair() {
+        let order = make_basic_order(Address::repeat_byte(4), Address::repeat_byte(5));
+        let two = Float::parse("2".to_string()).unwrap();
+        let half = Float::parse("0.5".to_string()).unwrap();
+        let quote = make_quote(0, 0, Some(make_quote_value(two, two, half)), true);
+
+        let side = build_clear_side(Address::repeat_byte(0xAA), &order, &quote, vec![]).unwrap();
+
+        assert_eq!(side.orderbook, Address::repeat_byte(0xAA));
+        assert_eq!(side.input_io_index, 0);
+        assert_eq!(side.output_io_index, 0);
+        assert!(side.max_output.eq(two).unwrap());
+        assert!(side.ratio.eq(half).unwrap());
+    }
+}
@@ -0,0 +1,890 @@
+//! Native counterpart of the wasm GUI deployment flow. Answers for a GUI
+//! deployment (select-tokens, field values and deposits) are validated with
+//! the same rules as the webapp and turned into approval, deposit and
+//! addOrder calldata. The order carries the same GUI state meta as orders
+//! deployed from the webapp, so they can be restored and edited there.
+
+pub mod validation;
+
+use crate::add_order::{AddOrderArgs, AddOrderArgsError};
+use crate::deposit::DepositArgs;
+use crate::dotrain_order::{DotrainOrder, DotrainOrderError};
+use crate::erc20::{self, ExtendedTokenInfo, ERC20};
+use alloy::hex::FromHexError;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use rain_math_float::{Float, FloatError};
+use rain_metaboard_subgraph::metaboard_client::{
+    MetaboardSubgraphClient, MetaboardSubgraphClientError,
+};
+use rain_metaboard_subgraph::types::metas::BigInt as MetaBigInt;
+use rain_metadata::types::dotrain::{
+    gui_state_v1::{DotrainGuiStateV1, ShortenedTokenCfg, ValueCfg},
+    source_v1::DotrainSourceV1,
+};
+use rain_metadata::RainMetaDocumentV1Item;
+use rain_orderbook_app_settings::{
+    deployment::DeploymentCfg,
+    gui::{GuiCfg, GuiDeploymentCfg, GuiFieldDefinitionCfg},
+    network::NetworkCfg,
+    order::OrderCfg,
+    token::TokenCfg,
+    yaml::{context::ContextProfile, YamlError},
+};
+use rain_orderbook_bindings::{
+    IRaindexV6::deposit4Call, OrderBook::multicallCall, IERC20::approveCall,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use thiserror::Error;
+use validation::GuiValidationError;
+
+#[derive(Error, Debug)]
+pub enum DeploymentBuilderError {
+    #[error("Gui config not found")]
+    GuiConfigNotFound,
+    #[error("Deployment not found: {0}")]
+    DeploymentNotFound(String),
+    #[error("Field binding not found: {0}")]
+    FieldBindingNotFound(String),
+    #[error("Missing field value: {0}")]
+    FieldValueNotSet(String),
+    #[error("Deposit token not found in gui config: {0}")]
+    DepositTokenNotFound(String),
+    #[error("Deposit amount cannot be an empty string")]
+    DepositAmountCannotBeEmpty,
+    #[error("Select tokens not set")]
+    SelectTokensNotSet,
+    #[error("Token not in select tokens: {0}")]
+    TokenNotInSelectTokens(String),
+    #[error("Token must be selected: {0}")]
+    TokenMustBeSelected(String),
+    #[error("Orderbook not found")]
+    OrderbookNotFound,
+    #[error("Vault id not found for output index: {0}")]
+    VaultIdNotFound(String),
+    #[error(transparent)]
+    DotrainOrderError(#[from] DotrainOrderError),
+    #[error(transparent)]
+    YamlError(#[from] YamlError),
+    #[error(transparent)]
+    ValidationError(#[from] GuiValidationError),
+    #[error(transparent)]
+    FloatError(#[from] FloatError),
+    #[error(transparent)]
+    FromHexError(#[from] FromHexError),
+    #[error(transparent)]
+    ERC20Error(#[from] erc20::Error),
+    #[error(transparent)]
+    AddOrderArgsError(#[from] AddOrderArgsError),
+    #[error(transparent)]
+    SerdeYamlError(#[from] serde_yaml::Error),
+    #[error(transparent)]
+    RainMetadataError(#[from] rain_metadata::Error),
+    #[error("No address found in metaboard subgraph")]
+    NoAddressInMetaboardSubgraph,
+    #[error(transparent)]
+    MetaboardSubgraphClientError(#[from] MetaboardSubgraphClientError),
+}
+
+/// Answers to a GUI deployment, keyed the same way as the `gui` section of
+/// the dotrain frontmatter:
+///
+/// ```yaml
+/// select-tokens:
+///   token1: 0x4200000000000000000000000000000000000042
+/// fields:
+///   max-price: 1500
+/// deposits:
+///   token1: 100
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DeploymentAnswers {
+    #[serde(default)]
+    pub select_tokens: BTreeMap<String, String>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub deposits: BTreeMap<String, String>,
+}
+
+impl DeploymentAnswers {
+    pub fn parse(yaml: &str) -> Result<Self, DeploymentBuilderError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentApproval {
+    pub token: Address,
+    pub symbol: String,
+    pub amount: U256,
+    pub calldata: Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentExternalCall {
+    pub to: Address,
+    pub calldata: Bytes,
+}
+
+/// Everything needed to deploy the order. `deposit_and_add_order` is the
+/// orderbook multicall of `add_order` followed by every deposit, to be sent
+/// once the approvals are mined. `emit_meta` publishes the order's dotrain to
+/// the metaboard, none when the metaboard already has it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeploymentCalldatas {
+    pub orderbook: Address,
+    pub chain_id: u32,
+    pub rpcs: Vec<String>,
+    pub approvals: Vec<DeploymentApproval>,
+    pub deposits: Vec<Bytes>,
+    pub add_order: Bytes,
+    pub deposit_and_add_order: Bytes,
+    pub emit_meta: Option<DeploymentExternalCall>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeploymentBuilder {
+    dotrain_order: DotrainOrder,
+    selected_deployment: String,
+    field_values: BTreeMap<String, String>,
+    deposits: BTreeMap<String, String>,
+}
+
+impl DeploymentBuilder {
+    pub async fn new(
+        dotrain: String,
+        settings: Option<Vec<String>>,
+        selected_deployment: String,
+    ) -> Result<Self, DeploymentBuilderError> {
+        let dotrain_order = DotrainOrder::create_with_profile(
+            dotrain,
+            settings,
+            ContextProfile::gui(selected_deployment.clone()),
+        )
+        .await?;
+
+        let keys = GuiCfg::parse_deployment_keys(dotrain_order.dotrain_yaml().documents)?;
+        if !keys.contains(&selected_deployment) {
+            return Err(DeploymentBuilderError::DeploymentNotFound(
+                selected_deployment,
+            ));
+        }
+
+        Ok(Self {
+            dotrain_order,
+            selected_deployment,
+            field_values: BTreeMap::new(),
+            deposits: BTreeMap::new(),
+        })
+    }
+
+    pub fn get_current_deployment(&self) -> Result<GuiDeploymentCfg, DeploymentBuilderError> {
+        let gui = self
+            .dotrain_order
+            .dotrain_yaml()
+            .get_gui(&self.selected_deployment)?
+            .ok_or(DeploymentBuilderError::GuiConfigNotFound)?;
+        gui.deployments
+            .into_iter()
+            .find(|(key, _)| key == &self.selected_deployment)
+            .map(|(_, deployment)| deployment)
+            .ok_or(DeploymentBuilderError::DeploymentNotFound(
+                self.selected_deployment.clone(),
+            ))
+    }
+
+    /// Applies every answer, select-tokens first as fields and deposits can
+    /// only be resolved once their tokens exist.
+    pub async fn apply_answers(
+        &mut self,
+        answers: &DeploymentAnswers,
+    ) -> Result<(), DeploymentBuilderError> {
+        for (key, address) in &answers.select_tokens {
+            self.set_select_token(key, Address::from_str(address)?)
+                .await?;
+        }
+        for (binding, value) in &answers.fields {
+            self.set_field_value(binding, value.clone())?;
+        }
+        for (token, amount) in &answers.deposits {
+            self.set_deposit(token, amount.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Adds `key` to the tokens of the deployment's network with its
+    /// metadata read from chain, replacing any previous selection
+    pub async fn set_select_token(
+        &mut self,
+        key: &str,
+        address: Address,
+    ) -> Result<(), DeploymentBuilderError> {
+        let documents = self.dotrain_order.dotrain_yaml().documents;
+        let select_tokens =
+            GuiCfg::parse_select_tokens(documents.clone(), &self.selected_deployment)?
+                .ok_or(DeploymentBuilderError::SelectTokensNotSet)?;
+        if !select_tokens.iter().any(|token| token.key == key) {
+            return Err(DeploymentBuilderError::TokenNotInSelectTokens(
+                key.to_string(),
+            ));
+        }
+
+        if TokenCfg::parse_from_yaml(documents.clone(), key, None).is_ok() {
+            TokenCfg::remove_record_from_yaml(self.dotrain_order.orderbook_yaml().documents, key)?;
+        }
+
+        let order_key =
+            DeploymentCfg::parse_order_key(documents.clone(), &self.selected_deployment)?;
+        let network_key = OrderCfg::parse_network_key(documents.clone(), &order_key)?;
+        let rpcs = NetworkCfg::parse_rpcs(documents, &network_key)?;
+
+        let token_info = ERC20::new(rpcs, address).token_info(None).await?;
+
+        TokenCfg::add_record_to_yaml(
+            self.dotrain_order.orderbook_yaml().documents,
+            key,
+            &network_key,
+            &address.to_string(),
+            Some(&token_info.decimals.to_string()),
+            Some(&token_info.name),
+            Some(&token_info.symbol),
+        )?;
+        Ok(())
+    }
+
+    fn get_field_definition(
+        &self,
+        binding: &str,
+    ) -> Result<GuiFieldDefinitionCfg, DeploymentBuilderError> {
+        self.get_current_deployment()?
+            .fields
+            .into_iter()
+            .find(|field| field.binding == binding)
+            .ok_or(DeploymentBuilderError::FieldBindingNotFound(
+                binding.to_string(),
+            ))
+    }
+
+    pub fn set_field_value(
+        &mut self,
+        binding: &str,
+        value: String,
+    ) -> Result<(), DeploymentBuilderError> {
+        let field_definition = self.get_field_definition(binding)?;
+        if let Some(validation) = &field_definition.validation {
+            validation::validate_field_value(&field_definition.name, &value, validation)?;
+        }
+        self.field_values.insert(binding.to_string(), value);
+        Ok(())
+    }
+
+    pub async fn set_deposit(
+        &mut self,
+        token: &str,
+        amount: String,
+    ) -> Result<(), DeploymentBuilderError> {
+        let deployment = self.get_current_deployment()?;
+        let gui_deposit = deployment
+            .deposits
+            .iter()
+            .find(|deposit| deposit.token.as_ref().is_some_and(|t| t.key == token))
+            .ok_or(DeploymentBuilderError::DepositTokenNotFound(
+                token.to_string(),
+            ))?;
+
+        if amount.is_empty() {
+            return Err(DeploymentBuilderError::DepositAmountCannotBeEmpty);
+        }
+
+        if let Some(validation) = &gui_deposit.validation {
+            let token_cfg = self.dotrain_order.orderbook_yaml().get_token(token)?;
+            let token_info = ExtendedTokenInfo::from_token_cfg(&token_cfg).await?;
+            validation::validate_deposit_amount(&token_info.name, &amount, validation)?;
+        }
+
+        self.deposits.insert(token.to_string(), amount);
+        Ok(())
+    }
+
+    /// Falls back to each field's default, erroring on fields without one
+    fn check_field_values(&mut self) -> Result<(), DeploymentBuilderError> {
+        for field in self.get_current_deployment()?.fields {
+            if self.field_values.contains_key(&field.binding) {
+                continue;
+            }
+            match field.default {
+                Some(default) => self.set_field_value(&field.binding, default)?,
+                None => return Err(DeploymentBuilderError::FieldValueNotSet(field.name)),
+            }
+        }
+        Ok(())
+    }
+
+    fn check_select_tokens(&self) -> Result<(), DeploymentBuilderError> {
+        let select_tokens = GuiCfg::parse_select_tokens(
+            self.dotrain_order.dotrain_yaml().documents,
+            &self.selected_deployment,
+        )?;
+        for select_token in select_tokens.unwrap_or_default() {
+            if self
+                .dotrain_order
+                .orderbook_yaml()
+                .get_token(&select_token.key)
+                .is_err()
+            {
+                return Err(DeploymentBuilderError::TokenMustBeSelected(
+                    select_token.key,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks every answer is set, assigns random vault ids to the order's
+    /// IO without one and writes the field values into the scenario bindings
+    fn prepare_deployment(&mut self) -> Result<GuiDeploymentCfg, DeploymentBuilderError> {
+        self.check_select_tokens()?;
+        self.check_field_values()?;
+
+        let deployment = self.get_current_deployment()?;
+        self.dotrain_order
+            .dotrain_yaml()
+            .get_order(&deployment.deployment.order.key)?
+            .populate_vault_ids()?;
+        self.dotrain_order
+            .dotrain_yaml()
+            .get_scenario(&deployment.deployment.scenario.key)?
+            .update_bindings(
+                self.field_values
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<HashMap<_, _>>(),
+            )?;
+
+        self.get_current_deployment()
+    }
+
+    /// Deposits into the vault of every order output with a non-zero deposit
+    async fn deposit_args(
+        &self,
+        deployment: &GuiDeploymentCfg,
+    ) -> Result<Vec<(DepositArgs, String)>, DeploymentBuilderError> {
+        let mut deposit_args = Vec::new();
+        for (key, amount) in &self.deposits {
+            let amount = Float::parse(amount.clone())?;
+            if amount.eq(Float::parse("0".to_string())?)? {
+                continue;
+            }
+            let token = self.dotrain_order.orderbook_yaml().get_token(key)?;
+            let Some((index, output)) = deployment
+                .deployment
+                .order
+                .outputs
+                .iter()
+                .enumerate()
+                .find(|(_, output)| {
+                    output
+                        .token
+                        .as_ref()
+                        .is_some_and(|t| t.address == token.address)
+                })
+            else {
+                continue;
+            };
+            let vault_id = output
+                .vault_id
+                .ok_or(DeploymentBuilderError::VaultIdNotFound(index.to_string()))?;
+
+            let token_info = ExtendedTokenInfo::from_token_cfg(&token).await?;
+            deposit_args.push((
+                DepositArgs {
+                    token: token.address,
+                    vault_id: vault_id.into(),
+                    amount,
+                    decimals: token_info.decimals,
+                },
+                token_info.symbol,
+            ));
+        }
+        Ok(deposit_args)
+    }
+
+    /// Rainlang of the deployment's order with the field values bound
+    pub async fn compose_rainlang(&mut self) -> Result<String, DeploymentBuilderError> {
+        let deployment = self.prepare_deployment()?;
+        Ok(self
+            .dotrain_order
+            .compose_deployment_to_rainlang(deployment.deployment.key.clone())
+            .await?)
+    }
+
+    /// Generates the approvals, deposits and addOrder calldata for the
+    /// deployment. With an `owner`, approvals are only generated where the
+    /// current allowance differs from the deposit, like the webapp does.
+    pub async fn build(
+        &mut self,
+        owner: Option<Address>,
+    ) -> Result<DeploymentCalldatas, DeploymentBuilderError> {
+        let deployment = self.prepare_deployment()?;
+        let orderbook = deployment
+            .deployment
+            .order
+            .orderbook
+            .clone()
+            .ok_or(DeploymentBuilderError::OrderbookNotFound)?;
+        let rpcs = orderbook.network.rpcs.clone();
+
+        let mut approvals = Vec::new();
+        let mut deposits = Vec::new();
+        for (deposit_args, symbol) in self.deposit_args(&deployment).await? {
+            let amount = deposit_args
+                .amount
+                .to_fixed_decimal(deposit_args.decimals)?;
+            let needs_approval = match owner {
+                Some(owner) => {
+                    let allowance = ERC20::new(rpcs.clone(), deposit_args.token)
+                        .allowance(owner, orderbook.address)
+                        .await?;
+                    allowance != amount
+                }
+                None => true,
+            };
+            if needs_approval {
+                approvals.push(DeploymentApproval {
+                    token: deposit_args.token,
+                    symbol,
+                    amount,
+                    calldata: approveCall {
+                        spender: orderbook.address,
+                        amount,
+                    }
+                    .abi_encode()
+                    .into(),
+                });
+            }
+
+            let deposit_call = deposit4Call::try_from(deposit_args)?;
+            deposits.push(Bytes::from(deposit_call.abi_encode()));
+        }
+
+        let dotrain = self
+            .dotrain_order
+            .generate_dotrain_for_deployment(&deployment.deployment.key)?;
+        let gui_state = self.dotrain_gui_state(&deployment, &dotrain)?;
+        let subject = gui_state.dotrain_hash();
+        let add_order_args = AddOrderArgs::new_from_deployment(
+            dotrain,
+            deployment.deployment.as_ref().clone(),
+            Some(vec![RainMetaDocumentV1Item::try_from(gui_state)?]),
+        )
+        .await?;
+        let rpcs = rpcs.iter().map(|rpc| rpc.to_string()).collect::<Vec<_>>();
+        let add_order = Bytes::from(
+            add_order_args
+                .try_into_call(rpcs.clone())
+                .await?
+                .abi_encode(),
+        );
+
+        let mut calls = vec![add_order.clone()];
+        calls.extend(deposits.iter().cloned());
+        let deposit_and_add_order = Bytes::from(multicallCall { data: calls }.abi_encode());
+
+        let metaboard = self.get_metaboard_client(&deployment)?;
+        let emit_meta = if should_emit_meta_call(&metaboard, subject).await? {
+            let metaboard_address = *metaboard
+                .get_metaboard_addresses(None, None)
+                .await?
+                .first()
+                .ok_or(DeploymentBuilderError::NoAddressInMetaboardSubgraph)?;
+            add_order_args
+                .try_into_emit_meta_call()?
+                .map(|call| DeploymentExternalCall {
+                    to: metaboard_address,
+                    calldata: Bytes::from(call.abi_encode()),
+                })
+        } else {
+            None
+        };
+
+        Ok(DeploymentCalldatas {
+            orderbook: orderbook.address,
+            chain_id: orderbook.network.chain_id,
+            rpcs,
+            approvals,
+            deposits,
+            add_order,
+            deposit_and_add_order,
+            emit_meta,
+        })
+    }
+
+    /// GUI state stored in the order meta, the same the webapp stores to
+    /// restore a deployment
+    fn dotrain_gui_state(
+        &self,
+        deployment: &GuiDeploymentCfg,
+        dotrain: &str,
+    ) -> Result<DotrainGuiStateV1, DeploymentBuilderError> {
+        let order = &deployment.deployment.order;
+
+        let mut resolved = HashMap::new();
+        for io in order.inputs.iter().chain(order.outputs.iter()) {
+            if let Some(token) = &io.token {
+                resolved.insert(token.key.clone(), token.address);
+            }
+        }
+        let select_tokens = GuiCfg::parse_select_tokens(
+            self.dotrain_order.dotrain_yaml().documents,
+            &self.selected_deployment,
+        )?
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|select_token| {
+            resolved.get(&select_token.key).map(|address| {
+                (
+                    select_token.key,
+                    ShortenedTokenCfg {
+                        network: order.network.key.clone(),
+                        address: *address,
+                    },
+                )
+            })
+        })
+        .collect();
+
+        // Keyed by IO position so the webapp can put the vault IDs back
+        let mut vault_ids = BTreeMap::new();
+        for (i, input) in order.inputs.iter().enumerate() {
+            vault_ids.insert(
+                format!("input_{}", i),
+                input.vault_id.map(|v| format!("0x{:x}", v)),
+            );
+        }
+        for (i, output) in order.outputs.iter().enumerate() {
+            vault_ids.insert(
+                format!("output_{}", i),
+                output.vault_id.map(|v| format!("0x{:x}", v)),
+            );
+        }
+
+        let value = |id: &String, value: &String| ValueCfg {
+            id: id.clone(),
+            name: None,
+            value: value.clone(),
+        };
+        Ok(DotrainGuiStateV1 {
+            dotrain_hash: DotrainSourceV1(dotrain.to_string()).hash(),
+            field_values: self
+                .field_values
+                .iter()
+                .map(|(binding, v)| (binding.clone(), value(binding, v)))
+                .collect(),
+            deposits: self
+                .deposits
+                .iter()
+                .map(|(token, amount)| (token.clone(), value(token, amount)))
+                .collect(),
+            select_tokens,
+            vault_ids,
+            selected_deployment: self.selected_deployment.clone(),
+        })
+    }
+
+    fn get_metaboard_client(
+        &self,
+        deployment: &GuiDeploymentCfg,
+    ) -> Result<MetaboardSubgraphClient, DeploymentBuilderError> {
+        let metaboard = self
+            .dotrain_order
+            .orderbook_yaml()
+            .get_metaboard(&deployment.deployment.order.network.key)?;
+        Ok(MetaboardSubgraphClient::new(metaboard.url.clone()))
+    }
+}
+
+/// Whether the metaboard has no meta for the dotrain yet
+async fn should_emit_meta_call(
+    client: &MetaboardSubgraphClient,
+    subject: impl AsRef<[u8]>,
+) -> Result<bool, DeploymentBuilderError> {
+    match client
+        .get_metabytes_by_subject(&MetaBigInt(format!("0x{}", alloy::hex::encode(subject))))
+        .await
+    {
+        Ok(metas) => Ok(metas.is_empty()),
+        Err(MetaboardSubgraphClientError::Empty(_)) => Ok(true),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
+
+    fn dotrain() -> String {
+        format!(
+            r#"version: {version}
+gui:
+  name: Test gui
+  description: Test description
+  deployments:
+    flare:
+      name: Flare order name
+      description: Flare order description
+      deposits:
+        - token: token1
+          validation:
+            maximum: "100"
+      fields:
+        - binding: max-price
+          name: Max price
+          validation:
+            type: number
+            minimum: "1"
+        - binding: label
+          name: Label
+          default: order
+      select-tokens:
+        - key: token2
+networks:
+  flare:
+    rpcs:
+      - https://rpc.ankr.com/flare
+    chain-id: 14
+    currency: FLR
+rainlangs:
+  flare:
+    address: 0x1111111111111111111111111111111111111111
+    network: flare
+orderbooks:
+  flare:
+    address: 0xCEe8Cd002F151A536394E564b84076c41bBBcD4d
+    network: flare
+tokens:
+  token1:
+    address: 0x4200000000000000000000000000000000000042
+    network: flare
+    decimals: 18
+    label: Token 1
+    symbol: T1
+scenarios:
+  flare:
+    rainlang: flare
+orders:
+  flare:
+    rainlang: flare
+    orderbook: flare
+    inputs:
+      - token: token1
+    outputs:
+      - token: token1
+deployments:
+  flare:
+    scenario: flare
+    order: flare
+---
+#max-price !
+#label !
+#calculate-io
+_ _: 0 0;
+#handle-io
+:;
+#handle-add-order
+:;"#,
+            version = SpecVersion::current()
+        )
+    }
+
+    async fn builder() -> DeploymentBuilder {
+        DeploymentBuilder::new(dotrain(), None, "flare".to_string())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = DeploymentAnswers::parse(
+            r#"
+select-tokens:
+  token2: 0x4200000000000000000000000000000000000043
+fields:
+  max-price: 1500
+  flag: true
+deposits:
+  token1: "10.5"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            answers.select_tokens["token2"],
+            "0x4200000000000000000000000000000000000043"
+        );
+        assert_eq!(answers.fields["max-price"], "1500");
+        assert_eq!(answers.fields["flag"], "true");
+        assert_eq!(answers.deposits["token1"], "10.5");
+
+        assert_eq!(
+            DeploymentAnswers::parse("fields: {}").unwrap(),
+            DeploymentAnswers::default()
+        );
+        assert!(DeploymentAnswers::parse("deposit: {}").is_err());
+        assert!(DeploymentAnswers::parse("fields: [1]").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_new_missing_deployment() {
+        let err = DeploymentBuilder::new(dotrain(), None, "missing".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missing"));
+    }
+
+    #[tokio::test]
+    async fn test_set_field_value() {
+        let mut builder = builder().await;
+        builder
+            .set_field_value("max-price", "10".to_string())
+            .unwrap();
+        assert_eq!(builder.field_values["max-price"], "10");
+
+        let err = builder
+            .set_field_value("max-price", "0.5".to_string())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::ValidationError(GuiValidationError::BelowMinimum { .. })
+        ));
+
+        let err = builder
+            .set_field_value("missing", "1".to_string())
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::FieldBindingNotFound(binding) if binding == "missing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_field_values() {
+        let mut builder = builder().await;
+        let err = builder.check_field_values().unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::FieldValueNotSet(name) if name == "Max price"
+        ));
+
+        builder
+            .set_field_value("max-price", "10".to_string())
+            .unwrap();
+        builder.check_field_values().unwrap();
+        assert_eq!(builder.field_values["label"], "order");
+    }
+
+    #[tokio::test]
+    async fn test_set_deposit() {
+        let mut builder = builder().await;
+        builder
+            .set_deposit("token1", "100".to_string())
+            .await
+            .unwrap();
+        assert_eq!(builder.deposits["token1"], "100");
+
+        let err = builder
+            .set_deposit("token1", "101".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::ValidationError(GuiValidationError::AboveMaximum { name, .. })
+                if name == "Token 1"
+        ));
+
+        let err = builder
+            .set_deposit("token1", "".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::DepositAmountCannotBeEmpty
+        ));
+
+        let err = builder
+            .set_deposit("token2", "1".to_string())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::DepositTokenNotFound(token) if token == "token2"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dotrain_gui_state() {
+        let mut builder = builder().await;
+        builder
+            .set_field_value("max-price", "10".to_string())
+            .unwrap();
+        builder
+            .set_deposit("token1", "100".to_string())
+            .await
+            .unwrap();
+
+        let deployment = builder.get_current_deployment().unwrap();
+        let state = builder
+            .dotrain_gui_state(&deployment, "dotrain body")
+            .unwrap();
+        assert_eq!(
+            state.dotrain_hash,
+            DotrainSourceV1("dotrain body".to_string()).hash()
+        );
+        assert_eq!(state.selected_deployment, "flare");
+        assert_eq!(state.field_values["max-price"].value, "10");
+        assert_eq!(state.field_values["max-price"].id, "max-price");
+        assert_eq!(state.deposits["token1"].value, "100");
+        assert!(state.select_tokens.is_empty());
+        assert_eq!(
+            state.vault_ids.keys().collect::<Vec<_>>(),
+            vec!["input_0", "output_0"]
+        );
+
+        let meta = RainMetaDocumentV1Item::try_from(state.clone()).unwrap();
+        assert_eq!(DotrainGuiStateV1::try_from(meta).unwrap(), state);
+    }
+
+    #[tokio::test]
+    async fn test_select_tokens() {
+        let mut builder = builder().await;
+        let err = builder.check_select_tokens().unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::TokenMustBeSelected(key) if key == "token2"
+        ));
+
+        let err = builder
+            .set_select_token("token1", Address::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DeploymentBuilderError::TokenNotInSelectTokens(key) if key == "token1"
+        ));
+
+        let err = builder
+            .apply_answers(&DeploymentAnswers {
+                select_tokens: BTreeMap::from([("token2".to_string(), "0xzz".to_string())]),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DeploymentBuilderError::FromHexError(_)));
+    }
+}
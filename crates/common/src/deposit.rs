@@ -84,7 +84,7 @@ impl DepositArgs {
         transaction_args: TransactionArgs,
         transaction_status_changed: S,
     ) -> Result<(), DepositError> {
-        let (ledger_client, address) = transaction_args.clone().try_into_ledger_client().await?;
+        let (client, address) = transaction_args.clone().try_into_signing_client().await?;
 
         // Check allowance already granted for this token and contract
         let current_allowance = self
@@ -101,7 +101,7 @@ impl DepositArgs {
             let params =
                 transaction_args.try_into_write_contract_parameters(approve_call, self.token)?;
 
-            WriteTransaction::new(ledger_client, params, 4, transaction_status_changed)
+            WriteTransaction::new(client, params, 4, transaction_status_changed)
                 .execute()
                 .await?;
         }
@@ -116,13 +116,13 @@ impl DepositArgs {
         transaction_args: TransactionArgs,
         transaction_status_changed: S,
     ) -> Result<(), DepositError> {
-        let (ledger_client, _) = transaction_args.clone().try_into_ledger_client().await?;
+        let (client, _) = transaction_args.clone().try_into_signing_client().await?;
 
         let deposit_call: deposit4Call = self.clone().try_into()?;
         let params = transaction_args
             .try_into_write_contract_parameters(deposit_call, transaction_args.orderbook_address)?;
 
-        WriteTransaction::new(ledger_client, params, 4, transaction_status_changed)
+        WriteTransaction::new(client, params, 4, transaction_status_changed)
             .execute()
             .await?;
 
@@ -202,6 +202,7 @@ mod tests {
             chain_id: Some(1),
             max_priority_fee_per_gas: Some(200),
             max_fee_per_gas: Some(100),
+            ..Default::default()
         };
 
         let amount = Float::parse("100".to_string()).unwrap().get_inner();
@@ -229,6 +230,7 @@ mod tests {
             chain_id: Some(1),
             max_priority_fee_per_gas: Some(200),
             max_fee_per_gas: Some(100),
+            ..Default::default()
         };
         let approve_call = approveCall {
             spender: Address::ZERO,
@@ -21,7 +21,9 @@ use rain_interpreter_eval::eval::ForkParseArgs;
 use rain_interpreter_eval::fork::{Forker, NewForkedEvm};
 pub use rain_interpreter_eval::trace::{RainEvalResult, RainEvalResults, TraceSearchError};
 use rain_interpreter_eval::{error::ForkCallError, eval::ForkEvalArgs};
+use rain_math_float::FloatError;
 use rain_orderbook_app_settings::blocks::BlockError;
+use rain_orderbook_app_settings::rainlang::RainlangCfg;
 use rain_orderbook_app_settings::scenario::ScenarioCfg;
 use rain_orderbook_app_settings::yaml::dotrain::DotrainYamlValidation;
 use rain_orderbook_app_settings::{
@@ -50,6 +52,14 @@ impl FuzzResult {
     }
 }
 
+/// The fork block and the random values given to the elided bindings of a
+/// single fuzz run
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzSample {
+    pub block_number: u64,
+    pub fuzzed_bindings: Vec<(String, String)>,
+}
+
 #[derive(Clone)]
 pub struct FuzzRunner {
     pub forker: Forker,
@@ -101,6 +111,14 @@ pub enum FuzzRunnerError {
     RainEvalResultConversion(#[from] RainEvalResultFromRawCallResultError),
     #[error("Invalid input args: {0}")]
     InvalidArgs(String),
+    #[error("Invariant {invariant} references unknown stack column {column}")]
+    UnknownInvariantColumn { invariant: String, column: String },
+    #[error("Invariant {invariant} references unknown binding {binding}")]
+    UnknownInvariantBinding { invariant: String, binding: String },
+    #[error("Invariant {invariant} varies binding {binding} which is not fuzzed")]
+    InvariantBindingNotFuzzed { invariant: String, binding: String },
+    #[error(transparent)]
+    FloatError(#[from] FloatError),
 }
 
 impl From<ForkCallError> for FuzzRunnerError {
@@ -204,6 +222,22 @@ impl FuzzRunner {
         context: &mut FuzzRunnerContext,
         scenario: &ScenarioCfg,
     ) -> Result<FuzzResult, FuzzRunnerError> {
+        let (_, runs) = self.run_scenario_samples(context, scenario).await?;
+
+        Ok(FuzzResult {
+            scenario: scenario.key.clone(),
+            runs: runs.into(),
+        })
+    }
+
+    /// Runs the scenario like [FuzzRunner::run_scenario] and also returns
+    /// the block and fuzzed bindings of every run, in the same order as the
+    /// results
+    pub(super) async fn run_scenario_samples(
+        &mut self,
+        context: &mut FuzzRunnerContext,
+        scenario: &ScenarioCfg,
+    ) -> Result<(Vec<FuzzSample>, Vec<RainEvalResult>), FuzzRunnerError> {
         // If the scenario doesn't have runs, default is 1
         let no_of_runs = scenario.runs.unwrap_or(1);
 
@@ -259,6 +293,7 @@ impl FuzzRunner {
 
         let dotrain = Arc::new(context.dotrain.clone());
         let mut handles = vec![];
+        let mut samples = vec![];
 
         for block_number in blocks {
             self.forker.roll_fork(Some(block_number), None)?;
@@ -280,37 +315,17 @@ impl FuzzRunner {
                     let hex = alloy::primitives::hex::encode_prefixed(val);
                     final_bindings.push(Rebind(elided_binding.to_string(), hex));
                 }
+                samples.push(FuzzSample {
+                    block_number,
+                    fuzzed_bindings: final_bindings
+                        .iter()
+                        .map(|Rebind(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                });
 
                 let handle = tokio::spawn(async move {
                     final_bindings.extend(scenario_bindings.clone());
-
-                    let rainlang_string = RainDocument::compose_text(
-                        &dotrain,
-                        &ORDERBOOK_ORDER_ENTRYPOINTS,
-                        None,
-                        Some(final_bindings),
-                    )?;
-
-                    // Create a 5x5 grid of zero values for context - later we'll
-                    // replace these with sane values based on Orderbook context
-                    let mut context = vec![vec![U256::from(0); 5]; 5];
-                    // set random hash for context order hash cell
-                    context[1][0] = rand::random();
-
-                    let args = ForkEvalArgs {
-                        rainlang_string,
-                        source_index: 0,
-                        rainlang: rainlang_cfg.address,
-                        namespace: FullyQualifiedNamespace::default(),
-                        context,
-                        decode_errors: true,
-                        inputs: vec![],
-                        state_overlay: vec![],
-                    };
-                    fork_clone
-                        .fork_eval(args)
-                        .map_err(|e| FuzzRunnerError::ForkCallError(Box::new(e)))
-                        .await
+                    eval_with_bindings(&fork_clone, &dotrain, &rainlang_cfg, final_bindings).await
                 });
                 handles.push(handle);
             }
@@ -319,14 +334,10 @@ impl FuzzRunner {
         let mut runs: Vec<RainEvalResult> = Vec::new();
 
         for handle in handles {
-            let res = handle.await??;
-            runs.push(res.try_into()?);
+            runs.push(handle.await??);
         }
 
-        Ok(FuzzResult {
-            scenario: scenario.key.clone(),
-            runs: runs.into(),
-        })
+        Ok((samples, runs))
     }
 
     /// Debugs (evals) the given order pair on the self [Forker] instance.
@@ -727,6 +738,40 @@ impl FuzzRunner {
     }
 }
 
+/// Composes the dotrain with the given bindings and evals its calculate-io
+/// source once on the currently selected fork
+pub(super) async fn eval_with_bindings(
+    forker: &Forker,
+    dotrain: &str,
+    rainlang_cfg: &RainlangCfg,
+    bindings: Vec<Rebind>,
+) -> Result<RainEvalResult, FuzzRunnerError> {
+    let rainlang_string =
+        RainDocument::compose_text(dotrain, &ORDERBOOK_ORDER_ENTRYPOINTS, None, Some(bindings))?;
+
+    // Create a 5x5 grid of zero values for context - later we'll
+    // replace these with sane values based on Orderbook context
+    let mut context = vec![vec![U256::from(0); 5]; 5];
+    // set random hash for context order hash cell
+    context[1][0] = rand::random();
+
+    let args = ForkEvalArgs {
+        rainlang_string,
+        source_index: 0,
+        rainlang: rainlang_cfg.address,
+        namespace: FullyQualifiedNamespace::default(),
+        context,
+        decode_errors: true,
+        inputs: vec![],
+        state_overlay: vec![],
+    };
+    let res = forker
+        .fork_eval(args)
+        .map_err(|e| FuzzRunnerError::ForkCallError(Box::new(e)))
+        .await?;
+    Ok(res.try_into()?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
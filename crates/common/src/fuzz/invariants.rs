@@ -0,0 +1,648 @@
+use super::impls::eval_with_bindings;
+use super::*;
+use alloy::primitives::{B256, U256};
+use dotrain::Rebind;
+use proptest::prelude::RngCore;
+use rain_interpreter_eval::error::ForkCallError;
+use rain_math_float::{Float, FloatError};
+use rain_orderbook_app_settings::invariant::{
+    InvariantCfg, InvariantCheckCfg, InvariantOpCfg, MonotonicDirectionCfg,
+};
+use rain_orderbook_app_settings::scenario::ScenarioCfg;
+use std::collections::BTreeMap;
+
+/// Upper bound on the evals spent shrinking a single counterexample
+const MAX_SHRINK_EVALS: usize = 512;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantCounterexample {
+    pub block_number: u64,
+    /// Values of the fuzzed bindings, scenario bindings are left out
+    pub bindings: BTreeMap<String, String>,
+    /// The invariant's stack value on this run
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantViolation {
+    pub invariant: String,
+    pub check: String,
+    /// Number of runs that broke the invariant
+    pub failed_runs: usize,
+    pub counterexample: InvariantCounterexample,
+    /// For monotonic invariants, the run with the smaller value of the
+    /// varied binding that the counterexample is compared against
+    pub previous: Option<InvariantCounterexample>,
+    /// How many times the counterexample was shrunk from the first failing run
+    pub shrink_steps: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantSummary {
+    pub key: String,
+    pub check: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvariantReport {
+    pub scenario: String,
+    pub runs: usize,
+    pub invariants: Vec<InvariantSummary>,
+    pub violations: Vec<InvariantViolation>,
+}
+
+impl InvariantReport {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl FuzzRunner {
+    pub async fn check_invariants_by_key(
+        &mut self,
+        context: &mut FuzzRunnerContext,
+        key: &str,
+    ) -> Result<InvariantReport, FuzzRunnerError> {
+        let scenario = context.dotrain_yaml.get_scenario(key)?;
+        self.check_invariants(context, &scenario).await
+    }
+
+    /// Fuzzes the scenario and checks each of its invariants against every
+    /// run. The first failing run of a comparison is shrunk towards zero one
+    /// fuzzed binding at a time; monotonic invariants re-eval every run with
+    /// a second random value of the varied binding and report the pair.
+    pub async fn check_invariants(
+        &mut self,
+        context: &mut FuzzRunnerContext,
+        scenario: &ScenarioCfg,
+    ) -> Result<InvariantReport, FuzzRunnerError> {
+        let (samples, results) = self.run_scenario_samples(context, scenario).await?;
+        let table = RainEvalResults::from(results).into_flattened_table();
+
+        let mut violations = vec![];
+        for invariant in &scenario.invariants {
+            let column = table
+                .column_names
+                .iter()
+                .position(|name| *name == invariant.value)
+                .ok_or_else(|| FuzzRunnerError::UnknownInvariantColumn {
+                    invariant: invariant.key.clone(),
+                    column: invariant.value.clone(),
+                })?;
+            let values = table
+                .rows
+                .iter()
+                .map(|row| row.get(column).copied().map(stack_float))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(FuzzRunnerError::CorruptTraces)?;
+
+            let violation = match &invariant.check {
+                InvariantCheckCfg::Compare { op, bound } => {
+                    self.check_compare(context, scenario, invariant, *op, bound, &samples, &values)
+                        .await?
+                }
+                InvariantCheckCfg::Monotonic { direction, binding } => {
+                    self.check_monotonic(
+                        context, scenario, invariant, *direction, binding, &samples, &values,
+                    )
+                    .await?
+                }
+            };
+            violations.extend(violation);
+        }
+
+        Ok(InvariantReport {
+            scenario: scenario.key.clone(),
+            runs: samples.len(),
+            invariants: scenario
+                .invariants
+                .iter()
+                .map(|i| InvariantSummary {
+                    key: i.key.clone(),
+                    check: i.to_string(),
+                })
+                .collect(),
+            violations,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn check_compare(
+        &mut self,
+        context: &FuzzRunnerContext,
+        scenario: &ScenarioCfg,
+        invariant: &InvariantCfg,
+        op: InvariantOpCfg,
+        bound: &str,
+        samples: &[FuzzSample],
+        values: &[Float],
+    ) -> Result<Option<InvariantViolation>, FuzzRunnerError> {
+        let mut failing = vec![];
+        for (i, (sample, value)) in samples.iter().zip(values).enumerate() {
+            let bound = resolve_bound(invariant, bound, scenario, sample)?;
+            if !compare(*value, op, bound)? {
+                failing.push(i);
+            }
+        }
+        let Some(&first) = failing.first() else {
+            return Ok(None);
+        };
+
+        let (sample, value, shrink_steps) = self
+            .shrink(
+                &context.dotrain,
+                scenario,
+                invariant,
+                op,
+                bound,
+                samples[first].clone(),
+                values[first],
+            )
+            .await?;
+
+        Ok(Some(InvariantViolation {
+            invariant: invariant.key.clone(),
+            check: invariant.to_string(),
+            failed_runs: failing.len(),
+            counterexample: counterexample(&sample, value),
+            previous: None,
+            shrink_steps,
+        }))
+    }
+
+    /// Bisects each fuzzed binding between zero and its failing value,
+    /// keeping the value closest to zero found that still breaks the
+    /// invariant. Bindings are decoded and halved as floats, so candidates are
+    /// midpoints of the values the rainlang sees rather than of their packed
+    /// encoding. Candidates that revert are not counterexamples of the
+    /// invariant and are treated as passing.
+    #[allow(clippy::too_many_arguments)]
+    async fn shrink(
+        &mut self,
+        dotrain: &str,
+        scenario: &ScenarioCfg,
+        invariant: &InvariantCfg,
+        op: InvariantOpCfg,
+        bound: &str,
+        mut sample: FuzzSample,
+        mut value: Float,
+    ) -> Result<(FuzzSample, Float, usize), FuzzRunnerError> {
+        self.forker.roll_fork(Some(sample.block_number), None)?;
+
+        let two = Float::parse("2".to_string())?;
+        let mut evals = 0;
+        let mut shrink_steps = 0;
+        for i in 0..sample.fuzzed_bindings.len() {
+            let Ok(mut failing) = binding_float(&sample.fuzzed_bindings[i].1) else {
+                continue;
+            };
+            let mut passing: Option<Float> = None;
+            let mut candidate = Float::zero()?;
+
+            while evals < MAX_SHRINK_EVALS {
+                evals += 1;
+                let mut candidate_sample = sample.clone();
+                candidate_sample.fuzzed_bindings[i].1 = candidate.as_hex();
+
+                match self
+                    .eval_compare(dotrain, scenario, invariant, op, bound, &candidate_sample)
+                    .await?
+                {
+                    Some(candidate_value) => {
+                        failing = candidate;
+                        sample = candidate_sample;
+                        value = candidate_value;
+                        shrink_steps += 1;
+                    }
+                    None => passing = Some(candidate),
+                }
+
+                // Zero itself fails, nothing is closer
+                let Some(passing) = passing else {
+                    break;
+                };
+                // Midpoints of extreme values can leave the float range, the
+                // smallest failing value so far is kept
+                let Ok(next) = passing.add(failing).and_then(|sum| sum.div(two)) else {
+                    break;
+                };
+                // Out of precision once the midpoint lands on either end
+                if next.eq(passing)? || next.eq(failing)? {
+                    break;
+                }
+                candidate = next;
+            }
+        }
+
+        Ok((sample, value, shrink_steps))
+    }
+
+    /// Returns the value when the sample breaks the invariant
+    async fn eval_compare(
+        &mut self,
+        dotrain: &str,
+        scenario: &ScenarioCfg,
+        invariant: &InvariantCfg,
+        op: InvariantOpCfg,
+        bound: &str,
+        sample: &FuzzSample,
+    ) -> Result<Option<Float>, FuzzRunnerError> {
+        let Some(value) = self
+            .eval_value(dotrain, scenario, invariant, sample)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let bound = resolve_bound(invariant, bound, scenario, sample)?;
+        Ok((!compare(value, op, bound)?).then_some(value))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn check_monotonic(
+        &mut self,
+        context: &mut FuzzRunnerContext,
+        scenario: &ScenarioCfg,
+        invariant: &InvariantCfg,
+        direction: MonotonicDirectionCfg,
+        binding: &str,
+        samples: &[FuzzSample],
+        values: &[Float],
+    ) -> Result<Option<InvariantViolation>, FuzzRunnerError> {
+        let mut failing = vec![];
+        for (sample, value) in samples.iter().zip(values) {
+            let index = sample
+                .fuzzed_bindings
+                .iter()
+                .position(|(k, _)| k == binding)
+                .ok_or_else(|| {
+                    if scenario.bindings.contains_key(binding) {
+                        FuzzRunnerError::InvariantBindingNotFuzzed {
+                            invariant: invariant.key.clone(),
+                            binding: binding.to_string(),
+                        }
+                    } else {
+                        FuzzRunnerError::UnknownInvariantBinding {
+                            invariant: invariant.key.clone(),
+                            binding: binding.to_string(),
+                        }
+                    }
+                })?;
+
+            let mut val: [u8; 32] = [0; 32];
+            context.rng.fill_bytes(&mut val);
+            let mut other = sample.clone();
+            other.fuzzed_bindings[index].1 = alloy::primitives::hex::encode_prefixed(val);
+
+            self.forker.roll_fork(Some(sample.block_number), None)?;
+            let Some(other_value) = self
+                .eval_value(&context.dotrain, scenario, invariant, &other)
+                .await?
+            else {
+                continue;
+            };
+
+            let x = binding_float(&sample.fuzzed_bindings[index].1)?;
+            let other_x = binding_float(&other.fuzzed_bindings[index].1)?;
+            if x.eq(other_x)? {
+                continue;
+            }
+            let (low, high) = if x.lt(other_x)? {
+                ((sample, *value), (&other, other_value))
+            } else {
+                ((&other, other_value), (sample, *value))
+            };
+
+            let holds = match direction {
+                MonotonicDirectionCfg::Increasing => !high.1.lt(low.1)?,
+                MonotonicDirectionCfg::Decreasing => !high.1.gt(low.1)?,
+            };
+            if !holds {
+                failing.push((counterexample(low.0, low.1), counterexample(high.0, high.1)));
+            }
+        }
+
+        let failed_runs = failing.len();
+        Ok(failing
+            .into_iter()
+            .next()
+            .map(|(previous, counterexample)| InvariantViolation {
+                invariant: invariant.key.clone(),
+                check: invariant.to_string(),
+                failed_runs,
+                counterexample,
+                previous: Some(previous),
+                shrink_steps: 0,
+            }))
+    }
+
+    /// Evals the sample on the selected fork and reads the invariant's
+    /// column, `None` if the eval reverts with a decoded error. Any other
+    /// failure (compose, fork, rpc) is returned, it says nothing about the
+    /// invariant.
+    async fn eval_value(
+        &self,
+        dotrain: &str,
+        scenario: &ScenarioCfg,
+        invariant: &InvariantCfg,
+        sample: &FuzzSample,
+    ) -> Result<Option<Float>, FuzzRunnerError> {
+        let mut bindings: Vec<Rebind> = sample
+            .fuzzed_bindings
+            .iter()
+            .map(|(k, v)| Rebind(k.clone(), v.clone()))
+            .collect();
+        bindings.extend(
+            scenario
+                .bindings
+                .iter()
+                .map(|(k, v)| Rebind(k.clone(), v.clone())),
+        );
+
+        let result =
+            match eval_with_bindings(&self.forker, dotrain, &scenario.rainlang, bindings).await {
+                Ok(result) => result,
+                Err(FuzzRunnerError::ForkCallError(err))
+                    if matches!(*err, ForkCallError::AbiDecodedError(_)) =>
+                {
+                    return Ok(None);
+                }
+                Err(err) => return Err(err),
+            };
+        let table = RainEvalResults::from(vec![result]).into_flattened_table();
+        let value = table
+            .column_names
+            .iter()
+            .position(|name| *name == invariant.value)
+            .and_then(|column| table.rows.first()?.get(column).copied())
+            .ok_or_else(|| FuzzRunnerError::UnknownInvariantColumn {
+                invariant: invariant.key.clone(),
+                column: invariant.value.clone(),
+            })?;
+        Ok(Some(stack_float(value)))
+    }
+}
+
+/// Stack items are raw floats
+fn stack_float(value: U256) -> Float {
+    Float::from_raw(B256::from(value))
+}
+
+/// Fuzzed bindings are raw 32 byte floats, scenario bindings are decimals
+fn binding_float(value: &str) -> Result<Float, FloatError> {
+    if value.starts_with("0x") && value.len() == 66 {
+        Float::from_hex(value)
+    } else {
+        Float::parse(value.to_string())
+    }
+}
+
+/// A bound is a number literal or the name of a binding. Values only known
+/// on chain, such as a vault balance, have to be bound by the scenario.
+fn resolve_bound(
+    invariant: &InvariantCfg,
+    bound: &str,
+    scenario: &ScenarioCfg,
+    sample: &FuzzSample,
+) -> Result<Float, FuzzRunnerError> {
+    if let Ok(literal) = Float::parse(bound.to_string()) {
+        return Ok(literal);
+    }
+    let value = sample
+        .fuzzed_bindings
+        .iter()
+        .find(|(k, _)| k == bound)
+        .map(|(_, v)| v)
+        .or_else(|| scenario.bindings.get(bound))
+        .ok_or_else(|| FuzzRunnerError::UnknownInvariantBinding {
+            invariant: invariant.key.clone(),
+            binding: bound.to_string(),
+        })?;
+    Ok(binding_float(value)?)
+}
+
+fn compare(value: Float, op: InvariantOpCfg, bound: Float) -> Result<bool, FloatError> {
+    match op {
+        InvariantOpCfg::Eq => value.eq(bound),
+        InvariantOpCfg::Gt => value.gt(bound),
+        InvariantOpCfg::Gte => Ok(!value.lt(bound)?),
+        InvariantOpCfg::Lt => value.lt(bound),
+        InvariantOpCfg::Lte => value.lte(bound),
+    }
+}
+
+fn format_float(value: Float) -> String {
+    value.format().unwrap_or_else(|_| value.as_hex())
+}
+
+fn counterexample(sample: &FuzzSample, value: Float) -> InvariantCounterexample {
+    InvariantCounterexample {
+        block_number: sample.block_number,
+        bindings: sample.fuzzed_bindings.iter().cloned().collect(),
+        value: format_float(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
+    use rain_orderbook_test_fixtures::LocalEvm;
+
+    fn float(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn invariant(bound: &str) -> InvariantCfg {
+        InvariantCfg {
+            key: "capped".to_string(),
+            value: "0.0".to_string(),
+            check: InvariantCheckCfg::Compare {
+                op: InvariantOpCfg::Lte,
+                bound: bound.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert!(compare(float("1"), InvariantOpCfg::Eq, float("1")).unwrap());
+        assert!(compare(float("2"), InvariantOpCfg::Gt, float("1")).unwrap());
+        assert!(compare(float("1"), InvariantOpCfg::Gte, float("1")).unwrap());
+        assert!(!compare(float("1"), InvariantOpCfg::Lt, float("1")).unwrap());
+        assert!(compare(float("0.5"), InvariantOpCfg::Lte, float("1")).unwrap());
+        assert!(!compare(float("-1"), InvariantOpCfg::Gt, float("0")).unwrap());
+    }
+
+    #[test]
+    fn test_stack_float() {
+        assert!(stack_float(U256::from(6)).eq(float("6")).unwrap());
+        assert!(stack_float(U256::ZERO).eq(float("0")).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_bound() {
+        let scenario = ScenarioCfg {
+            bindings: HashMap::from([("max-output".to_string(), "100".to_string())]),
+            ..Default::default()
+        };
+        let sample = FuzzSample {
+            block_number: 1,
+            fuzzed_bindings: vec![("amount".to_string(), B256::from(U256::from(7)).to_string())],
+        };
+
+        let bound = resolve_bound(&invariant("1.5"), "1.5", &scenario, &sample).unwrap();
+        assert!(bound.eq(float("1.5")).unwrap());
+        let bound =
+            resolve_bound(&invariant("max-output"), "max-output", &scenario, &sample).unwrap();
+        assert!(bound.eq(float("100")).unwrap());
+        let bound = resolve_bound(&invariant("amount"), "amount", &scenario, &sample).unwrap();
+        assert!(bound.eq(float("7")).unwrap());
+
+        let err = resolve_bound(&invariant("missing"), "missing", &scenario, &sample).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invariant capped references unknown binding missing"
+        );
+    }
+
+    fn dotrain(local_evm: &LocalEvm, invariants: &str) -> String {
+        format!(
+            r#"
+version: {spec_version}
+rainlangs:
+    some-key:
+        address: {rainlang_address}
+networks:
+    some-key:
+        rpcs:
+            - {rpc_url}
+        chain-id: 123
+scenarios:
+    some-key:
+        runs: 20
+        bindings:
+            bound: 3
+        invariants:
+{invariants}
+---
+#bound !bind it
+#fuzzed !fuzz it
+#calculate-io
+a: bound,
+b: fuzzed;
+#handle-io
+:;
+#handle-add-order
+:;"#,
+            rpc_url = local_evm.url(),
+            rainlang_address = local_evm.rainlang,
+            spec_version = SpecVersion::current()
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+    async fn test_check_invariants_pass() {
+        let local_evm = LocalEvm::new().await;
+        let dotrain = dotrain(
+            &local_evm,
+            r#"
+            bound-positive:
+                value: 0.0
+                gt: 0
+            bound-is-binding:
+                value: 0.0
+                eq: bound
+            fuzzed-increasing:
+                value: 0.1
+                monotonic: increasing
+                in: fuzzed"#,
+        );
+        let mut runner = FuzzRunner::new(None).unwrap();
+        let mut context = FuzzRunnerContext::new(&dotrain, None, None).unwrap();
+
+        let report = runner
+            .check_invariants_by_key(&mut context, "some-key")
+            .await
+            .unwrap();
+
+        assert!(report.passed(), "{:#?}", report.violations);
+        assert_eq!(report.runs, 20);
+        assert_eq!(
+            report
+                .invariants
+                .iter()
+                .map(|i| (i.key.as_str(), i.check.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("bound-is-binding", "0.0 == bound"),
+                ("bound-positive", "0.0 > 0"),
+                ("fuzzed-increasing", "0.1 increasing in fuzzed"),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+    async fn test_check_invariants_violations() {
+        let local_evm = LocalEvm::new().await;
+        let dotrain = dotrain(
+            &local_evm,
+            r#"
+            fuzzed-capped:
+                value: 0.1
+                lte: bound
+            fuzzed-decreasing:
+                value: 0.1
+                monotonic: decreasing
+                in: fuzzed"#,
+        );
+        let mut runner = FuzzRunner::new(None).unwrap();
+        let mut context = FuzzRunnerContext::new(&dotrain, None, Some([1; 32])).unwrap();
+
+        let report = runner
+            .check_invariants_by_key(&mut context, "some-key")
+            .await
+            .unwrap();
+
+        assert!(!report.passed());
+        assert_eq!(report.violations.len(), 2);
+
+        let capped = &report.violations[0];
+        assert_eq!(capped.invariant, "fuzzed-capped");
+        assert!(capped.failed_runs > 0);
+        assert!(capped.previous.is_none());
+        let shrunk = Float::from_hex(&capped.counterexample.bindings["fuzzed"]).unwrap();
+        assert!(shrunk.gt(float("3")).unwrap());
+
+        let decreasing = &report.violations[1];
+        assert_eq!(decreasing.invariant, "fuzzed-decreasing");
+        assert!(decreasing.previous.is_some());
+        assert_eq!(decreasing.shrink_steps, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 10)]
+    async fn test_check_invariants_unknown_column() {
+        let local_evm = LocalEvm::new().await;
+        let dotrain = dotrain(
+            &local_evm,
+            r#"
+            missing:
+                value: 0.9
+                gt: 0"#,
+        );
+        let mut runner = FuzzRunner::new(None).unwrap();
+        let mut context = FuzzRunnerContext::new(&dotrain, None, None).unwrap();
+
+        let err = runner
+            .check_invariants_by_key(&mut context, "some-key")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FuzzRunnerError::UnknownInvariantColumn { ref column, .. } if column == "0.9"
+        ));
+    }
+}
@@ -10,6 +10,10 @@ use wasm_bindgen_utils::{impl_wasm_traits, prelude::*, serialize_hashmap_as_obje
 mod impls;
 #[cfg(not(target_family = "wasm"))]
 pub use impls::*;
+#[cfg(not(target_family = "wasm"))]
+mod invariants;
+#[cfg(not(target_family = "wasm"))]
+pub use invariants::*;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(target_family = "wasm", derive(Tsify))]
@@ -0,0 +1,291 @@
+use alloy::primitives::{utils::format_ether, Address, Bytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{BlockNumberOrTag, TransactionInput, TransactionRequest};
+use alloy::serde::WithOtherFields;
+use alloy::transports::TransportError;
+use rain_error_decoding::{AbiDecodedErrorType, ErrorRegistry};
+use rain_orderbook_app_settings::network::NetworkCfg;
+use rain_orderbook_bindings::provider::{mk_read_provider, ReadProviderError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+/// Gas and fee estimate of a transaction, with its expected cost in the
+/// network's native currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimate {
+    pub gas_limit: u64,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub base_fee_per_gas: U256,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub max_priority_fee_per_gas: U256,
+    /// Twice the base fee plus the priority fee, leaving room for the base
+    /// fee to rise before the transaction is included
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub max_fee_per_gas: U256,
+    /// Gas limit times the base fee plus the priority fee, in wei
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub total_cost: U256,
+    pub formatted_total_cost: String,
+    #[cfg_attr(target_family = "wasm", tsify(optional))]
+    pub currency: Option<String>,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(GasEstimate);
+
+impl GasEstimate {
+    pub fn new(
+        gas_limit: u64,
+        base_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+        currency: Option<String>,
+    ) -> Self {
+        let base_fee_per_gas = U256::from(base_fee_per_gas);
+        let max_priority_fee_per_gas = U256::from(max_priority_fee_per_gas);
+        let total_cost =
+            U256::from(gas_limit).saturating_mul(base_fee_per_gas + max_priority_fee_per_gas);
+        Self {
+            gas_limit,
+            base_fee_per_gas,
+            max_priority_fee_per_gas,
+            max_fee_per_gas: base_fee_per_gas * U256::from(2) + max_priority_fee_per_gas,
+            total_cost,
+            formatted_total_cost: format_ether(total_cost),
+            currency,
+        }
+    }
+}
+
+/// Outcome of estimating a calldata, either its cost or the reason the
+/// transaction would revert
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(tag = "type", content = "data")]
+pub enum CalldataCost {
+    Estimate(GasEstimate),
+    Revert(String),
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(CalldataCost);
+
+/// Calldata along with the estimate of sending it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedCalldata {
+    #[cfg_attr(target_family = "wasm", tsify(type = "Hex"))]
+    pub calldata: Bytes,
+    pub cost: CalldataCost,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(EstimatedCalldata);
+
+#[derive(Error, Debug)]
+pub enum GasEstimateError {
+    #[error(transparent)]
+    ReadProviderError(#[from] ReadProviderError),
+    #[error(transparent)]
+    TransportError(#[from] TransportError),
+    #[error("The network returned no base fee for the latest block")]
+    MissingBaseFee,
+}
+
+/// Estimates the gas and fees of sending `calldata` from `from` to `to` on
+/// the network's RPCs.
+///
+/// A transaction that would revert isn't an error, its revert data is
+/// decoded into the returned [`CalldataCost::Revert`] reason.
+pub async fn estimate_calldata_cost(
+    network: &NetworkCfg,
+    from: Address,
+    to: Address,
+    calldata: Bytes,
+    registry: Option<&dyn ErrorRegistry>,
+) -> Result<CalldataCost, GasEstimateError> {
+    let provider = mk_read_provider(&network.rpcs)?;
+    let request = TransactionRequest::default()
+        .from(from)
+        .to(to)
+        .input(TransactionInput::new(calldata));
+
+    let gas_limit = match provider.estimate_gas(WithOtherFields::new(request)).await {
+        Ok(gas_limit) => gas_limit,
+        Err(err) => match revert_reason(&err, registry).await {
+            Some(reason) => return Ok(CalldataCost::Revert(reason)),
+            None => return Err(err.into()),
+        },
+    };
+    let base_fee_per_gas = provider
+        .get_fee_history(1, BlockNumberOrTag::Latest, &[])
+        .await?
+        .next_block_base_fee()
+        .ok_or(GasEstimateError::MissingBaseFee)?;
+    let max_priority_fee_per_gas = provider.get_max_priority_fee_per_gas().await?;
+
+    Ok(CalldataCost::Estimate(GasEstimate::new(
+        gas_limit,
+        base_fee_per_gas,
+        max_priority_fee_per_gas,
+        network.currency.clone(),
+    )))
+}
+
+/// Human readable reason of a reverted call, decoded from its revert data
+/// when the node returns any
+async fn revert_reason(
+    err: &TransportError,
+    registry: Option<&dyn ErrorRegistry>,
+) -> Option<String> {
+    let payload = err.as_error_resp()?;
+    match payload.as_revert_data() {
+        Some(data) if !data.is_empty() => {
+            match AbiDecodedErrorType::selector_registry_abi_decode(data.as_ref(), registry).await {
+                Ok(decoded) => Some(decoded.to_string()),
+                Err(_) => Some(format!("Reverted with undecodable data {data}")),
+            }
+        }
+        _ if payload.message.contains("revert") => Some(payload.message.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+    use alloy::json_abi::Error as AlloyError;
+    use httpmock::MockServer;
+    use rain_error_decoding::AbiDecodeFailedErrors;
+    use serde_json::json;
+
+    struct FakeRegistry;
+
+    #[async_trait::async_trait]
+    impl ErrorRegistry for FakeRegistry {
+        async fn lookup(
+            &self,
+            selector: [u8; 4],
+        ) -> Result<Vec<AlloyError>, AbiDecodeFailedErrors> {
+            // 0x734bc71c -> "TokenSelfTrade()"
+            if selector == [0x73, 0x4b, 0xc7, 0x1c] {
+                Ok(vec!["TokenSelfTrade()".parse().unwrap()])
+            } else {
+                Ok(vec![])
+            }
+        }
+    }
+
+    fn network(server: &MockServer) -> NetworkCfg {
+        NetworkCfg {
+            rpcs: vec![server.url("/rpc").parse().unwrap()],
+            currency: Some("ETH".to_string()),
+            ..NetworkCfg::dummy()
+        }
+    }
+
+    #[test]
+    fn test_gas_estimate_new() {
+        let estimate = GasEstimate::new(100_000, 20_000_000_000, 1_000_000_000, None);
+        assert_eq!(estimate.max_fee_per_gas, U256::from(41_000_000_000u64));
+        assert_eq!(estimate.total_cost, U256::from(2_100_000_000_000_000u64));
+        assert_eq!(estimate.formatted_total_cost, "0.002100000000000000");
+    }
+
+    #[tokio::test]
+    async fn test_estimate_calldata_cost() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_estimateGas");
+            then.json_body(json!({"jsonrpc": "2.0", "id": 1, "result": "0x186a0"}));
+        });
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_feeHistory");
+            then.json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "oldestBlock": "0x1",
+                    "baseFeePerGas": ["0x4a817c800", "0x4a817c800"],
+                    "gasUsedRatio": [0.5],
+                    "reward": []
+                }
+            }));
+        });
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_maxPriorityFeePerGas");
+            then.json_body(json!({"jsonrpc": "2.0", "id": 1, "result": "0x3b9aca00"}));
+        });
+
+        let cost = estimate_calldata_cost(
+            &network(&server),
+            Address::ZERO,
+            Address::ZERO,
+            Bytes::from(vec![1, 2, 3, 4]),
+            Some(&FakeRegistry),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            cost,
+            CalldataCost::Estimate(GasEstimate::new(
+                100_000,
+                20_000_000_000,
+                1_000_000_000,
+                Some("ETH".to_string())
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_estimate_calldata_cost_revert() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_estimateGas");
+            then.json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": 3, "message": "execution reverted", "data": "0x734bc71c"}
+            }));
+        });
+
+        let cost = estimate_calldata_cost(
+            &network(&server),
+            Address::ZERO,
+            Address::ZERO,
+            Bytes::new(),
+            Some(&FakeRegistry),
+        )
+        .await
+        .unwrap();
+        match cost {
+            CalldataCost::Revert(reason) => assert!(reason.contains("TokenSelfTrade")),
+            _ => panic!("expected a revert"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_estimate_calldata_cost_rpc_error() {
+        let server = MockServer::start_async().await;
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_estimateGas");
+            then.json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32000, "message": "insufficient funds for gas"}
+            }));
+        });
+
+        let err = estimate_calldata_cost(
+            &network(&server),
+            Address::ZERO,
+            Address::ZERO,
+            Bytes::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, GasEstimateError::TransportError(_)));
+    }
+}
@@ -1,16 +1,21 @@
 pub mod add_order;
+pub mod clear;
 pub mod csv;
+pub mod deployment_builder;
 pub mod deposit;
 pub mod dotrain_add_order_lsp;
 pub mod dotrain_order;
 pub mod erc20;
 pub mod fuzz;
+pub mod gas_estimate;
 pub mod local_db;
 pub mod meta;
+pub mod multicall;
 pub mod oracle;
 pub mod parsed_meta;
 pub mod raindex_client;
 pub mod rainlang;
+pub mod registry;
 pub mod remove_order;
 #[cfg(not(target_family = "wasm"))]
 pub mod replays;
@@ -20,12 +25,16 @@ pub mod subgraph;
 pub mod take_orders;
 pub mod transaction;
 pub mod types;
-#[cfg(all(not(target_family = "wasm"), test))]
+#[cfg(not(target_family = "wasm"))]
 pub mod unit_tests;
 pub mod utils;
+#[cfg(not(target_family = "wasm"))]
+pub mod watch;
 pub mod withdraw;
 pub use dotrain;
 pub use dotrain_lsp;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod test_fixtures;
 #[cfg(test)]
 pub mod test_helpers;
 
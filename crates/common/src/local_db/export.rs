@@ -1,5 +1,6 @@
 use crate::local_db::query::{
-    create_tables::REQUIRED_TABLES, LocalDbQueryExecutor, SqlStatement, SqlValue,
+    create_tables::REQUIRED_TABLES, rollback_to_block::ROLLBACK_CHILD_TABLES, LocalDbQueryExecutor,
+    SqlStatement, SqlValue,
 };
 use crate::local_db::LocalDbError;
 use alloy::primitives::Address;
@@ -46,6 +47,44 @@ pub async fn export_data_only<E>(
 where
     E: LocalDbQueryExecutor + ?Sized,
 {
+    export_tables(executor, ob_id, None).await
+}
+
+/// Export the rows of a `(chain_id, orderbook_address)` recorded in blocks
+/// `(start_block, end_block]` as `INSERT OR REPLACE` statements, so applying
+/// the delta to a database holding the orderbook up to `start_block` brings
+/// it to `end_block`, and applying it twice is harmless.
+///
+/// Tables tracking a `last_block` export the rows updated within the range,
+/// child tables without a block column follow their parent's block, and
+/// tables with no block to filter on (such as `erc20_tokens`) are exported
+/// whole.
+///
+/// Returns `Ok(None)` when no matching rows are found.
+pub async fn export_delta<E>(
+    executor: &E,
+    ob_id: &OrderbookIdentifier,
+    start_block: u64,
+    end_block: u64,
+) -> Result<Option<String>, LocalDbError>
+where
+    E: LocalDbQueryExecutor + ?Sized,
+{
+    export_tables(executor, ob_id, Some((start_block, end_block))).await
+}
+
+async fn export_tables<E>(
+    executor: &E,
+    ob_id: &OrderbookIdentifier,
+    block_range: Option<(u64, u64)>,
+) -> Result<Option<String>, LocalDbError>
+where
+    E: LocalDbQueryExecutor + ?Sized,
+{
+    let insert_verb = match block_range {
+        Some(_) => "INSERT OR REPLACE",
+        None => "INSERT",
+    };
     let table_names = REQUIRED_TABLES
         .iter()
         .copied()
@@ -60,7 +99,12 @@ where
             continue;
         }
 
-        let select_stmt = build_select_statement(table, &columns, ob_id);
+        let select_stmt = match block_range {
+            Some((start_block, end_block)) => {
+                build_delta_select_statement(table, &columns, ob_id, start_block, end_block)
+            }
+            None => build_select_statement(table, &columns, ob_id),
+        };
         let rows: Vec<Value> = executor
             .query_json(&select_stmt)
             .await
@@ -69,7 +113,7 @@ where
             continue;
         }
 
-        let table_sql = build_insert_statements(table, &columns, &rows)?;
+        let table_sql = build_insert_statements(table, insert_verb, &columns, &rows)?;
         if !had_rows {
             inserts.push_str("BEGIN;\n");
             had_rows = true;
@@ -114,6 +158,15 @@ fn build_select_statement(
     table: &str,
     columns: &[TableInfoRow],
     ob_id: &OrderbookIdentifier,
+) -> SqlStatement {
+    build_filtered_select_statement(table, columns, ob_id, "")
+}
+
+fn build_filtered_select_statement(
+    table: &str,
+    columns: &[TableInfoRow],
+    ob_id: &OrderbookIdentifier,
+    extra_filter: &str,
 ) -> SqlStatement {
     let columns_sql = columns.iter().map(|c| format!("\"{}\"", c.name)).join(", ");
 
@@ -140,15 +193,54 @@ fn build_select_statement(
 
     let order_clause = order_columns.join(", ");
     let mut stmt = SqlStatement::new(format!(
-        "SELECT {columns_sql} FROM \"{table}\" WHERE chain_id = ?1 AND orderbook_address = ?2 ORDER BY {order_clause};"
+        "SELECT {columns_sql} FROM \"{table}\" WHERE chain_id = ?1 AND orderbook_address = ?2{extra_filter} ORDER BY {order_clause};"
     ));
     stmt.push(SqlValue::from(ob_id.chain_id as u64));
     stmt.push(SqlValue::from(ob_id.orderbook_address));
     stmt
 }
 
+fn build_delta_select_statement(
+    table: &str,
+    columns: &[TableInfoRow],
+    ob_id: &OrderbookIdentifier,
+    start_block: u64,
+    end_block: u64,
+) -> SqlStatement {
+    let Some(range_filter) = delta_range_filter(table, columns) else {
+        return build_select_statement(table, columns, ob_id);
+    };
+    let mut stmt = build_filtered_select_statement(table, columns, ob_id, &range_filter);
+    stmt.push(SqlValue::from(start_block));
+    stmt.push(SqlValue::from(end_block));
+    stmt
+}
+
+/// Filter restricting a table to the rows of blocks `(?3, ?4]`, or `None`
+/// when the table carries no block to filter on.
+fn delta_range_filter(table: &str, columns: &[TableInfoRow]) -> Option<String> {
+    if let Some(block_column) = ["block_number", "last_block"]
+        .into_iter()
+        .find(|name| columns.iter().any(|c| c.name.eq_ignore_ascii_case(name)))
+    {
+        return Some(format!(
+            " AND \"{block_column}\" > ?3 AND \"{block_column}\" <= ?4"
+        ));
+    }
+
+    ROLLBACK_CHILD_TABLES
+        .iter()
+        .find(|(child, _)| *child == table)
+        .map(|(child, parent)| {
+            format!(
+                " AND EXISTS (SELECT 1 FROM \"{parent}\" p WHERE p.chain_id = \"{child}\".chain_id AND p.orderbook_address = \"{child}\".orderbook_address AND p.transaction_hash = \"{child}\".transaction_hash AND p.log_index = \"{child}\".log_index AND p.block_number > ?3 AND p.block_number <= ?4)"
+            )
+        })
+}
+
 fn build_insert_statements(
     table: &str,
+    insert_verb: &str,
     columns: &[TableInfoRow],
     rows: &[Value],
 ) -> Result<String, LocalDbError> {
@@ -162,7 +254,7 @@ fn build_insert_statements(
     for row in rows {
         let values_sql = format_row_values(row, &column_names).map_err(LocalDbError::from)?;
         output.push_str(&format!(
-            "INSERT INTO \"{table}\" ({quoted_columns}) VALUES ({values_sql});\n"
+            "{insert_verb} INTO \"{table}\" ({quoted_columns}) VALUES ({values_sql});\n"
         ));
     }
 
@@ -1164,6 +1256,84 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn export_delta_includes_only_rows_within_block_range() {
+        let executor = TestExecutor::new();
+        let ob_id = OrderbookIdentifier {
+            chain_id: 42161,
+            orderbook_address: Address::from_str("0x0000000000000000000000000000000000000aaa")
+                .unwrap(),
+        };
+
+        let sql = export_delta(&executor, &ob_id, 1012, 1015)
+            .await
+            .unwrap()
+            .expect("delta should have rows");
+
+        assert!(sql.starts_with("BEGIN;"));
+        assert!(sql.ends_with("COMMIT;\n"));
+        assert!(!sql.contains("INSERT INTO"), "delta inserts must replace");
+        for included in [
+            "order_tx_main",
+            "io_token_main",
+            "take_tx_main",
+            "context_entry_main",
+            "context_value_main",
+            "clear_tx_main",
+            "token_addr_main",
+        ] {
+            assert!(sql.contains(included), "delta should include {included}");
+        }
+        for excluded in [
+            "dep_tx_main",
+            "with_tx_main",
+            "after_sender_main",
+            "meta_tx_main",
+            "hash_main",
+            "_alt",
+            "_other",
+        ] {
+            assert!(!sql.contains(excluded), "delta should exclude {excluded}");
+        }
+
+        let watermark_only = export_delta(&executor, &ob_id, 2_000, 2_010)
+            .await
+            .unwrap()
+            .expect("watermark row is in range");
+        assert!(watermark_only.contains("INSERT OR REPLACE INTO \"target_watermarks\""));
+        assert!(!watermark_only.contains("raw_tx_main"));
+    }
+
+    #[tokio::test]
+    async fn export_delta_can_be_applied_repeatedly() {
+        let executor = TestExecutor::new();
+        let ob_id = OrderbookIdentifier {
+            chain_id: 10,
+            orderbook_address: Address::from_str("0x0000000000000000000000000000000000000ccc")
+                .unwrap(),
+        };
+        let sql = export_delta(&executor, &ob_id, 0, 3_000)
+            .await
+            .unwrap()
+            .expect("delta should have rows");
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(CREATE_TABLES_SQL).unwrap();
+        conn.execute_batch(&sql).expect("apply delta");
+        conn.execute_batch(&sql).expect("re-apply delta");
+
+        let order_ios: i64 = conn
+            .query_row("SELECT COUNT(*) FROM order_ios", [], |row| row.get(0))
+            .unwrap();
+        let watermark: i64 = conn
+            .query_row("SELECT last_block FROM target_watermarks", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(order_ios, 1);
+        assert_eq!(watermark, 2_030);
+    }
+
     fn expected_dump(chain_id: u32, orderbook: Address, label: &str, base_idx: i64) -> String {
         let base = base_idx;
         let dep_idx = base + 1;
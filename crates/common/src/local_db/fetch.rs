@@ -1,7 +1,8 @@
+use super::log_source::LogSource;
 use super::LocalDbError;
 use crate::{
     retry::retry_with_backoff,
-    rpc_client::{LogEntryResponse, RpcClient, RpcClientError},
+    rpc_client::{LogEntryResponse, RpcClientError},
 };
 use alloy::primitives::{Address, U256};
 use alloy::rpc::types::Filter;
@@ -116,8 +117,10 @@ impl Default for FetchConfig {
     }
 }
 
-async fn collect_logs(
-    rpc_client: &RpcClient,
+/// Fetches the logs matching the filter in `chunk_size` block ranges, in
+/// chain order. Block timestamps are left as the source returned them.
+pub async fn fetch_logs<S: LogSource + ?Sized>(
+    source: &S,
     filter: &Filter,
     config: &FetchConfig,
 ) -> Result<Vec<LogEntryResponse>, LocalDbError> {
@@ -132,16 +135,25 @@ async fn collect_logs(
         return Ok(Vec::new());
     }
 
-    let mut events = fetch_logs_for_filters(rpc_client, filters, config).await?;
-
+    let mut events = fetch_logs_for_filters(source, filters, config).await?;
     sort_events_by_block_and_log(&mut events);
-    backfill_missing_timestamps(rpc_client, &mut events, config).await?;
 
     Ok(events)
 }
 
-pub async fn fetch_orderbook_events(
-    rpc_client: &RpcClient,
+async fn collect_logs<S: LogSource + ?Sized>(
+    source: &S,
+    filter: &Filter,
+    config: &FetchConfig,
+) -> Result<Vec<LogEntryResponse>, LocalDbError> {
+    let mut events = fetch_logs(source, filter, config).await?;
+    backfill_missing_timestamps(source, &mut events, config).await?;
+
+    Ok(events)
+}
+
+pub async fn fetch_orderbook_events<S: LogSource + ?Sized>(
+    source: &S,
     address: Address,
     from_block: u64,
     to_block: u64,
@@ -152,11 +164,11 @@ pub async fn fetch_orderbook_events(
         .from_block(from_block)
         .to_block(to_block)
         .event_signature(ORDERBOOK_EVENT_TOPICS.to_vec());
-    collect_logs(rpc_client, &filter, config).await
+    collect_logs(source, &filter, config).await
 }
 
-pub async fn fetch_store_events(
-    rpc_client: &RpcClient,
+pub async fn fetch_store_events<S: LogSource + ?Sized>(
+    source: &S,
     addresses: &[Address],
     from_block: u64,
     to_block: u64,
@@ -167,11 +179,11 @@ pub async fn fetch_store_events(
         .from_block(from_block)
         .to_block(to_block)
         .event_signature(STORE_SET_TOPICS.to_vec());
-    collect_logs(rpc_client, &filter, config).await
+    collect_logs(source, &filter, config).await
 }
 
-async fn fetch_block_timestamps(
-    rpc_client: &RpcClient,
+async fn fetch_block_timestamps<S: LogSource + ?Sized>(
+    source: &S,
     block_numbers: Vec<u64>,
     config: &FetchConfig,
 ) -> Result<HashMap<u64, U256>, LocalDbError> {
@@ -184,19 +196,10 @@ async fn fetch_block_timestamps(
     let rate_limit_delay = config.rate_limit_delay_ms();
     let results: Vec<Result<(u64, U256), LocalDbError>> = futures::stream::iter(block_numbers)
         .map(|block_number| {
-            let client = rpc_client.clone();
             let max_attempts = config.max_retry_attempts();
             async move {
                 let block_response = retry_with_backoff(
-                    || {
-                        let client = client.clone();
-                        async move {
-                            client
-                                .get_block_by_number(block_number)
-                                .await
-                                .map_err(map_rpc_error)
-                        }
-                    },
+                    || source.block_by_number(block_number),
                     max_attempts,
                     retry_delay,
                     rate_limit_delay,
@@ -219,8 +222,8 @@ async fn fetch_block_timestamps(
     results.into_iter().collect()
 }
 
-async fn backfill_missing_timestamps(
-    rpc_client: &RpcClient,
+async fn backfill_missing_timestamps<S: LogSource + ?Sized>(
+    source: &S,
     events: &mut [LogEntryResponse],
     config: &FetchConfig,
 ) -> Result<(), LocalDbError> {
@@ -239,7 +242,7 @@ async fn backfill_missing_timestamps(
     }
 
     let block_numbers: Vec<u64> = missing_blocks.into_iter().collect();
-    let timestamps = fetch_block_timestamps(rpc_client, block_numbers, config).await?;
+    let timestamps = fetch_block_timestamps(source, block_numbers, config).await?;
 
     for event in events.iter_mut() {
         let has_timestamp = event.block_timestamp.as_ref().is_some();
@@ -303,8 +306,8 @@ fn build_log_filters(filter: &Filter, config: &FetchConfig) -> Result<Vec<Filter
     Ok(filters)
 }
 
-async fn fetch_logs_for_filters(
-    rpc_client: &RpcClient,
+async fn fetch_logs_for_filters<S: LogSource + ?Sized>(
+    source: &S,
     filters: Vec<Filter>,
     config: &FetchConfig,
 ) -> Result<Vec<LogEntryResponse>, LocalDbError> {
@@ -317,16 +320,11 @@ async fn fetch_logs_for_filters(
     let rate_limit_delay = config.rate_limit_delay_ms();
     let results: Vec<Vec<LogEntryResponse>> = futures::stream::iter(filters)
         .map(|filter| {
-            let client = rpc_client.clone();
             let max_attempts = config.max_retry_attempts();
 
             async move {
                 let response = retry_with_backoff(
-                    || {
-                        let client = client.clone();
-                        let filter = filter.clone();
-                        async move { client.get_logs(&filter).await.map_err(map_rpc_error) }
-                    },
+                    || source.logs(&filter),
                     max_attempts,
                     retry_delay,
                     rate_limit_delay,
@@ -355,7 +353,7 @@ fn sort_events_by_block_and_log(events: &mut [LogEntryResponse]) {
     });
 }
 
-fn map_rpc_error(error: RpcClientError) -> LocalDbError {
+pub(crate) fn map_rpc_error(error: RpcClientError) -> LocalDbError {
     match error {
         RpcClientError::JsonSerialization(err) => LocalDbError::JsonParse(err),
         other => LocalDbError::Rpc(other),
@@ -438,6 +436,7 @@ mod tests {
     #[cfg(not(target_family = "wasm"))]
     mod tokio_tests {
         use super::*;
+        use crate::rpc_client::RpcClient;
         use alloy::hex;
         use alloy::primitives::{Address, Bytes, B256, U256};
         use alloy::rpc::types::FilterBlockError;
@@ -1124,5 +1123,67 @@ mod tests {
             assert_eq!(events[0].block_timestamp, Some(U256::from(0x64)));
             assert_eq!(events[1].block_timestamp, Some(U256::from(0x65)));
         }
+
+        #[tokio::test]
+        async fn fetch_logs_chunks_without_backfilling_timestamps() {
+            let server = MockServer::start();
+
+            let logs_mock = server.mock(|when, then| {
+                when.method(POST)
+                    .path("/")
+                    .body_contains("\"eth_getLogs\"");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(
+                        json!({
+                            "jsonrpc":"2.0",
+                            "id":1,
+                            "result":[{
+                                "address":"0x0000000000000000000000000000000000000abc",
+                                "topics":[Set::SIGNATURE_HASH.to_string()],
+                                "data":"0xdeadbeef",
+                                "blockNumber":"0x1",
+                                "transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001",
+                                "transactionIndex":"0x0",
+                                "blockHash":"0x0000000000000000000000000000000000000000000000000000000000000001",
+                                "logIndex":"0x0",
+                                "removed":false
+                            }]
+                        })
+                        .to_string(),
+                    );
+            });
+            let block_mock = server.mock(|when, then| {
+                when.method(POST)
+                    .path("/")
+                    .body_contains("\"eth_getBlockByNumber\"");
+                then.status(500);
+            });
+
+            let filter = Filter::new()
+                .address(Address::from_str("0x0000000000000000000000000000000000000abc").unwrap())
+                .from_block(1)
+                .to_block(3)
+                .event_signature(vec![Set::SIGNATURE_HASH]);
+            let events = fetch_logs(
+                &RpcClient::new_with_urls(vec![Url::parse(&server.url("/")).unwrap()]).unwrap(),
+                &filter,
+                &FetchConfig {
+                    chunk_size: 1,
+                    max_concurrent_requests: 1,
+                    max_concurrent_blocks: 1,
+                    max_retry_attempts: 1,
+                    retry_delay_ms: 0,
+                    rate_limit_delay_ms: 0,
+                },
+            )
+            .await
+            .unwrap();
+
+            logs_mock.assert_hits(3);
+            block_mock.assert_hits(0);
+            assert_eq!(events.len(), 3);
+            assert_eq!(events[0].block_timestamp, None);
+        }
     }
 }
@@ -40,7 +40,7 @@ pub enum InsertError {
     RawEventSerialization(String),
 }
 
-fn u256_to_u64(value: &U256, field: &'static str) -> Result<u64, InsertError> {
+pub(crate) fn u256_to_u64(value: &U256, field: &'static str) -> Result<u64, InsertError> {
     value
         .try_into()
         .map_err(|_| InsertError::IoIndexOverflow { field })
@@ -0,0 +1,381 @@
+use super::fetch::map_rpc_error;
+use super::LocalDbError;
+use crate::rpc_client::{BlockResponse, LogEntryResponse, RpcClient, RpcClientError};
+use alloy::primitives::{B256, U256};
+use alloy::rpc::types::Filter;
+use async_trait::async_trait;
+use serde_json::Map;
+
+/// Provider of the raw logs, blocks and chain head the local DB syncs from.
+///
+/// The fetch helpers handle chunking, concurrency, retries and timestamp
+/// backfilling on top of any source, so implementations only answer single
+/// requests.
+#[async_trait(?Send)]
+pub trait LogSource {
+    async fn latest_block_number(&self) -> Result<u64, LocalDbError>;
+
+    async fn block_by_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<BlockResponse>, LocalDbError>;
+
+    async fn logs(&self, filter: &Filter) -> Result<Vec<LogEntryResponse>, LocalDbError>;
+}
+
+/// Lowercased fragments of the errors providers return when an `eth_getLogs`
+/// range holds more logs than they are willing to return.
+const TOO_MANY_RESULTS_PATTERNS: [&str; 7] = [
+    "too many results",
+    "query returned more than",
+    "response size exceeded",
+    "response is too big",
+    "too many logs",
+    "maximum block range",
+    "range too large",
+];
+
+/// Rate limit messages often mention limits or ranges too, and halving the
+/// range would only send more requests, so they are never treated as such.
+fn is_too_many_results(error: &RpcClientError) -> bool {
+    let RpcClientError::RpcError { message } = error else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    !message.contains("rate")
+        && TOO_MANY_RESULTS_PATTERNS
+            .iter()
+            .any(|pattern| message.contains(pattern))
+}
+
+/// JSON-RPC source, also used for HyperRPC through its URL.
+///
+/// When the provider rejects a range for returning too many results, the
+/// range is halved until it fits or a single block is still rejected.
+#[async_trait(?Send)]
+impl LogSource for RpcClient {
+    async fn latest_block_number(&self) -> Result<u64, LocalDbError> {
+        self.get_latest_block_number().await.map_err(map_rpc_error)
+    }
+
+    async fn block_by_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<BlockResponse>, LocalDbError> {
+        self.get_block_by_number(block_number)
+            .await
+            .map_err(map_rpc_error)
+    }
+
+    async fn logs(&self, filter: &Filter) -> Result<Vec<LogEntryResponse>, LocalDbError> {
+        let (Some(from_block), Some(to_block)) = (filter.get_from_block(), filter.get_to_block())
+        else {
+            return self.get_logs(filter).await.map_err(map_rpc_error);
+        };
+
+        let mut logs = Vec::new();
+        let mut pending = vec![(from_block, to_block)];
+        while let Some((start, end)) = pending.pop() {
+            let range = filter.clone().from_block(start).to_block(end);
+            match self.get_logs(&range).await {
+                Ok(mut range_logs) => logs.append(&mut range_logs),
+                Err(error) if start < end && is_too_many_results(&error) => {
+                    let mid = start + (end - start) / 2;
+                    pending.push((mid + 1, end));
+                    pending.push((start, mid));
+                }
+                Err(error) => return Err(map_rpc_error(error)),
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// Source replaying logs recorded as JSONL, one `eth_getLogs` entry per line,
+/// for offline replays and tests.
+///
+/// The chain head is the highest block holding a log, and blocks are only
+/// known through the logs they hold.
+#[derive(Debug, Clone, Default)]
+pub struct JsonlLogSource {
+    logs: Vec<LogEntryResponse>,
+}
+
+impl JsonlLogSource {
+    pub fn new(logs: Vec<LogEntryResponse>) -> Self {
+        Self { logs }
+    }
+
+    pub fn from_jsonl(jsonl: &str) -> Result<Self, LocalDbError> {
+        let logs = jsonl
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|source| LocalDbError::InvalidJsonlLog {
+                    line: index + 1,
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { logs })
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self, LocalDbError> {
+        Self::from_jsonl(&std::fs::read_to_string(path)?)
+    }
+
+    fn matches(filter: &Filter, log: &LogEntryResponse) -> bool {
+        let block_number = log.block_number.saturating_to::<u64>();
+        let in_range = filter
+            .get_from_block()
+            .is_none_or(|from_block| block_number >= from_block)
+            && filter
+                .get_to_block()
+                .is_none_or(|to_block| block_number <= to_block);
+        let topic_matches = filter.topics.iter().enumerate().all(|(index, topic)| {
+            topic.is_empty()
+                || log
+                    .topics
+                    .get(index)
+                    .and_then(|bytes| B256::try_from(bytes.as_ref()).ok())
+                    .is_some_and(|value| topic.matches(&value))
+        });
+        !log.removed && in_range && filter.address.matches(&log.address) && topic_matches
+    }
+}
+
+#[async_trait(?Send)]
+impl LogSource for JsonlLogSource {
+    async fn latest_block_number(&self) -> Result<u64, LocalDbError> {
+        Ok(self
+            .logs
+            .iter()
+            .map(|log| log.block_number.saturating_to::<u64>())
+            .max()
+            .unwrap_or_default())
+    }
+
+    async fn block_by_number(
+        &self,
+        block_number: u64,
+    ) -> Result<Option<BlockResponse>, LocalDbError> {
+        let block_number = U256::from(block_number);
+        let mut block_logs = self
+            .logs
+            .iter()
+            .filter(|log| log.block_number == block_number)
+            .peekable();
+        let Some(hash) = block_logs.peek().map(|log| log.block_hash) else {
+            return Ok(None);
+        };
+        let timestamp = block_logs
+            .find_map(|log| log.block_timestamp)
+            .ok_or_else(|| LocalDbError::MissingField {
+                field: "blockTimestamp".to_string(),
+            })?;
+        Ok(Some(BlockResponse {
+            timestamp,
+            hash,
+            extra: Map::new(),
+        }))
+    }
+
+    async fn logs(&self, filter: &Filter) -> Result<Vec<LogEntryResponse>, LocalDbError> {
+        Ok(self
+            .logs
+            .iter()
+            .filter(|log| Self::matches(filter, log))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, Bytes};
+    use serde_json::json;
+
+    fn log_line(address: &str, topic: &str, block_number: u64, timestamp: Option<u64>) -> String {
+        let mut log = json!({
+            "address": address,
+            "topics": [topic],
+            "data": "0x",
+            "blockNumber": format!("0x{block_number:x}"),
+            "transactionHash": format!("0x{:064x}", block_number),
+            "transactionIndex": "0x0",
+            "blockHash": format!("0x{:064x}", block_number + 1000),
+            "logIndex": "0x0",
+            "removed": false
+        });
+        if let Some(timestamp) = timestamp {
+            log["blockTimestamp"] = json!(format!("0x{timestamp:x}"));
+        }
+        log.to_string()
+    }
+
+    const ADDRESS_A: &str = "0x00000000000000000000000000000000000000aa";
+    const ADDRESS_B: &str = "0x00000000000000000000000000000000000000bb";
+    const TOPIC_X: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+    const TOPIC_Y: &str = "0x0000000000000000000000000000000000000000000000000000000000000002";
+
+    fn source() -> JsonlLogSource {
+        let jsonl = [
+            log_line(ADDRESS_A, TOPIC_X, 10, Some(100)),
+            String::new(),
+            log_line(ADDRESS_A, TOPIC_Y, 12, None),
+            log_line(ADDRESS_B, TOPIC_X, 15, Some(150)),
+        ]
+        .join("\n");
+        JsonlLogSource::from_jsonl(&jsonl).unwrap()
+    }
+
+    #[test]
+    fn too_many_results_detects_provider_messages() {
+        for message in [
+            "query returned more than 10000 results",
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range",
+            "exceed maximum block range: 5000",
+        ] {
+            assert!(is_too_many_results(&RpcClientError::RpcError {
+                message: message.to_string(),
+            }));
+        }
+        for message in [
+            "execution reverted",
+            "daily request limit exceeded",
+            "rate limit exceeded for block range queries",
+            "invalid block range params",
+        ] {
+            assert!(!is_too_many_results(&RpcClientError::RpcError {
+                message: message.to_string(),
+            }));
+        }
+        assert!(!is_too_many_results(&RpcClientError::RateLimited {
+            message: "too many results".to_string(),
+        }));
+    }
+
+    #[tokio::test]
+    async fn jsonl_source_filters_by_address_topic_and_range() {
+        let source = source();
+        let address_a = ADDRESS_A.parse::<Address>().unwrap();
+        let topic_x = TOPIC_X.parse::<B256>().unwrap();
+
+        let logs = source
+            .logs(&Filter::new().address(address_a).from_block(0).to_block(20))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 2);
+
+        let logs = source
+            .logs(
+                &Filter::new()
+                    .address(vec![address_a, ADDRESS_B.parse().unwrap()])
+                    .event_signature(vec![topic_x])
+                    .from_block(11)
+                    .to_block(20),
+            )
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].block_number, U256::from(15));
+        assert_eq!(
+            logs[0].topics,
+            vec![Bytes::from(topic_x.as_slice().to_vec())]
+        );
+    }
+
+    #[tokio::test]
+    async fn jsonl_source_derives_head_and_blocks_from_logs() {
+        let source = source();
+        assert_eq!(source.latest_block_number().await.unwrap(), 15);
+
+        let block = source.block_by_number(10).await.unwrap().unwrap();
+        assert_eq!(block.timestamp, U256::from(100));
+        assert_eq!(block.hash, B256::from(U256::from(1010)));
+
+        assert!(source.block_by_number(11).await.unwrap().is_none());
+        assert!(matches!(
+            source.block_by_number(12).await,
+            Err(LocalDbError::MissingField { .. })
+        ));
+        assert_eq!(
+            JsonlLogSource::default()
+                .latest_block_number()
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn jsonl_source_reports_invalid_line() {
+        let jsonl = format!("{}\nnot json", log_line(ADDRESS_A, TOPIC_X, 1, Some(1)));
+        assert!(matches!(
+            JsonlLogSource::from_jsonl(&jsonl),
+            Err(LocalDbError::InvalidJsonlLog { line: 2, .. })
+        ));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[tokio::test]
+    async fn rpc_source_halves_ranges_rejected_for_too_many_results() {
+        use httpmock::prelude::*;
+        use url::Url;
+
+        let server = MockServer::start();
+        let too_many = server.mock(|when, then| {
+            when.method(POST)
+                .body_contains("\"eth_getLogs\"")
+                .body_contains("\"fromBlock\":\"0x1\"")
+                .body_contains("\"toBlock\":\"0x4\"");
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": -32005, "message": "query returned more than 10000 results"}
+            }));
+        });
+        let lower_half = server.mock(|when, then| {
+            when.method(POST)
+                .body_contains("\"fromBlock\":\"0x1\"")
+                .body_contains("\"toBlock\":\"0x2\"");
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": [serde_json::from_str::<serde_json::Value>(&log_line(ADDRESS_A, TOPIC_X, 2, Some(20))).unwrap()]
+            }));
+        });
+        let upper_half = server.mock(|when, then| {
+            when.method(POST)
+                .body_contains("\"fromBlock\":\"0x3\"")
+                .body_contains("\"toBlock\":\"0x4\"");
+            then.status(200).json_body(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": [serde_json::from_str::<serde_json::Value>(&log_line(ADDRESS_A, TOPIC_X, 4, Some(40))).unwrap()]
+            }));
+        });
+
+        let client = RpcClient::new_with_urls(vec![Url::parse(&server.url("/")).unwrap()]).unwrap();
+        let logs = LogSource::logs(
+            &client,
+            &Filter::new()
+                .address(ADDRESS_A.parse::<Address>().unwrap())
+                .from_block(1)
+                .to_block(4),
+        )
+        .await
+        .unwrap();
+
+        too_many.assert();
+        lower_half.assert();
+        upper_half.assert();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![U256::from(2), U256::from(4)]
+        );
+    }
+}
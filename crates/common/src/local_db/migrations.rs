@@ -0,0 +1,313 @@
+use crate::local_db::query::db_migrations::{
+    create_db_migrations_table_stmt, insert_db_migration_stmt, update_db_schema_version_stmt,
+};
+use crate::local_db::query::fetch_db_metadata::{fetch_db_metadata_stmt, DbMetadataRow};
+use crate::local_db::query::{LocalDbQueryExecutor, SqlStatement, SqlStatementBatch};
+use crate::local_db::LocalDbError;
+use serde::{Deserialize, Serialize};
+
+/// Schema step taking a database from `version - 1` to `version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Migrations in version order, each one version above the previous.
+///
+/// Bumping `DB_SCHEMA_VERSION` for an additive change appends the migration
+/// reaching the new version here, alongside the same change to the
+/// `create_tables` schema used by fresh databases. Databases on an older
+/// version are then migrated in place instead of being reset.
+pub const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub migrations: Vec<AppliedMigration>,
+    /// Whether the migrations were only planned and left unapplied
+    pub dry_run: bool,
+}
+
+/// Migrations of the registry taking a database from `from` to `to`, empty
+/// when it is already at `to`.
+pub fn migration_path(
+    registry: &[Migration],
+    from: u32,
+    to: u32,
+) -> Result<&[Migration], LocalDbError> {
+    if from == to {
+        return Ok(&[]);
+    }
+    let no_path = || LocalDbError::NoMigrationPath { from, to };
+    if from > to {
+        return Err(no_path());
+    }
+
+    let start = registry
+        .iter()
+        .position(|migration| migration.version == from + 1)
+        .ok_or_else(no_path)?;
+    let end = start + (to - from) as usize;
+    let path = registry.get(start..end).ok_or_else(no_path)?;
+    let is_contiguous = path
+        .iter()
+        .zip(from + 1..=to)
+        .all(|(migration, version)| migration.version == version);
+    if !is_contiguous {
+        return Err(no_path());
+    }
+    Ok(path)
+}
+
+/// Transaction running a migration's SQL, recording it in `db_migrations` and
+/// moving `db_metadata` to its version, so a failing migration leaves the
+/// database on the previous version.
+pub fn migration_batch(migration: &Migration) -> SqlStatementBatch {
+    SqlStatementBatch::with_statements(vec![
+        create_db_migrations_table_stmt(),
+        SqlStatement::new(migration.sql),
+        insert_db_migration_stmt(migration.version, migration.name),
+        update_db_schema_version_stmt(migration.version),
+    ])
+    .ensure_transaction()
+}
+
+/// Applies the migrations in order, each in its own transaction.
+pub async fn apply_migrations<DB>(db: &DB, migrations: &[Migration]) -> Result<(), LocalDbError>
+where
+    DB: LocalDbQueryExecutor + ?Sized,
+{
+    for migration in migrations {
+        db.execute_batch(&migration_batch(migration))
+            .await
+            .map_err(|source| LocalDbError::MigrationFailed {
+                version: migration.version,
+                name: migration.name.to_string(),
+                source,
+            })?;
+    }
+    Ok(())
+}
+
+/// Migrates the database from its `db_metadata` version to `to`, or only
+/// reports the migrations it would apply when `dry_run` is set.
+pub async fn migrate<DB>(
+    db: &DB,
+    registry: &[Migration],
+    to: u32,
+    dry_run: bool,
+) -> Result<MigrationReport, LocalDbError>
+where
+    DB: LocalDbQueryExecutor + ?Sized,
+{
+    let rows: Vec<DbMetadataRow> = db.query_json(&fetch_db_metadata_stmt()).await?;
+    let from = rows
+        .first()
+        .map(|row| row.db_schema_version)
+        .ok_or(LocalDbError::MissingDbMetadataRow)?;
+
+    let path = migration_path(registry, from, to)?;
+    if !dry_run {
+        apply_migrations(db, path).await?;
+    }
+
+    Ok(MigrationReport {
+        from_version: from,
+        to_version: to,
+        migrations: path
+            .iter()
+            .map(|migration| AppliedMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+            })
+            .collect(),
+        dry_run,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_db::query::{FromDbJson, LocalDbQueryError};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    const REGISTRY: &[Migration] = &[
+        Migration {
+            version: 3,
+            name: "add_orders_index",
+            sql: "CREATE INDEX IF NOT EXISTS idx_test ON order_events(order_hash);",
+        },
+        Migration {
+            version: 4,
+            name: "add_notes",
+            sql: "ALTER TABLE order_events ADD COLUMN note TEXT;",
+        },
+    ];
+
+    struct MockDb {
+        version: Option<u32>,
+        fail_sql: Option<&'static str>,
+        batches: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl MockDb {
+        fn new(version: Option<u32>) -> Self {
+            Self {
+                version,
+                fail_sql: None,
+                batches: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[cfg_attr(target_family = "wasm", async_trait(?Send))]
+    #[cfg_attr(not(target_family = "wasm"), async_trait)]
+    impl LocalDbQueryExecutor for MockDb {
+        async fn execute_batch(&self, batch: &SqlStatementBatch) -> Result<(), LocalDbQueryError> {
+            let sqls: Vec<String> = batch
+                .statements()
+                .iter()
+                .map(|stmt| stmt.sql().to_string())
+                .collect();
+            if self
+                .fail_sql
+                .is_some_and(|fail_sql| sqls.iter().any(|sql| sql == fail_sql))
+            {
+                return Err(LocalDbQueryError::database("boom"));
+            }
+            self.batches.lock().unwrap().push(sqls);
+            Ok(())
+        }
+
+        async fn query_json<T>(&self, _: &SqlStatement) -> Result<T, LocalDbQueryError>
+        where
+            T: FromDbJson,
+        {
+            let rows: Vec<DbMetadataRow> = self
+                .version
+                .map(|db_schema_version| DbMetadataRow {
+                    id: 1,
+                    db_schema_version,
+                    created_at: None,
+                    updated_at: None,
+                })
+                .into_iter()
+                .collect();
+            serde_json::from_value(serde_json::to_value(rows).unwrap())
+                .map_err(|e| LocalDbQueryError::deserialization(e.to_string()))
+        }
+
+        async fn query_text(&self, _: &SqlStatement) -> Result<String, LocalDbQueryError> {
+            Ok(String::new())
+        }
+
+        async fn wipe_and_recreate(&self) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn migration_path_selects_contiguous_steps() {
+        assert!(migration_path(REGISTRY, 2, 2).unwrap().is_empty());
+        assert_eq!(migration_path(REGISTRY, 2, 4).unwrap(), REGISTRY);
+        assert_eq!(migration_path(REGISTRY, 3, 4).unwrap(), &REGISTRY[1..]);
+        assert_eq!(migration_path(REGISTRY, 2, 3).unwrap(), &REGISTRY[..1]);
+
+        for (from, to) in [(1, 3), (2, 5), (4, 3)] {
+            assert!(matches!(
+                migration_path(REGISTRY, from, to),
+                Err(LocalDbError::NoMigrationPath { .. })
+            ));
+        }
+
+        let gap = &[
+            REGISTRY[0],
+            Migration {
+                version: 5,
+                ..REGISTRY[1]
+            },
+        ];
+        assert!(migration_path(gap, 2, 4).is_err());
+    }
+
+    #[test]
+    fn migration_batch_records_and_bumps_version_in_one_transaction() {
+        let batch = migration_batch(&REGISTRY[0]);
+        assert!(batch.is_transaction());
+        let statements = batch.statements();
+        assert_eq!(statements.len(), 6);
+        assert_eq!(statements[2].sql(), REGISTRY[0].sql);
+        assert_eq!(
+            statements[3],
+            insert_db_migration_stmt(3, "add_orders_index")
+        );
+        assert_eq!(statements[4], update_db_schema_version_stmt(3));
+    }
+
+    #[tokio::test]
+    async fn migrate_applies_each_migration_in_order() {
+        let db = MockDb::new(Some(2));
+        let report = migrate(&db, REGISTRY, 4, false).await.unwrap();
+
+        assert_eq!(report.from_version, 2);
+        assert_eq!(report.to_version, 4);
+        assert_eq!(
+            report.migrations,
+            vec![
+                AppliedMigration {
+                    version: 3,
+                    name: "add_orders_index".to_string()
+                },
+                AppliedMigration {
+                    version: 4,
+                    name: "add_notes".to_string()
+                },
+            ]
+        );
+        let batches = db.batches.lock().unwrap();
+        assert_eq!(batches.len(), 2);
+        assert!(batches[0].contains(&REGISTRY[0].sql.to_string()));
+        assert!(batches[1].contains(&REGISTRY[1].sql.to_string()));
+    }
+
+    #[tokio::test]
+    async fn migrate_dry_run_leaves_db_untouched() {
+        let db = MockDb::new(Some(3));
+        let report = migrate(&db, REGISTRY, 4, true).await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.migrations.len(), 1);
+        assert!(db.batches.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn migrate_reports_failing_migration() {
+        let db = MockDb {
+            fail_sql: Some(REGISTRY[1].sql),
+            ..MockDb::new(Some(2))
+        };
+        let err = migrate(&db, REGISTRY, 4, false).await.unwrap_err();
+        assert!(matches!(
+            err,
+            LocalDbError::MigrationFailed { version: 4, .. }
+        ));
+        assert_eq!(db.batches.lock().unwrap().len(), 1);
+
+        let db = MockDb::new(None);
+        assert!(matches!(
+            migrate(&db, REGISTRY, 4, false).await,
+            Err(LocalDbError::MissingDbMetadataRow)
+        ));
+    }
+}
@@ -7,6 +7,8 @@ pub mod fetch;
 #[cfg(not(target_family = "wasm"))]
 pub mod functions;
 pub mod insert;
+pub mod log_source;
+pub mod migrations;
 pub mod pipeline;
 pub mod query;
 pub mod token_fetch;
@@ -14,7 +16,7 @@ pub mod token_fetch;
 use crate::erc20::Error as TokenError;
 use crate::rpc_client::RpcClientError;
 use alloy::primitives::ruint::ParseError;
-use alloy::primitives::{hex::FromHexError, Address};
+use alloy::primitives::{hex::FromHexError, Address, B256};
 use alloy::rpc::types::FilterBlockError;
 use decode::DecodeError;
 pub use fetch::{FetchConfig, FetchConfigError};
@@ -22,6 +24,7 @@ use insert::InsertError;
 use query::{LocalDbQueryError, SqlBuildError};
 use rain_orderbook_app_settings::remote::manifest::FetchManifestError;
 use rain_orderbook_app_settings::yaml::YamlError;
+use rain_orderbook_subgraph_client::OrderbookSubgraphClientError;
 use serde::{Deserialize, Serialize};
 use std::array::TryFromSliceError;
 use std::num::ParseIntError;
@@ -98,6 +101,15 @@ pub enum LocalDbError {
         found: u32,
     },
 
+    #[error(
+        "Manifest entry for chain {chain_id} orderbook {orderbook_address} does not end at delta start block {start_block}"
+    )]
+    ManifestDeltaBaseMismatch {
+        chain_id: u32,
+        orderbook_address: Address,
+        start_block: u64,
+    },
+
     #[error("Failed to build dump url '{url}'")]
     DumpUrlConstructionFailed {
         url: String,
@@ -131,6 +143,20 @@ pub enum LocalDbError {
     #[error("HTTP request failed with status: {status}")]
     HttpStatus { status: u16 },
 
+    #[error("Dump at {url} has SHA-256 {actual}, manifest expects {expected}")]
+    DumpChecksumMismatch {
+        url: String,
+        expected: B256,
+        actual: B256,
+    },
+
+    #[error("Manifest at {url} is not validly signed by publisher {publisher}: {reason}")]
+    ManifestSignatureInvalid {
+        url: String,
+        publisher: Address,
+        reason: String,
+    },
+
     #[error(transparent)]
     ManifestFetch(#[from] FetchManifestError),
 
@@ -160,6 +186,30 @@ pub enum LocalDbError {
     #[error("Database schema version mismatch: expected {expected}, found {found}")]
     SchemaVersionMismatch { expected: u32, found: u32 },
 
+    #[error("Invalid log on line {line} of the JSONL log file: {source}")]
+    InvalidJsonlLog {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    Subgraph(#[from] OrderbookSubgraphClientError),
+
+    #[error("Invalid subgraph {field} '{value}'")]
+    InvalidSubgraphValue { field: &'static str, value: String },
+
+    #[error("No migration path from schema version {from} to {to}")]
+    NoMigrationPath { from: u32, to: u32 },
+
+    #[error("Migration {version} ({name}) failed: {source}")]
+    MigrationFailed {
+        version: u32,
+        name: String,
+        #[source]
+        source: LocalDbQueryError,
+    },
+
     #[error("Invalid bootstrap implementation")]
     InvalidBootstrapImplementation,
 
@@ -264,6 +314,14 @@ impl LocalDbError {
                 "Network '{}' has mismatched chain ids (expected {}, found {})",
                 network_key, expected, found
             ),
+            LocalDbError::ManifestDeltaBaseMismatch {
+                chain_id,
+                orderbook_address,
+                start_block,
+            } => format!(
+                "The previous manifest entry for chain {} orderbook {:#x} does not end at block {}, so a delta starting there cannot be published",
+                chain_id, orderbook_address, start_block
+            ),
             LocalDbError::DumpUrlConstructionFailed { url, source } => {
                 format!("Failed to build dump url '{}': {}", url, source)
             }
@@ -289,6 +347,22 @@ impl LocalDbError {
             LocalDbError::HttpStatus { status } => {
                 format!("HTTP request failed with status code: {}", status)
             }
+            LocalDbError::DumpChecksumMismatch {
+                url,
+                expected,
+                actual,
+            } => format!(
+                "The dump downloaded from {} does not match the manifest checksum (expected SHA-256 {}, got {}) and was not imported",
+                url, expected, actual
+            ),
+            LocalDbError::ManifestSignatureInvalid {
+                url,
+                publisher,
+                reason,
+            } => format!(
+                "The manifest at {} could not be verified as published by {:#x}: {}",
+                url, publisher, reason
+            ),
             LocalDbError::ManifestFetch(err) => format!("Failed to fetch manifest: {}", err),
             LocalDbError::TaskJoin(err) => format!("Task join error: {}", err),
             LocalDbError::LocalDbQueryError(err) => format!("Database query error: {}", err),
@@ -307,6 +381,25 @@ impl LocalDbError {
                 "Database schema version mismatch: expected {}, found {}",
                 expected, found
             ),
+            LocalDbError::InvalidJsonlLog { line, source } => {
+                format!("Invalid log on line {} of the JSONL log file: {}", line, source)
+            }
+            LocalDbError::Subgraph(err) => format!("Subgraph error: {}", err),
+            LocalDbError::InvalidSubgraphValue { field, value } => {
+                format!("Invalid {} '{}' returned by the subgraph", field, value)
+            }
+            LocalDbError::NoMigrationPath { from, to } => format!(
+                "No migration path from database schema version {} to {}",
+                from, to
+            ),
+            LocalDbError::MigrationFailed {
+                version,
+                name,
+                source,
+            } => format!(
+                "Database migration {} ({}) failed: {}",
+                version, name, source
+            ),
             LocalDbError::InvalidBootstrapImplementation => {
                 "This bootstrap implementation is invalid.".to_string()
             }
@@ -1,3 +1,4 @@
+use crate::local_db::migrations::{apply_migrations, migration_path, MIGRATIONS};
 use crate::local_db::query::clear_orderbook_data::clear_orderbook_data_batch;
 use crate::local_db::query::clear_tables::clear_tables_stmt;
 use crate::local_db::query::create_tables::create_tables_stmt;
@@ -20,6 +21,8 @@ use std::collections::HashSet;
 pub struct BootstrapConfig {
     pub ob_id: OrderbookIdentifier,
     pub dump_stmt: Option<SqlStatementBatch>,
+    /// Manifest deltas continuing the orderbook rows already in the database.
+    pub delta_stmt: Option<SqlStatementBatch>,
     pub latest_block: u64,
     pub block_number_threshold: u32,
     pub deployment_block: u64,
@@ -118,6 +121,17 @@ pub trait BootstrapPipeline {
         Ok(())
     }
 
+    /// Migrates the schema in place from version `from` to `to` through the
+    /// migration registry, failing with `NoMigrationPath` when it has no
+    /// contiguous path between them.
+    async fn migrate_schema<DB>(&self, db: &DB, from: u32, to: u32) -> Result<(), LocalDbError>
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
+        let migrations = migration_path(MIGRATIONS, from, to)?;
+        apply_migrations(db, migrations).await
+    }
+
     async fn check_integrity<DB>(&self, db: &DB) -> Result<bool, LocalDbError>
     where
         DB: LocalDbQueryExecutor + ?Sized,
@@ -364,6 +378,24 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn migrate_schema_without_path_leaves_db_untouched() {
+        let adapter = TestBootstrapPipeline::new();
+        let db = MockDb::default();
+
+        adapter
+            .migrate_schema(&db, DB_SCHEMA_VERSION, DB_SCHEMA_VERSION)
+            .await
+            .expect("nothing to migrate");
+
+        let err = adapter
+            .migrate_schema(&db, DB_SCHEMA_VERSION + 1, DB_SCHEMA_VERSION)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LocalDbError::NoMigrationPath { .. }));
+        assert!(db.calls().is_empty());
+    }
+
     #[tokio::test]
     async fn ensure_schema_honors_override_ok() {
         let adapter = TestBootstrapPipeline::new();
@@ -703,6 +735,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: OrderbookIdentifier::new(1, Address::ZERO),
             dump_stmt: None,
+            delta_stmt: None,
             latest_block: 0,
             block_number_threshold: 10_000,
             deployment_block: 1,
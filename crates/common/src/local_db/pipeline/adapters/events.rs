@@ -4,6 +4,7 @@ use url::Url;
 
 use crate::local_db::decode::{decode_events, DecodedEvent, DecodedEventData};
 use crate::local_db::fetch::{fetch_orderbook_events, fetch_store_events};
+use crate::local_db::log_source::LogSource;
 use crate::local_db::pipeline::EventsPipeline;
 use crate::local_db::{FetchConfig, LocalDbError};
 use crate::rpc_client::{LogEntryResponse, RpcClient};
@@ -12,40 +13,54 @@ use crate::rpc_client::{LogEntryResponse, RpcClient};
 ///
 /// Construction determines the backend policy:
 /// - `with_regular_rpcs` for browser/public RPCs
-/// - `with_hyperrpc` for producer/HyperRPC
-/// - `from_local_db` if the runner builds a LocalDb externally
+/// - `with_hyperrpc` / `with_hyperrpc_url` for producer/HyperRPC
+/// - `with_source` for any other [`LogSource`], e.g. a JSONL replay
 #[derive(Debug, Clone)]
-pub struct DefaultEventsPipeline {
-    rpc_client: RpcClient,
+pub struct DefaultEventsPipeline<S = RpcClient> {
+    source: S,
 }
 
 impl DefaultEventsPipeline {
     /// Constructs the pipeline using regular/public RPC URLs.
     pub fn with_regular_rpcs(rpcs: Vec<Url>) -> Result<Self, LocalDbError> {
         let rpc_client = RpcClient::new_with_urls(rpcs)?;
-        Ok(Self { rpc_client })
+        Ok(Self { source: rpc_client })
     }
 
     /// Constructs the pipeline using HyperRPC (producer path).
     pub fn with_hyperrpc(chain_id: u32, api_token: String) -> Result<Self, LocalDbError> {
         let rpc_client = RpcClient::new_with_hyper_rpc(chain_id, &api_token)?;
-        Ok(Self { rpc_client })
+        Ok(Self { source: rpc_client })
+    }
+
+    /// Constructs the pipeline using HyperRPC at a configured URL template,
+    /// for chains without a built-in HyperRPC URL.
+    pub fn with_hyperrpc_url(
+        chain_id: u32,
+        url_template: &str,
+        api_token: String,
+    ) -> Result<Self, LocalDbError> {
+        let rpc_client = RpcClient::new_with_hyper_rpc_url(chain_id, url_template, &api_token)?;
+        Ok(Self { source: rpc_client })
+    }
+}
+
+impl<S: LogSource> DefaultEventsPipeline<S> {
+    pub fn with_source(source: S) -> Self {
+        Self { source }
     }
 }
 
 #[async_trait(?Send)]
-impl EventsPipeline for DefaultEventsPipeline {
+impl<S: LogSource> EventsPipeline for DefaultEventsPipeline<S> {
     async fn latest_block(&self) -> Result<u64, LocalDbError> {
-        self.rpc_client
-            .get_latest_block_number()
-            .await
-            .map_err(Into::into)
+        self.source.latest_block_number().await
     }
 
     async fn block_hash(&self, block_number: u64) -> Result<B256, LocalDbError> {
         let block = self
-            .rpc_client
-            .get_block_by_number(block_number)
+            .source
+            .block_by_number(block_number)
             .await?
             .ok_or_else(|| LocalDbError::BlockHashNotFound { block_number })?;
         Ok(block.hash)
@@ -58,14 +73,7 @@ impl EventsPipeline for DefaultEventsPipeline {
         to_block: u64,
         cfg: &FetchConfig,
     ) -> Result<Vec<LogEntryResponse>, LocalDbError> {
-        fetch_orderbook_events(
-            &self.rpc_client,
-            orderbook_address,
-            from_block,
-            to_block,
-            cfg,
-        )
-        .await
+        fetch_orderbook_events(&self.source, orderbook_address, from_block, to_block, cfg).await
     }
 
     async fn fetch_stores(
@@ -75,7 +83,7 @@ impl EventsPipeline for DefaultEventsPipeline {
         to_block: u64,
         cfg: &FetchConfig,
     ) -> Result<Vec<LogEntryResponse>, LocalDbError> {
-        fetch_store_events(&self.rpc_client, store_addresses, from_block, to_block, cfg).await
+        fetch_store_events(&self.source, store_addresses, from_block, to_block, cfg).await
     }
 
     fn decode(
@@ -89,6 +97,7 @@ impl EventsPipeline for DefaultEventsPipeline {
 #[cfg(all(test, not(target_family = "wasm")))]
 mod tests {
     use super::*;
+    use crate::local_db::log_source::JsonlLogSource;
     use crate::rpc_client::RpcClientError;
     use alloy::primitives::{b256, Bytes, U256};
     use alloy::sol_types::SolEvent;
@@ -192,7 +201,7 @@ mod tests {
 
         let mut pipeline =
             DefaultEventsPipeline::with_hyperrpc(137, "token".to_string()).expect("valid pipeline");
-        pipeline.rpc_client.update_rpc_urls(vec![
+        pipeline.source.update_rpc_urls(vec![
             Url::parse(&server.base_url()).expect("valid server url")
         ]);
 
@@ -205,4 +214,44 @@ mod tests {
 
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn with_source_reads_from_jsonl_logs() {
+        let jsonl = json!({
+            "address": "0x0000000000000000000000000000000000000123",
+            "topics": [MetaV1_2::SIGNATURE_HASH.to_string()],
+            "data": "0x",
+            "blockNumber": "0x64",
+            "blockTimestamp": "0x05",
+            "transactionHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "transactionIndex": "0x0",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000002",
+            "logIndex": "0x0",
+            "removed": false
+        })
+        .to_string();
+        let pipeline = DefaultEventsPipeline::with_source(
+            JsonlLogSource::from_jsonl(&jsonl).expect("valid jsonl"),
+        );
+
+        assert_eq!(pipeline.latest_block().await.unwrap(), 100);
+        assert_eq!(
+            pipeline.block_hash(100).await.unwrap(),
+            B256::with_last_byte(2)
+        );
+        let logs = pipeline
+            .fetch_orderbook(
+                Address::from_str("0x0000000000000000000000000000000000000123").unwrap(),
+                0,
+                100,
+                &FetchConfig::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(matches!(
+            pipeline.block_hash(99).await,
+            Err(LocalDbError::BlockHashNotFound { block_number: 99 })
+        ));
+    }
 }
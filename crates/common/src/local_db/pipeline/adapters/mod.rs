@@ -1,5 +1,6 @@
 pub mod apply;
 pub mod bootstrap;
 pub mod events;
+pub mod subgraph;
 pub mod tokens;
 pub mod window;
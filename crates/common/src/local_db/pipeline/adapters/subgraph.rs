@@ -0,0 +1,1044 @@
+use crate::erc20::TokenInfo;
+use crate::local_db::decode::{
+    sort_decoded_events_by_block_and_log, DecodedEvent, DecodedEventData, EventType,
+};
+use crate::local_db::insert::{decoded_events_to_statements, generate_erc20_token_statements};
+use crate::local_db::pipeline::adapters::bootstrap::{
+    BootstrapConfig, BootstrapPipeline, BootstrapState,
+};
+use crate::local_db::query::seed_vault_balances::{
+    seed_vault_balances_batch, SeedVaultBalanceChange,
+};
+use crate::local_db::query::upsert_target_watermark::upsert_target_watermark_stmt;
+use crate::local_db::query::{LocalDbQueryExecutor, SqlStatementBatch};
+use crate::local_db::{LocalDbError, OrderbookIdentifier};
+use alloy::hex;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::sol_types::SolValue;
+use async_trait::async_trait;
+use rain_math_float::Float;
+use rain_orderbook_bindings::IRaindexV6::{
+    AddOrderV3, DepositV2, OrderV4, RemoveOrderV3, TakeOrderConfigV4, TakeOrderV3, WithdrawV2, IOV2,
+};
+use rain_orderbook_subgraph_client::types::common::{
+    SgBigInt, SgBytes, SgErc20, SgOrder, SgTrade, SgTransaction, SgVault, SgVaultBalanceChangeType,
+    SgVaultBalanceChangeVault, SgVaultsListFilterArgs,
+};
+use rain_orderbook_subgraph_client::types::Id;
+use rain_orderbook_subgraph_client::{OrderbookSubgraphClient, SgPaginationArgs};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+use url::Url;
+
+const SUBGRAPH_PAGE_SIZE: u16 = 200;
+const TAKE_ORDER_TYPENAME: &str = "TakeOrder";
+
+/// Bootstrap adapter seeding an orderbook from its subgraph instead of
+/// replaying every log since `deployment_block`.
+///
+/// Runs the wrapped adapter first, so dumps and schema handling keep their
+/// environment policy. When that leaves the orderbook without a watermark,
+/// the subgraph state up to its indexed block is written in one transaction
+/// and the watermark is moved there, so the log-based sync resumes from the
+/// following block. Without a subgraph URL only the wrapped adapter runs.
+#[derive(Debug)]
+pub struct SubgraphBootstrapAdapter<B> {
+    inner: B,
+    client: Option<OrderbookSubgraphClient>,
+}
+
+impl<B> SubgraphBootstrapAdapter<B> {
+    pub fn new(inner: B, subgraph_url: Option<Url>) -> Self {
+        Self {
+            inner,
+            client: subgraph_url.map(OrderbookSubgraphClient::new),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<B> BootstrapPipeline for SubgraphBootstrapAdapter<B>
+where
+    B: BootstrapPipeline,
+{
+    async fn engine_run<DB>(&self, db: &DB, config: &BootstrapConfig) -> Result<(), LocalDbError>
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
+        self.inner.engine_run(db, config).await?;
+        let Some(client) = &self.client else {
+            return Ok(());
+        };
+
+        let BootstrapState {
+            last_synced_block, ..
+        } = self.inspect_state(db, &config.ob_id).await?;
+        if last_synced_block.is_some() {
+            return Ok(());
+        }
+
+        let snapshot = SubgraphSnapshot::fetch(client, &config.ob_id).await?;
+        let batch = subgraph_snapshot_batch(&config.ob_id, &snapshot)?;
+        db.execute_batch(&batch.ensure_transaction()).await?;
+        Ok(())
+    }
+
+    async fn runner_run<DB>(
+        &self,
+        db: &DB,
+        db_schema_version: Option<u32>,
+    ) -> Result<(), LocalDbError>
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
+        self.inner.runner_run(db, db_schema_version).await
+    }
+}
+
+/// Orderbook state read from the subgraph. Entities from blocks after
+/// `block_number` are ignored when seeding, since indexing keeps going while
+/// the pages are fetched.
+#[derive(Debug, Clone)]
+pub struct SubgraphSnapshot {
+    pub block_number: u64,
+    pub block_hash: Option<B256>,
+    pub orders: Vec<SgOrder>,
+    pub trades: Vec<SgTrade>,
+    pub vaults: Vec<SubgraphVault>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubgraphVault {
+    pub vault: SgVault,
+    pub balance_changes: Vec<SgVaultBalanceChangeType>,
+}
+
+impl SubgraphSnapshot {
+    pub async fn fetch(
+        client: &OrderbookSubgraphClient,
+        ob_id: &OrderbookIdentifier,
+    ) -> Result<Self, LocalDbError> {
+        let meta = client.latest_indexed_block().await?;
+        let block_number = u64::try_from(meta.number)
+            .map_err(|_| invalid_value("indexed block", meta.number.to_string()))?;
+        let block_hash = meta
+            .hash
+            .as_ref()
+            .map(|hash| parse_b256("indexed block hash", hash))
+            .transpose()?;
+
+        let mut orders = vec![];
+        for order in client.orders_list_all().await? {
+            if parse_address("orderbook", &order.orderbook.id)? == ob_id.orderbook_address {
+                orders.push(order);
+            }
+        }
+
+        let mut trades = vec![];
+        for order in &orders {
+            trades.extend(
+                client
+                    .order_trades_list_all(Id::new(order.id.0.as_str()), None, None)
+                    .await?,
+            );
+        }
+
+        let mut vaults = vec![];
+        for vault in fetch_orderbook_vaults(client, ob_id.orderbook_address).await? {
+            let balance_changes = client
+                .vault_balance_changes_list_all(Id::new(vault.id.0.as_str()), None)
+                .await?;
+            vaults.push(SubgraphVault {
+                vault,
+                balance_changes,
+            });
+        }
+
+        Ok(Self {
+            block_number,
+            block_hash,
+            orders,
+            trades,
+            vaults,
+        })
+    }
+}
+
+/// Every vault of the orderbook, including emptied ones whose history still
+/// has to be seeded.
+async fn fetch_orderbook_vaults(
+    client: &OrderbookSubgraphClient,
+    orderbook: Address,
+) -> Result<Vec<SgVault>, LocalDbError> {
+    let mut vaults = vec![];
+    let mut page = 1;
+    loop {
+        let page_data = client
+            .vaults_list(
+                SgVaultsListFilterArgs {
+                    owners: vec![],
+                    hide_zero_balance: false,
+                    tokens: vec![],
+                    orderbooks: vec![hex::encode_prefixed(orderbook)],
+                    only_active_orders: false,
+                },
+                SgPaginationArgs {
+                    page,
+                    page_size: SUBGRAPH_PAGE_SIZE,
+                },
+            )
+            .await?;
+        let batch_len = page_data.len();
+        vaults.extend(page_data);
+        if batch_len < usize::from(SUBGRAPH_PAGE_SIZE) {
+            return Ok(vaults);
+        }
+        page += 1;
+    }
+}
+
+/// Builds the batch seeding `erc20_tokens`, `order_events`, `order_ios`,
+/// `deposits`, `withdrawals`, `take_orders`, `vault_balance_changes` and
+/// `running_vault_balances` from the snapshot, then moving the watermark to
+/// its block.
+///
+/// The subgraph exposes no log indexes, so rows get synthetic ones: their
+/// position within the transaction with entities ordered by subgraph id.
+/// Logs synced afterwards all come from later blocks and cannot collide.
+///
+/// Clears are only reflected in running vault balances. The subgraph's clear
+/// entity does not say which order was alice and which was bob, so clear and
+/// clear bounty changes get no `vault_balance_changes` row rather than a
+/// wrong `CLEAR_*` kind. Per order metrics built on those rows leave out
+/// clears from before the snapshot block, and the balances are lost if a
+/// reorg rebuilds running balances from `vault_deltas` below it.
+pub fn subgraph_snapshot_batch(
+    ob_id: &OrderbookIdentifier,
+    snapshot: &SubgraphSnapshot,
+) -> Result<SqlStatementBatch, LocalDbError> {
+    let in_snapshot = |transaction: &SgTransaction| -> Result<bool, LocalDbError> {
+        Ok(parse_u64("block number", &transaction.block_number)? <= snapshot.block_number)
+    };
+
+    let mut tokens: BTreeMap<Address, TokenInfo> = BTreeMap::new();
+    let mut orders: HashMap<&str, OrderV4> = HashMap::new();
+    for order in &snapshot.orders {
+        orders.insert(order.id.0.as_str(), decode_order(&order.order_bytes)?);
+        for vault in order.inputs.iter().chain(&order.outputs) {
+            let (address, info) = token_info(&vault.token)?;
+            tokens.insert(address, info);
+        }
+    }
+    for SubgraphVault { vault, .. } in &snapshot.vaults {
+        let (address, info) = token_info(&vault.token)?;
+        tokens.insert(address, info);
+    }
+
+    // Assign the synthetic log indexes before building any row.
+    let mut log_indexes = LogIndexes::default();
+    for order in &snapshot.orders {
+        for event in &order.add_events {
+            if in_snapshot(&event.transaction)? {
+                log_indexes.register(&event.transaction, format!("add:{}", order.id.0))?;
+            }
+        }
+        for event in &order.remove_events {
+            if in_snapshot(&event.transaction)? {
+                log_indexes.register(&event.transaction, format!("remove:{}", order.id.0))?;
+            }
+        }
+    }
+    let mut change_keys: HashMap<&str, String> = HashMap::new();
+    for trade in &snapshot.trades {
+        if !is_take_order(trade) || !in_snapshot(&trade.trade_event.transaction)? {
+            continue;
+        }
+        let key = format!("trade:{}", trade.id.0);
+        log_indexes.register(&trade.trade_event.transaction, key.clone())?;
+        change_keys.insert(trade.input_vault_balance_change.id.0.as_str(), key.clone());
+        change_keys.insert(trade.output_vault_balance_change.id.0.as_str(), key);
+    }
+    for SubgraphVault {
+        balance_changes, ..
+    } in &snapshot.vaults
+    {
+        for change in balance_changes {
+            let Some(parts) = ChangeParts::of(change) else {
+                continue;
+            };
+            if in_snapshot(parts.transaction)? {
+                log_indexes.register(parts.transaction, parts.key(&change_keys))?;
+            }
+        }
+    }
+
+    let mut events = vec![];
+    for order in &snapshot.orders {
+        let decoded = &orders[order.id.0.as_str()];
+        let order_hash = parse_b256("order hash", &order.order_hash)?;
+        for event in &order.add_events {
+            if in_snapshot(&event.transaction)? {
+                events.push(log_indexes.event(
+                    &event.transaction,
+                    &format!("add:{}", order.id.0),
+                    EventType::AddOrderV3,
+                    DecodedEvent::AddOrderV3(Box::new(AddOrderV3 {
+                        sender: decoded.owner,
+                        orderHash: order_hash,
+                        order: decoded.clone(),
+                    })),
+                )?);
+            }
+        }
+        for event in &order.remove_events {
+            if in_snapshot(&event.transaction)? {
+                events.push(log_indexes.event(
+                    &event.transaction,
+                    &format!("remove:{}", order.id.0),
+                    EventType::RemoveOrderV3,
+                    DecodedEvent::RemoveOrderV3(Box::new(RemoveOrderV3 {
+                        sender: decoded.owner,
+                        orderHash: order_hash,
+                        order: decoded.clone(),
+                    })),
+                )?);
+            }
+        }
+    }
+
+    for trade in &snapshot.trades {
+        if !is_take_order(trade) || !in_snapshot(&trade.trade_event.transaction)? {
+            continue;
+        }
+        let order = orders
+            .get(trade.order.id.0.as_str())
+            .ok_or_else(|| invalid_value("trade order", trade.order.id.0.clone()))?;
+        let input = &trade.input_vault_balance_change;
+        let output = &trade.output_vault_balance_change;
+        events.push(log_indexes.event(
+            &trade.trade_event.transaction,
+            &format!("trade:{}", trade.id.0),
+            EventType::TakeOrderV3,
+            DecodedEvent::TakeOrderV3(Box::new(TakeOrderV3 {
+                sender: parse_address("trade sender", &trade.trade_event.sender)?,
+                config: TakeOrderConfigV4 {
+                    order: order.clone(),
+                    inputIOIndex: io_index(&order.validInputs, &input.vault)?,
+                    outputIOIndex: io_index(&order.validOutputs, &output.vault)?,
+                    signedContext: vec![],
+                },
+                // Taker amounts mirror the order's vault changes.
+                input: abs_float(&output.amount)?.get_inner(),
+                output: abs_float(&input.amount)?.get_inner(),
+            })),
+        )?);
+    }
+
+    let mut changes = vec![];
+    for SubgraphVault {
+        vault,
+        balance_changes,
+    } in &snapshot.vaults
+    {
+        let owner = parse_address("vault owner", &vault.owner)?;
+        let token = parse_address("token address", &vault.token.address)?;
+        let vault_id = parse_vault_id(&vault.vault_id)?;
+        let decimals = tokens[&token].decimals;
+
+        for change in balance_changes {
+            let Some(parts) = ChangeParts::of(change) else {
+                continue;
+            };
+            if !in_snapshot(parts.transaction)? {
+                continue;
+            }
+            let key = parts.key(&change_keys);
+            let old_balance = parse_float("vault balance", parts.old_vault_balance)?;
+            let running_balance = parse_float("vault balance", parts.new_vault_balance)?;
+            let delta = (running_balance - old_balance)
+                .map_err(|_| invalid_value("vault balance change", parts.id.0.clone()))?;
+            let is_decrease = delta
+                .lt(Float::default())
+                .map_err(|_| invalid_value("vault balance change", parts.id.0.clone()))?;
+
+            match parts.kind {
+                ChangeKind::Deposit => events.push(log_indexes.event(
+                    parts.transaction,
+                    &key,
+                    EventType::DepositV2,
+                    DecodedEvent::DepositV2(Box::new(DepositV2 {
+                        sender: owner,
+                        token,
+                        vaultId: vault_id,
+                        depositAmountUint256: parts.amount_uint256(decimals)?,
+                    })),
+                )?),
+                ChangeKind::Withdrawal => {
+                    let amount = abs_float(parts.amount)?;
+                    events.push(log_indexes.event(
+                        parts.transaction,
+                        &key,
+                        EventType::WithdrawV2,
+                        DecodedEvent::WithdrawV2(Box::new(WithdrawV2 {
+                            sender: owner,
+                            token,
+                            vaultId: vault_id,
+                            targetAmount: amount.get_inner(),
+                            withdrawAmount: amount.get_inner(),
+                            withdrawAmountUint256: parts.amount_uint256(decimals)?,
+                        })),
+                    )?)
+                }
+                ChangeKind::Take | ChangeKind::Clear | ChangeKind::ClearBounty => {}
+            }
+
+            changes.push(SeedVaultBalanceChange {
+                transaction_hash: parse_b256("transaction hash", &parts.transaction.id)?,
+                owner,
+                token,
+                vault_id,
+                block_number: parse_u64("block number", &parts.transaction.block_number)?,
+                block_timestamp: parse_u64("timestamp", &parts.transaction.timestamp)?,
+                log_index: log_indexes.get(parts.transaction, &key)?,
+                change_type: parts.change_type(is_decrease),
+                delta,
+                running_balance,
+            });
+        }
+    }
+    sort_decoded_events_by_block_and_log(&mut events);
+
+    let decimals_by_token: HashMap<Address, u8> = tokens
+        .iter()
+        .map(|(address, info)| (*address, info.decimals))
+        .collect();
+    let tokens: Vec<(Address, TokenInfo)> = tokens.into_iter().collect();
+
+    let mut batch = SqlStatementBatch::new();
+    batch.extend(generate_erc20_token_statements(ob_id, &tokens));
+    batch.extend(decoded_events_to_statements(
+        ob_id,
+        &events,
+        &decimals_by_token,
+    )?);
+    batch.extend(seed_vault_balances_batch(ob_id, &changes));
+    batch.add(upsert_target_watermark_stmt(
+        ob_id,
+        snapshot.block_number,
+        snapshot
+            .block_hash
+            .map(|hash| Bytes::from(hash.to_vec()))
+            .unwrap_or_default(),
+    ));
+    Ok(batch)
+}
+
+/// Synthetic log indexes per transaction, keyed by entity.
+#[derive(Default)]
+struct LogIndexes(BTreeMap<B256, BTreeSet<String>>);
+
+impl LogIndexes {
+    fn register(&mut self, transaction: &SgTransaction, key: String) -> Result<(), LocalDbError> {
+        let hash = parse_b256("transaction hash", &transaction.id)?;
+        self.0.entry(hash).or_default().insert(key);
+        Ok(())
+    }
+
+    fn get(&self, transaction: &SgTransaction, key: &str) -> Result<u64, LocalDbError> {
+        let hash = parse_b256("transaction hash", &transaction.id)?;
+        self.0
+            .get(&hash)
+            .and_then(|keys| keys.iter().position(|entry| entry == key))
+            .map(|position| position as u64)
+            .ok_or_else(|| invalid_value("transaction", transaction.id.0.clone()))
+    }
+
+    fn event(
+        &self,
+        transaction: &SgTransaction,
+        key: &str,
+        event_type: EventType,
+        decoded_data: DecodedEvent,
+    ) -> Result<DecodedEventData<DecodedEvent>, LocalDbError> {
+        Ok(DecodedEventData {
+            event_type,
+            block_number: U256::from(parse_u64("block number", &transaction.block_number)?),
+            block_timestamp: U256::from(parse_u64("timestamp", &transaction.timestamp)?),
+            transaction_hash: parse_b256("transaction hash", &transaction.id)?,
+            log_index: U256::from(self.get(transaction, key)?),
+            decoded_data,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ChangeKind {
+    Deposit,
+    Withdrawal,
+    Take,
+    Clear,
+    ClearBounty,
+}
+
+struct ChangeParts<'a> {
+    kind: ChangeKind,
+    id: &'a SgBytes,
+    amount: &'a SgBytes,
+    old_vault_balance: &'a SgBytes,
+    new_vault_balance: &'a SgBytes,
+    transaction: &'a SgTransaction,
+}
+
+impl<'a> ChangeParts<'a> {
+    fn of(change: &'a SgVaultBalanceChangeType) -> Option<Self> {
+        macro_rules! parts {
+            ($kind:expr, $change:expr) => {
+                Some(ChangeParts {
+                    kind: $kind,
+                    id: &$change.id,
+                    amount: &$change.amount,
+                    old_vault_balance: &$change.old_vault_balance,
+                    new_vault_balance: &$change.new_vault_balance,
+                    transaction: &$change.transaction,
+                })
+            };
+        }
+        match change {
+            SgVaultBalanceChangeType::Deposit(change) => parts!(ChangeKind::Deposit, change),
+            SgVaultBalanceChangeType::Withdrawal(change) => parts!(ChangeKind::Withdrawal, change),
+            SgVaultBalanceChangeType::TradeVaultBalanceChange(change) => {
+                let kind = if change.trade.trade_event.__typename == TAKE_ORDER_TYPENAME {
+                    ChangeKind::Take
+                } else {
+                    ChangeKind::Clear
+                };
+                parts!(kind, change)
+            }
+            SgVaultBalanceChangeType::ClearBounty(change) => {
+                parts!(ChangeKind::ClearBounty, change)
+            }
+            SgVaultBalanceChangeType::Unknown => None,
+        }
+    }
+
+    /// Take order changes share the log index of their trade.
+    fn key(&self, change_keys: &HashMap<&str, String>) -> String {
+        change_keys
+            .get(self.id.0.as_str())
+            .cloned()
+            .unwrap_or_else(|| format!("change:{}", self.id.0))
+    }
+
+    /// Clear sides are unknown, see [`subgraph_snapshot_batch`].
+    fn change_type(&self, is_decrease: bool) -> Option<&'static str> {
+        match (self.kind, is_decrease) {
+            (ChangeKind::Deposit, _) => Some("DEPOSIT"),
+            (ChangeKind::Withdrawal, _) => Some("WITHDRAW"),
+            (ChangeKind::Take, false) => Some("TAKE_INPUT"),
+            (ChangeKind::Take, true) => Some("TAKE_OUTPUT"),
+            (ChangeKind::Clear | ChangeKind::ClearBounty, _) => None,
+        }
+    }
+
+    fn amount_uint256(&self, decimals: u8) -> Result<U256, LocalDbError> {
+        abs_float(self.amount)?
+            .to_fixed_decimal(decimals)
+            .map_err(|_| invalid_value("amount", self.amount.0.clone()))
+    }
+}
+
+fn is_take_order(trade: &SgTrade) -> bool {
+    trade
+        .input_vault_balance_change
+        .trade
+        .trade_event
+        .__typename
+        == TAKE_ORDER_TYPENAME
+}
+
+fn io_index(ios: &[IOV2], vault: &SgVaultBalanceChangeVault) -> Result<U256, LocalDbError> {
+    let token = parse_address("token address", &vault.token.address)?;
+    let vault_id = parse_vault_id(&vault.vault_id)?;
+    ios.iter()
+        .position(|io| io.token == token && io.vaultId == vault_id)
+        .map(U256::from)
+        .ok_or_else(|| invalid_value("trade vault", vault.id.0.clone()))
+}
+
+fn token_info(token: &SgErc20) -> Result<(Address, TokenInfo), LocalDbError> {
+    let address = parse_address("token address", &token.address)?;
+    let decimals = token
+        .decimals
+        .as_ref()
+        .and_then(|decimals| decimals.0.parse::<u8>().ok())
+        .ok_or_else(|| invalid_value("token decimals", token.address.0.clone()))?;
+    Ok((
+        address,
+        TokenInfo {
+            decimals,
+            name: token.name.clone().unwrap_or_default(),
+            symbol: token.symbol.clone().unwrap_or_default(),
+        },
+    ))
+}
+
+fn decode_order(order_bytes: &SgBytes) -> Result<OrderV4, LocalDbError> {
+    hex::decode(&order_bytes.0)
+        .ok()
+        .and_then(|bytes| OrderV4::abi_decode(&bytes).ok())
+        .ok_or_else(|| invalid_value("order bytes", order_bytes.0.clone()))
+}
+
+fn abs_float(value: &SgBytes) -> Result<Float, LocalDbError> {
+    parse_float("amount", value)?
+        .abs()
+        .map_err(|_| invalid_value("amount", value.0.clone()))
+}
+
+fn parse_float(field: &'static str, value: &SgBytes) -> Result<Float, LocalDbError> {
+    Float::from_hex(&value.0).map_err(|_| invalid_value(field, value.0.clone()))
+}
+
+fn parse_u64(field: &'static str, value: &SgBigInt) -> Result<u64, LocalDbError> {
+    value
+        .0
+        .parse()
+        .map_err(|_| invalid_value(field, value.0.clone()))
+}
+
+fn parse_address(field: &'static str, value: &SgBytes) -> Result<Address, LocalDbError> {
+    Address::from_str(&value.0).map_err(|_| invalid_value(field, value.0.clone()))
+}
+
+fn parse_b256(field: &'static str, value: &SgBytes) -> Result<B256, LocalDbError> {
+    B256::from_str(&value.0).map_err(|_| invalid_value(field, value.0.clone()))
+}
+
+/// Vault ids may come back without leading zeros.
+fn parse_vault_id(value: &SgBytes) -> Result<B256, LocalDbError> {
+    U256::from_str(&value.0)
+        .map(B256::from)
+        .map_err(|_| invalid_value("vault id", value.0.clone()))
+}
+
+fn invalid_value(field: &'static str, value: String) -> LocalDbError {
+    LocalDbError::InvalidSubgraphValue { field, value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_db::query::fetch_tables::{fetch_tables_stmt, TableResponse};
+    use crate::local_db::query::fetch_target_watermark::{
+        fetch_target_watermark_stmt, TargetWatermarkRow,
+    };
+    use crate::local_db::query::{FromDbJson, LocalDbQueryError, SqlStatement, SqlValue};
+    use rain_orderbook_bindings::IRaindexV6::EvaluableV4;
+    use rain_orderbook_subgraph_client::types::common::{
+        SgAddOrder, SgDeposit, SgOrderbook, SgTradeEvent, SgTradeEventTypename, SgTradeRef,
+        SgTradeStructPartialOrder, SgTradeVaultBalanceChange,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const ORDERBOOK: Address = Address::repeat_byte(0xab);
+    const OWNER: Address = Address::repeat_byte(0x11);
+    const TOKEN_IN: Address = Address::repeat_byte(0x22);
+    const TOKEN_OUT: Address = Address::repeat_byte(0x33);
+
+    fn bytes(value: impl ToString) -> SgBytes {
+        SgBytes(value.to_string())
+    }
+
+    fn float(value: &str) -> SgBytes {
+        bytes(Float::parse(value.to_string()).unwrap().as_hex())
+    }
+
+    fn transaction(byte: u8, block_number: u64) -> SgTransaction {
+        SgTransaction {
+            id: bytes(B256::repeat_byte(byte)),
+            from: bytes(OWNER),
+            block_number: SgBigInt(block_number.to_string()),
+            timestamp: SgBigInt((1_000 + block_number).to_string()),
+        }
+    }
+
+    fn erc20(address: Address) -> SgErc20 {
+        SgErc20 {
+            id: bytes(address),
+            address: bytes(address),
+            name: Some("Token".to_string()),
+            symbol: Some("TKN".to_string()),
+            decimals: Some(SgBigInt("18".to_string())),
+        }
+    }
+
+    fn change_vault(token: Address, vault_id: u8) -> SgVaultBalanceChangeVault {
+        SgVaultBalanceChangeVault {
+            id: bytes(format!("vault-{vault_id}")),
+            vault_id: bytes(format!("0x{vault_id:x}")),
+            token: erc20(token),
+        }
+    }
+
+    fn trade_change(
+        id: &str,
+        vault: SgVaultBalanceChangeVault,
+        amount: &str,
+        old: &str,
+        new: &str,
+        tx: SgTransaction,
+    ) -> SgTradeVaultBalanceChange {
+        SgTradeVaultBalanceChange {
+            id: bytes(id),
+            __typename: "TradeVaultBalanceChange".to_string(),
+            amount: float(amount),
+            new_vault_balance: float(new),
+            old_vault_balance: float(old),
+            vault,
+            timestamp: tx.timestamp.clone(),
+            transaction: tx,
+            orderbook: SgOrderbook {
+                id: bytes(ORDERBOOK),
+            },
+            trade: SgTradeRef {
+                trade_event: SgTradeEventTypename {
+                    __typename: TAKE_ORDER_TYPENAME.to_string(),
+                },
+            },
+        }
+    }
+
+    fn order() -> OrderV4 {
+        OrderV4 {
+            owner: OWNER,
+            nonce: B256::repeat_byte(0x01),
+            evaluable: EvaluableV4 {
+                interpreter: Address::repeat_byte(0x02),
+                store: Address::repeat_byte(0x03),
+                bytecode: Bytes::from(vec![0x01]),
+            },
+            validInputs: vec![IOV2 {
+                token: TOKEN_IN,
+                vaultId: B256::from(U256::from(1)),
+            }],
+            validOutputs: vec![IOV2 {
+                token: TOKEN_OUT,
+                vaultId: B256::from(U256::from(2)),
+            }],
+        }
+    }
+
+    fn snapshot() -> SubgraphSnapshot {
+        let add_tx = transaction(0x01, 10);
+        let take_tx = transaction(0x02, 11);
+        let late_tx = transaction(0x03, 30);
+
+        let deposit = SgDeposit {
+            id: bytes("deposit-1"),
+            __typename: "Deposit".to_string(),
+            amount: float("5"),
+            new_vault_balance: float("5"),
+            old_vault_balance: float("0"),
+            vault: change_vault(TOKEN_IN, 1),
+            timestamp: add_tx.timestamp.clone(),
+            transaction: add_tx.clone(),
+            orderbook: SgOrderbook {
+                id: bytes(ORDERBOOK),
+            },
+        };
+        let input_change = trade_change(
+            "take-in",
+            change_vault(TOKEN_IN, 1),
+            "1",
+            "5",
+            "6",
+            take_tx.clone(),
+        );
+        let output_change = trade_change(
+            "take-out",
+            change_vault(TOKEN_OUT, 2),
+            "-2",
+            "2",
+            "0",
+            take_tx.clone(),
+        );
+        let late_change =
+            trade_change("late-in", change_vault(TOKEN_IN, 1), "1", "6", "7", late_tx);
+
+        SubgraphSnapshot {
+            block_number: 20,
+            block_hash: Some(B256::repeat_byte(0xee)),
+            orders: vec![SgOrder {
+                id: bytes("order-1"),
+                order_bytes: bytes(Bytes::from(order().abi_encode())),
+                order_hash: bytes(B256::repeat_byte(0x08)),
+                owner: bytes(OWNER),
+                outputs: vec![],
+                inputs: vec![],
+                orderbook: SgOrderbook {
+                    id: bytes(ORDERBOOK),
+                },
+                active: true,
+                timestamp_added: SgBigInt("1010".to_string()),
+                meta: None,
+                add_events: vec![SgAddOrder {
+                    transaction: add_tx,
+                }],
+                trades: vec![],
+                remove_events: vec![],
+            }],
+            trades: vec![SgTrade {
+                id: bytes("trade-1"),
+                trade_event: SgTradeEvent {
+                    transaction: take_tx.clone(),
+                    sender: bytes(Address::repeat_byte(0x44)),
+                },
+                output_vault_balance_change: output_change,
+                order: SgTradeStructPartialOrder {
+                    id: bytes("order-1"),
+                    order_hash: bytes(B256::repeat_byte(0x08)),
+                },
+                input_vault_balance_change: input_change.clone(),
+                timestamp: take_tx.timestamp.clone(),
+                orderbook: SgOrderbook {
+                    id: bytes(ORDERBOOK),
+                },
+            }],
+            vaults: vec![SubgraphVault {
+                vault: SgVault {
+                    id: bytes("vault-1"),
+                    owner: bytes(OWNER),
+                    vault_id: bytes("0x1"),
+                    balance: float("7"),
+                    token: erc20(TOKEN_IN),
+                    orderbook: SgOrderbook {
+                        id: bytes(ORDERBOOK),
+                    },
+                    orders_as_output: vec![],
+                    orders_as_input: vec![],
+                    balance_changes: vec![],
+                },
+                balance_changes: vec![
+                    SgVaultBalanceChangeType::Deposit(deposit),
+                    SgVaultBalanceChangeType::TradeVaultBalanceChange(input_change),
+                    SgVaultBalanceChangeType::TradeVaultBalanceChange(late_change),
+                ],
+            }],
+        }
+    }
+
+    fn statements_into<'a>(batch: &'a SqlStatementBatch, table: &str) -> Vec<&'a SqlStatement> {
+        batch
+            .statements()
+            .iter()
+            .filter(|stmt| stmt.sql().contains(&format!("INTO {table} (")))
+            .collect()
+    }
+
+    #[test]
+    fn snapshot_batch_seeds_events_balances_and_watermark() {
+        let ob_id = OrderbookIdentifier::new(1, ORDERBOOK);
+        let batch = subgraph_snapshot_batch(&ob_id, &snapshot()).unwrap();
+
+        assert_eq!(statements_into(&batch, "erc20_tokens").len(), 1);
+        assert_eq!(statements_into(&batch, "order_events").len(), 1);
+        assert_eq!(statements_into(&batch, "order_ios").len(), 2);
+
+        // The add order and deposit share a transaction, ordered by key.
+        let deposits = statements_into(&batch, "deposits");
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].params()[5], SqlValue::U64(1));
+        assert_eq!(deposits[0].params()[6], SqlValue::from(OWNER));
+
+        let takes = statements_into(&batch, "take_orders");
+        assert_eq!(takes.len(), 1);
+        let take = takes[0].params();
+        assert_eq!(take[5], SqlValue::U64(0));
+        assert_eq!(take[9], SqlValue::U64(0));
+        assert_eq!(take[10], SqlValue::U64(0));
+        assert_eq!(
+            take[11],
+            SqlValue::from(Float::parse("2".to_string()).unwrap())
+        );
+        assert_eq!(
+            take[12],
+            SqlValue::from(Float::parse("1".to_string()).unwrap())
+        );
+
+        // The change past the indexed block is left to the log-based sync.
+        let changes = statements_into(&batch, "vault_balance_changes");
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].params()[9], SqlValue::from("DEPOSIT"));
+        assert_eq!(changes[1].params()[9], SqlValue::from("TAKE_INPUT"));
+        assert_eq!(
+            changes[1].params()[11],
+            SqlValue::from(Float::parse("6".to_string()).unwrap())
+        );
+
+        let running = statements_into(&batch, "running_vault_balances");
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].params()[6], SqlValue::U64(11));
+
+        let watermark = batch.statements().last().unwrap();
+        assert!(watermark.sql().contains("target_watermarks"));
+        assert_eq!(watermark.params()[2], SqlValue::U64(20));
+        assert_eq!(
+            watermark.params()[3],
+            SqlValue::from(B256::repeat_byte(0xee))
+        );
+    }
+
+    #[test]
+    fn snapshot_batch_moves_running_balance_for_clears() {
+        let mut snapshot = snapshot();
+        let mut clear = trade_change(
+            "clear-in",
+            change_vault(TOKEN_IN, 1),
+            "2",
+            "6",
+            "8",
+            transaction(0x04, 12),
+        );
+        clear.trade.trade_event.__typename = "Clear".to_string();
+        snapshot.vaults[0]
+            .balance_changes
+            .push(SgVaultBalanceChangeType::TradeVaultBalanceChange(clear));
+
+        let batch =
+            subgraph_snapshot_batch(&OrderbookIdentifier::new(1, ORDERBOOK), &snapshot).unwrap();
+
+        let changes = statements_into(&batch, "vault_balance_changes");
+        assert_eq!(changes.len(), 2);
+        assert!(changes
+            .iter()
+            .all(|change| change.params()[9] != SqlValue::from("CLEAR_ALICE_INPUT")));
+
+        let running = statements_into(&batch, "running_vault_balances");
+        assert_eq!(running.len(), 1);
+        assert_eq!(
+            running[0].params()[5],
+            SqlValue::from(Float::parse("8".to_string()).unwrap())
+        );
+        assert_eq!(running[0].params()[6], SqlValue::U64(12));
+    }
+
+    #[tokio::test]
+    async fn engine_run_without_subgraph_only_runs_inner() {
+        let db = MockDb::default();
+        let adapter = SubgraphBootstrapAdapter::new(NoopBootstrap, None);
+        let config = BootstrapConfig {
+            ob_id: OrderbookIdentifier::new(1, ORDERBOOK),
+            dump_stmt: None,
+            delta_stmt: None,
+            latest_block: 100,
+            block_number_threshold: 10_000,
+            deployment_block: 1,
+        };
+        adapter.engine_run(&db, &config).await.unwrap();
+        assert_eq!(*db.batches.lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn snapshot_batch_rejects_undecodable_order() {
+        let mut snapshot = snapshot();
+        snapshot.orders[0].order_bytes = bytes("0x1234");
+        let err = subgraph_snapshot_batch(&OrderbookIdentifier::new(1, ORDERBOOK), &snapshot)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            LocalDbError::InvalidSubgraphValue {
+                field: "order bytes",
+                ..
+            }
+        ));
+    }
+
+    struct NoopBootstrap;
+
+    #[async_trait(?Send)]
+    impl BootstrapPipeline for NoopBootstrap {
+        async fn engine_run<DB>(&self, _: &DB, _: &BootstrapConfig) -> Result<(), LocalDbError>
+        where
+            DB: LocalDbQueryExecutor + ?Sized,
+        {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockDb {
+        json_map: HashMap<String, serde_json::Value>,
+        batches: Mutex<usize>,
+    }
+
+    #[cfg_attr(target_family = "wasm", async_trait(?Send))]
+    #[cfg_attr(not(target_family = "wasm"), async_trait)]
+    impl LocalDbQueryExecutor for MockDb {
+        async fn execute_batch(&self, _: &SqlStatementBatch) -> Result<(), LocalDbQueryError> {
+            *self.batches.lock().unwrap() += 1;
+            Ok(())
+        }
+
+        async fn query_json<T>(&self, stmt: &SqlStatement) -> Result<T, LocalDbQueryError>
+        where
+            T: FromDbJson,
+        {
+            let value = self
+                .json_map
+                .get(stmt.sql())
+                .cloned()
+                .unwrap_or(serde_json::json!([]));
+            serde_json::from_value(value)
+                .map_err(|e| LocalDbQueryError::deserialization(e.to_string()))
+        }
+
+        async fn query_text(&self, _: &SqlStatement) -> Result<String, LocalDbQueryError> {
+            Ok(String::new())
+        }
+
+        async fn wipe_and_recreate(&self) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn engine_run_keeps_synced_orderbook() {
+        let ob_id = OrderbookIdentifier::new(1, ORDERBOOK);
+        let mut db = MockDb::default();
+        db.json_map.insert(
+            fetch_tables_stmt().sql().to_string(),
+            serde_json::to_value(vec![TableResponse {
+                name: "target_watermarks".to_string(),
+            }])
+            .unwrap(),
+        );
+        db.json_map.insert(
+            fetch_target_watermark_stmt(&ob_id).sql().to_string(),
+            serde_json::to_value(vec![TargetWatermarkRow {
+                chain_id: 1,
+                orderbook_address: ORDERBOOK,
+                last_block: 50,
+                last_hash: Bytes::new(),
+                updated_at: 0,
+            }])
+            .unwrap(),
+        );
+
+        // Unreachable subgraph: the synced orderbook must not query it.
+        let adapter = SubgraphBootstrapAdapter::new(
+            NoopBootstrap,
+            Some(Url::parse("http://127.0.0.1:1").unwrap()),
+        );
+        let config = BootstrapConfig {
+            ob_id,
+            dump_stmt: None,
+            delta_stmt: None,
+            latest_block: 100,
+            block_number_threshold: 10_000,
+            deployment_block: 1,
+        };
+        adapter.engine_run(&db, &config).await.unwrap();
+        assert_eq!(*db.batches.lock().unwrap(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 use super::adapters::apply::{ApplyPipeline, ApplyPipelineTargetInfo};
 use super::adapters::bootstrap::{BootstrapConfig, BootstrapPipeline};
+use super::sync_events::sync_events_from_decoded;
 use super::{
     EventsPipeline, StatusBus, SyncConfig, SyncOutcome, SyncPhase, TokensPipeline, WindowPipeline,
 };
@@ -10,6 +11,13 @@ use crate::local_db::decode::{
 };
 use crate::local_db::query::fetch_erc20_tokens_by_addresses::Erc20TokenRow;
 use crate::local_db::query::fetch_store_addresses::{fetch_store_addresses_stmt, StoreAddressRow};
+use crate::local_db::query::fetch_stored_block_hashes::{
+    fetch_stored_block_hashes_stmt, StoredBlockHashRow,
+};
+use crate::local_db::query::fetch_target_watermark::{
+    fetch_target_watermark_stmt, TargetWatermarkRow,
+};
+use crate::local_db::query::rollback_to_block::rollback_to_block_batch;
 use crate::local_db::query::{LocalDbQueryExecutor, SqlStatement, SqlStatementBatch};
 use crate::local_db::{LocalDbError, OrderbookIdentifier};
 use crate::rpc_client::LogEntryResponse;
@@ -17,6 +25,10 @@ use alloy::primitives::Address;
 use std::collections::{BTreeSet, HashSet};
 use url::Url;
 
+/// Number of stored block hashes examined per page while searching for the
+/// common ancestor after a reorg is detected.
+const REORG_ANCESTOR_PAGE_SIZE: u32 = 100;
+
 /// Generic engine that orchestrates a full sync cycle by delegating
 /// environment-specific behavior to the supplied pipeline adapters.
 pub struct SyncEngine<B, W, E, T, A, S> {
@@ -35,6 +47,10 @@ pub struct SyncInputs {
     pub metadata_rpcs: Vec<Url>,
     pub cfg: SyncConfig,
     pub dump_str: Option<String>,
+    /// Manifest deltas to apply on top of the existing rows of the orderbook,
+    /// provisioned instead of `dump_str` when the local watermark is already
+    /// at the end of the base snapshot or of an earlier delta.
+    pub delta_dump_str: Option<String>,
     pub block_number_threshold: u32,
     pub manifest_end_block: u64,
 }
@@ -65,6 +81,7 @@ where
         DB: LocalDbQueryExecutor + ?Sized,
     {
         let latest_block = self.bootstrap_phase(db, input).await?;
+        let rolled_back_to = self.reconcile_reorg(db, input).await?;
         let (start_block, target_block) = self.compute_window(db, input, latest_block).await?;
         if start_block > target_block {
             self.status.send(SyncPhase::Idle).await?;
@@ -74,6 +91,8 @@ where
                 target_block,
                 fetched_logs: 0,
                 decoded_events: 0,
+                rolled_back_to,
+                events: Vec::new(),
             });
         }
 
@@ -104,6 +123,7 @@ where
             .await?;
 
         let target_hash = self.events.block_hash(target_block).await?;
+        let events = sync_events_from_decoded(&input.ob_id, &decoded_events)?;
 
         self.apply_changes(
             db,
@@ -128,6 +148,8 @@ where
             target_block,
             fetched_logs: all_raw_logs.len(),
             decoded_events: decoded_events.len(),
+            rolled_back_to,
+            events,
         })
     }
 }
@@ -154,14 +176,8 @@ where
                 db,
                 &BootstrapConfig {
                     ob_id: input.ob_id.clone(),
-                    dump_stmt: input.dump_str.as_ref().map(|value| {
-                        let mut batch_stmt = SqlStatementBatch::new();
-                        for line in value.lines() {
-                            let stmt = SqlStatement::new(line);
-                            batch_stmt.add(stmt);
-                        }
-                        batch_stmt
-                    }),
+                    dump_stmt: input.dump_str.as_deref().map(dump_batch),
+                    delta_stmt: input.delta_dump_str.as_deref().map(dump_batch),
                     block_number_threshold: input.block_number_threshold,
                     latest_block: input.manifest_end_block,
                     deployment_block: input.cfg.deployment_block,
@@ -172,6 +188,89 @@ where
         Ok(latest_block)
     }
 
+    /// Verifies that the stored watermark hash is still canonical. When it is
+    /// not, walks back through the block hashes recorded in `raw_events` until
+    /// one matches the chain, discards everything above that block and
+    /// rewinds the watermark so the window resumes from the common ancestor.
+    ///
+    /// Returns the block that was rolled back to, if any.
+    async fn reconcile_reorg<DB>(
+        &self,
+        db: &DB,
+        input: &SyncInputs,
+    ) -> Result<Option<u64>, LocalDbError>
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
+        let rows: Vec<TargetWatermarkRow> = db
+            .query_json(&fetch_target_watermark_stmt(&input.ob_id))
+            .await?;
+        let Some(watermark) = rows.into_iter().next() else {
+            return Ok(None);
+        };
+        if watermark.last_block == 0 || watermark.last_hash.is_empty() {
+            return Ok(None);
+        }
+
+        self.status
+            .send(SyncPhase::VerifyingChainContinuity)
+            .await?;
+        let canonical_hash = self.events.block_hash(watermark.last_block).await?;
+        if watermark.last_hash.as_ref() == canonical_hash.as_slice() {
+            return Ok(None);
+        }
+
+        let ancestor = self
+            .find_common_ancestor(db, input, watermark.last_block)
+            .await?;
+        let ancestor_hash = self.events.block_hash(ancestor).await?;
+
+        self.status.send(SyncPhase::RollingBackReorg).await?;
+        db.execute_batch(&rollback_to_block_batch(
+            &input.ob_id,
+            ancestor,
+            ancestor_hash,
+        ))
+        .await?;
+
+        Ok(Some(ancestor))
+    }
+
+    /// Returns the newest block strictly below `below_block` whose stored hash
+    /// matches the chain. Blocks without recorded events carry nothing to
+    /// roll back, so only blocks present in `raw_events` are compared. Falls
+    /// back to the block before deployment when no stored hash survives.
+    async fn find_common_ancestor<DB>(
+        &self,
+        db: &DB,
+        input: &SyncInputs,
+        below_block: u64,
+    ) -> Result<u64, LocalDbError>
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
+        let mut cursor = below_block;
+        loop {
+            let rows: Vec<StoredBlockHashRow> = db
+                .query_json(&fetch_stored_block_hashes_stmt(
+                    &input.ob_id,
+                    cursor,
+                    REORG_ANCESTOR_PAGE_SIZE,
+                ))
+                .await?;
+            let Some(last) = rows.last() else {
+                return Ok(input.cfg.deployment_block.saturating_sub(1));
+            };
+            cursor = last.block_number;
+
+            for row in &rows {
+                if self.events.block_hash(row.block_number).await? == row.block_hash {
+                    return Ok(row.block_number);
+                }
+            }
+        }
+    }
+
     async fn compute_window<DB>(
         &self,
         db: &DB,
@@ -349,6 +448,15 @@ where
         .map_err(Into::into)
 }
 
+/// Splits a downloaded dump into one statement per line.
+fn dump_batch(dump: &str) -> SqlStatementBatch {
+    let mut batch_stmt = SqlStatementBatch::new();
+    for line in dump.lines() {
+        batch_stmt.add(SqlStatement::new(line));
+    }
+    batch_stmt
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,6 +467,8 @@ mod tests {
     use crate::local_db::pipeline::adapters::apply::ApplyPipelineTargetInfo;
     use crate::local_db::pipeline::adapters::bootstrap::BootstrapState;
     use crate::local_db::pipeline::{FinalityConfig, SyncPhase, WindowOverrides};
+    use crate::local_db::query::fetch_stored_block_hashes::FETCH_STORED_BLOCK_HASHES_SQL;
+    use crate::local_db::query::fetch_target_watermark::FETCH_TARGET_WATERMARK_SQL;
     use crate::local_db::query::{
         fetch_erc20_tokens_by_addresses::Erc20TokenRow, fetch_store_addresses::StoreAddressRow,
         LocalDbQueryError, SqlStatement, SqlStatementBatch, SqlValue,
@@ -504,6 +614,7 @@ mod tests {
                 window_overrides: WindowOverrides::default(),
             },
             dump_str: None,
+            delta_dump_str: None,
             block_number_threshold: 10_000,
             manifest_end_block: 1,
         }
@@ -697,6 +808,7 @@ mod tests {
         store_results: Mutex<VecDeque<Result<Vec<LogEntryResponse>, LocalDbError>>>,
         decode_results: Mutex<VecDeque<Result<Vec<DecodedEventData<DecodedEvent>>, LocalDbError>>>,
         block_hashes: Mutex<VecDeque<Result<B256, LocalDbError>>>,
+        block_hash_calls: Mutex<Vec<u64>>,
         orderbook_calls: Mutex<Vec<(Address, u64, u64)>>,
         store_calls: Mutex<Vec<(Vec<Address>, u64, u64)>>,
         store_barrier: Mutex<Option<Arc<Barrier>>>,
@@ -731,6 +843,10 @@ mod tests {
             self.inner.block_hashes.lock().unwrap().push_back(result);
         }
 
+        fn block_hash_calls(&self) -> Vec<u64> {
+            self.inner.block_hash_calls.lock().unwrap().clone()
+        }
+
         fn orderbook_calls(&self) -> Vec<(Address, u64, u64)> {
             self.inner.orderbook_calls.lock().unwrap().clone()
         }
@@ -819,7 +935,12 @@ mod tests {
                 .unwrap_or(Ok(Vec::new()))
         }
 
-        async fn block_hash(&self, _block_number: u64) -> Result<B256, LocalDbError> {
+        async fn block_hash(&self, block_number: u64) -> Result<B256, LocalDbError> {
+            self.inner
+                .block_hash_calls
+                .lock()
+                .unwrap()
+                .push(block_number);
             self.inner
                 .block_hashes
                 .lock()
@@ -1051,6 +1172,8 @@ mod tests {
     #[derive(Default)]
     struct TestDb {
         store_responses: Mutex<VecDeque<Result<Vec<StoreAddressRow>, LocalDbQueryError>>>,
+        watermark_rows: Mutex<Vec<TargetWatermarkRow>>,
+        stored_hash_pages: Mutex<VecDeque<Vec<StoredBlockHashRow>>>,
         query_statements: Mutex<Vec<SqlStatement>>,
         executed_batches: Mutex<Vec<SqlStatementBatch>>,
     }
@@ -1062,6 +1185,35 @@ mod tests {
         ) {
             *self.store_responses.lock().unwrap() = VecDeque::from(responses);
         }
+
+        fn set_watermark(&self, last_block: u64, last_hash: B256) {
+            *self.watermark_rows.lock().unwrap() = vec![TargetWatermarkRow {
+                chain_id: base_target().chain_id,
+                orderbook_address: base_target().orderbook_address,
+                last_block,
+                last_hash: last_hash.into(),
+                updated_at: 1,
+            }];
+        }
+
+        fn set_stored_hash_pages(&self, pages: Vec<Vec<StoredBlockHashRow>>) {
+            *self.stored_hash_pages.lock().unwrap() = VecDeque::from(pages);
+        }
+
+        fn executed_batches(&self) -> Vec<SqlStatementBatch> {
+            self.executed_batches.lock().unwrap().clone()
+        }
+    }
+
+    fn to_db_json<T, R>(rows: &R) -> Result<T, LocalDbQueryError>
+    where
+        T: crate::local_db::query::FromDbJson,
+        R: serde::Serialize,
+    {
+        let value = serde_json::to_value(rows)
+            .map_err(|err| LocalDbQueryError::deserialization(err.to_string()))?;
+        serde_json::from_value(value)
+            .map_err(|err| LocalDbQueryError::deserialization(err.to_string()))
     }
 
     #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -1077,16 +1229,25 @@ mod tests {
             T: crate::local_db::query::FromDbJson,
         {
             self.query_statements.lock().unwrap().push(stmt.clone());
+            if stmt.sql() == FETCH_TARGET_WATERMARK_SQL {
+                return to_db_json(&*self.watermark_rows.lock().unwrap());
+            }
+            if stmt.sql() == FETCH_STORED_BLOCK_HASHES_SQL {
+                let page = self
+                    .stored_hash_pages
+                    .lock()
+                    .unwrap()
+                    .pop_front()
+                    .unwrap_or_default();
+                return to_db_json(&page);
+            }
             let response = self
                 .store_responses
                 .lock()
                 .unwrap()
                 .pop_front()
                 .unwrap_or_else(|| Ok(Vec::new()))?;
-            let value = serde_json::to_value(response)
-                .map_err(|err| LocalDbQueryError::deserialization(err.to_string()))?;
-            serde_json::from_value(value)
-                .map_err(|err| LocalDbQueryError::deserialization(err.to_string()))
+            to_db_json(&response)
         }
 
         async fn query_text(&self, _stmt: &SqlStatement) -> Result<String, LocalDbQueryError> {
@@ -1286,6 +1447,149 @@ mod tests {
         assert!(harness.apply.build_calls().is_empty());
     }
 
+    #[tokio::test]
+    async fn run_keeps_data_when_watermark_hash_is_canonical() {
+        let harness = EngineHarness::new();
+        let stored = b256!("0x00000000000000000000000000000000000000000000000000000000000000a1");
+        harness.db.set_watermark(50, stored);
+        harness.events.set_latest_blocks(vec![Ok(60)]);
+        harness.events.push_block_hash(Ok(stored));
+        harness.window.set_results(vec![Ok((51, 50))]);
+
+        let outcome = harness.run(&base_inputs()).await.expect("run succeeds");
+        assert_eq!(outcome.rolled_back_to, None);
+
+        assert_eq!(
+            harness.status.phases(),
+            vec![
+                SyncPhase::FetchingLatestBlock,
+                SyncPhase::RunningBootstrap,
+                SyncPhase::VerifyingChainContinuity,
+                SyncPhase::ComputingSyncWindow,
+                SyncPhase::Idle,
+            ]
+        );
+        assert_eq!(harness.events.block_hash_calls(), vec![50]);
+        assert!(harness.db.executed_batches().is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_rolls_back_to_common_ancestor_on_hash_mismatch() {
+        let harness = EngineHarness::new();
+        let stale = b256!("0x00000000000000000000000000000000000000000000000000000000000000b0");
+        let canonical_45 =
+            b256!("0x00000000000000000000000000000000000000000000000000000000000000c5");
+        harness.db.set_watermark(50, stale);
+        harness.db.set_stored_hash_pages(vec![vec![
+            StoredBlockHashRow {
+                block_number: 48,
+                block_hash: b256!(
+                    "0x00000000000000000000000000000000000000000000000000000000000000b8"
+                ),
+            },
+            StoredBlockHashRow {
+                block_number: 45,
+                block_hash: canonical_45,
+            },
+        ]]);
+        harness.events.set_latest_blocks(vec![Ok(60)]);
+        // watermark check, block 48, block 45, ancestor hash
+        harness.events.push_block_hash(Ok(b256!(
+            "0x00000000000000000000000000000000000000000000000000000000000000d0"
+        )));
+        harness.events.push_block_hash(Ok(b256!(
+            "0x00000000000000000000000000000000000000000000000000000000000000d8"
+        )));
+        harness.events.push_block_hash(Ok(canonical_45));
+        harness.events.push_block_hash(Ok(canonical_45));
+        harness.window.set_results(vec![Ok((46, 45))]);
+
+        let outcome = harness.run(&base_inputs()).await.expect("run succeeds");
+        assert_eq!(outcome.rolled_back_to, Some(45));
+        assert_eq!(harness.events.block_hash_calls(), vec![50, 48, 45, 45]);
+
+        assert_eq!(
+            harness.status.phases(),
+            vec![
+                SyncPhase::FetchingLatestBlock,
+                SyncPhase::RunningBootstrap,
+                SyncPhase::VerifyingChainContinuity,
+                SyncPhase::RollingBackReorg,
+                SyncPhase::ComputingSyncWindow,
+                SyncPhase::Idle,
+            ]
+        );
+
+        let batches = harness.db.executed_batches();
+        assert_eq!(batches.len(), 1);
+        let rollback = &batches[0];
+        assert!(rollback.is_transaction());
+        assert!(rollback
+            .statements()
+            .iter()
+            .any(|stmt| stmt.sql().starts_with("DELETE FROM raw_events")
+                && stmt.params().get(2) == Some(&SqlValue::U64(45))));
+        let watermark = rollback
+            .statements()
+            .iter()
+            .find(|stmt| stmt.sql().starts_with("INSERT INTO target_watermarks"))
+            .expect("watermark rewound");
+        assert_eq!(watermark.params()[2], SqlValue::U64(45));
+        assert_eq!(
+            watermark.params()[3],
+            SqlValue::Text(canonical_45.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn run_rolls_back_before_deployment_when_no_stored_hash_matches() {
+        let harness = EngineHarness::new();
+        harness.db.set_watermark(
+            30,
+            b256!("0x00000000000000000000000000000000000000000000000000000000000000e0"),
+        );
+        harness
+            .db
+            .set_stored_hash_pages(vec![vec![StoredBlockHashRow {
+                block_number: 12,
+                block_hash: b256!(
+                    "0x00000000000000000000000000000000000000000000000000000000000000e1"
+                ),
+            }]]);
+        harness.events.set_latest_blocks(vec![Ok(40)]);
+        harness.window.set_results(vec![Ok((10, 9))]);
+
+        let mut inputs = base_inputs();
+        inputs.cfg.deployment_block = 10;
+
+        let outcome = harness.run(&inputs).await.expect("run succeeds");
+        assert_eq!(outcome.rolled_back_to, Some(9));
+        // watermark check, block 12, fallback ancestor hash
+        assert_eq!(harness.events.block_hash_calls(), vec![30, 12, 9]);
+        assert_eq!(harness.db.executed_batches().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn run_propagates_block_hash_error_during_continuity_check() {
+        let harness = EngineHarness::new();
+        harness.db.set_watermark(
+            30,
+            b256!("0x00000000000000000000000000000000000000000000000000000000000000f0"),
+        );
+        harness.events.set_latest_blocks(vec![Ok(40)]);
+        harness
+            .events
+            .push_block_hash(Err(LocalDbError::BlockHashNotFound { block_number: 30 }));
+
+        let err = harness.run(&base_inputs()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            LocalDbError::BlockHashNotFound { block_number: 30 }
+        ));
+        assert!(harness.window.calls().is_empty());
+        assert!(harness.db.executed_batches().is_empty());
+    }
+
     #[tokio::test]
     async fn run_fetches_store_logs_when_addresses_present() {
         let harness = EngineHarness::new();
@@ -1621,6 +1925,7 @@ mod tests {
             .set_fetch_missing_results(vec![Ok(Vec::new())]);
         let mut inputs = base_inputs();
         inputs.dump_str = Some("SELECT 1".into());
+        inputs.delta_dump_str = Some("SELECT 2\nSELECT 3".into());
 
         harness.run(&inputs).await.expect("run succeeds");
 
@@ -1631,6 +1936,9 @@ mod tests {
         let statements = dump_stmt.statements();
         assert_eq!(statements.len(), 1);
         assert_eq!(statements[0].sql(), "SELECT 1");
+        let delta_stmt = config.delta_stmt.as_ref().expect("delta statement present");
+        assert_eq!(delta_stmt.len(), 2);
+        assert_eq!(delta_stmt.statements()[1].sql(), "SELECT 3");
         assert_eq!(config.deployment_block, inputs.cfg.deployment_block);
         assert_eq!(config.block_number_threshold, inputs.block_number_threshold);
         assert_eq!(config.latest_block, inputs.manifest_end_block);
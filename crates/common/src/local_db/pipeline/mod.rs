@@ -10,6 +10,7 @@
 pub mod adapters;
 pub mod engine;
 pub mod runner;
+pub mod sync_events;
 
 use super::OrderbookIdentifier;
 use crate::erc20::TokenInfo;
@@ -75,6 +76,11 @@ pub struct SyncOutcome {
     pub fetched_logs: usize,
     /// Count of decoded events materialized during the cycle.
     pub decoded_events: usize,
+    /// Block the orderbook data was rolled back to when a chain
+    /// reorganization was detected at the start of the cycle.
+    pub rolled_back_to: Option<u64>,
+    /// Domain events decoded from the persisted logs, in block/log order.
+    pub events: Vec<sync_events::SyncEvent>,
 }
 
 /// Typed sync phases for status reporting.
@@ -84,6 +90,8 @@ pub struct SyncOutcome {
 pub enum SyncPhase {
     FetchingLatestBlock,
     RunningBootstrap,
+    VerifyingChainContinuity,
+    RollingBackReorg,
     ComputingSyncWindow,
     FetchingOrderbookLogs,
     DecodingOrderbookLogs,
@@ -103,6 +111,8 @@ impl SyncPhase {
         match self {
             Self::FetchingLatestBlock => "Fetching latest block",
             Self::RunningBootstrap => "Running bootstrap",
+            Self::VerifyingChainContinuity => "Verifying chain continuity",
+            Self::RollingBackReorg => "Rolling back reorganized blocks",
             Self::ComputingSyncWindow => "Computing sync window",
             Self::FetchingOrderbookLogs => "Fetching orderbook logs",
             Self::DecodingOrderbookLogs => "Decoding orderbook logs",
@@ -135,9 +145,9 @@ pub trait StatusBus {
 /// - Compute `safe_head = max(deployment_block, latest - finality.depth)` and
 ///   apply overrides from `cfg.window_overrides` (subject to clamp).
 ///
-/// Policy (environment-specific):
-/// - Continuity check: producer verifies parent hash continuity vs stored
-///   watermark hash; browser may skip or apply a light check.
+/// Continuity is not this pipeline's concern: the engine verifies the stored
+/// watermark hash against the chain and rolls back reorganized blocks before
+/// the window is computed, so the watermark read here is always canonical.
 ///
 /// Invariants:
 /// - If `start_block > target_block`, the sync cycle is a no-op.
@@ -5,6 +5,8 @@ use crate::local_db::pipeline::adapters::bootstrap::BootstrapPipeline;
 use crate::local_db::pipeline::engine::SyncEngine;
 use crate::local_db::pipeline::{EventsPipeline, StatusBus, TokensPipeline, WindowPipeline};
 use crate::local_db::LocalDbError;
+use alloy::primitives::B256;
+use rain_orderbook_app_settings::local_db_manifest::{ManifestDelta, ManifestOrderbook};
 use rain_orderbook_app_settings::orderbook::OrderbookCfg;
 use rain_orderbook_app_settings::remote::manifest::ManifestMap;
 use std::collections::HashMap;
@@ -19,7 +21,8 @@ pub type DumpFuture = PinnedDbFuture<String>;
 
 pub type ManifestFetcher =
     Arc<dyn Fn(&HashMap<String, OrderbookCfg>) -> ManifestFuture + Send + Sync>;
-pub type DumpDownloader = Arc<dyn Fn(&Url) -> DumpFuture + Send + Sync>;
+/// Downloads the dump at a URL, given the SHA-256 the manifest lists for it.
+pub type DumpDownloader = Arc<dyn Fn(&Url, &B256) -> DumpFuture + Send + Sync>;
 pub type EngineBuilder<B, W, E, T, A, S> = Arc<
     dyn Fn(&RunnerTarget) -> Result<EnginePipelines<B, W, E, T, A, S>, LocalDbError> + Send + Sync,
 >;
@@ -69,8 +72,48 @@ where
         (self.manifest_fetcher)(orderbooks).await
     }
 
-    pub async fn download_dump(&self, url: &Url) -> Result<String, LocalDbError> {
-        (self.dump_downloader)(url).await
+    pub async fn download_dump(&self, url: &Url, sha256: &B256) -> Result<String, LocalDbError> {
+        (self.dump_downloader)(url, sha256).await
+    }
+
+    /// Downloads the base snapshot of a manifest entry followed by all of its
+    /// deltas, joined in order into a single script.
+    pub async fn download_full_dump(
+        &self,
+        entry: &ManifestOrderbook,
+    ) -> Result<String, LocalDbError> {
+        let dumps = std::iter::once((&entry.dump_url, &entry.dump_sha256)).chain(
+            entry
+                .deltas
+                .iter()
+                .map(|delta| (&delta.dump_url, &delta.dump_sha256)),
+        );
+        self.download_dumps(dumps).await
+    }
+
+    /// Downloads the given manifest deltas, joined in order into a single
+    /// script.
+    pub async fn download_deltas(&self, deltas: &[ManifestDelta]) -> Result<String, LocalDbError> {
+        self.download_dumps(
+            deltas
+                .iter()
+                .map(|delta| (&delta.dump_url, &delta.dump_sha256)),
+        )
+        .await
+    }
+
+    async fn download_dumps<'a>(
+        &self,
+        dumps: impl IntoIterator<Item = (&'a Url, &'a B256)>,
+    ) -> Result<String, LocalDbError> {
+        let mut sql = String::new();
+        for (url, sha256) in dumps {
+            sql.push_str(&self.download_dump(url, sha256).await?);
+            if !sql.ends_with('\n') {
+                sql.push('\n');
+            }
+        }
+        Ok(sql)
     }
 
     pub fn build_engine(
@@ -130,9 +173,10 @@ pub fn default_manifest_fetcher() -> ManifestFetcher {
 }
 
 pub fn default_dump_downloader() -> DumpDownloader {
-    Arc::new(|url: &Url| {
+    Arc::new(|url: &Url, sha256: &B256| {
         let url = url.clone();
-        Box::pin(async move { download_and_gunzip(&url).await })
+        let sha256 = *sha256;
+        Box::pin(async move { download_and_gunzip(&url, &sha256).await })
     })
 }
 
@@ -146,13 +190,14 @@ mod tests {
         BootstrapConfig, BootstrapPipeline, BootstrapState,
     };
     use crate::local_db::pipeline::engine::SyncInputs;
+    use crate::local_db::pipeline::runner::remotes::dump_sha256;
     use crate::local_db::pipeline::runner::utils::parse_runner_settings;
     use crate::local_db::pipeline::{FinalityConfig, SyncConfig, SyncPhase, WindowOverrides};
     use crate::local_db::query::sql_statement_batch::SqlStatementBatch;
     use crate::local_db::query::LocalDbQueryExecutor;
     use crate::local_db::{LocalDbError, OrderbookIdentifier};
     use crate::rpc_client::LogEntryResponse;
-    use alloy::primitives::{address, b256, Address, B256};
+    use alloy::primitives::{address, b256, Address, Bytes, B256};
     use async_trait::async_trait;
     use rain_orderbook_app_settings::local_db_manifest::MANIFEST_VERSION;
     use rain_orderbook_app_settings::local_db_remotes::LocalDbRemoteCfg;
@@ -194,6 +239,7 @@ mod tests {
                     window_overrides: WindowOverrides::default(),
                 },
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 100,
                 manifest_end_block: 1,
             },
@@ -436,7 +482,7 @@ mod tests {
         });
 
         let downloader: DumpDownloader =
-            Arc::new(|_url| Box::pin(async { Ok("dump".to_string()) }));
+            Arc::new(|_url, _sha256| Box::pin(async { Ok("dump".to_string()) }));
 
         let environment = RunnerEnvironment::new(
             fetcher,
@@ -464,7 +510,8 @@ mod tests {
         let fetcher: ManifestFetcher = Arc::new(|_orderbooks| {
             Box::pin(async { Err(LocalDbError::HttpStatus { status: 500 }) })
         });
-        let downloader: DumpDownloader = Arc::new(|_url| Box::pin(async { Ok(String::new()) }));
+        let downloader: DumpDownloader =
+            Arc::new(|_url, _sha256| Box::pin(async { Ok(String::new()) }));
         let environment = RunnerEnvironment::new(
             fetcher,
             downloader,
@@ -492,7 +539,7 @@ mod tests {
         let fetcher: ManifestFetcher =
             Arc::new(|_orderbooks| Box::pin(async { Ok(HashMap::new()) }));
         let downloader: DumpDownloader =
-            Arc::new(|_url| Box::pin(async { Ok("hello".to_string()) }));
+            Arc::new(|_url, _sha256| Box::pin(async { Ok("hello".to_string()) }));
         let environment = RunnerEnvironment::new(
             fetcher,
             downloader,
@@ -507,9 +554,10 @@ mod tests {
                 ))
             }),
         );
-        let contents =
-            block_on(environment.download_dump(&Url::parse("https://example.com").unwrap()))
-                .expect("dump");
+        let contents = block_on(
+            environment.download_dump(&Url::parse("https://example.com").unwrap(), &B256::ZERO),
+        )
+        .expect("dump");
         assert_eq!(contents, "hello");
     }
 
@@ -517,7 +565,7 @@ mod tests {
     fn download_dump_propagates_errors() {
         let fetcher: ManifestFetcher =
             Arc::new(|_orderbooks| Box::pin(async { Ok(HashMap::new()) }));
-        let downloader: DumpDownloader = Arc::new(|_url| {
+        let downloader: DumpDownloader = Arc::new(|_url, _sha256| {
             Box::pin(async {
                 Err(LocalDbError::IoError(std::io::Error::other(
                     "download failed",
@@ -538,18 +586,69 @@ mod tests {
                 ))
             }),
         );
-        let err = block_on(environment.download_dump(&Url::parse("https://example.com").unwrap()))
-            .unwrap_err();
+        let err = block_on(
+            environment.download_dump(&Url::parse("https://example.com").unwrap(), &B256::ZERO),
+        )
+        .unwrap_err();
         assert!(matches!(err, LocalDbError::IoError(_)));
     }
 
+    #[test]
+    fn download_full_dump_joins_base_and_deltas_in_order() {
+        let fetcher: ManifestFetcher =
+            Arc::new(|_orderbooks| Box::pin(async { Ok(HashMap::new()) }));
+        let downloader: DumpDownloader = Arc::new(|url: &Url, sha256: &B256| {
+            let path = url.path().trim_start_matches('/').to_string();
+            let marker = sha256[0];
+            Box::pin(async move { Ok(format!("-- {path} {marker}")) })
+        });
+        let environment = RunnerEnvironment::new(
+            fetcher,
+            downloader,
+            Arc::new(|_target: &RunnerTarget| {
+                Ok(EnginePipelines::new(
+                    StubBootstrap { marker: "b" },
+                    StubWindow { marker: "w" },
+                    StubEvents { marker: "e" },
+                    StubTokens { marker: "t" },
+                    StubApply { marker: "a" },
+                    StubStatus { marker: "s" },
+                ))
+            }),
+        );
+        let delta = |start_block: u64, end_block: u64| ManifestDelta {
+            dump_url: Url::parse(&format!("https://example.com/delta-{end_block}")).unwrap(),
+            dump_sha256: B256::repeat_byte(end_block as u8),
+            start_block,
+            start_block_hash: Bytes::from(vec![start_block as u8]),
+            end_block,
+            end_block_hash: Bytes::from(vec![end_block as u8]),
+            end_block_time_ms: end_block,
+        };
+        let entry = ManifestOrderbook {
+            address: address!("0000000000000000000000000000000000000001"),
+            dump_url: Url::parse("https://example.com/base").unwrap(),
+            dump_sha256: B256::repeat_byte(10),
+            end_block: 10,
+            end_block_hash: Bytes::from(vec![10]),
+            end_block_time_ms: 10,
+            deltas: vec![delta(10, 20), delta(20, 30)],
+        };
+
+        let full = block_on(environment.download_full_dump(&entry)).expect("full dump");
+        assert_eq!(full, "-- base 10\n-- delta-20 20\n-- delta-30 30\n");
+
+        let deltas = block_on(environment.download_deltas(&entry.deltas[1..])).expect("deltas");
+        assert_eq!(deltas, "-- delta-30 30\n");
+    }
+
     #[test]
     fn build_engine_returns_pipelines() {
         let marker = Arc::new(AtomicUsize::new(0));
         let marker_clone = marker.clone();
         let environment = RunnerEnvironment::new(
             Arc::new(|_orderbooks| Box::pin(async { Ok(HashMap::new()) })),
-            Arc::new(|_url| Box::pin(async { Ok("dump".into()) })),
+            Arc::new(|_url, _sha256| Box::pin(async { Ok("dump".into()) })),
             Arc::new(move |_target: &RunnerTarget| {
                 marker_clone.fetch_add(1, Ordering::SeqCst);
                 Ok(EnginePipelines::new(
@@ -609,7 +708,7 @@ mod tests {
             StubStatus,
         > = RunnerEnvironment::new(
             Arc::new(|_orderbooks| Box::pin(async { Ok(HashMap::new()) })),
-            Arc::new(|_url| Box::pin(async { Ok(String::new()) })),
+            Arc::new(|_url, _sha256| Box::pin(async { Ok(String::new()) })),
             builder,
         );
         let result = environment.build_engine(&sample_target());
@@ -632,7 +731,7 @@ mod tests {
         });
         let download_counter = Arc::new(AtomicUsize::new(0));
         let download_counter_clone = download_counter.clone();
-        let downloader: DumpDownloader = Arc::new(move |_url| {
+        let downloader: DumpDownloader = Arc::new(move |_url, _sha256| {
             let counter = download_counter_clone.clone();
             Box::pin(async move {
                 counter.fetch_add(1, Ordering::SeqCst);
@@ -660,8 +759,12 @@ mod tests {
         block_on(clone.fetch_manifests(&HashMap::new())).unwrap();
         assert_eq!(fetch_counter.load(Ordering::SeqCst), 2);
 
-        block_on(environment.download_dump(&Url::parse("https://example.com").unwrap())).unwrap();
-        block_on(clone.download_dump(&Url::parse("https://example.com").unwrap())).unwrap();
+        block_on(
+            environment.download_dump(&Url::parse("https://example.com").unwrap(), &B256::ZERO),
+        )
+        .unwrap();
+        block_on(clone.download_dump(&Url::parse("https://example.com").unwrap(), &B256::ZERO))
+            .unwrap();
         assert_eq!(download_counter.load(Ordering::SeqCst), 2);
     }
 
@@ -713,6 +816,7 @@ orderbooks:
                     .key
                     .clone(),
                 url: url.clone(),
+                publisher: None,
             };
             orderbook.local_db_remote = Some(Arc::new(remote));
         }
@@ -732,6 +836,7 @@ networks:
     orderbooks:
       - address: "0x00000000000000000000000000000000000000a1"
         dump-url: "{base}/dump.sql.gz"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 123
         end-block-hash: "0x01"
         end-block-time-ms: 1000
@@ -777,7 +882,7 @@ networks:
 
         let downloader = default_dump_downloader();
         let url = Url::parse(&format!("{}/dump.sql.gz", server.base_url())).unwrap();
-        let contents = block_on(downloader(&url)).expect("contents");
+        let contents = block_on(downloader(&url, &dump_sha256(&gzipped))).expect("contents");
         assert_eq!(contents, body_text);
     }
 
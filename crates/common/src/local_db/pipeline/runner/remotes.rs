@@ -1,10 +1,15 @@
 use super::utils::RunnerTarget;
+use crate::local_db::query::fetch_target_watermark::TargetWatermarkRow;
 use crate::local_db::LocalDbError;
+use alloy::primitives::{Address, Signature, B256};
 use flate2::read::GzDecoder;
 use itertools::Itertools;
-use rain_orderbook_app_settings::local_db_manifest::ManifestOrderbook;
+use rain_orderbook_app_settings::local_db_manifest::{
+    LocalDbManifest, ManifestDelta, ManifestOrderbook,
+};
 use rain_orderbook_app_settings::orderbook::OrderbookCfg;
 use rain_orderbook_app_settings::remote::manifest::{fetch_multiple_manifests, ManifestMap};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::Read;
 use url::Url;
@@ -28,7 +33,8 @@ pub(crate) fn collect_manifest_urls(
     Ok(urls.into_iter().unique().collect())
 }
 
-/// Fetches manifests for all distinct remotes referenced by the orderbooks.
+/// Fetches manifests for all distinct remotes referenced by the orderbooks
+/// and checks the signature of those whose remote names a publisher.
 pub async fn get_manifests(
     orderbooks: &HashMap<String, OrderbookCfg>,
 ) -> Result<ManifestMap, LocalDbError> {
@@ -37,13 +43,69 @@ pub async fn get_manifests(
         return Ok(HashMap::new());
     }
 
-    fetch_multiple_manifests(urls)
+    let manifests = fetch_multiple_manifests(urls)
         .await
-        .map_err(LocalDbError::ManifestFetch)
+        .map_err(LocalDbError::ManifestFetch)?;
+    verify_manifest_publishers(orderbooks, &manifests)?;
+    Ok(manifests)
 }
 
-/// Downloads a gzip-compressed SQL dump and returns the decompressed contents.
-pub async fn download_and_gunzip(url: &Url) -> Result<String, LocalDbError> {
+fn verify_manifest_publishers(
+    orderbooks: &HashMap<String, OrderbookCfg>,
+    manifests: &ManifestMap,
+) -> Result<(), LocalDbError> {
+    let publishers = orderbooks
+        .values()
+        .filter_map(|orderbook| orderbook.local_db_remote.as_ref())
+        .filter_map(|remote| Some((&remote.url, remote.publisher?)))
+        .unique();
+    for (url, publisher) in publishers {
+        if let Some(manifest) = manifests.get(url) {
+            verify_manifest_signature(url, manifest, publisher)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the manifest carries an EIP-191 signature of its signing
+/// payload by `publisher`.
+pub fn verify_manifest_signature(
+    url: &Url,
+    manifest: &LocalDbManifest,
+    publisher: Address,
+) -> Result<(), LocalDbError> {
+    let invalid = |reason: String| LocalDbError::ManifestSignatureInvalid {
+        url: url.to_string(),
+        publisher,
+        reason,
+    };
+
+    let signature = manifest
+        .signature
+        .as_ref()
+        .ok_or_else(|| invalid("the manifest is not signed".to_string()))?;
+    let signature = Signature::from_raw(signature).map_err(|e| invalid(e.to_string()))?;
+    let payload = manifest.signing_payload()?;
+    let signer = signature
+        .recover_address_from_msg(payload.as_bytes())
+        .map_err(|e| invalid(e.to_string()))?;
+    if signer != publisher {
+        return Err(invalid(format!("it is signed by {:#x}", signer)));
+    }
+    Ok(())
+}
+
+/// SHA-256 digest recorded in manifests for a compressed dump.
+pub fn dump_sha256(bytes: &[u8]) -> B256 {
+    B256::from_slice(&Sha256::digest(bytes))
+}
+
+/// Downloads a gzip-compressed SQL dump, checks it against the SHA-256 the
+/// manifest lists for it and returns the decompressed contents.
+pub async fn download_and_gunzip(
+    url: &Url,
+    expected_sha256: &B256,
+) -> Result<String, LocalDbError> {
     let response = reqwest::get(url.clone()).await?;
     if !response.status().is_success() {
         return Err(LocalDbError::HttpStatus {
@@ -52,12 +114,58 @@ pub async fn download_and_gunzip(url: &Url) -> Result<String, LocalDbError> {
     }
 
     let bytes = response.bytes().await?.to_vec();
+    let actual = dump_sha256(&bytes);
+    if actual != *expected_sha256 {
+        return Err(LocalDbError::DumpChecksumMismatch {
+            url: url.to_string(),
+            expected: *expected_sha256,
+            actual,
+        });
+    }
+
     let mut decoder = GzDecoder::new(bytes.as_slice());
     let mut out = String::new();
     decoder.read_to_string(&mut out)?;
     Ok(out)
 }
 
+/// Dumps a database needs from a manifest entry to reach the entry's head.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManifestDumpPlan<'a> {
+    /// Import the base snapshot followed by every delta.
+    Full,
+    /// Apply only these deltas on top of the rows already stored.
+    Deltas(&'a [ManifestDelta]),
+    /// Download nothing and let the regular sync cover the remaining blocks.
+    Skip,
+}
+
+/// Picks the dumps to download for a database whose orderbook watermark is
+/// `watermark`. Only the missing deltas are fetched when the watermark is the
+/// end of the base snapshot or of a delta; the base snapshot is re-imported
+/// only for a fresh database or one lagging the manifest head by more than
+/// `block_number_threshold` blocks, mirroring the bootstrap threshold check.
+pub fn plan_manifest_dumps<'a>(
+    entry: &'a ManifestOrderbook,
+    watermark: Option<&TargetWatermarkRow>,
+    block_number_threshold: u32,
+) -> ManifestDumpPlan<'a> {
+    let Some(watermark) = watermark else {
+        return ManifestDumpPlan::Full;
+    };
+
+    match entry.deltas_after(watermark.last_block, &watermark.last_hash) {
+        Some([]) => ManifestDumpPlan::Skip,
+        Some(deltas) => ManifestDumpPlan::Deltas(deltas),
+        None if entry.head_block().saturating_sub(watermark.last_block)
+            > u64::from(block_number_threshold) =>
+        {
+            ManifestDumpPlan::Full
+        }
+        None => ManifestDumpPlan::Skip,
+    }
+}
+
 /// Finds the manifest entry corresponding to the runner target, if present.
 pub fn lookup_manifest_entry(
     manifest_map: &ManifestMap,
@@ -77,7 +185,8 @@ pub fn lookup_manifest_entry(
 #[cfg(all(test, not(target_family = "wasm")))]
 mod tests {
     use super::*;
-    use alloy::primitives::{address, Bytes};
+    use alloy::primitives::{address, Address, Bytes, B256};
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
     use flate2::write::GzEncoder;
     use httpmock::prelude::*;
     use rain_orderbook_app_settings::local_db_manifest::{
@@ -185,6 +294,7 @@ orderbooks:
                 document: default_document(),
                 key: remote_key,
                 url: url.clone(),
+                publisher: None,
             };
             orderbook.local_db_remote = Some(Arc::new(remote));
         }
@@ -201,9 +311,11 @@ orderbooks:
         let manifest_entry = ManifestOrderbook {
             address: target.inputs.ob_id.orderbook_address,
             dump_url: Url::parse("https://example.com/dump.sql.gz").unwrap(),
+            dump_sha256: B256::ZERO,
             end_block: 123,
             end_block_hash: Bytes::from_static(&[0x01, 0x02, 0x03]),
             end_block_time_ms: 456,
+            deltas: vec![],
         };
 
         let manifest = LocalDbManifest {
@@ -216,6 +328,7 @@ orderbooks:
                     orderbooks: vec![manifest_entry.clone()],
                 },
             )]),
+            signature: None,
         };
 
         let map = HashMap::from([(target.manifest_url.clone(), manifest)]);
@@ -260,7 +373,7 @@ orderbooks:
         let server_two = MockServer::start();
 
         let manifest_one = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -268,13 +381,14 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "https://example.com/dump1.sql.gz"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 100
         end-block-hash: "0x01"
         end-block-time-ms: 1000
 "#;
 
         let manifest_two = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   goerli:
@@ -282,6 +396,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000002"
         dump-url: "https://example.com/dump2.sql.gz"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 200
         end-block-hash: "0x02"
         end-block-time-ms: 2000
@@ -330,7 +445,7 @@ networks: {{}}
 "#
         );
         let manifest_two = r#"
-manifest-version: 2
+manifest-version: 999
 db-schema-version: 2
 networks: {}
 "#;
@@ -379,6 +494,77 @@ networks: {}
         }
     }
 
+    #[test]
+    fn get_manifests_rejects_unsigned_manifest_of_publisher_remote() {
+        let rt = build_runtime();
+        let server = MockServer::start();
+        let manifest = format!(
+            r#"
+manifest-version: {MANIFEST_VERSION}
+db-schema-version: 2
+networks: {{}}
+"#
+        );
+        server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200).body(&manifest);
+        });
+
+        let mut parsed = parsed_settings();
+        let url = Url::parse(&server.base_url()).unwrap();
+        let publisher = address!("00000000000000000000000000000000000000ee");
+        for orderbook in parsed.orderbooks.values_mut() {
+            let remote = orderbook.local_db_remote.as_ref().expect("remote");
+            orderbook.local_db_remote = Some(Arc::new(LocalDbRemoteCfg {
+                document: default_document(),
+                key: remote.key.clone(),
+                url: url.clone(),
+                publisher: Some(publisher),
+            }));
+        }
+
+        let err = rt.block_on(get_manifests(&parsed.orderbooks)).unwrap_err();
+        match err {
+            LocalDbError::ManifestSignatureInvalid {
+                url: err_url,
+                publisher: err_publisher,
+                ..
+            } => {
+                assert_eq!(err_url, url.to_string());
+                assert_eq!(err_publisher, publisher);
+            }
+            other => panic!("expected ManifestSignatureInvalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn verify_manifest_signature_checks_publisher() {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x01)).unwrap();
+        let url = Url::parse("https://remotes.example.com/a.yaml").unwrap();
+        let mut manifest = LocalDbManifest::new();
+        manifest.add_network("mainnet", 1).unwrap();
+
+        let err = verify_manifest_signature(&url, &manifest, signer.address()).unwrap_err();
+        assert!(matches!(err, LocalDbError::ManifestSignatureInvalid { .. }));
+
+        let payload = manifest.signing_payload().unwrap();
+        let signature = signer.sign_message_sync(payload.as_bytes()).unwrap();
+        manifest.signature = Some(Bytes::copy_from_slice(&signature.as_bytes()));
+        verify_manifest_signature(&url, &manifest, signer.address()).expect("valid signature");
+
+        let other = Address::repeat_byte(0x22);
+        let err = verify_manifest_signature(&url, &manifest, other).unwrap_err();
+        assert!(matches!(
+            err,
+            LocalDbError::ManifestSignatureInvalid { publisher, .. } if publisher == other
+        ));
+
+        let mut tampered = manifest.clone();
+        tampered.add_network("polygon", 137).unwrap();
+        let err = verify_manifest_signature(&url, &tampered, signer.address()).unwrap_err();
+        assert!(matches!(err, LocalDbError::ManifestSignatureInvalid { .. }));
+    }
+
     #[test]
     fn download_and_gunzip_success() {
         let rt = build_runtime();
@@ -400,11 +586,47 @@ networks: {}
 
         let url = Url::parse(&format!("{}/dump.sql.gz", server.base_url())).unwrap();
         let contents = rt
-            .block_on(download_and_gunzip(&url))
+            .block_on(download_and_gunzip(&url, &dump_sha256(&gzipped)))
             .expect("decompressed");
         assert_eq!(contents, body_text);
     }
 
+    #[test]
+    fn download_and_gunzip_rejects_checksum_mismatch() {
+        let rt = build_runtime();
+        let server = MockServer::start();
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(b"INSERT INTO orders VALUES (1);")
+            .expect("encode gzip");
+        let gzipped = encoder.finish().expect("finish gzip");
+        let actual_sha256 = dump_sha256(&gzipped);
+
+        server.mock(|when, then| {
+            when.method(GET).path("/dump.sql.gz");
+            then.status(200).body(gzipped.clone());
+        });
+
+        let url = Url::parse(&format!("{}/dump.sql.gz", server.base_url())).unwrap();
+        let expected_sha256 = B256::repeat_byte(0x11);
+        let err = rt
+            .block_on(download_and_gunzip(&url, &expected_sha256))
+            .unwrap_err();
+        match err {
+            LocalDbError::DumpChecksumMismatch {
+                url: err_url,
+                expected,
+                actual,
+            } => {
+                assert_eq!(err_url, url.to_string());
+                assert_eq!(expected, expected_sha256);
+                assert_eq!(actual, actual_sha256);
+            }
+            other => panic!("expected DumpChecksumMismatch, got {other:?}"),
+        }
+    }
+
     #[test]
     fn download_and_gunzip_non_success_status() {
         let rt = build_runtime();
@@ -414,7 +636,9 @@ networks: {}
             then.status(404);
         });
         let url = Url::parse(&format!("{}/dump.sql.gz", server.base_url())).unwrap();
-        let err = rt.block_on(download_and_gunzip(&url)).unwrap_err();
+        let err = rt
+            .block_on(download_and_gunzip(&url, &B256::ZERO))
+            .unwrap_err();
         match err {
             LocalDbError::HttpStatus { status } => assert_eq!(status, 404),
             other => panic!("expected HttpStatus error, got {other:?}"),
@@ -430,7 +654,9 @@ networks: {}
             then.status(200).body("not-gzip");
         });
         let url = Url::parse(&format!("{}/dump.sql.gz", server.base_url())).unwrap();
-        let err = rt.block_on(download_and_gunzip(&url)).unwrap_err();
+        let err = rt
+            .block_on(download_and_gunzip(&url, &dump_sha256(b"not-gzip")))
+            .unwrap_err();
         match err {
             LocalDbError::IoError(_) => {}
             other => panic!("expected IoError due to decompression failure, got {other:?}"),
@@ -442,7 +668,9 @@ networks: {}
         let rt = build_runtime();
         let url = Url::parse("nosuch://unreachable.example/dump.sql.gz").unwrap();
 
-        let err = rt.block_on(download_and_gunzip(&url)).unwrap_err();
+        let err = rt
+            .block_on(download_and_gunzip(&url, &B256::ZERO))
+            .unwrap_err();
         match err {
             LocalDbError::Http(_) => {}
             other => panic!("expected Http error for unreachable URL, got {other:?}"),
@@ -478,4 +706,65 @@ networks: {}
 
         assert!(lookup_manifest_entry(&manifest_map, &target).is_none());
     }
+
+    #[test]
+    fn plan_manifest_dumps_fetches_only_missing_deltas() {
+        let hash = |byte: u8| Bytes::from(vec![byte]);
+        let delta = |start_block: u64, end_block: u64| ManifestDelta {
+            dump_url: Url::parse(&format!("https://example.com/delta-{end_block}.sql.gz")).unwrap(),
+            dump_sha256: B256::ZERO,
+            start_block,
+            start_block_hash: hash(start_block as u8),
+            end_block,
+            end_block_hash: hash(end_block as u8),
+            end_block_time_ms: end_block,
+        };
+        let entry = ManifestOrderbook {
+            address: Address::ZERO,
+            dump_url: Url::parse("https://example.com/dump.sql.gz").unwrap(),
+            dump_sha256: B256::ZERO,
+            end_block: 100,
+            end_block_hash: hash(100),
+            end_block_time_ms: 100,
+            deltas: vec![delta(100, 150), delta(150, 200)],
+        };
+        let watermark = |last_block: u64, last_hash: Bytes| TargetWatermarkRow {
+            chain_id: 1,
+            orderbook_address: Address::ZERO,
+            last_block,
+            last_hash,
+            updated_at: 0,
+        };
+
+        assert_eq!(
+            plan_manifest_dumps(&entry, None, 10),
+            ManifestDumpPlan::Full
+        );
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(100, hash(100))), 10),
+            ManifestDumpPlan::Deltas(&entry.deltas)
+        );
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(150, hash(150))), 10),
+            ManifestDumpPlan::Deltas(&entry.deltas[1..])
+        );
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(200, hash(200))), 10),
+            ManifestDumpPlan::Skip
+        );
+        // Off the delta chain: only a watermark lagging past the threshold
+        // re-imports the base snapshot.
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(150, hash(1))), 10),
+            ManifestDumpPlan::Full
+        );
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(195, hash(1))), 10),
+            ManifestDumpPlan::Skip
+        );
+        assert_eq!(
+            plan_manifest_dumps(&entry, Some(&watermark(250, hash(1))), 10),
+            ManifestDumpPlan::Skip
+        );
+    }
 }
@@ -85,6 +85,7 @@ pub fn build_runner_targets(
                 window_overrides: WindowOverrides::default(),
             },
             dump_str: None,
+            delta_dump_str: None,
             block_number_threshold: sync_cfg.bootstrap_block_threshold,
             manifest_end_block: 0,
         };
@@ -282,6 +283,7 @@ orderbooks:
             finality_depth: 32,
             bootstrap_block_threshold: 100,
             sync_interval_ms: 5000,
+            hyperrpc_url: None,
         };
 
         let (fetch, finality) = map_sync_to_engine(&sync).expect("map succeeds");
@@ -0,0 +1,527 @@
+//! Domain events published after a sync cycle has been persisted.
+//!
+//! Orders, trades, deposits and withdrawals are mapped straight from the
+//! decoded logs of the cycle. Vault balance changes are computed in SQL while
+//! the batch is applied, so they are read back from `vault_balance_changes`
+//! for the synced window and only when someone is listening.
+
+use super::SyncOutcome;
+use crate::local_db::decode::{DecodedEvent, DecodedEventData};
+use crate::local_db::insert::u256_to_u64;
+use crate::local_db::query::fetch_vault_balance_changes::LocalDbVaultBalanceChange;
+use crate::local_db::query::fetch_vault_balance_changes_in_range::fetch_vault_balance_changes_in_range_stmt;
+use crate::local_db::query::LocalDbQueryExecutor;
+use crate::local_db::{LocalDbError, OrderbookIdentifier};
+use crate::types::VaultBalanceChangeKind;
+use alloy::primitives::{keccak256, Address, B256, U256};
+use alloy::sol_types::SolValue;
+use serde::{Deserialize, Serialize};
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+/// How a trade was executed against an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "camelCase")]
+pub enum SyncTradeKind {
+    Take,
+    Clear,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(SyncTradeKind);
+
+/// What happened in a [`SyncEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SyncEventKind {
+    #[serde(rename_all = "camelCase")]
+    OrderAdded {
+        #[cfg_attr(target_family = "wasm", tsify(type = "Hex"))]
+        order_hash: B256,
+        owner: Address,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderRemoved {
+        #[cfg_attr(target_family = "wasm", tsify(type = "Hex"))]
+        order_hash: B256,
+        owner: Address,
+    },
+    /// An order was filled, either by a taker or by a clear. The amounts
+    /// moved arrive as `vaultBalanceChange` events in the same transaction.
+    #[serde(rename_all = "camelCase")]
+    Trade {
+        #[cfg_attr(target_family = "wasm", tsify(type = "Hex"))]
+        order_hash: B256,
+        owner: Address,
+        /// Taker or clearer that submitted the transaction
+        sender: Address,
+        trade_kind: SyncTradeKind,
+    },
+    #[serde(rename_all = "camelCase")]
+    Deposit {
+        sender: Address,
+        token: Address,
+        #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+        vault_id: U256,
+        /// Raw token amount
+        #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+        amount: U256,
+    },
+    #[serde(rename_all = "camelCase")]
+    Withdraw {
+        sender: Address,
+        token: Address,
+        #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+        vault_id: U256,
+        /// Raw token amount that left the vault
+        #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+        amount: U256,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultBalanceChange {
+        owner: Address,
+        token: Address,
+        #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+        vault_id: U256,
+        #[cfg_attr(target_family = "wasm", tsify(type = "RaindexVaultBalanceChangeType"))]
+        change_type: VaultBalanceChangeKind,
+        /// Float hex of the change
+        delta: String,
+        /// Float hex of the vault balance after the change
+        running_balance: String,
+    },
+}
+
+/// A domain event applied to the local DB by a sync cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEvent {
+    pub ob_id: OrderbookIdentifier,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    #[cfg_attr(target_family = "wasm", tsify(type = "Hex"))]
+    pub transaction_hash: B256,
+    pub log_index: u64,
+    pub kind: SyncEventKind,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(SyncEvent);
+
+/// Maps decoded orderbook logs to domain events, keeping their order.
+///
+/// Store writes, metadata, `AfterClearV2` and unknown logs carry nothing a
+/// subscriber acts on and are skipped.
+pub fn sync_events_from_decoded(
+    ob_id: &OrderbookIdentifier,
+    decoded_events: &[DecodedEventData<DecodedEvent>],
+) -> Result<Vec<SyncEvent>, LocalDbError> {
+    let mut events = Vec::new();
+
+    for event in decoded_events {
+        let kinds = match &event.decoded_data {
+            DecodedEvent::AddOrderV3(decoded) => vec![SyncEventKind::OrderAdded {
+                order_hash: decoded.orderHash,
+                owner: decoded.order.owner,
+            }],
+            DecodedEvent::RemoveOrderV3(decoded) => vec![SyncEventKind::OrderRemoved {
+                order_hash: decoded.orderHash,
+                owner: decoded.order.owner,
+            }],
+            DecodedEvent::TakeOrderV3(decoded) => vec![SyncEventKind::Trade {
+                order_hash: keccak256(decoded.config.order.abi_encode()),
+                owner: decoded.config.order.owner,
+                sender: decoded.sender,
+                trade_kind: SyncTradeKind::Take,
+            }],
+            DecodedEvent::ClearV3(decoded) => [&decoded.alice, &decoded.bob]
+                .into_iter()
+                .map(|order| SyncEventKind::Trade {
+                    order_hash: keccak256(order.abi_encode()),
+                    owner: order.owner,
+                    sender: decoded.sender,
+                    trade_kind: SyncTradeKind::Clear,
+                })
+                .collect(),
+            DecodedEvent::DepositV2(decoded) => vec![SyncEventKind::Deposit {
+                sender: decoded.sender,
+                token: decoded.token,
+                vault_id: U256::from_be_bytes(decoded.vaultId.0),
+                amount: decoded.depositAmountUint256,
+            }],
+            DecodedEvent::WithdrawV2(decoded) => vec![SyncEventKind::Withdraw {
+                sender: decoded.sender,
+                token: decoded.token,
+                vault_id: U256::from_be_bytes(decoded.vaultId.0),
+                amount: decoded.withdrawAmountUint256,
+            }],
+            DecodedEvent::AfterClearV2(_)
+            | DecodedEvent::MetaV1_2(_)
+            | DecodedEvent::InterpreterStoreSet(_)
+            | DecodedEvent::Unknown(_) => continue,
+        };
+
+        let block_number = u256_to_u64(&event.block_number, "block_number")?;
+        let block_timestamp = u256_to_u64(&event.block_timestamp, "block_timestamp")?;
+        let log_index = u256_to_u64(&event.log_index, "log_index")?;
+        events.extend(kinds.into_iter().map(|kind| SyncEvent {
+            ob_id: ob_id.clone(),
+            block_number,
+            block_timestamp,
+            transaction_hash: event.transaction_hash,
+            log_index,
+            kind,
+        }));
+    }
+
+    Ok(events)
+}
+
+impl From<(&OrderbookIdentifier, LocalDbVaultBalanceChange)> for SyncEvent {
+    fn from((ob_id, change): (&OrderbookIdentifier, LocalDbVaultBalanceChange)) -> Self {
+        Self {
+            ob_id: ob_id.clone(),
+            block_number: change.block_number,
+            block_timestamp: change.block_timestamp,
+            transaction_hash: change.transaction_hash,
+            log_index: change.log_index,
+            kind: SyncEventKind::VaultBalanceChange {
+                owner: change.owner,
+                token: change.token,
+                vault_id: change.vault_id,
+                change_type: VaultBalanceChangeKind::from_local_db_change_type(&change.change_type),
+                delta: change.delta,
+                running_balance: change.running_balance,
+            },
+        }
+    }
+}
+
+/// Returns every event applied by a persisted cycle: the outcome's decoded
+/// events plus the vault balance changes the cycle wrote, ordered by block
+/// and log index.
+pub async fn collect_sync_events<DB>(
+    db: &DB,
+    outcome: &SyncOutcome,
+) -> Result<Vec<SyncEvent>, LocalDbError>
+where
+    DB: LocalDbQueryExecutor + ?Sized,
+{
+    if outcome.start_block > outcome.target_block {
+        return Ok(Vec::new());
+    }
+
+    let changes: Vec<LocalDbVaultBalanceChange> = db
+        .query_json(&fetch_vault_balance_changes_in_range_stmt(
+            &outcome.ob_id,
+            outcome.start_block,
+            outcome.target_block,
+        ))
+        .await?;
+
+    let mut events = outcome.events.clone();
+    events.extend(
+        changes
+            .into_iter()
+            .map(|change| SyncEvent::from((&outcome.ob_id, change))),
+    );
+    // stable, so a log's own event stays ahead of the balance changes it caused
+    events.sort_by_key(|event| (event.block_number, event.log_index));
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_db::decode::{EventType, UnknownEventDecoded};
+    use crate::local_db::query::{FromDbJson, LocalDbQueryError, SqlStatement, SqlStatementBatch};
+    use alloy::primitives::{address, b256, Bytes};
+    use async_trait::async_trait;
+    use rain_orderbook_bindings::IRaindexV6::{
+        AddOrderV3, ClearConfigV2, ClearV3, DepositV2, EvaluableV4, OrderV4, TakeOrderConfigV4,
+        TakeOrderV3, WithdrawV2,
+    };
+    use serde_json::json;
+
+    fn ob_id() -> OrderbookIdentifier {
+        OrderbookIdentifier::new(1, address!("0000000000000000000000000000000000001234"))
+    }
+
+    fn order(owner: Address) -> OrderV4 {
+        OrderV4 {
+            owner,
+            nonce: U256::from(1).into(),
+            evaluable: EvaluableV4 {
+                interpreter: Address::from([2u8; 20]),
+                store: Address::from([3u8; 20]),
+                bytecode: Bytes::from(vec![0x01]),
+            },
+            validInputs: vec![],
+            validOutputs: vec![],
+        }
+    }
+
+    fn event(
+        event_type: EventType,
+        block: u64,
+        log_index: u64,
+        decoded_data: DecodedEvent,
+    ) -> DecodedEventData<DecodedEvent> {
+        DecodedEventData {
+            event_type,
+            block_number: U256::from(block),
+            block_timestamp: U256::from(1_700_000_000u64 + block),
+            transaction_hash: B256::from(U256::from(block)),
+            log_index: U256::from(log_index),
+            decoded_data,
+        }
+    }
+
+    fn deposit(block: u64, log_index: u64) -> DecodedEventData<DecodedEvent> {
+        event(
+            EventType::DepositV2,
+            block,
+            log_index,
+            DecodedEvent::DepositV2(Box::new(DepositV2 {
+                sender: Address::from([1u8; 20]),
+                token: Address::from([4u8; 20]),
+                vaultId: U256::from(7).into(),
+                depositAmountUint256: U256::from(1000),
+            })),
+        )
+    }
+
+    struct ChangesDb {
+        rows: serde_json::Value,
+    }
+
+    #[cfg_attr(target_family = "wasm", async_trait(?Send))]
+    #[cfg_attr(not(target_family = "wasm"), async_trait)]
+    impl LocalDbQueryExecutor for ChangesDb {
+        async fn execute_batch(&self, _batch: &SqlStatementBatch) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+
+        async fn query_json<T>(&self, stmt: &SqlStatement) -> Result<T, LocalDbQueryError>
+        where
+            T: FromDbJson,
+        {
+            assert!(stmt.sql().contains("FROM vault_balance_changes"));
+            serde_json::from_value(self.rows.clone())
+                .map_err(|e| LocalDbQueryError::deserialization(e.to_string()))
+        }
+
+        async fn query_text(&self, _stmt: &SqlStatement) -> Result<String, LocalDbQueryError> {
+            Ok(String::new())
+        }
+
+        async fn wipe_and_recreate(&self) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn maps_orders_trades_and_vault_movements() {
+        let owner = Address::from([9u8; 20]);
+        let alice = order(Address::from([0xa1; 20]));
+        let bob = order(Address::from([0xb0; 20]));
+        let taker = Address::from([0x0c; 20]);
+
+        let decoded = vec![
+            event(
+                EventType::AddOrderV3,
+                10,
+                0,
+                DecodedEvent::AddOrderV3(Box::new(AddOrderV3 {
+                    sender: owner,
+                    orderHash: B256::from([8u8; 32]),
+                    order: order(owner),
+                })),
+            ),
+            deposit(10, 1),
+            event(
+                EventType::TakeOrderV3,
+                11,
+                0,
+                DecodedEvent::TakeOrderV3(Box::new(TakeOrderV3 {
+                    sender: taker,
+                    config: TakeOrderConfigV4 {
+                        order: order(owner),
+                        inputIOIndex: U256::ZERO,
+                        outputIOIndex: U256::ZERO,
+                        signedContext: vec![],
+                    },
+                    input: U256::from(1).into(),
+                    output: U256::from(2).into(),
+                })),
+            ),
+            event(
+                EventType::ClearV3,
+                12,
+                0,
+                DecodedEvent::ClearV3(Box::new(ClearV3 {
+                    sender: taker,
+                    alice: alice.clone(),
+                    bob: bob.clone(),
+                    clearConfig: ClearConfigV2 {
+                        aliceInputIOIndex: U256::ZERO,
+                        aliceOutputIOIndex: U256::ZERO,
+                        bobInputIOIndex: U256::ZERO,
+                        bobOutputIOIndex: U256::ZERO,
+                        aliceBountyVaultId: U256::ZERO.into(),
+                        bobBountyVaultId: U256::ZERO.into(),
+                    },
+                })),
+            ),
+            event(
+                EventType::WithdrawV2,
+                13,
+                0,
+                DecodedEvent::WithdrawV2(Box::new(WithdrawV2 {
+                    sender: owner,
+                    token: Address::from([4u8; 20]),
+                    vaultId: U256::from(7).into(),
+                    targetAmount: U256::from(600).into(),
+                    withdrawAmount: U256::from(500).into(),
+                    withdrawAmountUint256: U256::from(500),
+                })),
+            ),
+        ];
+
+        let events = sync_events_from_decoded(&ob_id(), &decoded).unwrap();
+        let kinds: Vec<_> = events.iter().map(|e| e.kind.clone()).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                SyncEventKind::OrderAdded {
+                    order_hash: B256::from([8u8; 32]),
+                    owner,
+                },
+                SyncEventKind::Deposit {
+                    sender: Address::from([1u8; 20]),
+                    token: Address::from([4u8; 20]),
+                    vault_id: U256::from(7),
+                    amount: U256::from(1000),
+                },
+                SyncEventKind::Trade {
+                    order_hash: keccak256(order(owner).abi_encode()),
+                    owner,
+                    sender: taker,
+                    trade_kind: SyncTradeKind::Take,
+                },
+                SyncEventKind::Trade {
+                    order_hash: keccak256(alice.abi_encode()),
+                    owner: alice.owner,
+                    sender: taker,
+                    trade_kind: SyncTradeKind::Clear,
+                },
+                SyncEventKind::Trade {
+                    order_hash: keccak256(bob.abi_encode()),
+                    owner: bob.owner,
+                    sender: taker,
+                    trade_kind: SyncTradeKind::Clear,
+                },
+                SyncEventKind::Withdraw {
+                    sender: owner,
+                    token: Address::from([4u8; 20]),
+                    vault_id: U256::from(7),
+                    amount: U256::from(500),
+                },
+            ]
+        );
+        assert_eq!(events[1].block_number, 10);
+        assert_eq!(events[1].block_timestamp, 1_700_000_010);
+        assert_eq!(events[1].log_index, 1);
+        assert_eq!(events[1].ob_id, ob_id());
+    }
+
+    #[test]
+    fn skips_events_without_subscribers_value() {
+        let decoded = vec![event(
+            EventType::Unknown,
+            1,
+            0,
+            DecodedEvent::Unknown(UnknownEventDecoded {
+                raw_data: "0x".to_string(),
+                note: "unknown".to_string(),
+            }),
+        )];
+
+        assert!(sync_events_from_decoded(&ob_id(), &decoded)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn serializes_with_type_tag() {
+        let event = sync_events_from_decoded(&ob_id(), &[deposit(5, 2)])
+            .unwrap()
+            .remove(0);
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(value["kind"]["type"], "deposit");
+        assert_eq!(value["kind"]["vaultId"], "0x7");
+        assert_eq!(value["blockNumber"], 5);
+        assert_eq!(value["logIndex"], 2);
+    }
+
+    #[tokio::test]
+    async fn collect_merges_vault_balance_changes_in_log_order() {
+        let decoded = vec![deposit(10, 1), deposit(12, 0)];
+        let outcome = SyncOutcome {
+            ob_id: ob_id(),
+            start_block: 10,
+            target_block: 12,
+            fetched_logs: 2,
+            decoded_events: 2,
+            rolled_back_to: None,
+            events: sync_events_from_decoded(&ob_id(), &decoded).unwrap(),
+        };
+        let db = ChangesDb {
+            rows: json!([{
+                "transactionHash": b256!("0x000000000000000000000000000000000000000000000000000000000000000a"),
+                "logIndex": 1,
+                "blockNumber": 10,
+                "blockTimestamp": 1_700_000_010u64,
+                "owner": Address::from([1u8; 20]),
+                "changeType": "DEPOSIT",
+                "token": Address::from([4u8; 20]),
+                "vaultId": "0x7",
+                "delta": "0x01",
+                "runningBalance": "0x01"
+            }]),
+        };
+
+        let events = collect_sync_events(&db, &outcome).await.unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0].kind, SyncEventKind::Deposit { .. }));
+        assert!(matches!(
+            events[1].kind,
+            SyncEventKind::VaultBalanceChange {
+                change_type: VaultBalanceChangeKind::Deposit,
+                ..
+            }
+        ));
+        assert_eq!(events[2].block_number, 12);
+    }
+
+    #[tokio::test]
+    async fn collect_skips_query_for_empty_window() {
+        let outcome = SyncOutcome {
+            ob_id: ob_id(),
+            start_block: 11,
+            target_block: 10,
+            fetched_logs: 0,
+            decoded_events: 0,
+            rolled_back_to: None,
+            events: vec![],
+        };
+        let db = ChangesDb { rows: json!(null) };
+
+        assert!(collect_sync_events(&db, &outcome).await.unwrap().is_empty());
+    }
+}
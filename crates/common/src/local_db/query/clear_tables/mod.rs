@@ -12,6 +12,7 @@ pub fn clear_tables_stmt() -> SqlStatement {
 mod tests {
     use super::*;
     use crate::local_db::query::create_tables::REQUIRED_TABLES;
+    use crate::local_db::query::db_migrations::DB_MIGRATIONS_TABLE;
     use std::collections::HashSet;
 
     fn normalize_ident(s: &str) -> String {
@@ -68,8 +69,12 @@ mod tests {
             offset = end + 1;
         }
 
-        let required: HashSet<String> =
-            REQUIRED_TABLES.iter().map(|t| normalize_ident(t)).collect();
+        // The migrations table isn't part of the schema but goes with it
+        let required: HashSet<String> = REQUIRED_TABLES
+            .iter()
+            .chain([&DB_MIGRATIONS_TABLE])
+            .map(|t| normalize_ident(t))
+            .collect();
 
         let missing: HashSet<_> = required.difference(&dropped).cloned().collect();
         let extra: HashSet<_> = dropped.difference(&required).cloned().collect();
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS db_migrations (
+    version INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1 @@
+INSERT INTO db_migrations (version, name) VALUES (?1, ?2);
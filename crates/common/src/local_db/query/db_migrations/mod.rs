@@ -0,0 +1,59 @@
+use crate::local_db::query::{SqlStatement, SqlValue};
+
+pub const DB_MIGRATIONS_TABLE: &str = "db_migrations";
+
+const CREATE_TABLE_SQL: &str = include_str!("create_table.sql");
+const INSERT_SQL: &str = include_str!("insert.sql");
+const UPDATE_SCHEMA_VERSION_SQL: &str = include_str!("update_schema_version.sql");
+
+/// Creates the table recording applied migrations. It is created by the first
+/// migration a database goes through rather than with the schema, so databases
+/// predating it keep passing the required tables check.
+pub fn create_db_migrations_table_stmt() -> SqlStatement {
+    SqlStatement::new(CREATE_TABLE_SQL)
+}
+
+pub fn insert_db_migration_stmt(version: u32, name: &str) -> SqlStatement {
+    SqlStatement::new_with_params(
+        INSERT_SQL,
+        [SqlValue::from(version), SqlValue::from(name.to_string())],
+    )
+}
+
+pub fn update_db_schema_version_stmt(version: u32) -> SqlStatement {
+    SqlStatement::new_with_params(UPDATE_SCHEMA_VERSION_SQL, [SqlValue::from(version)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_table_stmt_is_idempotent_and_param_free() {
+        let stmt = create_db_migrations_table_stmt();
+        assert!(stmt
+            .sql()
+            .to_lowercase()
+            .starts_with("create table if not exists db_migrations"));
+        assert!(stmt.params().is_empty());
+    }
+
+    #[test]
+    fn insert_stmt_binds_version_and_name() {
+        let stmt = insert_db_migration_stmt(3, "add_index");
+        assert_eq!(stmt.sql(), INSERT_SQL);
+        assert_eq!(
+            stmt.params(),
+            &[SqlValue::U64(3), SqlValue::Text("add_index".to_string())]
+        );
+    }
+
+    #[test]
+    fn update_schema_version_stmt_targets_metadata_row() {
+        let stmt = update_db_schema_version_stmt(4);
+        let lower = stmt.sql().to_lowercase();
+        assert!(lower.starts_with("update db_metadata set db_schema_version = ?1"));
+        assert!(lower.contains("where id = 1"));
+        assert_eq!(stmt.params(), &[SqlValue::U64(4)]);
+    }
+}
@@ -0,0 +1 @@
+UPDATE db_metadata SET db_schema_version = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = 1;
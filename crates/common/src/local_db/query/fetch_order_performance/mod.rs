@@ -0,0 +1,198 @@
+use crate::local_db::{
+    query::{SqlBuildError, SqlStatement, SqlValue},
+    OrderbookIdentifier,
+};
+use alloy::primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+const QUERY_TEMPLATE: &str = include_str!("query.sql");
+
+/// Per vault balance movements of an order's vault over a window. All amounts
+/// are Float hex strings in the vault's token. Each token of the order also
+/// gets a row without a vault id that sums its vaults of that token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LocalDbVaultPerformance {
+    /// `None` on the order's totals for the token
+    pub vault_id: Option<U256>,
+    pub token: Address,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    pub token_decimals: Option<u8>,
+    /// Vault balance right before the window
+    pub starting_balance: String,
+    /// Vault balance at the end of the window
+    pub ending_balance: String,
+    pub total_deposits: String,
+    pub total_withdrawals: String,
+    /// Sum of the balance changes of the order's own trades in the window,
+    /// trades of other orders sharing the vault and clear bounties are left out
+    pub pnl: String,
+    /// Timestamp of the first balance change in the window
+    pub first_timestamp: Option<u64>,
+    /// Timestamp of the last balance change in the window
+    pub last_timestamp: Option<u64>,
+}
+
+/// Block and/or timestamp bounds of a performance window, all inclusive.
+/// Unset bounds leave the window open on that side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerformanceWindow {
+    pub start_timestamp: Option<u64>,
+    pub end_timestamp: Option<u64>,
+    pub start_block: Option<u64>,
+    pub end_block: Option<u64>,
+}
+
+const START_TS_CLAUSE: &str = "/*START_TS_CLAUSE*/";
+const START_TS_BODY: &str = "\nAND block_timestamp >= {param}\n";
+
+const END_TS_CLAUSE: &str = "/*END_TS_CLAUSE*/";
+const END_TS_BODY: &str = "\nAND block_timestamp <= {param}\n";
+
+const START_BLOCK_CLAUSE: &str = "/*START_BLOCK_CLAUSE*/";
+const START_BLOCK_BODY: &str = "\nAND block_number >= {param}\n";
+
+const END_BLOCK_CLAUSE: &str = "/*END_BLOCK_CLAUSE*/";
+const END_BLOCK_BODY: &str = "\nAND block_number <= {param}\n";
+
+const BEFORE_START_TS_CLAUSE: &str = "/*BEFORE_START_TS_CLAUSE*/";
+const BEFORE_START_TS_BODY: &str = "\nOR block_timestamp < {param}\n";
+
+const BEFORE_START_BLOCK_CLAUSE: &str = "/*BEFORE_START_BLOCK_CLAUSE*/";
+const BEFORE_START_BLOCK_BODY: &str = "\nOR block_number < {param}\n";
+
+fn to_i64_param(name: &str, value: Option<u64>) -> Result<Option<SqlValue>, SqlBuildError> {
+    value
+        .map(|v| {
+            i64::try_from(v).map(SqlValue::I64).map_err(|e| {
+                SqlBuildError::new(format!("{} out of range for i64: {} ({})", name, v, e))
+            })
+        })
+        .transpose()
+}
+
+pub fn build_fetch_order_performance_stmt(
+    ob_id: &OrderbookIdentifier,
+    order_hash: B256,
+    window: &PerformanceWindow,
+) -> Result<SqlStatement, SqlBuildError> {
+    let mut stmt = SqlStatement::new(QUERY_TEMPLATE);
+    stmt.push(SqlValue::from(ob_id.chain_id));
+    stmt.push(SqlValue::from(ob_id.orderbook_address));
+    stmt.push(SqlValue::from(order_hash));
+
+    let start_ts = to_i64_param("start_timestamp", window.start_timestamp)?;
+    let end_ts = to_i64_param("end_timestamp", window.end_timestamp)?;
+    let start_block = to_i64_param("start_block", window.start_block)?;
+    let end_block = to_i64_param("end_block", window.end_block)?;
+
+    stmt.bind_param_clause(START_TS_CLAUSE, START_TS_BODY, start_ts.clone())?;
+    stmt.bind_param_clause(END_TS_CLAUSE, END_TS_BODY, end_ts)?;
+    stmt.bind_param_clause(START_BLOCK_CLAUSE, START_BLOCK_BODY, start_block.clone())?;
+    stmt.bind_param_clause(END_BLOCK_CLAUSE, END_BLOCK_BODY, end_block)?;
+
+    // changes before the window's start make up the starting balances
+    stmt.bind_param_clause(BEFORE_START_TS_CLAUSE, BEFORE_START_TS_BODY, start_ts)?;
+    stmt.bind_param_clause(
+        BEFORE_START_BLOCK_CLAUSE,
+        BEFORE_START_BLOCK_BODY,
+        start_block,
+    )?;
+
+    Ok(stmt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        hex,
+        primitives::{b256, Address},
+    };
+
+    #[test]
+    fn builds_with_timestamp_window() {
+        let order_hash =
+            b256!("0x00000000000000000000000000000000000000000000000000000000deadface");
+        let stmt = build_fetch_order_performance_stmt(
+            &OrderbookIdentifier::new(137, Address::ZERO),
+            order_hash,
+            &PerformanceWindow {
+                start_timestamp: Some(11),
+                end_timestamp: Some(22),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!stmt.sql.contains("/*"));
+        // only the order's own trades count towards its pnl
+        assert!(stmt.sql.contains("c.alice_order_hash = p.order_hash"));
+        assert!(stmt.sql.contains("c.bob_order_hash = p.order_hash"));
+        assert!(stmt.sql.contains("block_timestamp >= ?4"));
+        assert!(stmt.sql.contains("block_timestamp <= ?5"));
+        assert!(stmt.sql.contains("OR block_timestamp < ?6"));
+        assert!(!stmt.sql.contains("block_number >="));
+        assert!(!stmt.sql.contains("OR block_number <"));
+        assert_eq!(
+            stmt.params,
+            vec![
+                SqlValue::U64(137),
+                SqlValue::Text(Address::ZERO.to_string()),
+                SqlValue::Text(hex::encode_prefixed(order_hash)),
+                SqlValue::I64(11),
+                SqlValue::I64(22),
+                SqlValue::I64(11),
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_with_block_window() {
+        let stmt = build_fetch_order_performance_stmt(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            B256::ZERO,
+            &PerformanceWindow {
+                start_block: Some(100),
+                end_block: Some(200),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(stmt.sql.contains("block_number >= ?4"));
+        assert!(stmt.sql.contains("block_number <= ?5"));
+        assert!(stmt.sql.contains("OR block_number < ?6"));
+        assert!(!stmt.sql.contains("block_timestamp >="));
+        assert_eq!(stmt.params.len(), 6);
+        assert_eq!(stmt.params[5], SqlValue::I64(100));
+    }
+
+    #[test]
+    fn builds_open_window() {
+        let stmt = build_fetch_order_performance_stmt(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            B256::ZERO,
+            &PerformanceWindow::default(),
+        )
+        .unwrap();
+        assert!(!stmt.sql.contains("/*"));
+        assert!(!stmt.sql.contains("block_timestamp >="));
+        assert!(!stmt.sql.contains("block_number >="));
+        // no starting balances without a window start
+        assert!(stmt.sql.contains("WHERE 0 = 1"));
+        assert_eq!(stmt.params.len(), 3);
+    }
+
+    #[test]
+    fn rejects_out_of_range_bounds() {
+        let err = build_fetch_order_performance_stmt(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            B256::ZERO,
+            &PerformanceWindow {
+                end_block: Some(u64::MAX),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("end_block out of range"));
+    }
+}
@@ -0,0 +1,230 @@
+WITH
+params AS (
+  SELECT
+    ?1 AS chain_id,
+    ?2 AS orderbook_address,
+    ?3 AS order_hash
+),
+order_vaults AS (
+  SELECT DISTINCT
+    oe.order_owner AS owner,
+    io.token,
+    io.vault_id
+  FROM order_events oe
+  JOIN params p
+    ON oe.chain_id = p.chain_id
+   AND oe.orderbook_address = p.orderbook_address
+   AND oe.order_hash = p.order_hash
+  JOIN order_ios io
+    ON io.chain_id = oe.chain_id
+   AND io.orderbook_address = oe.orderbook_address
+   AND io.transaction_hash = oe.transaction_hash
+   AND io.log_index = oe.log_index
+  WHERE oe.event_type = 'AddOrderV3'
+),
+order_add_events AS (
+  SELECT oe.*
+  FROM order_events oe
+  JOIN params p
+    ON oe.chain_id = p.chain_id
+   AND oe.orderbook_address = p.orderbook_address
+   AND oe.order_hash = p.order_hash
+  WHERE oe.event_type = 'AddOrderV3'
+),
+-- trade logs of this order and the prefix of the balance change types they
+-- write for it, clears of the order against itself produce both sides
+order_trade_logs AS (
+  SELECT DISTINCT t.transaction_hash, t.log_index, 'TAKE' AS kind
+  FROM take_orders t
+  JOIN order_add_events oe
+    ON oe.chain_id = t.chain_id
+   AND oe.orderbook_address = t.orderbook_address
+   AND oe.order_owner = t.order_owner
+   AND oe.order_nonce = t.order_nonce
+   AND (
+        oe.block_number < t.block_number
+     OR (oe.block_number = t.block_number AND oe.log_index <= t.log_index)
+   )
+  -- a later add of another order with the same owner and nonce takes over
+  WHERE NOT EXISTS (
+    SELECT 1
+    FROM order_events newer
+    WHERE newer.chain_id = t.chain_id
+      AND newer.orderbook_address = t.orderbook_address
+      AND newer.event_type = 'AddOrderV3'
+      AND newer.order_owner = t.order_owner
+      AND newer.order_nonce = t.order_nonce
+      AND newer.order_hash != oe.order_hash
+      AND (
+           newer.block_number < t.block_number
+        OR (newer.block_number = t.block_number AND newer.log_index <= t.log_index)
+      )
+      AND (
+           newer.block_number > oe.block_number
+        OR (newer.block_number = oe.block_number AND newer.log_index > oe.log_index)
+      )
+  )
+  UNION ALL
+  SELECT c.transaction_hash, c.log_index, 'CLEAR_ALICE'
+  FROM clear_v3_events c
+  JOIN params p
+    ON c.chain_id = p.chain_id
+   AND c.orderbook_address = p.orderbook_address
+   AND c.alice_order_hash = p.order_hash
+  UNION ALL
+  SELECT c.transaction_hash, c.log_index, 'CLEAR_BOB'
+  FROM clear_v3_events c
+  JOIN params p
+    ON c.chain_id = p.chain_id
+   AND c.orderbook_address = p.orderbook_address
+   AND c.bob_order_hash = p.order_hash
+),
+vault_changes AS (
+  SELECT
+    ov.token,
+    ov.vault_id,
+    vbc.transaction_hash,
+    vbc.block_number,
+    vbc.block_timestamp,
+    vbc.log_index,
+    vbc.change_type,
+    vbc.delta,
+    vbc.running_balance
+  FROM order_vaults ov
+  CROSS JOIN params p
+  JOIN vault_balance_changes vbc
+    ON vbc.chain_id = p.chain_id
+   AND vbc.orderbook_address = p.orderbook_address
+   AND vbc.owner = ov.owner
+   AND vbc.token = ov.token
+   AND vbc.vault_id = ov.vault_id
+),
+windowed_changes AS (
+  SELECT * FROM vault_changes
+  WHERE 1 = 1
+  /*START_TS_CLAUSE*/
+  /*END_TS_CLAUSE*/
+  /*START_BLOCK_CLAUSE*/
+  /*END_BLOCK_CLAUSE*/
+),
+prior_changes AS (
+  SELECT * FROM vault_changes
+  WHERE 0 = 1
+  /*BEFORE_START_TS_CLAUSE*/
+  /*BEFORE_START_BLOCK_CLAUSE*/
+),
+starting_balances AS (
+  SELECT pc.token, pc.vault_id, pc.running_balance AS balance
+  FROM prior_changes pc
+  WHERE NOT EXISTS (
+    SELECT 1
+    FROM prior_changes later
+    WHERE later.token = pc.token
+      AND later.vault_id = pc.vault_id
+      AND (
+           later.block_number > pc.block_number
+        OR (later.block_number = pc.block_number AND later.log_index > pc.log_index)
+      )
+  )
+),
+ending_balances AS (
+  SELECT wc.token, wc.vault_id, wc.running_balance AS balance
+  FROM windowed_changes wc
+  WHERE NOT EXISTS (
+    SELECT 1
+    FROM windowed_changes later
+    WHERE later.token = wc.token
+      AND later.vault_id = wc.vault_id
+      AND (
+           later.block_number > wc.block_number
+        OR (later.block_number = wc.block_number AND later.log_index > wc.log_index)
+      )
+  )
+),
+-- the vaults can be shared with other orders of the owner, only this order's
+-- trades and the owner's own deposits and withdrawals count towards its returns
+order_changes AS (
+  SELECT wc.*
+  FROM windowed_changes wc
+  WHERE wc.change_type IN ('DEPOSIT', 'WITHDRAW')
+     OR EXISTS (
+       SELECT 1
+       FROM order_trade_logs ol
+       WHERE ol.transaction_hash = wc.transaction_hash
+         AND ol.log_index = wc.log_index
+         AND wc.change_type IN (ol.kind || '_INPUT', ol.kind || '_OUTPUT')
+     )
+),
+window_totals AS (
+  SELECT
+    ov.token,
+    ov.vault_id,
+    MIN(oc.block_timestamp) AS first_timestamp,
+    MAX(oc.block_timestamp) AS last_timestamp,
+    FLOAT_SUM(
+      CASE WHEN oc.change_type = 'DEPOSIT' THEN oc.delta ELSE FLOAT_ZERO_HEX() END
+    ) AS total_deposits,
+    FLOAT_SUM(
+      CASE WHEN oc.change_type = 'WITHDRAW' THEN FLOAT_NEGATE(oc.delta) ELSE FLOAT_ZERO_HEX() END
+    ) AS total_withdrawals,
+    FLOAT_SUM(
+      CASE WHEN oc.change_type NOT IN ('DEPOSIT', 'WITHDRAW') THEN oc.delta ELSE FLOAT_ZERO_HEX() END
+    ) AS pnl
+  FROM order_vaults ov
+  LEFT JOIN order_changes oc
+    ON oc.token = ov.token
+   AND oc.vault_id = ov.vault_id
+  GROUP BY ov.token, ov.vault_id
+),
+vault_performance AS (
+  SELECT
+    wt.vault_id,
+    wt.token,
+    COALESCE(sb.balance, FLOAT_ZERO_HEX()) AS starting_balance,
+    COALESCE(eb.balance, sb.balance, FLOAT_ZERO_HEX()) AS ending_balance,
+    wt.total_deposits,
+    wt.total_withdrawals,
+    wt.pnl,
+    wt.first_timestamp,
+    wt.last_timestamp
+  FROM window_totals wt
+  LEFT JOIN starting_balances sb ON sb.token = wt.token AND sb.vault_id = wt.vault_id
+  LEFT JOIN ending_balances eb ON eb.token = wt.token AND eb.vault_id = wt.vault_id
+),
+-- order level totals, one row per token with no vault id
+order_performance AS (
+  SELECT * FROM vault_performance
+  UNION ALL
+  SELECT
+    NULL,
+    vp.token,
+    FLOAT_SUM(vp.starting_balance),
+    FLOAT_SUM(vp.ending_balance),
+    FLOAT_SUM(vp.total_deposits),
+    FLOAT_SUM(vp.total_withdrawals),
+    FLOAT_SUM(vp.pnl),
+    MIN(vp.first_timestamp),
+    MAX(vp.last_timestamp)
+  FROM vault_performance vp
+  GROUP BY vp.token
+)
+SELECT
+  op.vault_id,
+  op.token,
+  tok.name AS token_name,
+  tok.symbol AS token_symbol,
+  tok.decimals AS token_decimals,
+  op.starting_balance,
+  op.ending_balance,
+  op.total_deposits,
+  op.total_withdrawals,
+  op.pnl,
+  op.first_timestamp,
+  op.last_timestamp
+FROM order_performance op
+CROSS JOIN params p
+LEFT JOIN erc20_tokens tok
+  ON tok.chain_id = p.chain_id
+ AND tok.orderbook_address = p.orderbook_address
+ AND tok.token_address = op.token
+ORDER BY op.token, op.vault_id IS NULL, op.vault_id;
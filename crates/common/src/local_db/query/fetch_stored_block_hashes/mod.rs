@@ -0,0 +1,68 @@
+use crate::local_db::{
+    query::{SqlStatement, SqlValue},
+    OrderbookIdentifier,
+};
+use alloy::primitives::B256;
+use serde::{Deserialize, Serialize};
+
+pub const FETCH_STORED_BLOCK_HASHES_SQL: &str = include_str!("query.sql");
+
+/// Block hash recorded alongside a persisted raw event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredBlockHashRow {
+    pub block_number: u64,
+    pub block_hash: B256,
+}
+
+/// Fetches up to `limit` distinct `(block_number, block_hash)` pairs recorded
+/// in `raw_events` strictly below `before_block`, newest first.
+pub fn fetch_stored_block_hashes_stmt(
+    ob_id: &OrderbookIdentifier,
+    before_block: u64,
+    limit: u32,
+) -> SqlStatement {
+    SqlStatement::new_with_params(
+        FETCH_STORED_BLOCK_HASHES_SQL,
+        [
+            SqlValue::from(ob_id.chain_id),
+            SqlValue::from(ob_id.orderbook_address),
+            SqlValue::from(before_block),
+            SqlValue::from(limit),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    #[test]
+    fn stmt_binds_params_in_order() {
+        let stmt = fetch_stored_block_hashes_stmt(
+            &OrderbookIdentifier::new(8453, Address::repeat_byte(0xab)),
+            500,
+            25,
+        );
+        assert_eq!(stmt.sql(), FETCH_STORED_BLOCK_HASHES_SQL);
+        assert_eq!(
+            stmt.params(),
+            &[
+                SqlValue::U64(8453),
+                SqlValue::Text("0xabababababababababababababababababababab".to_string()),
+                SqlValue::U64(500),
+                SqlValue::U64(25),
+            ]
+        );
+    }
+
+    #[test]
+    fn sql_reads_raw_events_newest_first() {
+        let lower = FETCH_STORED_BLOCK_HASHES_SQL.to_lowercase();
+        assert!(lower.contains("from raw_events"));
+        assert!(lower.contains("json_extract(raw_json, '$.blockhash')"));
+        assert!(lower.contains("block_number < ?3"));
+        assert!(lower.contains("order by block_number desc"));
+        assert!(lower.contains("limit ?4"));
+    }
+}
@@ -0,0 +1,10 @@
+SELECT DISTINCT
+  block_number,
+  json_extract(raw_json, '$.blockHash') AS block_hash
+FROM raw_events
+WHERE chain_id = ?1
+  AND orderbook_address = ?2
+  AND block_number < ?3
+  AND json_extract(raw_json, '$.blockHash') IS NOT NULL
+ORDER BY block_number DESC
+LIMIT ?4;
@@ -0,0 +1,60 @@
+use crate::local_db::{
+    query::{SqlStatement, SqlValue},
+    OrderbookIdentifier,
+};
+
+pub const FETCH_VAULT_BALANCE_CHANGES_IN_RANGE_SQL: &str = include_str!("query.sql");
+
+/// Every vault balance change of an orderbook within the inclusive block
+/// range, oldest first. Rows decode into
+/// [`LocalDbVaultBalanceChange`](super::fetch_vault_balance_changes::LocalDbVaultBalanceChange).
+pub fn fetch_vault_balance_changes_in_range_stmt(
+    ob_id: &OrderbookIdentifier,
+    start_block: u64,
+    end_block: u64,
+) -> SqlStatement {
+    SqlStatement::new_with_params(
+        FETCH_VAULT_BALANCE_CHANGES_IN_RANGE_SQL,
+        [
+            SqlValue::from(ob_id.chain_id),
+            SqlValue::from(ob_id.orderbook_address),
+            SqlValue::from(start_block),
+            SqlValue::from(end_block),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    #[test]
+    fn stmt_binds_target_and_range() {
+        let ob_id = OrderbookIdentifier::new(42, Address::from([0x22u8; 20]));
+        let stmt = fetch_vault_balance_changes_in_range_stmt(&ob_id, 10, 20);
+
+        assert_eq!(stmt.sql(), FETCH_VAULT_BALANCE_CHANGES_IN_RANGE_SQL);
+        assert_eq!(stmt.params().len(), 4);
+        assert_eq!(stmt.params()[0], SqlValue::U64(42));
+        assert_eq!(
+            stmt.params()[1],
+            SqlValue::Text(ob_id.orderbook_address.to_string())
+        );
+        assert_eq!(stmt.params()[2], SqlValue::U64(10));
+        assert_eq!(stmt.params()[3], SqlValue::U64(20));
+    }
+
+    #[test]
+    fn stmt_orders_oldest_first() {
+        let stmt = fetch_vault_balance_changes_in_range_stmt(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            0,
+            1,
+        );
+        let lower = stmt.sql().to_lowercase();
+        assert!(lower.contains("from vault_balance_changes"));
+        assert!(lower.contains("block_number between ?3 and ?4"));
+        assert!(lower.contains("order by vbc.block_number asc, vbc.log_index asc"));
+    }
+}
@@ -0,0 +1,16 @@
+SELECT
+  vbc.transaction_hash AS transactionHash,
+  vbc.log_index AS logIndex,
+  vbc.block_number AS blockNumber,
+  vbc.block_timestamp AS blockTimestamp,
+  vbc.owner,
+  vbc.change_type AS changeType,
+  vbc.token,
+  vbc.vault_id AS vaultId,
+  vbc.delta,
+  vbc.running_balance AS runningBalance
+FROM vault_balance_changes vbc
+WHERE vbc.chain_id = ?1
+  AND vbc.orderbook_address = ?2
+  AND vbc.block_number BETWEEN ?3 AND ?4
+ORDER BY vbc.block_number ASC, vbc.log_index ASC;
@@ -2,11 +2,13 @@ pub mod clear_orderbook_data;
 pub mod clear_tables;
 pub mod create_tables;
 pub mod create_views;
+pub mod db_migrations;
 pub mod executor;
 pub mod fetch_all_tokens;
 pub mod fetch_db_metadata;
 pub mod fetch_erc20_tokens_by_addresses;
 pub mod fetch_last_synced_block;
+pub mod fetch_order_performance;
 pub mod fetch_order_trades;
 pub mod fetch_order_trades_count;
 pub mod fetch_order_vaults_volume;
@@ -14,13 +16,17 @@ pub mod fetch_orders;
 pub(crate) mod fetch_orders_common;
 pub mod fetch_orders_count;
 pub mod fetch_store_addresses;
+pub mod fetch_stored_block_hashes;
 pub mod fetch_tables;
 pub mod fetch_target_watermark;
 pub mod fetch_transaction_by_hash;
 pub mod fetch_vault_balance_changes;
+pub mod fetch_vault_balance_changes_in_range;
 pub mod fetch_vaults;
 pub mod insert_db_metadata;
 pub mod integrity_check;
+pub mod rollback_to_block;
+pub mod seed_vault_balances;
 pub mod sql_statement;
 pub mod sql_statement_batch;
 pub mod update_last_synced_block;
@@ -0,0 +1,190 @@
+use crate::local_db::query::upsert_target_watermark::upsert_target_watermark_stmt;
+use crate::local_db::query::upsert_vault_balances::rebuild_running_vault_balances_batch;
+use crate::local_db::query::{SqlStatement, SqlStatementBatch, SqlValue};
+use crate::local_db::OrderbookIdentifier;
+use alloy::primitives::B256;
+
+/// Child tables that carry no block number of their own, paired with the
+/// parent table whose `block_number` decides whether a row is orphaned.
+/// Children are deleted before their parents so the lookups still resolve.
+pub const ROLLBACK_CHILD_TABLES: &[(&str, &str)] = &[
+    ("context_values", "take_orders"),
+    ("take_order_contexts", "take_orders"),
+    ("order_ios", "order_events"),
+];
+
+/// Tables scoped by `block_number` that are pruned above the rollback block.
+pub const ROLLBACK_BLOCK_TABLES: &[&str] = &[
+    "take_orders",
+    "order_events",
+    "clear_v3_events",
+    "after_clear_v2_events",
+    "meta_events",
+    "interpreter_store_sets",
+    "deposits",
+    "withdrawals",
+    "vault_balance_changes",
+    "raw_events",
+];
+
+/// Builds a transactional batch that discards every row of the orderbook
+/// above `block_number`, recomputes `running_vault_balances` from the
+/// remaining history and moves the watermark back to `(block_number, hash)`.
+pub fn rollback_to_block_batch(
+    ob_id: &OrderbookIdentifier,
+    block_number: u64,
+    block_hash: B256,
+) -> SqlStatementBatch {
+    let mut batch = SqlStatementBatch::new();
+
+    for (child, parent) in ROLLBACK_CHILD_TABLES {
+        batch.add(SqlStatement::new_with_params(
+            format!(
+                r#"DELETE FROM {child}
+WHERE chain_id = ?1 AND orderbook_address = ?2 AND EXISTS (
+  SELECT 1 FROM {parent} p
+  WHERE p.chain_id = {child}.chain_id
+    AND p.orderbook_address = {child}.orderbook_address
+    AND p.transaction_hash = {child}.transaction_hash
+    AND p.log_index = {child}.log_index
+    AND p.block_number > ?3
+)"#
+            ),
+            scoped_params(ob_id, block_number),
+        ));
+    }
+
+    for table in ROLLBACK_BLOCK_TABLES {
+        batch.add(SqlStatement::new_with_params(
+            format!(
+                "DELETE FROM {table}\nWHERE chain_id = ?1 AND orderbook_address = ?2 AND block_number > ?3"
+            ),
+            scoped_params(ob_id, block_number),
+        ));
+    }
+
+    batch.extend(rebuild_running_vault_balances_batch(ob_id, block_number));
+    batch.add(upsert_target_watermark_stmt(
+        ob_id,
+        block_number,
+        block_hash.into(),
+    ));
+
+    batch.ensure_transaction()
+}
+
+fn scoped_params(ob_id: &OrderbookIdentifier, block_number: u64) -> [SqlValue; 3] {
+    [
+        SqlValue::from(ob_id.chain_id),
+        SqlValue::from(ob_id.orderbook_address),
+        SqlValue::from(block_number),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_db::query::create_tables::REQUIRED_TABLES;
+    use alloy::primitives::{b256, Address};
+
+    const HASH: B256 = b256!("0x00000000000000000000000000000000000000000000000000000000000000aa");
+
+    fn ob_id() -> OrderbookIdentifier {
+        OrderbookIdentifier::new(8453, Address::repeat_byte(0x11))
+    }
+
+    #[test]
+    fn batch_is_transactional_and_scoped() {
+        let batch = rollback_to_block_batch(&ob_id(), 120, HASH);
+        assert!(batch.is_transaction());
+
+        let statements = batch.statements();
+        assert_eq!(statements.first().unwrap().sql(), "BEGIN TRANSACTION");
+        assert_eq!(statements.last().unwrap().sql(), "COMMIT");
+
+        let deletes: Vec<_> = statements
+            .iter()
+            .filter(|s| s.sql().starts_with("DELETE FROM") && s.sql().contains("?3"))
+            .collect();
+        assert_eq!(
+            deletes.len(),
+            ROLLBACK_CHILD_TABLES.len() + ROLLBACK_BLOCK_TABLES.len()
+        );
+        for stmt in deletes {
+            assert_eq!(
+                stmt.params(),
+                &[
+                    SqlValue::U64(8453),
+                    SqlValue::Text("0x1111111111111111111111111111111111111111".to_string()),
+                    SqlValue::U64(120),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn children_are_deleted_before_parents() {
+        let batch = rollback_to_block_batch(&ob_id(), 1, HASH);
+        let position = |table: &str| {
+            batch
+                .statements()
+                .iter()
+                .position(|s| s.sql().starts_with(&format!("DELETE FROM {table}\n")))
+                .unwrap_or_else(|| panic!("missing delete for {table}"))
+        };
+        for (child, parent) in ROLLBACK_CHILD_TABLES {
+            assert!(position(child) < position(parent));
+        }
+    }
+
+    #[test]
+    fn covers_every_event_table() {
+        let pruned: Vec<&str> = ROLLBACK_CHILD_TABLES
+            .iter()
+            .map(|(child, _)| *child)
+            .chain(ROLLBACK_BLOCK_TABLES.iter().copied())
+            .collect();
+        let untouched = [
+            "db_metadata",
+            "target_watermarks",
+            "sync_status",
+            "erc20_tokens",
+            "running_vault_balances",
+        ];
+        for table in REQUIRED_TABLES {
+            assert!(
+                pruned.contains(table) || untouched.contains(table),
+                "table {table} is not handled by rollback"
+            );
+        }
+    }
+
+    #[test]
+    fn rebuilds_running_balances_and_rewinds_watermark() {
+        let batch = rollback_to_block_batch(&ob_id(), 77, HASH);
+        let sql: Vec<String> = batch
+            .statements()
+            .iter()
+            .map(|s| s.sql().to_lowercase())
+            .collect();
+
+        let clear_running = sql
+            .iter()
+            .position(|s| s.starts_with("delete from running_vault_balances"))
+            .expect("running balances cleared");
+        let rebuild_running = sql
+            .iter()
+            .position(|s| s.contains("insert or replace into running_vault_balances"))
+            .expect("running balances rebuilt");
+        let watermark = sql
+            .iter()
+            .position(|s| s.contains("insert into target_watermarks"))
+            .expect("watermark rewound");
+        assert!(clear_running < rebuild_running);
+        assert!(rebuild_running < watermark);
+
+        let watermark_stmt = &batch.statements()[watermark];
+        assert_eq!(watermark_stmt.params()[2], SqlValue::U64(77));
+        assert_eq!(watermark_stmt.params()[3], SqlValue::Text(HASH.to_string()));
+    }
+}
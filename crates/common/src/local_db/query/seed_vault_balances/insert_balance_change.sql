@@ -0,0 +1,14 @@
+INSERT OR IGNORE INTO vault_balance_changes (
+  chain_id,
+  orderbook_address,
+  transaction_hash,
+  owner,
+  token,
+  vault_id,
+  block_number,
+  block_timestamp,
+  log_index,
+  change_type,
+  delta,
+  running_balance
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12);
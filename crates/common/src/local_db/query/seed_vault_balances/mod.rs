@@ -0,0 +1,171 @@
+use crate::local_db::{
+    query::{SqlStatement, SqlStatementBatch, SqlValue},
+    OrderbookIdentifier,
+};
+use alloy::primitives::{Address, B256};
+use rain_math_float::Float;
+use std::collections::HashMap;
+
+const INSERT_BALANCE_CHANGE_SQL: &str = include_str!("insert_balance_change.sql");
+const UPSERT_RUNNING_BALANCE_SQL: &str = include_str!("upsert_running_balance.sql");
+
+/// Vault balance change whose running balance is already known, as reported
+/// by an external index instead of being derived from `vault_deltas`.
+#[derive(Debug, Clone)]
+pub struct SeedVaultBalanceChange {
+    pub transaction_hash: B256,
+    pub owner: Address,
+    pub token: Address,
+    pub vault_id: B256,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub log_index: u64,
+    /// `None` when the index cannot tell which `vault_deltas` kind the change
+    /// is, it then only moves the vault's running balance
+    pub change_type: Option<&'static str>,
+    pub delta: Float,
+    pub running_balance: Float,
+}
+
+/// Inserts the changes with a known kind into `vault_balance_changes` and
+/// sets each vault's `running_vault_balances` row to the running balance of
+/// its latest change.
+pub fn seed_vault_balances_batch(
+    ob_id: &OrderbookIdentifier,
+    changes: &[SeedVaultBalanceChange],
+) -> SqlStatementBatch {
+    let mut batch = SqlStatementBatch::new();
+    let mut latest: HashMap<(Address, Address, B256), &SeedVaultBalanceChange> = HashMap::new();
+
+    for change in changes {
+        if let Some(change_type) = change.change_type {
+            batch.add(SqlStatement::new_with_params(
+                INSERT_BALANCE_CHANGE_SQL,
+                [
+                    SqlValue::from(ob_id.chain_id),
+                    SqlValue::from(ob_id.orderbook_address),
+                    SqlValue::from(change.transaction_hash),
+                    SqlValue::from(change.owner),
+                    SqlValue::from(change.token),
+                    SqlValue::from(change.vault_id),
+                    SqlValue::from(change.block_number),
+                    SqlValue::from(change.block_timestamp),
+                    SqlValue::from(change.log_index),
+                    SqlValue::from(change_type),
+                    SqlValue::from(change.delta),
+                    SqlValue::from(change.running_balance),
+                ],
+            ));
+        }
+
+        latest
+            .entry((change.owner, change.token, change.vault_id))
+            .and_modify(|current| {
+                if (change.block_number, change.log_index)
+                    > (current.block_number, current.log_index)
+                {
+                    *current = change;
+                }
+            })
+            .or_insert(change);
+    }
+
+    let mut running: Vec<_> = latest.into_values().collect();
+    running.sort_by_key(|change| (change.owner, change.token, change.vault_id));
+    for change in running {
+        batch.add(SqlStatement::new_with_params(
+            UPSERT_RUNNING_BALANCE_SQL,
+            [
+                SqlValue::from(ob_id.chain_id),
+                SqlValue::from(ob_id.orderbook_address),
+                SqlValue::from(change.owner),
+                SqlValue::from(change.token),
+                SqlValue::from(change.vault_id),
+                SqlValue::from(change.running_balance),
+                SqlValue::from(change.block_number),
+                SqlValue::from(change.log_index),
+            ],
+        ));
+    }
+
+    batch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(
+        vault: u8,
+        block_number: u64,
+        log_index: u64,
+        balance: &str,
+    ) -> SeedVaultBalanceChange {
+        SeedVaultBalanceChange {
+            transaction_hash: B256::from([block_number as u8; 32]),
+            owner: Address::from([0x11; 20]),
+            token: Address::from([0x22; 20]),
+            vault_id: B256::from([vault; 32]),
+            block_number,
+            block_timestamp: 1_000 + block_number,
+            log_index,
+            change_type: Some("DEPOSIT"),
+            delta: Float::parse("1".to_string()).unwrap(),
+            running_balance: Float::parse(balance.to_string()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn batch_inserts_changes_and_latest_running_balance_per_vault() {
+        let ob_id = OrderbookIdentifier::new(1, Address::from([0xab; 20]));
+        let changes = vec![
+            change(1, 20, 0, "2"),
+            change(1, 10, 3, "1"),
+            change(2, 15, 1, "5"),
+        ];
+
+        let batch = seed_vault_balances_batch(&ob_id, &changes);
+        let statements = batch.statements();
+        assert_eq!(statements.len(), 5);
+        assert!(statements[..3]
+            .iter()
+            .all(|stmt| stmt.sql() == INSERT_BALANCE_CHANGE_SQL && stmt.params().len() == 12));
+
+        let running = &statements[3..];
+        assert!(running
+            .iter()
+            .all(|stmt| stmt.sql() == UPSERT_RUNNING_BALANCE_SQL));
+        assert_eq!(
+            running[0].params()[5],
+            SqlValue::from(Float::parse("2".to_string()).unwrap())
+        );
+        assert_eq!(running[0].params()[6], SqlValue::U64(20));
+        assert_eq!(running[1].params()[4], SqlValue::from(B256::from([2; 32])));
+        assert_eq!(running[1].params()[7], SqlValue::U64(1));
+    }
+
+    #[test]
+    fn untyped_changes_only_move_running_balance() {
+        let ob_id = OrderbookIdentifier::new(1, Address::from([0xab; 20]));
+        let mut untyped = change(1, 30, 0, "9");
+        untyped.change_type = None;
+        let changes = vec![change(1, 20, 0, "2"), untyped];
+
+        let batch = seed_vault_balances_batch(&ob_id, &changes);
+        let statements = batch.statements();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].sql(), INSERT_BALANCE_CHANGE_SQL);
+        assert_eq!(statements[1].sql(), UPSERT_RUNNING_BALANCE_SQL);
+        assert_eq!(
+            statements[1].params()[5],
+            SqlValue::from(Float::parse("9".to_string()).unwrap())
+        );
+        assert_eq!(statements[1].params()[6], SqlValue::U64(30));
+    }
+
+    #[test]
+    fn empty_changes_yield_empty_batch() {
+        let ob_id = OrderbookIdentifier::new(1, Address::ZERO);
+        assert!(seed_vault_balances_batch(&ob_id, &[]).is_empty());
+    }
+}
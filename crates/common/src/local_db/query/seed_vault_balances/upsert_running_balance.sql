@@ -0,0 +1,21 @@
+INSERT OR REPLACE INTO running_vault_balances (
+  chain_id,
+  orderbook_address,
+  owner,
+  token,
+  vault_id,
+  balance,
+  last_block,
+  last_log_index,
+  updated_at
+) VALUES (
+  ?1,
+  ?2,
+  ?3,
+  ?4,
+  ?5,
+  ?6,
+  ?7,
+  ?8,
+  (CAST(strftime('%s', 'now') AS INTEGER) * 1000)
+);
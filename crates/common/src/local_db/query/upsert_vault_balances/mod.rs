@@ -5,6 +5,8 @@ use crate::local_db::{
 
 const UPSERT_RUNNING_SQL: &str = include_str!("insert_running_balances.sql");
 const INSERT_BALANCE_CHANGES_SQL: &str = include_str!("insert_balance_changes.sql");
+const CLEAR_RUNNING_SQL: &str =
+    "DELETE FROM running_vault_balances\nWHERE chain_id = ?1 AND orderbook_address = ?2";
 
 pub fn upsert_vault_balances_batch(
     ob_id: &OrderbookIdentifier,
@@ -16,6 +18,23 @@ pub fn upsert_vault_balances_batch(
     SqlStatementBatch::from(vec![change_stmt, running_stmt])
 }
 
+/// Recomputes `running_vault_balances` from scratch using every vault delta up
+/// to and including `end_block`. Used after a rollback discards later history.
+pub fn rebuild_running_vault_balances_batch(
+    ob_id: &OrderbookIdentifier,
+    end_block: u64,
+) -> SqlStatementBatch {
+    let clear_stmt = SqlStatement::new_with_params(
+        CLEAR_RUNNING_SQL,
+        [
+            SqlValue::from(ob_id.chain_id),
+            SqlValue::from(ob_id.orderbook_address),
+        ],
+    );
+    let running_stmt = build_stmt(UPSERT_RUNNING_SQL, ob_id, 0, end_block);
+    SqlStatementBatch::from(vec![clear_stmt, running_stmt])
+}
+
 fn build_stmt(
     template: &str,
     ob_id: &OrderbookIdentifier,
@@ -108,4 +127,26 @@ mod tests {
             "missing FLOAT_SUM aggregation in query"
         );
     }
+
+    #[test]
+    fn rebuild_clears_then_replays_from_genesis() {
+        let ob_id = OrderbookIdentifier::new(6, Address::from([0x66u8; 20]));
+        let batch = rebuild_running_vault_balances_batch(&ob_id, 321);
+        assert_eq!(batch.len(), 2);
+
+        let clear = &batch.statements()[0];
+        assert!(clear
+            .sql()
+            .to_lowercase()
+            .starts_with("delete from running_vault_balances"));
+        assert_eq!(clear.params().len(), 2);
+
+        let running = &batch.statements()[1];
+        assert!(running
+            .sql()
+            .to_lowercase()
+            .contains("insert or replace into running_vault_balances"));
+        assert_eq!(running.params()[2], SqlValue::U64(0));
+        assert_eq!(running.params()[3], SqlValue::U64(321));
+    }
 }
@@ -0,0 +1,496 @@
+use alloy::primitives::{hex, keccak256, Address, Bytes, B256};
+use alloy::sol_types::{SolCall, SolValue};
+use rain_math_float::{Float, FloatError};
+use rain_orderbook_bindings::{
+    IRaindexV6::{addOrder4Call, deposit4Call, removeOrder3Call, takeOrders4Call, withdraw4Call},
+    OrderBook::multicallCall,
+};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use thiserror::Error;
+use wasm_bindgen_utils::prelude::*;
+
+/// A call that can be batched into an orderbook multicall
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderbookCall {
+    Deposit(deposit4Call),
+    Withdraw(withdraw4Call),
+    AddOrder(addOrder4Call),
+    RemoveOrder(removeOrder3Call),
+    TakeOrders(takeOrders4Call),
+}
+
+impl OrderbookCall {
+    /// Decodes the calldata of a supported call, flattening a multicall into
+    /// its calls
+    pub fn decode(calldata: &[u8]) -> Result<Vec<Self>, OrderbookMulticallError> {
+        let selector: [u8; 4] = calldata
+            .get(..4)
+            .and_then(|selector| selector.try_into().ok())
+            .ok_or(OrderbookMulticallError::MissingSelector)?;
+
+        let call = if selector == deposit4Call::SELECTOR {
+            OrderbookCall::Deposit(deposit4Call::abi_decode(calldata)?)
+        } else if selector == withdraw4Call::SELECTOR {
+            OrderbookCall::Withdraw(withdraw4Call::abi_decode(calldata)?)
+        } else if selector == addOrder4Call::SELECTOR {
+            OrderbookCall::AddOrder(addOrder4Call::abi_decode(calldata)?)
+        } else if selector == removeOrder3Call::SELECTOR {
+            OrderbookCall::RemoveOrder(removeOrder3Call::abi_decode(calldata)?)
+        } else if selector == takeOrders4Call::SELECTOR {
+            OrderbookCall::TakeOrders(takeOrders4Call::abi_decode(calldata)?)
+        } else if selector == multicallCall::SELECTOR {
+            let mut calls = vec![];
+            for data in multicallCall::abi_decode(calldata)?.data {
+                calls.extend(Self::decode(&data)?);
+            }
+            return Ok(calls);
+        } else {
+            return Err(OrderbookMulticallError::UnsupportedCall(
+                hex::encode_prefixed(selector),
+            ));
+        };
+        Ok(vec![call])
+    }
+
+    pub fn abi_encode(&self) -> Bytes {
+        Bytes::from(match self {
+            OrderbookCall::Deposit(call) => call.abi_encode(),
+            OrderbookCall::Withdraw(call) => call.abi_encode(),
+            OrderbookCall::AddOrder(call) => call.abi_encode(),
+            OrderbookCall::RemoveOrder(call) => call.abi_encode(),
+            OrderbookCall::TakeOrders(call) => call.abi_encode(),
+        })
+    }
+}
+
+/// Batches deposits, withdrawals, added, removed and taken orders on one
+/// orderbook into a single multicall.
+///
+/// Calls run in the order they are added. Vault balances given with
+/// [`OrderbookMulticallBuilder::set_vault_balance`] are tracked through the
+/// deposits and withdrawals so withdrawing from a vault that is empty by then
+/// is rejected. Vaults without a known balance are only checked for
+/// non-positive amounts, and taken orders don't change tracked balances.
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct OrderbookMulticallBuilder {
+    calls: Vec<OrderbookCall>,
+    vault_balances: HashMap<(Address, B256), Float>,
+}
+
+impl OrderbookMulticallBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn calls(&self) -> &[OrderbookCall] {
+        &self.calls
+    }
+
+    pub fn push(&mut self, call: OrderbookCall) -> &mut Self {
+        self.calls.push(call);
+        self
+    }
+
+    pub fn push_calldata(&mut self, calldata: &[u8]) -> Result<&mut Self, OrderbookMulticallError> {
+        self.calls.extend(OrderbookCall::decode(calldata)?);
+        Ok(self)
+    }
+
+    /// Sets the balance of the caller's vault before the multicall runs
+    pub fn set_vault_balance(
+        &mut self,
+        token: Address,
+        vault_id: B256,
+        balance: Float,
+    ) -> &mut Self {
+        self.vault_balances.insert((token, vault_id), balance);
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), OrderbookMulticallError> {
+        if self.calls.is_empty() {
+            return Err(OrderbookMulticallError::NoCalls);
+        }
+
+        let zero = Float::default();
+        let mut balances = self.vault_balances.clone();
+        let mut added_orders = vec![];
+        let mut removed_orders = HashSet::new();
+        for (index, call) in self.calls.iter().enumerate() {
+            match call {
+                OrderbookCall::Deposit(call) => {
+                    let amount = Float::from_raw(call.depositAmount);
+                    if !amount.gt(zero)? {
+                        return Err(OrderbookMulticallError::NonPositiveDeposit {
+                            index,
+                            token: call.token,
+                            vault_id: call.vaultId,
+                        });
+                    }
+                    if let Some(balance) = balances.get_mut(&(call.token, call.vaultId)) {
+                        *balance = (*balance + amount)?;
+                    }
+                }
+                OrderbookCall::Withdraw(call) => {
+                    let target_amount = Float::from_raw(call.targetAmount);
+                    if !target_amount.gt(zero)? {
+                        return Err(OrderbookMulticallError::NonPositiveWithdraw {
+                            index,
+                            token: call.token,
+                            vault_id: call.vaultId,
+                        });
+                    }
+                    if let Some(balance) = balances.get_mut(&(call.token, call.vaultId)) {
+                        if !balance.gt(zero)? {
+                            return Err(OrderbookMulticallError::WithdrawFromEmptyVault {
+                                index,
+                                token: call.token,
+                                vault_id: call.vaultId,
+                            });
+                        }
+                        *balance = if target_amount.gte(*balance)? {
+                            zero
+                        } else {
+                            (*balance - target_amount)?
+                        };
+                    }
+                }
+                OrderbookCall::AddOrder(call) => {
+                    if added_orders.contains(&&call.config) {
+                        return Err(OrderbookMulticallError::DuplicateAddOrder { index });
+                    }
+                    added_orders.push(&call.config);
+                }
+                OrderbookCall::RemoveOrder(call) => {
+                    let order_hash = keccak256(call.order.abi_encode());
+                    if !removed_orders.insert(order_hash) {
+                        return Err(OrderbookMulticallError::DuplicateRemoveOrder {
+                            index,
+                            order_hash,
+                        });
+                    }
+                }
+                OrderbookCall::TakeOrders(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the calls and encodes them as one `multicall` calldata
+    pub fn build(&self) -> Result<Bytes, OrderbookMulticallError> {
+        self.validate()?;
+        let multicall = multicallCall {
+            data: self.calls.iter().map(OrderbookCall::abi_encode).collect(),
+        };
+        Ok(Bytes::from(multicall.abi_encode()))
+    }
+}
+
+#[wasm_export]
+impl OrderbookMulticallBuilder {
+    /// Creates an empty builder
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = OrderbookMulticallBuilder.new();
+    /// const builder = result.value;
+    /// builder.addCalldata(await vault.getWithdrawCalldata(amount).value);
+    /// builder.addCalldata(await order.getRemoveCalldata().value);
+    /// const calldata = builder.build().value;
+    /// ```
+    #[wasm_export(
+        js_name = "new",
+        return_description = "Empty multicall builder",
+        preserve_js_class
+    )]
+    pub fn new_wasm_binding() -> Result<OrderbookMulticallBuilder, OrderbookMulticallError> {
+        Ok(Self::new())
+    }
+
+    /// Adds the calldata of a deposit, withdraw, addOrder, removeOrder or
+    /// takeOrders call, or of a multicall of them
+    #[wasm_export(js_name = "addCalldata", unchecked_return_type = "void")]
+    pub fn add_calldata_wasm_binding(
+        &mut self,
+        #[wasm_export(param_description = "Calldata to add", unchecked_param_type = "Hex")]
+        calldata: String,
+    ) -> Result<(), OrderbookMulticallError> {
+        self.push_calldata(&Bytes::from_str(&calldata)?)?;
+        Ok(())
+    }
+
+    /// Sets the known balance of a vault so withdrawals from it are checked
+    #[wasm_export(js_name = "setVaultBalance", unchecked_return_type = "void")]
+    pub fn set_vault_balance_wasm_binding(
+        &mut self,
+        #[wasm_export(param_description = "Token address", unchecked_param_type = "Address")]
+        token: String,
+        #[wasm_export(
+            js_name = "vaultId",
+            param_description = "Vault ID",
+            unchecked_param_type = "Hex"
+        )]
+        vault_id: String,
+        #[wasm_export(param_description = "Balance of the vault in Float value")] balance: &Float,
+    ) -> Result<(), OrderbookMulticallError> {
+        self.set_vault_balance(
+            Address::from_str(&token)?,
+            B256::from_str(&vault_id)?,
+            *balance,
+        );
+        Ok(())
+    }
+
+    /// Validates the calls and encodes them as one multicall
+    #[wasm_export(
+        js_name = "build",
+        return_description = "Encoded multicall calldata",
+        unchecked_return_type = "Hex"
+    )]
+    pub fn build_wasm_binding(&self) -> Result<Bytes, OrderbookMulticallError> {
+        self.build()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OrderbookMulticallError {
+    #[error("No calls to batch")]
+    NoCalls,
+    #[error("Calldata is too short to contain a selector")]
+    MissingSelector,
+    #[error("Unsupported orderbook call with selector {0}")]
+    UnsupportedCall(String),
+    #[error("Call {index} deposits a non-positive amount of {token} into vault {vault_id}")]
+    NonPositiveDeposit {
+        index: usize,
+        token: Address,
+        vault_id: B256,
+    },
+    #[error("Call {index} withdraws a non-positive amount of {token} from vault {vault_id}")]
+    NonPositiveWithdraw {
+        index: usize,
+        token: Address,
+        vault_id: B256,
+    },
+    #[error("Call {index} withdraws {token} from vault {vault_id} which is empty by then")]
+    WithdrawFromEmptyVault {
+        index: usize,
+        token: Address,
+        vault_id: B256,
+    },
+    #[error("Call {index} adds an order that an earlier call already adds")]
+    DuplicateAddOrder { index: usize },
+    #[error("Call {index} removes order {order_hash} that an earlier call already removes")]
+    DuplicateRemoveOrder { index: usize, order_hash: B256 },
+    #[error(transparent)]
+    SolTypesError(#[from] alloy::sol_types::Error),
+    #[error(transparent)]
+    FloatError(#[from] FloatError),
+    #[error(transparent)]
+    FromHexError(#[from] hex::FromHexError),
+}
+
+impl OrderbookMulticallError {
+    pub fn to_readable_msg(&self) -> String {
+        match self {
+            OrderbookMulticallError::NoCalls => "Add at least one call to the multicall.".to_string(),
+            OrderbookMulticallError::MissingSelector => {
+                "The calldata is too short to be an orderbook call.".to_string()
+            }
+            OrderbookMulticallError::UnsupportedCall(selector) => format!(
+                "The call with selector {selector} is not a deposit, withdraw, add, remove or take orders call."
+            ),
+            OrderbookMulticallError::NonPositiveDeposit { index, .. } => {
+                format!("The deposit at position {index} must have an amount greater than zero.")
+            }
+            OrderbookMulticallError::NonPositiveWithdraw { index, .. } => {
+                format!("The withdrawal at position {index} must have an amount greater than zero.")
+            }
+            OrderbookMulticallError::WithdrawFromEmptyVault { index, .. } => format!(
+                "The withdrawal at position {index} is from a vault that is empty by then."
+            ),
+            OrderbookMulticallError::DuplicateAddOrder { index } => {
+                format!("The order added at position {index} is already added earlier.")
+            }
+            OrderbookMulticallError::DuplicateRemoveOrder { index, .. } => {
+                format!("The order removed at position {index} is already removed earlier.")
+            }
+            OrderbookMulticallError::SolTypesError(err) => {
+                format!("Failed to decode the orderbook call: {err}")
+            }
+            OrderbookMulticallError::FloatError(err) => format!("Float error: {err}"),
+            OrderbookMulticallError::FromHexError(err) => format!("Invalid hex value: {err}"),
+        }
+    }
+}
+
+impl From<OrderbookMulticallError> for JsValue {
+    fn from(value: OrderbookMulticallError) -> Self {
+        JsError::new(&value.to_string()).into()
+    }
+}
+
+impl From<OrderbookMulticallError> for WasmEncodedError {
+    fn from(value: OrderbookMulticallError) -> Self {
+        WasmEncodedError {
+            msg: value.to_string(),
+            readable_msg: value.to_readable_msg(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, U256};
+    use rain_orderbook_bindings::IRaindexV6::OrderV4;
+
+    fn float(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn deposit(vault_id: u64, amount: &str) -> OrderbookCall {
+        OrderbookCall::Deposit(deposit4Call {
+            token: address!("0x1111111111111111111111111111111111111111"),
+            vaultId: B256::from(U256::from(vault_id)),
+            depositAmount: float(amount).get_inner(),
+            tasks: vec![],
+        })
+    }
+
+    fn withdraw(vault_id: u64, amount: &str) -> OrderbookCall {
+        OrderbookCall::Withdraw(withdraw4Call {
+            token: address!("0x1111111111111111111111111111111111111111"),
+            vaultId: B256::from(U256::from(vault_id)),
+            targetAmount: float(amount).get_inner(),
+            tasks: vec![],
+        })
+    }
+
+    fn builder(calls: Vec<OrderbookCall>) -> OrderbookMulticallBuilder {
+        let mut builder = OrderbookMulticallBuilder::new();
+        for call in calls {
+            builder.push(call);
+        }
+        builder
+    }
+
+    #[test]
+    fn test_decode_flattens_multicall() {
+        let calls = vec![deposit(1, "5"), withdraw(2, "3")];
+        let multicall = builder(calls.clone()).build().unwrap();
+
+        assert_eq!(OrderbookCall::decode(&multicall).unwrap(), calls);
+        assert_eq!(
+            OrderbookCall::decode(&calls[0].abi_encode()).unwrap(),
+            vec![calls[0].clone()]
+        );
+
+        let mut builder = OrderbookMulticallBuilder::new();
+        builder.push_calldata(&multicall).unwrap();
+        assert_eq!(builder.calls(), calls.as_slice());
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            OrderbookCall::decode(&[1, 2]).unwrap_err(),
+            OrderbookMulticallError::MissingSelector
+        ));
+        assert_eq!(
+            OrderbookCall::decode(&[1, 2, 3, 4])
+                .unwrap_err()
+                .to_string(),
+            "Unsupported orderbook call with selector 0x01020304"
+        );
+        let mut calldata = deposit(1, "5").abi_encode().to_vec();
+        calldata.truncate(20);
+        assert!(matches!(
+            OrderbookCall::decode(&calldata).unwrap_err(),
+            OrderbookMulticallError::SolTypesError(_)
+        ));
+    }
+
+    #[test]
+    fn test_build() {
+        let calls = vec![deposit(1, "5"), withdraw(1, "3")];
+        let calldata = builder(calls.clone()).build().unwrap();
+        let multicall = multicallCall::abi_decode(&calldata).unwrap();
+        assert_eq!(
+            multicall.data,
+            calls
+                .iter()
+                .map(OrderbookCall::abi_encode)
+                .collect::<Vec<_>>()
+        );
+
+        assert!(matches!(
+            OrderbookMulticallBuilder::new().build().unwrap_err(),
+            OrderbookMulticallError::NoCalls
+        ));
+    }
+
+    #[test]
+    fn test_validate_amounts() {
+        assert!(matches!(
+            builder(vec![deposit(1, "0")]).validate().unwrap_err(),
+            OrderbookMulticallError::NonPositiveDeposit { index: 0, .. }
+        ));
+        assert!(matches!(
+            builder(vec![deposit(1, "1"), withdraw(1, "-1")])
+                .validate()
+                .unwrap_err(),
+            OrderbookMulticallError::NonPositiveWithdraw { index: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_validate_vault_balances() {
+        let token = address!("0x1111111111111111111111111111111111111111");
+        let vault_id = B256::from(U256::from(1));
+
+        // unknown balances are never considered empty
+        builder(vec![withdraw(1, "10"), withdraw(1, "10")])
+            .validate()
+            .unwrap();
+
+        let mut emptied = builder(vec![withdraw(1, "2"), withdraw(1, "3"), withdraw(1, "1")]);
+        emptied.set_vault_balance(token, vault_id, float("5"));
+        assert!(matches!(
+            emptied.validate().unwrap_err(),
+            OrderbookMulticallError::WithdrawFromEmptyVault { index: 2, .. }
+        ));
+
+        let mut refilled = builder(vec![withdraw(1, "10"), deposit(1, "1"), withdraw(1, "1")]);
+        refilled.set_vault_balance(token, vault_id, float("5"));
+        refilled.validate().unwrap();
+
+        let mut other_vault = builder(vec![withdraw(2, "1")]);
+        other_vault.set_vault_balance(token, vault_id, float("0"));
+        other_vault.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_duplicate_orders() {
+        let remove = OrderbookCall::RemoveOrder(removeOrder3Call {
+            order: OrderV4::default(),
+            tasks: vec![],
+        });
+        let err = builder(vec![remove.clone(), deposit(1, "1"), remove])
+            .validate()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OrderbookMulticallError::DuplicateRemoveOrder { index: 2, .. }
+        ));
+
+        let add = OrderbookCall::AddOrder(addOrder4Call::default());
+        assert!(matches!(
+            builder(vec![add.clone(), add]).validate().unwrap_err(),
+            OrderbookMulticallError::DuplicateAddOrder { index: 1 }
+        ));
+    }
+}
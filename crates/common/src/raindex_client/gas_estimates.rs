@@ -0,0 +1,343 @@
+use super::*;
+use crate::gas_estimate::{estimate_calldata_cost, CalldataCost, EstimatedCalldata};
+use crate::raindex_client::orders::RaindexOrder;
+use crate::raindex_client::vaults::RaindexVault;
+use crate::take_orders::TakeOrdersMode;
+use alloy::primitives::Bytes;
+use rain_math_float::Float;
+
+#[wasm_export]
+impl RaindexClient {
+    /// Estimates the gas and cost of sending calldata on a network
+    ///
+    /// Runs `eth_estimateGas` against the network's RPCs and prices the gas
+    /// with the current base and priority fees in the network's currency. A
+    /// transaction that would revert returns its decoded revert reason instead
+    /// of an estimate.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await client.estimateCalldata(1, sender, orderbook, calldata);
+    /// if (result.error) {
+    ///   console.error("Cannot estimate:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const cost = result.value;
+    /// if (cost.type === "Revert") {
+    ///   console.warn("The transaction would revert:", cost.data);
+    /// }
+    /// ```
+    #[wasm_export(
+        js_name = "estimateCalldata",
+        return_description = "Gas estimate or revert reason of the calldata",
+        unchecked_return_type = "CalldataCost"
+    )]
+    pub async fn estimate_calldata_wasm_binding(
+        &self,
+        #[wasm_export(js_name = "chainId", param_description = "Chain ID for the network")]
+        chain_id: u32,
+        #[wasm_export(
+            param_description = "Address sending the transaction",
+            unchecked_param_type = "Address"
+        )]
+        from: String,
+        #[wasm_export(
+            param_description = "Address the transaction is sent to",
+            unchecked_param_type = "Address"
+        )]
+        to: String,
+        #[wasm_export(
+            param_description = "Calldata to estimate",
+            unchecked_param_type = "Hex"
+        )]
+        calldata: String,
+    ) -> Result<CalldataCost, RaindexError> {
+        self.estimate_calldata(
+            chain_id,
+            Address::from_str(&from)?,
+            Address::from_str(&to)?,
+            Bytes::from_str(&calldata)?,
+        )
+        .await
+    }
+}
+impl RaindexClient {
+    pub async fn estimate_calldata(
+        &self,
+        chain_id: u32,
+        from: Address,
+        to: Address,
+        calldata: Bytes,
+    ) -> Result<CalldataCost, RaindexError> {
+        let network = self.orderbook_yaml.get_network_by_chain_id(chain_id)?;
+        Ok(estimate_calldata_cost(&network, from, to, calldata, None).await?)
+    }
+
+    async fn estimated_calldata(
+        &self,
+        chain_id: u32,
+        from: Address,
+        to: Address,
+        calldata: Bytes,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        let cost = self
+            .estimate_calldata(chain_id, from, to, calldata.clone())
+            .await?;
+        Ok(EstimatedCalldata { calldata, cost })
+    }
+}
+
+#[wasm_export]
+impl RaindexVault {
+    /// Generates deposit calldata along with its gas estimate
+    ///
+    /// Same calldata as `getDepositCalldata`. The estimate reports the revert
+    /// reason when the deposit would fail, e.g. without enough allowance.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await vault.getDepositCalldataWithEstimate(amount, sender);
+    /// if (result.error) {
+    ///   console.error("Cannot generate deposit:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, cost } = result.value;
+    /// ```
+    #[wasm_export(
+        js_name = "getDepositCalldataWithEstimate",
+        return_description = "Deposit calldata with its gas estimate or revert reason",
+        unchecked_return_type = "EstimatedCalldata"
+    )]
+    pub async fn get_deposit_calldata_with_estimate_wasm_binding(
+        &self,
+        #[wasm_export(param_description = "Amount to deposit in Float value")] amount: &Float,
+        #[wasm_export(
+            param_description = "Address sending the deposit",
+            unchecked_param_type = "Address"
+        )]
+        from: String,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        self.get_deposit_calldata_with_estimate(amount, Address::from_str(&from)?)
+            .await
+    }
+
+    /// Generates withdraw calldata along with its gas estimate
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await vault.getWithdrawCalldataWithEstimate(amount, sender);
+    /// if (result.error) {
+    ///   console.error("Cannot generate withdrawal:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, cost } = result.value;
+    /// ```
+    #[wasm_export(
+        js_name = "getWithdrawCalldataWithEstimate",
+        return_description = "Withdraw calldata with its gas estimate or revert reason",
+        unchecked_return_type = "EstimatedCalldata"
+    )]
+    pub async fn get_withdraw_calldata_with_estimate_wasm_binding(
+        &self,
+        #[wasm_export(param_description = "Amount to withdraw in Float value")] amount: &Float,
+        #[wasm_export(
+            param_description = "Address sending the withdrawal",
+            unchecked_param_type = "Address"
+        )]
+        from: String,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        self.get_withdraw_calldata_with_estimate(amount, Address::from_str(&from)?)
+            .await
+    }
+}
+impl RaindexVault {
+    pub async fn get_deposit_calldata_with_estimate(
+        &self,
+        amount: &Float,
+        from: Address,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        let calldata = self.get_deposit_calldata(amount).await?;
+        self.estimated_calldata(from, calldata).await
+    }
+
+    pub async fn get_withdraw_calldata_with_estimate(
+        &self,
+        amount: &Float,
+        from: Address,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        let calldata = self.get_withdraw_calldata(amount).await?;
+        self.estimated_calldata(from, calldata).await
+    }
+
+    async fn estimated_calldata(
+        &self,
+        from: Address,
+        calldata: Bytes,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        #[cfg(target_family = "wasm")]
+        let orderbook = Address::from_str(&self.orderbook())?;
+        #[cfg(not(target_family = "wasm"))]
+        let orderbook = self.orderbook();
+        self.get_raindex_client()
+            .estimated_calldata(self.chain_id(), from, orderbook, calldata)
+            .await
+    }
+}
+
+#[wasm_export]
+impl RaindexOrder {
+    /// Generates remove order calldata along with its gas estimate
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.getRemoveCalldataWithEstimate(owner);
+    /// if (result.error) {
+    ///   console.error("Failed:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, cost } = result.value;
+    /// ```
+    #[wasm_export(
+        js_name = "getRemoveCalldataWithEstimate",
+        return_description = "Remove order calldata with its gas estimate or revert reason",
+        unchecked_return_type = "EstimatedCalldata"
+    )]
+    pub async fn get_remove_calldata_with_estimate_wasm_binding(
+        &self,
+        #[wasm_export(
+            param_description = "Address sending the removal, the order owner",
+            unchecked_param_type = "Address"
+        )]
+        from: String,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        self.get_remove_calldata_with_estimate(Address::from_str(&from)?)
+            .await
+    }
+
+    /// Generates take order calldata along with its gas estimate
+    ///
+    /// Takes the same parameters as `getTakeCalldata`. While the taker still
+    /// needs to approve the sell token, the returned calldata and estimate are
+    /// those of the approval.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.getTakeCalldataWithEstimate(0, 0, taker, "buyUpTo", "10", "1.2");
+    /// if (result.error) {
+    ///   console.error(result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, cost } = result.value;
+    /// ```
+    #[wasm_export(
+        js_name = "getTakeCalldataWithEstimate",
+        return_description = "Take orders or approval calldata with its gas estimate or revert reason",
+        unchecked_return_type = "EstimatedCalldata"
+    )]
+    pub async fn get_take_calldata_with_estimate(
+        &self,
+        #[wasm_export(
+            js_name = "inputIndex",
+            param_description = "Index of the input token in the order's validInputs array"
+        )]
+        input_index: u32,
+        #[wasm_export(
+            js_name = "outputIndex",
+            param_description = "Index of the output token in the order's validOutputs array"
+        )]
+        output_index: u32,
+        #[wasm_export(param_description = "Address of the taker account")] taker: String,
+        #[wasm_export(
+            param_description = "Take orders mode: buyExact, buyUpTo, spendExact, or spendUpTo"
+        )]
+        mode: TakeOrdersMode,
+        #[wasm_export(param_description = "Target amount as decimal string")] amount: String,
+        #[wasm_export(
+            js_name = "priceCap",
+            param_description = "Maximum price cap as decimal string"
+        )]
+        price_cap: String,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        let from = Address::from_str(&taker)?;
+        let result = self
+            .get_take_calldata(input_index, output_index, taker, mode, amount, price_cap)
+            .await?;
+        let (to, calldata) = result.next_transaction();
+        self.get_raindex_client()
+            .estimated_calldata(self.chain_id(), from, to, calldata)
+            .await
+    }
+}
+impl RaindexOrder {
+    pub async fn get_remove_calldata_with_estimate(
+        &self,
+        from: Address,
+    ) -> Result<EstimatedCalldata, RaindexError> {
+        #[cfg(target_family = "wasm")]
+        let orderbook = Address::from_str(&self.orderbook())?;
+        #[cfg(not(target_family = "wasm"))]
+        let orderbook = self.orderbook();
+        let calldata = self.get_remove_calldata()?;
+        self.get_raindex_client()
+            .estimated_calldata(self.chain_id(), from, orderbook, calldata)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(target_family = "wasm"))]
+    mod non_wasm {
+        use crate::gas_estimate::CalldataCost;
+        use crate::raindex_client::tests::get_test_yaml;
+        use crate::raindex_client::RaindexClient;
+        use alloy::primitives::{Address, Bytes};
+        use httpmock::MockServer;
+        use serde_json::json;
+
+        #[tokio::test]
+        async fn test_estimate_calldata() {
+            let server = MockServer::start_async().await;
+            server.mock(|when, then| {
+                when.path("/rpc1").body_contains("eth_estimateGas");
+                then.json_body(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": {"code": 3, "message": "execution reverted"}
+                }));
+            });
+
+            let client = RaindexClient::new(
+                vec![get_test_yaml(
+                    &server.url("/sg1"),
+                    &server.url("/sg2"),
+                    &server.url("/rpc1"),
+                    &server.url("/rpc2"),
+                )],
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            let cost = client
+                .estimate_calldata(1, Address::ZERO, Address::ZERO, Bytes::new())
+                .await
+                .unwrap();
+            assert_eq!(cost, CalldataCost::Revert("execution reverted".to_string()));
+
+            let err = client
+                .estimate_calldata(999, Address::ZERO, Address::ZERO, Bytes::new())
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                crate::raindex_client::RaindexError::YamlError(_)
+            ));
+        }
+    }
+}
@@ -57,6 +57,13 @@ impl BootstrapPipeline for ClientBootstrapAdapter {
             last_synced_block, ..
         } = self.inspect_state(db, &config.ob_id).await?;
 
+        if let Some(delta_stmt) = config.delta_stmt.as_ref() {
+            if !self.is_fresh_db(db, &config.ob_id).await? {
+                db.execute_batch(delta_stmt).await?;
+                return Ok(());
+            }
+        }
+
         if let Some(dump_stmt) = config.dump_stmt.as_ref() {
             if self.is_fresh_db(db, &config.ob_id).await? {
                 db.execute_batch(dump_stmt).await?;
@@ -107,10 +114,14 @@ impl BootstrapPipeline for ClientBootstrapAdapter {
 
         match self.ensure_schema(db, db_schema_version).await {
             Ok(_) => {}
-            Err(LocalDbError::MissingDbMetadataRow)
-            | Err(LocalDbError::SchemaVersionMismatch { .. }) => {
+            Err(LocalDbError::MissingDbMetadataRow) => {
                 self.reset_db(db, db_schema_version).await?;
             }
+            Err(LocalDbError::SchemaVersionMismatch { expected, found }) => {
+                if self.migrate_schema(db, found, expected).await.is_err() {
+                    self.reset_db(db, db_schema_version).await?;
+                }
+            }
             Err(err) => return Err(err),
         }
 
@@ -465,6 +476,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: None,
             latest_block: 100,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -480,6 +492,40 @@ mod tests {
         assert_eq!(db.calls(), vec![dump_stmt.sql().to_string()]);
     }
 
+    #[tokio::test]
+    async fn engine_run_applies_only_deltas_on_synced_db() {
+        let adapter = ClientBootstrapAdapter::new();
+        let dump_stmt = SqlStatement::new("--dump-sql");
+        let delta_stmt = SqlStatement::new("--delta-sql");
+        let cfg = BootstrapConfig {
+            ob_id: sample_ob_id(),
+            dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: Some(SqlStatementBatch::from(vec![delta_stmt.clone()])),
+            latest_block: 300,
+            block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
+            deployment_block: 1,
+        };
+
+        let db = MockDb::default()
+            .with_json(&fetch_tables_stmt(), required_tables_json())
+            .with_json(
+                &fetch_target_watermark_stmt(&cfg.ob_id),
+                json!([watermark_row(200)]),
+            )
+            .with_text(&delta_stmt, "ok");
+
+        adapter.engine_run(&db, &cfg).await.unwrap();
+        assert_eq!(db.calls(), vec![delta_stmt.sql().to_string()]);
+
+        let fresh_db = MockDb::default()
+            .with_json(&fetch_tables_stmt(), required_tables_json())
+            .with_json(&fetch_target_watermark_stmt(&cfg.ob_id), json!([]))
+            .with_text(&dump_stmt, "ok");
+
+        adapter.engine_run(&fresh_db, &cfg).await.unwrap();
+        assert_eq!(fresh_db.calls(), vec![dump_stmt.sql().to_string()]);
+    }
+
     #[tokio::test]
     async fn engine_run_clears_and_applies_dump_when_threshold_exceeded() {
         let adapter = ClientBootstrapAdapter::new();
@@ -490,6 +536,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: None,
             latest_block: latest,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -569,6 +616,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: None,
+            delta_stmt: None,
             latest_block: latest,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
@@ -596,6 +644,7 @@ mod tests {
         let cfg = BootstrapConfig {
             ob_id: sample_ob_id(),
             dump_stmt: Some(SqlStatementBatch::from(vec![dump_stmt.clone()])),
+            delta_stmt: None,
             latest_block: latest,
             block_number_threshold: TEST_BLOCK_NUMBER_THRESHOLD,
             deployment_block: 1,
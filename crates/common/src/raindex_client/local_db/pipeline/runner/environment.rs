@@ -107,6 +107,7 @@ mod tests {
                     window_overrides: WindowOverrides::default(),
                 },
                 dump_str: None,
+                delta_dump_str: None,
                 block_number_threshold: 10000,
                 manifest_end_block: 1,
             },
@@ -13,7 +13,7 @@ use crate::local_db::{
         },
         runner::{
             environment::RunnerEnvironment,
-            remotes::lookup_manifest_entry,
+            remotes::{lookup_manifest_entry, plan_manifest_dumps, ManifestDumpPlan},
             utils::{
                 build_runner_targets, parse_runner_settings, ParsedRunnerSettings, RunnerTarget,
             },
@@ -21,7 +21,10 @@ use crate::local_db::{
         },
         EventsPipeline, StatusBus, TokensPipeline, WindowPipeline,
     },
-    query::LocalDbQueryExecutor,
+    query::{
+        fetch_target_watermark::{fetch_target_watermark_stmt, TargetWatermarkRow},
+        LocalDbQueryExecutor,
+    },
     LocalDbError, OrderbookIdentifier,
 };
 use crate::raindex_client::local_db::pipeline::bootstrap::ClientBootstrapAdapter;
@@ -147,7 +150,7 @@ where
         let needs_provisioning = !self.has_provisioned_dumps;
 
         if needs_provisioning {
-            let (provisioned, mut provisioning_failures) = self.provision_dumps(targets).await;
+            let (provisioned, mut provisioning_failures) = self.provision_dumps(db, targets).await;
             let had_provisioning_failures = !provisioning_failures.is_empty();
             targets = provisioned;
 
@@ -172,32 +175,56 @@ where
         Ok(RunOutcome::Report(report))
     }
 
-    async fn provision_dumps(
+    async fn provision_dumps<DB>(
         &self,
+        db: &DB,
         targets: Vec<RunnerTarget>,
-    ) -> (Vec<RunnerTarget>, Vec<TargetFailure>) {
+    ) -> (Vec<RunnerTarget>, Vec<TargetFailure>)
+    where
+        DB: LocalDbQueryExecutor + ?Sized,
+    {
         let manifest_map = &self.manifest_map;
         let environment = self.environment.clone();
         let futures = targets.into_iter().map(move |mut target| {
             let environment = environment.clone();
             async move {
-                if let Some(entry) = lookup_manifest_entry(manifest_map, &target) {
-                    let dump_sql = environment.download_dump(&entry.dump_url).await;
-                    return match dump_sql {
-                        Ok(sql) => {
-                            target.inputs.dump_str = Some(sql);
-                            target.inputs.manifest_end_block = entry.end_block;
-                            Ok(target)
+                let Some(entry) = lookup_manifest_entry(manifest_map, &target) else {
+                    return Ok(target);
+                };
+
+                let provisioned = async {
+                    let watermark: Vec<TargetWatermarkRow> = db
+                        .query_json(&fetch_target_watermark_stmt(&target.inputs.ob_id))
+                        .await?;
+                    match plan_manifest_dumps(
+                        &entry,
+                        watermark.first(),
+                        target.inputs.block_number_threshold,
+                    ) {
+                        ManifestDumpPlan::Full => {
+                            target.inputs.dump_str =
+                                Some(environment.download_full_dump(&entry).await?);
                         }
-                        Err(error) => Err(TargetFailure {
-                            ob_id: target.inputs.ob_id.clone(),
-                            orderbook_key: Some(target.orderbook_key.clone()),
-                            stage: TargetStage::DumpDownload,
-                            error,
-                        }),
-                    };
+                        ManifestDumpPlan::Deltas(deltas) => {
+                            target.inputs.delta_dump_str =
+                                Some(environment.download_deltas(deltas).await?);
+                        }
+                        ManifestDumpPlan::Skip => {}
+                    }
+                    target.inputs.manifest_end_block = entry.head_block();
+                    Ok::<(), LocalDbError>(())
+                }
+                .await;
+
+                match provisioned {
+                    Ok(()) => Ok(target),
+                    Err(error) => Err(TargetFailure {
+                        ob_id: target.inputs.ob_id.clone(),
+                        orderbook_key: Some(target.orderbook_key.clone()),
+                        stage: TargetStage::DumpDownload,
+                        error,
+                    }),
                 }
-                Ok(target)
             }
         });
 
@@ -336,7 +363,8 @@ mod tests {
     use alloy::primitives::{address, b256, Address, Bytes, B256};
     use async_trait::async_trait;
     use rain_orderbook_app_settings::local_db_manifest::{
-        LocalDbManifest, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION, MANIFEST_VERSION,
+        LocalDbManifest, ManifestDelta, ManifestNetwork, ManifestOrderbook, DB_SCHEMA_VERSION,
+        MANIFEST_VERSION,
     };
     use rain_orderbook_app_settings::orderbook::OrderbookCfg;
     use rain_orderbook_app_settings::remote::manifest::ManifestMap;
@@ -442,6 +470,7 @@ mod tests {
     struct BootstrapRecord {
         orderbook_key: String,
         dump_sql: Option<String>,
+        delta_sql: Option<String>,
         latest_block: u64,
     }
 
@@ -488,6 +517,7 @@ mod tests {
             &self,
             orderbook_key: String,
             dump_sql: Option<String>,
+            delta_sql: Option<String>,
             latest_block: u64,
         ) {
             self.bootstrap_records
@@ -496,6 +526,7 @@ mod tests {
                 .push(BootstrapRecord {
                     orderbook_key,
                     dump_sql,
+                    delta_sql,
                     latest_block,
                 });
         }
@@ -704,10 +735,12 @@ mod tests {
         where
             DB: LocalDbQueryExecutor + ?Sized,
         {
+            let delta_sql = config.delta_stmt.as_ref().map(dump_sql);
             let dump_sql = config.dump_stmt.as_ref().map(dump_sql);
             self.telemetry.record_bootstrap(
                 self.orderbook_key.clone(),
                 dump_sql,
+                delta_sql,
                 config.latest_block,
             );
             Ok(())
@@ -991,12 +1024,15 @@ mod tests {
                     orderbooks: vec![ManifestOrderbook {
                         address: orderbook_address,
                         dump_url,
+                        dump_sha256: B256::ZERO,
                         end_block,
                         end_block_hash: Bytes::copy_from_slice(end_block_hash.as_slice()),
                         end_block_time_ms: 1_000,
+                        deltas: vec![],
                     }],
                 },
             )]),
+            signature: None,
         };
         HashMap::from([(remote, manifest)])
     }
@@ -1163,7 +1199,7 @@ orderbooks:
         let dump_downloader = {
             let telemetry = telemetry.clone();
             let counter = Arc::new(AtomicUsize::new(0));
-            Arc::new(move |url: &Url| {
+            Arc::new(move |url: &Url, _sha256: &B256| {
                 let telemetry = telemetry.clone();
                 let counter = Arc::clone(&counter);
                 let url = url.clone();
@@ -1443,6 +1479,78 @@ orderbooks:
         assert_eq!(record_b.latest_block, 0);
     }
 
+    #[tokio::test]
+    async fn provisioning_applies_only_missing_deltas() {
+        let delta =
+            |start_block: u64, start_hash: u8, end_block: u64, end_hash: u8| ManifestDelta {
+                dump_url: Url::parse(&format!("https://dumps.example/ob-a-{end_block}.sql"))
+                    .unwrap(),
+                dump_sha256: B256::ZERO,
+                start_block,
+                start_block_hash: Bytes::from(vec![start_hash]),
+                end_block,
+                end_block_hash: Bytes::from(vec![end_hash]),
+                end_block_time_ms: 2_000,
+            };
+        let mut manifest_map = manifest_for_a();
+        for network in manifest_map
+            .get_mut(&remote_url_a())
+            .unwrap()
+            .networks
+            .values_mut()
+        {
+            let entry = &mut network.orderbooks[0];
+            entry.end_block_hash = Bytes::from(vec![0x11]);
+            entry.deltas = vec![delta(111, 0x11, 150, 0x15), delta(150, 0x15, 200, 0x20)];
+        }
+
+        let telemetry = Telemetry::default();
+        let environment = build_environment(
+            manifest_map,
+            HashMap::from([(ORDERBOOK_KEY_B.to_string(), EngineBehavior::Success)]),
+            1,
+            1,
+            telemetry.clone(),
+        );
+        let mut runner = ClientRunner::with_environment(
+            two_orderbooks_settings_yaml(),
+            environment,
+            AlwaysLeadership,
+        )
+        .unwrap();
+        let db = RecordingDb::default();
+        prepare_db_for_targets(&db, &runner.base_targets);
+        db.set_json_value(
+            &fetch_target_watermark_stmt(&OrderbookIdentifier::new(CHAIN_ID, ORDERBOOK_A)),
+            [TargetWatermarkRow {
+                chain_id: CHAIN_ID,
+                orderbook_address: ORDERBOOK_A,
+                last_block: 150,
+                last_hash: Bytes::from(vec![0x15]),
+                updated_at: 1,
+            }],
+        );
+
+        let report = unwrap_report(runner.run(&db).await.expect("run succeeds"));
+        assert_eq!(report.successes.len(), 2);
+        assert_eq!(
+            telemetry.dump_requests(),
+            vec![Url::parse("https://dumps.example/ob-a-200.sql").unwrap()]
+        );
+
+        let records = telemetry.bootstrap_records();
+        let record_a = records
+            .iter()
+            .find(|r| r.orderbook_key == ORDERBOOK_KEY_A)
+            .expect("record for ob-a");
+        assert!(record_a.dump_sql.is_none());
+        assert!(record_a
+            .delta_sql
+            .as_ref()
+            .is_some_and(|sql| sql.contains("ob-a-200.sql")));
+        assert_eq!(record_a.latest_block, 200);
+    }
+
     #[tokio::test]
     async fn run_returns_immediately_when_no_targets() {
         let telemetry = Telemetry::default();
@@ -1484,7 +1592,7 @@ orderbooks:
         };
         let dump_downloader = {
             let call_count = Arc::clone(&call_count);
-            Arc::new(move |_url: &Url| {
+            Arc::new(move |_url: &Url, _sha256: &B256| {
                 let count = call_count.fetch_add(1, Ordering::SeqCst);
                 Box::pin(async move {
                     if count == 0 {
@@ -1532,7 +1640,7 @@ orderbooks:
             })
         };
 
-        let dump_downloader = Arc::new(|_url: &Url| {
+        let dump_downloader = Arc::new(|_url: &Url, _sha256: &B256| {
             Box::pin(async move {
                 Err(LocalDbError::CustomError(
                     "dump downloader should not run".into(),
@@ -1588,7 +1696,7 @@ orderbooks:
 
         let dump_downloader = {
             let telemetry = telemetry.clone();
-            Arc::new(move |url: &Url| {
+            Arc::new(move |url: &Url, _sha256: &B256| {
                 let telemetry = telemetry.clone();
                 let url = url.clone();
                 Box::pin(async move {
@@ -1637,7 +1745,7 @@ orderbooks:
             })
         };
 
-        let dump_downloader = Arc::new(|url: &Url| {
+        let dump_downloader = Arc::new(|url: &Url, _sha256: &B256| {
             let url = url.clone();
             Box::pin(async move {
                 if url == dump_url_a() {
@@ -1700,7 +1808,7 @@ orderbooks:
         let dump_downloader = {
             let telemetry = telemetry.clone();
             let counter = Arc::new(AtomicUsize::new(0));
-            Arc::new(move |url: &Url| {
+            Arc::new(move |url: &Url, _sha256: &B256| {
                 let telemetry = telemetry.clone();
                 let counter = Arc::clone(&counter);
                 let url = url.clone();
@@ -9,11 +9,14 @@ use crate::local_db::pipeline::adapters::{
 };
 use crate::local_db::pipeline::runner::utils::ParsedRunnerSettings;
 use crate::local_db::pipeline::runner::RunOutcome;
+use crate::local_db::pipeline::sync_events::{collect_sync_events, SyncEvent};
+use crate::local_db::pipeline::SyncOutcome;
 use crate::local_db::query::LocalDbQueryExecutor;
 use crate::local_db::LocalDbError;
 use crate::raindex_client::local_db::pipeline::bootstrap::ClientBootstrapAdapter;
 use crate::raindex_client::local_db::pipeline::status::TracingStatusBus;
 use crate::raindex_client::local_db::{LocalDb, SyncReadiness};
+use futures::stream::{self, BoxStream, StreamExt};
 use rain_orderbook_app_settings::local_db_manifest::DB_SCHEMA_VERSION;
 use rain_orderbook_app_settings::network::NetworkCfg;
 use std::collections::HashMap;
@@ -23,6 +26,13 @@ use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before the slowest one starts missing them.
+const SYNC_EVENTS_CAPACITY: usize = 1024;
+
+/// Domain events applied by the sync loops, see [`NativeSyncHandle::subscribe`].
+pub type SyncEventStream = BoxStream<'static, SyncEvent>;
 
 type NativeClientRunner = ClientRunner<
     ClientBootstrapAdapter,
@@ -53,6 +63,7 @@ impl NativeRunner for NativeClientRunner {
 pub struct NativeSyncHandle {
     stop_flag: Arc<AtomicBool>,
     thread_handle: Option<JoinHandle<()>>,
+    events: broadcast::Sender<SyncEvent>,
 }
 
 impl std::fmt::Debug for NativeSyncHandle {
@@ -68,6 +79,29 @@ impl NativeSyncHandle {
         self.stop_flag.store(true, Ordering::SeqCst);
     }
 
+    /// Streams domain events as each sync cycle persists them.
+    ///
+    /// Only events applied after subscribing are delivered. A subscriber that
+    /// falls more than [`SYNC_EVENTS_CAPACITY`] events behind skips the oldest
+    /// ones, and the stream ends once the scheduler stops.
+    pub fn subscribe(&self) -> SyncEventStream {
+        stream::unfold(self.events.subscribe(), |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            skipped,
+                            "sync event subscriber lagged, {skipped} events were dropped"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+
     pub fn stop_and_join(mut self) {
         self.stop_flag.store(true, Ordering::SeqCst);
         if let Some(handle) = self.thread_handle.take() {
@@ -98,6 +132,8 @@ pub fn start(
 
     let stop_flag = Arc::new(AtomicBool::new(false));
     let stop_flag_clone = Arc::clone(&stop_flag);
+    let (events, _) = broadcast::channel(SYNC_EVENTS_CAPACITY);
+    let events_clone = events.clone();
 
     let thread_handle = thread::spawn(move || {
         let rt = tokio::runtime::Builder::new_current_thread()
@@ -182,6 +218,7 @@ pub fn start(
                             network.key.clone(),
                             network.chain_id,
                             sync_readiness.clone(),
+                            events_clone.clone(),
                         ));
                     }
 
@@ -199,6 +236,7 @@ pub fn start(
     Ok(NativeSyncHandle {
         stop_flag,
         thread_handle: Some(thread_handle),
+        events,
     })
 }
 
@@ -210,6 +248,7 @@ async fn run_network_loop<R: NativeRunner>(
     network_key: String,
     chain_id: u32,
     sync_readiness: SyncReadiness,
+    events: broadcast::Sender<SyncEvent>,
 ) {
     tracing::info!(network = %network_key, chain_id, "starting native sync loop");
 
@@ -221,6 +260,10 @@ async fn run_network_loop<R: NativeRunner>(
         match runner.run_once(&db).await {
             Ok(outcome) => match outcome {
                 RunOutcome::Report(report) => {
+                    for success in &report.successes {
+                        publish_sync_events(&db, &success.outcome, &events).await;
+                    }
+
                     if report.failures.is_empty() {
                         sync_readiness.mark_ready(chain_id);
                         tracing::debug!(
@@ -270,10 +313,38 @@ async fn run_network_loop<R: NativeRunner>(
     tracing::info!(network = %network_key, chain_id, "native sync loop stopped");
 }
 
+async fn publish_sync_events(
+    db: &LocalDb,
+    outcome: &SyncOutcome,
+    events: &broadcast::Sender<SyncEvent>,
+) {
+    // reading back vault balance changes is wasted work without listeners
+    if events.receiver_count() == 0 {
+        return;
+    }
+
+    match collect_sync_events(db, outcome).await {
+        Ok(collected) => {
+            for event in collected {
+                let _ = events.send(event);
+            }
+        }
+        Err(err) => {
+            tracing::warn!(
+                chain_id = outcome.ob_id.chain_id,
+                ob = %format!("{:#x}", outcome.ob_id.orderbook_address),
+                error = %err,
+                "failed to collect sync events"
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::local_db::pipeline::runner::{RunReport, TargetFailure, TargetStage};
+    use crate::local_db::pipeline::runner::{RunReport, TargetFailure, TargetStage, TargetSuccess};
+    use crate::local_db::pipeline::sync_events::SyncEventKind;
     use crate::local_db::query::{FromDbJson, LocalDbQueryError, SqlStatement, SqlStatementBatch};
     use crate::local_db::OrderbookIdentifier;
     use alloy::primitives::Address;
@@ -307,6 +378,71 @@ mod tests {
         LocalDb::new(NoopExecutor)
     }
 
+    /// Answers every query with an empty row set.
+    struct EmptyRowsExecutor;
+
+    #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+    impl LocalDbQueryExecutor for EmptyRowsExecutor {
+        async fn execute_batch(&self, _: &SqlStatementBatch) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+        async fn query_json<T: FromDbJson>(
+            &self,
+            _: &SqlStatement,
+        ) -> Result<T, LocalDbQueryError> {
+            serde_json::from_value(serde_json::json!([]))
+                .map_err(|e| LocalDbQueryError::deserialization(e.to_string()))
+        }
+        async fn query_text(&self, _: &SqlStatement) -> Result<String, LocalDbQueryError> {
+            Ok(String::new())
+        }
+        async fn wipe_and_recreate(&self) -> Result<(), LocalDbQueryError> {
+            Ok(())
+        }
+    }
+
+    fn sample_sync_event() -> SyncEvent {
+        SyncEvent {
+            ob_id: OrderbookIdentifier::new(1, Address::ZERO),
+            block_number: 10,
+            block_timestamp: 1_700_000_000,
+            transaction_hash: Default::default(),
+            log_index: 0,
+            kind: SyncEventKind::OrderRemoved {
+                order_hash: Default::default(),
+                owner: Address::ZERO,
+            },
+        }
+    }
+
+    /// Reports one successful target that applied [`sample_sync_event`].
+    struct EventsRunner;
+
+    impl NativeRunner for EventsRunner {
+        fn run_once<'a, DB: LocalDbQueryExecutor + ?Sized>(
+            &'a mut self,
+            _db: &'a DB,
+        ) -> Pin<Box<dyn Future<Output = Result<RunOutcome, LocalDbError>> + 'a>> {
+            Box::pin(async move {
+                Ok(RunOutcome::Report(RunReport {
+                    successes: vec![TargetSuccess {
+                        outcome: SyncOutcome {
+                            ob_id: OrderbookIdentifier::new(1, Address::ZERO),
+                            start_block: 10,
+                            target_block: 10,
+                            fetched_logs: 1,
+                            decoded_events: 1,
+                            rolled_back_to: None,
+                            events: vec![sample_sync_event()],
+                        },
+                    }],
+                    failures: vec![],
+                }))
+            })
+        }
+    }
+
     struct RecordingRunner {
         calls: Arc<AtomicUsize>,
         failures: Arc<AtomicUsize>,
@@ -402,6 +538,7 @@ mod tests {
         let handle = NativeSyncHandle {
             stop_flag: Arc::new(AtomicBool::new(false)),
             thread_handle: None,
+            events: broadcast::channel(1).0,
         };
         assert!(!handle.stop_flag.load(Ordering::SeqCst));
         handle.stop();
@@ -413,6 +550,7 @@ mod tests {
         let handle = NativeSyncHandle {
             stop_flag: Arc::new(AtomicBool::new(false)),
             thread_handle: Some(thread::spawn(|| {})),
+            events: broadcast::channel(1).0,
         };
         handle.stop_and_join();
     }
@@ -422,6 +560,7 @@ mod tests {
         let handle = NativeSyncHandle {
             stop_flag: Arc::new(AtomicBool::new(false)),
             thread_handle: None,
+            events: broadcast::channel(1).0,
         };
         handle.stop_and_join();
     }
@@ -431,6 +570,7 @@ mod tests {
         let handle = NativeSyncHandle {
             stop_flag: Arc::new(AtomicBool::new(false)),
             thread_handle: None,
+            events: broadcast::channel(1).0,
         };
         let debug = format!("{:?}", handle);
         assert!(debug.contains("NativeSyncHandle"));
@@ -464,6 +604,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 tokio::time::sleep(std::time::Duration::from_millis(20)).await;
@@ -503,6 +644,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 tokio::time::sleep(std::time::Duration::from_millis(30)).await;
@@ -542,6 +684,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 tokio::time::sleep(std::time::Duration::from_millis(30)).await;
@@ -578,6 +721,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 tokio::time::sleep(std::time::Duration::from_millis(30)).await;
@@ -620,6 +764,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 tokio::time::sleep(std::time::Duration::from_millis(20)).await;
@@ -658,6 +803,7 @@ mod tests {
                     "test".to_string(),
                     1,
                     readiness.clone(),
+                    broadcast::channel(1).0,
                 ));
 
                 for _ in 0..200 {
@@ -682,4 +828,53 @@ mod tests {
             })
             .await;
     }
+
+    #[tokio::test]
+    async fn subscribers_receive_applied_events() {
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let (events, _) = broadcast::channel(16);
+                let handle = NativeSyncHandle {
+                    stop_flag: Arc::new(AtomicBool::new(false)),
+                    thread_handle: None,
+                    events: events.clone(),
+                };
+                let mut stream = handle.subscribe();
+
+                tokio::task::spawn_local(run_network_loop(
+                    EventsRunner,
+                    LocalDb::new(EmptyRowsExecutor),
+                    Arc::clone(&handle.stop_flag),
+                    10000,
+                    "test".to_string(),
+                    1,
+                    SyncReadiness::new(),
+                    events,
+                ));
+
+                let received =
+                    tokio::time::timeout(std::time::Duration::from_secs(1), stream.next())
+                        .await
+                        .expect("event should be published")
+                        .expect("stream should stay open");
+                assert_eq!(received, sample_sync_event());
+
+                handle.stop();
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn subscription_ends_when_scheduler_is_gone() {
+        let handle = NativeSyncHandle {
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            thread_handle: None,
+            events: broadcast::channel(1).0,
+        };
+        let mut stream = handle.subscribe();
+        drop(handle);
+
+        assert!(stream.next().await.is_none());
+    }
 }
@@ -9,10 +9,13 @@ use crate::local_db::pipeline::adapters::{
 };
 use crate::local_db::pipeline::runner::utils::ParsedRunnerSettings;
 use crate::local_db::pipeline::runner::RunOutcome;
+use crate::local_db::pipeline::sync_events::collect_sync_events;
+use crate::local_db::pipeline::SyncOutcome;
 use crate::local_db::LocalDbError;
 use crate::raindex_client::local_db::pipeline::bootstrap::ClientBootstrapAdapter;
 use crate::raindex_client::local_db::pipeline::status::{
-    set_scheduler_state, set_status_callback, ClientStatusBus,
+    emit_sync_event, has_sync_event_callback, set_scheduler_state, set_status_callback,
+    ClientStatusBus,
 };
 use crate::raindex_client::local_db::{LocalDb, NetworkSyncStatus, SchedulerState, SyncReadiness};
 use gloo_timers::future::TimeoutFuture;
@@ -235,6 +238,10 @@ async fn run_network_loop<R>(
                     was_leader_last_cycle = true;
                     set_scheduler_state(SchedulerState::Leader);
 
+                    for success in &report.successes {
+                        publish_sync_events(&db, &success.outcome).await;
+                    }
+
                     if report.failures.is_empty() {
                         sync_readiness.mark_ready(chain_id);
                         emit_network_status(
@@ -281,6 +288,29 @@ async fn run_network_loop<R>(
     }
 }
 
+async fn publish_sync_events(db: &LocalDb, outcome: &SyncOutcome) {
+    // reading back vault balance changes is wasted work without a listener
+    if !has_sync_event_callback() {
+        return;
+    }
+
+    match collect_sync_events(db, outcome).await {
+        Ok(events) => {
+            for event in &events {
+                emit_sync_event(event);
+            }
+        }
+        Err(err) => {
+            tracing::warn!(
+                chain_id = outcome.ob_id.chain_id,
+                ob = %format!("{:#x}", outcome.ob_id.orderbook_address),
+                error = %err,
+                "failed to collect sync events"
+            );
+        }
+    }
+}
+
 fn emit_network_status(callback: Option<&Function>, status: NetworkSyncStatus) {
     if let Some(callback) = callback {
         if let Ok(value) = serde_wasm_bindgen::to_value(&status) {
@@ -29,6 +29,14 @@ mod tests {
             SyncPhase::RunningBootstrap.to_message(),
             "Running bootstrap"
         );
+        assert_eq!(
+            SyncPhase::VerifyingChainContinuity.to_message(),
+            "Verifying chain continuity"
+        );
+        assert_eq!(
+            SyncPhase::RollingBackReorg.to_message(),
+            "Rolling back reorganized blocks"
+        );
         assert_eq!(
             SyncPhase::ComputingSyncWindow.to_message(),
             "Computing sync window"
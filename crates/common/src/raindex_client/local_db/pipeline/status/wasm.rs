@@ -1,3 +1,4 @@
+use crate::local_db::pipeline::sync_events::SyncEvent;
 use crate::local_db::pipeline::{StatusBus, SyncPhase};
 use crate::local_db::{LocalDbError, OrderbookIdentifier};
 use crate::raindex_client::local_db::{OrderbookSyncStatus, SchedulerState};
@@ -8,6 +9,7 @@ use wasm_bindgen_utils::prelude::*;
 
 thread_local! {
     static STATUS_CALLBACK: RefCell<Option<Rc<Function>>> = const { RefCell::new(None) };
+    static SYNC_EVENT_CALLBACK: RefCell<Option<Rc<Function>>> = const { RefCell::new(None) };
     static SCHEDULER_STATE: RefCell<SchedulerState> = const { RefCell::new(SchedulerState::Leader) };
 }
 
@@ -17,6 +19,26 @@ pub fn set_status_callback(callback: Option<Rc<Function>>) {
     });
 }
 
+pub fn set_sync_event_callback(callback: Option<Rc<Function>>) {
+    SYNC_EVENT_CALLBACK.with(|c| {
+        *c.borrow_mut() = callback;
+    });
+}
+
+pub fn has_sync_event_callback() -> bool {
+    SYNC_EVENT_CALLBACK.with(|c| c.borrow().is_some())
+}
+
+pub fn emit_sync_event(event: &SyncEvent) {
+    SYNC_EVENT_CALLBACK.with(|c| {
+        if let Some(callback) = c.borrow().as_ref() {
+            if let Ok(value) = serde_wasm_bindgen::to_value(event) {
+                let _ = callback.call1(&JsValue::NULL, &value);
+            }
+        }
+    });
+}
+
 pub fn set_scheduler_state(state: SchedulerState) {
     SCHEDULER_STATE.with(|s| {
         *s.borrow_mut() = state;
@@ -244,6 +266,44 @@ mod wasm_tests {
         );
     }
 
+    #[wasm_bindgen_test]
+    fn emit_sync_event_invokes_registered_callback() {
+        use crate::local_db::pipeline::sync_events::{SyncEvent, SyncEventKind};
+
+        let recorded = Rc::new(RefCell::new(Vec::new()));
+        let recorded_clone = Rc::clone(&recorded);
+        let closure = Closure::wrap(Box::new(move |value: JsValue| {
+            if let Ok(event) = serde_wasm_bindgen::from_value::<SyncEvent>(value) {
+                recorded_clone.borrow_mut().push(event);
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+        let function: js_sys::Function = closure.as_ref().clone().unchecked_into();
+        closure.forget();
+
+        let event = SyncEvent {
+            ob_id: test_ob_id(),
+            block_number: 10,
+            block_timestamp: 1_700_000_000,
+            transaction_hash: Default::default(),
+            log_index: 0,
+            kind: SyncEventKind::OrderRemoved {
+                order_hash: Default::default(),
+                owner: Default::default(),
+            },
+        };
+
+        emit_sync_event(&event);
+        assert!(recorded.borrow().is_empty());
+
+        set_sync_event_callback(Some(Rc::new(function)));
+        assert!(has_sync_event_callback());
+        emit_sync_event(&event);
+        set_sync_event_callback(None);
+        assert!(!has_sync_event_callback());
+
+        assert_eq!(*recorded.borrow(), vec![event]);
+    }
+
     #[wasm_bindgen_test]
     async fn send_does_not_emit_when_not_leader() {
         let recorded = Rc::new(RefCell::new(Vec::new()));
@@ -2,7 +2,8 @@ use crate::local_db::{query::LocalDbQueryError, LocalDbError};
 use crate::raindex_client::local_db::{LocalDb, SyncReadiness};
 use crate::{
     add_order::AddOrderArgsError, deposit::DepositError, dotrain_order::DotrainOrderError,
-    meta::TryDecodeRainlangSourceError, transaction::WritableTransactionExecuteError,
+    gas_estimate::GasEstimateError, meta::TryDecodeRainlangSourceError,
+    multicall::OrderbookMulticallError, transaction::WritableTransactionExecuteError,
     utils::amount_formatter::AmountFormatterError,
 };
 use alloy::{
@@ -24,8 +25,8 @@ use rain_orderbook_app_settings::{
     },
 };
 use rain_orderbook_subgraph_client::{
-    types::order_detail_traits::OrderDetailError, MultiSubgraphArgs, OrderbookSubgraphClient,
-    OrderbookSubgraphClientError,
+    performance::PerformanceError, types::order_detail_traits::OrderDetailError, MultiSubgraphArgs,
+    OrderbookSubgraphClient, OrderbookSubgraphClientError,
 };
 use serde::{Deserialize, Serialize};
 #[cfg(not(target_family = "wasm"))]
@@ -45,15 +46,18 @@ use url::Url;
 use wasm_bindgen_utils::{impl_wasm_traits, prelude::*, wasm_export};
 
 pub mod add_orders;
+pub mod gas_estimates;
 pub mod local_db;
 pub mod order_quotes;
 pub mod orderbook_yaml;
 pub mod orders;
 pub mod orders_list;
+pub mod rebalance_vaults;
 pub mod remove_orders;
 pub mod take_orders;
 pub mod trades;
 pub mod transactions;
+pub mod update_orders;
 pub mod vaults;
 pub mod vaults_list;
 
@@ -204,6 +208,35 @@ impl RaindexClient {
             ),
         })
     }
+
+    /// Registers a callback invoked with every order, trade, deposit,
+    /// withdrawal and vault balance change the local DB sync applies.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// client.setSyncEventCallback((event) => {
+    ///   if (event.kind.type === "trade") {
+    ///     console.log("Order filled", event.kind.orderHash);
+    ///   }
+    /// });
+    /// ```
+    #[wasm_export(js_name = "setSyncEventCallback", unchecked_return_type = "void")]
+    pub fn set_sync_event_callback(
+        &self,
+        #[wasm_export(
+            param_description = "Callback invoked with each applied SyncEvent, or undefined to stop receiving events"
+        )]
+        callback: Option<js_sys::Function>,
+    ) -> Result<(), RaindexError> {
+        if callback.is_some() && self.local_db_state.scheduler.borrow().is_none() {
+            return Err(RaindexError::LocalDbSyncNotRunning);
+        }
+        crate::raindex_client::local_db::pipeline::status::set_sync_event_callback(
+            callback.map(Rc::new),
+        );
+        Ok(())
+    }
 }
 
 #[wasm_export]
@@ -332,6 +365,23 @@ impl RaindexClient {
             ),
         })
     }
+
+    /// Streams orders, trades, deposits, withdrawals and vault balance
+    /// changes as the local DB sync applies them.
+    pub fn subscribe_sync_events(
+        &self,
+    ) -> Result<
+        crate::raindex_client::local_db::pipeline::runner::scheduler::SyncEventStream,
+        RaindexError,
+    > {
+        self.local_db_state
+            .scheduler
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_ref()
+            .map(|handle| handle.subscribe())
+            .ok_or(RaindexError::LocalDbSyncNotRunning)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -384,6 +434,8 @@ pub enum RaindexError {
     #[error(transparent)]
     OrderbookSubgraphClientError(#[from] OrderbookSubgraphClientError),
     #[error(transparent)]
+    PerformanceError(#[from] PerformanceError),
+    #[error(transparent)]
     TryDecodeRainlangSourceError(#[from] TryDecodeRainlangSourceError),
     #[error(transparent)]
     U256ParseError(#[from] ParseError),
@@ -441,6 +493,8 @@ pub enum RaindexError {
     LocalDbUnsupportedNetwork(u32),
     #[error("YAML has local-db-sync but no {0} was provided")]
     LocalDbSetupMissing(String),
+    #[error("Local database sync is not running")]
+    LocalDbSyncNotRunning,
     #[error("No liquidity available for the requested token pair")]
     NoLiquidity,
     #[error("Insufficient liquidity: requested {requested}, available {available}")]
@@ -472,6 +526,22 @@ pub enum RaindexError {
     MetaboardSubgraphError(String),
     #[error("Invalid dotrain source metadata found")]
     InvalidDotrainSourceMetadata,
+    #[error("Order is not active: {0}")]
+    OrderNotActive(B256),
+    #[error(
+        "Deployment {deployment} does not target orderbook {orderbook} on chain ID {chain_id}"
+    )]
+    DeploymentOrderbookMismatch {
+        deployment: String,
+        orderbook: Address,
+        chain_id: u32,
+    },
+    #[error(transparent)]
+    GasEstimateError(#[from] GasEstimateError),
+    #[error("Invalid rebalance targets: {0}")]
+    InvalidRebalanceTargets(String),
+    #[error(transparent)]
+    OrderbookMulticallError(#[from] OrderbookMulticallError),
 }
 
 impl From<DotrainOrderError> for RaindexError {
@@ -540,6 +610,9 @@ impl RaindexError {
             RaindexError::OrderbookSubgraphClientError(err) => {
                 format!("Failed to query subgraph: {}. Check network connection and subgraph availability.", err)
             }
+            RaindexError::PerformanceError(err) => {
+                format!("Failed to measure order performance: {}", err)
+            }
             RaindexError::TryDecodeRainlangSourceError(err) => {
                 format!("Failed to decode Rainlang source: {}. The source code may be corrupted or incompatible.", err)
             }
@@ -627,6 +700,9 @@ impl RaindexError {
             RaindexError::LocalDbSetupMissing(field) => {
                 format!("YAML has local-db-sync configured but no {field} was provided.")
             }
+            RaindexError::LocalDbSyncNotRunning => {
+                "Local database sync is not running. Add local-db-sync to the YAML to receive sync events.".to_string()
+            }
             RaindexError::NoLiquidity => {
                 "No liquidity available for the requested token pair".to_string()
             }
@@ -672,6 +748,23 @@ impl RaindexError {
                 "Found metadata but it could not be parsed as valid dotrain source".to_string()
             }
             RaindexError::OracleFetchError(err) => err.clone(),
+            RaindexError::OrderNotActive(order_hash) => {
+                format!("The order with hash '{order_hash}' has been removed and cannot be updated.")
+            }
+            RaindexError::DeploymentOrderbookMismatch {
+                deployment,
+                orderbook,
+                chain_id,
+            } => format!(
+                "The deployment '{deployment}' must use the order's orderbook {orderbook} on chain ID {chain_id}."
+            ),
+            RaindexError::GasEstimateError(err) => {
+                format!("Failed to estimate the transaction cost: {err}")
+            }
+            RaindexError::InvalidRebalanceTargets(err) => {
+                format!("The vault rebalance targets are invalid: {err}")
+            }
+            RaindexError::OrderbookMulticallError(err) => err.to_readable_msg(),
         }
     }
 }
@@ -927,6 +1020,27 @@ using-tokens-from:
             assert_eq!(token.network.key, "test-network");
             assert_eq!(token.decimals, Some(18));
         }
+
+        #[tokio::test]
+        async fn test_subscribe_sync_events_requires_local_db_sync() {
+            let client = RaindexClient::new(
+                vec![get_test_yaml(
+                    "http://localhost:8000/sg1",
+                    "http://localhost:8000/sg2",
+                    "http://localhost:8000/rpc1",
+                    "http://localhost:8000/rpc2",
+                )],
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+            assert!(matches!(
+                client.subscribe_sync_events(),
+                Err(RaindexError::LocalDbSyncNotRunning)
+            ));
+        }
     }
 
     #[cfg(target_family = "wasm")]
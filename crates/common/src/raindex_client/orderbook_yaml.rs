@@ -1,6 +1,7 @@
 use super::*;
 use rain_orderbook_app_settings::{
     accounts::AccountCfg, network::NetworkCfg, orderbook::OrderbookCfg, token::TokenCfg,
+    watcher::WatcherCfg,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -237,6 +238,10 @@ impl RaindexClient {
     pub fn get_orderbook_by_address(&self, address: Address) -> Result<OrderbookCfg, RaindexError> {
         Ok(self.orderbook_yaml.get_orderbook_by_address(address)?)
     }
+
+    pub fn get_all_watchers(&self) -> Result<HashMap<String, WatcherCfg>, RaindexError> {
+        Ok(self.orderbook_yaml.get_watchers()?)
+    }
 }
 
 #[cfg(target_family = "wasm")]
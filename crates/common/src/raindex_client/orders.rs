@@ -1,9 +1,11 @@
 use super::local_db::orders::LocalDbOrders;
+use super::local_db::query::fetch_order_performance::fetch_order_performance;
 use super::local_db::query::fetch_order_vaults_volume::fetch_order_vaults_volume;
 use super::trades::RaindexTrade;
 use super::ClientRef;
 use super::QuerySource;
 use super::*;
+use crate::local_db::query::fetch_order_performance::PerformanceWindow;
 use crate::local_db::query::fetch_orders::LocalDbOrder;
 use crate::local_db::query::fetch_vaults::LocalDbVault;
 use crate::local_db::OrderbookIdentifier;
@@ -21,7 +23,7 @@ use crate::{
     meta::TryDecodeRainlangSource,
     raindex_client::{
         transactions::RaindexTransaction,
-        vaults::{RaindexVault, RaindexVaultType, RaindexVaultVolume},
+        vaults::{RaindexVault, RaindexVaultPerformance, RaindexVaultType, RaindexVaultVolume},
     },
 };
 use alloy::primitives::{b256, keccak256, Address, Bytes, B256, U256};
@@ -34,10 +36,11 @@ use rain_metaboard_subgraph::types::metas::BigInt as MetaBigInt;
 use rain_metadata::types::dotrain::source_v1::DotrainSourceV1;
 use rain_metadata::{KnownMagic, RainMetaDocumentV1Item};
 use rain_orderbook_subgraph_client::{
+    performance::OrderPerformance,
     types::{
         common::{
             SgBigInt, SgBytes, SgOrder, SgOrderAsIO, SgOrderbook, SgOrdersListFilterArgs,
-            SgOrdersTokensFilterArgs, SgVault,
+            SgOrdersTokensFilterArgs, SgTrade, SgVault,
         },
         Id,
     },
@@ -547,49 +550,189 @@ impl RaindexOrder {
         }
     }
 
-    // /// Gets comprehensive performance metrics and analytics for this order over a specified time period
-    // ///
-    // /// Retrieves detailed performance data including profit/loss, volume statistics, and other
-    // /// key metrics that help assess the effectiveness of the trading algorithm implemented by this order.
-    // ///
-    // /// ## Examples
-    // ///
-    // /// ```javascript
-    // /// const result = await order.getPerformance(
-    // ///   Math.floor(Date.now() / 1000) - 604800, // 1 week ago
-    // ///   Math.floor(Date.now() / 1000)
-    // /// );
-    // /// if (result.error) {
-    // ///   console.error("Error fetching performance:", result.error.readableMsg);
-    // ///   return;
-    // /// }
-    // /// const performance = result.value;
-    // /// // Do something with performance
-    // /// ```
-    // #[wasm_export(
-    //     js_name = "getPerformance",
-    //     return_description = "Comprehensive performance metrics for the order",
-    //     unchecked_return_type = "OrderPerformance"
-    // )]
-    // pub async fn get_performance(
-    //     &self,
-    //     #[wasm_export(
-    //         js_name = "startTimestamp",
-    //         param_description = "Unix timestamp for the start of the analysis period (optional, defaults to order creation)"
-    //     )]
-    //     start_timestamp: Option<u64>,
-    //     #[wasm_export(
-    //         js_name = "endTimestamp",
-    //         param_description = "Unix timestamp for the end of the analysis period (optional, defaults to current time)"
-    //     )]
-    //     end_timestamp: Option<u64>,
-    // ) -> Result<OrderPerformance, RaindexError> {
-    //     let client = self.get_orderbook_client()?;
-    //     let performance = client
-    //         .order_performance(Id::new(self.id.to_string()), start_timestamp, end_timestamp)
-    //         .await?;
-    //     Ok(performance)
-    // }
+    /// Gets each of the order's vaults PnL, starting capital, net deposits and APY over a
+    /// block and/or timestamp window
+    ///
+    /// The balances, deposits, withdrawals and PnL are aggregated in SQL from the
+    /// vaults' balance changes, counting only this order's trades, so this is only
+    /// available for chains that are served from the local DB. After the vaults
+    /// come the order's totals for each of its tokens, which have no vault id.
+    /// APY is derived from those totals and the window's duration.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.getVaultsPerformance(
+    ///   Math.floor(Date.now() / 1000) - 604800, // 1 week ago
+    ///   undefined,
+    ///   undefined,
+    ///   undefined
+    /// );
+    /// if (result.error) {
+    ///   console.error("Error fetching performance:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const vaults = result.value;
+    /// // Do something with the vaults performance
+    /// ```
+    #[wasm_export(
+        js_name = "getVaultsPerformance",
+        return_description = "Performance of each of the order's vaults over the window, followed by the order's totals per token",
+        unchecked_return_type = "RaindexVaultPerformance[]",
+        preserve_js_class
+    )]
+    pub async fn get_vaults_performance(
+        &self,
+        #[wasm_export(
+            js_name = "startTimestamp",
+            param_description = "Unix timestamp for the start of the window (optional)"
+        )]
+        start_timestamp: Option<u64>,
+        #[wasm_export(
+            js_name = "endTimestamp",
+            param_description = "Unix timestamp for the end of the window (optional)"
+        )]
+        end_timestamp: Option<u64>,
+        #[wasm_export(
+            js_name = "startBlock",
+            param_description = "Block number for the start of the window (optional)"
+        )]
+        start_block: Option<u64>,
+        #[wasm_export(
+            js_name = "endBlock",
+            param_description = "Block number for the end of the window (optional)"
+        )]
+        end_block: Option<u64>,
+    ) -> Result<Vec<RaindexVaultPerformance>, RaindexError> {
+        let chain_id = self.chain_id();
+        #[cfg(target_family = "wasm")]
+        let orderbook = Address::from_str(&self.orderbook())?;
+        #[cfg(not(target_family = "wasm"))]
+        let orderbook = self.orderbook();
+
+        let raindex_client = self.get_raindex_client();
+        match raindex_client.query_source(chain_id) {
+            QuerySource::LocalDb(local_db) => {
+                #[cfg(target_family = "wasm")]
+                let order_hash = B256::from_str(&self.order_hash())?;
+                #[cfg(not(target_family = "wasm"))]
+                let order_hash = B256::from_str(&self.order_hash().to_string())?;
+
+                let window = PerformanceWindow {
+                    start_timestamp,
+                    end_timestamp,
+                    start_block,
+                    end_block,
+                };
+                let vaults = fetch_order_performance(
+                    &local_db,
+                    &OrderbookIdentifier::new(chain_id, orderbook),
+                    order_hash,
+                    &window,
+                )
+                .await?;
+
+                vaults
+                    .into_iter()
+                    .map(|v| {
+                        RaindexVaultPerformance::try_from_local_db_vault_performance(
+                            chain_id, v, &window,
+                        )
+                    })
+                    .collect()
+            }
+            QuerySource::Subgraph => Err(RaindexError::LocalDbUnsupportedNetwork(chain_id)),
+        }
+    }
+
+    /// Gets comprehensive performance metrics and analytics for this order over a specified time period
+    ///
+    /// Retrieves per-vault volume and APY along with the order's overall performance
+    /// denominated in one of its io tokens. When `quoteToken` is given the order's
+    /// performance is denominated in that token, otherwise the io token with the
+    /// highest net volume is used.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.getPerformance(
+    ///   undefined, // denominate in the best io token
+    ///   Math.floor(Date.now() / 1000) - 604800, // 1 week ago
+    ///   Math.floor(Date.now() / 1000)
+    /// );
+    /// if (result.error) {
+    ///   console.error("Error fetching performance:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const performance = result.value;
+    /// // Do something with performance
+    /// ```
+    #[wasm_export(
+        js_name = "getPerformance",
+        return_description = "Comprehensive performance metrics for the order",
+        unchecked_return_type = "OrderPerformance"
+    )]
+    pub async fn get_performance(
+        &self,
+        #[wasm_export(
+            js_name = "quoteToken",
+            param_description = "Address of the io token to denominate the performance in (optional)"
+        )]
+        quote_token: Option<String>,
+        #[wasm_export(
+            js_name = "startTimestamp",
+            param_description = "Unix timestamp for the start of the analysis period (optional, defaults to order creation)"
+        )]
+        start_timestamp: Option<u64>,
+        #[wasm_export(
+            js_name = "endTimestamp",
+            param_description = "Unix timestamp for the end of the analysis period (optional, defaults to current time)"
+        )]
+        end_timestamp: Option<u64>,
+    ) -> Result<OrderPerformance, RaindexError> {
+        let quote_token = quote_token
+            .map(|address| Address::from_str(&address))
+            .transpose()?;
+        self.performance(quote_token, start_timestamp, end_timestamp)
+            .await
+    }
+
+    /// Gets the order's performance over the trailing time window ending now
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.getPerformanceForWindow(86400 * 30); // last 30 days
+    /// if (result.error) {
+    ///   console.error("Error fetching performance:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const performance = result.value;
+    /// // Do something with performance
+    /// ```
+    #[wasm_export(
+        js_name = "getPerformanceForWindow",
+        return_description = "Performance metrics for the order over the window",
+        unchecked_return_type = "OrderPerformance"
+    )]
+    pub async fn get_performance_for_window(
+        &self,
+        #[wasm_export(
+            js_name = "windowSeconds",
+            param_description = "Length of the trailing window in seconds"
+        )]
+        window_seconds: u64,
+        #[wasm_export(
+            js_name = "quoteToken",
+            param_description = "Address of the io token to denominate the performance in (optional)"
+        )]
+        quote_token: Option<String>,
+    ) -> Result<OrderPerformance, RaindexError> {
+        let end_timestamp = chrono::Utc::now().timestamp().max(0) as u64;
+        let start_timestamp = end_timestamp.saturating_sub(window_seconds);
+        self.get_performance(quote_token, Some(start_timestamp), Some(end_timestamp))
+            .await
+    }
 
     /// Converts the order from RaindexOrder to an SgOrder type
     ///
@@ -1230,15 +1373,36 @@ impl RaindexClient {
         chain_id: u32,
         sell_token: Address,
         buy_token: Address,
+    ) -> Result<Vec<RaindexOrder>, RaindexError> {
+        self.fetch_active_orders(
+            chain_id,
+            Some(GetOrdersTokenFilter {
+                inputs: Some(vec![sell_token]),
+                outputs: Some(vec![buy_token]),
+            }),
+        )
+        .await
+    }
+
+    /// Fetches every active order on `chain_id`, regardless of the pairs it
+    /// trades. Used by the multi-hop router to build its token graph.
+    pub(crate) async fn fetch_active_orders_for_chain(
+        &self,
+        chain_id: u32,
+    ) -> Result<Vec<RaindexOrder>, RaindexError> {
+        self.fetch_active_orders(chain_id, None).await
+    }
+
+    async fn fetch_active_orders(
+        &self,
+        chain_id: u32,
+        tokens: Option<GetOrdersTokenFilter>,
     ) -> Result<Vec<RaindexOrder>, RaindexError> {
         let filters = GetOrdersFilters {
             owners: vec![],
             active: Some(true),
             order_hash: None,
-            tokens: Some(GetOrdersTokenFilter {
-                inputs: Some(vec![sell_token]),
-                outputs: Some(vec![buy_token]),
-            }),
+            tokens,
             orderbook_addresses: None,
         };
 
@@ -1462,6 +1626,69 @@ impl RaindexOrder {
         })
     }
 
+    /// Measures the order's performance from its trades, see [`RaindexOrder::get_performance`]
+    pub async fn performance(
+        &self,
+        quote_token: Option<Address>,
+        start_timestamp: Option<u64>,
+        end_timestamp: Option<u64>,
+    ) -> Result<OrderPerformance, RaindexError> {
+        let chain_id = self.chain_id();
+        let raindex_client = self.get_raindex_client();
+        match raindex_client.query_source(chain_id) {
+            QuerySource::LocalDb(local_db) => {
+                #[cfg(target_family = "wasm")]
+                let orderbook = Address::from_str(&self.orderbook())?;
+                #[cfg(not(target_family = "wasm"))]
+                let orderbook = self.orderbook();
+                #[cfg(target_family = "wasm")]
+                let order_hash = B256::from_str(&self.order_hash())?;
+                #[cfg(not(target_family = "wasm"))]
+                let order_hash = B256::from_str(&self.order_hash().to_string())?;
+
+                // local trades are already sorted by timestamp in desc order
+                let trades = LocalDbOrders::new(&local_db, ClientRef::clone(&raindex_client))
+                    .trades_list(
+                        &OrderbookIdentifier::new(chain_id, orderbook),
+                        &order_hash,
+                        start_timestamp,
+                        end_timestamp,
+                        None,
+                    )
+                    .await?;
+
+                let sg_order = self.clone().into_sg_order()?;
+                let sg_trades = trades
+                    .into_iter()
+                    .map(|trade| {
+                        let mut sg_trade = trade.into_sg_trade(sg_order.id.0.clone())?;
+                        align_trade_tokens(&sg_order, &mut sg_trade);
+                        Ok(sg_trade)
+                    })
+                    .collect::<Result<Vec<SgTrade>, RaindexError>>()?;
+
+                Ok(OrderPerformance::measure_denominated(
+                    &sg_order,
+                    &sg_trades,
+                    quote_token,
+                    start_timestamp,
+                    end_timestamp,
+                )?)
+            }
+            QuerySource::Subgraph => {
+                let client = self.get_orderbook_client()?;
+                Ok(client
+                    .order_performance(
+                        Id::new(self.id.to_string()),
+                        quote_token,
+                        start_timestamp,
+                        end_timestamp,
+                    )
+                    .await?)
+            }
+        }
+    }
+
     pub fn into_sg_order(self) -> Result<SgOrder, RaindexError> {
         #[cfg(target_family = "wasm")]
         let timestamp_added = self.timestamp_added.to_string();
@@ -1511,6 +1738,27 @@ pub(crate) async fn fetch_orders_dotrain_sources(
     .await
 }
 
+/// Local DB trades carry their own copy of the token metadata, so swap them for
+/// the order's io tokens to let the performance measurement match vaults and
+/// pairs by token.
+fn align_trade_tokens(order: &SgOrder, trade: &mut SgTrade) {
+    for balance_change in [
+        &mut trade.input_vault_balance_change,
+        &mut trade.output_vault_balance_change,
+    ] {
+        let token = &mut balance_change.vault.token;
+        if let Some(order_token) = order
+            .inputs
+            .iter()
+            .chain(order.outputs.iter())
+            .map(|vault| &vault.token)
+            .find(|order_token| order_token.address.0.eq_ignore_ascii_case(&token.address.0))
+        {
+            *token = order_token.clone();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(not(target_family = "wasm"))]
@@ -0,0 +1,879 @@
+use super::*;
+use crate::erc20::ERC20;
+use crate::local_db::OrderbookIdentifier;
+use crate::multicall::{OrderbookCall, OrderbookMulticallBuilder};
+use crate::raindex_client::vaults::{GetVaultsFilters, RaindexVault, DEFAULT_PAGE_SIZE};
+use crate::take_orders::preflight::{
+    build_approval_calldata, check_taker_allowance, check_taker_balance,
+};
+use alloy::primitives::{Bytes, U256};
+use rain_math_float::Float;
+use rain_orderbook_bindings::IRaindexV6::{deposit4Call, withdraw4Call};
+
+/// Target balances of an owner's vaults, keyed by token address and vault ID.
+///
+/// A target is either an amount of the token or a percentage of the token's
+/// current total across the vaults listed for it:
+///
+/// ```yaml
+/// tokens:
+///   0x4200000000000000000000000000000000000042:
+///     0x01: 100
+///     0x02: 25%
+///     0x03: 75%
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct VaultRebalanceTargets {
+    #[serde(default)]
+    pub tokens: BTreeMap<String, BTreeMap<String, String>>,
+}
+impl_wasm_traits!(VaultRebalanceTargets);
+
+impl VaultRebalanceTargets {
+    pub fn parse(yaml: &str) -> Result<Self, RaindexError> {
+        serde_yaml::from_str(yaml).map_err(|e| RaindexError::InvalidRebalanceTargets(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum VaultTarget {
+    Balance(Float),
+    Ratio(Float),
+}
+
+impl VaultTarget {
+    fn parse(value: &str) -> Result<Self, RaindexError> {
+        let invalid = |reason: &str| {
+            RaindexError::InvalidRebalanceTargets(format!("target {value} {reason}"))
+        };
+        let value = value.trim();
+        let (amount, is_ratio) = match value.strip_suffix('%') {
+            Some(percentage) => (percentage.trim(), true),
+            None => (value, false),
+        };
+        let amount = Float::parse(amount.to_string())?;
+        if amount.lt(Float::default())? {
+            return Err(invalid("cannot be negative"));
+        }
+        if !is_ratio {
+            return Ok(VaultTarget::Balance(amount));
+        }
+        let ratio = amount.div(Float::parse("100".to_string())?)?;
+        if ratio.gt(Float::parse("1".to_string())?)? {
+            return Err(invalid("cannot exceed 100%"));
+        }
+        Ok(VaultTarget::Ratio(ratio))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Tsify)]
+pub enum VaultRebalanceAction {
+    Deposit,
+    Withdraw,
+}
+impl_wasm_traits!(VaultRebalanceAction);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRebalanceStep {
+    #[tsify(type = "Address")]
+    pub token: Address,
+    #[tsify(optional)]
+    pub symbol: Option<String>,
+    #[tsify(type = "Hex")]
+    pub vault_id: B256,
+    pub action: VaultRebalanceAction,
+    #[tsify(type = "Hex")]
+    pub current: Float,
+    #[tsify(type = "Hex")]
+    pub target: Float,
+    #[tsify(type = "Hex")]
+    pub amount: Float,
+}
+impl_wasm_traits!(VaultRebalanceStep);
+
+/// Approval the owner has to send before the rebalance multicall, as its
+/// deposits pull more of the token than the orderbook is allowed to spend
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRebalanceApproval {
+    #[tsify(type = "Address")]
+    pub token: Address,
+    #[tsify(optional)]
+    pub symbol: Option<String>,
+    #[tsify(type = "string")]
+    pub amount: U256,
+    #[tsify(type = "Hex")]
+    pub calldata: Bytes,
+}
+impl_wasm_traits!(VaultRebalanceApproval);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRebalancePlan {
+    #[tsify(type = "Address")]
+    pub orderbook: Address,
+    pub chain_id: u32,
+    #[tsify(type = "Address")]
+    pub owner: Address,
+    /// Withdrawals followed by deposits, in the order the multicall runs them
+    pub steps: Vec<VaultRebalanceStep>,
+    pub approvals: Vec<VaultRebalanceApproval>,
+    /// Orderbook multicall of the steps, none when every vault is already at
+    /// its target
+    #[tsify(optional, type = "Hex")]
+    pub calldata: Option<Bytes>,
+    /// Human readable description of the steps and approvals
+    pub summary: String,
+}
+impl_wasm_traits!(VaultRebalancePlan);
+
+struct TokenVaults {
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    balances: BTreeMap<B256, Float>,
+}
+
+#[wasm_export]
+impl RaindexClient {
+    /// Plans the deposits and withdrawals bringing an owner's vaults to their
+    /// target balances
+    ///
+    /// Moves tokens between the listed vaults of the same token through the
+    /// owner's wallet in a single orderbook multicall, withdrawing before
+    /// depositing. Checks the wallet holds the tokens the deposits need beyond
+    /// the withdrawn amounts and lists the approvals the deposits still need.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await client.planVaultRebalance(1, orderbook, owner, {
+    ///   tokens: { [usdc]: { "0x01": "50%", "0x02": "50%" } }
+    /// });
+    /// if (result.error) {
+    ///   console.error("Cannot plan rebalance:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { summary, approvals, calldata } = result.value;
+    /// ```
+    #[wasm_export(
+        js_name = "planVaultRebalance",
+        return_description = "Rebalance steps with their multicall and a readable summary",
+        unchecked_return_type = "VaultRebalancePlan"
+    )]
+    pub async fn plan_vault_rebalance_wasm_binding(
+        &self,
+        #[wasm_export(js_name = "chainId", param_description = "Chain ID of the orderbook")]
+        chain_id: u32,
+        #[wasm_export(
+            js_name = "orderbookAddress",
+            param_description = "Orderbook holding the vaults",
+            unchecked_param_type = "Address"
+        )]
+        orderbook_address: String,
+        #[wasm_export(
+            param_description = "Owner of the vaults",
+            unchecked_param_type = "Address"
+        )]
+        owner: String,
+        #[wasm_export(param_description = "Target balances keyed by token and vault ID")]
+        targets: VaultRebalanceTargets,
+    ) -> Result<VaultRebalancePlan, RaindexError> {
+        self.plan_vault_rebalance(
+            &OrderbookIdentifier::new(chain_id, Address::from_str(&orderbook_address)?),
+            Address::from_str(&owner)?,
+            &targets,
+        )
+        .await
+    }
+}
+impl RaindexClient {
+    pub async fn plan_vault_rebalance(
+        &self,
+        ob_id: &OrderbookIdentifier,
+        owner: Address,
+        targets: &VaultRebalanceTargets,
+    ) -> Result<VaultRebalancePlan, RaindexError> {
+        let targets = parse_targets(targets)?;
+        let mut tokens = self
+            .get_owner_vaults(ob_id, owner, targets.keys().copied().collect())
+            .await?;
+        let rpcs = self.get_rpc_urls_for_chain(ob_id.chain_id)?;
+
+        let mut withdrawals = vec![];
+        let mut deposits = vec![];
+        let mut approvals = vec![];
+        for (token, vault_targets) in &targets {
+            let vaults = tokens.remove(token).unwrap_or(TokenVaults {
+                symbol: None,
+                decimals: None,
+                balances: BTreeMap::new(),
+            });
+            let erc20 = ERC20::new(rpcs.clone(), *token);
+            let decimals = match vaults.decimals {
+                Some(decimals) => decimals,
+                None => erc20.decimals().await?,
+            };
+            let steps = plan_token_steps(
+                *token,
+                vaults.symbol,
+                decimals,
+                &vaults.balances,
+                vault_targets,
+            )?;
+            if steps.is_empty() {
+                continue;
+            }
+
+            let (withdrawn, deposited) = totals(&steps)?;
+            if deposited.gt(Float::default())? {
+                let needed = if deposited.gt(withdrawn)? {
+                    (deposited - withdrawn)?
+                } else {
+                    Float::default()
+                };
+                check_taker_balance(&erc20, owner, needed.to_fixed_decimal(decimals)?)
+                    .await
+                    .map_err(|e| RaindexError::PreflightError(e.to_string()))?;
+                let allowance = check_taker_allowance(
+                    &erc20,
+                    owner,
+                    ob_id.orderbook_address,
+                    deposited.to_fixed_decimal(decimals)?,
+                )
+                .await
+                .map_err(|e| RaindexError::PreflightError(e.to_string()))?;
+                if let Some(amount) = allowance.approval_amount {
+                    approvals.push(VaultRebalanceApproval {
+                        token: *token,
+                        symbol: steps[0].symbol.clone(),
+                        amount,
+                        calldata: build_approval_calldata(ob_id.orderbook_address, amount),
+                    });
+                }
+            }
+
+            for step in steps {
+                match step.action {
+                    VaultRebalanceAction::Withdraw => withdrawals.push(step),
+                    VaultRebalanceAction::Deposit => deposits.push(step),
+                }
+            }
+        }
+
+        let steps: Vec<VaultRebalanceStep> = withdrawals.into_iter().chain(deposits).collect();
+        let calldata = if steps.is_empty() {
+            None
+        } else {
+            Some(build_multicall(&steps)?)
+        };
+        let summary = describe_plan(ob_id, owner, &steps, &approvals)?;
+
+        Ok(VaultRebalancePlan {
+            orderbook: ob_id.orderbook_address,
+            chain_id: ob_id.chain_id,
+            owner,
+            steps,
+            approvals,
+            calldata,
+            summary,
+        })
+    }
+
+    /// Current balances of the owner's vaults on the orderbook, by token
+    async fn get_owner_vaults(
+        &self,
+        ob_id: &OrderbookIdentifier,
+        owner: Address,
+        tokens: Vec<Address>,
+    ) -> Result<BTreeMap<Address, TokenVaults>, RaindexError> {
+        let filters = GetVaultsFilters {
+            owners: vec![owner],
+            hide_zero_balance: false,
+            tokens: Some(tokens),
+            orderbook_addresses: Some(vec![ob_id.orderbook_address]),
+            only_active_orders: false,
+        };
+
+        // The local DB ignores the page and returns every vault at once, only
+        // the subgraph has to be paged until it returns a partial page
+        let (local_db, _, _) = self.classify_chains(Some(vec![ob_id.chain_id]))?;
+        let paged = local_db.is_none();
+
+        let mut vaults: Vec<RaindexVault> = vec![];
+        let mut page: u16 = 1;
+        loop {
+            let items = self
+                .get_vaults(
+                    Some(ChainIds(vec![ob_id.chain_id])),
+                    Some(filters.clone()),
+                    Some(page),
+                )
+                .await?
+                .items();
+            let is_last = !paged || items.len() < usize::from(DEFAULT_PAGE_SIZE);
+            vaults.extend(items);
+            match page.checked_add(1) {
+                Some(next) if !is_last => page = next,
+                _ => break,
+            }
+        }
+
+        let mut tokens: BTreeMap<Address, TokenVaults> = BTreeMap::new();
+        for vault in vaults {
+            let token = vault.token();
+            #[cfg(target_family = "wasm")]
+            let (token_address, vault_id) = (
+                Address::from_str(&token.address())?,
+                B256::from_str(&vault.vault_id_hex())?,
+            );
+            #[cfg(not(target_family = "wasm"))]
+            let (token_address, vault_id) = (token.address(), B256::from(vault.vault_id()));
+
+            let entry = tokens.entry(token_address).or_insert(TokenVaults {
+                symbol: token.symbol(),
+                decimals: Some(token.decimals()),
+                balances: BTreeMap::new(),
+            });
+            entry.balances.insert(vault_id, vault.balance());
+        }
+        Ok(tokens)
+    }
+}
+
+fn parse_targets(
+    targets: &VaultRebalanceTargets,
+) -> Result<BTreeMap<Address, BTreeMap<B256, VaultTarget>>, RaindexError> {
+    let mut parsed = BTreeMap::new();
+    for (token, vaults) in &targets.tokens {
+        let token = Address::from_str(token.trim()).map_err(|e| {
+            RaindexError::InvalidRebalanceTargets(format!("token {token} is invalid: {e}"))
+        })?;
+        let mut vault_targets = BTreeMap::new();
+        for (vault_id, target) in vaults {
+            let vault_id = U256::from_str(vault_id.trim()).map_err(|e| {
+                RaindexError::InvalidRebalanceTargets(format!(
+                    "vault ID {vault_id} is invalid: {e}"
+                ))
+            })?;
+            vault_targets.insert(B256::from(vault_id), VaultTarget::parse(target)?);
+        }
+        parsed.insert(token, vault_targets);
+    }
+    Ok(parsed)
+}
+
+/// Deposits and withdrawals bringing each of a token's vaults from its
+/// current balance to its target, vaults missing from `balances` being empty
+///
+/// Targets are truncated to the token's `decimals` so every amount can be
+/// sent on chain. The dust percentages leave over stays in the owner's wallet.
+fn plan_token_steps(
+    token: Address,
+    symbol: Option<String>,
+    decimals: u8,
+    balances: &BTreeMap<B256, Float>,
+    targets: &BTreeMap<B256, VaultTarget>,
+) -> Result<Vec<VaultRebalanceStep>, RaindexError> {
+    let zero = Float::default();
+    let current = |vault_id: &B256| balances.get(vault_id).copied().unwrap_or(zero);
+
+    let mut total = zero;
+    let mut total_ratio = zero;
+    for (vault_id, target) in targets {
+        total = (total + current(vault_id))?;
+        if let VaultTarget::Ratio(ratio) = target {
+            total_ratio = (total_ratio + *ratio)?;
+        }
+    }
+    if total_ratio.gt(Float::parse("1".to_string())?)? {
+        return Err(RaindexError::InvalidRebalanceTargets(format!(
+            "percentages of token {token} add up to more than 100%"
+        )));
+    }
+
+    let mut steps = vec![];
+    for (vault_id, target) in targets {
+        let current = current(vault_id);
+        let target = truncate(
+            match target {
+                VaultTarget::Balance(balance) => *balance,
+                VaultTarget::Ratio(ratio) => total.mul(*ratio)?,
+            },
+            decimals,
+        )?;
+        let (action, amount) = if target.gt(current)? {
+            (VaultRebalanceAction::Deposit, (target - current)?)
+        } else if target.lt(current)? {
+            (VaultRebalanceAction::Withdraw, (current - target)?)
+        } else {
+            continue;
+        };
+        steps.push(VaultRebalanceStep {
+            token,
+            symbol: symbol.clone(),
+            vault_id: *vault_id,
+            action,
+            current,
+            target,
+            amount,
+        });
+    }
+    Ok(steps)
+}
+
+/// `amount` rounded down to a whole number of the token's smallest unit
+fn truncate(amount: Float, decimals: u8) -> Result<Float, RaindexError> {
+    let (fixed, _) = amount.to_fixed_decimal_lossy(decimals)?;
+    Ok(Float::from_fixed_decimal(fixed, decimals)?)
+}
+
+/// Total withdrawn and deposited amounts of the steps
+fn totals(steps: &[VaultRebalanceStep]) -> Result<(Float, Float), RaindexError> {
+    let mut withdrawn = Float::default();
+    let mut deposited = Float::default();
+    for step in steps {
+        match step.action {
+            VaultRebalanceAction::Withdraw => withdrawn = (withdrawn + step.amount)?,
+            VaultRebalanceAction::Deposit => deposited = (deposited + step.amount)?,
+        }
+    }
+    Ok((withdrawn, deposited))
+}
+
+fn build_multicall(steps: &[VaultRebalanceStep]) -> Result<Bytes, RaindexError> {
+    let mut builder = OrderbookMulticallBuilder::new();
+    for step in steps {
+        builder.set_vault_balance(step.token, step.vault_id, step.current);
+        builder.push(match step.action {
+            VaultRebalanceAction::Withdraw => OrderbookCall::Withdraw(withdraw4Call {
+                token: step.token,
+                vaultId: step.vault_id,
+                targetAmount: step.amount.get_inner(),
+                tasks: vec![],
+            }),
+            VaultRebalanceAction::Deposit => OrderbookCall::Deposit(deposit4Call {
+                token: step.token,
+                vaultId: step.vault_id,
+                depositAmount: step.amount.get_inner(),
+                tasks: vec![],
+            }),
+        });
+    }
+    Ok(builder.build()?)
+}
+
+fn describe_plan(
+    ob_id: &OrderbookIdentifier,
+    owner: Address,
+    steps: &[VaultRebalanceStep],
+    approvals: &[VaultRebalanceApproval],
+) -> Result<String, RaindexError> {
+    let mut lines = vec![format!(
+        "Rebalance of {owner}'s vaults on orderbook {} (chain ID {})",
+        ob_id.orderbook_address, ob_id.chain_id
+    )];
+    if steps.is_empty() {
+        lines.push("All vaults are already at their targets".to_string());
+        return Ok(lines.join("\n"));
+    }
+
+    for approval in approvals {
+        lines.push(format!(
+            "Approve the orderbook to spend {} of {} first",
+            approval.amount,
+            token_name(approval.token, &approval.symbol)
+        ));
+    }
+    for (index, step) in steps.iter().enumerate() {
+        let (verb, preposition) = match step.action {
+            VaultRebalanceAction::Withdraw => ("Withdraw", "from"),
+            VaultRebalanceAction::Deposit => ("Deposit", "into"),
+        };
+        lines.push(format!(
+            "{}. {verb} {} {} {preposition} vault {} ({} -> {})",
+            index + 1,
+            step.amount.format()?,
+            token_name(step.token, &step.symbol),
+            step.vault_id,
+            step.current.format()?,
+            step.target.format()?
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn token_name(token: Address, symbol: &Option<String>) -> String {
+    match symbol {
+        Some(symbol) => format!("{symbol} ({token})"),
+        None => token.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const TOKEN: Address = address!("0x4200000000000000000000000000000000000042");
+
+    fn f(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn vault(id: u64) -> B256 {
+        B256::from(U256::from(id))
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        let targets = VaultRebalanceTargets::parse(
+            r#"
+tokens:
+  "0x4200000000000000000000000000000000000042":
+    0x01: 100
+    "2": 25%
+"#,
+        )
+        .unwrap();
+        let parsed = parse_targets(&targets).unwrap();
+        match parsed[&TOKEN][&vault(1)] {
+            VaultTarget::Balance(balance) => assert!(balance.eq(f("100")).unwrap()),
+            _ => panic!("expected a balance"),
+        }
+        match parsed[&TOKEN][&vault(2)] {
+            VaultTarget::Ratio(ratio) => assert!(ratio.eq(f("0.25")).unwrap()),
+            _ => panic!("expected a ratio"),
+        }
+
+        assert!(VaultRebalanceTargets::parse("token: {}").is_err());
+        assert!(VaultTarget::parse("-1").is_err());
+        assert!(VaultTarget::parse("101%").is_err());
+        let invalid_vault = VaultRebalanceTargets::parse(
+            "tokens: {\"0x4200000000000000000000000000000000000042\": {vault: 1}}",
+        )
+        .unwrap();
+        assert!(matches!(
+            parse_targets(&invalid_vault),
+            Err(RaindexError::InvalidRebalanceTargets(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_token_steps() {
+        let balances = BTreeMap::from([(vault(1), f("150")), (vault(2), f("50"))]);
+        let targets = BTreeMap::from([
+            (vault(1), VaultTarget::Ratio(f("0.5"))),
+            (vault(2), VaultTarget::Ratio(f("0.5"))),
+            (vault(3), VaultTarget::Balance(f("20"))),
+        ]);
+        let steps =
+            plan_token_steps(TOKEN, Some("USDC".to_string()), 6, &balances, &targets).unwrap();
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].vault_id, vault(1));
+        assert_eq!(steps[0].action, VaultRebalanceAction::Withdraw);
+        assert!(steps[0].amount.eq(f("50")).unwrap());
+        assert_eq!(steps[1].action, VaultRebalanceAction::Deposit);
+        assert!(steps[1].amount.eq(f("50")).unwrap());
+        assert_eq!(steps[2].vault_id, vault(3));
+        assert!(steps[2].current.eq(f("0")).unwrap());
+        assert!(steps[2].amount.eq(f("20")).unwrap());
+
+        let (withdrawn, deposited) = totals(&steps).unwrap();
+        assert!(withdrawn.eq(f("50")).unwrap());
+        assert!(deposited.eq(f("70")).unwrap());
+
+        let balanced = BTreeMap::from([(vault(1), VaultTarget::Balance(f("150")))]);
+        assert!(plan_token_steps(TOKEN, None, 6, &balances, &balanced)
+            .unwrap()
+            .is_empty());
+
+        let over = BTreeMap::from([
+            (vault(1), VaultTarget::Ratio(f("0.6"))),
+            (vault(2), VaultTarget::Ratio(f("0.6"))),
+        ]);
+        assert!(matches!(
+            plan_token_steps(TOKEN, None, 6, &balances, &over),
+            Err(RaindexError::InvalidRebalanceTargets(_))
+        ));
+    }
+
+    /// Every amount converts to the token's decimals without loss
+    fn assert_representable(steps: &[VaultRebalanceStep], decimals: u8) {
+        for step in steps {
+            step.amount.to_fixed_decimal(decimals).unwrap();
+            step.target.to_fixed_decimal(decimals).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_plan_token_steps_truncates_odd_unit_split() {
+        let balances = BTreeMap::from([(vault(1), f("100.000001"))]);
+        let targets = BTreeMap::from([
+            (vault(1), VaultTarget::Ratio(f("0.5"))),
+            (vault(2), VaultTarget::Ratio(f("0.5"))),
+        ]);
+        let steps = plan_token_steps(TOKEN, None, 6, &balances, &targets).unwrap();
+        assert_representable(&steps, 6);
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].action, VaultRebalanceAction::Withdraw);
+        assert!(steps[0].target.eq(f("50")).unwrap());
+        assert!(steps[0].amount.eq(f("50.000001")).unwrap());
+        assert_eq!(steps[1].action, VaultRebalanceAction::Deposit);
+        assert!(steps[1].amount.eq(f("50")).unwrap());
+
+        // The dust is withdrawn to the wallet
+        let (withdrawn, deposited) = totals(&steps).unwrap();
+        assert!((withdrawn - deposited).unwrap().eq(f("0.000001")).unwrap());
+    }
+
+    #[test]
+    fn test_plan_token_steps_truncates_three_way_split() {
+        let third = f("1").div(f("3")).unwrap();
+        let balances = BTreeMap::from([(vault(1), f("100"))]);
+        let targets = BTreeMap::from([
+            (vault(1), VaultTarget::Ratio(third)),
+            (vault(2), VaultTarget::Ratio(third)),
+            (vault(3), VaultTarget::Ratio(third)),
+        ]);
+        let steps = plan_token_steps(TOKEN, None, 6, &balances, &targets).unwrap();
+        assert_representable(&steps, 6);
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].action, VaultRebalanceAction::Withdraw);
+        assert!(steps[0].amount.eq(f("66.666667")).unwrap());
+        for step in &steps[1..] {
+            assert_eq!(step.action, VaultRebalanceAction::Deposit);
+            assert!(step.amount.eq(f("33.333333")).unwrap());
+        }
+
+        let (withdrawn, deposited) = totals(&steps).unwrap();
+        assert!((withdrawn - deposited).unwrap().eq(f("0.000001")).unwrap());
+        build_multicall(&steps).unwrap();
+    }
+
+    #[test]
+    fn test_build_multicall_and_describe_plan() {
+        let balances = BTreeMap::from([(vault(1), f("150")), (vault(2), f("50"))]);
+        let targets = BTreeMap::from([
+            (vault(1), VaultTarget::Balance(f("100"))),
+            (vault(2), VaultTarget::Balance(f("100"))),
+        ]);
+        let steps =
+            plan_token_steps(TOKEN, Some("USDC".to_string()), 6, &balances, &targets).unwrap();
+
+        let calldata = build_multicall(&steps).unwrap();
+        let calls = OrderbookCall::decode(&calldata).unwrap();
+        assert!(matches!(calls[0], OrderbookCall::Withdraw(_)));
+        assert!(matches!(calls[1], OrderbookCall::Deposit(_)));
+
+        let summary = describe_plan(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            Address::ZERO,
+            &steps,
+            &[],
+        )
+        .unwrap();
+        assert!(summary.contains("1. Withdraw 50 USDC"));
+        assert!(summary.contains("2. Deposit 50 USDC"));
+        assert!(summary.contains("(50 -> 100)"));
+
+        let summary = describe_plan(
+            &OrderbookIdentifier::new(1, Address::ZERO),
+            Address::ZERO,
+            &[],
+            &[],
+        )
+        .unwrap();
+        assert!(summary.ends_with("All vaults are already at their targets"));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    mod non_wasm {
+        use super::*;
+        use crate::local_db::query::{
+            fetch_vaults::LocalDbVault, FromDbJson, LocalDbQueryError, LocalDbQueryExecutor,
+            SqlStatement, SqlStatementBatch,
+        };
+        use crate::raindex_client::local_db::LocalDb;
+        use crate::raindex_client::tests::{
+            get_test_yaml, new_with_local_db, CHAIN_ID_1_ORDERBOOK_ADDRESS,
+        };
+        use async_trait::async_trait;
+        use httpmock::MockServer;
+        use serde_json::json;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        const POLYGON_ORDERBOOK: Address = address!("0x0987654321098765432109876543210987654321");
+
+        /// Returns the same vaults for every query, as the local DB ignores
+        /// the requested page
+        #[derive(Clone)]
+        struct VaultsExec {
+            json: String,
+            queries: Arc<AtomicUsize>,
+        }
+
+        #[async_trait]
+        impl LocalDbQueryExecutor for VaultsExec {
+            async fn execute_batch(
+                &self,
+                _batch: &SqlStatementBatch,
+            ) -> Result<(), LocalDbQueryError> {
+                Ok(())
+            }
+
+            async fn query_json<T>(&self, _stmt: &SqlStatement) -> Result<T, LocalDbQueryError>
+            where
+                T: FromDbJson,
+            {
+                self.queries.fetch_add(1, Ordering::SeqCst);
+                serde_json::from_str(&self.json)
+                    .map_err(|e| LocalDbQueryError::deserialization(e.to_string()))
+            }
+
+            async fn query_text(&self, _stmt: &SqlStatement) -> Result<String, LocalDbQueryError> {
+                Err(LocalDbQueryError::database("query_text not supported"))
+            }
+
+            async fn wipe_and_recreate(&self) -> Result<(), LocalDbQueryError> {
+                Err(LocalDbQueryError::not_implemented("wipe_and_recreate"))
+            }
+        }
+
+        fn targets(token: Address, vault_id: &str, target: &str) -> VaultRebalanceTargets {
+            VaultRebalanceTargets {
+                tokens: BTreeMap::from([(
+                    token.to_string(),
+                    BTreeMap::from([(vault_id.to_string(), target.to_string())]),
+                )]),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_plan_vault_rebalance_local_db() {
+            let owner = Address::repeat_byte(0x11);
+            let local_vault = LocalDbVault {
+                chain_id: 137,
+                vault_id: U256::from(1),
+                token: TOKEN,
+                owner,
+                orderbook_address: POLYGON_ORDERBOOK,
+                token_name: "USD Coin".to_string(),
+                token_symbol: "USDC".to_string(),
+                token_decimals: 6,
+                balance: f("150").as_hex(),
+                input_orders: None,
+                output_orders: None,
+            };
+            let exec = VaultsExec {
+                json: serde_json::to_string(&vec![local_vault]).unwrap(),
+                queries: Arc::new(AtomicUsize::new(0)),
+            };
+            let client = new_with_local_db(
+                vec![get_test_yaml(
+                    "https://example/sg1",
+                    "https://example/sg2",
+                    "https://example/rpc1",
+                    "https://example/rpc2",
+                )],
+                LocalDb::new(exec.clone()),
+                vec![137],
+            )
+            .await;
+
+            let plan = client
+                .plan_vault_rebalance(
+                    &OrderbookIdentifier::new(137, POLYGON_ORDERBOOK),
+                    owner,
+                    &targets(TOKEN, "0x01", "100"),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(exec.queries.load(Ordering::SeqCst), 1);
+            assert_eq!(plan.steps.len(), 1);
+            assert_eq!(plan.steps[0].vault_id, vault(1));
+            assert_eq!(plan.steps[0].action, VaultRebalanceAction::Withdraw);
+            assert!(plan.steps[0].amount.eq(f("50")).unwrap());
+            assert!(plan.approvals.is_empty());
+
+            let calls = OrderbookCall::decode(&plan.calldata.unwrap()).unwrap();
+            assert_eq!(calls.len(), 1);
+            assert!(matches!(calls[0], OrderbookCall::Withdraw(_)));
+            assert!(plan.summary.contains("1. Withdraw 50 USDC"));
+        }
+
+        #[tokio::test]
+        async fn test_plan_vault_rebalance_subgraph() {
+            let token = address!("0x1d80c49bbbcd1c0911346656b529df9e5c2f783d");
+            let sg_server = MockServer::start_async().await;
+            let sg_mock = sg_server.mock(|when, then| {
+                when.path("/sg1");
+                then.status(200).json_body_obj(&json!({
+                    "data": {
+                        "vaults": [{
+                            "id": "0x0123",
+                            "owner": "0x0000000000000000000000000000000000000000",
+                            "vaultId": "0x0123",
+                            "balance": f("1").as_hex(),
+                            "token": {
+                                "id": "token1",
+                                "address": token,
+                                "name": "Token 1",
+                                "symbol": "TKN1",
+                                "decimals": "18"
+                            },
+                            "orderbook": {
+                                "id": CHAIN_ID_1_ORDERBOOK_ADDRESS
+                            },
+                            "ordersAsOutput": [],
+                            "ordersAsInput": [],
+                            "balanceChanges": []
+                        }]
+                    }
+                }));
+            });
+            let client = RaindexClient::new(
+                vec![get_test_yaml(
+                    &sg_server.url("/sg1"),
+                    &sg_server.url("/sg2"),
+                    &sg_server.url("/rpc1"),
+                    &sg_server.url("/rpc2"),
+                )],
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            let ob_id = OrderbookIdentifier::new(
+                1,
+                Address::from_str(CHAIN_ID_1_ORDERBOOK_ADDRESS).unwrap(),
+            );
+
+            let plan = client
+                .plan_vault_rebalance(&ob_id, Address::ZERO, &targets(token, "0x0123", "0"))
+                .await
+                .unwrap();
+            assert_eq!(sg_mock.hits(), 1);
+            assert_eq!(plan.steps.len(), 1);
+            assert_eq!(plan.steps[0].action, VaultRebalanceAction::Withdraw);
+            assert!(plan.steps[0].amount.eq(f("1")).unwrap());
+            assert!(plan.calldata.is_some());
+
+            let plan = client
+                .plan_vault_rebalance(&ob_id, Address::ZERO, &targets(token, "0x0123", "1"))
+                .await
+                .unwrap();
+            assert!(plan.steps.is_empty());
+            assert!(plan.calldata.is_none());
+            assert!(plan
+                .summary
+                .ends_with("All vaults are already at their targets"));
+        }
+    }
+}
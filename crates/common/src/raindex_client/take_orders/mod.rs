@@ -1,6 +1,7 @@
 pub(crate) mod approval;
 mod request;
 pub(crate) mod result;
+pub mod routing;
 mod selection;
 pub mod single;
 
@@ -11,15 +12,20 @@ mod e2e_tests;
 mod single_tests;
 
 pub use request::TakeOrdersRequest;
-pub use result::{ApprovalInfo, TakeOrderEstimate, TakeOrdersCalldataResult, TakeOrdersInfo};
+pub use result::{
+    ApprovalInfo, RouteApproval, RoutedTakeOrdersHop, RoutedTakeOrdersResult, TakeOrderEstimate,
+    TakeOrdersCalldataResult, TakeOrdersInfo,
+};
 pub use single::{build_candidate_from_quote, estimate_take_order, execute_single_take};
 
 use super::{RaindexClient, RaindexError};
 use crate::rpc_client::RpcClient;
 use crate::take_orders::{
-    build_take_orders_config_from_simulation, find_failing_order_index, simulate_take_orders,
+    build_take_order_candidates, build_take_orders_config_from_simulation,
+    find_failing_order_index, simulate_take_orders,
 };
 use approval::{check_approval_needed, ApprovalCheckParams};
+use rain_math_float::Float;
 use rain_orderbook_bindings::provider::mk_read_provider;
 use wasm_bindgen_utils::prelude::*;
 use wasm_bindgen_utils::wasm_export;
@@ -175,6 +181,99 @@ impl RaindexClient {
             "Exceeded maximum preflight iterations".to_string(),
         ))
     }
+
+    /// Generates a sequence of `takeOrders4` calls that routes the trade through
+    /// intermediate tokens when `sellToken` → `buyToken` has no (or worse) direct
+    /// liquidity.
+    ///
+    /// Every active order on the chain is quoted and its IO pairs form a token
+    /// graph. All paths of up to `maxHops` hops are simulated (best orderbook per
+    /// hop) and the one delivering the most `buyToken` at the lowest blended
+    /// price within `priceCap` is returned. `priceCap` applies to the blended
+    /// price of the whole route; each hop is additionally bounded by its worst
+    /// quoted ratio plus `hopSlippage`.
+    ///
+    /// Unlike `getTakeOrdersCalldata` no preflight simulation is run, since
+    /// later hops spend tokens the taker only receives from earlier hops. Any
+    /// missing approvals are listed in `approvals`.
+    ///
+    /// ## Example (JS)
+    /// ```javascript
+    /// const res = await client.getRoutedTakeOrdersCalldata(request, 3, "0.005");
+    /// if (!res.error) {
+    ///   const { path, hops, multicall, effectivePrice, approvals } = res.value;
+    /// }
+    /// ```
+    #[wasm_export(
+        js_name = "getRoutedTakeOrdersCalldata",
+        return_description = "Per-hop takeOrders4 calldata and blended price information",
+        unchecked_return_type = "RoutedTakeOrdersResult"
+    )]
+    pub async fn get_routed_take_orders_calldata(
+        &self,
+        #[wasm_export(
+            js_name = "request",
+            param_description = "Take orders request parameters"
+        )]
+        request: TakeOrdersRequest,
+        #[wasm_export(
+            js_name = "maxHops",
+            param_description = "Maximum number of hops to consider (optional, defaults to 3)"
+        )]
+        max_hops: Option<u32>,
+        #[wasm_export(
+            js_name = "hopSlippage",
+            param_description = "Per-hop slippage tolerance as a decimal fraction (optional, defaults to 0.005)"
+        )]
+        hop_slippage: Option<String>,
+    ) -> Result<RoutedTakeOrdersResult, RaindexError> {
+        let req = request::parse_request(&request)?;
+        let max_hops = max_hops.unwrap_or(routing::DEFAULT_MAX_HOPS).max(1);
+        let hop_slippage = Float::parse(
+            hop_slippage.unwrap_or_else(|| routing::DEFAULT_HOP_SLIPPAGE.to_string()),
+        )?;
+        if hop_slippage.lt(Float::zero()?)? {
+            return Err(RaindexError::NegativeAmount);
+        }
+
+        let orders = self.fetch_active_orders_for_chain(request.chain_id).await?;
+
+        let rpc_urls = self.get_rpc_urls_for_chain(request.chain_id)?;
+        let rpc_client = RpcClient::new_with_urls(rpc_urls.clone())?;
+        let block_number = rpc_client.get_latest_block_number().await?;
+
+        let candidates = build_take_order_candidates(&orders, Some(block_number), None).await?;
+
+        let route = routing::select_best_route(
+            &candidates,
+            req.sell_token,
+            req.buy_token,
+            req.mode,
+            req.price_cap,
+            max_hops,
+        )?;
+        let hops = routing::build_hop_configs(&route, req.mode, hop_slippage)?;
+
+        let mut approvals = vec![];
+        for hop in &hops {
+            let params = ApprovalCheckParams {
+                rpc_urls: rpc_urls.clone(),
+                sell_token: hop.hop.sell_token,
+                taker: req.taker,
+                orderbook: hop.hop.orderbook,
+                mode: hop.mode,
+                price_cap: hop.max_io_ratio,
+            };
+            if let Some(approval) = check_approval_needed(&params)
+                .await?
+                .and_then(|result| result.into_approval_data())
+            {
+                approvals.push(approval.into());
+            }
+        }
+
+        result::build_routed_result(&route, hops, approvals)
+    }
 }
 
 #[cfg(test)]
@@ -4,9 +4,12 @@ use alloy::primitives::{Address, Bytes};
 use alloy::sol_types::SolCall;
 use rain_math_float::Float;
 use rain_orderbook_bindings::IRaindexV6::takeOrders4Call;
+use rain_orderbook_bindings::OrderBook::multicallCall;
 use serde::{Deserialize, Serialize};
 use std::ops::{Div, Mul};
-use wasm_bindgen_utils::prelude::*;
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+use super::routing::{BuiltHop, PlannedRoute};
 
 #[derive(Clone)]
 pub(crate) struct ApprovalInfoData {
@@ -222,6 +225,24 @@ impl TakeOrdersCalldataResult {
             inner: TakeOrdersCalldataResultInner::Ready(info),
         }
     }
+
+    pub(crate) fn into_approval_data(self) -> Option<ApprovalInfoData> {
+        match self.inner {
+            TakeOrdersCalldataResultInner::NeedsApproval(data) => Some(data),
+            TakeOrdersCalldataResultInner::Ready(_) => None,
+        }
+    }
+
+    /// Target and calldata of the transaction to send next, the approval
+    /// while one is needed
+    pub(crate) fn next_transaction(&self) -> (Address, Bytes) {
+        match &self.inner {
+            TakeOrdersCalldataResultInner::NeedsApproval(data) => {
+                (data.token, data.calldata.clone())
+            }
+            TakeOrdersCalldataResultInner::Ready(data) => (data.orderbook, data.calldata.clone()),
+        }
+    }
 }
 
 #[cfg(target_family = "wasm")]
@@ -419,6 +440,145 @@ pub(crate) fn build_calldata_result(
     }))
 }
 
+/// A token approval the taker must grant before a routed take can execute.
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteApproval {
+    #[tsify(type = "Address")]
+    pub token: Address,
+    #[tsify(type = "Address")]
+    pub spender: Address,
+    #[tsify(type = "Hex")]
+    pub amount: Float,
+    pub formatted_amount: String,
+    #[tsify(type = "Hex")]
+    pub calldata: Bytes,
+}
+impl_wasm_traits!(RouteApproval);
+
+impl From<ApprovalInfoData> for RouteApproval {
+    fn from(data: ApprovalInfoData) -> Self {
+        Self {
+            token: data.token,
+            spender: data.spender,
+            amount: data.amount,
+            formatted_amount: data.formatted_amount,
+            calldata: data.calldata,
+        }
+    }
+}
+
+/// One `takeOrders4` call of a multi-hop route.
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutedTakeOrdersHop {
+    #[tsify(type = "Address")]
+    pub orderbook: Address,
+    #[tsify(type = "Address")]
+    pub sell_token: Address,
+    #[tsify(type = "Address")]
+    pub buy_token: Address,
+    #[tsify(type = "Hex")]
+    pub calldata: Bytes,
+    #[tsify(type = "Hex")]
+    pub effective_price: Float,
+    #[tsify(type = "Hex[]")]
+    pub prices: Vec<Float>,
+    #[tsify(type = "Hex")]
+    pub expected_sell: Float,
+    #[tsify(type = "Hex")]
+    pub expected_buy: Float,
+    /// `maximumIORatio` encoded for this hop, i.e. its slippage bound.
+    #[tsify(type = "Hex")]
+    pub max_io_ratio: Float,
+}
+impl_wasm_traits!(RoutedTakeOrdersHop);
+
+/// A take routed through intermediate tokens.
+///
+/// `hops` must be executed in order by the taker. When every hop settles on
+/// the same orderbook, `multicall` bundles them into one `multicall(bytes[])`
+/// call on that orderbook.
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutedTakeOrdersResult {
+    #[tsify(type = "Address[]")]
+    pub path: Vec<Address>,
+    pub hops: Vec<RoutedTakeOrdersHop>,
+    #[tsify(optional, type = "Hex")]
+    pub multicall: Option<Bytes>,
+    /// Blended sell per 1 buy across the whole route.
+    #[tsify(type = "Hex")]
+    pub effective_price: Float,
+    #[tsify(type = "Hex")]
+    pub expected_sell: Float,
+    #[tsify(type = "Hex")]
+    pub expected_buy: Float,
+    /// Approvals still missing for the taker, one per hop at most.
+    pub approvals: Vec<RouteApproval>,
+}
+impl_wasm_traits!(RoutedTakeOrdersResult);
+
+pub(crate) fn build_routed_result(
+    route: &PlannedRoute,
+    hops: Vec<BuiltHop>,
+    approvals: Vec<RouteApproval>,
+) -> Result<RoutedTakeOrdersResult, RaindexError> {
+    let zero = Float::zero()?;
+
+    let hop_results = hops
+        .into_iter()
+        .map(|hop| {
+            let sim = &hop.built.sim;
+            let effective_price = if sim.total_output.gt(zero)? {
+                sim.total_input.div(sim.total_output)?
+            } else {
+                zero
+            };
+            Ok(RoutedTakeOrdersHop {
+                orderbook: hop.hop.orderbook,
+                sell_token: hop.hop.sell_token,
+                buy_token: hop.hop.buy_token,
+                calldata: Bytes::copy_from_slice(
+                    &takeOrders4Call {
+                        config: hop.built.config.clone(),
+                    }
+                    .abi_encode(),
+                ),
+                effective_price,
+                prices: sim.legs.iter().map(|leg| leg.candidate.ratio).collect(),
+                expected_sell: sim.total_input,
+                expected_buy: sim.total_output,
+                max_io_ratio: hop.max_io_ratio,
+            })
+        })
+        .collect::<Result<Vec<_>, RaindexError>>()?;
+
+    let single_orderbook = hop_results
+        .windows(2)
+        .all(|pair| pair[0].orderbook == pair[1].orderbook);
+    let multicall = if hop_results.len() > 1 && single_orderbook {
+        Some(Bytes::from(
+            multicallCall {
+                data: hop_results.iter().map(|hop| hop.calldata.clone()).collect(),
+            }
+            .abi_encode(),
+        ))
+    } else {
+        None
+    };
+
+    Ok(RoutedTakeOrdersResult {
+        path: route.path.clone(),
+        hops: hop_results,
+        multicall,
+        effective_price: route.effective_price()?,
+        expected_sell: route.total_input(),
+        expected_buy: route.total_output(),
+        approvals,
+    })
+}
+
 #[cfg(all(test, not(target_family = "wasm")))]
 mod tests {
     use super::*;
@@ -0,0 +1,597 @@
+use super::selection::select_best_orderbook_simulation;
+use crate::raindex_client::RaindexError;
+use crate::take_orders::{
+    build_take_orders_config_from_simulation, BuiltTakeOrdersConfig, ParsedTakeOrdersMode,
+    SimulationResult, TakeOrderCandidate, TakeOrdersMode,
+};
+use crate::utils::float::cmp_float;
+use alloy::primitives::Address;
+use rain_math_float::Float;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::{Add, Div, Mul};
+
+pub const DEFAULT_MAX_HOPS: u32 = 3;
+pub const DEFAULT_HOP_SLIPPAGE: &str = "0.005";
+
+#[derive(Clone, Debug)]
+pub(crate) struct PlannedHop {
+    pub sell_token: Address,
+    pub buy_token: Address,
+    pub orderbook: Address,
+    pub sim: SimulationResult,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct PlannedRoute {
+    pub path: Vec<Address>,
+    pub hops: Vec<PlannedHop>,
+}
+
+impl PlannedRoute {
+    pub fn total_input(&self) -> Float {
+        self.hops[0].sim.total_input
+    }
+
+    pub fn total_output(&self) -> Float {
+        self.hops[self.hops.len() - 1].sim.total_output
+    }
+
+    pub fn effective_price(&self) -> Result<Float, RaindexError> {
+        let zero = Float::zero()?;
+        let output = self.total_output();
+        if output.gt(zero)? {
+            Ok(self.total_input().div(output)?)
+        } else {
+            Ok(zero)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct BuiltHop {
+    pub hop: PlannedHop,
+    pub built: BuiltTakeOrdersConfig,
+    pub mode: ParsedTakeOrdersMode,
+    /// Per-hop `maximumIORatio`: the worst simulated leg ratio widened by the
+    /// hop slippage.
+    pub max_io_ratio: Float,
+}
+
+fn candidate_tokens(candidate: &TakeOrderCandidate) -> (Address, Address) {
+    (
+        candidate.order.validInputs[candidate.input_io_index as usize].token,
+        candidate.order.validOutputs[candidate.output_io_index as usize].token,
+    )
+}
+
+/// Enumerates every simple token path from `sell_token` to `buy_token` of at
+/// most `max_hops` edges, where each edge is a pair some candidate trades in
+/// the taker's direction (taker gives the order's input token).
+///
+/// Paths are returned shortest first, then in address order.
+pub(crate) fn find_token_paths(
+    candidates: &[TakeOrderCandidate],
+    sell_token: Address,
+    buy_token: Address,
+    max_hops: u32,
+) -> Vec<Vec<Address>> {
+    let mut graph: BTreeMap<Address, BTreeSet<Address>> = BTreeMap::new();
+    for candidate in candidates {
+        let (input, output) = candidate_tokens(candidate);
+        if input != output {
+            graph.entry(input).or_default().insert(output);
+        }
+    }
+
+    let mut paths = vec![];
+    let mut stack = vec![vec![sell_token]];
+    while let Some(path) = stack.pop() {
+        let last = path[path.len() - 1];
+        if last == buy_token {
+            paths.push(path);
+            continue;
+        }
+        if path.len() as u32 > max_hops {
+            continue;
+        }
+        if let Some(next_tokens) = graph.get(&last) {
+            for next in next_tokens.iter().rev() {
+                if !path.contains(next) {
+                    let mut extended = path.clone();
+                    extended.push(*next);
+                    stack.push(extended);
+                }
+            }
+        }
+    }
+
+    paths.sort_by_key(|path| path.len());
+    paths
+}
+
+fn hop_candidates(
+    candidates: &[TakeOrderCandidate],
+    sell_token: Address,
+    buy_token: Address,
+) -> Vec<TakeOrderCandidate> {
+    candidates
+        .iter()
+        .filter(|candidate| candidate_tokens(candidate) == (sell_token, buy_token))
+        .cloned()
+        .collect()
+}
+
+/// The highest ratio among `candidates`, used as a price cap that keeps every
+/// candidate in play. The route-level cap is applied to the blended price.
+fn uncapped_price(candidates: &[TakeOrderCandidate]) -> Result<Float, RaindexError> {
+    let mut max = candidates[0].ratio;
+    for candidate in &candidates[1..] {
+        if candidate.ratio.gt(max)? {
+            max = candidate.ratio;
+        }
+    }
+    Ok(max)
+}
+
+fn simulate_hop(
+    candidates: &[TakeOrderCandidate],
+    sell_token: Address,
+    buy_token: Address,
+    mode: TakeOrdersMode,
+    amount: Float,
+) -> Result<Option<PlannedHop>, RaindexError> {
+    let candidates = hop_candidates(candidates, sell_token, buy_token);
+    if candidates.is_empty() || amount.lte(Float::zero()?)? {
+        return Ok(None);
+    }
+    let price_cap = uncapped_price(&candidates)?;
+    let mode = ParsedTakeOrdersMode { mode, amount };
+
+    match select_best_orderbook_simulation(candidates, mode, price_cap) {
+        Ok((orderbook, sim)) => Ok(Some(PlannedHop {
+            sell_token,
+            buy_token,
+            orderbook,
+            sim,
+        })),
+        Err(RaindexError::NoLiquidity) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Simulates `mode` along `path`, choosing the best orderbook per hop.
+///
+/// Buy routes are sized backwards from the requested output, then replayed
+/// forwards so a hop never spends more than the previous hop delivers. Spend
+/// routes simply feed each hop's output into the next hop.
+pub(crate) fn plan_route(
+    candidates: &[TakeOrderCandidate],
+    path: &[Address],
+    mode: ParsedTakeOrdersMode,
+) -> Result<Option<PlannedRoute>, RaindexError> {
+    let pairs: Vec<(Address, Address)> = path.windows(2).map(|w| (w[0], w[1])).collect();
+    if pairs.is_empty() {
+        return Ok(None);
+    }
+
+    let mut hops: Vec<PlannedHop> = Vec::with_capacity(pairs.len());
+
+    if mode.is_buy_mode() {
+        let mut needed = mode.target_amount();
+        for (sell_token, buy_token) in pairs.iter().rev() {
+            let Some(hop) = simulate_hop(
+                candidates,
+                *sell_token,
+                *buy_token,
+                TakeOrdersMode::BuyUpTo,
+                needed,
+            )?
+            else {
+                return Ok(None);
+            };
+            needed = hop.sim.total_input;
+            hops.push(hop);
+        }
+        hops.reverse();
+
+        for i in 1..hops.len() {
+            let available = hops[i - 1].sim.total_output;
+            if available.lt(hops[i].sim.total_input)? {
+                let Some(hop) = simulate_hop(
+                    candidates,
+                    hops[i].sell_token,
+                    hops[i].buy_token,
+                    TakeOrdersMode::SpendUpTo,
+                    available,
+                )?
+                else {
+                    return Ok(None);
+                };
+                hops[i] = hop;
+            }
+        }
+    } else {
+        let mut available = mode.target_amount();
+        for (sell_token, buy_token) in pairs {
+            let Some(hop) = simulate_hop(
+                candidates,
+                sell_token,
+                buy_token,
+                TakeOrdersMode::SpendUpTo,
+                available,
+            )?
+            else {
+                return Ok(None);
+            };
+            available = hop.sim.total_output;
+            hops.push(hop);
+        }
+    }
+
+    Ok(Some(PlannedRoute {
+        path: path.to_vec(),
+        hops,
+    }))
+}
+
+fn is_better_route(route: &PlannedRoute, best: &PlannedRoute) -> Result<bool, RaindexError> {
+    match cmp_float(&route.total_output(), &best.total_output())? {
+        Ordering::Greater => return Ok(true),
+        Ordering::Less => return Ok(false),
+        Ordering::Equal => {}
+    }
+    match cmp_float(&route.effective_price()?, &best.effective_price()?)? {
+        Ordering::Less => Ok(true),
+        Ordering::Greater => Ok(false),
+        Ordering::Equal => Ok(route.hops.len() < best.hops.len()),
+    }
+}
+
+/// Picks the route that delivers the most output, then the one with the
+/// lowest blended price, among routes whose blended price is within
+/// `price_cap`. This mirrors how the direct path picks between orderbooks.
+pub(crate) fn select_best_route(
+    candidates: &[TakeOrderCandidate],
+    sell_token: Address,
+    buy_token: Address,
+    mode: ParsedTakeOrdersMode,
+    price_cap: Float,
+    max_hops: u32,
+) -> Result<PlannedRoute, RaindexError> {
+    let mut best: Option<PlannedRoute> = None;
+
+    for path in find_token_paths(candidates, sell_token, buy_token, max_hops) {
+        let Some(route) = plan_route(candidates, &path, mode)? else {
+            continue;
+        };
+        if route.effective_price()?.gt(price_cap)? {
+            continue;
+        }
+        let is_better = match &best {
+            None => true,
+            Some(best) => is_better_route(&route, best)?,
+        };
+        if is_better {
+            best = Some(route);
+        }
+    }
+
+    let best = best.ok_or(RaindexError::NoLiquidity)?;
+
+    if mode.is_exact_mode() {
+        let achieved = if mode.is_buy_mode() {
+            best.total_output()
+        } else {
+            best.total_input()
+        };
+        if achieved.lt(mode.target_amount())? {
+            return Err(RaindexError::InsufficientLiquidity {
+                requested: mode.target_amount().format()?,
+                available: achieved.format()?,
+            });
+        }
+    }
+
+    Ok(best)
+}
+
+fn worst_leg_ratio(sim: &SimulationResult) -> Result<Float, RaindexError> {
+    let mut worst = sim.legs[0].candidate.ratio;
+    for leg in &sim.legs[1..] {
+        if leg.candidate.ratio.gt(worst)? {
+            worst = leg.candidate.ratio;
+        }
+    }
+    Ok(worst)
+}
+
+/// Turns a planned route into one `takeOrders4` config per hop.
+///
+/// Each hop keeps the direction of the request: buy routes buy exactly what
+/// the next hop needs, spend routes spend what the previous hop delivered.
+/// Every hop's `maximumIORatio` is its worst simulated leg ratio widened by
+/// `hop_slippage`, so a hop reverts rather than filling far off-quote.
+pub(crate) fn build_hop_configs(
+    route: &PlannedRoute,
+    mode: ParsedTakeOrdersMode,
+    hop_slippage: Float,
+) -> Result<Vec<BuiltHop>, RaindexError> {
+    let one = Float::parse("1".to_string())?;
+    let last = route.hops.len() - 1;
+
+    route
+        .hops
+        .iter()
+        .enumerate()
+        .map(|(i, hop)| {
+            let hop_mode = match (mode.is_buy_mode(), i == last, i == 0) {
+                (true, true, _) => ParsedTakeOrdersMode {
+                    mode: mode.mode,
+                    amount: hop.sim.total_output,
+                },
+                (true, false, _) => ParsedTakeOrdersMode {
+                    mode: TakeOrdersMode::BuyExact,
+                    amount: hop.sim.total_output,
+                },
+                (false, _, true) => ParsedTakeOrdersMode {
+                    mode: mode.mode,
+                    amount: hop.sim.total_input,
+                },
+                (false, _, false) => ParsedTakeOrdersMode {
+                    mode: TakeOrdersMode::SpendUpTo,
+                    amount: hop.sim.total_input,
+                },
+            };
+            let max_io_ratio = worst_leg_ratio(&hop.sim)?.mul(one.add(hop_slippage)?)?;
+            let built =
+                build_take_orders_config_from_simulation(hop.sim.clone(), hop_mode, max_io_ratio)?
+                    .ok_or(RaindexError::NoLiquidity)?;
+            Ok(BuiltHop {
+                hop: hop.clone(),
+                built,
+                mode: hop_mode,
+                max_io_ratio,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(target_family = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::test_helpers::orders::make_basic_order;
+
+    const USDC: Address = Address::repeat_byte(0x01);
+    const WBTC: Address = Address::repeat_byte(0x02);
+    const WETH: Address = Address::repeat_byte(0x03);
+    const DAI: Address = Address::repeat_byte(0x04);
+    const OB: Address = Address::repeat_byte(0xAA);
+
+    fn f(value: &str) -> Float {
+        Float::parse(value.to_string()).unwrap()
+    }
+
+    fn candidate(sell: Address, buy: Address, max_output: &str, ratio: &str) -> TakeOrderCandidate {
+        TakeOrderCandidate {
+            orderbook: OB,
+            order: make_basic_order(sell, buy),
+            input_io_index: 0,
+            output_io_index: 0,
+            max_output: f(max_output),
+            ratio: f(ratio),
+            signed_context: vec![],
+        }
+    }
+
+    fn mode(mode: TakeOrdersMode, amount: &str) -> ParsedTakeOrdersMode {
+        ParsedTakeOrdersMode {
+            mode,
+            amount: f(amount),
+        }
+    }
+
+    fn two_hop_book() -> Vec<TakeOrderCandidate> {
+        vec![
+            // 20000 USDC per WBTC, 1 WBTC available.
+            candidate(USDC, WBTC, "1", "20000"),
+            // 0.05 WBTC per WETH, 10 WETH available.
+            candidate(WBTC, WETH, "10", "0.05"),
+        ]
+    }
+
+    #[test]
+    fn test_find_token_paths_two_hops() {
+        let paths = find_token_paths(&two_hop_book(), USDC, WETH, 3);
+        assert_eq!(paths, vec![vec![USDC, WBTC, WETH]]);
+    }
+
+    #[test]
+    fn test_find_token_paths_respects_max_hops() {
+        assert!(find_token_paths(&two_hop_book(), USDC, WETH, 1).is_empty());
+    }
+
+    #[test]
+    fn test_find_token_paths_shortest_first_and_no_cycles() {
+        let mut book = two_hop_book();
+        book.push(candidate(USDC, WETH, "1", "1000"));
+        book.push(candidate(WETH, USDC, "1", "0.001"));
+        book.push(candidate(WBTC, DAI, "1", "1"));
+
+        let paths = find_token_paths(&book, USDC, WETH, 3);
+        assert_eq!(paths, vec![vec![USDC, WETH], vec![USDC, WBTC, WETH]]);
+    }
+
+    #[test]
+    fn test_plan_route_buy_sizes_backwards() {
+        let route = plan_route(
+            &two_hop_book(),
+            &[USDC, WBTC, WETH],
+            mode(TakeOrdersMode::BuyUpTo, "10"),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(route.hops.len(), 2);
+        // 10 WETH needs 0.5 WBTC, which needs 10000 USDC.
+        assert!(route.hops[1].sim.total_input.eq(f("0.5")).unwrap());
+        assert!(route.hops[0].sim.total_output.eq(f("0.5")).unwrap());
+        assert!(route.total_input().eq(f("10000")).unwrap());
+        assert!(route.total_output().eq(f("10")).unwrap());
+        assert!(route.effective_price().unwrap().eq(f("1000")).unwrap());
+    }
+
+    #[test]
+    fn test_plan_route_buy_shrinks_when_upstream_is_short() {
+        let book = vec![
+            candidate(USDC, WBTC, "0.25", "20000"),
+            candidate(WBTC, WETH, "10", "0.05"),
+        ];
+        let route = plan_route(
+            &book,
+            &[USDC, WBTC, WETH],
+            mode(TakeOrdersMode::BuyUpTo, "10"),
+        )
+        .unwrap()
+        .unwrap();
+
+        // Only 0.25 WBTC is available, which buys 5 WETH.
+        assert!(route.hops[1].sim.total_input.eq(f("0.25")).unwrap());
+        assert!(route.total_output().eq(f("5")).unwrap());
+        assert!(route.total_input().eq(f("5000")).unwrap());
+    }
+
+    #[test]
+    fn test_plan_route_spend_feeds_forward() {
+        let route = plan_route(
+            &two_hop_book(),
+            &[USDC, WBTC, WETH],
+            mode(TakeOrdersMode::SpendUpTo, "4000"),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert!(route.hops[0].sim.total_output.eq(f("0.2")).unwrap());
+        assert!(route.hops[1].sim.total_input.eq(f("0.2")).unwrap());
+        assert!(route.total_output().eq(f("4")).unwrap());
+    }
+
+    #[test]
+    fn test_plan_route_missing_hop() {
+        let book = vec![candidate(USDC, WBTC, "1", "20000")];
+        assert!(plan_route(
+            &book,
+            &[USDC, WBTC, WETH],
+            mode(TakeOrdersMode::BuyUpTo, "1")
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_select_best_route_prefers_cheaper_path() {
+        let mut book = two_hop_book();
+        // Direct liquidity exists but is pricier than going through WBTC.
+        book.push(candidate(USDC, WETH, "100", "1200"));
+
+        let route = select_best_route(
+            &book,
+            USDC,
+            WETH,
+            mode(TakeOrdersMode::BuyUpTo, "10"),
+            f("5000"),
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(route.path, vec![USDC, WBTC, WETH]);
+    }
+
+    #[test]
+    fn test_select_best_route_applies_blended_price_cap() {
+        let result = select_best_route(
+            &two_hop_book(),
+            USDC,
+            WETH,
+            mode(TakeOrdersMode::BuyUpTo, "10"),
+            f("999"),
+            3,
+        );
+        assert!(matches!(result, Err(RaindexError::NoLiquidity)));
+    }
+
+    #[test]
+    fn test_select_best_route_exact_insufficient() {
+        let result = select_best_route(
+            &two_hop_book(),
+            USDC,
+            WETH,
+            mode(TakeOrdersMode::BuyExact, "100"),
+            f("5000"),
+            3,
+        );
+        assert!(matches!(
+            result,
+            Err(RaindexError::InsufficientLiquidity { .. })
+        ));
+    }
+
+    #[test]
+    fn test_build_hop_configs_slippage_bounds() {
+        let request = mode(TakeOrdersMode::BuyExact, "10");
+        let route = plan_route(&two_hop_book(), &[USDC, WBTC, WETH], request)
+            .unwrap()
+            .unwrap();
+
+        let hops = build_hop_configs(&route, request, f("0.01")).unwrap();
+
+        assert_eq!(hops.len(), 2);
+        assert!(hops[0].max_io_ratio.eq(f("20200")).unwrap());
+        assert!(hops[1].max_io_ratio.eq(f("0.0505")).unwrap());
+        assert_eq!(hops[0].mode.mode, TakeOrdersMode::BuyExact);
+        assert!(hops[0].mode.amount.eq(f("0.5")).unwrap());
+        assert_eq!(hops[1].mode.mode, TakeOrdersMode::BuyExact);
+        assert!(hops[1].built.config.IOIsInput);
+    }
+
+    #[test]
+    fn test_build_hop_configs_spend_route() {
+        let request = mode(TakeOrdersMode::SpendExact, "4000");
+        let route = plan_route(&two_hop_book(), &[USDC, WBTC, WETH], request)
+            .unwrap()
+            .unwrap();
+
+        let hops = build_hop_configs(&route, request, f("0")).unwrap();
+
+        assert_eq!(hops[0].mode.mode, TakeOrdersMode::SpendExact);
+        assert_eq!(hops[1].mode.mode, TakeOrdersMode::SpendUpTo);
+        assert!(hops[1].mode.amount.eq(f("0.2")).unwrap());
+        assert!(!hops[1].built.config.IOIsInput);
+    }
+
+    #[test]
+    fn test_build_routed_result_bundles_single_orderbook_hops() {
+        use super::super::result::build_routed_result;
+        use alloy::sol_types::SolCall;
+        use rain_orderbook_bindings::OrderBook::multicallCall;
+
+        let request = mode(TakeOrdersMode::BuyUpTo, "10");
+        let route = plan_route(&two_hop_book(), &[USDC, WBTC, WETH], request)
+            .unwrap()
+            .unwrap();
+        let hops = build_hop_configs(&route, request, f("0.01")).unwrap();
+
+        let result = build_routed_result(&route, hops, vec![]).unwrap();
+
+        assert_eq!(result.path, vec![USDC, WBTC, WETH]);
+        assert_eq!(result.hops.len(), 2);
+        assert_eq!(result.hops[0].sell_token, USDC);
+        assert_eq!(result.hops[1].buy_token, WETH);
+        assert!(result.expected_sell.eq(f("10000")).unwrap());
+        assert!(result.expected_buy.eq(f("10")).unwrap());
+        let multicall = multicallCall::abi_decode(&result.multicall.unwrap()).unwrap();
+        assert_eq!(multicall.data.len(), 2);
+        assert_eq!(multicall.data[0], result.hops[0].calldata);
+    }
+}
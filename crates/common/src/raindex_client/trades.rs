@@ -11,7 +11,13 @@ use crate::raindex_client::{
     vaults::{LocalTradeBalanceInfo, LocalTradeTokenInfo, RaindexVaultBalanceChange},
 };
 use alloy::primitives::{Address, Bytes, B256, U256};
-use rain_orderbook_subgraph_client::types::{common::SgTrade, Id};
+use rain_orderbook_subgraph_client::types::{
+    common::{
+        SgBigInt, SgBytes, SgOrderbook, SgTrade, SgTradeEvent, SgTradeStructPartialOrder,
+        SgTransaction,
+    },
+    Id,
+};
 use std::str::FromStr;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_utils::prelude::js_sys::BigInt;
@@ -347,6 +353,31 @@ impl RaindexTrade {
             orderbook: trade.orderbook,
         })
     }
+
+    pub(crate) fn into_sg_trade(self, order_id: String) -> Result<SgTrade, RaindexError> {
+        let transaction: SgTransaction = self.transaction.into();
+        Ok(SgTrade {
+            id: SgBytes(self.id.to_string()),
+            trade_event: SgTradeEvent {
+                sender: transaction.from.clone(),
+                transaction,
+            },
+            output_vault_balance_change: self
+                .output_vault_balance_change
+                .into_sg_trade_balance_change()?,
+            order: SgTradeStructPartialOrder {
+                id: SgBytes(order_id),
+                order_hash: SgBytes(self.order_hash.to_string()),
+            },
+            input_vault_balance_change: self
+                .input_vault_balance_change
+                .into_sg_trade_balance_change()?,
+            timestamp: SgBigInt(self.timestamp.to_string()),
+            orderbook: SgOrderbook {
+                id: SgBytes(self.orderbook.to_string()),
+            },
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1497,5 +1528,53 @@ mod test_helpers {
             let count = order.get_trade_count(None, None).await.unwrap();
             assert_eq!(count, 2);
         }
+
+        #[test]
+        fn test_into_sg_trade_round_trip() {
+            let sg_trade: SgTrade = serde_json::from_value(get_trades_json()[1].clone()).unwrap();
+            let trade = RaindexTrade::try_from_sg_trade(1, sg_trade).unwrap();
+
+            let converted = trade.clone().into_sg_trade("0x0234".to_string()).unwrap();
+            assert_eq!(converted.order.id.0, "0x0234");
+            assert_eq!(
+                converted.trade_event.sender.0,
+                trade.transaction().from().to_string()
+            );
+            assert_eq!(
+                converted.input_vault_balance_change.vault.vault_id.0,
+                U256::from(0x0234).to_string()
+            );
+            assert_eq!(
+                converted
+                    .output_vault_balance_change
+                    .trade
+                    .trade_event
+                    .__typename,
+                "TakeOrder"
+            );
+
+            let round_trip = RaindexTrade::try_from_sg_trade(1, converted).unwrap();
+            assert_eq!(round_trip.id(), trade.id());
+            assert_eq!(round_trip.order_hash(), trade.order_hash());
+            assert_eq!(round_trip.timestamp(), trade.timestamp());
+            assert_eq!(round_trip.orderbook(), trade.orderbook());
+            assert_eq!(round_trip.transaction().id(), trade.transaction().id());
+            let (input, expected_input) = (
+                round_trip.input_vault_balance_change(),
+                trade.input_vault_balance_change(),
+            );
+            assert_eq!(input.vault_id(), expected_input.vault_id());
+            assert_eq!(input.token().address(), expected_input.token().address());
+            assert!(input.amount().eq(expected_input.amount()).unwrap());
+            let (output, expected_output) = (
+                round_trip.output_vault_balance_change(),
+                trade.output_vault_balance_change(),
+            );
+            assert!(output.amount().eq(expected_output.amount()).unwrap());
+            assert!(output
+                .new_balance()
+                .eq(expected_output.new_balance())
+                .unwrap());
+        }
     }
 }
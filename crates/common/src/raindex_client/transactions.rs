@@ -7,7 +7,10 @@ use crate::raindex_client::QuerySource;
 use alloy::primitives::{Address, B256, U256};
 #[cfg(target_family = "wasm")]
 use gloo_timers::future::TimeoutFuture;
-use rain_orderbook_subgraph_client::types::{common::SgTransaction, Id};
+use rain_orderbook_subgraph_client::types::{
+    common::{SgBigInt, SgBytes, SgTransaction},
+    Id,
+};
 use rain_orderbook_subgraph_client::OrderbookSubgraphClientError;
 use serde::{Deserialize, Serialize};
 #[cfg(not(target_family = "wasm"))]
@@ -228,6 +231,17 @@ impl TryFrom<SgTransaction> for RaindexTransaction {
     }
 }
 
+impl From<RaindexTransaction> for SgTransaction {
+    fn from(transaction: RaindexTransaction) -> Self {
+        Self {
+            id: SgBytes(transaction.id.to_string()),
+            from: SgBytes(transaction.from.to_string()),
+            block_number: SgBigInt(transaction.block_number.to_string()),
+            timestamp: SgBigInt(transaction.timestamp.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_helpers {
     #[cfg(not(target_family = "wasm"))]
@@ -0,0 +1,318 @@
+use super::*;
+use crate::add_order::AddOrderArgs;
+use crate::dotrain_order::DotrainOrder;
+use crate::raindex_client::orders::RaindexOrder;
+use alloy::primitives::Bytes;
+use alloy::sol_types::SolCall;
+use rain_metadata::{KnownMagic, RainMetaDocumentV1Item};
+use rain_orderbook_bindings::{
+    IRaindexV6::{removeOrder3Call, OrderV4, IOV2},
+    OrderBook::multicallCall,
+};
+use std::collections::HashMap;
+
+/// Bindings overriding the deployment's scenario bindings, keyed by name
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Tsify)]
+pub struct OrderUpdateBindings(pub HashMap<String, String>);
+impl_wasm_traits!(OrderUpdateBindings);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct RaindexOrderUpdate {
+    /// Orderbook multicall of `removeOrder3` for the existing order followed
+    /// by `addOrder4` for its replacement
+    #[tsify(type = "Hex")]
+    pub calldata: Bytes,
+    #[tsify(optional)]
+    pub old_rainlang: Option<String>,
+    pub new_rainlang: String,
+    /// Line diff of the old and new rainlang, each line prefixed with `- `,
+    /// `+ ` or two spaces
+    pub rainlang_diff: String,
+    #[tsify(type = "Address")]
+    pub orderbook: Address,
+    pub chain_id: u32,
+}
+impl_wasm_traits!(RaindexOrderUpdate);
+
+#[wasm_export]
+impl RaindexOrder {
+    /// Builds the calldata replacing this order with a new deployment
+    ///
+    /// Removes the order and adds the deployment's order in a single orderbook
+    /// multicall. The new order reuses the vault IDs of the old order's inputs
+    /// and outputs for the same tokens, and keeps its additional meta. The
+    /// deployment must target the order's orderbook.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await order.buildUpdate(dotrain, [settings], "my-deployment", { "max-price": "10" });
+    /// if (result.error) {
+    ///   console.error("Cannot build update:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, rainlangDiff } = result.value;
+    /// // Review the diff and submit the calldata to the orderbook
+    /// ```
+    #[wasm_export(
+        js_name = "buildUpdate",
+        return_description = "Remove and add order multicall with the rainlang diff",
+        unchecked_return_type = "RaindexOrderUpdate"
+    )]
+    pub async fn build_update_wasm_binding(
+        &self,
+        #[wasm_export(param_description = "Dotrain text of the new order")] dotrain: String,
+        #[wasm_export(param_description = "Optional additional YAML configuration strings")]
+        settings: Option<Vec<String>>,
+        #[wasm_export(
+            js_name = "deploymentKey",
+            param_description = "Key of the deployment to replace the order with"
+        )]
+        deployment_key: String,
+        #[wasm_export(
+            param_description = "Bindings overriding the deployment's scenario bindings"
+        )]
+        bindings: OrderUpdateBindings,
+    ) -> Result<RaindexOrderUpdate, RaindexError> {
+        self.build_update(dotrain, settings, &deployment_key, bindings.0)
+            .await
+    }
+}
+impl RaindexOrder {
+    pub async fn build_update(
+        &self,
+        dotrain: String,
+        settings: Option<Vec<String>>,
+        deployment_key: &str,
+        bindings: HashMap<String, String>,
+    ) -> Result<RaindexOrderUpdate, RaindexError> {
+        #[cfg(target_family = "wasm")]
+        let orderbook = Address::from_str(&self.orderbook())?;
+        #[cfg(not(target_family = "wasm"))]
+        let orderbook = self.orderbook();
+        #[cfg(target_family = "wasm")]
+        let order_hash = B256::from_str(&self.order_hash())?;
+        #[cfg(not(target_family = "wasm"))]
+        let order_hash = self.order_hash();
+        #[cfg(target_family = "wasm")]
+        let meta = self.meta().map(|meta| Bytes::from_str(&meta)).transpose()?;
+        #[cfg(not(target_family = "wasm"))]
+        let meta = self.meta();
+
+        if !self.active() {
+            return Err(RaindexError::OrderNotActive(order_hash));
+        }
+
+        let dotrain_order = DotrainOrder::create(dotrain, settings).await?;
+        let deployment = dotrain_order
+            .dotrain_yaml()
+            .get_deployment(deployment_key)?;
+        let targets_order =
+            deployment.order.orderbook.as_ref().is_some_and(|cfg| {
+                cfg.address == orderbook && cfg.network.chain_id == self.chain_id()
+            });
+        if !targets_order {
+            return Err(RaindexError::DeploymentOrderbookMismatch {
+                deployment: deployment_key.to_string(),
+                orderbook,
+                chain_id: self.chain_id(),
+            });
+        }
+
+        let additional_meta = match meta {
+            Some(meta) => RainMetaDocumentV1Item::cbor_decode(&meta)?
+                .into_iter()
+                .filter(|item| {
+                    !matches!(
+                        item.magic,
+                        KnownMagic::RainlangSourceV1
+                            | KnownMagic::DotrainSourceV1
+                            | KnownMagic::DotrainGuiStateV1
+                            | KnownMagic::RaindexSignedContextOracleV1
+                    )
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let old_order: OrderV4 = self.try_into()?;
+        let mut add_order_args = AddOrderArgs::new_from_deployment(
+            dotrain_order.generate_dotrain_for_deployment(deployment_key)?,
+            deployment,
+            Some(additional_meta),
+        )
+        .await?;
+        add_order_args.bindings.extend(bindings);
+        reuse_vault_ids(&mut add_order_args.inputs, &old_order.validInputs);
+        reuse_vault_ids(&mut add_order_args.outputs, &old_order.validOutputs);
+
+        let rpcs = self
+            .get_rpc_urls()?
+            .iter()
+            .map(|rpc| rpc.to_string())
+            .collect();
+        let add_order_call = add_order_args.try_into_call(rpcs).await?;
+        let remove_order_call = removeOrder3Call {
+            order: old_order,
+            tasks: vec![],
+        };
+        let calldata = multicallCall {
+            data: vec![
+                Bytes::copy_from_slice(&remove_order_call.abi_encode()),
+                Bytes::copy_from_slice(&add_order_call.abi_encode()),
+            ],
+        }
+        .abi_encode();
+
+        let old_rainlang = self.rainlang();
+        let new_rainlang = add_order_args.compose_to_rainlang()?;
+        let rainlang_diff = diff_lines(old_rainlang.as_deref().unwrap_or(""), &new_rainlang);
+
+        Ok(RaindexOrderUpdate {
+            calldata: Bytes::from(calldata),
+            old_rainlang,
+            new_rainlang,
+            rainlang_diff,
+            orderbook,
+            chain_id: self.chain_id(),
+        })
+    }
+}
+
+/// Replaces each io's vault ID with the old order's vault ID for its token,
+/// preferring the old io at the same index. Each old io is reused at most
+/// once, so ios sharing a token keep distinct vaults
+fn reuse_vault_ids(ios: &mut [IOV2], old_ios: &[IOV2]) {
+    let mut used = vec![false; old_ios.len()];
+    let mut reused = vec![false; ios.len()];
+    for (i, io) in ios.iter_mut().enumerate() {
+        if let Some(old_io) = old_ios.get(i).filter(|old_io| old_io.token == io.token) {
+            io.vaultId = old_io.vaultId;
+            used[i] = true;
+            reused[i] = true;
+        }
+    }
+    for (io, _) in ios.iter_mut().zip(reused).filter(|(_, reused)| !reused) {
+        let old_index = (0..old_ios.len()).find(|&j| !used[j] && old_ios[j].token == io.token);
+        if let Some(j) = old_index {
+            io.vaultId = old_ios[j].vaultId;
+            used[j] = true;
+        }
+    }
+}
+
+/// Line diff of two texts from their longest common subsequence of lines
+fn diff_lines(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the common subsequence length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    fn io(token: Address, vault_id: B256) -> IOV2 {
+        IOV2 {
+            token,
+            vaultId: vault_id,
+        }
+    }
+
+    #[test]
+    fn test_reuse_vault_ids() {
+        let token1 = address!("0x1111111111111111111111111111111111111111");
+        let token2 = address!("0x2222222222222222222222222222222222222222");
+        let token3 = address!("0x3333333333333333333333333333333333333333");
+        let old_vault1 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let old_vault2 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        let old_vault3 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000003");
+        let new_vault = b256!("0x00000000000000000000000000000000000000000000000000000000000000ff");
+
+        let old_ios = vec![
+            io(token1, old_vault1),
+            io(token2, old_vault2),
+            io(token1, old_vault3),
+        ];
+        let mut ios = vec![
+            io(token2, new_vault),
+            io(token3, new_vault),
+            io(token1, new_vault),
+        ];
+        reuse_vault_ids(&mut ios, &old_ios);
+
+        assert_eq!(ios[0].vaultId, old_vault2);
+        assert_eq!(ios[1].vaultId, new_vault);
+        assert_eq!(ios[2].vaultId, old_vault3);
+    }
+
+    #[test]
+    fn test_reuse_vault_ids_uses_each_old_vault_once() {
+        let token1 = address!("0x1111111111111111111111111111111111111111");
+        let token2 = address!("0x2222222222222222222222222222222222222222");
+        let old_vault1 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let old_vault2 =
+            b256!("0x0000000000000000000000000000000000000000000000000000000000000002");
+        let new_vault = b256!("0x00000000000000000000000000000000000000000000000000000000000000ff");
+
+        let old_ios = vec![io(token1, old_vault1), io(token2, old_vault2)];
+        let mut ios = vec![
+            io(token2, new_vault),
+            io(token1, new_vault),
+            io(token1, new_vault),
+        ];
+        reuse_vault_ids(&mut ios, &old_ios);
+
+        assert_eq!(ios[0].vaultId, old_vault2);
+        assert_eq!(ios[1].vaultId, old_vault1);
+        assert_eq!(ios[2].vaultId, new_vault);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "a: 1,\nb: 2;\n:;";
+        let new = "a: 1,\nb: 3,\nc: 4;\n:;";
+        assert_eq!(
+            diff_lines(old, new),
+            "  a: 1,\n- b: 2;\n+ b: 3,\n+ c: 4;\n  :;"
+        );
+
+        assert_eq!(diff_lines("", "a"), "+ a");
+        assert_eq!(diff_lines("a", ""), "- a");
+        assert_eq!(diff_lines("a\nb", "a\nb"), "  a\n  b");
+    }
+}
@@ -1,5 +1,6 @@
 use super::ClientRef;
 use super::*;
+use crate::local_db::query::fetch_order_performance::{LocalDbVaultPerformance, PerformanceWindow};
 use crate::local_db::query::fetch_order_vaults_volume::LocalDbVaultVolume;
 use crate::local_db::query::fetch_vaults::LocalDbVault;
 use crate::local_db::{
@@ -26,12 +27,15 @@ use async_trait::async_trait;
 use rain_math_float::Float;
 use rain_orderbook_bindings::{IRaindexV6::deposit4Call, IERC20::approveCall};
 use rain_orderbook_subgraph_client::{
-    performance::vol::{VaultVolume, VolumeDetails},
+    performance::{
+        annual_rate,
+        vol::{VaultVolume, VolumeDetails},
+    },
     types::{
         common::{
-            SgBigInt, SgBytes, SgErc20, SgOrderAsIO, SgOrderbook, SgTradeVaultBalanceChange,
-            SgVault, SgVaultBalanceChangeType, SgVaultBalanceChangeUnwrapped,
-            SgVaultsListFilterArgs,
+            SgBigInt, SgBytes, SgErc20, SgOrderAsIO, SgOrderbook, SgTradeEventTypename, SgTradeRef,
+            SgTradeVaultBalanceChange, SgTransaction, SgVault, SgVaultBalanceChangeType,
+            SgVaultBalanceChangeUnwrapped, SgVaultBalanceChangeVault, SgVaultsListFilterArgs,
         },
         Id,
     },
@@ -42,7 +46,7 @@ use std::str::FromStr;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_utils::prelude::js_sys::BigInt;
 
-const DEFAULT_PAGE_SIZE: u16 = 100;
+pub(crate) const DEFAULT_PAGE_SIZE: u16 = 100;
 
 pub(crate) struct SubgraphVaults<'a> {
     client: &'a RaindexClient,
@@ -319,6 +323,10 @@ impl RaindexVaultToken {
 
 #[wasm_export]
 impl RaindexVault {
+    #[wasm_export(skip)]
+    pub fn get_raindex_client(&self) -> ClientRef {
+        ClientRef::clone(&self.raindex_client)
+    }
     #[wasm_export(skip)]
     pub fn get_orderbook_client(&self) -> Result<OrderbookSubgraphClient, RaindexError> {
         self.raindex_client.get_orderbook_client(self.orderbook)
@@ -891,6 +899,39 @@ impl RaindexVaultBalanceChange {
             orderbook: Address::from_str(&balance_change.orderbook.id.0)?,
         })
     }
+
+    pub(crate) fn into_sg_trade_balance_change(
+        self,
+    ) -> Result<SgTradeVaultBalanceChange, RaindexError> {
+        let trade_typename = match self.r#type {
+            RaindexVaultBalanceChangeType::Clear => "Clear",
+            RaindexVaultBalanceChangeType::TakeOrder => "TakeOrder",
+            _ => "Unknown",
+        };
+        let transaction: SgTransaction = self.transaction.into();
+        Ok(SgTradeVaultBalanceChange {
+            id: transaction.id.clone(),
+            __typename: "TradeVaultBalanceChange".to_string(),
+            amount: SgBytes(self.amount.as_hex()),
+            new_vault_balance: SgBytes(self.new_balance.as_hex()),
+            old_vault_balance: SgBytes(self.old_balance.as_hex()),
+            vault: SgVaultBalanceChangeVault {
+                id: SgBytes(B256::from(self.vault_id).to_string()),
+                vault_id: SgBytes(self.vault_id.to_string()),
+                token: self.token.try_into()?,
+            },
+            timestamp: SgBigInt(self.timestamp.to_string()),
+            transaction,
+            orderbook: SgOrderbook {
+                id: SgBytes(self.orderbook.to_string()),
+            },
+            trade: SgTradeRef {
+                trade_event: SgTradeEventTypename {
+                    __typename: trade_typename.to_string(),
+                },
+            },
+        })
+    }
 }
 
 impl RaindexVaultBalanceChange {
@@ -1249,6 +1290,258 @@ impl RaindexVaultVolumeDetails {
     }
 }
 
+/// Balance movements and returns of one of an order's vaults over a block or
+/// timestamp window, measured from the local DB. Without an id it is the
+/// order's total over all of its vaults of the token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[wasm_bindgen]
+pub struct RaindexVaultPerformance {
+    id: Option<U256>,
+    token: RaindexVaultToken,
+    starting_balance: Float,
+    formatted_starting_balance: String,
+    ending_balance: Float,
+    formatted_ending_balance: String,
+    total_deposits: Float,
+    formatted_total_deposits: String,
+    total_withdrawals: Float,
+    formatted_total_withdrawals: String,
+    net_deposits: Float,
+    formatted_net_deposits: String,
+    starting_capital: Float,
+    formatted_starting_capital: String,
+    pnl: Float,
+    formatted_pnl: String,
+    apy: Option<Float>,
+    formatted_apy: Option<String>,
+    start_time: u64,
+    end_time: u64,
+}
+#[cfg(target_family = "wasm")]
+#[wasm_bindgen]
+impl RaindexVaultPerformance {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> Result<Option<BigInt>, RaindexError> {
+        self.id
+            .map(|id| {
+                BigInt::from_str(&id.to_string())
+                    .map_err(|e| RaindexError::JsError(e.to_string().into()))
+            })
+            .transpose()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn token(&self) -> RaindexVaultToken {
+        self.token.clone()
+    }
+    #[wasm_bindgen(getter = startingBalance)]
+    pub fn starting_balance(&self) -> Float {
+        self.starting_balance
+    }
+    #[wasm_bindgen(getter = formattedStartingBalance)]
+    pub fn formatted_starting_balance(&self) -> String {
+        self.formatted_starting_balance.clone()
+    }
+    #[wasm_bindgen(getter = endingBalance)]
+    pub fn ending_balance(&self) -> Float {
+        self.ending_balance
+    }
+    #[wasm_bindgen(getter = formattedEndingBalance)]
+    pub fn formatted_ending_balance(&self) -> String {
+        self.formatted_ending_balance.clone()
+    }
+    #[wasm_bindgen(getter = totalDeposits)]
+    pub fn total_deposits(&self) -> Float {
+        self.total_deposits
+    }
+    #[wasm_bindgen(getter = formattedTotalDeposits)]
+    pub fn formatted_total_deposits(&self) -> String {
+        self.formatted_total_deposits.clone()
+    }
+    #[wasm_bindgen(getter = totalWithdrawals)]
+    pub fn total_withdrawals(&self) -> Float {
+        self.total_withdrawals
+    }
+    #[wasm_bindgen(getter = formattedTotalWithdrawals)]
+    pub fn formatted_total_withdrawals(&self) -> String {
+        self.formatted_total_withdrawals.clone()
+    }
+    #[wasm_bindgen(getter = netDeposits)]
+    pub fn net_deposits(&self) -> Float {
+        self.net_deposits
+    }
+    #[wasm_bindgen(getter = formattedNetDeposits)]
+    pub fn formatted_net_deposits(&self) -> String {
+        self.formatted_net_deposits.clone()
+    }
+    #[wasm_bindgen(getter = startingCapital)]
+    pub fn starting_capital(&self) -> Float {
+        self.starting_capital
+    }
+    #[wasm_bindgen(getter = formattedStartingCapital)]
+    pub fn formatted_starting_capital(&self) -> String {
+        self.formatted_starting_capital.clone()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn pnl(&self) -> Float {
+        self.pnl
+    }
+    #[wasm_bindgen(getter = formattedPnl)]
+    pub fn formatted_pnl(&self) -> String {
+        self.formatted_pnl.clone()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn apy(&self) -> Option<Float> {
+        self.apy
+    }
+    #[wasm_bindgen(getter = formattedApy)]
+    pub fn formatted_apy(&self) -> Option<String> {
+        self.formatted_apy.clone()
+    }
+    #[wasm_bindgen(getter = startTime)]
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+    #[wasm_bindgen(getter = endTime)]
+    pub fn end_time(&self) -> u64 {
+        self.end_time
+    }
+}
+#[cfg(not(target_family = "wasm"))]
+impl RaindexVaultPerformance {
+    pub fn id(&self) -> Option<U256> {
+        self.id
+    }
+    pub fn token(&self) -> RaindexVaultToken {
+        self.token.clone()
+    }
+    pub fn starting_balance(&self) -> Float {
+        self.starting_balance
+    }
+    pub fn formatted_starting_balance(&self) -> String {
+        self.formatted_starting_balance.clone()
+    }
+    pub fn ending_balance(&self) -> Float {
+        self.ending_balance
+    }
+    pub fn formatted_ending_balance(&self) -> String {
+        self.formatted_ending_balance.clone()
+    }
+    pub fn total_deposits(&self) -> Float {
+        self.total_deposits
+    }
+    pub fn formatted_total_deposits(&self) -> String {
+        self.formatted_total_deposits.clone()
+    }
+    pub fn total_withdrawals(&self) -> Float {
+        self.total_withdrawals
+    }
+    pub fn formatted_total_withdrawals(&self) -> String {
+        self.formatted_total_withdrawals.clone()
+    }
+    pub fn net_deposits(&self) -> Float {
+        self.net_deposits
+    }
+    pub fn formatted_net_deposits(&self) -> String {
+        self.formatted_net_deposits.clone()
+    }
+    pub fn starting_capital(&self) -> Float {
+        self.starting_capital
+    }
+    pub fn formatted_starting_capital(&self) -> String {
+        self.formatted_starting_capital.clone()
+    }
+    pub fn pnl(&self) -> Float {
+        self.pnl
+    }
+    pub fn formatted_pnl(&self) -> String {
+        self.formatted_pnl.clone()
+    }
+    pub fn apy(&self) -> Option<Float> {
+        self.apy
+    }
+    pub fn formatted_apy(&self) -> Option<String> {
+        self.formatted_apy.clone()
+    }
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+    pub fn end_time(&self) -> u64 {
+        self.end_time
+    }
+}
+impl RaindexVaultPerformance {
+    pub fn try_from_local_db_vault_performance(
+        chain_id: u32,
+        performance: LocalDbVaultPerformance,
+        window: &PerformanceWindow,
+    ) -> Result<Self, RaindexError> {
+        let decimals = performance
+            .token_decimals
+            .ok_or(RaindexError::MissingErc20Decimals(
+                performance.token.to_string(),
+            ))?;
+
+        let token = RaindexVaultToken {
+            chain_id,
+            id: performance.token.to_string(),
+            address: performance.token,
+            name: performance.token_name,
+            symbol: performance.token_symbol,
+            decimals,
+        };
+
+        let starting_balance = Float::from_hex(&performance.starting_balance)?;
+        let ending_balance = Float::from_hex(&performance.ending_balance)?;
+        let total_deposits = Float::from_hex(&performance.total_deposits)?;
+        let total_withdrawals = Float::from_hex(&performance.total_withdrawals)?;
+        let pnl = Float::from_hex(&performance.pnl)?;
+        let net_deposits = (total_deposits - total_withdrawals)?;
+        // the capital put to work is what the vault held at the start plus what
+        // was deposited during the window
+        let starting_capital = (starting_balance + total_deposits)?;
+
+        // block windows are measured by the timestamps of their first and last changes
+        let start_time = window
+            .start_timestamp
+            .or(performance.first_timestamp)
+            .unwrap_or_default();
+        let end_time = window
+            .end_timestamp
+            .or(performance.last_timestamp)
+            .unwrap_or(start_time);
+
+        let apy = if starting_capital.is_zero()? || end_time <= start_time {
+            None
+        } else {
+            Some(((pnl / starting_capital)? / annual_rate(start_time, end_time)?)?)
+        };
+
+        Ok(Self {
+            id: performance.vault_id,
+            token,
+            formatted_starting_balance: starting_balance.format()?,
+            starting_balance,
+            formatted_ending_balance: ending_balance.format()?,
+            ending_balance,
+            formatted_total_deposits: total_deposits.format()?,
+            total_deposits,
+            formatted_total_withdrawals: total_withdrawals.format()?,
+            total_withdrawals,
+            formatted_net_deposits: net_deposits.format()?,
+            net_deposits,
+            formatted_starting_capital: starting_capital.format()?,
+            starting_capital,
+            formatted_pnl: pnl.format()?,
+            pnl,
+            formatted_apy: apy.map(|v| v.format()).transpose()?,
+            apy,
+            start_time,
+            end_time,
+        })
+    }
+}
+
 #[wasm_export]
 impl RaindexClient {
     /// Fetches vault data from multiple subgraphs across different networks
@@ -2293,6 +2586,110 @@ mod tests {
             );
         }
 
+        fn local_db_vault_performance(
+            starting_balance: &str,
+            total_deposits: &str,
+            total_withdrawals: &str,
+            pnl: &str,
+        ) -> LocalDbVaultPerformance {
+            let hex = |v: &str| Float::parse(v.to_string()).unwrap().as_hex();
+            LocalDbVaultPerformance {
+                vault_id: Some(U256::from(7)),
+                token: address!("0x0000000000000000000000000000000000000007"),
+                token_name: Some("Token".to_string()),
+                token_symbol: Some("TKN".to_string()),
+                token_decimals: Some(18),
+                starting_balance: hex(starting_balance),
+                ending_balance: hex("155"),
+                total_deposits: hex(total_deposits),
+                total_withdrawals: hex(total_withdrawals),
+                pnl: hex(pnl),
+                first_timestamp: Some(1_000),
+                last_timestamp: Some(2_000),
+            }
+        }
+
+        #[test]
+        fn test_try_from_local_db_vault_performance() {
+            let window = PerformanceWindow {
+                start_timestamp: Some(0),
+                end_timestamp: Some(365 * 24 * 60 * 60 / 2),
+                ..Default::default()
+            };
+            let performance = RaindexVaultPerformance::try_from_local_db_vault_performance(
+                1,
+                local_db_vault_performance("100", "50", "10", "15"),
+                &window,
+            )
+            .unwrap();
+
+            assert_eq!(performance.id(), Some(U256::from(7)));
+            assert_eq!(performance.token().symbol(), Some("TKN".to_string()));
+            assert_eq!(performance.formatted_net_deposits(), "40");
+            assert_eq!(performance.formatted_starting_capital(), "150");
+            assert_eq!(performance.formatted_pnl(), "15");
+            assert_eq!(performance.formatted_ending_balance(), "155");
+            assert_eq!(performance.start_time(), 0);
+            assert_eq!(performance.end_time(), 365 * 24 * 60 * 60 / 2);
+            // 10% over half a year
+            assert!(performance
+                .apy()
+                .unwrap()
+                .eq(Float::parse("0.2".to_string()).unwrap())
+                .unwrap());
+        }
+
+        #[test]
+        fn test_try_from_local_db_vault_performance_block_window() {
+            let window = PerformanceWindow {
+                start_block: Some(10),
+                end_block: Some(20),
+                ..Default::default()
+            };
+            let performance = RaindexVaultPerformance::try_from_local_db_vault_performance(
+                1,
+                local_db_vault_performance("0", "0", "0", "15"),
+                &window,
+            )
+            .unwrap();
+
+            assert_eq!(performance.start_time(), 1_000);
+            assert_eq!(performance.end_time(), 2_000);
+            // no capital to measure the returns against
+            assert!(performance.apy().is_none());
+            assert!(performance.formatted_apy().is_none());
+        }
+
+        #[test]
+        fn test_try_from_local_db_order_totals() {
+            let mut totals = local_db_vault_performance("100", "0", "0", "5");
+            totals.vault_id = None;
+            let performance = RaindexVaultPerformance::try_from_local_db_vault_performance(
+                1,
+                totals,
+                &PerformanceWindow::default(),
+            )
+            .unwrap();
+
+            assert_eq!(performance.id(), None);
+            assert_eq!(performance.formatted_pnl(), "5");
+            assert_eq!(performance.start_time(), 1_000);
+            assert_eq!(performance.end_time(), 2_000);
+        }
+
+        #[test]
+        fn test_try_from_local_db_vault_performance_missing_decimals() {
+            let mut vault = local_db_vault_performance("1", "0", "0", "0");
+            vault.token_decimals = None;
+            let err = RaindexVaultPerformance::try_from_local_db_vault_performance(
+                1,
+                vault,
+                &PerformanceWindow::default(),
+            )
+            .unwrap_err();
+            assert!(matches!(err, RaindexError::MissingErc20Decimals(_)));
+        }
+
         #[tokio::test]
         async fn test_try_from_local_db_maps_token_metadata() {
             // Build a minimal client; it won't be used in mapping
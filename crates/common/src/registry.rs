@@ -0,0 +1,520 @@
+use crate::dotrain_order::DotrainOrder;
+use dotrain::RainDocument;
+use futures::future::join_all;
+use rain_orderbook_app_settings::gui::{GuiCfg, NameAndDescriptionCfg};
+use rain_orderbook_app_settings::yaml::{
+    context::ContextProfile,
+    dotrain::{DotrainYaml, DotrainYamlValidation},
+    YamlError, YamlParsable,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use strict_yaml_rust::StrictYaml;
+use thiserror::Error;
+use url::Url;
+
+/// A registry of dotrain orders that share one settings file.
+///
+/// The registry file has the settings URL on its first line, followed by one
+/// `key url` line per order:
+///
+/// ```text
+/// https://example.com/shared-settings.yaml
+/// fixed-limit https://example.com/fixed-limit.rain
+/// auction-dca https://example.com/auction-dca.rain
+/// ```
+///
+/// Every order is parsed with the shared settings as an extra YAML source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DotrainRegistry {
+    /// URL the registry file was fetched from
+    pub registry_url: Url,
+    /// Raw registry file content
+    pub registry: String,
+    /// Shared settings URL from the first line of the registry
+    pub settings_url: Url,
+    /// Shared settings YAML content
+    pub settings: String,
+    /// Order key to .rain file URL
+    pub order_urls: HashMap<String, Url>,
+    /// Order key to .rain file content
+    pub orders: HashMap<String, String>,
+}
+
+#[derive(Error, Debug)]
+pub enum DotrainRegistryError {
+    #[error("Failed to fetch registry from URL: {0}")]
+    RegistryFetchError(String),
+    #[error("Failed to parse registry content")]
+    RegistryParseError,
+    #[error("Failed to fetch settings from URL: {0}")]
+    SettingsFetchError(String),
+    #[error("Failed to fetch order content from URL: {0}")]
+    OrderFetchError(String),
+    #[error("Order key not found: {0}")]
+    OrderKeyNotFound(String),
+    #[error("Invalid registry format: {0}")]
+    InvalidRegistryFormat(String),
+    #[error("HTTP request failed: {0}")]
+    HttpError(String),
+    #[error("Invalid URL: {0}")]
+    UrlParseError(#[from] url::ParseError),
+    #[error(transparent)]
+    YamlError(#[from] YamlError),
+}
+
+impl DotrainRegistryError {
+    pub fn to_readable_msg(&self) -> String {
+        match self {
+            DotrainRegistryError::RegistryFetchError(url) => {
+                format!("Unable to fetch the registry file from {}. Please check your internet connection and ensure the URL is accessible.", url)
+            }
+            DotrainRegistryError::RegistryParseError => {
+                "The registry file format is invalid. Please ensure it follows the expected format with settings URL on the first line and order entries on subsequent lines.".to_string()
+            }
+            DotrainRegistryError::SettingsFetchError(url) => {
+                format!("Unable to fetch the settings file from {}. Please check your internet connection and ensure the URL is accessible.", url)
+            }
+            DotrainRegistryError::OrderFetchError(url) => {
+                format!("Unable to fetch the order file from {}. Please check your internet connection and ensure the URL is accessible.", url)
+            }
+            DotrainRegistryError::OrderKeyNotFound(key) => {
+                format!("The order key '{}' was not found in the registry. Please check the available order keys.", key)
+            }
+            DotrainRegistryError::InvalidRegistryFormat(msg) => {
+                format!("Invalid registry format: {}", msg)
+            }
+            DotrainRegistryError::HttpError(msg) => {
+                format!("Network error: {}", msg)
+            }
+            DotrainRegistryError::UrlParseError(err) => {
+                format!("Invalid URL format: {}. Please ensure the URL is properly formatted.", err)
+            }
+            DotrainRegistryError::YamlError(err) => err.to_readable_msg(),
+        }
+    }
+}
+
+/// A problem found while validating an order, `deployment` is unset when the
+/// order itself could not be parsed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegistryOrderError {
+    pub deployment: Option<String>,
+    pub msg: String,
+    pub readable_msg: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RegistryValidation {
+    pub valid: BTreeMap<String, NameAndDescriptionCfg>,
+    pub invalid: BTreeMap<String, Vec<RegistryOrderError>>,
+}
+
+impl DotrainRegistry {
+    /// Fetches the registry file, the shared settings and every order
+    pub async fn new(registry_url: Url) -> Result<Self, DotrainRegistryError> {
+        let (registry, settings_url, order_urls) =
+            Self::fetch_and_parse_registry(&registry_url).await?;
+        let settings = Self::fetch_settings(&settings_url).await?;
+        let orders = Self::fetch_orders(&order_urls).await?;
+
+        Ok(Self {
+            registry_url,
+            registry,
+            settings_url,
+            settings,
+            order_urls,
+            orders,
+        })
+    }
+
+    /// Checks the registry file format without fetching settings or orders
+    pub async fn validate(registry_url: &Url) -> Result<(), DotrainRegistryError> {
+        Self::fetch_and_parse_registry(registry_url).await?;
+        Ok(())
+    }
+
+    /// Order keys in alphabetical order
+    pub fn order_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.order_urls.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn order(&self, order_key: &str) -> Result<&String, DotrainRegistryError> {
+        self.orders
+            .get(order_key)
+            .ok_or_else(|| DotrainRegistryError::OrderKeyNotFound(order_key.to_string()))
+    }
+
+    pub fn settings_sources(&self) -> Option<Vec<String>> {
+        if self.settings.is_empty() {
+            None
+        } else {
+            Some(vec![self.settings.clone()])
+        }
+    }
+
+    pub fn order_details(
+        &self,
+        order_key: &str,
+    ) -> Result<NameAndDescriptionCfg, DotrainRegistryError> {
+        let documents = self.yaml_documents(self.order(order_key)?)?;
+        Ok(GuiCfg::parse_order_details(documents)?)
+    }
+
+    pub fn deployment_details(
+        &self,
+        order_key: &str,
+    ) -> Result<BTreeMap<String, NameAndDescriptionCfg>, DotrainRegistryError> {
+        let documents = self.yaml_documents(self.order(order_key)?)?;
+        Ok(GuiCfg::parse_deployment_details(documents)?)
+    }
+
+    /// Parses every order and each of its deployments the way the GUI does
+    /// when a deployment is selected, collecting every problem per order
+    pub async fn validate_orders(&self) -> RegistryValidation {
+        let mut validation = RegistryValidation::default();
+        for order_key in self.order_keys() {
+            match self.validate_order(&order_key).await {
+                Ok(details) => {
+                    validation.valid.insert(order_key, details);
+                }
+                Err(errors) => {
+                    validation.invalid.insert(order_key, errors);
+                }
+            }
+        }
+        validation
+    }
+
+    async fn validate_order(
+        &self,
+        order_key: &str,
+    ) -> Result<NameAndDescriptionCfg, Vec<RegistryOrderError>> {
+        let order_error = |err: DotrainRegistryError| {
+            vec![RegistryOrderError {
+                deployment: None,
+                msg: err.to_string(),
+                readable_msg: err.to_readable_msg(),
+            }]
+        };
+        let dotrain = self.order(order_key).map_err(order_error)?;
+        let documents = self
+            .yaml_documents(dotrain)
+            .map_err(|err| order_error(err.into()))?;
+        let details = GuiCfg::parse_order_details(documents.clone())
+            .map_err(|err| order_error(err.into()))?;
+        let deployment_keys =
+            GuiCfg::parse_deployment_keys(documents).map_err(|err| order_error(err.into()))?;
+
+        let mut errors = vec![];
+        for deployment_key in deployment_keys {
+            if let Err((msg, readable_msg)) =
+                self.validate_deployment(dotrain, &deployment_key).await
+            {
+                errors.push(RegistryOrderError {
+                    deployment: Some(deployment_key),
+                    msg,
+                    readable_msg,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(details)
+        } else {
+            Err(errors)
+        }
+    }
+
+    async fn validate_deployment(
+        &self,
+        dotrain: &str,
+        deployment_key: &str,
+    ) -> Result<(), (String, String)> {
+        let dotrain_order = DotrainOrder::create_with_profile(
+            dotrain.to_string(),
+            self.settings_sources(),
+            ContextProfile::gui(deployment_key.to_string()),
+        )
+        .await
+        .map_err(|err| (err.to_string(), err.to_readable_msg()))?;
+
+        let dotrain_yaml = dotrain_order.dotrain_yaml();
+        dotrain_yaml
+            .get_deployment(deployment_key)
+            .and_then(|_| dotrain_yaml.get_gui(deployment_key))
+            .map_err(|err| (err.to_string(), err.to_readable_msg()))?;
+        Ok(())
+    }
+
+    fn yaml_documents(&self, dotrain: &str) -> Result<Vec<Arc<RwLock<StrictYaml>>>, YamlError> {
+        let frontmatter = RainDocument::get_front_matter(dotrain)
+            .unwrap_or("")
+            .to_string();
+        let mut sources = vec![frontmatter];
+        if let Some(settings) = self.settings_sources() {
+            sources.extend(settings);
+        }
+        Ok(DotrainYaml::new(sources, DotrainYamlValidation::default())?.documents)
+    }
+
+    pub async fn fetch_and_parse_registry(
+        registry_url: &Url,
+    ) -> Result<(String, Url, HashMap<String, Url>), DotrainRegistryError> {
+        let registry_content = Self::fetch_url_content(registry_url).await?;
+        let (settings_url, order_urls) = Self::parse_registry_content(&registry_content)?;
+        Ok((registry_content, settings_url, order_urls))
+    }
+
+    pub fn parse_registry_content(
+        content: &str,
+    ) -> Result<(Url, HashMap<String, Url>), DotrainRegistryError> {
+        let lines: Vec<&str> = content
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if lines.is_empty() {
+            return Err(DotrainRegistryError::InvalidRegistryFormat(
+                "Registry file is empty".to_string(),
+            ));
+        }
+
+        let first_line = lines[0];
+        if first_line.contains(' ') {
+            return Err(DotrainRegistryError::InvalidRegistryFormat(
+                "First line should be a settings URL without a key".to_string(),
+            ));
+        }
+
+        let settings_url = Url::parse(first_line)?;
+        let mut order_urls = HashMap::new();
+
+        for line in &lines[1..] {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() != 2 {
+                return Err(DotrainRegistryError::InvalidRegistryFormat(format!(
+                    "Invalid order entry format: '{}'. Expected: 'key url'",
+                    line
+                )));
+            }
+
+            let key = parts[0].to_string();
+            let url = Url::parse(parts[1])?;
+
+            order_urls.insert(key, url);
+        }
+
+        Ok((settings_url, order_urls))
+    }
+
+    pub async fn fetch_url_content(url: &Url) -> Result<String, DotrainRegistryError> {
+        let response = reqwest::get(url.as_str())
+            .await
+            .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(DotrainRegistryError::HttpError(format!(
+                "HTTP {}",
+                response.status()
+            )));
+        }
+
+        response
+            .text()
+            .await
+            .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))
+    }
+
+    pub async fn fetch_settings(settings_url: &Url) -> Result<String, DotrainRegistryError> {
+        Self::fetch_url_content(settings_url).await
+    }
+
+    pub async fn fetch_orders(
+        order_urls: &HashMap<String, Url>,
+    ) -> Result<HashMap<String, String>, DotrainRegistryError> {
+        let mut futures = Vec::new();
+
+        for (key, url) in order_urls {
+            let key_clone = key.clone();
+            let url_clone = url.clone();
+            futures.push(async move {
+                let content = reqwest::get(url_clone.as_str())
+                    .await
+                    .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?
+                    .text()
+                    .await
+                    .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?;
+                Ok::<(String, String), DotrainRegistryError>((key_clone, content))
+            });
+        }
+
+        let results = join_all(futures).await;
+        let mut orders = HashMap::new();
+
+        for result in results {
+            let (key, content) = result?;
+            orders.insert(key, content);
+        }
+
+        Ok(orders)
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::registry::{mock_order, mock_settings};
+    use httpmock::MockServer;
+
+    fn registry(orders: Vec<(&str, String)>) -> DotrainRegistry {
+        DotrainRegistry {
+            registry_url: Url::parse("https://example.com/registry.txt").unwrap(),
+            registry: String::new(),
+            settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
+            settings: mock_settings(),
+            order_urls: orders
+                .iter()
+                .map(|(key, _)| {
+                    (
+                        key.to_string(),
+                        Url::parse(&format!("https://example.com/{key}.rain")).unwrap(),
+                    )
+                })
+                .collect(),
+            orders: orders
+                .into_iter()
+                .map(|(key, content)| (key.to_string(), content))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_registry_content() {
+        let (settings_url, order_urls) = DotrainRegistry::parse_registry_content(
+            "https://example.com/settings.yaml\n\nfixed-limit https://example.com/fixed-limit.rain\n  auction-dca https://example.com/auction-dca.rain  ",
+        )
+        .unwrap();
+        assert_eq!(settings_url.as_str(), "https://example.com/settings.yaml");
+        assert_eq!(order_urls.len(), 2);
+        assert_eq!(
+            order_urls["auction-dca"].as_str(),
+            "https://example.com/auction-dca.rain"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_registry_content() {
+        let err = DotrainRegistry::parse_registry_content("").unwrap_err();
+        assert!(
+            matches!(err, DotrainRegistryError::InvalidRegistryFormat(msg) if msg == "Registry file is empty")
+        );
+
+        let err = DotrainRegistry::parse_registry_content("invalid first line").unwrap_err();
+        assert!(matches!(
+            err,
+            DotrainRegistryError::InvalidRegistryFormat(_)
+        ));
+
+        let err =
+            DotrainRegistry::parse_registry_content("https://example.com/settings.yaml\ninvalid")
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid registry format: Invalid order entry format: 'invalid'. Expected: 'key url'"
+        );
+
+        let err = DotrainRegistry::parse_registry_content("not-a-url").unwrap_err();
+        assert!(matches!(err, DotrainRegistryError::UrlParseError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_new_and_validate() {
+        let server = MockServer::start_async().await;
+        let registry_content = format!(
+            "{}/settings.yaml\nfirst {}/first.rain\nsecond {}/second.rain",
+            server.url(""),
+            server.url(""),
+            server.url("")
+        );
+        server.mock(|when, then| {
+            when.method("GET").path("/registry.txt");
+            then.status(200).body(registry_content.clone());
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/settings.yaml");
+            then.status(200).body(mock_settings());
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/first.rain");
+            then.status(200).body(mock_order("flare"));
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/second.rain");
+            then.status(200).body("second");
+        });
+        server.mock(|when, then| {
+            when.method("GET").path("/broken.txt");
+            then.status(500);
+        });
+
+        let registry_url = Url::parse(&server.url("/registry.txt")).unwrap();
+        DotrainRegistry::validate(&registry_url).await.unwrap();
+
+        let registry = DotrainRegistry::new(registry_url.clone()).await.unwrap();
+        assert_eq!(registry.registry_url, registry_url);
+        assert_eq!(registry.registry, registry_content);
+        assert_eq!(registry.settings, mock_settings());
+        assert_eq!(registry.order_keys(), vec!["first", "second"]);
+        assert_eq!(registry.order("first").unwrap(), &mock_order("flare"));
+        assert_eq!(registry.order("second").unwrap(), "second");
+
+        let err = DotrainRegistry::new(Url::parse(&server.url("/broken.txt")).unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DotrainRegistryError::HttpError(msg) if msg.contains("HTTP 500")));
+    }
+
+    #[test]
+    fn test_order_and_deployment_details() {
+        let registry = registry(vec![("fixed-limit", mock_order("flare"))]);
+
+        let details = registry.order_details("fixed-limit").unwrap();
+        assert_eq!(details.name, "Test gui");
+        assert_eq!(details.description, "Test description");
+
+        let deployments = registry.deployment_details("fixed-limit").unwrap();
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(deployments["flare"].name, "Flare order name");
+
+        let err = registry.deployment_details("missing").unwrap_err();
+        assert!(matches!(err, DotrainRegistryError::OrderKeyNotFound(key) if key == "missing"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_orders() {
+        let registry = registry(vec![
+            ("valid", mock_order("flare")),
+            ("not-dotrain", "not-a-valid-dotrain".to_string()),
+            ("bad-deployment", mock_order("missing-order")),
+        ]);
+
+        let validation = registry.validate_orders().await;
+        assert_eq!(validation.valid.len(), 1);
+        assert_eq!(validation.valid["valid"].name, "Test gui");
+
+        assert_eq!(validation.invalid.len(), 2);
+        let errors = &validation.invalid["not-dotrain"];
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].deployment, None);
+
+        let errors = &validation.invalid["bad-deployment"];
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].deployment, Some("flare".to_string()));
+        assert!(errors[0].msg.contains("missing-order"));
+    }
+}
@@ -54,7 +54,7 @@ impl RemoveOrderArgs {
         transaction_args: TransactionArgs,
         transaction_status_changed: S,
     ) -> Result<(), RemoveOrderArgsError> {
-        let (ledger_client, _) = transaction_args.clone().try_into_ledger_client().await?;
+        let (client, _) = transaction_args.clone().try_into_signing_client().await?;
 
         let remove_order_call: removeOrder3Call = self.try_into()?;
         let params = transaction_args.try_into_write_contract_parameters(
@@ -62,7 +62,7 @@ impl RemoveOrderArgs {
             transaction_args.orderbook_address,
         )?;
 
-        WriteTransaction::new(ledger_client, params, 4, transaction_status_changed)
+        WriteTransaction::new(client, params, 4, transaction_status_changed)
             .execute()
             .await?;
 
@@ -159,6 +159,7 @@ mod tests {
             chain_id: Some(1),
             max_priority_fee_per_gas: Some(200),
             max_fee_per_gas: Some(100),
+            ..Default::default()
         };
 
         let params = args
@@ -1,9 +1,20 @@
-use alloy::primitives::B256;
+use crate::local_db::{
+    fetch::{fetch_logs, FetchConfig},
+    LocalDbError,
+};
+use crate::rpc_client::{RpcClient, RpcClientError};
+use alloy::{
+    primitives::{Address, B256},
+    rpc::types::Filter,
+    sol_types::SolEvent,
+};
 use rain_interpreter_eval::{
     error::ForkCallError,
     fork::{Forker, NewForkedEvm},
     trace::{RainEvalResult, RainEvalResultFromRawCallResultError},
 };
+use rain_orderbook_bindings::IRaindexV6::{ClearV3, TakeOrderV3};
+use std::collections::HashSet;
 use url::Url;
 
 pub struct NewTradeReplayer {
@@ -19,6 +30,52 @@ pub enum TradeReplayerError {
     ForkerError(#[from] ForkCallError),
     #[error(transparent)]
     RainEvalResultConversion(#[from] RainEvalResultFromRawCallResultError),
+    #[error(transparent)]
+    RpcClient(#[from] RpcClientError),
+    #[error(transparent)]
+    LogFetch(#[from] LocalDbError),
+}
+
+/// A transaction that took or cleared orders, the block is known when it
+/// came from an indexer or the orderbook's logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeTransaction {
+    pub tx_hash: B256,
+    pub block_number: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct TradeReplay {
+    pub transaction: TradeTransaction,
+    pub result: Result<RainEvalResult, TradeReplayerError>,
+}
+
+/// Finds every transaction that emitted a take or clear on the orderbook
+/// within the inclusive block range, in chain order. The range is fetched in
+/// chunks like the local DB sync, halving any the provider rejects as too big.
+pub async fn find_trade_transactions(
+    rpc_urls: Vec<Url>,
+    orderbook: Address,
+    from_block: u64,
+    to_block: u64,
+) -> Result<Vec<TradeTransaction>, TradeReplayerError> {
+    let rpc_client = RpcClient::new_with_urls(rpc_urls)?;
+    let filter = Filter::new()
+        .address(orderbook)
+        .from_block(from_block)
+        .to_block(to_block)
+        .event_signature(vec![TakeOrderV3::SIGNATURE_HASH, ClearV3::SIGNATURE_HASH]);
+    let logs = fetch_logs(&rpc_client, &filter, &FetchConfig::default()).await?;
+
+    let mut seen = HashSet::new();
+    Ok(logs
+        .into_iter()
+        .filter(|log| seen.insert(log.transaction_hash))
+        .map(|log| TradeTransaction {
+            tx_hash: log.transaction_hash,
+            block_number: Some(log.block_number.saturating_to()),
+        })
+        .collect())
 }
 
 impl TradeReplayer {
@@ -40,6 +97,20 @@ impl TradeReplayer {
         let res = self.forker.replay_transaction(tx_hash).await?;
         Ok(res.try_into()?)
     }
+
+    /// Replays each transaction in turn, a failed replay does not stop the
+    /// rest of the batch
+    pub async fn replay_trades(&mut self, transactions: Vec<TradeTransaction>) -> Vec<TradeReplay> {
+        let mut replays = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let result = self.replay_tx(transaction.tx_hash).await;
+            replays.push(TradeReplay {
+                transaction,
+                result,
+            });
+        }
+        replays
+    }
 }
 
 #[cfg(test)]
@@ -249,6 +320,40 @@ amount price: 2 1;
 
         assert_eq!(res.traces[1].stack, expected_stack);
         assert_eq!(res.traces.len(), 2);
+
+        let transactions = find_trade_transactions(
+            vec![local_evm.url().as_str().try_into().unwrap()],
+            *orderbook.address(),
+            0,
+            tx.block_number.unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            transactions,
+            vec![TradeTransaction {
+                tx_hash: tx.transaction_hash,
+                block_number: tx.block_number,
+            }]
+        );
+
+        let mut transactions = transactions;
+        transactions.push(TradeTransaction {
+            tx_hash: B256::ZERO,
+            block_number: None,
+        });
+        let replays = replayer.replay_trades(transactions).await;
+        assert_eq!(replays.len(), 2);
+        assert_eq!(
+            replays[0].result.as_ref().unwrap().traces[1].stack,
+            expected_stack
+        );
+        assert!(matches!(
+            replays[1].result,
+            Err(TradeReplayerError::ForkerError(
+                ForkCallError::ReplayTransactionError(_)
+            ))
+        ));
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -91,6 +91,33 @@ impl RpcClient {
         })
     }
 
+    /// Connects to HyperRPC at a URL template, in which `{chain-id}` and
+    /// `{api-token}` are replaced by the chain ID and the API token.
+    pub fn new_with_hyper_rpc_url(
+        chain_id: u32,
+        url_template: &str,
+        api_token: &str,
+    ) -> Result<Self, RpcClientError> {
+        let url = Self::build_hyper_url_from_template(url_template, chain_id, api_token)?;
+        let provider = Arc::new(mk_read_provider(std::slice::from_ref(&url))?);
+        Ok(Self {
+            chain_id: Some(chain_id),
+            rpc_urls: vec![url],
+            provider,
+        })
+    }
+
+    pub fn build_hyper_url_from_template(
+        url_template: &str,
+        chain_id: u32,
+        api_token: &str,
+    ) -> Result<Url, RpcClientError> {
+        let url = url_template
+            .replace("{chain-id}", &chain_id.to_string())
+            .replace("{api-token}", api_token);
+        Ok(Url::parse(&url)?)
+    }
+
     pub fn build_hyper_url(chain_id: u32, api_token: &str) -> Result<Url, RpcClientError> {
         let base = match chain_id {
             137 => "https://polygon.rpc.hypersync.xyz",
@@ -321,6 +348,33 @@ mod tests {
         assert!(url.unwrap().to_string().contains("test_token"));
     }
 
+    #[test]
+    fn test_build_hyper_url_from_template() {
+        let url = RpcClient::build_hyper_url_from_template(
+            "https://{chain-id}.rpc.hypersync.xyz/{api-token}",
+            1,
+            "test_token",
+        )
+        .unwrap();
+        assert_eq!(url.as_str(), "https://1.rpc.hypersync.xyz/test_token");
+
+        let client = RpcClient::new_with_hyper_rpc_url(
+            10,
+            "https://optimism.rpc.hypersync.xyz/{api-token}",
+            "test_token",
+        )
+        .unwrap();
+        assert_eq!(
+            client.rpc_urls()[0].as_str(),
+            "https://optimism.rpc.hypersync.xyz/test_token"
+        );
+
+        assert!(matches!(
+            RpcClient::build_hyper_url_from_template("not a url", 1, "test_token"),
+            Err(RpcClientError::UrlParse(_))
+        ));
+    }
+
     #[test]
     fn test_build_hyper_url_unsupported_chain_id() {
         let url = RpcClient::build_hyper_url(9999, "test_token");
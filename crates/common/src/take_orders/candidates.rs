@@ -7,7 +7,7 @@ use rain_orderbook_bindings::IRaindexV6::{OrderV4, SignedContextV1};
 #[cfg(target_family = "wasm")]
 use std::str::FromStr;
 
-fn indices_in_bounds(order: &OrderV4, input_index: u32, output_index: u32) -> bool {
+pub(crate) fn indices_in_bounds(order: &OrderV4, input_index: u32, output_index: u32) -> bool {
     (input_index as usize) < order.validInputs.len()
         && (output_index as usize) < order.validOutputs.len()
 }
@@ -24,7 +24,7 @@ fn matches_direction(
     order_input_token == input_token && order_output_token == output_token
 }
 
-fn has_capacity(
+pub(crate) fn has_capacity(
     data: &crate::raindex_client::order_quotes::RaindexOrderQuoteValue,
 ) -> Result<bool, RaindexError> {
     Ok(data.max_output.gt(Float::zero()?)?)
@@ -42,7 +42,7 @@ pub struct TakeOrderCandidate {
     pub signed_context: Vec<SignedContextV1>,
 }
 
-fn get_orderbook_address(order: &RaindexOrder) -> Result<Address, RaindexError> {
+pub(crate) fn get_orderbook_address(order: &RaindexOrder) -> Result<Address, RaindexError> {
     #[cfg(target_family = "wasm")]
     {
         Ok(Address::from_str(&order.orderbook())?)
@@ -59,6 +59,31 @@ pub async fn build_take_order_candidates_for_pair(
     output_token: Address,
     block_number: Option<u64>,
     chunk_size: Option<u32>,
+) -> Result<Vec<TakeOrderCandidate>, RaindexError> {
+    build_candidates(
+        orders,
+        Some((input_token, output_token)),
+        block_number,
+        chunk_size,
+    )
+    .await
+}
+
+/// Builds candidates for every quoted IO pair of `orders`, whatever tokens
+/// it trades. Used to build the token graph for multi-hop routing.
+pub async fn build_take_order_candidates(
+    orders: &[RaindexOrder],
+    block_number: Option<u64>,
+    chunk_size: Option<u32>,
+) -> Result<Vec<TakeOrderCandidate>, RaindexError> {
+    build_candidates(orders, None, block_number, chunk_size).await
+}
+
+async fn build_candidates(
+    orders: &[RaindexOrder],
+    direction: Option<(Address, Address)>,
+    block_number: Option<u64>,
+    chunk_size: Option<u32>,
 ) -> Result<Vec<TakeOrderCandidate>, RaindexError> {
     let all_quotes = get_order_quotes_batch(orders, block_number, chunk_size).await?;
 
@@ -69,6 +94,23 @@ pub async fn build_take_order_candidates_for_pair(
         let oracle_url = order.oracle_url();
 
         for quote in &quotes {
+            let (input_token, output_token) = match direction {
+                Some(direction) => direction,
+                None => {
+                    if !indices_in_bounds(
+                        &order_v4,
+                        quote.pair.input_index,
+                        quote.pair.output_index,
+                    ) {
+                        continue;
+                    }
+                    (
+                        order_v4.validInputs[quote.pair.input_index as usize].token,
+                        order_v4.validOutputs[quote.pair.output_index as usize].token,
+                    )
+                }
+            };
+
             let signed_context = match &oracle_url {
                 Some(url) => {
                     fetch_oracle_for_pair(
@@ -104,7 +146,7 @@ pub async fn build_take_order_candidates_for_pair(
 }
 
 /// Fetch signed context from an order's oracle endpoint for a specific IO pair.
-async fn fetch_oracle_for_pair(
+pub(crate) async fn fetch_oracle_for_pair(
     oracle_url: &str,
     order: &OrderV4,
     input_io_index: u32,
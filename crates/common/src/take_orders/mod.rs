@@ -3,7 +3,9 @@ pub mod config;
 pub mod preflight;
 pub mod simulation;
 
-pub use candidates::{build_take_order_candidates_for_pair, TakeOrderCandidate};
+pub use candidates::{
+    build_take_order_candidates, build_take_order_candidates_for_pair, TakeOrderCandidate,
+};
 pub use config::{
     build_take_orders_config_from_simulation, BuiltTakeOrdersConfig, ParsedTakeOrdersMode,
     TakeOrdersMode,
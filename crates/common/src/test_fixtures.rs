@@ -0,0 +1,83 @@
+pub mod registry {
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
+
+    /// Settings of the registry fixtures, one flare orderbook and token
+    pub fn mock_settings() -> String {
+        format!(
+            r#"version: {version}
+networks:
+  flare:
+    rpcs:
+      - https://rpc.ankr.com/flare
+    chain-id: 14
+    currency: FLR
+subgraphs:
+  flare: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-flare/0.8/gn
+rainlangs:
+  flare:
+    address: 0x1111111111111111111111111111111111111111
+    network: flare
+orderbooks:
+  flare:
+    address: 0xCEe8Cd002F151A536394E564b84076c41bBBcD4d
+    network: flare
+    subgraph: flare
+    deployment-block: 0
+tokens:
+  token1:
+    address: 0x4200000000000000000000000000000000000042
+    network: flare
+"#,
+            version = SpecVersion::current()
+        )
+    }
+
+    /// Order with a `test-binding` gui field, deployed with the
+    /// `deployment_order` order
+    pub fn mock_order(deployment_order: &str) -> String {
+        format!(
+            r#"gui:
+  name: Test gui
+  description: Test description
+  deployments:
+    flare:
+      name: Flare order name
+      description: Flare order description
+      deposits:
+        - token: token1
+          presets:
+            - "0"
+      fields:
+        - binding: test-binding
+          name: Test binding
+          presets:
+            - value: "0xbeef"
+          default: 10
+scenarios:
+  flare:
+    rainlang: flare
+    bindings:
+      test-binding: 0
+orders:
+  flare:
+    rainlang: flare
+    orderbook: flare
+    inputs:
+      - token: token1
+    outputs:
+      - token: token1
+deployments:
+  flare:
+    scenario: flare
+    order: {deployment_order}
+---
+#test-binding !
+#calculate-io
+_ _: test-binding 0;
+#handle-io
+:;
+#handle-add-order
+:;"#
+        )
+    }
+}
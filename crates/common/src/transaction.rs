@@ -1,19 +1,30 @@
-use alloy::primitives::{ruint::FromUintError, Address};
+use alloy::primitives::{ruint::FromUintError, Address, Bytes, B256, U256};
 use alloy::sol_types::SolCall;
 use rain_math_float::FloatError;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[cfg(not(target_family = "wasm"))]
 use alloy::{
-    network::AnyNetwork,
+    consensus::{SignableTransaction, TxEip1559},
+    network::{AnyNetwork, EthereumWallet},
+    primitives::TxKind,
     providers::{Provider, ProviderBuilder, WalletProvider},
-    signers::ledger::{HDPath, LedgerError, LedgerSigner},
+    rpc::types::{TransactionInput, TransactionRequest},
+    serde::WithOtherFields,
+    signers::{
+        ledger::{HDPath, LedgerError, LedgerSigner},
+        local::{LocalSignerError, PrivateKeySigner},
+    },
+    transports::TransportError,
 };
 use alloy_ethers_typecast::{
     ReadableClient, ReadableClientError, WritableClientError, WriteContractParameters,
     WriteContractParametersBuilder, WriteContractParametersBuilderError,
 };
+#[cfg(not(target_family = "wasm"))]
+use rain_orderbook_bindings::provider::{mk_read_provider, ReadProviderError};
 
 #[derive(Error, Debug)]
 pub enum WritableTransactionExecuteError {
@@ -45,11 +56,83 @@ pub enum TransactionArgsError {
     Ledger(#[from] LedgerError),
     #[cfg(not(target_family = "wasm"))]
     #[error(transparent)]
+    LocalSigner(#[from] LocalSignerError),
+    #[cfg(not(target_family = "wasm"))]
+    #[error(transparent)]
     Url(#[from] url::ParseError),
+    #[cfg(not(target_family = "wasm"))]
+    #[error(transparent)]
+    Rpc(#[from] TransportError),
+    #[cfg(not(target_family = "wasm"))]
+    #[error(transparent)]
+    ReadProvider(#[from] ReadProviderError),
+    #[error("Environment variable {0} is not set")]
+    MissingEnvVar(String),
+    #[error("Password is required to decrypt keystore {0}")]
+    MissingKeystorePassword(PathBuf),
+    #[error("Signer cannot send transactions, they must be signed externally")]
+    UnsignedSigner,
     #[error("Invalid input args: {0}")]
     InvalidArgs(String),
 }
 
+/// How write transactions are authorised.
+#[derive(Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TransactionSigner {
+    /// Ledger hardware wallet, using the `derivation_index` account.
+    #[default]
+    Ledger,
+    /// Hex encoded private key read from the named environment variable.
+    PrivateKeyEnv { env_var: String },
+    /// Encrypted JSON keystore file. The password is never serialized.
+    Keystore {
+        path: PathBuf,
+        #[serde(skip)]
+        password: Option<String>,
+    },
+    /// Nothing is signed or sent. Transactions are populated for `from` so
+    /// they can be signed externally.
+    Unsigned { from: Address },
+}
+
+impl std::fmt::Debug for TransactionSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ledger => write!(f, "Ledger"),
+            Self::PrivateKeyEnv { env_var } => f
+                .debug_struct("PrivateKeyEnv")
+                .field("env_var", env_var)
+                .finish(),
+            Self::Keystore { path, password } => f
+                .debug_struct("Keystore")
+                .field("path", path)
+                .field("password", &password.as_ref().map(|_| "<redacted>"))
+                .finish(),
+            Self::Unsigned { from } => f.debug_struct("Unsigned").field("from", from).finish(),
+        }
+    }
+}
+
+/// A fully populated EIP-1559 transaction, ready to be signed externally.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedTransaction {
+    pub chain_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub value: U256,
+    pub data: Bytes,
+    /// `0x02 || rlp([...])` payload the signer has to sign.
+    pub signing_payload: Bytes,
+    /// keccak256 of `signing_payload`.
+    pub signing_hash: B256,
+}
+
 #[derive(Clone, Serialize, Deserialize, Default)]
 pub struct TransactionArgs {
     pub orderbook_address: Address,
@@ -58,6 +141,8 @@ pub struct TransactionArgs {
     pub rpcs: Vec<String>,
     pub max_priority_fee_per_gas: Option<u128>,
     pub max_fee_per_gas: Option<u128>,
+    #[serde(default)]
+    pub signer: TransactionSigner,
 }
 
 impl TransactionArgs {
@@ -131,6 +216,147 @@ impl TransactionArgs {
         // if we are here, we have tried all rpcs and failed
         Err(err.unwrap())
     }
+
+    /// Builds a wallet client for the configured signer. Unsigned mode has no
+    /// wallet, use [`Self::try_populate_unsigned_transaction`] instead.
+    #[cfg(not(target_family = "wasm"))]
+    pub async fn try_into_signing_client(
+        self,
+    ) -> Result<
+        (
+            impl Provider<AnyNetwork> + WalletProvider<AnyNetwork> + Clone + std::fmt::Debug,
+            Address,
+        ),
+        TransactionArgsError,
+    > {
+        let url = self.first_rpc_url()?;
+        let (wallet, address) = self.try_into_wallet().await?;
+
+        let provider = ProviderBuilder::new_with_network::<AnyNetwork>()
+            .wallet(wallet)
+            .connect_http(url);
+
+        Ok((provider, address))
+    }
+
+    /// Address that transactions will be sent from.
+    #[cfg(not(target_family = "wasm"))]
+    pub async fn try_get_sender(&self) -> Result<Address, TransactionArgsError> {
+        match &self.signer {
+            TransactionSigner::Unsigned { from } => Ok(*from),
+            _ => Ok(self.try_into_wallet().await?.1),
+        }
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    async fn try_into_wallet(&self) -> Result<(EthereumWallet, Address), TransactionArgsError> {
+        match &self.signer {
+            TransactionSigner::Ledger => {
+                let derivation_index = self.derivation_index.unwrap_or(0);
+                let signer =
+                    LedgerSigner::new(HDPath::LedgerLive(derivation_index), self.chain_id).await?;
+                let address = signer.get_address().await?;
+                Ok((signer.into(), address))
+            }
+            TransactionSigner::PrivateKeyEnv { env_var } => {
+                let key = std::env::var(env_var)
+                    .map_err(|_| TransactionArgsError::MissingEnvVar(env_var.clone()))?;
+                let signer: PrivateKeySigner = key.trim().parse()?;
+                let address = signer.address();
+                Ok((signer.into(), address))
+            }
+            TransactionSigner::Keystore { path, password } => {
+                let password = password
+                    .as_ref()
+                    .ok_or_else(|| TransactionArgsError::MissingKeystorePassword(path.clone()))?;
+                let signer = PrivateKeySigner::decrypt_keystore(path, password)?;
+                let address = signer.address();
+                Ok((signer.into(), address))
+            }
+            TransactionSigner::Unsigned { .. } => Err(TransactionArgsError::UnsignedSigner),
+        }
+    }
+
+    /// Populates nonce, gas limit, fees and chain ID for `params` so the
+    /// transaction can be signed by an external tool. Reads fall back across
+    /// all configured `rpcs`.
+    ///
+    /// `max_fee_per_gas` / `max_priority_fee_per_gas` are taken from the args
+    /// when set and estimated from the network otherwise.
+    #[cfg(not(target_family = "wasm"))]
+    pub async fn try_populate_unsigned_transaction<T: SolCall + Clone>(
+        &self,
+        params: &WriteContractParameters<T>,
+    ) -> Result<UnsignedTransaction, TransactionArgsError> {
+        let from = self.try_get_sender().await?;
+        let rpcs = self
+            .rpcs
+            .iter()
+            .map(|rpc| rpc.parse())
+            .collect::<Result<Vec<url::Url>, _>>()?;
+        let provider = mk_read_provider(&rpcs)?;
+
+        let data = Bytes::from(params.call.abi_encode());
+        // Orderbook and token calls are never payable
+        let value = U256::ZERO;
+        let request = TransactionRequest::default()
+            .from(from)
+            .to(params.address)
+            .input(TransactionInput::new(data.clone()));
+
+        let chain_id = match self.chain_id {
+            Some(chain_id) => chain_id,
+            None => provider.get_chain_id().await?,
+        };
+        let nonce = provider.get_transaction_count(from).pending().await?;
+        let gas_limit = provider.estimate_gas(WithOtherFields::new(request)).await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+                (Some(max_fee), Some(max_priority_fee)) => (max_fee, max_priority_fee),
+                (max_fee, max_priority_fee) => {
+                    let estimate = provider.estimate_eip1559_fees().await?;
+                    (
+                        max_fee.unwrap_or(estimate.max_fee_per_gas),
+                        max_priority_fee.unwrap_or(estimate.max_priority_fee_per_gas),
+                    )
+                }
+            };
+
+        let tx = TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to: TxKind::Call(params.address),
+            value,
+            access_list: Default::default(),
+            input: data.clone(),
+        };
+
+        Ok(UnsignedTransaction {
+            chain_id,
+            from,
+            to: params.address,
+            nonce,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            value,
+            data,
+            signing_payload: tx.encoded_for_signing().into(),
+            signing_hash: tx.signature_hash(),
+        })
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn first_rpc_url(&self) -> Result<url::Url, TransactionArgsError> {
+        let rpc = self
+            .rpcs
+            .first()
+            .ok_or_else(|| TransactionArgsError::InvalidArgs("rpcs cannot be empty".into()))?;
+        Ok(rpc.parse()?)
+    }
 }
 
 #[cfg(all(test, not(target_family = "wasm")))]
@@ -150,6 +376,7 @@ mod tests {
             rpcs: vec!["https://mainnet.infura.io/v3/your-api-key".to_string()],
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
+            signer: TransactionSigner::Ledger,
         };
 
         let call = vaultBalance2Call {
@@ -174,6 +401,7 @@ mod tests {
             rpcs: vec!["https://mainnet.infura.io/v3/your-api-key".to_string()],
             max_priority_fee_per_gas: Some(100),
             max_fee_per_gas: Some(200),
+            signer: TransactionSigner::Ledger,
         };
 
         let call = vaultBalance2Call {
@@ -217,6 +445,7 @@ mod tests {
             rpcs: vec![server.url("/rpc")],
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
+            signer: TransactionSigner::Ledger,
         };
 
         args.try_fill_chain_id().await.unwrap();
@@ -244,6 +473,7 @@ mod tests {
             rpcs: vec![server.url("/rpc")],
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
+            signer: TransactionSigner::Ledger,
         };
 
         let err = args.try_fill_chain_id().await.unwrap_err();
@@ -278,12 +508,231 @@ mod tests {
             rpcs: vec![server.url("/rpc")],
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
+            signer: TransactionSigner::Ledger,
         };
 
         let result = args.clone().try_into_ledger_client().await;
         assert!(result.is_err());
     }
 
+    // Anvil's first default account
+    const TEST_PRIVATE_KEY: &str =
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TEST_ADDRESS: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    #[tokio::test]
+    async fn test_try_get_sender_private_key_env() {
+        let env_var = "RAIN_TEST_TRANSACTION_PRIVATE_KEY";
+        std::env::set_var(env_var, TEST_PRIVATE_KEY);
+
+        let args = TransactionArgs {
+            signer: TransactionSigner::PrivateKeyEnv {
+                env_var: env_var.to_string(),
+            },
+            ..Default::default()
+        };
+        assert_eq!(args.try_get_sender().await.unwrap(), TEST_ADDRESS);
+
+        let args = TransactionArgs {
+            signer: TransactionSigner::PrivateKeyEnv {
+                env_var: "RAIN_TEST_TRANSACTION_UNSET_KEY".to_string(),
+            },
+            ..Default::default()
+        };
+        let err = args.try_get_sender().await.unwrap_err();
+        assert!(
+            matches!(&err, TransactionArgsError::MissingEnvVar(var) if var == "RAIN_TEST_TRANSACTION_UNSET_KEY")
+        );
+    }
+
+    // Web3 Secret Storage test vector, password "testpassword"
+    const TEST_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    #[tokio::test]
+    async fn test_try_get_sender_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+        std::fs::write(&path, TEST_KEYSTORE).unwrap();
+
+        let args = TransactionArgs {
+            signer: TransactionSigner::Keystore {
+                path: path.clone(),
+                password: Some("testpassword".to_string()),
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            args.try_get_sender().await.unwrap(),
+            address!("008aeeda4d805471df9b2a5b0f38a0c3bcba786b")
+        );
+
+        let args = TransactionArgs {
+            signer: TransactionSigner::Keystore {
+                path: path.clone(),
+                password: None,
+            },
+            ..Default::default()
+        };
+        let err = args.try_get_sender().await.unwrap_err();
+        assert!(matches!(err, TransactionArgsError::MissingKeystorePassword(p) if p == path));
+
+        let args = TransactionArgs {
+            signer: TransactionSigner::Keystore {
+                path,
+                password: Some("wrong".to_string()),
+            },
+            ..Default::default()
+        };
+        let err = args.try_get_sender().await.unwrap_err();
+        assert!(matches!(err, TransactionArgsError::LocalSigner(_)));
+    }
+
+    #[tokio::test]
+    async fn test_try_into_signing_client_unsigned_err() {
+        let args = TransactionArgs {
+            rpcs: vec!["http://localhost:8545".to_string()],
+            signer: TransactionSigner::Unsigned { from: TEST_ADDRESS },
+            ..Default::default()
+        };
+
+        assert_eq!(args.try_get_sender().await.unwrap(), TEST_ADDRESS);
+        let err = args.try_into_signing_client().await.unwrap_err();
+        assert!(matches!(err, TransactionArgsError::UnsignedSigner));
+    }
+
+    #[test]
+    fn test_keystore_password_not_serialized() {
+        let signer = TransactionSigner::Keystore {
+            path: PathBuf::from("/tmp/keystore.json"),
+            password: Some("secret".to_string()),
+        };
+
+        let json = serde_json::to_string(&signer).unwrap();
+        assert!(!json.contains("secret"));
+
+        let deserialized: TransactionSigner = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized,
+            TransactionSigner::Keystore {
+                path: PathBuf::from("/tmp/keystore.json"),
+                password: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_try_populate_unsigned_transaction() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_getTransactionCount");
+            then.status(200)
+                .body(r#"{ "jsonrpc": "2.0", "id": 0, "result": "0x7" }"#);
+        });
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_estimateGas");
+            then.status(200)
+                .body(r#"{ "jsonrpc": "2.0", "id": 0, "result": "0x5208" }"#);
+        });
+
+        let args = TransactionArgs {
+            chain_id: Some(1),
+            rpcs: vec![server.url("/rpc")],
+            max_priority_fee_per_gas: Some(2),
+            max_fee_per_gas: Some(30),
+            signer: TransactionSigner::Unsigned { from: TEST_ADDRESS },
+            ..Default::default()
+        };
+        let call = vaultBalance2Call {
+            owner: Address::ZERO,
+            token: Address::ZERO,
+            vaultId: B256::ZERO,
+        };
+        let params = args
+            .try_into_write_contract_parameters(call.clone(), Address::repeat_byte(0x11))
+            .unwrap();
+
+        let tx = args
+            .try_populate_unsigned_transaction(&params)
+            .await
+            .unwrap();
+
+        assert_eq!(tx.chain_id, 1);
+        assert_eq!(tx.from, TEST_ADDRESS);
+        assert_eq!(tx.to, Address::repeat_byte(0x11));
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.gas_limit, 21000);
+        assert_eq!(tx.max_fee_per_gas, 30);
+        assert_eq!(tx.max_priority_fee_per_gas, 2);
+        assert_eq!(tx.value, U256::ZERO);
+        assert_eq!(tx.data, Bytes::from(call.abi_encode()));
+        assert_eq!(tx.signing_payload[0], 0x02);
+        assert_eq!(
+            tx.signing_hash,
+            alloy::primitives::keccak256(&tx.signing_payload)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_try_populate_unsigned_transaction_falls_back_across_rpcs() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/down");
+            then.status(500);
+        });
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_getTransactionCount");
+            then.status(200)
+                .body(r#"{ "jsonrpc": "2.0", "id": 0, "result": "0x7" }"#);
+        });
+        server.mock(|when, then| {
+            when.path("/rpc").body_contains("eth_estimateGas");
+            then.status(200)
+                .body(r#"{ "jsonrpc": "2.0", "id": 0, "result": "0x5208" }"#);
+        });
+
+        let args = TransactionArgs {
+            chain_id: Some(1),
+            rpcs: vec![server.url("/down"), server.url("/rpc")],
+            max_priority_fee_per_gas: Some(2),
+            max_fee_per_gas: Some(30),
+            signer: TransactionSigner::Unsigned { from: TEST_ADDRESS },
+            ..Default::default()
+        };
+        let params = args
+            .try_into_write_contract_parameters(
+                vaultBalance2Call {
+                    owner: Address::ZERO,
+                    token: Address::ZERO,
+                    vaultId: B256::ZERO,
+                },
+                Address::repeat_byte(0x11),
+            )
+            .unwrap();
+
+        let tx = args
+            .try_populate_unsigned_transaction(&params)
+            .await
+            .unwrap();
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.gas_limit, 21000);
+    }
+
     // NOTE: `alloy` ignores all ledger tests so it seems like there is no way
     // to mock a device. hence there is only a test case for a scenario that
     // should fail regardless of whether a ledger is connected or not
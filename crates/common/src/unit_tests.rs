@@ -1,11 +1,17 @@
 use alloy::primitives::U256;
+use alloy::sol_types::{Revert, SolError};
 use alloy_ethers_typecast::{ReadableClient, ReadableClientError};
-use dotrain::{error::ComposeError, RainDocument, Rebind};
+use dotrain::{
+    error::ComposeError,
+    types::ast::{BindingItem, Node},
+    RainDocument, Rebind,
+};
 use futures::TryFutureExt;
 use proptest::{
     prelude::RngCore,
     test_runner::{RngAlgorithm, TestRng},
 };
+use rain_error_decoding::AbiDecodedErrorType;
 use rain_interpreter_bindings::IInterpreterStoreV3::FullyQualifiedNamespace;
 use rain_interpreter_eval::{
     error::ForkCallError,
@@ -16,13 +22,15 @@ use rain_interpreter_eval::{
 use rain_orderbook_app_settings::{
     blocks::BlockError,
     rainlang::RainlangCfg,
-    unit_test::TestConfig,
+    unit_test::{TestConfig, UnitTestConfigSource},
     yaml::{
         orderbook::{OrderbookYaml, OrderbookYamlValidation},
         YamlError, YamlParsable,
     },
 };
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
 
 #[derive(Clone)]
@@ -33,6 +41,8 @@ pub struct TestRunner {
     pub rng: TestRng,
     pub test_setup: TestSetup,
     pub test_config: TestConfig,
+    /// Forks at this block instead of the latest block of the rainlang's network
+    pub fork_block_number: Option<u64>,
 }
 
 #[derive(Clone)]
@@ -74,6 +84,8 @@ pub enum TestRunnerError {
     YamlError(#[from] YamlError),
     #[error(transparent)]
     RainEvalResultFromRawCallResult(#[from] RainEvalResultFromRawCallResultError),
+    #[error("Invalid test config: {0}")]
+    InvalidTestConfig(String),
 }
 
 impl From<ForkCallError> for TestRunnerError {
@@ -119,6 +131,7 @@ impl TestRunner {
                 scenario_name: String::new(),
             },
             test_config: test_config.clone(),
+            fork_block_number: None,
         })
     }
 
@@ -408,9 +421,14 @@ impl TestRunner {
             .map(|rpc| rpc.to_string())
             .collect::<Vec<String>>();
 
-        let block_number = ReadableClient::new_from_http_urls(rpcs.clone())?
-            .get_block_number()
-            .await?;
+        let block_number = match self.fork_block_number {
+            Some(block_number) => block_number,
+            None => {
+                ReadableClient::new_from_http_urls(rpcs.clone())?
+                    .get_block_number()
+                    .await?
+            }
+        };
 
         let blocks = self
             .test_config
@@ -432,18 +450,326 @@ impl TestRunner {
         let results = self.run_post_entrypoint(pre_stack, calculate_stack).await?;
         Ok(results)
     }
+
+    /// Runs the test once per configured scenario run. Elided bindings are
+    /// redrawn from the seeded rng for every run, a failing run does not stop
+    /// the following ones.
+    pub async fn run_all(&mut self) -> Vec<UnitTestRunOutcome> {
+        let runs = self.test_config.scenario.runs.unwrap_or(1).max(1);
+        let assertions = assertion_messages(&self.dotrains.test_dotrain);
+
+        let mut outcomes = Vec::new();
+        for run in 1..=runs {
+            let started = Instant::now();
+            let result = self.run_unit_test().await;
+            outcomes.push(UnitTestRunOutcome::new(
+                run,
+                self.test_setup.block_number,
+                &assertions,
+                result.err(),
+                started.elapsed().as_secs_f64(),
+            ));
+        }
+        outcomes
+    }
+}
+
+/// Parses the `test:` front matter of a test dotrain
+pub fn parse_test_config(test_dotrain: &str) -> Result<TestConfig, TestRunnerError> {
+    let front_matter = RainDocument::get_front_matter(test_dotrain)
+        .ok_or_else(|| TestRunnerError::InvalidTestConfig("missing front matter".to_string()))?;
+    let source = serde_yaml::from_str::<UnitTestConfigSource>(front_matter)
+        .map_err(|e| TestRunnerError::InvalidTestConfig(e.to_string()))?;
+    Ok(source.test.into_test_config())
+}
+
+/// Reasons of the `ensure` assertions in the `#post` source of a test
+/// dotrain, in evaluation order, read from the parsed rainlang of the
+/// source.
+pub fn assertion_messages(test_dotrain: &str) -> Vec<String> {
+    let document = RainDocument::create(test_dotrain.to_string(), None, None, None);
+    let Some(BindingItem::Exp(post)) = document
+        .bindings()
+        .iter()
+        .find(|binding| binding.name == "post")
+        .map(|binding| &binding.item)
+    else {
+        return vec![];
+    };
+
+    let mut messages = Vec::new();
+    for source in post.ast() {
+        for line in &source.lines {
+            collect_ensure_reasons(&line.nodes, &mut messages);
+        }
+    }
+    messages
+}
+
+/// Inputs are evaluated before the word that takes them, so nested `ensure`
+/// calls come before their parent
+fn collect_ensure_reasons(nodes: &[Node], messages: &mut Vec<String>) {
+    for node in nodes {
+        let Node::Opcode(opcode) = node else {
+            continue;
+        };
+        collect_ensure_reasons(&opcode.inputs, messages);
+        if opcode.opcode.name != "ensure" {
+            continue;
+        }
+        let reason = opcode.inputs.iter().rev().find_map(|input| match input {
+            Node::Literal(literal) => literal
+                .value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"')),
+            _ => None,
+        });
+        if let Some(reason) = reason {
+            messages.push(reason.to_string());
+        }
+    }
+}
+
+/// Reason of the `Error(string)` revert that failed a run, which is what a
+/// failing `ensure` reverts with
+fn revert_reason(error: &TestRunnerError) -> Option<String> {
+    let TestRunnerError::ForkCallError(error) = error else {
+        return None;
+    };
+    let ForkCallError::AbiDecodedError(decoded) = error.as_ref() else {
+        return None;
+    };
+    match decoded {
+        AbiDecodedErrorType::Known { name, args, .. } if name == "Error" => {
+            let reason = args.first()?;
+            Some(
+                reason
+                    .strip_prefix('"')
+                    .and_then(|reason| reason.strip_suffix('"'))
+                    .unwrap_or(reason)
+                    .to_string(),
+            )
+        }
+        AbiDecodedErrorType::Unknown(data) => Revert::abi_decode(data).ok().map(|r| r.reason),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AssertionStatus {
+    Passed,
+    Failed,
+    /// Not evaluated because an earlier assertion failed
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionOutcome {
+    pub message: String,
+    pub status: AssertionStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitTestRunOutcome {
+    pub run: u64,
+    pub block_number: u64,
+    pub assertions: Vec<AssertionOutcome>,
+    /// Set when the run failed for a reason other than one of its assertions
+    pub error: Option<String>,
+    pub duration_secs: f64,
+}
+
+impl UnitTestRunOutcome {
+    /// Assertions are evaluated in order and the first failing `ensure`
+    /// reverts with its reason, so the ones before it passed and the ones
+    /// after it never ran.
+    pub fn new(
+        run: u64,
+        block_number: u64,
+        assertions: &[String],
+        error: Option<TestRunnerError>,
+        duration_secs: f64,
+    ) -> Self {
+        let failed_at = error
+            .as_ref()
+            .and_then(revert_reason)
+            .and_then(|reason| assertions.iter().position(|m| *m == reason));
+        let error = error.map(|e| e.to_string());
+
+        let assertions = assertions
+            .iter()
+            .enumerate()
+            .map(|(i, message)| AssertionOutcome {
+                message: message.clone(),
+                status: match failed_at {
+                    Some(index) if i == index => AssertionStatus::Failed,
+                    Some(index) if i > index => AssertionStatus::Skipped,
+                    None if error.is_some() => AssertionStatus::Skipped,
+                    _ => AssertionStatus::Passed,
+                },
+            })
+            .collect();
+
+        Self {
+            run,
+            block_number,
+            assertions,
+            error: if failed_at.is_some() { None } else { error },
+            duration_secs,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && self
+                .assertions
+                .iter()
+                .all(|a| a.status == AssertionStatus::Passed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rain_orderbook_app_settings::{spec_version::SpecVersion, unit_test::UnitTestConfigSource};
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
     use rain_orderbook_test_fixtures::LocalEvm;
 
     fn get_test_config(test_dotrain: &str) -> TestConfig {
-        let frontmatter = RainDocument::get_front_matter(test_dotrain).unwrap();
-        let source = serde_yaml::from_str::<UnitTestConfigSource>(frontmatter).unwrap();
-        source.test.into_test_config()
+        parse_test_config(test_dotrain).unwrap()
+    }
+
+    const TEST_DOTRAIN_BODY: &str = r#"
+test:
+    scenario-name: some-key
+    scenario:
+        runs: 2
+---
+#pre
+input-token: 0x01,
+output-token: 0x02,
+output-cap: 10;
+
+#post
+:ensure(equal-to(calculated-io-ratio() 999) "io ratio should be 999"),
+:ensure(equal-to(calculated-max-output() 10) "max output (capped) should be 10");
+
+#other
+:ensure(1 "not a post assertion");
+"#;
+
+    #[test]
+    fn test_parse_test_config() {
+        let config = parse_test_config(TEST_DOTRAIN_BODY).unwrap();
+        assert_eq!(config.scenario_name, "some-key");
+        assert_eq!(config.scenario.runs, Some(2));
+
+        let err = parse_test_config("#post\n_: 1;").unwrap_err();
+        assert!(matches!(err, TestRunnerError::InvalidTestConfig(_)));
+    }
+
+    #[test]
+    fn test_assertion_messages() {
+        assert_eq!(
+            assertion_messages(TEST_DOTRAIN_BODY),
+            vec![
+                "io ratio should be 999".to_string(),
+                "max output (capped) should be 10".to_string(),
+            ]
+        );
+        assert!(assertion_messages("---\n#pre\n_: 1;").is_empty());
+    }
+
+    #[test]
+    fn test_run_outcome_statuses() {
+        let assertions = assertion_messages(TEST_DOTRAIN_BODY);
+
+        let passed = UnitTestRunOutcome::new(1, 10, &assertions, None, 0.5);
+        assert!(passed.passed());
+        assert!(passed
+            .assertions
+            .iter()
+            .all(|a| a.status == AssertionStatus::Passed));
+
+        let reverted = |decoded: AbiDecodedErrorType| {
+            Some(TestRunnerError::ForkCallError(Box::new(
+                ForkCallError::AbiDecodedError(decoded),
+            )))
+        };
+
+        let failed = UnitTestRunOutcome::new(
+            2,
+            10,
+            &assertions,
+            reverted(AbiDecodedErrorType::Known {
+                name: "Error".to_string(),
+                args: vec!["io ratio should be 999".to_string()],
+                sig: "Error(string)".to_string(),
+                data: vec![],
+            }),
+            0.5,
+        );
+        assert!(!failed.passed());
+        assert_eq!(failed.error, None);
+        assert_eq!(failed.assertions[0].status, AssertionStatus::Failed);
+        assert_eq!(failed.assertions[1].status, AssertionStatus::Skipped);
+
+        let failed_undecoded = UnitTestRunOutcome::new(
+            2,
+            10,
+            &assertions,
+            reverted(AbiDecodedErrorType::Unknown(
+                Revert::from("max output (capped) should be 10").abi_encode(),
+            )),
+            0.5,
+        );
+        assert_eq!(failed_undecoded.error, None);
+        assert_eq!(
+            failed_undecoded.assertions[0].status,
+            AssertionStatus::Passed
+        );
+        assert_eq!(
+            failed_undecoded.assertions[1].status,
+            AssertionStatus::Failed
+        );
+
+        // a reason that only contains an assertion's message is not that assertion
+        let other_revert = UnitTestRunOutcome::new(
+            2,
+            10,
+            &assertions,
+            reverted(AbiDecodedErrorType::Known {
+                name: "Error".to_string(),
+                args: vec!["not: io ratio should be 999".to_string()],
+                sig: "Error(string)".to_string(),
+                data: vec![],
+            }),
+            0.5,
+        );
+        assert!(other_revert.error.is_some());
+        assert!(other_revert
+            .assertions
+            .iter()
+            .all(|a| a.status == AssertionStatus::Skipped));
+
+        let errored = UnitTestRunOutcome::new(
+            3,
+            10,
+            &assertions,
+            Some(TestRunnerError::InvalidArgs("no fork".to_string())),
+            0.5,
+        );
+        assert!(!errored.passed());
+        assert_eq!(
+            errored.error,
+            Some("Invalid input args: no fork".to_string())
+        );
+        assert!(errored
+            .assertions
+            .iter()
+            .all(|a| a.status == AssertionStatus::Skipped));
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 10)]
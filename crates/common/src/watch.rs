@@ -0,0 +1,816 @@
+//! Webhook notifications for the `watchers:` settings section.
+//!
+//! Order and vault triggers are driven by the events a local DB sync cycle
+//! applied. Whether an order can still be quoted is not part of the indexed
+//! state, so `order-unquotable` is evaluated by quoting watched orders
+//! periodically through [`WatchNotifier::check_quotes`].
+//!
+//! Webhooks are posted through [`WatchWebhooks`], which can be cloned into a
+//! separate task so slow deliveries don't hold up reading sync events.
+
+use crate::local_db::pipeline::sync_events::{SyncEvent, SyncEventKind, SyncTradeKind};
+use crate::local_db::OrderbookIdentifier;
+use crate::raindex_client::{RaindexClient, RaindexError};
+use crate::retry::{retry_with_backoff, RetryError};
+use alloy::primitives::{Address, B256, U256};
+use alloy_ethers_typecast::{ReadableClient, ReadableClientError};
+use rain_math_float::{Float, FloatError};
+use rain_orderbook_app_settings::watcher::{WatchTriggerCfg, WatcherCfg};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug)]
+pub enum WatchError {
+    #[error("Watcher {watcher} has an invalid min-balance {value}: {error}")]
+    InvalidMinBalance {
+        watcher: String,
+        value: String,
+        error: FloatError,
+    },
+    #[error("Float error: {0}")]
+    Float(#[from] FloatError),
+    #[error("Unknown watcher {0}")]
+    UnknownWatcher(String),
+    #[error("Webhook request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Webhook responded with status {0}")]
+    HttpStatus(StatusCode),
+    #[error("Webhook retry attempts must be greater than zero")]
+    InvalidRetryMaxAttempts,
+    #[error(transparent)]
+    ReadableClient(#[from] ReadableClientError),
+}
+
+impl From<RetryError<WatchError>> for WatchError {
+    fn from(err: RetryError<WatchError>) -> Self {
+        match err {
+            RetryError::InvalidMaxAttempts => WatchError::InvalidRetryMaxAttempts,
+            RetryError::Operation(inner) => inner,
+        }
+    }
+}
+
+impl WatchError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            WatchError::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            WatchError::HttpStatus(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+
+    fn is_rate_limited(&self) -> bool {
+        matches!(self, WatchError::HttpStatus(StatusCode::TOO_MANY_REQUESTS))
+    }
+}
+
+/// How webhook deliveries are retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookRetry {
+    pub max_attempts: usize,
+    pub base_delay_ms: u64,
+    pub rate_limit_delay_ms: u64,
+}
+
+impl Default for WebhookRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: crate::retry::DEFAULT_BASE_DELAY_MILLIS,
+            rate_limit_delay_ms: 5_000,
+        }
+    }
+}
+
+/// What a watcher was notified about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "trigger", rename_all = "kebab-case")]
+pub enum WatchAlert {
+    #[serde(rename_all = "camelCase")]
+    OrderTaken {
+        order_hash: B256,
+        owner: Address,
+        sender: Address,
+        trade_kind: SyncTradeKind,
+        transaction_hash: B256,
+        block_number: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderRemoved {
+        order_hash: B256,
+        owner: Address,
+        transaction_hash: B256,
+        block_number: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    OrderUnquotable {
+        order_hash: B256,
+        errors: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultBalanceLow {
+        owner: Address,
+        token: Address,
+        vault_id: U256,
+        balance: String,
+        min_balance: String,
+        transaction_hash: B256,
+        block_number: u64,
+    },
+}
+
+/// Body posted to a watcher's webhook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchNotification {
+    pub watcher: String,
+    pub chain_id: u32,
+    pub orderbook: Address,
+    #[serde(flatten)]
+    pub alert: WatchAlert,
+}
+
+struct WatcherState {
+    cfg: WatcherCfg,
+    ob_id: OrderbookIdentifier,
+    /// Parsed `min-balance` per entry of `cfg.vaults`
+    min_balances: Vec<Float>,
+    /// Vaults currently below their threshold, so a drain alerts once
+    low_vaults: HashSet<usize>,
+    unquotable_orders: HashSet<B256>,
+}
+
+impl WatcherState {
+    fn new(cfg: WatcherCfg) -> Result<Self, WatchError> {
+        let min_balances = cfg
+            .vaults
+            .iter()
+            .map(|vault| {
+                Float::parse(vault.min_balance.clone()).map_err(|error| {
+                    WatchError::InvalidMinBalance {
+                        watcher: cfg.key.clone(),
+                        value: vault.min_balance.clone(),
+                        error,
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            ob_id: OrderbookIdentifier::new(cfg.orderbook.network.chain_id, cfg.orderbook.address),
+            cfg,
+            min_balances,
+            low_vaults: HashSet::new(),
+            unquotable_orders: HashSet::new(),
+        })
+    }
+
+    fn notification(&self, alert: WatchAlert) -> WatchNotification {
+        WatchNotification {
+            watcher: self.cfg.key.clone(),
+            chain_id: self.ob_id.chain_id,
+            orderbook: self.ob_id.orderbook_address,
+            alert,
+        }
+    }
+
+    fn alert_for_event(&mut self, event: &SyncEvent) -> Result<Option<WatchAlert>, WatchError> {
+        if event.ob_id != self.ob_id {
+            return Ok(None);
+        }
+
+        let alert = match &event.kind {
+            SyncEventKind::Trade {
+                order_hash,
+                owner,
+                sender,
+                trade_kind,
+            } if self.cfg.has_trigger(WatchTriggerCfg::OrderTaken)
+                && self.cfg.watches_order(order_hash) =>
+            {
+                Some(WatchAlert::OrderTaken {
+                    order_hash: *order_hash,
+                    owner: *owner,
+                    sender: *sender,
+                    trade_kind: *trade_kind,
+                    transaction_hash: event.transaction_hash,
+                    block_number: event.block_number,
+                })
+            }
+            SyncEventKind::OrderRemoved { order_hash, owner }
+                if self.cfg.has_trigger(WatchTriggerCfg::OrderRemoved)
+                    && self.cfg.watches_order(order_hash) =>
+            {
+                Some(WatchAlert::OrderRemoved {
+                    order_hash: *order_hash,
+                    owner: *owner,
+                    transaction_hash: event.transaction_hash,
+                    block_number: event.block_number,
+                })
+            }
+            SyncEventKind::VaultBalanceChange {
+                owner,
+                token,
+                vault_id,
+                running_balance,
+                ..
+            } if self.cfg.has_trigger(WatchTriggerCfg::VaultBalanceLow) => {
+                let Some(index) = self.cfg.vaults.iter().position(|vault| {
+                    vault.owner == *owner && vault.token == *token && vault.vault_id == *vault_id
+                }) else {
+                    return Ok(None);
+                };
+
+                let balance = Float::from_hex(running_balance)?;
+                if !balance.lt(self.min_balances[index])? {
+                    self.low_vaults.remove(&index);
+                    return Ok(None);
+                }
+                if !self.low_vaults.insert(index) {
+                    return Ok(None);
+                }
+
+                Some(WatchAlert::VaultBalanceLow {
+                    owner: *owner,
+                    token: *token,
+                    vault_id: *vault_id,
+                    balance: balance.format()?,
+                    min_balance: self.cfg.vaults[index].min_balance.clone(),
+                    transaction_hash: event.transaction_hash,
+                    block_number: event.block_number,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(alert)
+    }
+
+    /// Records the latest quote outcome for an order, alerting when it stops
+    /// being quotable. Recovering clears the state so a later failure alerts
+    /// again.
+    fn alert_for_quote(&mut self, order_hash: B256, errors: Vec<String>) -> Option<WatchAlert> {
+        if !self.cfg.has_trigger(WatchTriggerCfg::OrderUnquotable)
+            || !self.cfg.watches_order(&order_hash)
+        {
+            return None;
+        }
+
+        if errors.is_empty() {
+            self.unquotable_orders.remove(&order_hash);
+            return None;
+        }
+        if !self.unquotable_orders.insert(order_hash) {
+            return None;
+        }
+        Some(WatchAlert::OrderUnquotable { order_hash, errors })
+    }
+}
+
+/// Turns sync events and quote checks into webhook notifications
+pub struct WatchNotifier {
+    watchers: Vec<WatcherState>,
+    webhooks: WatchWebhooks,
+    /// Chain head per chain ID when watching started, see
+    /// [`WatchNotifier::skip_history`]
+    start_blocks: HashMap<u32, u64>,
+}
+
+impl WatchNotifier {
+    pub fn new(watchers: Vec<WatcherCfg>, retry: WebhookRetry) -> Result<Self, WatchError> {
+        let http = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+        let mut watchers = watchers
+            .into_iter()
+            .map(WatcherState::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        watchers.sort_by(|a, b| a.cfg.key.cmp(&b.cfg.key));
+
+        let webhooks = WatchWebhooks {
+            urls: watchers
+                .iter()
+                .map(|w| (w.cfg.key.clone(), w.cfg.webhook.clone()))
+                .collect(),
+            http,
+            retry,
+        };
+        Ok(Self {
+            watchers,
+            webhooks,
+            start_blocks: HashMap::new(),
+        })
+    }
+
+    /// Webhooks of the watchers, to post notifications with
+    pub fn webhooks(&self) -> WatchWebhooks {
+        self.webhooks.clone()
+    }
+
+    /// Ignores events at or below `block_number` on the chain
+    pub fn skip_events_until(&mut self, chain_id: u32, block_number: u64) {
+        self.start_blocks.insert(chain_id, block_number);
+    }
+
+    /// Ignores the events up to the current head of every watched chain, so
+    /// the first sync catching up on history doesn't alert for past trades,
+    /// removals and balance changes.
+    pub async fn skip_history(&mut self) -> Result<(), WatchError> {
+        let mut chains: HashMap<u32, Vec<String>> = HashMap::new();
+        for watcher in &self.watchers {
+            let network = &watcher.cfg.orderbook.network;
+            chains
+                .entry(network.chain_id)
+                .or_insert_with(|| network.rpcs.iter().map(|rpc| rpc.to_string()).collect());
+        }
+
+        for (chain_id, rpcs) in chains {
+            let head = ReadableClient::new_from_http_urls(rpcs)?
+                .get_block_number()
+                .await?;
+            self.skip_events_until(chain_id, head);
+        }
+        Ok(())
+    }
+
+    /// Notifications triggered by one applied sync event
+    pub fn notifications_for_event(
+        &mut self,
+        event: &SyncEvent,
+    ) -> Result<Vec<WatchNotification>, WatchError> {
+        if self
+            .start_blocks
+            .get(&event.ob_id.chain_id)
+            .is_some_and(|start_block| event.block_number <= *start_block)
+        {
+            return Ok(vec![]);
+        }
+
+        let mut notifications = Vec::new();
+        for watcher in &mut self.watchers {
+            if let Some(alert) = watcher.alert_for_event(event)? {
+                notifications.push(watcher.notification(alert));
+            }
+        }
+        Ok(notifications)
+    }
+
+    /// Notifications triggered by the latest quote outcome of an order, an
+    /// empty `errors` means the order was quotable.
+    pub fn notifications_for_quote(
+        &mut self,
+        ob_id: &OrderbookIdentifier,
+        order_hash: B256,
+        errors: Vec<String>,
+    ) -> Vec<WatchNotification> {
+        let mut notifications = Vec::new();
+        for watcher in &mut self.watchers {
+            if watcher.ob_id != *ob_id {
+                continue;
+            }
+            if let Some(alert) = watcher.alert_for_quote(order_hash, errors.clone()) {
+                notifications.push(watcher.notification(alert));
+            }
+        }
+        notifications
+    }
+
+    /// Orders watched for `order-unquotable`, once per orderbook
+    pub fn quoted_orders(&self) -> Vec<(OrderbookIdentifier, B256)> {
+        let mut orders = Vec::new();
+        for watcher in &self.watchers {
+            if !watcher.cfg.has_trigger(WatchTriggerCfg::OrderUnquotable) {
+                continue;
+            }
+            for order_hash in &watcher.cfg.orders {
+                let entry = (watcher.ob_id.clone(), *order_hash);
+                if !orders.contains(&entry) {
+                    orders.push(entry);
+                }
+            }
+        }
+        orders
+    }
+
+    /// Quotes every order watched for `order-unquotable` and returns the
+    /// notifications for orders that stopped being quotable. Removed orders
+    /// are left to the `order-removed` trigger.
+    pub async fn check_quotes(&mut self, client: &RaindexClient) -> Vec<WatchNotification> {
+        let mut notifications = Vec::new();
+        for (ob_id, order_hash) in self.quoted_orders() {
+            let errors = match quote_errors(client, &ob_id, order_hash).await {
+                Ok(Some(errors)) => errors,
+                Ok(None) => continue,
+                Err(e) => vec![e.to_readable_msg()],
+            };
+            notifications.extend(self.notifications_for_quote(&ob_id, order_hash, errors));
+        }
+        notifications
+    }
+}
+
+/// Webhook of every watcher, keyed by watcher
+#[derive(Debug, Clone)]
+pub struct WatchWebhooks {
+    urls: HashMap<String, Url>,
+    http: reqwest::Client,
+    retry: WebhookRetry,
+}
+
+impl WatchWebhooks {
+    /// Posts a notification to its watcher's webhook, retrying transient
+    /// failures.
+    pub async fn send(&self, notification: &WatchNotification) -> Result<(), WatchError> {
+        let url = self
+            .urls
+            .get(&notification.watcher)
+            .cloned()
+            .ok_or_else(|| WatchError::UnknownWatcher(notification.watcher.clone()))?;
+
+        retry_with_backoff(
+            || {
+                let request = self.http.post(url.clone()).json(notification);
+                async move {
+                    let response = request.send().await?;
+                    if !response.status().is_success() {
+                        return Err(WatchError::HttpStatus(response.status()));
+                    }
+                    Ok(())
+                }
+            },
+            self.retry.max_attempts,
+            self.retry.base_delay_ms,
+            self.retry.rate_limit_delay_ms,
+            WatchError::is_retryable,
+            WatchError::is_rate_limited,
+        )
+        .await
+        .map_err(WatchError::from)
+    }
+}
+
+/// Quote failures of an active order, `None` when the order is no longer
+/// active.
+async fn quote_errors(
+    client: &RaindexClient,
+    ob_id: &OrderbookIdentifier,
+    order_hash: B256,
+) -> Result<Option<Vec<String>>, RaindexError> {
+    let order = client.get_order_by_hash(ob_id, order_hash).await?;
+    if !order.active() {
+        return Ok(None);
+    }
+
+    let quotes = order.get_quotes(None, None).await?;
+    if quotes.iter().any(|quote| quote.success) {
+        return Ok(Some(vec![]));
+    }
+
+    let mut errors: Vec<String> = quotes.into_iter().filter_map(|quote| quote.error).collect();
+    if errors.is_empty() {
+        errors.push("Order has no quotable pairs".to_string());
+    }
+    Ok(Some(errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VaultBalanceChangeKind;
+    use httpmock::prelude::*;
+    use rain_orderbook_app_settings::network::NetworkCfg;
+    use rain_orderbook_app_settings::orderbook::OrderbookCfg;
+    use rain_orderbook_app_settings::watcher::WatchedVaultCfg;
+    use std::sync::Arc;
+
+    fn watcher(key: &str, webhook: &str) -> WatcherCfg {
+        WatcherCfg {
+            key: key.to_string(),
+            orderbook: Arc::new(OrderbookCfg {
+                address: Address::with_last_byte(0xAA),
+                network: Arc::new(NetworkCfg {
+                    chain_id: 1,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            webhook: Url::parse(webhook).unwrap(),
+            orders: vec![B256::with_last_byte(1)],
+            vaults: vec![WatchedVaultCfg {
+                owner: Address::with_last_byte(2),
+                token: Address::with_last_byte(3),
+                vault_id: U256::from(4),
+                min_balance: "10".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn event(kind: SyncEventKind) -> SyncEvent {
+        SyncEvent {
+            ob_id: OrderbookIdentifier::new(1, Address::with_last_byte(0xAA)),
+            block_number: 100,
+            block_timestamp: 1_000,
+            transaction_hash: B256::with_last_byte(0xEE),
+            log_index: 0,
+            kind,
+        }
+    }
+
+    fn balance_change(balance: &str) -> SyncEvent {
+        event(SyncEventKind::VaultBalanceChange {
+            owner: Address::with_last_byte(2),
+            token: Address::with_last_byte(3),
+            vault_id: U256::from(4),
+            change_type: VaultBalanceChangeKind::Withdrawal,
+            delta: Float::parse("1".to_string()).unwrap().as_hex(),
+            running_balance: Float::parse(balance.to_string()).unwrap().as_hex(),
+        })
+    }
+
+    fn notifier(watchers: Vec<WatcherCfg>) -> WatchNotifier {
+        WatchNotifier::new(
+            watchers,
+            WebhookRetry {
+                max_attempts: 3,
+                base_delay_ms: 1,
+                rate_limit_delay_ms: 1,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_order_events_for_watched_orders() {
+        let mut notifier = notifier(vec![watcher("alerts", "http://localhost/hook")]);
+
+        let taken = notifier
+            .notifications_for_event(&event(SyncEventKind::Trade {
+                order_hash: B256::with_last_byte(1),
+                owner: Address::with_last_byte(2),
+                sender: Address::with_last_byte(5),
+                trade_kind: SyncTradeKind::Take,
+            }))
+            .unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].watcher, "alerts");
+        assert!(matches!(taken[0].alert, WatchAlert::OrderTaken { .. }));
+
+        let unwatched = notifier
+            .notifications_for_event(&event(SyncEventKind::OrderRemoved {
+                order_hash: B256::with_last_byte(9),
+                owner: Address::with_last_byte(2),
+            }))
+            .unwrap();
+        assert!(unwatched.is_empty());
+
+        let removed = notifier
+            .notifications_for_event(&event(SyncEventKind::OrderRemoved {
+                order_hash: B256::with_last_byte(1),
+                owner: Address::with_last_byte(2),
+            }))
+            .unwrap();
+        assert!(matches!(removed[0].alert, WatchAlert::OrderRemoved { .. }));
+    }
+
+    #[test]
+    fn test_triggers_and_orderbook_filter() {
+        let mut cfg = watcher("alerts", "http://localhost/hook");
+        cfg.triggers = vec![WatchTriggerCfg::OrderRemoved];
+        let mut notifier = notifier(vec![cfg]);
+
+        let trade = SyncEventKind::Trade {
+            order_hash: B256::with_last_byte(1),
+            owner: Address::with_last_byte(2),
+            sender: Address::with_last_byte(5),
+            trade_kind: SyncTradeKind::Clear,
+        };
+        assert!(notifier
+            .notifications_for_event(&event(trade))
+            .unwrap()
+            .is_empty());
+
+        let mut other_orderbook = event(SyncEventKind::OrderRemoved {
+            order_hash: B256::with_last_byte(1),
+            owner: Address::with_last_byte(2),
+        });
+        other_orderbook.ob_id = OrderbookIdentifier::new(2, Address::with_last_byte(0xAA));
+        assert!(notifier
+            .notifications_for_event(&other_orderbook)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_vault_balance_low_fires_once_per_drop() {
+        let mut notifier = notifier(vec![watcher("alerts", "http://localhost/hook")]);
+
+        assert!(notifier
+            .notifications_for_event(&balance_change("20"))
+            .unwrap()
+            .is_empty());
+
+        let low = notifier
+            .notifications_for_event(&balance_change("5"))
+            .unwrap();
+        assert_eq!(low.len(), 1);
+        match &low[0].alert {
+            WatchAlert::VaultBalanceLow {
+                balance,
+                min_balance,
+                ..
+            } => {
+                assert_eq!(balance, "5");
+                assert_eq!(min_balance, "10");
+            }
+            other => panic!("unexpected alert {:?}", other),
+        }
+
+        // still low, already alerted
+        assert!(notifier
+            .notifications_for_event(&balance_change("4"))
+            .unwrap()
+            .is_empty());
+
+        // topped up and drained again
+        assert!(notifier
+            .notifications_for_event(&balance_change("50"))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            notifier
+                .notifications_for_event(&balance_change("1"))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_skips_events_up_to_start_block() {
+        let mut notifier = notifier(vec![watcher("alerts", "http://localhost/hook")]);
+        notifier.skip_events_until(1, 100);
+
+        let removed = SyncEventKind::OrderRemoved {
+            order_hash: B256::with_last_byte(1),
+            owner: Address::with_last_byte(2),
+        };
+        assert!(notifier
+            .notifications_for_event(&event(removed.clone()))
+            .unwrap()
+            .is_empty());
+
+        let mut later = event(removed.clone());
+        later.block_number = 101;
+        assert_eq!(notifier.notifications_for_event(&later).unwrap().len(), 1);
+
+        notifier.skip_events_until(2, 1_000);
+        assert_eq!(
+            notifier
+                .notifications_for_event(&event(removed))
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_invalid_min_balance() {
+        let mut cfg = watcher("alerts", "http://localhost/hook");
+        cfg.vaults[0].min_balance = "lots".to_string();
+        let err = WatchNotifier::new(vec![cfg], WebhookRetry::default())
+            .err()
+            .unwrap();
+        assert!(matches!(err, WatchError::InvalidMinBalance { .. }));
+    }
+
+    #[test]
+    fn test_unquotable_transitions() {
+        let mut notifier = notifier(vec![watcher("alerts", "http://localhost/hook")]);
+        let ob_id = OrderbookIdentifier::new(1, Address::with_last_byte(0xAA));
+        let order_hash = B256::with_last_byte(1);
+
+        assert_eq!(notifier.quoted_orders(), vec![(ob_id.clone(), order_hash)]);
+        assert!(notifier
+            .notifications_for_quote(&ob_id, order_hash, vec![])
+            .is_empty());
+
+        let failing = notifier.notifications_for_quote(
+            &ob_id,
+            order_hash,
+            vec!["execution reverted".to_string()],
+        );
+        assert_eq!(
+            failing[0].alert,
+            WatchAlert::OrderUnquotable {
+                order_hash,
+                errors: vec!["execution reverted".to_string()],
+            }
+        );
+        assert!(notifier
+            .notifications_for_quote(&ob_id, order_hash, vec!["again".to_string()])
+            .is_empty());
+
+        assert!(notifier
+            .notifications_for_quote(&ob_id, order_hash, vec![])
+            .is_empty());
+        assert_eq!(
+            notifier
+                .notifications_for_quote(&ob_id, order_hash, vec!["again".to_string()])
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_notification_serialization() {
+        let notification = WatchNotification {
+            watcher: "alerts".to_string(),
+            chain_id: 1,
+            orderbook: Address::with_last_byte(0xAA),
+            alert: WatchAlert::OrderUnquotable {
+                order_hash: B256::with_last_byte(1),
+                errors: vec!["boom".to_string()],
+            },
+        };
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["trigger"], "order-unquotable");
+        assert_eq!(json["watcher"], "alerts");
+        assert_eq!(json["chainId"], 1);
+        assert_eq!(json["errors"][0], "boom");
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_server_errors() {
+        let server = MockServer::start_async().await;
+        let mut failing = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/hook");
+                then.status(503);
+            })
+            .await;
+
+        let notifier = notifier(vec![watcher("alerts", &server.url("/hook"))]);
+        let notification = WatchNotification {
+            watcher: "alerts".to_string(),
+            chain_id: 1,
+            orderbook: Address::with_last_byte(0xAA),
+            alert: WatchAlert::OrderUnquotable {
+                order_hash: B256::with_last_byte(1),
+                errors: vec![],
+            },
+        };
+
+        let err = notifier.webhooks().send(&notification).await.unwrap_err();
+        assert!(matches!(
+            err,
+            WatchError::HttpStatus(StatusCode::SERVICE_UNAVAILABLE)
+        ));
+        failing.assert_hits_async(3).await;
+        failing.delete_async().await;
+
+        let ok = server
+            .mock_async(|when, then| {
+                when.method(POST)
+                    .path("/hook")
+                    .json_body_partial(r#"{"trigger":"order-unquotable","watcher":"alerts"}"#);
+                then.status(200);
+            })
+            .await;
+        notifier.webhooks().send(&notification).await.unwrap();
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_client_errors() {
+        let server = MockServer::start_async().await;
+        let mock = server
+            .mock_async(|when, then| {
+                when.method(POST).path("/hook");
+                then.status(400);
+            })
+            .await;
+
+        let notifier = notifier(vec![watcher("alerts", &server.url("/hook"))]);
+        let notification = WatchNotification {
+            watcher: "alerts".to_string(),
+            chain_id: 1,
+            orderbook: Address::with_last_byte(0xAA),
+            alert: WatchAlert::OrderUnquotable {
+                order_hash: B256::with_last_byte(1),
+                errors: vec![],
+            },
+        };
+
+        assert!(notifier.webhooks().send(&notification).await.is_err());
+        mock.assert_hits_async(1).await;
+    }
+}
@@ -36,7 +36,7 @@ impl WithdrawArgs {
         transaction_args: TransactionArgs,
         transaction_status_changed: S,
     ) -> Result<(), WritableTransactionExecuteError> {
-        let (ledger_client, _) = transaction_args.clone().try_into_ledger_client().await?;
+        let (client, _) = transaction_args.clone().try_into_signing_client().await?;
 
         let withdraw_call: withdraw4Call = self.clone().into();
         let params = transaction_args.try_into_write_contract_parameters(
@@ -44,7 +44,7 @@ impl WithdrawArgs {
             transaction_args.orderbook_address,
         )?;
 
-        WriteTransaction::new(ledger_client, params, 4, transaction_status_changed)
+        WriteTransaction::new(client, params, 4, transaction_status_changed)
             .execute()
             .await?;
 
@@ -114,6 +114,7 @@ mod tests {
             chain_id: Some(1),
             max_priority_fee_per_gas: Some(200),
             max_fee_per_gas: Some(100),
+            ..Default::default()
         };
 
         let amount = Float::parse("456".to_string()).unwrap().get_inner();
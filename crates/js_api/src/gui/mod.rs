@@ -705,6 +705,8 @@ pub enum GuiError {
     NoAddressInMetaboardSubgraph,
     #[error(transparent)]
     MetaboardSubgraphClientError(#[from] MetaboardSubgraphClientError),
+    #[error(transparent)]
+    GasEstimateError(#[from] rain_orderbook_common::gas_estimate::GasEstimateError),
 }
 
 impl GuiError {
@@ -798,6 +800,8 @@ impl GuiError {
                 "No address was found in the metaboard subgraph response.".to_string(),
             GuiError::MetaboardSubgraphClientError(err) =>
                 format!("There was a problem with the metaboard subgraph client: {err}"),
+            GuiError::GasEstimateError(err) =>
+                format!("Failed to estimate the transaction cost: {err}"),
         }
     }
 }
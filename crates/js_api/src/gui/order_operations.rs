@@ -17,7 +17,11 @@ use rain_orderbook_bindings::{
     IRaindexV6::deposit4Call, OrderBook::multicallCall, IERC20::approveCall,
 };
 use rain_orderbook_common::{
-    add_order::AddOrderArgs, deposit::DepositArgs, erc20::ERC20, transaction::TransactionArgs,
+    add_order::AddOrderArgs,
+    deposit::DepositArgs,
+    erc20::ERC20,
+    gas_estimate::{estimate_calldata_cost, EstimatedCalldata},
+    transaction::TransactionArgs,
 };
 use std::{collections::HashMap, str::FromStr, sync::Arc};
 use url::Url;
@@ -534,6 +538,52 @@ impl DotrainOrderGui {
         )))
     }
 
+    /// Generates calldata for adding the order along with its gas estimate.
+    ///
+    /// Same calldata as `generateAddOrderCalldata`, estimated against the
+    /// orderbook's network. The estimate carries the decoded revert reason
+    /// instead when adding the order would fail.
+    ///
+    /// ## Examples
+    ///
+    /// ```javascript
+    /// const result = await gui.generateAddOrderCalldataWithEstimate(owner);
+    /// if (result.error) {
+    ///   console.error("Cannot create order:", result.error.readableMsg);
+    ///   return;
+    /// }
+    /// const { calldata, cost } = result.value;
+    /// if (cost.type === "Revert") {
+    ///   console.warn("Adding the order would revert:", cost.data);
+    /// }
+    /// ```
+    #[wasm_export(
+        js_name = "generateAddOrderCalldataWithEstimate",
+        unchecked_return_type = "EstimatedCalldata",
+        return_description = "Encoded addOrder call with its gas estimate or revert reason"
+    )]
+    pub async fn generate_add_order_calldata_with_estimate(
+        &mut self,
+        #[wasm_export(
+            param_description = "Address sending the transaction, the order owner",
+            unchecked_param_type = "Address"
+        )]
+        from: String,
+    ) -> Result<EstimatedCalldata, GuiError> {
+        let from = Address::from_str(&from)?;
+        let AddOrderCalldataResult(calldata) = self.generate_add_order_calldata().await?;
+        let orderbook = self.get_orderbook()?;
+        let cost = estimate_calldata_cost(
+            &orderbook.network,
+            from,
+            orderbook.address,
+            calldata.clone(),
+            None,
+        )
+        .await?;
+        Ok(EstimatedCalldata { calldata, cost })
+    }
+
     /// Generates a multicall combining all deposits and add order in one calldata.
     ///
     /// This is the most efficient way to deploy an order, combining all necessary
@@ -2,7 +2,7 @@ use crate::gui::{DotrainOrderGui, GuiError};
 use crate::yaml::{OrderbookYaml, OrderbookYamlError};
 use rain_orderbook_app_settings::gui::NameAndDescriptionCfg;
 use rain_orderbook_common::raindex_client::{RaindexClient, RaindexError as RaindexClientError};
-use reqwest;
+use rain_orderbook_common::registry as core_registry;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
@@ -64,55 +64,9 @@ use wasm_bindgen_utils::{impl_wasm_traits, prelude::*, wasm_export};
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[wasm_bindgen]
 pub struct DotrainRegistry {
-    /// The URL of the registry file containing the list of orders and settings.
-    ///
-    /// This is the original URL passed to the constructor and serves as the entry point
-    /// for fetching the registry configuration. The registry file should contain a
-    /// settings URL on the first line followed by order entries.
-    registry_url: Url,
-
-    /// The raw content of the registry file as fetched from `registry_url`.
-    ///
-    /// Contains the complete registry file content including the settings URL and
-    /// all order entries. This is stored for reference and debugging purposes.
-    /// Format: settings URL on first line, then "key url" pairs for each order.
-    registry: String,
-
-    /// The URL of the shared settings YAML file extracted from the first line of the registry.
-    ///
-    /// This URL points to a YAML file containing shared configuration such as:
-    /// - Network configurations (RPCs, chain IDs)
-    /// - Subgraph endpoints
-    /// - Orderbook contract addresses
-    /// - Token definitions
-    /// - Other common settings used across multiple strategies
-    settings_url: Url,
-
-    /// The content of the shared settings YAML file fetched from `settings_url`.
-    ///
-    /// Contains shared configuration that will be merged with individual order content
-    /// to create complete dotrain files. This typically includes network settings,
-    /// contract addresses, and other common configuration that strategies can inherit.
-    settings: String,
-
-    /// A map of order keys to their corresponding .rain file URLs.
-    ///
-    /// Each entry represents an available order where:
-    /// - **Key**: Human-readable order identifier (e.g., "fixed-limit", "auction-dca")
-    /// - **Value**: URL pointing to the .rain file containing the order's dotrain configuration
-    ///
-    /// This mapping is parsed from the registry file lines (excluding the first settings line).
-    order_urls: HashMap<String, Url>,
-
-    /// A map of order keys to their corresponding .rain file contents.
-    ///
-    /// Each entry contains the raw dotrain content for an order:
-    /// - **Key**: Order identifier matching the keys in `order_urls`
-    /// - **Value**: Raw dotrain content fetched from the corresponding URL
-    ///
-    /// This content is fetched in parallel during registry initialization and stored
-    /// for quick access. It gets merged with `settings` content when creating GUIs.
-    orders: HashMap<String, String>,
+    /// The fetched registry with its shared settings and order contents, see
+    /// [`core_registry::DotrainRegistry`].
+    inner: core_registry::DotrainRegistry,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Tsify)]
@@ -186,6 +140,31 @@ impl DotrainRegistryError {
     }
 }
 
+impl From<core_registry::DotrainRegistryError> for DotrainRegistryError {
+    fn from(value: core_registry::DotrainRegistryError) -> Self {
+        use core_registry::DotrainRegistryError as CoreError;
+        match value {
+            CoreError::RegistryFetchError(url) => DotrainRegistryError::RegistryFetchError(url),
+            CoreError::RegistryParseError => DotrainRegistryError::RegistryParseError,
+            CoreError::SettingsFetchError(url) => DotrainRegistryError::SettingsFetchError(url),
+            CoreError::OrderFetchError(url) => DotrainRegistryError::OrderFetchError(url),
+            CoreError::OrderKeyNotFound(key) => DotrainRegistryError::OrderKeyNotFound(key),
+            CoreError::InvalidRegistryFormat(msg) => {
+                DotrainRegistryError::InvalidRegistryFormat(msg)
+            }
+            CoreError::HttpError(msg) => DotrainRegistryError::HttpError(msg),
+            CoreError::UrlParseError(err) => DotrainRegistryError::UrlParseError(err),
+            CoreError::YamlError(err) => DotrainRegistryError::GuiError(GuiError::YamlError(err)),
+        }
+    }
+}
+
+impl From<core_registry::DotrainRegistry> for DotrainRegistry {
+    fn from(inner: core_registry::DotrainRegistry) -> Self {
+        Self { inner }
+    }
+}
+
 impl From<DotrainRegistryError> for WasmEncodedError {
     fn from(value: DotrainRegistryError) -> Self {
         WasmEncodedError {
@@ -199,24 +178,25 @@ impl From<DotrainRegistryError> for WasmEncodedError {
 impl DotrainRegistry {
     #[wasm_bindgen(getter = registryUrl)]
     pub fn registry_url(&self) -> String {
-        self.registry_url.to_string()
+        self.inner.registry_url.to_string()
     }
     #[wasm_bindgen(getter)]
     pub fn registry(&self) -> String {
-        self.registry.clone()
+        self.inner.registry.clone()
     }
     #[wasm_bindgen(getter = settingsUrl)]
     pub fn settings_url(&self) -> String {
-        self.settings_url.to_string()
+        self.inner.settings_url.to_string()
     }
     #[wasm_bindgen(getter)]
     pub fn settings(&self) -> String {
-        self.settings.clone()
+        self.inner.settings.clone()
     }
     #[wasm_bindgen(getter = orderUrls)]
     pub fn order_urls(&self) -> OrderUrls {
         OrderUrls(
-            self.order_urls
+            self.inner
+                .order_urls
                 .iter()
                 .map(|(key, value)| (key.clone(), value.to_string()))
                 .collect(),
@@ -224,7 +204,7 @@ impl DotrainRegistry {
     }
     #[wasm_bindgen(getter = orders)]
     pub fn orders(&self) -> OrderUrls {
-        OrderUrls(self.orders.clone())
+        OrderUrls(self.inner.orders.clone())
     }
 }
 
@@ -258,19 +238,8 @@ impl DotrainRegistry {
         registry_url: String,
     ) -> Result<DotrainRegistry, DotrainRegistryError> {
         let registry_url = Url::parse(&registry_url)?;
-        let (registry_content, settings_url, order_urls) =
-            Self::fetch_and_parse_registry(&registry_url).await?;
-        let settings = Self::fetch_settings(&settings_url).await?;
-        let orders = Self::fetch_orders(&order_urls).await?;
-
-        Ok(DotrainRegistry {
-            registry_url,
-            registry: registry_content,
-            settings_url,
-            settings,
-            order_urls,
-            orders,
-        })
+        let inner = core_registry::DotrainRegistry::new(registry_url).await?;
+        Ok(DotrainRegistry { inner })
     }
 
     /// Validates a registry file without downloading settings or order content.
@@ -291,7 +260,7 @@ impl DotrainRegistry {
     ) -> Result<(), DotrainRegistryError> {
         let registry_url = Url::parse(&registry_url)?;
         // Only fetch and parse the registry file to verify format/URLs.
-        let _ = Self::fetch_and_parse_registry(&registry_url).await?;
+        core_registry::DotrainRegistry::validate(&registry_url).await?;
         Ok(())
     }
 
@@ -324,9 +293,9 @@ impl DotrainRegistry {
     pub fn get_all_order_details(&self) -> Result<OrderDetailsResult, DotrainRegistryError> {
         let mut valid = BTreeMap::new();
         let mut invalid = BTreeMap::new();
-        let settings = self.settings_sources();
+        let settings = self.inner.settings_sources();
 
-        for (order_key, dotrain) in &self.orders {
+        for (order_key, dotrain) in &self.inner.orders {
             match DotrainOrderGui::get_order_details(dotrain.clone(), settings.clone()) {
                 Ok(details) => {
                     valid.insert(order_key.clone(), details);
@@ -367,7 +336,7 @@ impl DotrainRegistry {
         return_description = "Array of order keys available in the registry"
     )]
     pub fn get_order_keys(&self) -> Result<Vec<String>, DotrainRegistryError> {
-        Ok(self.order_urls.keys().cloned().collect())
+        Ok(self.inner.order_urls.keys().cloned().collect())
     }
 
     /// Gets deployment details for a specific order.
@@ -402,11 +371,8 @@ impl DotrainRegistry {
         )]
         order_key: String,
     ) -> Result<BTreeMap<String, NameAndDescriptionCfg>, DotrainRegistryError> {
-        let dotrain = self
-            .orders
-            .get(&order_key)
-            .ok_or(DotrainRegistryError::OrderKeyNotFound(order_key.clone()))?;
-        let settings = self.settings_sources();
+        let dotrain = self.inner.order(&order_key)?;
+        let settings = self.inner.settings_sources();
         let deployment_details =
             DotrainOrderGui::get_deployment_details(dotrain.clone(), settings.clone())?;
         Ok(deployment_details)
@@ -478,11 +444,8 @@ impl DotrainRegistry {
         )]
         state_update_callback: Option<js_sys::Function>,
     ) -> Result<DotrainOrderGui, DotrainRegistryError> {
-        let dotrain = self
-            .orders
-            .get(&order_key)
-            .ok_or(DotrainRegistryError::OrderKeyNotFound(order_key.clone()))?;
-        let settings = self.settings_sources();
+        let dotrain = self.inner.order(&order_key)?;
+        let settings = self.inner.settings_sources();
 
         let gui_result = match serialized_state {
             Some(serialized_state) => {
@@ -543,7 +506,7 @@ impl DotrainRegistry {
         return_description = "OrderbookYaml instance from registry settings"
     )]
     pub fn get_orderbook_yaml(&self) -> Result<OrderbookYaml, DotrainRegistryError> {
-        let yaml = OrderbookYaml::new(vec![self.settings.clone()], None)?;
+        let yaml = OrderbookYaml::new(vec![self.inner.settings.clone()], None)?;
         Ok(yaml)
     }
 }
@@ -592,7 +555,7 @@ impl DotrainRegistry {
         status_callback: Option<js_sys::Function>,
     ) -> Result<RaindexClient, DotrainRegistryError> {
         let client = RaindexClient::new(
-            vec![self.settings.clone()],
+            vec![self.inner.settings.clone()],
             None,
             query_callback,
             wipe_callback,
@@ -609,131 +572,15 @@ impl DotrainRegistry {
         &self,
         db_path: Option<std::path::PathBuf>,
     ) -> Result<RaindexClient, DotrainRegistryError> {
-        let client = RaindexClient::new(vec![self.settings.clone()], None, db_path).await?;
+        let client = RaindexClient::new(vec![self.inner.settings.clone()], None, db_path).await?;
         Ok(client)
     }
 }
 
-impl DotrainRegistry {
-    fn settings_sources(&self) -> Option<Vec<String>> {
-        if self.settings.is_empty() {
-            None
-        } else {
-            Some(vec![self.settings.clone()])
-        }
-    }
-
-    async fn fetch_and_parse_registry(
-        registry_url: &Url,
-    ) -> Result<(String, Url, HashMap<String, Url>), DotrainRegistryError> {
-        let registry_content = Self::fetch_url_content(registry_url).await?;
-        let (settings_url, order_urls) = Self::parse_registry_content(&registry_content)?;
-        Ok((registry_content, settings_url, order_urls))
-    }
-
-    fn parse_registry_content(
-        content: &str,
-    ) -> Result<(Url, HashMap<String, Url>), DotrainRegistryError> {
-        let lines: Vec<&str> = content
-            .lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .collect();
-
-        if lines.is_empty() {
-            return Err(DotrainRegistryError::InvalidRegistryFormat(
-                "Registry file is empty".to_string(),
-            ));
-        }
-
-        let first_line = lines[0];
-        if first_line.contains(' ') {
-            return Err(DotrainRegistryError::InvalidRegistryFormat(
-                "First line should be a settings URL without a key".to_string(),
-            ));
-        }
-
-        let settings_url = Url::parse(first_line)?;
-        let mut order_urls = HashMap::new();
-
-        for line in &lines[1..] {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() != 2 {
-                return Err(DotrainRegistryError::InvalidRegistryFormat(format!(
-                    "Invalid order entry format: '{}'. Expected: 'key url'",
-                    line
-                )));
-            }
-
-            let key = parts[0].to_string();
-            let url = Url::parse(parts[1])?;
-
-            order_urls.insert(key, url);
-        }
-
-        Ok((settings_url, order_urls))
-    }
-
-    async fn fetch_url_content(url: &Url) -> Result<String, DotrainRegistryError> {
-        let response = reqwest::get(url.as_str())
-            .await
-            .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(DotrainRegistryError::HttpError(format!(
-                "HTTP {}",
-                response.status()
-            )));
-        }
-
-        response
-            .text()
-            .await
-            .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))
-    }
-
-    async fn fetch_settings(settings_url: &Url) -> Result<String, DotrainRegistryError> {
-        Self::fetch_url_content(settings_url).await
-    }
-
-    async fn fetch_orders(
-        order_urls: &HashMap<String, Url>,
-    ) -> Result<HashMap<String, String>, DotrainRegistryError> {
-        use futures::future::join_all;
-
-        let mut futures = Vec::new();
-
-        for (key, url) in order_urls {
-            let key_clone = key.clone();
-            let url_clone = url.clone();
-            futures.push(async move {
-                let content = reqwest::get(url_clone.as_str())
-                    .await
-                    .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?
-                    .text()
-                    .await
-                    .map_err(|e| DotrainRegistryError::HttpError(e.to_string()))?;
-                Ok::<(String, String), DotrainRegistryError>((key_clone, content))
-            });
-        }
-
-        let results = join_all(futures).await;
-        let mut orders = HashMap::new();
-
-        for result in results {
-            let (key, content) = result?;
-            orders.insert(key, content);
-        }
-
-        Ok(orders)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use rain_orderbook_app_settings::spec_version::SpecVersion;
-    use std::collections::HashMap;
 
     const MOCK_REGISTRY_CONTENT: &str = r#"https://example.com/settings.yaml
 fixed-limit https://example.com/fixed-limit.rain
@@ -905,43 +752,9 @@ _ _: 1 1;
         use super::*;
         use wasm_bindgen_test::wasm_bindgen_test;
 
-        #[wasm_bindgen_test]
-        fn test_parse_registry_content() {
-            let (settings_url, order_urls) =
-                DotrainRegistry::parse_registry_content(MOCK_REGISTRY_CONTENT).unwrap();
-
-            assert_eq!(
-                settings_url.to_string(),
-                "https://example.com/settings.yaml"
-            );
-            assert_eq!(order_urls.len(), 2);
-            assert_eq!(
-                order_urls.get("fixed-limit").map(|u| u.to_string()),
-                Some("https://example.com/fixed-limit.rain".to_string())
-            );
-            assert_eq!(
-                order_urls.get("auction-dca").map(|u| u.to_string()),
-                Some("https://example.com/auction-dca.rain".to_string())
-            );
-        }
-
-        #[wasm_bindgen_test]
-        fn test_parse_invalid_registry_content() {
-            let result = DotrainRegistry::parse_registry_content("");
-            assert!(result.is_err());
-
-            let result = DotrainRegistry::parse_registry_content("invalid first line");
-            assert!(result.is_err());
-
-            let result = DotrainRegistry::parse_registry_content(
-                "https://example.com/settings.yaml\ninvalid-entry",
-            );
-            assert!(result.is_err());
-        }
-
         #[wasm_bindgen_test]
         fn test_get_order_keys() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/test").unwrap(),
                 registry: "".to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
@@ -959,7 +772,7 @@ _ _: 1 1;
                 .into_iter()
                 .collect(),
                 orders: HashMap::new(),
-            };
+            });
 
             let keys = registry.get_order_keys().unwrap();
             assert_eq!(keys.len(), 2);
@@ -969,7 +782,7 @@ _ _: 1 1;
 
         #[wasm_bindgen_test]
         fn test_get_all_order_details() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/test").unwrap(),
                 registry: "".to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
@@ -992,7 +805,7 @@ _ _: 1 1;
                 ]
                 .into_iter()
                 .collect(),
-            };
+            });
 
             let result = registry.get_all_order_details();
             wasm_bindgen_test::console_log!("Result: {:?}", result);
@@ -1011,7 +824,7 @@ _ _: 1 1;
 
         #[wasm_bindgen_test]
         fn test_get_all_order_details_with_invalid_order() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/test").unwrap(),
                 registry: "".to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
@@ -1034,7 +847,7 @@ _ _: 1 1;
                 ]
                 .into_iter()
                 .collect(),
-            };
+            });
 
             let result = registry.get_all_order_details().unwrap();
             assert_eq!(result.valid.len(), 1);
@@ -1045,7 +858,7 @@ _ _: 1 1;
 
         #[wasm_bindgen_test]
         fn test_get_deployment_details() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/test").unwrap(),
                 registry: "".to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
@@ -1059,7 +872,7 @@ _ _: 1 1;
                 orders: vec![("fixed-limit".to_string(), get_first_dotrain_content())]
                     .into_iter()
                     .collect(),
-            };
+            });
 
             let result = registry.get_deployment_details("fixed-limit".to_string());
             assert!(result.is_ok());
@@ -1080,14 +893,14 @@ _ _: 1 1;
 
         #[wasm_bindgen_test]
         fn test_get_deployment_details_order_not_found() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/test").unwrap(),
                 registry: "".to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
                 settings: mock_settings_content(),
                 order_urls: HashMap::new(),
                 orders: HashMap::new(),
-            };
+            });
 
             let result = registry.get_deployment_details("non-existent".to_string());
             assert!(result.is_err());
@@ -1102,7 +915,7 @@ _ _: 1 1;
 
         #[wasm_bindgen_test]
         fn test_getter_methods() {
-            let registry = DotrainRegistry {
+            let registry = DotrainRegistry::from(core_registry::DotrainRegistry {
                 registry_url: Url::parse("https://example.com/registry.txt").unwrap(),
                 registry: MOCK_REGISTRY_CONTENT.to_string(),
                 settings_url: Url::parse("https://example.com/settings.yaml").unwrap(),
@@ -1116,7 +929,7 @@ _ _: 1 1;
                 orders: vec![("fixed-limit".to_string(), get_first_dotrain_content())]
                     .into_iter()
                     .collect(),
-            };
+            });
 
             assert_eq!(registry.registry_url(), "https://example.com/registry.txt");
             assert_eq!(registry.settings_url(), "https://example.com/settings.yaml");
@@ -1198,52 +1011,21 @@ _ _: 1 1;
                 format!("{}/settings.yaml", server.url(""))
             );
             assert_eq!(registry.settings(), mock_settings_content());
-            assert_eq!(registry.order_urls.len(), 2);
-            assert_eq!(registry.orders.len(), 2);
-            assert!(registry.order_urls.contains_key("first-order"));
-            assert!(registry.order_urls.contains_key("second-order"));
-            assert!(registry.orders.contains_key("first-order"));
-            assert!(registry.orders.contains_key("second-order"));
-
-            let first_order_content = registry.orders.get("first-order").unwrap();
-            let second_order_content = registry.orders.get("second-order").unwrap();
+            assert_eq!(registry.order_urls().0.len(), 2);
+            assert_eq!(registry.orders().0.len(), 2);
+            assert!(registry.order_urls().0.contains_key("first-order"));
+            assert!(registry.order_urls().0.contains_key("second-order"));
+            assert!(registry.orders().0.contains_key("first-order"));
+            assert!(registry.orders().0.contains_key("second-order"));
+
+            let orders = registry.orders().0;
+            let first_order_content = orders.get("first-order").unwrap();
+            let second_order_content = orders.get("second-order").unwrap();
             assert_ne!(first_order_content, second_order_content);
             assert!(first_order_content.contains("_ _: 0 0;"));
             assert!(second_order_content.contains("_ _: 1 1;"));
         }
 
-        #[tokio::test]
-        async fn test_fetch_and_parse_registry() {
-            let server = MockServer::start_async().await;
-
-            let test_registry_content = format!(
-                "{}/settings.yaml\norder1 {}/order1.rain\norder2 {}/order2.rain",
-                server.url(""),
-                server.url(""),
-                server.url("")
-            );
-
-            server.mock(|when, then| {
-                when.method("GET").path("/registry.txt");
-                then.status(200).body(test_registry_content.clone());
-            });
-
-            let registry_url = Url::parse(&format!("{}/registry.txt", server.url(""))).unwrap();
-            let (registry_content, settings_url, order_urls) =
-                DotrainRegistry::fetch_and_parse_registry(&registry_url)
-                    .await
-                    .unwrap();
-
-            assert_eq!(registry_content, test_registry_content);
-            assert_eq!(
-                settings_url.to_string(),
-                format!("{}/settings.yaml", server.url(""))
-            );
-            assert_eq!(order_urls.len(), 2);
-            assert!(order_urls.contains_key("order1"));
-            assert!(order_urls.contains_key("order2"));
-        }
-
         #[tokio::test]
         async fn test_validate_success() {
             let server = MockServer::start_async().await;
@@ -1277,97 +1059,6 @@ _ _: 1 1;
             assert!(result.is_err());
         }
 
-        #[tokio::test]
-        async fn test_fetch_url_content_success() {
-            let server = MockServer::start_async().await;
-
-            server.mock(|when, then| {
-                when.method("GET").path("/test.txt");
-                then.status(200).body("test content");
-            });
-
-            let url = Url::parse(&format!("{}/test.txt", server.url(""))).unwrap();
-            let content = DotrainRegistry::fetch_url_content(&url).await.unwrap();
-
-            assert_eq!(content, "test content");
-        }
-
-        #[tokio::test]
-        async fn test_fetch_url_content_http_error() {
-            let server = MockServer::start_async().await;
-
-            server.mock(|when, then| {
-                when.method("GET").path("/error.txt");
-                then.status(500);
-            });
-
-            let url = Url::parse(&format!("{}/error.txt", server.url(""))).unwrap();
-            let result = DotrainRegistry::fetch_url_content(&url).await;
-
-            assert!(result.is_err());
-            match result.err().unwrap() {
-                DotrainRegistryError::HttpError(msg) => {
-                    assert!(msg.contains("HTTP 500"));
-                }
-                _ => panic!("Expected HttpError"),
-            }
-        }
-
-        #[tokio::test]
-        async fn test_fetch_settings() {
-            let server = MockServer::start_async().await;
-
-            server.mock(|when, then| {
-                when.method("GET").path("/settings.yaml");
-                then.status(200).body("test settings content");
-            });
-
-            let url = Url::parse(&format!("{}/settings.yaml", server.url(""))).unwrap();
-            let settings = DotrainRegistry::fetch_settings(&url).await.unwrap();
-
-            assert_eq!(settings, "test settings content");
-        }
-
-        #[tokio::test]
-        async fn test_fetch_orders() {
-            let server = MockServer::start_async().await;
-
-            server.mock(|when, then| {
-                when.method("GET").path("/order1.rain");
-                then.status(200).body(get_first_dotrain_content());
-            });
-
-            server.mock(|when, then| {
-                when.method("GET").path("/order2.rain");
-                then.status(200).body(get_second_dotrain_content());
-            });
-
-            let order_urls: HashMap<String, Url> = vec![
-                (
-                    "order1".to_string(),
-                    Url::parse(&format!("{}/order1.rain", server.url(""))).unwrap(),
-                ),
-                (
-                    "order2".to_string(),
-                    Url::parse(&format!("{}/order2.rain", server.url(""))).unwrap(),
-                ),
-            ]
-            .into_iter()
-            .collect();
-
-            let orders = DotrainRegistry::fetch_orders(&order_urls).await.unwrap();
-
-            assert_eq!(orders.len(), 2);
-            assert_eq!(orders.get("order1").unwrap(), &get_first_dotrain_content());
-            assert_eq!(orders.get("order2").unwrap(), &get_second_dotrain_content());
-
-            let order1_content = orders.get("order1").unwrap();
-            let order2_content = orders.get("order2").unwrap();
-            assert_ne!(order1_content, order2_content);
-            assert!(order1_content.contains("_ _: 0 0;"));
-            assert!(order2_content.contains("_ _: 1 1;"));
-        }
-
         #[tokio::test]
         async fn test_invalid_registry_format() {
             let server = MockServer::start_async().await;
@@ -1430,8 +1121,8 @@ _ _: 1 1;
                 format!("{}/settings.yaml", server.url(""))
             );
             assert_eq!(registry.settings(), mock_settings_content());
-            assert_eq!(registry.order_urls.len(), 0);
-            assert_eq!(registry.orders.len(), 0);
+            assert_eq!(registry.order_urls().0.len(), 0);
+            assert_eq!(registry.orders().0.len(), 0);
 
             let keys = registry.get_order_keys().unwrap();
             assert!(keys.is_empty());
@@ -1472,10 +1163,10 @@ _ _: 1 1;
                 .await
                 .unwrap();
 
-            assert_eq!(registry.order_urls.len(), 2);
-            assert!(registry.order_urls.contains_key("first-order"));
-            assert!(registry.order_urls.contains_key("second-order"));
-            assert_eq!(registry.orders.len(), 2);
+            assert_eq!(registry.order_urls().0.len(), 2);
+            assert!(registry.order_urls().0.contains_key("first-order"));
+            assert!(registry.order_urls().0.contains_key("second-order"));
+            assert_eq!(registry.orders().0.len(), 2);
 
             let gui1 = registry
                 .get_gui("first-order".to_string(), "flare".to_string(), None, None)
@@ -25,6 +25,12 @@ pub enum ApiError {
     #[error(transparent)]
     Raindex(#[from] RaindexError),
 
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
     #[error("Internal server error: {0}")]
     Internal(String),
 }
@@ -56,10 +62,13 @@ impl ApiError {
                 | RaindexError::VaultNotFound(_, _, _)
                 | RaindexError::SubgraphNotFound(_, _)
                 | RaindexError::SubgraphNotConfigured(_)
-                | RaindexError::NoNetworksConfigured => Status::NotFound,
+                | RaindexError::NoNetworksConfigured
+                | RaindexError::TransactionIndexingTimeout { .. } => Status::NotFound,
 
                 _ => Status::InternalServerError,
             },
+            ApiError::BadRequest(_) => Status::BadRequest,
+            ApiError::NotFound(_) => Status::NotFound,
             ApiError::Internal(_) => Status::InternalServerError,
         }
     }
@@ -67,7 +76,9 @@ impl ApiError {
     fn to_response(&self) -> ApiErrorResponse {
         let readable_message = match self {
             ApiError::Raindex(e) => e.to_readable_msg(),
-            ApiError::Internal(msg) => msg.clone(),
+            ApiError::BadRequest(msg) | ApiError::NotFound(msg) | ApiError::Internal(msg) => {
+                msg.clone()
+            }
         };
 
         ApiErrorResponse {
@@ -167,6 +178,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_status_code_read_errors() {
+        assert_eq!(
+            ApiError::BadRequest("invalid owner".to_string()).status_code(),
+            Status::BadRequest
+        );
+        assert_eq!(
+            ApiError::NotFound("order".to_string()).status_code(),
+            Status::NotFound
+        );
+        assert_eq!(
+            ApiError::Raindex(RaindexError::TransactionIndexingTimeout {
+                tx_hash: B256::ZERO,
+                attempts: 1,
+            })
+            .status_code(),
+            Status::NotFound
+        );
+    }
+
     #[test]
     fn test_status_code_internal_server_error() {
         let internal_error = ApiError::Internal("Something went wrong".to_string());
@@ -1,13 +1,20 @@
 mod error;
+mod profiles;
 mod routes;
 
 use error::ApiErrorResponse;
+use profiles::{ProfileRegistry, ProfilesConfig};
+use rocket::fairing::AdHoc;
 use rocket::http::Method;
 use rocket::{launch, Build, Rocket};
 use rocket_cors::{AllowedHeaders, AllowedOrigins, CorsOptions};
+use routes::orders::{OrderResponse, OrdersListResponse, QuoteResponse, TradeResponse};
 use routes::take_orders::{
-    ApprovalApiResponse, BuyRequest, SellRequest, TakeOrdersApiResponse, TakeOrdersReadyResponse,
+    ApprovalApiResponse, BuyRequest, RoutedHopResponse, RoutedTakeOrdersResponse, SellRequest,
+    TakeOrdersApiResponse, TakeOrdersReadyResponse,
 };
+use routes::transactions::TransactionResponse;
+use routes::vaults::{BalanceChangeResponse, TokenResponse, VaultResponse, VaultsListResponse};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -17,17 +24,41 @@ use utoipa_swagger_ui::SwaggerUi;
         title = "Rain Orderbook API",
         description = "REST API for interacting with Rain Orderbook."
     ),
-    paths(routes::take_orders::buy, routes::take_orders::sell),
+    paths(
+        routes::take_orders::buy,
+        routes::take_orders::sell,
+        routes::orders::get_orders,
+        routes::orders::get_order,
+        routes::orders::get_order_trades,
+        routes::orders::get_order_quotes,
+        routes::vaults::get_vaults,
+        routes::vaults::get_balance_changes,
+        routes::transactions::get_transaction
+    ),
     components(schemas(
         BuyRequest,
         SellRequest,
         TakeOrdersApiResponse,
         ApprovalApiResponse,
         TakeOrdersReadyResponse,
+        RoutedTakeOrdersResponse,
+        RoutedHopResponse,
+        OrderResponse,
+        OrdersListResponse,
+        TradeResponse,
+        QuoteResponse,
+        VaultResponse,
+        VaultsListResponse,
+        TokenResponse,
+        BalanceChangeResponse,
+        TransactionResponse,
         ApiErrorResponse
     )),
     tags(
-        (name = "Take Orders", description = "Endpoints for generating take orders calldata")
+        (name = "Take Orders", description = "Endpoints for generating take orders calldata"),
+        (name = "Orders", description = "Endpoints for reading orders, their trades and quotes"),
+        (name = "Vaults", description = "Endpoints for reading vaults and their balance changes"),
+        (name = "Transactions", description = "Endpoints for reading indexed transactions")
     )
 )]
 struct ApiDoc;
@@ -44,6 +75,34 @@ fn configure_cors() -> CorsOptions {
     }
 }
 
+/// Loads the settings profiles from Rocket's configuration before launch, a
+/// profile that fails to load aborts the launch.
+fn settings_profiles() -> AdHoc {
+    AdHoc::try_on_ignite("Settings profiles", |rocket| async move {
+        let config: ProfilesConfig = match rocket.figment().extract() {
+            Ok(config) => config,
+            Err(e) => {
+                log::error!("Invalid settings profiles configuration: {}", e);
+                return Err(rocket);
+            }
+        };
+
+        match ProfileRegistry::load(&config).await {
+            Ok(registry) => {
+                let names = registry.names();
+                if !names.is_empty() {
+                    log::info!("Loaded settings profiles: {}", names.join(", "));
+                }
+                Ok(rocket.manage(registry))
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                Err(rocket)
+            }
+        }
+    })
+}
+
 fn rocket() -> Rocket<Build> {
     let cors = configure_cors()
         .to_cors()
@@ -51,7 +110,11 @@ fn rocket() -> Rocket<Build> {
 
     rocket::build()
         .attach(cors.clone())
+        .attach(settings_profiles())
         .mount("/", routes::take_orders::routes())
+        .mount("/", routes::orders::routes())
+        .mount("/", routes::vaults::routes())
+        .mount("/", routes::transactions::routes())
         .mount("/", rocket_cors::catch_all_options_routes())
         .mount(
             "/",
@@ -373,6 +436,119 @@ mod tests {
         assert_eq!(response.status(), Status::UnprocessableEntity);
     }
 
+    #[test]
+    fn test_buy_without_profile_or_yaml() {
+        let client = client();
+        let response = client
+            .post("/take-orders/buy")
+            .header(ContentType::JSON)
+            .body(
+                r#"{
+                "taker": "0x1111111111111111111111111111111111111111",
+                "chainId": 1,
+                "tokenIn": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                "tokenOut": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                "amount": "100",
+                "maxRatio": "2.5"
+            }"#,
+            )
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_sell_unknown_profile() {
+        let client = client();
+        let response = client
+            .post("/take-orders/sell")
+            .header(ContentType::JSON)
+            .body(
+                r#"{
+                "profile": "unknown",
+                "taker": "0x1111111111111111111111111111111111111111",
+                "chainId": 1,
+                "tokenIn": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                "tokenOut": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                "amount": "100",
+                "maxRatio": "2.5"
+            }"#,
+            )
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+        let body = response.into_string().unwrap();
+        assert!(body.contains("Settings profile unknown not found"));
+    }
+
+    #[test]
+    fn test_get_orders_unknown_profile() {
+        let client = client();
+        let response = client.get("/orders?profile=unknown").dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_get_orders_missing_yaml() {
+        let client = client();
+        let response = client.get("/orders?yamlContent=").dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_get_orders_invalid_owner() {
+        let client = client();
+        let response = client
+            .get("/orders?yamlContent=version%3A%201&owners=invalid-address")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        let body = response.into_string().unwrap();
+        assert!(body.contains("Invalid owner address"));
+    }
+
+    #[test]
+    fn test_get_order_invalid_hash() {
+        let client = client();
+        let response = client
+            .get("/orders/0x1234/trades?yamlContent=version%3A%201")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_get_vaults_invalid_token() {
+        let client = client();
+        let response = client
+            .get("/vaults?yamlContent=version%3A%201&tokens=0x1234")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_get_balance_changes_invalid_type() {
+        let client = client();
+        let response = client
+            .get("/vaults/0x01/balance-changes?yamlContent=version%3A%201&chainId=1&orderbook=0x1111111111111111111111111111111111111111&types=swap")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_get_transaction_invalid_orderbook() {
+        let client = client();
+        let response = client
+            .get("/tx/0x0000000000000000000000000000000000000000000000000000000000000000?yamlContent=version%3A%201&chainId=1&orderbook=invalid")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
     #[test]
     fn test_swagger_ui_returns_html() {
         let client = client();
@@ -417,6 +593,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_openapi_json_contains_read_paths() {
+        let client = client();
+        let response = client.get("/swagger/openapi.json").dispatch();
+        let body = response.into_string().unwrap();
+        let spec: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        for (path, tag) in [
+            ("/orders", "Orders"),
+            ("/orders/{hash}", "Orders"),
+            ("/orders/{hash}/trades", "Orders"),
+            ("/orders/{hash}/quotes", "Orders"),
+            ("/vaults", "Vaults"),
+            ("/vaults/{id}/balance-changes", "Vaults"),
+            ("/tx/{hash}", "Transactions"),
+        ] {
+            assert_eq!(spec["paths"][path]["get"]["tags"][0], tag, "{}", path);
+        }
+
+        let params = spec["paths"]["/orders"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        for name in ["yamlContent", "chainIds", "owners", "page", "pageSize"] {
+            assert!(
+                params.iter().any(|p| p["name"] == name),
+                "missing {} parameter",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_openapi_json_contains_schemas() {
         let client = client();
@@ -430,6 +637,10 @@ mod tests {
         assert!(schemas["TakeOrdersApiResponse"].is_object());
         assert!(schemas["ApprovalApiResponse"].is_object());
         assert!(schemas["TakeOrdersReadyResponse"].is_object());
+        assert!(schemas["OrdersListResponse"].is_object());
+        assert!(schemas["VaultsListResponse"].is_object());
+        assert!(schemas["TradeResponse"].is_object());
+        assert!(schemas["TransactionResponse"].is_object());
         assert!(schemas["ApiErrorResponse"].is_object());
     }
 
@@ -464,7 +675,11 @@ mod tests {
 
         assert_eq!(
             buy_schema["yamlContent"]["description"],
-            "YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses"
+            "YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile."
+        );
+        assert_eq!(
+            buy_schema["profile"]["description"],
+            "Name of a settings profile configured on the server. Required unless yamlContent is given."
         );
         assert_eq!(
             buy_schema["taker"]["description"],
@@ -507,7 +722,11 @@ mod tests {
 
         assert_eq!(
             sell_schema["yamlContent"]["description"],
-            "YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses"
+            "YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile."
+        );
+        assert_eq!(
+            sell_schema["profile"]["description"],
+            "Name of a settings profile configured on the server. Required unless yamlContent is given."
         );
         assert_eq!(
             sell_schema["taker"]["description"],
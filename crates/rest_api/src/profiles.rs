@@ -0,0 +1,352 @@
+use crate::error::ApiError;
+use futures::future::LocalBoxFuture;
+use rain_orderbook_app_settings::remote::settings::{fetch_settings, FetchSettingsError};
+use rain_orderbook_common::raindex_client::{RaindexClient, RaindexError};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::LocalSet;
+use url::Url;
+
+const DEFAULT_RELOAD_INTERVAL_SECS: u64 = 2;
+
+fn default_reload_interval_secs() -> u64 {
+    DEFAULT_RELOAD_INTERVAL_SECS
+}
+
+/// Server side settings, read from Rocket's configuration (`Rocket.toml` or
+/// `ROCKET_` prefixed environment variables).
+///
+/// ```toml
+/// [default.settings_profiles]
+/// base = "./settings/base.yaml"
+/// arbitrum = "https://example.com/settings/arbitrum.yaml"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProfilesConfig {
+    /// Settings YAML per profile name, either a file path or an http(s) URL
+    #[serde(default)]
+    pub settings_profiles: HashMap<String, String>,
+    /// How often file backed profiles are checked for changes
+    #[serde(default = "default_reload_interval_secs")]
+    pub settings_reload_interval_secs: u64,
+}
+
+impl Default for ProfilesConfig {
+    fn default() -> Self {
+        Self {
+            settings_profiles: HashMap::new(),
+            settings_reload_interval_secs: DEFAULT_RELOAD_INTERVAL_SECS,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("Failed to read settings file {0}: {1}")]
+    ReadFile(PathBuf, std::io::Error),
+    #[error("Failed to fetch settings: {0}")]
+    FetchSettings(#[from] FetchSettingsError),
+    #[error("Failed to build client: {0}")]
+    Raindex(#[from] RaindexError),
+    #[error("Failed to start profile worker: {0}")]
+    Worker(std::io::Error),
+    #[error("Settings profile {0} stopped before it was loaded")]
+    WorkerStopped(String),
+    #[error("Settings profile {0}: {1}")]
+    Profile(String, Box<ProfileError>),
+}
+
+/// Where a profile's settings YAML is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileSource {
+    File(PathBuf),
+    Url(Url),
+}
+
+impl ProfileSource {
+    pub fn parse(value: &str) -> Self {
+        match Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Self::Url(url),
+            _ => Self::File(PathBuf::from(value)),
+        }
+    }
+
+    async fn load(&self) -> Result<String, ProfileError> {
+        match self {
+            Self::File(path) => {
+                std::fs::read_to_string(path).map_err(|e| ProfileError::ReadFile(path.clone(), e))
+            }
+            Self::Url(url) => Ok(fetch_settings(url.clone()).await?),
+        }
+    }
+
+    /// Last modification time of a file source, remote sources are never reloaded
+    fn modified(&self) -> Option<SystemTime> {
+        match self {
+            Self::File(path) => std::fs::metadata(path).and_then(|m| m.modified()).ok(),
+            Self::Url(_) => None,
+        }
+    }
+}
+
+async fn build_client(source: &ProfileSource) -> Result<RaindexClient, ProfileError> {
+    let yaml = source.load().await?;
+    Ok(RaindexClient::new(vec![yaml], None, None).await?)
+}
+
+type Job = Box<dyn FnOnce(RaindexClient) -> LocalBoxFuture<'static, ()> + Send>;
+
+/// A long lived `RaindexClient` for one settings profile.
+///
+/// RaindexClient is not Send, so it lives on a dedicated thread with its own
+/// current thread runtime and requests are handed to it as jobs.
+pub struct ProfileHandle {
+    name: String,
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl ProfileHandle {
+    pub async fn spawn(
+        name: String,
+        source: ProfileSource,
+        reload_interval: Duration,
+    ) -> Result<Self, ProfileError> {
+        let (jobs, jobs_rx) = mpsc::unbounded_channel();
+        let (ready_tx, ready_rx) = oneshot::channel();
+
+        let worker_name = name.clone();
+        std::thread::Builder::new()
+            .name(format!("settings-profile-{}", name))
+            .spawn(move || {
+                let rt = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(rt) => rt,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(ProfileError::Worker(e)));
+                        return;
+                    }
+                };
+                LocalSet::new().block_on(
+                    &rt,
+                    run_worker(worker_name, source, reload_interval, jobs_rx, ready_tx),
+                );
+            })
+            .map_err(ProfileError::Worker)?;
+
+        ready_rx
+            .await
+            .map_err(|_| ProfileError::WorkerStopped(name.clone()))??;
+
+        Ok(Self { name, jobs })
+    }
+
+    /// Runs a task against the profile's current client
+    pub async fn run<F, Fut, T>(&self, task: F) -> Result<T, ApiError>
+    where
+        F: FnOnce(RaindexClient) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, ApiError>> + 'static,
+        T: Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+        let job: Job = Box::new(move |client| {
+            Box::pin(async move {
+                let _ = result_tx.send(task(client).await);
+            })
+        });
+
+        let stopped =
+            || ApiError::Internal(format!("Settings profile {} is not running", self.name));
+        self.jobs.send(job).map_err(|_| stopped())?;
+        result_rx.await.map_err(|_| stopped())?
+    }
+}
+
+async fn run_worker(
+    name: String,
+    source: ProfileSource,
+    reload_interval: Duration,
+    mut jobs: mpsc::UnboundedReceiver<Job>,
+    ready: oneshot::Sender<Result<(), ProfileError>>,
+) {
+    let mut modified = source.modified();
+    let mut client = match build_client(&source).await {
+        Ok(client) => {
+            let _ = ready.send(Ok(()));
+            client
+        }
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return;
+        }
+    };
+
+    let mut ticker = tokio::time::interval(reload_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let watch_file = matches!(source, ProfileSource::File(_));
+
+    loop {
+        tokio::select! {
+            job = jobs.recv() => match job {
+                Some(job) => {
+                    tokio::task::spawn_local(job(client.clone()));
+                }
+                // every handle is gone, the server is shutting down
+                None => break,
+            },
+            _ = ticker.tick(), if watch_file => {
+                let current = source.modified();
+                if current == modified {
+                    continue;
+                }
+                modified = current;
+
+                // a broken edit keeps serving the last good settings
+                match build_client(&source).await {
+                    Ok(reloaded) => {
+                        client = reloaded;
+                        log::info!("Reloaded settings profile {}", name);
+                    }
+                    Err(e) => log::warn!("Failed to reload settings profile {}: {}", name, e),
+                }
+            }
+        }
+    }
+}
+
+/// All settings profiles the server was configured with
+#[derive(Default)]
+pub struct ProfileRegistry {
+    profiles: HashMap<String, ProfileHandle>,
+}
+
+impl ProfileRegistry {
+    pub async fn load(config: &ProfilesConfig) -> Result<Self, ProfileError> {
+        let reload_interval = Duration::from_secs(config.settings_reload_interval_secs.max(1));
+
+        let mut profiles = HashMap::new();
+        for (name, value) in &config.settings_profiles {
+            let handle =
+                ProfileHandle::spawn(name.clone(), ProfileSource::parse(value), reload_interval)
+                    .await
+                    .map_err(|e| ProfileError::Profile(name.clone(), Box::new(e)))?;
+            profiles.insert(name.clone(), handle);
+        }
+
+        Ok(Self { profiles })
+    }
+
+    pub fn get(&self, name: &str) -> Result<&ProfileHandle, ApiError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ApiError::NotFound(format!("Settings profile {} not found", name)))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rain_orderbook_app_settings::spec_version::SpecVersion;
+    use std::io::Write;
+
+    fn settings_yaml(network: &str, chain_id: u32) -> String {
+        format!(
+            r#"
+version: {}
+networks:
+    {}:
+        rpcs:
+            - https://example.com/rpc
+        chain-id: {}
+"#,
+            SpecVersion::current(),
+            network,
+            chain_id
+        )
+    }
+
+    async fn network_names(handle: &ProfileHandle) -> Vec<String> {
+        handle
+            .run(|client| async move {
+                let mut names: Vec<String> = client.get_all_networks()?.into_keys().collect();
+                names.sort();
+                Ok(names)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_profile_source_parse() {
+        assert_eq!(
+            ProfileSource::parse("https://example.com/settings.yaml"),
+            ProfileSource::Url(Url::parse("https://example.com/settings.yaml").unwrap())
+        );
+        assert_eq!(
+            ProfileSource::parse("./settings.yaml"),
+            ProfileSource::File(PathBuf::from("./settings.yaml"))
+        );
+        assert_eq!(
+            ProfileSource::parse("/etc/raindex/settings.yaml"),
+            ProfileSource::File(PathBuf::from("/etc/raindex/settings.yaml"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_profile_runs_jobs_and_reloads_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(settings_yaml("base", 8453).as_bytes())
+            .unwrap();
+
+        let handle = ProfileHandle::spawn(
+            "base".to_string(),
+            ProfileSource::File(file.path().to_path_buf()),
+            Duration::from_millis(20),
+        )
+        .await
+        .unwrap();
+        assert_eq!(network_names(&handle).await, vec!["base".to_string()]);
+
+        // a broken edit keeps the last good client
+        std::fs::write(file.path(), "networks: [").unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(network_names(&handle).await, vec!["base".to_string()]);
+
+        std::fs::write(file.path(), settings_yaml("polygon", 137)).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(network_names(&handle).await, vec!["polygon".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_registry_load_errors() {
+        let config = ProfilesConfig {
+            settings_profiles: HashMap::from([(
+                "missing".to_string(),
+                "/definitely/not/a/settings.yaml".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let err = ProfileRegistry::load(&config).await.err().unwrap();
+        assert!(err.to_string().contains("Settings profile missing"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_unknown_profile() {
+        let registry = ProfileRegistry::default();
+        assert!(registry.names().is_empty());
+        assert!(matches!(registry.get("base"), Err(ApiError::NotFound(_))));
+    }
+}
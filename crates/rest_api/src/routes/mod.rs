@@ -1 +1,140 @@
+pub mod orders;
 pub mod take_orders;
+pub mod transactions;
+pub mod vaults;
+
+use crate::error::ApiError;
+use crate::profiles::ProfileRegistry;
+use alloy::primitives::{Address, B256};
+use rain_orderbook_common::raindex_client::RaindexClient;
+use std::future::Future;
+use std::str::FromStr;
+
+/// Runs a `RaindexClient` task to completion on a dedicated current thread runtime.
+///
+/// RaindexClient contains Rc<RefCell<...>> which is not Send, but Rocket requires
+/// Send futures, so the whole task is built and polled inside spawn_blocking.
+pub(crate) async fn run_blocking<F, Fut, T>(task: F) -> Result<T, ApiError>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, ApiError>>,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ApiError::Internal(format!("Failed to create runtime: {}", e)))?;
+
+        rt.block_on(task())
+    })
+    .await
+    .map_err(|e| ApiError::Internal(format!("Task execution failed: {}", e)))?
+}
+
+/// Runs a task against the client for a request's settings. Inline YAML takes
+/// precedence and gets a fresh client, otherwise the named profile's long lived
+/// client is used.
+pub(crate) async fn with_client<F, Fut, T>(
+    profiles: &ProfileRegistry,
+    profile: Option<String>,
+    yaml_content: Option<String>,
+    task: F,
+) -> Result<T, ApiError>
+where
+    F: FnOnce(RaindexClient) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, ApiError>> + 'static,
+    T: Send + 'static,
+{
+    match (yaml_content, profile) {
+        (Some(yaml_content), _) => {
+            run_blocking(move || async move {
+                let client = RaindexClient::new(vec![yaml_content], None, None).await?;
+                task(client).await
+            })
+            .await
+        }
+        (None, Some(profile)) => profiles.get(&profile)?.run(task).await,
+        (None, None) => Err(ApiError::BadRequest(
+            "Either a settings profile or yamlContent is required".to_string(),
+        )),
+    }
+}
+
+pub(crate) fn parse_address(field: &str, value: &str) -> Result<Address, ApiError> {
+    Address::from_str(value)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid {} address {}: {}", field, value, e)))
+}
+
+pub(crate) fn parse_addresses(
+    field: &str,
+    values: Option<&[String]>,
+) -> Result<Option<Vec<Address>>, ApiError> {
+    match values {
+        Some(values) if !values.is_empty() => values
+            .iter()
+            .map(|value| parse_address(field, value))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+        _ => Ok(None),
+    }
+}
+
+pub(crate) fn parse_hash(field: &str, value: &str) -> Result<B256, ApiError> {
+    B256::from_str(value)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid {} {}: {}", field, value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_addresses() {
+        assert_eq!(parse_addresses("owner", None).unwrap(), None);
+        assert_eq!(parse_addresses("owner", Some(&[])).unwrap(), None);
+
+        let parsed = parse_addresses(
+            "owner",
+            Some(&["0x1111111111111111111111111111111111111111".to_string()]),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(parsed, vec![Address::repeat_byte(0x11)]);
+
+        let err = parse_addresses("owner", Some(&["invalid".to_string()])).unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(msg) if msg.contains("Invalid owner address")));
+    }
+
+    #[tokio::test]
+    async fn test_with_client_requires_settings() {
+        let err = with_client(&ProfileRegistry::default(), None, None, |_| async {
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ApiError::BadRequest(_)));
+
+        let err = with_client(
+            &ProfileRegistry::default(),
+            Some("base".to_string()),
+            None,
+            |_| async { Ok(()) },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ApiError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_parse_hash() {
+        assert_eq!(
+            parse_hash("order hash", &B256::ZERO.to_string()).unwrap(),
+            B256::ZERO
+        );
+        assert!(matches!(
+            parse_hash("order hash", "0x1234"),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
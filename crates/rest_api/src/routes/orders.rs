@@ -0,0 +1,584 @@
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::profiles::ProfileRegistry;
+use crate::routes::transactions::TransactionResponse;
+use crate::routes::vaults::{chain_ids, BalanceChangeResponse, VaultResponse};
+use crate::routes::{parse_addresses, parse_hash, with_client};
+use alloy::primitives::B256;
+use rain_orderbook_common::raindex_client::order_quotes::RaindexOrderQuote;
+use rain_orderbook_common::raindex_client::orders::{
+    GetOrdersFilters, GetOrdersTokenFilter, RaindexOrder,
+};
+use rain_orderbook_common::raindex_client::trades::RaindexTrade;
+use rain_orderbook_common::raindex_client::{ChainIds, RaindexClient};
+use rocket::serde::json::Json;
+use rocket::{get, FromForm, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+const DEFAULT_PAGE_SIZE: u16 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponse {
+    /// Chain ID of the order's orderbook
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    /// Order hash
+    #[schema(example = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890")]
+    pub order_hash: String,
+    /// Order owner address
+    #[schema(example = "0x1111111111111111111111111111111111111111")]
+    pub owner: String,
+    /// Orderbook contract the order was added to
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+    /// Whether the order is still live
+    #[schema(example = true)]
+    pub active: bool,
+    /// Unix timestamp (seconds) the order was added at
+    #[schema(example = "1700000000")]
+    pub timestamp_added: String,
+    /// Number of trades the order has been part of
+    #[schema(example = 12)]
+    pub trades_count: u16,
+    /// Rainlang source of the order, when its meta carries one
+    pub rainlang: Option<String>,
+    /// Vaults the order receives tokens into
+    pub inputs: Vec<VaultResponse>,
+    /// Vaults the order sends tokens from
+    pub outputs: Vec<VaultResponse>,
+    /// Transaction that added the order
+    pub transaction: Option<TransactionResponse>,
+}
+
+impl From<RaindexOrder> for OrderResponse {
+    fn from(order: RaindexOrder) -> Self {
+        Self {
+            chain_id: order.chain_id(),
+            order_hash: order.order_hash().to_string(),
+            owner: order.owner().to_string(),
+            orderbook: order.orderbook().to_string(),
+            active: order.active(),
+            timestamp_added: order.timestamp_added().to_string(),
+            trades_count: order.trades_count(),
+            rainlang: order.rainlang(),
+            inputs: order
+                .inputs_list()
+                .items()
+                .into_iter()
+                .map(VaultResponse::from)
+                .collect(),
+            outputs: order
+                .outputs_list()
+                .items()
+                .into_iter()
+                .map(VaultResponse::from)
+                .collect(),
+            transaction: order.transaction().map(TransactionResponse::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrdersListResponse {
+    /// Orders on the requested page
+    pub orders: Vec<OrderResponse>,
+    /// Total number of orders matching the filters across all pages
+    #[schema(example = 250)]
+    pub total_count: u32,
+    /// Page number of this response
+    #[schema(example = 1)]
+    pub page: u16,
+    /// Number of orders per page
+    #[schema(example = 100)]
+    pub page_size: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeResponse {
+    /// Trade identifier
+    #[schema(example = "0x0123")]
+    pub id: String,
+    /// Hash of the order that was traded against
+    #[schema(example = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890")]
+    pub order_hash: String,
+    /// Orderbook contract the trade happened on
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+    /// Unix timestamp (seconds) of the trade
+    #[schema(example = "1700000000")]
+    pub timestamp: String,
+    /// Transaction the trade happened in
+    pub transaction: TransactionResponse,
+    /// Balance change of the order's input vault
+    pub input: BalanceChangeResponse,
+    /// Balance change of the order's output vault
+    pub output: BalanceChangeResponse,
+}
+
+impl From<RaindexTrade> for TradeResponse {
+    fn from(trade: RaindexTrade) -> Self {
+        Self {
+            id: trade.id().to_string(),
+            order_hash: trade.order_hash().to_string(),
+            orderbook: trade.orderbook().to_string(),
+            timestamp: trade.timestamp().to_string(),
+            transaction: trade.transaction().into(),
+            input: trade.input_vault_balance_change().into(),
+            output: trade.output_vault_balance_change().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteResponse {
+    /// Pair name in the form input/output
+    #[schema(example = "WETH/USDC")]
+    pub pair: String,
+    /// Index of the input in the order's inputs
+    #[schema(example = 0)]
+    pub input_index: u32,
+    /// Index of the output in the order's outputs
+    #[schema(example = 0)]
+    pub output_index: u32,
+    /// Block the quote was taken at
+    #[schema(example = 12345678)]
+    pub block_number: u64,
+    /// Whether the order could be quoted for this pair
+    #[schema(example = true)]
+    pub success: bool,
+    /// Maximum amount of output token the order offers
+    #[schema(example = "1000")]
+    pub max_output: Option<String>,
+    /// Maximum amount of input token the order takes for the max output
+    #[schema(example = "0.5")]
+    pub max_input: Option<String>,
+    /// Input token per 1 output token
+    #[schema(example = "0.0005")]
+    pub ratio: Option<String>,
+    /// Output token per 1 input token
+    #[schema(example = "2000")]
+    pub inverse_ratio: Option<String>,
+    /// Reason the quote failed
+    pub error: Option<String>,
+}
+
+impl From<RaindexOrderQuote> for QuoteResponse {
+    fn from(quote: RaindexOrderQuote) -> Self {
+        let data = quote.data;
+        Self {
+            pair: quote.pair.pair_name,
+            input_index: quote.pair.input_index,
+            output_index: quote.pair.output_index,
+            block_number: quote.block_number,
+            success: quote.success,
+            max_output: data.as_ref().map(|d| d.formatted_max_output.clone()),
+            max_input: data.as_ref().map(|d| d.formatted_max_input.clone()),
+            ratio: data.as_ref().map(|d| d.formatted_ratio.clone()),
+            inverse_ratio: data.map(|d| d.formatted_inverse_ratio),
+            error: quote.error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct OrdersQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Only query these chain IDs (all configured networks when omitted)
+    #[field(name = "chainIds")]
+    pub chain_ids: Option<Vec<u32>>,
+    /// Only return orders owned by these addresses
+    pub owners: Option<Vec<String>>,
+    /// Only return active (true) or removed (false) orders
+    pub active: Option<bool>,
+    /// Only return orders with one of these tokens as an input
+    #[field(name = "inputTokens")]
+    pub input_tokens: Option<Vec<String>>,
+    /// Only return orders with one of these tokens as an output
+    #[field(name = "outputTokens")]
+    pub output_tokens: Option<Vec<String>>,
+    /// Only return orders added to these orderbook contracts
+    pub orderbooks: Option<Vec<String>>,
+    /// Page number (defaults to 1)
+    #[param(example = 1)]
+    pub page: Option<u16>,
+    /// Number of orders per page (defaults to 100)
+    #[field(name = "pageSize")]
+    #[param(example = 100)]
+    pub page_size: Option<u16>,
+}
+
+impl OrdersQuery {
+    fn filters(&self) -> Result<GetOrdersFilters, ApiError> {
+        let inputs = parse_addresses("input token", self.input_tokens.as_deref())?;
+        let outputs = parse_addresses("output token", self.output_tokens.as_deref())?;
+        let tokens = (inputs.is_some() || outputs.is_some())
+            .then_some(GetOrdersTokenFilter { inputs, outputs });
+
+        Ok(GetOrdersFilters {
+            owners: parse_addresses("owner", self.owners.as_deref())?.unwrap_or_default(),
+            active: self.active,
+            order_hash: None,
+            tokens,
+            orderbook_addresses: parse_addresses("orderbook", self.orderbooks.as_deref())?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct OrderQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Only look for the order on these chain IDs (all configured networks when omitted)
+    #[field(name = "chainIds")]
+    pub chain_ids: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct OrderTradesQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Only look for the order on these chain IDs (all configured networks when omitted)
+    #[field(name = "chainIds")]
+    pub chain_ids: Option<Vec<u32>>,
+    /// Only return trades at or after this Unix timestamp (seconds)
+    #[field(name = "startTimestamp")]
+    pub start_timestamp: Option<u64>,
+    /// Only return trades at or before this Unix timestamp (seconds)
+    #[field(name = "endTimestamp")]
+    pub end_timestamp: Option<u64>,
+    /// Page number (defaults to 1)
+    #[param(example = 1)]
+    pub page: Option<u16>,
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct OrderQuotesQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Only look for the order on these chain IDs (all configured networks when omitted)
+    #[field(name = "chainIds")]
+    pub chain_ids: Option<Vec<u32>>,
+    /// Quote at this block instead of the latest one
+    #[field(name = "blockNumber")]
+    pub block_number: Option<u64>,
+}
+
+async fn find_order(
+    client: &RaindexClient,
+    chain_ids: Option<ChainIds>,
+    order_hash: B256,
+) -> Result<RaindexOrder, ApiError> {
+    let filters = GetOrdersFilters {
+        order_hash: Some(order_hash),
+        ..Default::default()
+    };
+    let result = client
+        .get_orders(chain_ids, Some(filters), Some(1), Some(1))
+        .await?;
+    result
+        .orders()
+        .first()
+        .cloned()
+        .ok_or_else(|| ApiError::NotFound(format!("Order {} not found", order_hash)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders",
+    tag = "Orders",
+    params(OrdersQuery),
+    responses(
+        (status = 200, description = "Orders matching the filters", body = OrdersListResponse),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/orders?<query..>")]
+pub async fn get_orders(
+    profiles: &State<ProfileRegistry>,
+    query: OrdersQuery,
+) -> Result<Json<OrdersListResponse>, ApiError> {
+    let filters = query.filters()?;
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let chain_ids = chain_ids(query.chain_ids);
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let result = client
+                .get_orders(chain_ids, Some(filters), Some(page), Some(page_size))
+                .await?;
+            Ok(OrdersListResponse {
+                orders: result
+                    .orders()
+                    .iter()
+                    .cloned()
+                    .map(OrderResponse::from)
+                    .collect(),
+                total_count: result.total_count(),
+                page,
+                page_size,
+            })
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{hash}",
+    tag = "Orders",
+    params(
+        ("hash" = String, Path, description = "Order hash"),
+        OrderQuery
+    ),
+    responses(
+        (status = 200, description = "The order", body = OrderResponse),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Order or configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/orders/<hash>?<query..>")]
+pub async fn get_order(
+    profiles: &State<ProfileRegistry>,
+    hash: String,
+    query: OrderQuery,
+) -> Result<Json<OrderResponse>, ApiError> {
+    let order_hash = parse_hash("order hash", &hash)?;
+    let chain_ids = chain_ids(query.chain_ids);
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let order = find_order(&client, chain_ids, order_hash).await?;
+            Ok(OrderResponse::from(order))
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{hash}/trades",
+    tag = "Orders",
+    params(
+        ("hash" = String, Path, description = "Order hash"),
+        OrderTradesQuery
+    ),
+    responses(
+        (status = 200, description = "Trades of the order, newest first", body = Vec<TradeResponse>),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Order or configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/orders/<hash>/trades?<query..>")]
+pub async fn get_order_trades(
+    profiles: &State<ProfileRegistry>,
+    hash: String,
+    query: OrderTradesQuery,
+) -> Result<Json<Vec<TradeResponse>>, ApiError> {
+    let order_hash = parse_hash("order hash", &hash)?;
+    let chain_ids = chain_ids(query.chain_ids);
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let order = find_order(&client, chain_ids, order_hash).await?;
+            let trades = order
+                .get_trades_list(query.start_timestamp, query.end_timestamp, query.page)
+                .await?;
+            Ok(trades
+                .into_iter()
+                .map(TradeResponse::from)
+                .collect::<Vec<_>>())
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{hash}/quotes",
+    tag = "Orders",
+    params(
+        ("hash" = String, Path, description = "Order hash"),
+        OrderQuotesQuery
+    ),
+    responses(
+        (status = 200, description = "Quotes for every input/output pair of the order", body = Vec<QuoteResponse>),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Order or configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/orders/<hash>/quotes?<query..>")]
+pub async fn get_order_quotes(
+    profiles: &State<ProfileRegistry>,
+    hash: String,
+    query: OrderQuotesQuery,
+) -> Result<Json<Vec<QuoteResponse>>, ApiError> {
+    let order_hash = parse_hash("order hash", &hash)?;
+    let chain_ids = chain_ids(query.chain_ids);
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let order = find_order(&client, chain_ids, order_hash).await?;
+            let quotes = order.get_quotes(query.block_number, None).await?;
+            Ok(quotes
+                .into_iter()
+                .map(QuoteResponse::from)
+                .collect::<Vec<_>>())
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_orders, get_order, get_order_trades, get_order_quotes]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    fn query() -> OrdersQuery {
+        OrdersQuery {
+            profile: None,
+            yaml_content: Some("version: 1".to_string()),
+            chain_ids: None,
+            owners: None,
+            active: None,
+            input_tokens: None,
+            output_tokens: None,
+            orderbooks: None,
+            page: None,
+            page_size: None,
+        }
+    }
+
+    #[test]
+    fn test_orders_query_filters() {
+        let mut query = query();
+        let filters = query.filters().unwrap();
+        assert!(filters.owners.is_empty());
+        assert!(filters.active.is_none());
+        assert!(filters.tokens.is_none());
+        assert!(filters.orderbook_addresses.is_none());
+
+        query.owners = Some(vec![
+            "0x1111111111111111111111111111111111111111".to_string()
+        ]);
+        query.active = Some(true);
+        query.output_tokens = Some(vec![
+            "0x2222222222222222222222222222222222222222".to_string()
+        ]);
+        query.orderbooks = Some(vec![
+            "0x3333333333333333333333333333333333333333".to_string()
+        ]);
+        let filters = query.filters().unwrap();
+        assert_eq!(filters.owners, vec![Address::repeat_byte(0x11)]);
+        assert_eq!(filters.active, Some(true));
+        let tokens = filters.tokens.unwrap();
+        assert!(tokens.inputs.is_none());
+        assert_eq!(tokens.outputs, Some(vec![Address::repeat_byte(0x22)]));
+        assert_eq!(
+            filters.orderbook_addresses,
+            Some(vec![Address::repeat_byte(0x33)])
+        );
+    }
+
+    #[test]
+    fn test_orders_query_invalid_token() {
+        let mut query = query();
+        query.input_tokens = Some(vec!["0x1234".to_string()]);
+        assert!(matches!(query.filters(), Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_orders_list_response_serialization() {
+        let response = OrdersListResponse {
+            orders: vec![],
+            total_count: 3,
+            page: 2,
+            page_size: 1,
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"orders\":[]"));
+        assert!(json.contains("\"totalCount\":3"));
+        assert!(json.contains("\"page\":2"));
+        assert!(json.contains("\"pageSize\":1"));
+    }
+
+    #[test]
+    fn test_quote_response_serialization() {
+        let response = QuoteResponse {
+            pair: "WETH/USDC".to_string(),
+            input_index: 0,
+            output_index: 1,
+            block_number: 100,
+            success: false,
+            max_output: None,
+            max_input: None,
+            ratio: None,
+            inverse_ratio: None,
+            error: Some("reverted".to_string()),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"inputIndex\":0"));
+        assert!(json.contains("\"outputIndex\":1"));
+        assert!(json.contains("\"maxOutput\":null"));
+        assert!(json.contains("\"inverseRatio\":null"));
+        assert!(json.contains("\"error\":\"reverted\""));
+    }
+}
@@ -1,20 +1,29 @@
 use crate::error::{ApiError, ApiErrorResponse};
-use rain_orderbook_common::raindex_client::take_orders::TakeOrdersRequest;
-use rain_orderbook_common::raindex_client::RaindexClient;
+use crate::profiles::ProfileRegistry;
+use crate::routes::with_client;
+use rain_orderbook_common::raindex_client::take_orders::{
+    RoutedTakeOrdersResult, TakeOrdersRequest,
+};
+use rain_orderbook_common::raindex_client::{RaindexClient, RaindexError};
 use rain_orderbook_common::take_orders::TakeOrdersMode;
 use rocket::serde::json::Json;
-use rocket::{post, Route};
+use rocket::{post, Route, State};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BuyRequest {
-    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses
+    /// Name of a settings profile configured on the server. Required unless yamlContent is given.
+    #[serde(default)]
+    #[schema(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[serde(default)]
     #[schema(
         example = "networks:\n  base:\n    rpc: https://mainnet.base.org\n    chain-id: 8453\nsubgraphs:\n  base: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-base/0.9/gn\norderbooks:\n  base:\n    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7\n    network: base\n    subgraph: base"
     )]
-    pub yaml_content: String,
+    pub yaml_content: Option<String>,
     /// Address that will execute the transaction
     #[schema(example = "0x1111111111111111111111111111111111111111")]
     pub taker: String,
@@ -37,16 +46,29 @@ pub struct BuyRequest {
     #[serde(default)]
     #[schema(example = false)]
     pub exact: bool,
+    /// Maximum number of hops through intermediate tokens when there is no direct liquidity. Defaults to 3.
+    #[serde(default)]
+    #[schema(example = 3)]
+    pub max_hops: Option<u32>,
+    /// Per-hop slippage tolerance (decimal fraction) for routed trades. Defaults to 0.005.
+    #[serde(default)]
+    #[schema(example = "0.005")]
+    pub hop_slippage: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SellRequest {
-    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses
+    /// Name of a settings profile configured on the server. Required unless yamlContent is given.
+    #[serde(default)]
+    #[schema(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[serde(default)]
     #[schema(
         example = "networks:\n  base:\n    rpc: https://mainnet.base.org\n    chain-id: 8453\nsubgraphs:\n  base: https://api.goldsky.com/api/public/project_clv14x04y9kzi01saerx7bxpg/subgraphs/ob4-base/0.9/gn\norderbooks:\n  base:\n    address: 0xd2938e7c9fe3597f78832ce780feb61945c377d7\n    network: base\n    subgraph: base"
     )]
-    pub yaml_content: String,
+    pub yaml_content: Option<String>,
     /// Address that will execute the transaction
     #[schema(example = "0x1111111111111111111111111111111111111111")]
     pub taker: String,
@@ -69,6 +91,14 @@ pub struct SellRequest {
     #[serde(default)]
     #[schema(example = false)]
     pub exact: bool,
+    /// Maximum number of hops through intermediate tokens when there is no direct liquidity. Defaults to 3.
+    #[serde(default)]
+    #[schema(example = 3)]
+    pub max_hops: Option<u32>,
+    /// Per-hop slippage tolerance (decimal fraction) for routed trades. Defaults to 0.005.
+    #[serde(default)]
+    #[schema(example = "0.005")]
+    pub hop_slippage: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -129,6 +159,57 @@ pub struct TakeOrdersReadyResponse {
     pub max_sell_cap: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutedHopResponse {
+    /// Address of the orderbook contract to call for this hop
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+    /// Token given in this hop
+    #[schema(example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub token_in: String,
+    /// Token received in this hop
+    #[schema(example = "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c")]
+    pub token_out: String,
+    /// ABI-encoded calldata for the takeOrders4 function
+    #[schema(example = "0x...")]
+    pub calldata: String,
+    /// Blended price of this hop (tokenIn per 1 tokenOut)
+    #[schema(example = "95000")]
+    pub effective_price: String,
+    /// Expected amount of this hop's tokenIn to spend
+    #[schema(example = "950")]
+    pub expected_sell: String,
+    /// Expected amount of this hop's tokenOut to receive
+    #[schema(example = "0.01")]
+    pub expected_buy: String,
+    /// Maximum price ratio encoded for this hop (slippage bound)
+    #[schema(example = "95475")]
+    pub max_ratio: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutedTakeOrdersResponse {
+    /// Tokens traversed from tokenIn to tokenOut
+    #[schema(example = json!(["0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c", "0x4200000000000000000000000000000000000006"]))]
+    pub path: Vec<String>,
+    /// takeOrders4 calls to execute in order
+    pub hops: Vec<RoutedHopResponse>,
+    /// multicall calldata bundling every hop, present when all hops use the same orderbook
+    #[schema(example = "0xac9650d8...")]
+    pub multicall: Option<String>,
+    /// Blended effective price across the whole route (tokenIn per 1 tokenOut)
+    #[schema(example = "0.00047")]
+    pub effective_price: String,
+    /// Expected amount of tokenIn to spend based on current quotes
+    #[schema(example = "470")]
+    pub expected_sell: String,
+    /// Expected amount of tokenOut to receive based on current quotes
+    #[schema(example = "1000000")]
+    pub expected_buy: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase", tag = "status", content = "data")]
 pub enum TakeOrdersApiResponse {
@@ -136,15 +217,71 @@ pub enum TakeOrdersApiResponse {
     NeedsApproval(ApprovalApiResponse),
     #[schema(title = "Ready")]
     Ready(TakeOrdersReadyResponse),
+    #[schema(title = "Routed")]
+    Routed(RoutedTakeOrdersResponse),
+}
+
+struct RoutingOptions {
+    max_hops: Option<u32>,
+    hop_slippage: Option<String>,
+}
+
+fn routed_response(result: RoutedTakeOrdersResult) -> Result<TakeOrdersApiResponse, ApiError> {
+    if let Some(approval) = result.approvals.first() {
+        return Ok(TakeOrdersApiResponse::NeedsApproval(ApprovalApiResponse {
+            token: approval.token.to_string(),
+            spender: approval.spender.to_string(),
+            amount: approval.amount.format().map_err(RaindexError::Float)?,
+            formatted_amount: approval.formatted_amount.clone(),
+            calldata: approval.calldata.to_string(),
+        }));
+    }
+
+    let hops = result
+        .hops
+        .iter()
+        .map(|hop| {
+            Ok(RoutedHopResponse {
+                orderbook: hop.orderbook.to_string(),
+                token_in: hop.sell_token.to_string(),
+                token_out: hop.buy_token.to_string(),
+                calldata: hop.calldata.to_string(),
+                effective_price: hop.effective_price.format().map_err(RaindexError::Float)?,
+                expected_sell: hop.expected_sell.format().map_err(RaindexError::Float)?,
+                expected_buy: hop.expected_buy.format().map_err(RaindexError::Float)?,
+                max_ratio: hop.max_io_ratio.format().map_err(RaindexError::Float)?,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(TakeOrdersApiResponse::Routed(RoutedTakeOrdersResponse {
+        path: result.path.iter().map(|token| token.to_string()).collect(),
+        hops,
+        multicall: result.multicall.map(|calldata| calldata.to_string()),
+        effective_price: result
+            .effective_price
+            .format()
+            .map_err(RaindexError::Float)?,
+        expected_sell: result.expected_sell.format().map_err(RaindexError::Float)?,
+        expected_buy: result.expected_buy.format().map_err(RaindexError::Float)?,
+    }))
 }
 
 async fn execute_take_orders(
-    yaml_content: String,
+    client: RaindexClient,
     request: TakeOrdersRequest,
+    routing: RoutingOptions,
 ) -> Result<TakeOrdersApiResponse, ApiError> {
-    let client = RaindexClient::new(vec![yaml_content], None, None).await?;
-
-    let result = client.get_take_orders_calldata(request).await?;
+    // Without direct liquidity, fall back to routing through intermediate tokens.
+    let result = match client.get_take_orders_calldata(request.clone()).await {
+        Err(RaindexError::NoLiquidity) => {
+            let routed = client
+                .get_routed_take_orders_calldata(request, routing.max_hops, routing.hop_slippage)
+                .await?;
+            return routed_response(routed);
+        }
+        result => result?,
+    };
 
     if let Some(approval_info) = result.approval_info() {
         let amount = approval_info.amount().format().map_err(|e| {
@@ -210,7 +347,7 @@ async fn execute_take_orders(
     tag = "Take Orders",
     request_body = BuyRequest,
     responses(
-        (status = 200, description = "Successfully generated buy calldata. Returns either approval info if token approval is needed, or ready calldata if approval is sufficient.", body = TakeOrdersApiResponse,
+        (status = 200, description = "Successfully generated buy calldata. Returns either approval info if token approval is needed, ready calldata if approval is sufficient, or routed calldata when only a multi-hop route exists.", body = TakeOrdersApiResponse,
             examples(
                 ("Ready" = (
                     summary = "Calldata ready to execute",
@@ -227,6 +364,30 @@ async fn execute_take_orders(
                         }
                     })
                 )),
+                ("Routed" = (
+                    summary = "Calldata routed through intermediate tokens",
+                    description = "Returned when there is no direct liquidity between tokenIn and tokenOut but a route of up to maxHops hops exists. Execute the hops in order, or submit the multicall calldata when present.",
+                    value = json!({
+                        "status": "routed",
+                        "data": {
+                            "path": ["0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c", "0x4200000000000000000000000000000000000006"],
+                            "hops": [{
+                                "orderbook": "0xd2938e7c9fe3597f78832ce780feb61945c377d7",
+                                "tokenIn": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                                "tokenOut": "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c",
+                                "calldata": "0x...",
+                                "effectivePrice": "95000",
+                                "expectedSell": "475",
+                                "expectedBuy": "0.005",
+                                "maxRatio": "95475"
+                            }],
+                            "multicall": "0xac9650d8...",
+                            "effectivePrice": "0.000475",
+                            "expectedSell": "475",
+                            "expectedBuy": "1000000"
+                        }
+                    })
+                )),
                 ("NeedsApproval" = (
                     summary = "Token approval required",
                     description = "Returned when the taker needs to approve token spending before executing. Submit the approval calldata first, then retry the request.",
@@ -249,14 +410,16 @@ async fn execute_take_orders(
     )
 )]
 #[post("/take-orders/buy", data = "<request>")]
-pub async fn buy(request: Json<BuyRequest>) -> Result<Json<TakeOrdersApiResponse>, ApiError> {
+pub async fn buy(
+    profiles: &State<ProfileRegistry>,
+    request: Json<BuyRequest>,
+) -> Result<Json<TakeOrdersApiResponse>, ApiError> {
     let mode = if request.exact {
         TakeOrdersMode::BuyExact
     } else {
         TakeOrdersMode::BuyUpTo
     };
 
-    let yaml_content = request.yaml_content.clone();
     let take_request = TakeOrdersRequest {
         taker: request.taker.clone(),
         chain_id: request.chain_id,
@@ -266,20 +429,18 @@ pub async fn buy(request: Json<BuyRequest>) -> Result<Json<TakeOrdersApiResponse
         amount: request.amount.clone(),
         price_cap: request.max_ratio.clone(),
     };
+    let routing = RoutingOptions {
+        max_hops: request.max_hops,
+        hop_slippage: request.hop_slippage.clone(),
+    };
 
-    // RaindexClient contains Rc<RefCell<...>> which is not Send, but Rocket requires
-    // Send futures. We use spawn_blocking with a dedicated runtime to run everything
-    // on a single thread where Rc<RefCell> is safe.
-    let response = tokio::task::spawn_blocking(move || {
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(|e| ApiError::Internal(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(execute_take_orders(yaml_content, take_request))
-    })
-    .await
-    .map_err(|e| ApiError::Internal(format!("Task execution failed: {}", e)))??;
+    let response = with_client(
+        profiles,
+        request.profile.clone(),
+        request.yaml_content.clone(),
+        move |client| execute_take_orders(client, take_request, routing),
+    )
+    .await?;
 
     Ok(Json(response))
 }
@@ -290,7 +451,7 @@ pub async fn buy(request: Json<BuyRequest>) -> Result<Json<TakeOrdersApiResponse
     tag = "Take Orders",
     request_body = SellRequest,
     responses(
-        (status = 200, description = "Successfully generated sell calldata. Returns either approval info if token approval is needed, or ready calldata if approval is sufficient.", body = TakeOrdersApiResponse,
+        (status = 200, description = "Successfully generated sell calldata. Returns either approval info if token approval is needed, ready calldata if approval is sufficient, or routed calldata when only a multi-hop route exists.", body = TakeOrdersApiResponse,
             examples(
                 ("Ready" = (
                     summary = "Calldata ready to execute",
@@ -307,6 +468,30 @@ pub async fn buy(request: Json<BuyRequest>) -> Result<Json<TakeOrdersApiResponse
                         }
                     })
                 )),
+                ("Routed" = (
+                    summary = "Calldata routed through intermediate tokens",
+                    description = "Returned when there is no direct liquidity between tokenIn and tokenOut but a route of up to maxHops hops exists. Execute the hops in order, or submit the multicall calldata when present.",
+                    value = json!({
+                        "status": "routed",
+                        "data": {
+                            "path": ["0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c", "0x4200000000000000000000000000000000000006"],
+                            "hops": [{
+                                "orderbook": "0xd2938e7c9fe3597f78832ce780feb61945c377d7",
+                                "tokenIn": "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+                                "tokenOut": "0x0555E30da8f98308EdB960aa94C0Db47230d2B9c",
+                                "calldata": "0x...",
+                                "effectivePrice": "95000",
+                                "expectedSell": "475",
+                                "expectedBuy": "0.005",
+                                "maxRatio": "95475"
+                            }],
+                            "multicall": "0xac9650d8...",
+                            "effectivePrice": "0.000475",
+                            "expectedSell": "475",
+                            "expectedBuy": "1000000"
+                        }
+                    })
+                )),
                 ("NeedsApproval" = (
                     summary = "Token approval required",
                     description = "Returned when the taker needs to approve token spending before executing. Submit the approval calldata first, then retry the request.",
@@ -329,14 +514,16 @@ pub async fn buy(request: Json<BuyRequest>) -> Result<Json<TakeOrdersApiResponse
     )
 )]
 #[post("/take-orders/sell", data = "<request>")]
-pub async fn sell(request: Json<SellRequest>) -> Result<Json<TakeOrdersApiResponse>, ApiError> {
+pub async fn sell(
+    profiles: &State<ProfileRegistry>,
+    request: Json<SellRequest>,
+) -> Result<Json<TakeOrdersApiResponse>, ApiError> {
     let mode = if request.exact {
         TakeOrdersMode::SpendExact
     } else {
         TakeOrdersMode::SpendUpTo
     };
 
-    let yaml_content = request.yaml_content.clone();
     let take_request = TakeOrdersRequest {
         taker: request.taker.clone(),
         chain_id: request.chain_id,
@@ -346,20 +533,18 @@ pub async fn sell(request: Json<SellRequest>) -> Result<Json<TakeOrdersApiRespon
         amount: request.amount.clone(),
         price_cap: request.max_ratio.clone(),
     };
+    let routing = RoutingOptions {
+        max_hops: request.max_hops,
+        hop_slippage: request.hop_slippage.clone(),
+    };
 
-    // RaindexClient contains Rc<RefCell<...>> which is not Send, but Rocket requires
-    // Send futures. We use spawn_blocking with a dedicated runtime to run everything
-    // on a single thread where Rc<RefCell> is safe.
-    let response = tokio::task::spawn_blocking(move || {
-        let rt = tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .map_err(|e| ApiError::Internal(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(execute_take_orders(yaml_content, take_request))
-    })
-    .await
-    .map_err(|e| ApiError::Internal(format!("Task execution failed: {}", e)))??;
+    let response = with_client(
+        profiles,
+        request.profile.clone(),
+        request.yaml_content.clone(),
+        move |client| execute_take_orders(client, take_request, routing),
+    )
+    .await?;
 
     Ok(Json(response))
 }
@@ -386,7 +571,8 @@ mod tests {
 
         let request: BuyRequest = serde_json::from_str(json).unwrap();
 
-        assert_eq!(request.yaml_content, "version: 1");
+        assert_eq!(request.yaml_content.as_deref(), Some("version: 1"));
+        assert_eq!(request.profile, None);
         assert_eq!(request.taker, "0x1111111111111111111111111111111111111111");
         assert_eq!(request.chain_id, 1);
         assert_eq!(
@@ -434,7 +620,8 @@ mod tests {
 
         let request: SellRequest = serde_json::from_str(json).unwrap();
 
-        assert_eq!(request.yaml_content, "version: 1");
+        assert_eq!(request.yaml_content.as_deref(), Some("version: 1"));
+        assert_eq!(request.profile, None);
         assert_eq!(request.taker, "0x1111111111111111111111111111111111111111");
         assert_eq!(request.chain_id, 1);
         assert_eq!(
@@ -511,4 +698,77 @@ mod tests {
         assert!(json.contains("\"formattedAmount\":"));
         assert!(json.contains("\"calldata\":"));
     }
+
+    #[test]
+    fn test_routed_response_serialization() {
+        let response = TakeOrdersApiResponse::Routed(RoutedTakeOrdersResponse {
+            path: vec![
+                "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                "0xcccccccccccccccccccccccccccccccccccccccc".to_string(),
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
+            ],
+            hops: vec![RoutedHopResponse {
+                orderbook: "0x1234567890123456789012345678901234567890".to_string(),
+                token_in: "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_string(),
+                token_out: "0xcccccccccccccccccccccccccccccccccccccccc".to_string(),
+                calldata: "0xabcdef".to_string(),
+                effective_price: "2".to_string(),
+                expected_sell: "20".to_string(),
+                expected_buy: "10".to_string(),
+                max_ratio: "2.01".to_string(),
+            }],
+            multicall: None,
+            effective_price: "4".to_string(),
+            expected_sell: "20".to_string(),
+            expected_buy: "5".to_string(),
+        });
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"status\":\"routed\""));
+        assert!(json.contains("\"path\":"));
+        assert!(json.contains("\"hops\":"));
+        assert!(json.contains("\"tokenIn\":"));
+        assert!(json.contains("\"maxRatio\":"));
+        assert!(json.contains("\"multicall\":null"));
+        assert!(json.contains("\"expectedBuy\":"));
+    }
+
+    #[test]
+    fn test_buy_request_with_profile() {
+        let json = r#"{
+            "profile": "base",
+            "taker": "0x1111111111111111111111111111111111111111",
+            "chainId": 8453,
+            "tokenIn": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "tokenOut": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+            "amount": "100",
+            "maxRatio": "2.5"
+        }"#;
+
+        let request: BuyRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.profile.as_deref(), Some("base"));
+        assert_eq!(request.yaml_content, None);
+    }
+
+    #[test]
+    fn test_buy_request_routing_options() {
+        let json = r#"{
+            "yamlContent": "version: 1",
+            "taker": "0x1111111111111111111111111111111111111111",
+            "chainId": 1,
+            "tokenIn": "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "tokenOut": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+            "amount": "100",
+            "maxRatio": "2.5",
+            "maxHops": 2,
+            "hopSlippage": "0.01"
+        }"#;
+
+        let request: BuyRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.max_hops, Some(2));
+        assert_eq!(request.hop_slippage.as_deref(), Some("0.01"));
+    }
 }
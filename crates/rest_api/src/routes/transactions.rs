@@ -0,0 +1,121 @@
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::profiles::ProfileRegistry;
+use crate::routes::{parse_address, parse_hash, with_client};
+use rain_orderbook_common::raindex_client::transactions::RaindexTransaction;
+use rocket::serde::json::Json;
+use rocket::{get, FromForm, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionResponse {
+    /// Transaction hash
+    #[schema(example = "0xabcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890")]
+    pub id: String,
+    /// Address that sent the transaction
+    #[schema(example = "0x1111111111111111111111111111111111111111")]
+    pub from: String,
+    /// Block the transaction was included in
+    #[schema(example = "12345678")]
+    pub block_number: String,
+    /// Unix timestamp (seconds) of the block
+    #[schema(example = "1700000000")]
+    pub timestamp: String,
+}
+
+impl From<RaindexTransaction> for TransactionResponse {
+    fn from(tx: RaindexTransaction) -> Self {
+        Self {
+            id: tx.id().to_string(),
+            from: tx.from().to_string(),
+            block_number: tx.block_number().to_string(),
+            timestamp: tx.timestamp().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct TransactionQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Chain ID the transaction was sent on
+    #[field(name = "chainId")]
+    #[param(example = 8453)]
+    pub chain_id: u32,
+    /// Orderbook contract the transaction interacted with
+    #[param(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx/{hash}",
+    tag = "Transactions",
+    params(
+        ("hash" = String, Path, description = "Transaction hash"),
+        TransactionQuery
+    ),
+    responses(
+        (status = 200, description = "Transaction indexed by the orderbook", body = TransactionResponse),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Transaction not indexed or configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/tx/<hash>?<query..>")]
+pub async fn get_transaction(
+    profiles: &State<ProfileRegistry>,
+    hash: String,
+    query: TransactionQuery,
+) -> Result<Json<TransactionResponse>, ApiError> {
+    let tx_hash = parse_hash("transaction hash", &hash)?;
+    let orderbook = parse_address("orderbook", &query.orderbook)?;
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            // a read endpoint reports what is indexed right now instead of polling for it
+            let tx = client
+                .get_transaction(query.chain_id, orderbook, tx_hash, Some(1), None)
+                .await?;
+            Ok(TransactionResponse::from(tx))
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_transaction]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_response_serialization() {
+        let response = TransactionResponse {
+            id: "0xabcdef".to_string(),
+            from: "0x1111111111111111111111111111111111111111".to_string(),
+            block_number: "100".to_string(),
+            timestamp: "1700000000".to_string(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"id\":\"0xabcdef\""));
+        assert!(json.contains("\"from\":"));
+        assert!(json.contains("\"blockNumber\":\"100\""));
+        assert!(json.contains("\"timestamp\":\"1700000000\""));
+    }
+}
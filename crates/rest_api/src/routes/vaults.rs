@@ -0,0 +1,453 @@
+use crate::error::{ApiError, ApiErrorResponse};
+use crate::profiles::ProfileRegistry;
+use crate::routes::transactions::TransactionResponse;
+use crate::routes::{parse_address, parse_addresses, with_client};
+use alloy::primitives::Bytes;
+use rain_orderbook_common::local_db::OrderbookIdentifier;
+use rain_orderbook_common::raindex_client::vaults::{
+    GetVaultsFilters, RaindexVault, RaindexVaultBalanceChange, RaindexVaultBalanceChangeType,
+    RaindexVaultToken, RaindexVaultType, VaultBalanceChangeFilter,
+};
+use rain_orderbook_common::raindex_client::ChainIds;
+use rocket::serde::json::Json;
+use rocket::{get, FromForm, Route, State};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenResponse {
+    /// Token contract address
+    #[schema(example = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913")]
+    pub address: String,
+    /// Token name, when known
+    #[schema(example = "USD Coin")]
+    pub name: Option<String>,
+    /// Token symbol, when known
+    #[schema(example = "USDC")]
+    pub symbol: Option<String>,
+    /// Token decimals
+    #[schema(example = 6)]
+    pub decimals: u8,
+}
+
+impl From<RaindexVaultToken> for TokenResponse {
+    fn from(token: RaindexVaultToken) -> Self {
+        Self {
+            address: token.address().to_string(),
+            name: token.name(),
+            symbol: token.symbol(),
+            decimals: token.decimals(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultResponse {
+    /// Chain ID of the vault's orderbook
+    #[schema(example = 8453)]
+    pub chain_id: u32,
+    /// Vault identifier, used by the balance changes endpoint
+    #[schema(example = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")]
+    pub id: String,
+    /// Owner-chosen vault id
+    #[schema(example = "1")]
+    pub vault_id: String,
+    /// Vault owner address
+    #[schema(example = "0x1111111111111111111111111111111111111111")]
+    pub owner: String,
+    /// Orderbook contract holding the vault
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+    /// Vault token
+    pub token: TokenResponse,
+    /// Human-readable vault balance
+    #[schema(example = "1000")]
+    pub balance: String,
+    /// How the vault is used by an order (input, output or inputOutput), present for order vaults
+    #[schema(example = "input")]
+    pub vault_type: Option<String>,
+}
+
+fn vault_type_name(vault_type: RaindexVaultType) -> String {
+    match vault_type {
+        RaindexVaultType::Input => "input",
+        RaindexVaultType::Output => "output",
+        RaindexVaultType::InputOutput => "inputOutput",
+    }
+    .to_string()
+}
+
+impl From<RaindexVault> for VaultResponse {
+    fn from(vault: RaindexVault) -> Self {
+        Self {
+            chain_id: vault.chain_id(),
+            id: vault.id().to_string(),
+            vault_id: vault.vault_id().to_string(),
+            owner: vault.owner().to_string(),
+            orderbook: vault.orderbook().to_string(),
+            token: vault.token().into(),
+            balance: vault.formatted_balance(),
+            vault_type: vault.vault_type().map(vault_type_name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultsListResponse {
+    /// Vaults on the requested page
+    pub vaults: Vec<VaultResponse>,
+    /// Page number of this response
+    #[schema(example = 1)]
+    pub page: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChangeResponse {
+    /// Kind of balance change (deposit, withdrawal, takeOrder, clear, clearBounty or unknown)
+    #[serde(rename = "type")]
+    #[schema(example = "deposit")]
+    pub change_type: String,
+    /// Owner-chosen vault id
+    #[schema(example = "1")]
+    pub vault_id: String,
+    /// Vault token
+    pub token: TokenResponse,
+    /// Human-readable signed amount the balance changed by
+    #[schema(example = "100")]
+    pub amount: String,
+    /// Human-readable balance before the change
+    #[schema(example = "900")]
+    pub old_balance: String,
+    /// Human-readable balance after the change
+    #[schema(example = "1000")]
+    pub new_balance: String,
+    /// Unix timestamp (seconds) of the change
+    #[schema(example = "1700000000")]
+    pub timestamp: String,
+    /// Transaction that caused the change
+    pub transaction: TransactionResponse,
+    /// Orderbook contract holding the vault
+    #[schema(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+}
+
+fn change_type_name(change_type: RaindexVaultBalanceChangeType) -> String {
+    match change_type {
+        RaindexVaultBalanceChangeType::Deposit => "deposit",
+        RaindexVaultBalanceChangeType::Withdrawal => "withdrawal",
+        RaindexVaultBalanceChangeType::TakeOrder => "takeOrder",
+        RaindexVaultBalanceChangeType::Clear => "clear",
+        RaindexVaultBalanceChangeType::ClearBounty => "clearBounty",
+        RaindexVaultBalanceChangeType::Unknown => "unknown",
+    }
+    .to_string()
+}
+
+impl From<RaindexVaultBalanceChange> for BalanceChangeResponse {
+    fn from(change: RaindexVaultBalanceChange) -> Self {
+        Self {
+            change_type: change_type_name(change.r#type()),
+            vault_id: change.vault_id().to_string(),
+            token: change.token().into(),
+            amount: change.formatted_amount(),
+            old_balance: change.formatted_old_balance(),
+            new_balance: change.formatted_new_balance(),
+            timestamp: change.timestamp().to_string(),
+            transaction: change.transaction().into(),
+            orderbook: change.orderbook().to_string(),
+        }
+    }
+}
+
+fn parse_change_filter(value: &str) -> Result<VaultBalanceChangeFilter, ApiError> {
+    match value {
+        "deposit" => Ok(VaultBalanceChangeFilter::Deposit),
+        "withdrawal" => Ok(VaultBalanceChangeFilter::Withdrawal),
+        "takeOrder" => Ok(VaultBalanceChangeFilter::TakeOrder),
+        "clear" => Ok(VaultBalanceChangeFilter::Clear),
+        "clearBounty" => Ok(VaultBalanceChangeFilter::ClearBounty),
+        _ => Err(ApiError::BadRequest(format!(
+            "Invalid balance change type {}, expected one of deposit, withdrawal, takeOrder, clear, clearBounty",
+            value
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct VaultsQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Only query these chain IDs (all configured networks when omitted)
+    #[field(name = "chainIds")]
+    pub chain_ids: Option<Vec<u32>>,
+    /// Only return vaults owned by these addresses
+    pub owners: Option<Vec<String>>,
+    /// Only return vaults of these tokens
+    pub tokens: Option<Vec<String>>,
+    /// Only return vaults held by these orderbook contracts
+    pub orderbooks: Option<Vec<String>>,
+    /// Leave out vaults with a zero balance
+    #[field(name = "hideZeroBalance")]
+    pub hide_zero_balance: Option<bool>,
+    /// Only return vaults used by active orders
+    #[field(name = "onlyActiveOrders")]
+    pub only_active_orders: Option<bool>,
+    /// Page number (defaults to 1)
+    #[param(example = 1)]
+    pub page: Option<u16>,
+}
+
+impl VaultsQuery {
+    fn filters(&self) -> Result<GetVaultsFilters, ApiError> {
+        Ok(GetVaultsFilters {
+            owners: parse_addresses("owner", self.owners.as_deref())?.unwrap_or_default(),
+            hide_zero_balance: self.hide_zero_balance.unwrap_or(false),
+            tokens: parse_addresses("token", self.tokens.as_deref())?,
+            orderbook_addresses: parse_addresses("orderbook", self.orderbooks.as_deref())?,
+            only_active_orders: self.only_active_orders.unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Debug, Clone, FromForm, IntoParams)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct BalanceChangesQuery {
+    /// Name of a settings profile configured on the server
+    #[param(example = "base")]
+    pub profile: Option<String>,
+    /// YAML configuration containing network RPC endpoints, subgraph URLs, and orderbook addresses. Overrides the profile.
+    #[field(name = "yamlContent")]
+    pub yaml_content: Option<String>,
+    /// Chain ID of the vault's orderbook
+    #[field(name = "chainId")]
+    #[param(example = 8453)]
+    pub chain_id: u32,
+    /// Orderbook contract holding the vault
+    #[param(example = "0xd2938e7c9fe3597f78832ce780feb61945c377d7")]
+    pub orderbook: String,
+    /// Only return these kinds of balance changes (deposit, withdrawal, takeOrder, clear, clearBounty)
+    pub types: Option<Vec<String>>,
+    /// Page number (defaults to 1)
+    #[param(example = 1)]
+    pub page: Option<u16>,
+}
+
+pub(crate) fn chain_ids(ids: Option<Vec<u32>>) -> Option<ChainIds> {
+    ids.filter(|ids| !ids.is_empty()).map(ChainIds)
+}
+
+#[utoipa::path(
+    get,
+    path = "/vaults",
+    tag = "Vaults",
+    params(VaultsQuery),
+    responses(
+        (status = 200, description = "Vaults matching the filters", body = VaultsListResponse),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/vaults?<query..>")]
+pub async fn get_vaults(
+    profiles: &State<ProfileRegistry>,
+    query: VaultsQuery,
+) -> Result<Json<VaultsListResponse>, ApiError> {
+    let filters = query.filters()?;
+    let page = query.page.unwrap_or(1).max(1);
+    let chain_ids = chain_ids(query.chain_ids);
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let vaults = client
+                .get_vaults(chain_ids, Some(filters), Some(page))
+                .await?;
+            Ok(VaultsListResponse {
+                vaults: vaults
+                    .items()
+                    .into_iter()
+                    .map(VaultResponse::from)
+                    .collect(),
+                page,
+            })
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/vaults/{id}/balance-changes",
+    tag = "Vaults",
+    params(
+        ("id" = String, Path, description = "Vault identifier as returned in the id field of GET /vaults"),
+        BalanceChangesQuery
+    ),
+    responses(
+        (status = 200, description = "Balance changes of the vault, newest first", body = Vec<BalanceChangeResponse>),
+        (status = 400, description = "Invalid request parameters", body = ApiErrorResponse),
+        (status = 404, description = "Vault or configuration not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[get("/vaults/<id>/balance-changes?<query..>")]
+pub async fn get_balance_changes(
+    profiles: &State<ProfileRegistry>,
+    id: String,
+    query: BalanceChangesQuery,
+) -> Result<Json<Vec<BalanceChangeResponse>>, ApiError> {
+    let vault_id = Bytes::from_str(&id)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid vault id {}: {}", id, e)))?;
+    let orderbook = parse_address("orderbook", &query.orderbook)?;
+    let filter_types = query
+        .types
+        .as_deref()
+        .filter(|types| !types.is_empty())
+        .map(|types| {
+            types
+                .iter()
+                .map(|value| parse_change_filter(value))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let response = with_client(
+        profiles,
+        query.profile.clone(),
+        query.yaml_content.clone(),
+        move |client| async move {
+            let vault = client
+                .get_vault(
+                    &OrderbookIdentifier::new(query.chain_id, orderbook),
+                    vault_id,
+                )
+                .await?;
+            let changes = vault.get_balance_changes(query.page, filter_types).await?;
+            Ok(changes
+                .into_iter()
+                .map(BalanceChangeResponse::from)
+                .collect::<Vec<_>>())
+        },
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+pub fn routes() -> Vec<Route> {
+    rocket::routes![get_vaults, get_balance_changes]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    fn query() -> VaultsQuery {
+        VaultsQuery {
+            profile: None,
+            yaml_content: Some("version: 1".to_string()),
+            chain_ids: None,
+            owners: None,
+            tokens: None,
+            orderbooks: None,
+            hide_zero_balance: None,
+            only_active_orders: None,
+            page: None,
+        }
+    }
+
+    #[test]
+    fn test_vaults_query_filters() {
+        let mut query = query();
+        let filters = query.filters().unwrap();
+        assert!(filters.owners.is_empty());
+        assert!(filters.tokens.is_none());
+        assert!(filters.orderbook_addresses.is_none());
+        assert!(!filters.hide_zero_balance);
+        assert!(!filters.only_active_orders);
+
+        query.owners = Some(vec![
+            "0x1111111111111111111111111111111111111111".to_string()
+        ]);
+        query.tokens = Some(vec![
+            "0x2222222222222222222222222222222222222222".to_string()
+        ]);
+        query.hide_zero_balance = Some(true);
+        let filters = query.filters().unwrap();
+        assert_eq!(filters.owners, vec![Address::repeat_byte(0x11)]);
+        assert_eq!(filters.tokens, Some(vec![Address::repeat_byte(0x22)]));
+        assert!(filters.hide_zero_balance);
+    }
+
+    #[test]
+    fn test_vaults_query_invalid_owner() {
+        let mut query = query();
+        query.owners = Some(vec!["invalid".to_string()]);
+        assert!(matches!(query.filters(), Err(ApiError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_parse_change_filter() {
+        assert_eq!(
+            parse_change_filter("takeOrder").unwrap(),
+            VaultBalanceChangeFilter::TakeOrder
+        );
+        assert_eq!(
+            parse_change_filter("clearBounty").unwrap(),
+            VaultBalanceChangeFilter::ClearBounty
+        );
+        assert!(matches!(
+            parse_change_filter("swap"),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_balance_change_response_serialization() {
+        let response = BalanceChangeResponse {
+            change_type: "deposit".to_string(),
+            vault_id: "1".to_string(),
+            token: TokenResponse {
+                address: "0x2222222222222222222222222222222222222222".to_string(),
+                name: None,
+                symbol: Some("TKN".to_string()),
+                decimals: 18,
+            },
+            amount: "100".to_string(),
+            old_balance: "0".to_string(),
+            new_balance: "100".to_string(),
+            timestamp: "1700000000".to_string(),
+            transaction: TransactionResponse {
+                id: "0xabcdef".to_string(),
+                from: "0x1111111111111111111111111111111111111111".to_string(),
+                block_number: "100".to_string(),
+                timestamp: "1700000000".to_string(),
+            },
+            orderbook: "0x3333333333333333333333333333333333333333".to_string(),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+
+        assert!(json.contains("\"type\":\"deposit\""));
+        assert!(json.contains("\"vaultId\":\"1\""));
+        assert!(json.contains("\"oldBalance\":\"0\""));
+        assert!(json.contains("\"newBalance\":\"100\""));
+        assert!(json.contains("\"blockNumber\":\"100\""));
+    }
+}
@@ -1187,6 +1187,7 @@ mod tests {
             rainlang: mock_rainlang(),
             runs: None,
             blocks: None,
+            invariants: vec![],
         };
         let order = OrderCfg {
             document: Arc::new(RwLock::new(StrictYaml::String("".to_string()))),
@@ -0,0 +1,363 @@
+use crate::yaml::{require_hash, require_string, FieldErrorKind, YamlError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strict_yaml_rust::{strict_yaml::Hash, StrictYaml};
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+pub const ALLOWED_INVARIANT_KEYS: [&str; 8] =
+    ["eq", "gt", "gte", "in", "lt", "lte", "monotonic", "value"];
+
+const COMPARISONS: [(&str, InvariantOpCfg); 5] = [
+    ("eq", InvariantOpCfg::Eq),
+    ("gt", InvariantOpCfg::Gt),
+    ("gte", InvariantOpCfg::Gte),
+    ("lt", InvariantOpCfg::Lt),
+    ("lte", InvariantOpCfg::Lte),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub enum InvariantOpCfg {
+    Eq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(InvariantOpCfg);
+
+impl InvariantOpCfg {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Gt => ">",
+            Self::Gte => ">=",
+            Self::Lt => "<",
+            Self::Lte => "<=",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub enum MonotonicDirectionCfg {
+    Increasing,
+    Decreasing,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(MonotonicDirectionCfg);
+
+impl fmt::Display for MonotonicDirectionCfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Increasing => write!(f, "increasing"),
+            Self::Decreasing => write!(f, "decreasing"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum InvariantCheckCfg {
+    /// Compares the value against a number literal or the value of a binding
+    Compare { op: InvariantOpCfg, bound: String },
+    /// The value must not move against `direction` when only the fuzzed
+    /// `binding` grows
+    Monotonic {
+        direction: MonotonicDirectionCfg,
+        binding: String,
+    },
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(InvariantCheckCfg);
+
+/// A property of a scenario that must hold on every fuzzed run. `value` is
+/// a stack column of the flattened eval results, the same paths chart
+/// metrics use (e.g. `0.1`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub struct InvariantCfg {
+    pub key: String,
+    pub value: String,
+    pub check: InvariantCheckCfg,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(InvariantCfg);
+
+impl fmt::Display for InvariantCfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.check {
+            InvariantCheckCfg::Compare { op, bound } => {
+                write!(f, "{} {} {}", self.value, op.symbol(), bound)
+            }
+            InvariantCheckCfg::Monotonic { direction, binding } => {
+                write!(f, "{} {} in {}", self.value, direction, binding)
+            }
+        }
+    }
+}
+
+impl InvariantCfg {
+    pub fn parse_from_yaml(
+        key: &str,
+        invariant_yaml: &StrictYaml,
+        scenario_key: &str,
+    ) -> Result<Self, YamlError> {
+        let location = format!("invariant '{key}' in scenario '{scenario_key}'");
+        let invariant_hash = require_hash(invariant_yaml, None, Some(location.clone()))?;
+
+        let value = require_string(invariant_yaml, Some("value"), Some(location.clone()))?;
+
+        let mut checks = COMPARISONS
+            .iter()
+            .filter(|(op_key, _)| has_key(invariant_hash, op_key))
+            .map(|(op_key, op)| {
+                let bound = require_string(invariant_yaml, Some(*op_key), Some(location.clone()))?;
+                Ok(InvariantCheckCfg::Compare { op: *op, bound })
+            })
+            .collect::<Result<Vec<_>, YamlError>>()?;
+
+        if has_key(invariant_hash, "monotonic") {
+            let direction =
+                match require_string(invariant_yaml, Some("monotonic"), Some(location.clone()))?
+                    .as_str()
+                {
+                    "increasing" => MonotonicDirectionCfg::Increasing,
+                    "decreasing" => MonotonicDirectionCfg::Decreasing,
+                    other => {
+                        return Err(YamlError::Field {
+                            kind: FieldErrorKind::InvalidValue {
+                                field: "monotonic".to_string(),
+                                reason: format!(
+                                    "'{other}' is not one of 'increasing' or 'decreasing'"
+                                ),
+                            },
+                            location,
+                        })
+                    }
+                };
+            let binding = require_string(invariant_yaml, Some("in"), Some(location.clone()))?;
+            checks.push(InvariantCheckCfg::Monotonic { direction, binding });
+        } else if has_key(invariant_hash, "in") {
+            return Err(YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "in".to_string(),
+                    reason: "'in' is only valid together with 'monotonic'".to_string(),
+                },
+                location,
+            });
+        }
+
+        if checks.len() != 1 {
+            return Err(YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "check".to_string(),
+                    reason: format!(
+                        "exactly one of {} or monotonic is required",
+                        COMPARISONS.map(|(op_key, _)| op_key).join(", ")
+                    ),
+                },
+                location,
+            });
+        }
+
+        Ok(InvariantCfg {
+            key: key.to_string(),
+            value,
+            check: checks.remove(0),
+        })
+    }
+
+    /// Parses the `invariants` map of a scenario, sorted by key
+    pub fn parse_all_from_scenario(
+        scenario_yaml: &StrictYaml,
+        scenario_key: &str,
+    ) -> Result<Vec<Self>, YamlError> {
+        let invariants_yaml = &scenario_yaml["invariants"];
+        if invariants_yaml.is_badvalue() {
+            return Ok(vec![]);
+        }
+
+        let location = format!("invariants in scenario '{scenario_key}'");
+        let mut invariants = require_hash(invariants_yaml, None, Some(location))?
+            .iter()
+            .map(|(key_yaml, invariant_yaml)| {
+                let key = key_yaml.as_str().unwrap_or_default();
+                Self::parse_from_yaml(key, invariant_yaml, scenario_key)
+            })
+            .collect::<Result<Vec<_>, YamlError>>()?;
+        invariants.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(invariants)
+    }
+}
+
+fn has_key(hash: &Hash, key: &str) -> bool {
+    hash.contains_key(&StrictYaml::String(key.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::yaml::tests::get_document;
+
+    fn parse(yaml: &str) -> Result<Vec<InvariantCfg>, YamlError> {
+        let document = get_document(yaml);
+        let document = document.read().unwrap();
+        InvariantCfg::parse_all_from_scenario(&document, "main")
+    }
+
+    #[test]
+    fn test_parse_invariants() {
+        let invariants = parse(
+            r#"
+invariants:
+    ratio-positive:
+        value: 0.1
+        gt: 0
+    output-within-max:
+        value: 0.0
+        lte: max-output
+    ratio-monotonic:
+        value: 0.1
+        monotonic: decreasing
+        in: amount
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            invariants,
+            vec![
+                InvariantCfg {
+                    key: "output-within-max".to_string(),
+                    value: "0.0".to_string(),
+                    check: InvariantCheckCfg::Compare {
+                        op: InvariantOpCfg::Lte,
+                        bound: "max-output".to_string(),
+                    },
+                },
+                InvariantCfg {
+                    key: "ratio-monotonic".to_string(),
+                    value: "0.1".to_string(),
+                    check: InvariantCheckCfg::Monotonic {
+                        direction: MonotonicDirectionCfg::Decreasing,
+                        binding: "amount".to_string(),
+                    },
+                },
+                InvariantCfg {
+                    key: "ratio-positive".to_string(),
+                    value: "0.1".to_string(),
+                    check: InvariantCheckCfg::Compare {
+                        op: InvariantOpCfg::Gt,
+                        bound: "0".to_string(),
+                    },
+                },
+            ]
+        );
+        assert_eq!(invariants[0].to_string(), "0.0 <= max-output");
+        assert_eq!(invariants[1].to_string(), "0.1 decreasing in amount");
+        assert_eq!(invariants[2].to_string(), "0.1 > 0");
+
+        assert_eq!(parse("bindings: {}").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_invariants_errors() {
+        let error = parse(
+            r#"
+invariants:
+    bad:
+        gt: 0
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::Missing("value".to_string()),
+                location: "invariant 'bad' in scenario 'main'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+invariants:
+    bad:
+        value: 0.1
+        gt: 0
+        lt: 10
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "check".to_string(),
+                    reason: "exactly one of eq, gt, gte, lt, lte or monotonic is required"
+                        .to_string(),
+                },
+                location: "invariant 'bad' in scenario 'main'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+invariants:
+    bad:
+        value: 0.1
+        monotonic: sideways
+        in: amount
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "monotonic".to_string(),
+                    reason: "'sideways' is not one of 'increasing' or 'decreasing'".to_string(),
+                },
+                location: "invariant 'bad' in scenario 'main'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+invariants:
+    bad:
+        value: 0.1
+        monotonic: increasing
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::Missing("in".to_string()),
+                location: "invariant 'bad' in scenario 'main'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+invariants:
+    - value: 0.1
+"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidType { .. },
+                ..
+            }
+        ));
+    }
+}
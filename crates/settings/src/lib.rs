@@ -3,6 +3,7 @@ pub mod blocks;
 pub mod chart;
 pub mod deployment;
 pub mod gui;
+pub mod invariant;
 pub mod local_db_manifest;
 pub mod local_db_remotes;
 pub mod local_db_sync;
@@ -22,6 +23,7 @@ pub mod subgraph;
 pub mod token;
 pub mod unit_test; // NOTE: this is used by unit_tests module in common crate
 pub mod utils;
+pub mod watcher;
 pub mod yaml;
 
 pub(crate) use chart::*;
@@ -1,14 +1,15 @@
 use crate::utils::{parse_positive_u32, parse_positive_u64, parse_url};
 use crate::yaml::{
-    optional_hash, require_hash, require_string, require_vec, FieldErrorKind, YamlError,
+    optional_hash, optional_string, optional_vec, require_hash, require_string, require_vec,
+    FieldErrorKind, YamlError,
 };
-use alloy::primitives::{Address, Bytes};
+use alloy::primitives::{Address, Bytes, B256};
 use std::collections::HashMap;
 use std::str::FromStr;
 use strict_yaml_rust::{strict_yaml::Hash, StrictYaml, StrictYamlEmitter};
 use url::Url;
 
-pub const MANIFEST_VERSION: u32 = 1;
+pub const MANIFEST_VERSION: u32 = 2;
 pub const DB_SCHEMA_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -16,6 +17,9 @@ pub struct LocalDbManifest {
     pub manifest_version: u32,
     pub db_schema_version: u32,
     pub networks: HashMap<String, ManifestNetwork>,
+    /// EIP-191 signature of [`LocalDbManifest::signing_payload`] by the
+    /// publisher of the manifest.
+    pub signature: Option<Bytes>,
 }
 
 impl Default for LocalDbManifest {
@@ -64,16 +68,66 @@ impl ManifestNetwork {
     }
 }
 
+/// Orderbook entry of a manifest: a base snapshot at `end_block` followed by
+/// deltas in block order, each starting where the previous one ended.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ManifestOrderbook {
     pub address: Address,
     pub dump_url: Url,
+    /// SHA-256 of the gzip-compressed dump served at `dump_url`.
+    pub dump_sha256: B256,
+    pub end_block: u64,
+    pub end_block_hash: Bytes,
+    pub end_block_time_ms: u64,
+    pub deltas: Vec<ManifestDelta>,
+}
+
+/// Dump holding the rows of blocks `(start_block, end_block]`, applied on top
+/// of a database synced to `start_block` with hash `start_block_hash`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestDelta {
+    pub dump_url: Url,
+    pub dump_sha256: B256,
+    pub start_block: u64,
+    pub start_block_hash: Bytes,
     pub end_block: u64,
     pub end_block_hash: Bytes,
     pub end_block_time_ms: u64,
 }
 
 impl ManifestOrderbook {
+    /// Last block covered by the base snapshot and its deltas.
+    pub fn head_block(&self) -> u64 {
+        self.deltas
+            .last()
+            .map_or(self.end_block, |delta| delta.end_block)
+    }
+
+    pub fn head_block_hash(&self) -> &Bytes {
+        self.deltas
+            .last()
+            .map_or(&self.end_block_hash, |delta| &delta.end_block_hash)
+    }
+
+    pub fn head_block_time_ms(&self) -> u64 {
+        self.deltas
+            .last()
+            .map_or(self.end_block_time_ms, |delta| delta.end_block_time_ms)
+    }
+
+    /// Deltas still to apply on a database synced to `block` with
+    /// `block_hash`, or `None` when that block is neither the end of the base
+    /// snapshot nor of a delta and the base snapshot has to be imported.
+    pub fn deltas_after(&self, block: u64, block_hash: &Bytes) -> Option<&[ManifestDelta]> {
+        if block == self.end_block && *block_hash == self.end_block_hash {
+            return Some(&self.deltas);
+        }
+        self.deltas
+            .iter()
+            .position(|delta| block == delta.end_block && *block_hash == delta.end_block_hash)
+            .map(|idx| &self.deltas[idx + 1..])
+    }
+
     fn to_yaml_hash(&self) -> StrictYaml {
         let mut hash = Hash::new();
         hash.insert(
@@ -84,6 +138,56 @@ impl ManifestOrderbook {
             StrictYaml::String("dump-url".to_string()),
             StrictYaml::String(self.dump_url.to_string()),
         );
+        hash.insert(
+            StrictYaml::String("dump-sha256".to_string()),
+            StrictYaml::String(self.dump_sha256.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("end-block".to_string()),
+            StrictYaml::String(self.end_block.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("end-block-hash".to_string()),
+            StrictYaml::String(self.end_block_hash.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("end-block-time-ms".to_string()),
+            StrictYaml::String(self.end_block_time_ms.to_string()),
+        );
+        if !self.deltas.is_empty() {
+            hash.insert(
+                StrictYaml::String("deltas".to_string()),
+                StrictYaml::Array(
+                    self.deltas
+                        .iter()
+                        .map(ManifestDelta::to_yaml_hash)
+                        .collect(),
+                ),
+            );
+        }
+        StrictYaml::Hash(hash)
+    }
+}
+
+impl ManifestDelta {
+    fn to_yaml_hash(&self) -> StrictYaml {
+        let mut hash = Hash::new();
+        hash.insert(
+            StrictYaml::String("dump-url".to_string()),
+            StrictYaml::String(self.dump_url.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("dump-sha256".to_string()),
+            StrictYaml::String(self.dump_sha256.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("start-block".to_string()),
+            StrictYaml::String(self.start_block.to_string()),
+        );
+        hash.insert(
+            StrictYaml::String("start-block-hash".to_string()),
+            StrictYaml::String(self.start_block_hash.to_string()),
+        );
         hash.insert(
             StrictYaml::String("end-block".to_string()),
             StrictYaml::String(self.end_block.to_string()),
@@ -113,6 +217,7 @@ impl LocalDbManifest {
             manifest_version: MANIFEST_VERSION,
             db_schema_version: DB_SCHEMA_VERSION,
             networks: HashMap::new(),
+            signature: None,
         }
     }
 
@@ -199,6 +304,13 @@ impl LocalDbManifest {
             StrictYaml::Hash(networks_hash),
         );
 
+        if let Some(signature) = &self.signature {
+            root.insert(
+                StrictYaml::String("signature".to_string()),
+                StrictYaml::String(signature.to_string()),
+            );
+        }
+
         StrictYaml::Hash(root)
     }
 
@@ -216,6 +328,17 @@ impl LocalDbManifest {
 
         Ok(out_str)
     }
+
+    /// Message the publisher signs: the manifest serialized without its
+    /// signature. Re-serializing a parsed manifest yields the same text, so
+    /// clients can rebuild it from what they fetched.
+    pub fn signing_payload(&self) -> Result<String, YamlError> {
+        LocalDbManifest {
+            signature: None,
+            ..self.clone()
+        }
+        .to_yaml_string()
+    }
 }
 
 pub fn current_manifest_version() -> u32 {
@@ -232,10 +355,23 @@ pub fn parse_manifest_doc(doc: &StrictYaml) -> Result<LocalDbManifest, YamlError
     let (manifest_version, db_schema_version) = parse_manifest_header(doc, location_root)?;
     let networks = parse_networks(doc, location_root)?;
 
+    let signature = optional_string(doc, "signature")
+        .map(|signature| {
+            Bytes::from_str(&signature).map_err(|e| YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "signature".to_string(),
+                    reason: e.to_string(),
+                },
+                location: location_root.to_string(),
+            })
+        })
+        .transpose()?;
+
     Ok(LocalDbManifest {
         manifest_version,
         db_schema_version,
         networks,
+        signature,
     })
 }
 
@@ -376,6 +512,7 @@ fn parse_single_orderbook(
 
     let dump_url_str = require_string(ob_yaml, Some("dump-url"), Some(location_ob.clone()))?;
     let dump_url = parse_url(&dump_url_str, "dump-url", location_ob.clone())?;
+    let dump_sha256 = parse_sha256(ob_yaml, &location_ob)?;
 
     let end_block = parse_positive_u64(
         &require_string(ob_yaml, Some("end-block"), Some(location_ob.clone()))?,
@@ -383,15 +520,7 @@ fn parse_single_orderbook(
         location_ob.clone(),
     )?;
 
-    let end_block_hash_str =
-        require_string(ob_yaml, Some("end-block-hash"), Some(location_ob.clone()))?;
-    let end_block_hash = Bytes::from_str(&end_block_hash_str).map_err(|e| YamlError::Field {
-        kind: FieldErrorKind::InvalidValue {
-            field: "end-block-hash".to_string(),
-            reason: e.to_string(),
-        },
-        location: location_ob.clone(),
-    })?;
+    let end_block_hash = parse_block_hash(ob_yaml, "end-block-hash", &location_ob)?;
 
     let end_block_time_ms = parse_positive_u64(
         &require_string(
@@ -403,15 +532,130 @@ fn parse_single_orderbook(
         location_ob.clone(),
     )?;
 
+    let mut deltas: Vec<ManifestDelta> = Vec::new();
+    for (delta_idx, delta_yaml) in optional_vec(ob_yaml, "deltas")
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        let location_delta = format!("{}.deltas[{}]", location_ob, delta_idx);
+        let delta = parse_single_delta(delta_yaml, &location_delta)?;
+
+        let (prev_block, prev_hash) = deltas.last().map_or((end_block, &end_block_hash), |prev| {
+            (prev.end_block, &prev.end_block_hash)
+        });
+        if delta.start_block != prev_block || delta.start_block_hash != *prev_hash {
+            return Err(YamlError::Field {
+                kind: FieldErrorKind::InvalidValue {
+                    field: "start-block".to_string(),
+                    reason: format!(
+                        "delta must start at the previous end block {} ({})",
+                        prev_block, prev_hash
+                    ),
+                },
+                location: location_delta,
+            });
+        }
+        deltas.push(delta);
+    }
+
     Ok(ManifestOrderbook {
         address,
         dump_url,
+        dump_sha256,
+        end_block,
+        end_block_hash,
+        end_block_time_ms,
+        deltas,
+    })
+}
+
+fn parse_single_delta(
+    delta_yaml: &StrictYaml,
+    location_delta: &str,
+) -> Result<ManifestDelta, YamlError> {
+    let dump_url_str = require_string(
+        delta_yaml,
+        Some("dump-url"),
+        Some(location_delta.to_string()),
+    )?;
+    let dump_url = parse_url(&dump_url_str, "dump-url", location_delta.to_string())?;
+    let dump_sha256 = parse_sha256(delta_yaml, location_delta)?;
+
+    let start_block = parse_positive_u64(
+        &require_string(
+            delta_yaml,
+            Some("start-block"),
+            Some(location_delta.to_string()),
+        )?,
+        "start-block",
+        location_delta.to_string(),
+    )?;
+    let start_block_hash = parse_block_hash(delta_yaml, "start-block-hash", location_delta)?;
+
+    let end_block = parse_positive_u64(
+        &require_string(
+            delta_yaml,
+            Some("end-block"),
+            Some(location_delta.to_string()),
+        )?,
+        "end-block",
+        location_delta.to_string(),
+    )?;
+    if end_block <= start_block {
+        return Err(YamlError::Field {
+            kind: FieldErrorKind::InvalidValue {
+                field: "end-block".to_string(),
+                reason: format!("must be greater than start-block {}", start_block),
+            },
+            location: location_delta.to_string(),
+        });
+    }
+    let end_block_hash = parse_block_hash(delta_yaml, "end-block-hash", location_delta)?;
+
+    let end_block_time_ms = parse_positive_u64(
+        &require_string(
+            delta_yaml,
+            Some("end-block-time-ms"),
+            Some(location_delta.to_string()),
+        )?,
+        "end-block-time-ms",
+        location_delta.to_string(),
+    )?;
+
+    Ok(ManifestDelta {
+        dump_url,
+        dump_sha256,
+        start_block,
+        start_block_hash,
         end_block,
         end_block_hash,
         end_block_time_ms,
     })
 }
 
+fn parse_block_hash(yaml: &StrictYaml, field: &str, location: &str) -> Result<Bytes, YamlError> {
+    let hash_str = require_string(yaml, Some(field), Some(location.to_string()))?;
+    Bytes::from_str(&hash_str).map_err(|e| YamlError::Field {
+        kind: FieldErrorKind::InvalidValue {
+            field: field.to_string(),
+            reason: e.to_string(),
+        },
+        location: location.to_string(),
+    })
+}
+
+fn parse_sha256(yaml: &StrictYaml, location: &str) -> Result<B256, YamlError> {
+    let sha256_str = require_string(yaml, Some("dump-sha256"), Some(location.to_string()))?;
+    B256::from_str(&sha256_str).map_err(|e| YamlError::Field {
+        kind: FieldErrorKind::InvalidValue {
+            field: "dump-sha256".to_string(),
+            reason: e.to_string(),
+        },
+        location: location.to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,9 +670,11 @@ mod tests {
         ManifestOrderbook {
             address: Address::from_str("0x00000000000000000000000000000000000000aa").unwrap(),
             dump_url: Url::parse("https://example.com/dump").unwrap(),
+            dump_sha256: B256::repeat_byte(0xaa),
             end_block: 42,
             end_block_hash: Bytes::from_str("0x0abc").unwrap(),
             end_block_time_ms: 1234,
+            deltas: vec![],
         }
     }
 
@@ -441,16 +687,20 @@ mod tests {
         let ob_main = ManifestOrderbook {
             address: Address::from_str("0x0000000000000000000000000000000000000001").unwrap(),
             dump_url: Url::parse("https://example.com/mainnet").unwrap(),
+            dump_sha256: B256::repeat_byte(0x01),
             end_block: 50,
             end_block_hash: Bytes::from_str("0x0def").unwrap(),
             end_block_time_ms: 2000,
+            deltas: vec![],
         };
         let ob_polygon = ManifestOrderbook {
             address: Address::from_str("0x0000000000000000000000000000000000000002").unwrap(),
             dump_url: Url::parse("https://example.com/polygon").unwrap(),
+            dump_sha256: B256::repeat_byte(0x02),
             end_block: 60,
             end_block_hash: Bytes::from_str("0x0fed").unwrap(),
             end_block_time_ms: 3000,
+            deltas: vec![],
         };
 
         manifest.push_orderbook("mainnet", ob_main.clone()).unwrap();
@@ -529,13 +779,13 @@ mod tests {
     fn test_header_helper_ok_and_incompatible() {
         // OK header
         let yaml_ok = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks: {}
 "#;
         let doc = load(yaml_ok);
         let (mv, sv) = parse_manifest_header(&doc, "manifest").expect("header parses");
-        assert_eq!(mv, 1);
+        assert_eq!(mv, 2);
         assert_eq!(sv, 2);
 
         // Incompatible manifest version
@@ -558,7 +808,7 @@ networks: {}
 
         // Incompatible db schema version
         let yaml_bad_schema = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 999
 networks: {}
 "#;
@@ -578,7 +828,7 @@ networks: {}
     #[test]
     fn test_networks_helper_empty_key_rejected() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   "":
@@ -606,6 +856,7 @@ networks:
         let ob_ok = r#"
 address: "0x0000000000000000000000000000000000000001"
 dump-url: "http://example.com"
+dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
 end-block: 1
 end-block-hash: "0x0abc"
 end-block-time-ms: 1
@@ -619,6 +870,7 @@ end-block-time-ms: 1
         let ob_bad = r#"
 address: "not-an-address"
 dump-url: "http://example.com"
+dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
 end-block: 1
 end-block-hash: "0x0abc"
 end-block-time-ms: 1
@@ -649,7 +901,7 @@ networks: {}
     #[test]
     fn test_missing_db_schema_version() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 networks: {}
 "#;
         let err = parse_manifest_doc(&load(yaml)).unwrap_err();
@@ -659,7 +911,7 @@ networks: {}
     #[test]
     fn test_missing_networks_defaults_to_empty() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 "#;
         let manifest = parse_manifest_doc(&load(yaml)).unwrap();
@@ -669,7 +921,7 @@ db-schema-version: 2
     #[test]
     fn test_manifest_doc_rejects_mismatched_db_schema_version() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 999
 networks: {}
 "#;
@@ -697,7 +949,7 @@ networks: {}
         assert!(matches!(err, YamlError::Field { .. }));
 
         let yaml_zero_schema = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 0
 networks: {}
 "#;
@@ -708,7 +960,7 @@ networks: {}
     #[test]
     fn test_network_missing_chain_id_and_invalid() {
         let yaml_missing = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet: {}
@@ -717,7 +969,7 @@ networks:
         assert!(matches!(err, YamlError::Field { .. }));
 
         let yaml_zero = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -731,7 +983,7 @@ networks:
     #[test]
     fn test_orderbooks_required_and_type() {
         let yaml_missing = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -741,7 +993,7 @@ networks:
         assert!(matches!(err, YamlError::Field { .. }));
 
         let yaml_non_list = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -756,7 +1008,7 @@ networks:
     fn test_orderbook_missing_fields() {
         // Full, valid baseline
         let good = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -764,6 +1016,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 1
         end-block-hash: "0x0abc"
         end-block-time-ms: 1
@@ -772,13 +1025,14 @@ networks:
 
         // Now omit each required field individually
         let missing_address = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
     chain-id: 1
     orderbooks:
       - dump-url: "http://example.com"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 1
         end-block-hash: "0x0abc"
         end-block-time-ms: 1
@@ -789,7 +1043,7 @@ networks:
         ));
 
         let missing_dump = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -805,8 +1059,26 @@ networks:
             YamlError::Field { .. }
         ));
 
+        let missing_sha256 = r#"
+manifest-version: 2
+db-schema-version: 2
+networks:
+  mainnet:
+    chain-id: 1
+    orderbooks:
+      - address: "0x0000000000000000000000000000000000000001"
+        dump-url: "http://example.com"
+        end-block: 1
+        end-block-hash: "0x0abc"
+        end-block-time-ms: 1
+"#;
+        assert!(matches!(
+            parse_manifest_doc(&load(missing_sha256)).unwrap_err(),
+            YamlError::Field { .. }
+        ));
+
         let missing_end_block = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -814,6 +1086,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block-hash: "0x0abc"
         end-block-time-ms: 1
 "#;
@@ -823,7 +1096,7 @@ networks:
         ));
 
         let missing_end_hash = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -831,6 +1104,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 1
         end-block-time-ms: 1
 "#;
@@ -840,7 +1114,7 @@ networks:
         ));
 
         let missing_end_time = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -848,6 +1122,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 1
         end-block-hash: "0x0abc"
 "#;
@@ -860,7 +1135,7 @@ networks:
     #[test]
     fn test_find_across_networks_and_negatives() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -868,6 +1143,7 @@ networks:
     orderbooks:
       - address: "0x1111111111111111111111111111111111111111"
         dump-url: "http://example.com/a"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 10
         end-block-hash: "0x0a"
         end-block-time-ms: 100
@@ -876,6 +1152,7 @@ networks:
     orderbooks:
       - address: "0x2222222222222222222222222222222222222222"
         dump-url: "http://example.com/b"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 20
         end-block-hash: "0x0b"
         end-block-time-ms: 200
@@ -938,7 +1215,7 @@ networks: {}
     #[test]
     fn test_empty_network_key_is_rejected() {
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   "":
@@ -957,4 +1234,163 @@ networks:
             other => panic!("unexpected error: {other:?}"),
         }
     }
+
+    #[test]
+    fn test_signature_roundtrip_and_signing_payload() {
+        let mut manifest = LocalDbManifest::new();
+        manifest.add_network("mainnet", 1).unwrap();
+        manifest
+            .push_orderbook("mainnet", sample_orderbook())
+            .unwrap();
+        let unsigned_yaml = manifest.to_yaml_string().unwrap();
+        assert!(!unsigned_yaml.contains("signature"));
+        assert!(unsigned_yaml.contains(&B256::repeat_byte(0xaa).to_string()));
+
+        let payload = manifest.signing_payload().unwrap();
+        assert_eq!(payload, unsigned_yaml);
+
+        manifest.signature = Some(Bytes::from(vec![0x1b; 65]));
+        let signed_yaml = manifest.to_yaml_string().unwrap();
+        assert!(signed_yaml.contains("signature"));
+
+        let reparsed = parse_manifest_doc(&load(&signed_yaml)).unwrap();
+        assert_eq!(reparsed, manifest);
+        assert_eq!(reparsed.signing_payload().unwrap(), payload);
+
+        let bad_signature = format!("{unsigned_yaml}\nsignature: not-hex\n");
+        let err = parse_manifest_doc(&load(&bad_signature)).unwrap_err();
+        assert!(matches!(
+            err,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue { ref field, .. },
+                ..
+            } if field == "signature"
+        ));
+    }
+
+    fn sample_delta(
+        start_block: u64,
+        start_hash: &str,
+        end_block: u64,
+        end_hash: &str,
+    ) -> ManifestDelta {
+        ManifestDelta {
+            dump_url: Url::parse(&format!("https://example.com/dump-{end_block}")).unwrap(),
+            dump_sha256: B256::repeat_byte(end_block as u8),
+            start_block,
+            start_block_hash: Bytes::from_str(start_hash).unwrap(),
+            end_block,
+            end_block_hash: Bytes::from_str(end_hash).unwrap(),
+            end_block_time_ms: end_block * 10,
+        }
+    }
+
+    #[test]
+    fn test_orderbook_deltas_roundtrip_and_lookup() {
+        let orderbook = ManifestOrderbook {
+            deltas: vec![
+                sample_delta(42, "0x0abc", 50, "0x0b50"),
+                sample_delta(50, "0x0b50", 60, "0x0b60"),
+            ],
+            ..sample_orderbook()
+        };
+        let mut manifest = LocalDbManifest::new();
+        manifest.add_network("mainnet", 1).unwrap();
+        manifest
+            .push_orderbook("mainnet", orderbook.clone())
+            .unwrap();
+
+        let yaml = manifest.to_yaml_string().unwrap();
+        assert!(yaml.contains("start-block-hash"));
+        let reparsed = parse_manifest_doc(&load(&yaml)).unwrap();
+        assert_eq!(manifest, reparsed);
+
+        assert_eq!(orderbook.head_block(), 60);
+        assert_eq!(
+            orderbook.head_block_hash(),
+            &Bytes::from_str("0x0b60").unwrap()
+        );
+        assert_eq!(orderbook.head_block_time_ms(), 600);
+
+        let hash = |value: &str| Bytes::from_str(value).unwrap();
+        assert_eq!(
+            orderbook.deltas_after(42, &hash("0x0abc")),
+            Some(&orderbook.deltas[..])
+        );
+        assert_eq!(
+            orderbook.deltas_after(50, &hash("0x0b50")),
+            Some(&orderbook.deltas[1..])
+        );
+        assert_eq!(orderbook.deltas_after(60, &hash("0x0b60")), Some(&[][..]));
+        assert_eq!(orderbook.deltas_after(50, &hash("0x0bad")), None);
+        assert_eq!(orderbook.deltas_after(55, &hash("0x0b50")), None);
+
+        let base_only = sample_orderbook();
+        assert_eq!(base_only.head_block(), 42);
+        assert!(!base_only
+            .to_yaml_hash()
+            .as_hash()
+            .unwrap()
+            .contains_key(&StrictYaml::String("deltas".to_string())));
+    }
+
+    #[test]
+    fn test_orderbook_rejects_non_contiguous_deltas() {
+        let base_loc = "manifest.networks.mainnet";
+        let yaml = r#"
+address: "0x0000000000000000000000000000000000000001"
+dump-url: "http://example.com"
+dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
+end-block: 10
+end-block-hash: "0x0a10"
+end-block-time-ms: 1
+deltas:
+  - dump-url: "http://example.com/delta-20"
+    dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
+    start-block: 10
+    start-block-hash: "0x0a10"
+    end-block: 20
+    end-block-hash: "0x0a20"
+    end-block-time-ms: 2
+  - dump-url: "http://example.com/delta-30"
+    dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
+    start-block: 21
+    start-block-hash: "0x0a20"
+    end-block: 30
+    end-block-hash: "0x0a30"
+    end-block-time-ms: 3
+"#;
+        let err = parse_single_orderbook(0, &load(yaml), base_loc).unwrap_err();
+        match err {
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue { field, .. },
+                location,
+            } => {
+                assert_eq!(field, "start-block");
+                assert_eq!(
+                    location,
+                    "manifest.networks.mainnet.orderbooks[0].deltas[1]"
+                );
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let backwards = r#"
+dump-url: "http://example.com/delta"
+dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
+start-block: 10
+start-block-hash: "0x0a10"
+end-block: 10
+end-block-hash: "0x0a10"
+end-block-time-ms: 1
+"#;
+        let err = parse_single_delta(&load(backwards), "delta").unwrap_err();
+        assert!(matches!(
+            err,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue { ref field, .. },
+                ..
+            } if field == "end-block"
+        ));
+    }
 }
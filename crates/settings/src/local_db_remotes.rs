@@ -1,10 +1,12 @@
 use crate::yaml::{
-    context::Context, default_document, optional_hash, require_string, FieldErrorKind, YamlError,
-    YamlParsableHash,
+    context::Context, default_document, optional_hash, optional_string, require_string,
+    FieldErrorKind, YamlError, YamlParsableHash,
 };
+use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
+    str::FromStr,
     sync::{Arc, RwLock},
 };
 use strict_yaml_rust::StrictYaml;
@@ -21,6 +23,9 @@ pub struct LocalDbRemoteCfg {
     pub key: String,
     #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
     pub url: Url,
+    /// Address whose EIP-191 signature the remote's manifest must carry.
+    #[cfg_attr(target_family = "wasm", tsify(optional, type = "string"))]
+    pub publisher: Option<Address>,
 }
 #[cfg(target_family = "wasm")]
 impl_wasm_traits!(LocalDbRemoteCfg);
@@ -55,7 +60,19 @@ impl YamlParsableHash for LocalDbRemoteCfg {
                         .to_string();
                     let location = format!("local-db-remotes[{}]", remote_key);
 
-                    let url_str = require_string(remote_yaml, None, Some(location.clone()))?;
+                    // A remote is either a bare manifest URL or a map with the
+                    // URL and the address expected to sign the manifest.
+                    let (url_str, publisher_str) = if remote_yaml.as_hash().is_some() {
+                        (
+                            require_string(remote_yaml, Some("url"), Some(location.clone()))?,
+                            optional_string(remote_yaml, "publisher"),
+                        )
+                    } else {
+                        (
+                            require_string(remote_yaml, None, Some(location.clone()))?,
+                            None,
+                        )
+                    };
                     let url =
                         LocalDbRemoteCfg::validate_url(&url_str).map_err(|e| YamlError::Field {
                             kind: FieldErrorKind::InvalidValue {
@@ -64,11 +81,23 @@ impl YamlParsableHash for LocalDbRemoteCfg {
                             },
                             location: location.clone(),
                         })?;
+                    let publisher = publisher_str
+                        .map(|publisher| {
+                            Address::from_str(&publisher).map_err(|e| YamlError::Field {
+                                kind: FieldErrorKind::InvalidValue {
+                                    field: "publisher".to_string(),
+                                    reason: e.to_string(),
+                                },
+                                location: location.clone(),
+                            })
+                        })
+                        .transpose()?;
 
                     let remote = LocalDbRemoteCfg {
                         document: document.clone(),
                         key: remote_key.clone(),
                         url,
+                        publisher,
                     };
 
                     if remotes.contains_key(&remote_key) {
@@ -92,13 +121,14 @@ impl Default for LocalDbRemoteCfg {
             document: default_document(),
             key: "".to_string(),
             url: Url::parse("https://example.com/localdb").unwrap(),
+            publisher: None,
         }
     }
 }
 
 impl PartialEq for LocalDbRemoteCfg {
     fn eq(&self, other: &Self) -> bool {
-        self.key == other.key && self.url == other.url
+        self.key == other.key && self.url == other.url && self.publisher == other.publisher
     }
 }
 
@@ -156,6 +186,49 @@ local-db-remotes:
         );
     }
 
+    #[test]
+    fn test_parse_local_db_remotes_with_publisher() {
+        let yaml = r#"
+local-db-remotes:
+    mainnet:
+        url: https://example.com/localdb/mainnet
+        publisher: 0x00000000000000000000000000000000000000aa
+    polygon: https://example.com/localdb/polygon
+"#;
+        let remotes =
+            LocalDbRemoteCfg::parse_all_from_yaml(vec![get_document(yaml)], None).unwrap();
+
+        let mainnet = remotes.get("mainnet").unwrap();
+        assert_eq!(
+            mainnet.url,
+            Url::parse("https://example.com/localdb/mainnet").unwrap()
+        );
+        assert_eq!(
+            mainnet.publisher,
+            Some(Address::from_str("0x00000000000000000000000000000000000000aa").unwrap())
+        );
+        assert_eq!(remotes.get("polygon").unwrap().publisher, None);
+
+        let yaml = r#"
+local-db-remotes:
+    mainnet:
+        url: https://example.com/localdb/mainnet
+        publisher: not-an-address
+"#;
+        let err =
+            LocalDbRemoteCfg::parse_all_from_yaml(vec![get_document(yaml)], None).unwrap_err();
+        match err {
+            YamlError::Field { kind, location } => {
+                assert_eq!(location, "local-db-remotes[mainnet]".to_string());
+                assert!(matches!(
+                    kind,
+                    FieldErrorKind::InvalidValue { field, .. } if field == "publisher"
+                ));
+            }
+            _ => panic!("unexpected error type"),
+        }
+    }
+
     #[test]
     fn test_parse_local_db_remotes_optional_absent_is_ok() {
         // No local-db-remotes key
@@ -1,13 +1,14 @@
 use crate::utils::{parse_positive_u32, parse_positive_u64};
 use crate::yaml::{
-    context::Context, default_document, optional_hash, require_string, FieldErrorKind, YamlError,
-    YamlParsableHash,
+    context::Context, default_document, optional_hash, optional_string, require_string,
+    FieldErrorKind, YamlError, YamlParsableHash,
 };
 
-const ALLOWED_LOCAL_DB_SYNC_KEYS: [&str; 8] = [
+const ALLOWED_LOCAL_DB_SYNC_KEYS: [&str; 9] = [
     "batch-size",
     "bootstrap-block-threshold",
     "finality-depth",
+    "hyperrpc-url",
     "max-concurrent-batches",
     "rate-limit-delay-ms",
     "retry-attempts",
@@ -36,6 +37,10 @@ pub struct LocalDbSyncCfg {
     pub finality_depth: u32,
     pub bootstrap_block_threshold: u32,
     pub sync_interval_ms: u64,
+    /// HyperRPC URL the producer fetches logs from, with `{chain-id}` and
+    /// `{api-token}` placeholders, for chains without a built-in HyperRPC URL
+    #[cfg_attr(target_family = "wasm", tsify(optional))]
+    pub hyperrpc_url: Option<String>,
 }
 #[cfg(target_family = "wasm")]
 impl_wasm_traits!(LocalDbSyncCfg);
@@ -90,6 +95,7 @@ impl LocalDbSyncCfg {
             "sync-interval-ms",
             location,
         )?;
+        let hyperrpc_url = optional_string(yaml, "hyperrpc-url");
 
         Ok(LocalDbSyncCfg {
             document: default_document(),
@@ -102,6 +108,7 @@ impl LocalDbSyncCfg {
             finality_depth,
             bootstrap_block_threshold,
             sync_interval_ms,
+            hyperrpc_url,
         })
     }
 }
@@ -221,6 +228,7 @@ impl Default for LocalDbSyncCfg {
             finality_depth: 1,
             bootstrap_block_threshold: 1,
             sync_interval_ms: 1,
+            hyperrpc_url: None,
         }
     }
 }
@@ -236,6 +244,7 @@ impl PartialEq for LocalDbSyncCfg {
             && self.finality_depth == other.finality_depth
             && self.bootstrap_block_threshold == other.bootstrap_block_threshold
             && self.sync_interval_ms == other.sync_interval_ms
+            && self.hyperrpc_url == other.hyperrpc_url
     }
 }
 
@@ -337,6 +346,7 @@ not-sync:
             finality_depth: 100,
             bootstrap_block_threshold: 25,
             sync_interval_ms: 5000,
+            hyperrpc_url: None,
         };
         let expected_eth = LocalDbSyncCfg {
             document: default_document(),
@@ -349,6 +359,7 @@ not-sync:
             finality_depth: 64,
             bootstrap_block_threshold: 30,
             sync_interval_ms: 10000,
+            hyperrpc_url: None,
         };
 
         assert_eq!(syncs.get("arbitrum").unwrap(), &expected_arb);
@@ -389,6 +400,34 @@ not-sync:
         );
     }
 
+    #[test]
+    fn test_parse_sync_hyperrpc_url() {
+        let yaml = format!(
+            "{}    hyperrpc-url: https://{{chain-id}}.rpc.hypersync.xyz/{{api-token}}\n",
+            full_sync_yaml(FullSyncArgs {
+                network: "optimism",
+                batch_size: 100,
+                max_concurrent_batches: 5,
+                retry_attempts: 3,
+                retry_delay_ms: 50,
+                rate_limit_delay_ms: 10,
+                finality_depth: 100,
+                bootstrap_block_threshold: 25,
+                sync_interval_ms: 5000,
+            })
+        );
+        let document = get_document(&yaml);
+        let syncs = LocalDbSyncCfg::parse_all_from_yaml(vec![document.clone()], None).unwrap();
+        assert_eq!(
+            syncs.get("optimism").unwrap().hyperrpc_url.as_deref(),
+            Some("https://{chain-id}.rpc.hypersync.xyz/{api-token}")
+        );
+
+        LocalDbSyncCfg::sanitize_documents(std::slice::from_ref(&document)).unwrap();
+        let syncs = LocalDbSyncCfg::parse_all_from_yaml(vec![document], None).unwrap();
+        assert!(syncs.get("optimism").unwrap().hyperrpc_url.is_some());
+    }
+
     #[test]
     fn test_parse_sync_missing_field() {
         let yaml = r#"
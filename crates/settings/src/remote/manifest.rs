@@ -55,7 +55,7 @@ mod tests {
     async fn test_fetch_manifest_happy_path() {
         let server = MockServer::start_async().await;
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -63,6 +63,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com/dump1"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 123
         end-block-hash: "0x0abc"
         end-block-time-ms: 1000
@@ -81,7 +82,7 @@ networks:
             .await
             .unwrap();
 
-        assert_eq!(manifest.manifest_version, 1);
+        assert_eq!(manifest.manifest_version, 2);
         assert_eq!(manifest.db_schema_version, 2);
         let net = manifest.networks.get("mainnet").unwrap();
         assert_eq!(net.chain_id, 1);
@@ -106,7 +107,7 @@ networks:
     async fn test_fetch_manifest_unknown_fields_ignored() {
         let server = MockServer::start_async().await;
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 extra-root: ignored
 networks:
@@ -116,6 +117,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000002"
         dump-url: "http://example.com/dump2"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 555
         end-block-hash: "0x0def"
         end-block-time-ms: 2000
@@ -168,7 +170,7 @@ networks:
     async fn test_fetch_manifest_invalid_types_and_values() {
         let server = MockServer::start_async().await;
         let yaml = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -176,6 +178,7 @@ networks:
     orderbooks:
       - address: 123 # invalid type
         dump-url: "not-a-url"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 0
         end-block-hash: 999 # invalid type
         end-block-time-ms: 0
@@ -214,7 +217,7 @@ networks:
         let server_two = MockServer::start_async().await;
 
         let yaml_one = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   mainnet:
@@ -222,13 +225,14 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000001"
         dump-url: "http://example.com/dump1"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 123
         end-block-hash: "0x0abc"
         end-block-time-ms: 1000
 "#;
 
         let yaml_two = r#"
-manifest-version: 1
+manifest-version: 2
 db-schema-version: 2
 networks:
   goerli:
@@ -236,6 +240,7 @@ networks:
     orderbooks:
       - address: "0x0000000000000000000000000000000000000002"
         dump-url: "http://example.com/dump2"
+        dump-sha256: "0x1111111111111111111111111111111111111111111111111111111111111111"
         end-block: 555
         end-block-hash: "0x0def"
         end-block-time-ms: 2000
@@ -267,7 +272,7 @@ networks:
             .unwrap();
 
         assert_eq!(manifests.len(), 2);
-        assert_eq!(manifests.get(&url_one).unwrap().manifest_version, 1);
-        assert_eq!(manifests.get(&url_two).unwrap().manifest_version, 1);
+        assert_eq!(manifests.get(&url_one).unwrap().manifest_version, 2);
+        assert_eq!(manifests.get(&url_two).unwrap().manifest_version, 2);
     }
 }
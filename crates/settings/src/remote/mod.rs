@@ -1,3 +1,4 @@
 pub mod chains;
 pub mod manifest;
+pub mod settings;
 pub mod tokens;
@@ -0,0 +1,89 @@
+use crate::yaml::{load_yaml, YamlError};
+use thiserror::Error;
+use url::Url;
+
+#[derive(Error, Debug)]
+pub enum FetchSettingsError {
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+    #[error(transparent)]
+    Yaml(#[from] YamlError),
+}
+
+/// Fetches a settings YAML document from a URL. The document is only checked
+/// to be well formed YAML, spec validation is left to whoever consumes it.
+pub async fn fetch_settings(url: Url) -> Result<String, FetchSettingsError> {
+    let text = reqwest::get(url.to_string())
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    load_yaml(&text)?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::MockServer;
+
+    #[tokio::test]
+    async fn test_fetch_settings_happy_path() {
+        let server = MockServer::start_async().await;
+        let yaml = r#"
+version: 4
+networks:
+  mainnet:
+    rpcs:
+      - https://mainnet.infura.io
+    chain-id: 1
+"#;
+
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/settings.yaml");
+                then.status(200).body(yaml);
+            })
+            .await;
+
+        let settings = fetch_settings(Url::parse(&server.url("/settings.yaml")).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(settings, yaml);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_settings_invalid_yaml() {
+        let server = MockServer::start_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/");
+                then.status(200).body("networks: [\n");
+            })
+            .await;
+
+        let err = fetch_settings(Url::parse(&server.base_url()).unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchSettingsError::Yaml(_)));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_settings_http_error() {
+        let server = MockServer::start_async().await;
+
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/");
+                then.status(404);
+            })
+            .await;
+
+        let err = fetch_settings(Url::parse(&server.base_url()).unwrap())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FetchSettingsError::ReqwestError(_)));
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     *,
 };
 use blocks::BlocksCfg;
+use invariant::{InvariantCfg, ALLOWED_INVARIANT_KEYS};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -18,7 +19,14 @@ use yaml::{
     require_string, FieldErrorKind, YamlError, YamlParsableHash,
 };
 
-const ALLOWED_SCENARIO_KEYS: [&str; 5] = ["bindings", "blocks", "rainlang", "runs", "scenarios"];
+const ALLOWED_SCENARIO_KEYS: [&str; 6] = [
+    "bindings",
+    "blocks",
+    "invariants",
+    "rainlang",
+    "runs",
+    "scenarios",
+];
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(target_family = "wasm", derive(Tsify))]
@@ -39,6 +47,8 @@ pub struct ScenarioCfg {
     pub runs: Option<u64>,
     #[cfg_attr(target_family = "wasm", tsify(optional))]
     pub blocks: Option<BlocksCfg>,
+    #[serde(default)]
+    pub invariants: Vec<InvariantCfg>,
     pub rainlang: Arc<RainlangCfg>,
 }
 #[cfg(target_family = "wasm")]
@@ -143,6 +153,22 @@ impl ScenarioCfg {
             None
         };
 
+        let mut invariants = parent_scenario.invariants.clone();
+        for invariant in InvariantCfg::parse_all_from_scenario(scenario_yaml, &scenario_key)? {
+            match invariants.iter().find(|i| i.key == invariant.key) {
+                Some(parent_invariant) if *parent_invariant != invariant => {
+                    return Err(YamlError::ParseScenarioConfigSourceError(
+                        ParseScenarioConfigSourceError::ParentInvariantShadowedError(
+                            invariant.key.clone(),
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => invariants.push(invariant),
+            }
+        }
+        invariants.sort_by(|a, b| a.key.cmp(&b.key));
+
         let mut current_rainlang: Option<RainlangCfg> = None;
 
         if let Ok(reg) = rainlangs
@@ -192,6 +218,7 @@ impl ScenarioCfg {
                 bindings: bindings.clone(),
                 runs,
                 blocks,
+                invariants: invariants.clone(),
                 rainlang: rainlang.clone().ok_or(
                     ParseScenarioConfigSourceError::RainlangNotFound(scenario_key),
                 )?,
@@ -210,6 +237,7 @@ impl ScenarioCfg {
                     ScenarioParent {
                         key: key.clone(),
                         bindings: Some(bindings.clone()),
+                        invariants: invariants.clone(),
                         rainlang: rainlang.clone(),
                     },
                     child_key,
@@ -368,6 +396,13 @@ impl ScenarioCfg {
                     } else {
                         sanitized.insert(key_yaml, v.clone());
                     }
+                } else if *allowed_key == "invariants" {
+                    if let StrictYaml::Hash(ref invariants) = *v {
+                        let sanitized_invariants = Self::sanitize_invariants_hash(invariants);
+                        sanitized.insert(key_yaml, StrictYaml::Hash(sanitized_invariants));
+                    } else {
+                        sanitized.insert(key_yaml, v.clone());
+                    }
                 } else {
                     sanitized.insert(key_yaml, v.clone());
                 }
@@ -377,6 +412,26 @@ impl ScenarioCfg {
         sanitized
     }
 
+    fn sanitize_invariants_hash(invariants_hash: &Hash) -> Hash {
+        let mut sanitized = Hash::new();
+        for (invariant_key, invariant_value) in invariants_hash {
+            let StrictYaml::Hash(ref invariant_hash) = *invariant_value else {
+                sanitized.insert(invariant_key.clone(), invariant_value.clone());
+                continue;
+            };
+
+            let mut sanitized_invariant = Hash::new();
+            for allowed_key in ALLOWED_INVARIANT_KEYS.iter() {
+                let key_yaml = StrictYaml::String(allowed_key.to_string());
+                if let Some(v) = invariant_hash.get(&key_yaml) {
+                    sanitized_invariant.insert(key_yaml, v.clone());
+                }
+            }
+            sanitized.insert(invariant_key.clone(), StrictYaml::Hash(sanitized_invariant));
+        }
+        sanitized
+    }
+
     fn sanitize_scenarios_hash(scenarios_hash: &Hash) -> Hash {
         let mut sanitized_scenarios: Vec<(String, StrictYaml)> = Vec::new();
 
@@ -434,6 +489,7 @@ impl YamlParsableHash for ScenarioCfg {
                         ScenarioParent {
                             key: "".to_string(),
                             bindings: None,
+                            invariants: vec![],
                             rainlang: None,
                         },
                         scenario_key.clone(),
@@ -492,6 +548,7 @@ impl Default for ScenarioCfg {
             bindings: HashMap::new(),
             runs: None,
             blocks: None,
+            invariants: vec![],
             rainlang: Arc::new(RainlangCfg::default()),
         }
     }
@@ -503,6 +560,7 @@ impl PartialEq for ScenarioCfg {
             && self.bindings == other.bindings
             && self.runs == other.runs
             && self.blocks == other.blocks
+            && self.invariants == other.invariants
             && self.rainlang == other.rainlang
     }
 }
@@ -521,6 +579,8 @@ pub enum ParseScenarioConfigSourceError {
     ParentOrderbookShadowedError(String),
     #[error("Failed to parse blocks: {0}")]
     BlocksParseError(String),
+    #[error("Parent invariant shadowed by child: {0}")]
+    ParentInvariantShadowedError(String),
 }
 
 impl ParseScenarioConfigSourceError {
@@ -538,6 +598,8 @@ impl ParseScenarioConfigSourceError {
                 format!("Orderbook conflict in your YAML configuration: The child scenario is trying to use orderbook '{}' which differs from the orderbook specified in the parent scenario. Child scenarios must use the same orderbook as their parent.", orderbook),
             ParseScenarioConfigSourceError::BlocksParseError(blocks) =>
                 format!("Failed to parse the 'blocks' configuration in your YAML: {}. Please ensure it follows the correct format.", blocks),
+            ParseScenarioConfigSourceError::ParentInvariantShadowedError(invariant) =>
+                format!("Invariant conflict in your YAML configuration: The child scenario is trying to redefine the invariant '{}' that was already defined in a parent scenario. Child scenarios cannot change invariants defined by parents.", invariant),
         }
     }
 }
@@ -546,6 +608,7 @@ impl ParseScenarioConfigSourceError {
 pub struct ScenarioParent {
     key: String,
     bindings: Option<HashMap<String, String>>,
+    invariants: Vec<InvariantCfg>,
     rainlang: Option<Arc<RainlangCfg>>,
 }
 
@@ -999,6 +1062,88 @@ scenarios:
         );
     }
 
+    #[test]
+    fn test_parse_scenario_invariants() {
+        let prefix = r#"
+networks:
+    mainnet:
+        rpcs:
+            - https://rpc.com
+        chain-id: 1
+rainlangs:
+    mainnet:
+        address: 0x1234567890123456789012345678901234567890
+        network: mainnet
+"#;
+
+        let yaml = r#"
+scenarios:
+    mainnet:
+        rainlang: mainnet
+        invariants:
+            ratio-positive:
+                value: 0.1
+                gt: 0
+        scenarios:
+            child:
+                invariants:
+                    ratio-positive:
+                        value: 0.1
+                        gt: 0
+                    output-capped:
+                        value: 0.0
+                        lte: max-output
+"#;
+        let documents = vec![get_document(prefix), get_document(yaml)];
+        let parent = ScenarioCfg::parse_from_yaml(documents.clone(), "mainnet", None).unwrap();
+        assert_eq!(
+            parent
+                .invariants
+                .iter()
+                .map(|i| i.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["ratio-positive"]
+        );
+        let child = ScenarioCfg::parse_from_yaml(documents, "mainnet.child", None).unwrap();
+        assert_eq!(
+            child
+                .invariants
+                .iter()
+                .map(|i| i.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["output-capped", "ratio-positive"]
+        );
+
+        let shadowed = r#"
+scenarios:
+    mainnet:
+        rainlang: mainnet
+        invariants:
+            ratio-positive:
+                value: 0.1
+                gt: 0
+        scenarios:
+            child:
+                invariants:
+                    ratio-positive:
+                        value: 0.1
+                        gte: 0
+"#;
+        let error = ScenarioCfg::parse_all_from_yaml(
+            vec![get_document(prefix), get_document(shadowed)],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::ParseScenarioConfigSourceError(
+                ParseScenarioConfigSourceError::ParentInvariantShadowedError(
+                    "ratio-positive".to_string()
+                )
+            )
+        );
+    }
+
     #[test]
     fn test_sanitize_drops_unknown_keys() {
         let yaml = r#"
@@ -1044,6 +1189,11 @@ scenarios:
             key1: value1
         runs: 10
         blocks: [1..2]
+        invariants:
+            ratio-positive:
+                value: 0.1
+                gt: 0
+                unknown-key: should-be-removed
         scenarios:
             nested:
                 bindings:
@@ -1070,6 +1220,19 @@ scenarios:
         assert!(mainnet.contains_key(&StrictYaml::String("runs".to_string())));
         assert!(mainnet.contains_key(&StrictYaml::String("blocks".to_string())));
         assert!(mainnet.contains_key(&StrictYaml::String("scenarios".to_string())));
+
+        let invariant = mainnet
+            .get(&StrictYaml::String("invariants".to_string()))
+            .unwrap()
+            .as_hash()
+            .unwrap()
+            .get(&StrictYaml::String("ratio-positive".to_string()))
+            .unwrap()
+            .as_hash()
+            .unwrap();
+        assert!(invariant.contains_key(&StrictYaml::String("value".to_string())));
+        assert!(invariant.contains_key(&StrictYaml::String("gt".to_string())));
+        assert!(!invariant.contains_key(&StrictYaml::String("unknown-key".to_string())));
     }
 
     #[test]
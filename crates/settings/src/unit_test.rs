@@ -88,6 +88,7 @@ impl TestConfigSource {
             bindings: bindings.clone(),
             runs: self.scenario.runs,
             blocks: self.scenario.blocks.clone(),
+            invariants: vec![],
             rainlang: Arc::new(RainlangCfg::dummy()),
         });
 
@@ -0,0 +1,542 @@
+use crate::utils::parse_url;
+use crate::yaml::{
+    context::Context, default_document, optional_hash, require_string, require_vec, FieldErrorKind,
+    YamlError, YamlParsableHash,
+};
+use crate::{OrderCfg, OrderbookCfg};
+use alloy::primitives::{Address, B256, U256};
+use rain_math_float::Float;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use strict_yaml_rust::StrictYaml;
+use url::Url;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+/// Condition that fires a watcher's webhook
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub enum WatchTriggerCfg {
+    /// A watched order was taken or cleared
+    OrderTaken,
+    /// A watched order was removed from the orderbook
+    OrderRemoved,
+    /// Quoting a watched order started failing
+    OrderUnquotable,
+    /// A watched vault's balance dropped below its `min-balance`
+    VaultBalanceLow,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(WatchTriggerCfg);
+
+impl WatchTriggerCfg {
+    pub const ALL: [WatchTriggerCfg; 4] = [
+        WatchTriggerCfg::OrderTaken,
+        WatchTriggerCfg::OrderRemoved,
+        WatchTriggerCfg::OrderUnquotable,
+        WatchTriggerCfg::VaultBalanceLow,
+    ];
+}
+
+impl FromStr for WatchTriggerCfg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "order-taken" => Ok(WatchTriggerCfg::OrderTaken),
+            "order-removed" => Ok(WatchTriggerCfg::OrderRemoved),
+            "order-unquotable" => Ok(WatchTriggerCfg::OrderUnquotable),
+            "vault-balance-low" => Ok(WatchTriggerCfg::VaultBalanceLow),
+            _ => Err(format!(
+                "unknown trigger '{}', expected one of order-taken, order-removed, order-unquotable, vault-balance-low",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub struct WatchedVaultCfg {
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub owner: Address,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub token: Address,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub vault_id: U256,
+    /// Decimal token amount, a balance below it fires `vault-balance-low`
+    pub min_balance: String,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(WatchedVaultCfg);
+
+/// Orders and vaults of one orderbook whose changes are posted to a webhook.
+///
+/// ```yaml
+/// watchers:
+///   my-watcher:
+///     orderbook: base
+///     webhook: https://example.com/hooks/raindex
+///     triggers:
+///       - order-taken
+///       - vault-balance-low
+///     orders:
+///       - 0x...
+///     vaults:
+///       - owner: 0x...
+///         token: 0x...
+///         vault-id: 0x01
+///         min-balance: 100
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+#[serde(rename_all = "kebab-case")]
+pub struct WatcherCfg {
+    #[serde(skip, default = "default_document")]
+    pub document: Arc<RwLock<StrictYaml>>,
+    pub key: String,
+    pub orderbook: Arc<OrderbookCfg>,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
+    pub webhook: Url,
+    /// Defaults to every trigger when omitted
+    pub triggers: Vec<WatchTriggerCfg>,
+    #[cfg_attr(target_family = "wasm", tsify(type = "string[]"))]
+    pub orders: Vec<B256>,
+    pub vaults: Vec<WatchedVaultCfg>,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(WatcherCfg);
+
+impl WatcherCfg {
+    pub fn has_trigger(&self, trigger: WatchTriggerCfg) -> bool {
+        self.triggers.contains(&trigger)
+    }
+
+    pub fn watches_order(&self, order_hash: &B256) -> bool {
+        self.orders.contains(order_hash)
+    }
+
+    fn invalid_value(field: &str, reason: String, location: &str) -> YamlError {
+        YamlError::Field {
+            kind: FieldErrorKind::InvalidValue {
+                field: field.to_string(),
+                reason,
+            },
+            location: location.to_string(),
+        }
+    }
+
+    fn parse_address(yaml: &StrictYaml, field: &str, location: &str) -> Result<Address, YamlError> {
+        let value = require_string(yaml, Some(field), Some(location.to_string()))?;
+        Address::from_str(&value).map_err(|e| Self::invalid_value(field, e.to_string(), location))
+    }
+
+    fn parse_triggers(
+        yaml: &StrictYaml,
+        location: &str,
+    ) -> Result<Vec<WatchTriggerCfg>, YamlError> {
+        if yaml["triggers"].is_badvalue() {
+            return Ok(WatchTriggerCfg::ALL.to_vec());
+        }
+        let triggers_yaml = require_vec(yaml, "triggers", Some(location.to_string()))?;
+
+        let mut triggers = Vec::new();
+        for (i, trigger_yaml) in triggers_yaml.iter().enumerate() {
+            let value = require_string(
+                trigger_yaml,
+                None,
+                Some(format!("triggers index '{}' in {}", i, location)),
+            )?;
+            let trigger = WatchTriggerCfg::from_str(&value)
+                .map_err(|reason| Self::invalid_value("triggers", reason, location))?;
+            if !triggers.contains(&trigger) {
+                triggers.push(trigger);
+            }
+        }
+        if triggers.is_empty() {
+            return Err(Self::invalid_value(
+                "triggers",
+                "at least one trigger is required".to_string(),
+                location,
+            ));
+        }
+        Ok(triggers)
+    }
+
+    fn parse_orders(yaml: &StrictYaml, location: &str) -> Result<Vec<B256>, YamlError> {
+        if yaml["orders"].is_badvalue() {
+            return Ok(vec![]);
+        }
+        let orders_yaml = require_vec(yaml, "orders", Some(location.to_string()))?;
+
+        orders_yaml
+            .iter()
+            .enumerate()
+            .map(|(i, order_yaml)| {
+                let value = require_string(
+                    order_yaml,
+                    None,
+                    Some(format!("orders index '{}' in {}", i, location)),
+                )?;
+                B256::from_str(&value)
+                    .map_err(|e| Self::invalid_value("orders", e.to_string(), location))
+            })
+            .collect()
+    }
+
+    fn parse_vaults(yaml: &StrictYaml, location: &str) -> Result<Vec<WatchedVaultCfg>, YamlError> {
+        if yaml["vaults"].is_badvalue() {
+            return Ok(vec![]);
+        }
+        let vaults_yaml = require_vec(yaml, "vaults", Some(location.to_string()))?;
+
+        vaults_yaml
+            .iter()
+            .enumerate()
+            .map(|(i, vault_yaml)| {
+                let location = format!("vaults index '{}' in {}", i, location);
+
+                let owner = Self::parse_address(vault_yaml, "owner", &location)?;
+                let token = Self::parse_address(vault_yaml, "token", &location)?;
+                let vault_id = OrderCfg::validate_vault_id(&require_string(
+                    vault_yaml,
+                    Some("vault-id"),
+                    Some(location.clone()),
+                )?)
+                .map_err(|e| Self::invalid_value("vault-id", e.to_string(), &location))?;
+                let min_balance =
+                    require_string(vault_yaml, Some("min-balance"), Some(location.clone()))?;
+                Float::parse(min_balance.clone())
+                    .map_err(|e| Self::invalid_value("min-balance", e.to_string(), &location))?;
+
+                Ok(WatchedVaultCfg {
+                    owner,
+                    token,
+                    vault_id,
+                    min_balance,
+                })
+            })
+            .collect()
+    }
+}
+
+impl YamlParsableHash for WatcherCfg {
+    fn parse_all_from_yaml(
+        documents: Vec<Arc<RwLock<StrictYaml>>>,
+        context: Option<&Context>,
+    ) -> Result<HashMap<String, Self>, YamlError> {
+        let mut watchers: HashMap<String, WatcherCfg> = HashMap::new();
+
+        let orderbooks = OrderbookCfg::parse_all_from_yaml(documents.clone(), context);
+
+        for document in &documents {
+            let document_read = document.read().map_err(|_| YamlError::ReadLockError)?;
+
+            if let Some(watchers_hash) = optional_hash(&document_read, "watchers") {
+                for (key_yaml, watcher_yaml) in watchers_hash {
+                    let watcher_key = key_yaml.as_str().unwrap_or_default().to_string();
+                    let location = format!("watcher '{}'", watcher_key);
+
+                    if watchers.contains_key(&watcher_key) {
+                        return Err(YamlError::KeyShadowing(watcher_key, "watchers".to_string()));
+                    }
+
+                    let orderbook_name =
+                        require_string(watcher_yaml, Some("orderbook"), Some(location.clone()))?;
+                    let orderbooks = orderbooks.as_ref().map_err(|e| YamlError::Field {
+                        kind: FieldErrorKind::InvalidValue {
+                            field: "orderbooks".to_string(),
+                            reason: e.to_string(),
+                        },
+                        location: "root".to_string(),
+                    })?;
+                    let orderbook = Arc::new(
+                        orderbooks
+                            .get(&orderbook_name)
+                            .ok_or_else(|| YamlError::KeyNotFound(orderbook_name.clone()))?
+                            .clone(),
+                    );
+
+                    let webhook = parse_url(
+                        &require_string(watcher_yaml, Some("webhook"), Some(location.clone()))?,
+                        "webhook",
+                        location.clone(),
+                    )?;
+                    let triggers = WatcherCfg::parse_triggers(watcher_yaml, &location)?;
+                    let orders = WatcherCfg::parse_orders(watcher_yaml, &location)?;
+                    let vaults = WatcherCfg::parse_vaults(watcher_yaml, &location)?;
+
+                    watchers.insert(
+                        watcher_key.clone(),
+                        WatcherCfg {
+                            document: document.clone(),
+                            key: watcher_key,
+                            orderbook,
+                            webhook,
+                            triggers,
+                            orders,
+                            vaults,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(watchers)
+    }
+}
+
+impl Default for WatcherCfg {
+    fn default() -> Self {
+        WatcherCfg {
+            document: default_document(),
+            key: String::new(),
+            orderbook: Arc::new(OrderbookCfg::default()),
+            webhook: Url::parse("http://localhost").unwrap(),
+            triggers: WatchTriggerCfg::ALL.to_vec(),
+            orders: vec![],
+            vaults: vec![],
+        }
+    }
+}
+
+impl PartialEq for WatcherCfg {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+            && self.orderbook == other.orderbook
+            && self.webhook == other.webhook
+            && self.triggers == other.triggers
+            && self.orders == other.orders
+            && self.vaults == other.vaults
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::yaml::tests::get_document;
+
+    const PREFIX: &str = r#"
+networks:
+    mainnet:
+        rpcs:
+            - https://mainnet.infura.io
+        chain-id: 1
+subgraphs:
+    mainnet: https://api.thegraph.com/subgraphs/name/xyz
+orderbooks:
+    mainnet:
+        address: 0x1234567890123456789012345678901234567890
+        network: mainnet
+        subgraph: mainnet
+        deployment-block: 1
+"#;
+
+    fn parse(watchers: &str) -> Result<HashMap<String, WatcherCfg>, YamlError> {
+        WatcherCfg::parse_all_from_yaml(
+            vec![get_document(&format!("{}{}", PREFIX, watchers))],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_parse_watchers() {
+        let watchers = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/hooks/raindex
+        triggers:
+            - order-taken
+            - vault-balance-low
+            - order-taken
+        orders:
+            - 0x0000000000000000000000000000000000000000000000000000000000000001
+        vaults:
+            - owner: 0x0000000000000000000000000000000000000002
+              token: 0x0000000000000000000000000000000000000003
+              vault-id: 0x01
+              min-balance: 100.5
+    everything:
+        orderbook: mainnet
+        webhook: https://example.com/hooks/all
+"#,
+        )
+        .unwrap();
+
+        let alerts = watchers.get("alerts").unwrap();
+        assert_eq!(alerts.orderbook.key, "mainnet");
+        assert_eq!(
+            alerts.webhook,
+            Url::parse("https://example.com/hooks/raindex").unwrap()
+        );
+        assert_eq!(
+            alerts.triggers,
+            vec![
+                WatchTriggerCfg::OrderTaken,
+                WatchTriggerCfg::VaultBalanceLow
+            ]
+        );
+        assert_eq!(alerts.orders, vec![B256::with_last_byte(1)]);
+        assert_eq!(
+            alerts.vaults,
+            vec![WatchedVaultCfg {
+                owner: Address::with_last_byte(2),
+                token: Address::with_last_byte(3),
+                vault_id: U256::from(1),
+                min_balance: "100.5".to_string(),
+            }]
+        );
+        assert!(alerts.watches_order(&B256::with_last_byte(1)));
+        assert!(!alerts.has_trigger(WatchTriggerCfg::OrderRemoved));
+
+        let everything = watchers.get("everything").unwrap();
+        assert_eq!(everything.triggers, WatchTriggerCfg::ALL.to_vec());
+        assert!(everything.orders.is_empty());
+        assert!(everything.vaults.is_empty());
+    }
+
+    #[test]
+    fn test_parse_watchers_errors() {
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        webhook: https://example.com/hooks/raindex
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::Missing("orderbook".to_string()),
+                location: "watcher 'alerts'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: missing
+        webhook: https://example.com/hooks/raindex
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(error, YamlError::KeyNotFound("missing".to_string()));
+
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: not a url
+"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue { ref field, .. },
+                ..
+            } if field == "webhook"
+        ));
+
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/hooks/raindex
+        triggers:
+            - order-expired
+"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown trigger 'order-expired'"));
+
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/hooks/raindex
+        vaults:
+            - owner: 0x0000000000000000000000000000000000000002
+              token: 0x0000000000000000000000000000000000000003
+              vault-id: 0x01
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::Missing("min-balance".to_string()),
+                location: "vaults index '0' in watcher 'alerts'".to_string(),
+            }
+        );
+
+        let error = parse(
+            r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/hooks/raindex
+        vaults:
+            - owner: 0x0000000000000000000000000000000000000002
+              token: 0x0000000000000000000000000000000000000003
+              vault-id: 0x01
+              min-balance: lots
+"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            YamlError::Field {
+                kind: FieldErrorKind::InvalidValue { ref field, .. },
+                ref location,
+            } if field == "min-balance" && location == "vaults index '0' in watcher 'alerts'"
+        ));
+    }
+
+    #[test]
+    fn test_parse_watchers_key_shadowing() {
+        let error = WatcherCfg::parse_all_from_yaml(
+            vec![
+                get_document(&format!(
+                    "{}{}",
+                    PREFIX,
+                    r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/a
+"#
+                )),
+                get_document(
+                    r#"
+watchers:
+    alerts:
+        orderbook: mainnet
+        webhook: https://example.com/b
+"#,
+                ),
+            ],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            YamlError::KeyShadowing("alerts".to_string(), "watchers".to_string())
+        );
+    }
+}
@@ -2,8 +2,8 @@ use super::{cache::Cache, sanitize_all_documents, ValidationConfig, *};
 use crate::{
     accounts::AccountCfg, local_db_remotes::LocalDbRemoteCfg, local_db_sync::LocalDbSyncCfg,
     metaboard::MetaboardCfg, remote_networks::RemoteNetworksCfg, remote_tokens::RemoteTokensCfg,
-    sentry::Sentry, spec_version::SpecVersion, subgraph::SubgraphCfg, NetworkCfg, OrderbookCfg,
-    RainlangCfg, TokenCfg,
+    sentry::Sentry, spec_version::SpecVersion, subgraph::SubgraphCfg, watcher::WatcherCfg,
+    NetworkCfg, OrderbookCfg, RainlangCfg, TokenCfg,
 };
 use alloy::primitives::Address;
 use serde::{
@@ -455,6 +455,18 @@ impl OrderbookYaml {
         let context = self.build_context();
         AccountCfg::parse_from_yaml(self.documents.clone(), key, Some(&context))
     }
+
+    pub fn get_watcher_keys(&self) -> Result<Vec<String>, YamlError> {
+        Ok(self.get_watchers()?.keys().cloned().collect())
+    }
+    pub fn get_watchers(&self) -> Result<HashMap<String, WatcherCfg>, YamlError> {
+        let context = self.build_context();
+        WatcherCfg::parse_all_from_yaml(self.documents.clone(), Some(&context))
+    }
+    pub fn get_watcher(&self, key: &str) -> Result<WatcherCfg, YamlError> {
+        let context = self.build_context();
+        WatcherCfg::parse_from_yaml(self.documents.clone(), key, Some(&context))
+    }
 }
 
 impl Serialize for OrderbookYaml {
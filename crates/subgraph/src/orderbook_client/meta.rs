@@ -0,0 +1,60 @@
+use super::*;
+use crate::types::meta::{SgMetaBlock, SgMetaQuery};
+
+impl OrderbookSubgraphClient {
+    /// Fetch the latest block the subgraph has indexed
+    pub async fn latest_indexed_block(&self) -> Result<SgMetaBlock, OrderbookSubgraphClientError> {
+        let data = self.query::<SgMetaQuery, ()>(()).await?;
+        let meta = data.meta.ok_or(OrderbookSubgraphClientError::Empty)?;
+
+        Ok(meta.block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use reqwest::Url;
+    use serde_json::json;
+
+    fn setup_client(server: &MockServer) -> OrderbookSubgraphClient {
+        let url = Url::parse(&server.url("")).unwrap();
+        OrderbookSubgraphClient::new(url)
+    }
+
+    #[tokio::test]
+    async fn test_latest_indexed_block_found() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+
+        sg_server.mock(|when, then| {
+            when.method(POST).path("/").body_contains("_meta");
+            then.status(200).json_body(json!({
+                "data": {
+                    "_meta": {
+                        "block": { "hash": "0xabc", "number": 1234 }
+                    }
+                }
+            }));
+        });
+
+        let block = client.latest_indexed_block().await.unwrap();
+        assert_eq!(block.number, 1234);
+        assert_eq!(block.hash, Some(SgBytes("0xabc".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_latest_indexed_block_missing_meta() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+
+        sg_server.mock(|when, then| {
+            when.method(POST).path("/");
+            then.status(200).json_body(json!({"data": {"_meta": null}}));
+        });
+
+        let result = client.latest_indexed_block().await;
+        assert!(matches!(result, Err(OrderbookSubgraphClientError::Empty)));
+    }
+}
@@ -18,6 +18,7 @@ use reqwest::Url;
 use thiserror::Error;
 use wasm_bindgen_utils::prelude::*;
 
+mod meta;
 mod order;
 mod order_trade;
 mod performance;
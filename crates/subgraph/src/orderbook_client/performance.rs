@@ -1,5 +1,7 @@
 use super::*;
 use crate::performance::vol::{get_vaults_vol, VaultVolume};
+use crate::performance::OrderPerformance;
+use alloy::primitives::Address;
 
 impl OrderbookSubgraphClient {
     pub async fn order_vaults_volume(
@@ -14,25 +16,27 @@ impl OrderbookSubgraphClient {
         Ok(get_vaults_vol(&trades)?)
     }
 
-    // TODO: APY related logic
-    // /// Fetches order data and measures an order's detailed performance (apy and vol)
-    // pub async fn order_performance(
-    //     &self,
-    //     order_id: cynic::Id,
-    //     start_timestamp: Option<u64>,
-    //     end_timestamp: Option<u64>,
-    // ) -> Result<OrderPerformance, OrderbookSubgraphClientError> {
-    //     let order = self.order_detail(&order_id).await?;
-    //     let trades = self
-    //         .order_trades_list_all(order_id, start_timestamp, end_timestamp)
-    //         .await?;
-    //     Ok(OrderPerformance::measure(
-    //         &order,
-    //         &trades,
-    //         start_timestamp,
-    //         end_timestamp,
-    //     )?)
-    // }
+    /// Fetches order data and measures an order's detailed performance (apy and vol),
+    /// optionally denominated in the given io token
+    pub async fn order_performance(
+        &self,
+        order_id: cynic::Id,
+        denomination: Option<Address>,
+        start_timestamp: Option<u64>,
+        end_timestamp: Option<u64>,
+    ) -> Result<OrderPerformance, OrderbookSubgraphClientError> {
+        let order = self.order_detail(&order_id).await?;
+        let trades = self
+            .order_trades_list_all(order_id, start_timestamp, end_timestamp)
+            .await?;
+        Ok(OrderPerformance::measure_denominated(
+            &order,
+            &trades,
+            denomination,
+            start_timestamp,
+            end_timestamp,
+        )?)
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +95,7 @@ mod tests {
                 id: SgBytes("ivbc_default".to_string()),
                 __typename: "TradeVaultBalanceChange".to_string(),
                 amount: SgBytes(F100.as_hex()),
-                new_vault_balance: SgBytes("".to_string()),
+                new_vault_balance: SgBytes(F100.as_hex()),
                 old_vault_balance: SgBytes("".to_string()),
                 vault: SgVaultBalanceChangeVault {
                     id: SgBytes("input_vault_id".to_string()),
@@ -118,7 +122,7 @@ mod tests {
                 id: SgBytes("ovbc_default".to_string()),
                 __typename: "TradeVaultBalanceChange".to_string(),
                 amount: SgBytes(NEG5.as_hex()),
-                new_vault_balance: SgBytes("".to_string()),
+                new_vault_balance: SgBytes(F1000.as_hex()),
                 old_vault_balance: SgBytes("".to_string()),
                 vault: SgVaultBalanceChangeVault {
                     id: SgBytes("output_vault_id".to_string()),
@@ -218,227 +222,203 @@ mod tests {
         ));
     }
 
-    // TODO: APY related logic
-    // use crate::performance::PerformanceError;
-    // use crate::types::common::{SgOrder, SgVault};
-    // use alloy::primitives::U256;
-    //
-    // fn default_sg_order(order_id_str: &str) -> SgOrder {
-    //     SgOrder {
-    //         id: SgBytes(order_id_str.to_string()),
-    //         order_hash: SgBytes(format!("0xhash_{}", order_id_str)),
-    //         owner: SgBytes("0xowner_default".to_string()),
-    //         order_bytes: SgBytes("0xorderbytes_default".to_string()),
-    //         timestamp_added: SgBigInt("1600000000".to_string()),
-    //         active: true,
-    //         orderbook: SgOrderbook {
-    //             id: SgBytes("0xorderbook_default".to_string()),
-    //         },
-    //         inputs: vec![SgVault {
-    //             id: SgBytes("input_vault_id".to_string()),
-    //             owner: SgBytes("0xowner_default".to_string()),
-    //             vault_id: SgBigInt("input_vault_sg_id".to_string()),
-    //             balance: SgBigInt("1000".to_string()),
-    //             token: default_sg_erc20("input"),
-    //             orderbook: SgOrderbook {
-    //                 id: SgBytes("0xorderbook_default".to_string()),
-    //             },
-    //             orders_as_output: vec![],
-    //             orders_as_input: vec![],
-    //             balance_changes: vec![],
-    //         }],
-    //         outputs: vec![SgVault {
-    //             id: SgBytes("output_vault_id".to_string()),
-    //             owner: SgBytes("0xowner_default".to_string()),
-    //             vault_id: SgBigInt("output_vault_sg_id".to_string()),
-    //             balance: SgBigInt("0".to_string()),
-    //             token: default_sg_erc20("output"),
-    //             orderbook: SgOrderbook {
-    //                 id: SgBytes("0xorderbook_default".to_string()),
-    //             },
-    //             orders_as_output: vec![],
-    //             orders_as_input: vec![],
-    //             balance_changes: vec![],
-    //         }],
-    //         meta: None,
-    //         add_events: vec![],
-    //         trades: vec![],
-    //         remove_events: vec![],
-    //     }
-    // }
-    //
-    // #[tokio::test]
-    // async fn test_order_performance_success() {
-    //     let sg_server = MockServer::start_async().await;
-    //     let client = setup_client(&sg_server);
-    //     let order_id_str = "0xperf_order_1";
-    //     let order_id = Id::new(order_id_str);
-    //     let order_data = default_sg_order(order_id_str);
-    //     let trades_data = vec![
-    //         default_sg_trade("trade_perf_1", order_id_str, 1600000100),
-    //         default_sg_trade("trade_perf_2", order_id_str, 1600000200),
-    //     ];
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(format!("\"id\":\"{}\"", order_id_str))
-    //             .body_contains("SgOrderDetailByIdQuery");
-    //         then.status(200)
-    //             .json_body(json!({"data": {"order": order_data}}));
-    //     });
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(order_id_str)
-    //             .body_contains("SgOrderTradesListQuery")
-    //             .body_contains("\"skip\":0");
-    //         then.status(200)
-    //             .json_body(json!({"data": {"trades": trades_data}}));
-    //     });
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(order_id_str)
-    //             .body_contains("SgOrderTradesListQuery")
-    //             .body_contains(format!("\"skip\":{}", ALL_PAGES_QUERY_PAGE_SIZE));
-    //         then.status(200).json_body(json!({"data": {"trades": []}}));
-    //     });
-    //
-    //     let result = client.order_performance(order_id, None, None).await;
-    //     assert!(result.is_ok(), "Result was: {:?}", result);
-    //     let performance_report = result.unwrap();
-    //     assert_eq!(performance_report.order_id, order_id_str);
-    //     assert_eq!(performance_report.inputs_vaults.len(), 1);
-    //     assert_eq!(performance_report.outputs_vaults.len(), 1);
-    //     assert_eq!(performance_report.inputs_vaults[0].id, "input_vault_sg_id");
-    //     assert_eq!(
-    //         performance_report.inputs_vaults[0].token,
-    //         default_sg_erc20("input")
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].id,
-    //         "output_vault_sg_id"
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].token,
-    //         default_sg_erc20("output")
-    //     );
-    //     assert_eq!(
-    //         performance_report.inputs_vaults[0].vol_details.net_vol,
-    //         U256::from(200)
-    //     );
-    //     assert_eq!(
-    //         performance_report.inputs_vaults[0].vol_details.total_in,
-    //         U256::from(200)
-    //     );
-    //     assert_eq!(
-    //         performance_report.inputs_vaults[0].vol_details.total_out,
-    //         U256::from(0)
-    //     );
-    //     assert_eq!(
-    //         performance_report.inputs_vaults[0].vol_details.total_vol,
-    //         U256::from(200)
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].vol_details.net_vol,
-    //         U256::from(100)
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].vol_details.total_in,
-    //         U256::from(0)
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].vol_details.total_out,
-    //         U256::from(100)
-    //     );
-    //     assert_eq!(
-    //         performance_report.outputs_vaults[0].vol_details.total_vol,
-    //         U256::from(100)
-    //     );
-    // }
-    //
-    // #[tokio::test]
-    // async fn test_order_performance_error_no_trades() {
-    //     let sg_server = MockServer::start_async().await;
-    //     let client = setup_client(&sg_server);
-    //     let order_id_str = "0xperf_order_notrades";
-    //     let order_id = Id::new(order_id_str);
-    //     let order_data = default_sg_order(order_id_str);
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(format!("\"id\":\"{}\"", order_id_str))
-    //             .body_contains("SgOrderDetailByIdQuery");
-    //         then.status(200)
-    //             .json_body(json!({"data": {"order": order_data}}));
-    //     });
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(order_id_str)
-    //             .body_contains("SgOrderTradesListQuery");
-    //         then.status(200).json_body(json!({"data": {"trades": []}}));
-    //     });
-    //
-    //     let result = client.order_performance(order_id, None, None).await;
-    //     assert!(result.is_err());
-    //     match result {
-    //         Err(OrderbookSubgraphClientError::PerformanceError(PerformanceError::NoTrades)) => (),
-    //         _ => panic!("Expected PerformanceError::NoTrades, got {:?}", result),
-    //     }
-    // }
-    //
-    // #[tokio::test]
-    // async fn test_order_performance_error_from_order_detail() {
-    //     let sg_server = MockServer::start_async().await;
-    //     let client = setup_client(&sg_server);
-    //     let order_id = Id::new("0xperf_order_err_detail");
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains("SgOrderDetailByIdQuery");
-    //         then.status(200).json_body(json!({"data": {"order": null}}));
-    //     });
-    //
-    //     let result = client.order_performance(order_id, None, None).await;
-    //     assert!(matches!(result, Err(OrderbookSubgraphClientError::Empty)));
-    // }
-    //
-    // #[tokio::test]
-    // async fn test_order_performance_error_from_trades_list_all() {
-    //     let sg_server = MockServer::start_async().await;
-    //     let client = setup_client(&sg_server);
-    //     let order_id_str = "0xperf_order_err_trades";
-    //     let order_id = Id::new(order_id_str);
-    //     let order_data = default_sg_order(order_id_str);
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(format!("\"id\":\"{}\"", order_id_str))
-    //             .body_contains("SgOrderDetailByIdQuery");
-    //         then.status(200)
-    //             .json_body(json!({"data": {"order": order_data}}));
-    //     });
-    //
-    //     sg_server.mock(|when, then| {
-    //         when.method(POST)
-    //             .path("/")
-    //             .body_contains(order_id_str)
-    //             .body_contains("SgOrderTradesListQuery");
-    //         then.status(500);
-    //     });
-    //
-    //     let result = client.order_performance(order_id, None, None).await;
-    //     assert!(matches!(
-    //         result,
-    //         Err(OrderbookSubgraphClientError::CynicClientError(_))
-    //     ));
-    // }
+    use crate::performance::PerformanceError;
+    use crate::types::common::{SgOrder, SgVault};
+
+    fn default_sg_order(order_id_str: &str) -> SgOrder {
+        SgOrder {
+            id: SgBytes(order_id_str.to_string()),
+            order_hash: SgBytes(format!("0xhash_{}", order_id_str)),
+            owner: SgBytes("0xowner_default".to_string()),
+            order_bytes: SgBytes("0xorderbytes_default".to_string()),
+            timestamp_added: SgBigInt("1600000000".to_string()),
+            active: true,
+            orderbook: SgOrderbook {
+                id: SgBytes("0xorderbook_default".to_string()),
+            },
+            inputs: vec![SgVault {
+                id: SgBytes("input_vault_id".to_string()),
+                owner: SgBytes("0xowner_default".to_string()),
+                vault_id: SgBytes("input_vault_sg_id".to_string()),
+                balance: SgBytes(F1000.as_hex()),
+                token: default_sg_erc20("input"),
+                orderbook: SgOrderbook {
+                    id: SgBytes("0xorderbook_default".to_string()),
+                },
+                orders_as_output: vec![],
+                orders_as_input: vec![],
+                balance_changes: vec![],
+            }],
+            outputs: vec![SgVault {
+                id: SgBytes("output_vault_id".to_string()),
+                owner: SgBytes("0xowner_default".to_string()),
+                vault_id: SgBytes("output_vault_sg_id".to_string()),
+                balance: SgBytes(F0.as_hex()),
+                token: default_sg_erc20("output"),
+                orderbook: SgOrderbook {
+                    id: SgBytes("0xorderbook_default".to_string()),
+                },
+                orders_as_output: vec![],
+                orders_as_input: vec![],
+                balance_changes: vec![],
+            }],
+            meta: None,
+            add_events: vec![],
+            trades: vec![],
+            remove_events: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_order_performance_success() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+        let order_id_str = "0xperf_order_1";
+        let order_id = Id::new(order_id_str);
+        let order_data = default_sg_order(order_id_str);
+        let trades_data = vec![
+            default_sg_trade("trade_perf_1", order_id_str, 1600000100),
+            default_sg_trade("trade_perf_2", order_id_str, 1600000200),
+        ];
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"id\":\"{}\"", order_id_str))
+                .body_contains("SgOrderDetailByIdQuery");
+            then.status(200)
+                .json_body(json!({"data": {"order": order_data}}));
+        });
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(order_id_str)
+                .body_contains("SgOrderTradesListQuery")
+                .body_contains("\"skip\":0");
+            then.status(200)
+                .json_body(json!({"data": {"trades": trades_data}}));
+        });
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(order_id_str)
+                .body_contains("SgOrderTradesListQuery")
+                .body_contains(format!("\"skip\":{}", ALL_PAGES_QUERY_PAGE_SIZE));
+            then.status(200).json_body(json!({"data": {"trades": []}}));
+        });
+
+        let result = client.order_performance(order_id, None, None, None).await;
+        assert!(result.is_ok(), "Result was: {:?}", result);
+        let performance_report = result.unwrap();
+        assert_eq!(performance_report.order_id, order_id_str);
+        assert_eq!(performance_report.inputs_vaults.len(), 1);
+        assert_eq!(performance_report.outputs_vaults.len(), 1);
+        assert_eq!(performance_report.inputs_vaults[0].id, "input_vault_sg_id");
+        assert_eq!(
+            performance_report.inputs_vaults[0].token,
+            default_sg_erc20("input")
+        );
+        assert_eq!(
+            performance_report.outputs_vaults[0].id,
+            "output_vault_sg_id"
+        );
+        assert_eq!(
+            performance_report.outputs_vaults[0].token,
+            default_sg_erc20("output")
+        );
+        let input_vol = performance_report.inputs_vaults[0].vol_details;
+        assert!(input_vol.net_vol.eq((F100 + F100).unwrap()).unwrap());
+        assert!(input_vol.total_in.eq((F100 + F100).unwrap()).unwrap());
+        assert!(input_vol.total_out.eq(F0).unwrap());
+        assert!(input_vol.total_vol.eq((F100 + F100).unwrap()).unwrap());
+        let output_vol = performance_report.outputs_vaults[0].vol_details;
+        assert!(output_vol.net_vol.eq((NEG5 + NEG5).unwrap()).unwrap());
+        assert!(output_vol.total_in.eq(F0).unwrap());
+        assert!(output_vol.total_out.eq(F10).unwrap());
+        assert!(output_vol.total_vol.eq(F10).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_order_performance_error_no_trades() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+        let order_id_str = "0xperf_order_notrades";
+        let order_id = Id::new(order_id_str);
+        let order_data = default_sg_order(order_id_str);
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"id\":\"{}\"", order_id_str))
+                .body_contains("SgOrderDetailByIdQuery");
+            then.status(200)
+                .json_body(json!({"data": {"order": order_data}}));
+        });
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(order_id_str)
+                .body_contains("SgOrderTradesListQuery");
+            then.status(200).json_body(json!({"data": {"trades": []}}));
+        });
+
+        let result = client.order_performance(order_id, None, None, None).await;
+        assert!(result.is_err());
+        match result {
+            Err(OrderbookSubgraphClientError::PerformanceError(PerformanceError::NoTrades)) => (),
+            _ => panic!("Expected PerformanceError::NoTrades, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_order_performance_error_from_order_detail() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+        let order_id = Id::new("0xperf_order_err_detail");
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains("SgOrderDetailByIdQuery");
+            then.status(200).json_body(json!({"data": {"order": null}}));
+        });
+
+        let result = client.order_performance(order_id, None, None, None).await;
+        assert!(matches!(result, Err(OrderbookSubgraphClientError::Empty)));
+    }
+
+    #[tokio::test]
+    async fn test_order_performance_error_from_trades_list_all() {
+        let sg_server = MockServer::start_async().await;
+        let client = setup_client(&sg_server);
+        let order_id_str = "0xperf_order_err_trades";
+        let order_id = Id::new(order_id_str);
+        let order_data = default_sg_order(order_id_str);
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"id\":\"{}\"", order_id_str))
+                .body_contains("SgOrderDetailByIdQuery");
+            then.status(200)
+                .json_body(json!({"data": {"order": order_data}}));
+        });
+
+        sg_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(order_id_str)
+                .body_contains("SgOrderTradesListQuery");
+            then.status(500);
+        });
+
+        let result = client.order_performance(order_id, None, None, None).await;
+        assert!(matches!(
+            result,
+            Err(OrderbookSubgraphClientError::CynicClientError(_))
+        ));
+    }
 }
@@ -1,12 +1,9 @@
-use super::{PerformanceError, YEAR18};
+use super::{annual_rate, PerformanceError, DAY_SECONDS};
 use crate::{
     performance::vol::VaultVolume,
     types::common::{SgErc20, SgTrade},
 };
-use alloy::primitives::U256;
-use chrono::TimeDelta;
 use rain_math_float::Float;
-use rain_orderbook_math::{BigUintMath, ONE18};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 #[cfg(target_family = "wasm")]
@@ -36,7 +33,7 @@ pub struct VaultAPY {
     pub apy_details: Option<APYDetails>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenPair {
     pub input: SgErc20,
@@ -72,32 +69,27 @@ pub fn get_vaults_apy(
             })
             .collect::<Vec<&SgTrade>>();
 
-        if vault_trades.is_empty() {
+        let Some(first_trade) = vault_trades.last() else {
             token_vaults_apy.push(VaultAPY {
                 id: vol.id.clone(),
                 token: vol.token.clone(),
                 apy_details: None,
             });
             continue;
-        }
+        };
 
         // this token vault first trade, indictaes the start time
         // to find the end of the first day to find the starting capital
-        let first_trade = vault_trades[vault_trades.len() - 1];
+        let first_trade_time = u64::from_str(&first_trade.timestamp.0)?;
         let first_day_last_trade = vault_trades
             .iter()
-            .filter(|v| {
+            .find(|v| {
                 u64::from_str(&v.timestamp.0)
-                    .ok()
-                    .zip(u64::from_str(&first_trade.timestamp.0).ok())
-                    .is_some_and(|(trade_time, first_trade_time)| {
-                        trade_time <= first_trade_time + TimeDelta::days(1).num_seconds() as u64
-                    })
+                    .is_ok_and(|trade_time| trade_time <= first_trade_time + DAY_SECONDS)
             })
-            .collect::<Vec<&&SgTrade>>()[0];
+            .unwrap_or(first_trade);
 
         // vaults starting capital at end of first day of its first ever trade
-        // as 18 point decimals
         let vault_balance_change = if first_day_last_trade
             .input_vault_balance_change
             .vault
@@ -110,12 +102,10 @@ pub fn get_vaults_apy(
         } else {
             &first_day_last_trade.output_vault_balance_change
         };
-        let starting_capital = U256::from_str(&vault_balance_change.new_vault_balance.0)?
-            .scale_18(vault_balance_change.vault.token.get_decimals()?)
-            .map_err(PerformanceError::from)?;
+        let starting_capital = Float::from_hex(&vault_balance_change.new_vault_balance.0)?;
 
         // the time range for this token vault
-        let mut start = u64::from_str(&first_trade.timestamp.0)?;
+        let mut start = first_trade_time;
         start_timestamp.inspect(|t| {
             if start > *t {
                 start = *t;
@@ -123,25 +113,14 @@ pub fn get_vaults_apy(
         });
         let end = end_timestamp.unwrap_or(chrono::Utc::now().timestamp() as u64);
 
-        // this token vault apy in 18 decimals point
-        let apy = if !starting_capital.is_zero() {
-            match U256::from(end - start)
-                .saturating_mul(ONE18)
-                .div_18(YEAR18
-            {
-                Err(_) => None,
-                Ok(annual_rate_18) => vol
-                    .vol_details
-                    .net_vol
-                    .div_18(starting_capital)
-                    .ok()
-                    .and_then(|v| v.div_18(annual_rate_18).ok()),
-            }
+        // this token vault apy
+        let apy = if !starting_capital.is_zero()? && end > start {
+            let annual_rate = annual_rate(start, end)?;
+            Some(((vol.vol_details.net_vol / starting_capital)? / annual_rate)?)
         } else {
             None
         };
 
-        // this token vault apy
         token_vaults_apy.push(VaultAPY {
             id: vol.id.clone(),
             token: vol.token.clone(),
@@ -149,7 +128,7 @@ pub fn get_vaults_apy(
                 start_time: start,
                 end_time: end,
                 apy,
-                is_neg: vol.is_net_vol_negative(),
+                is_neg: vol.is_net_vol_negative()?,
                 net_vol: vol.vol_details.net_vol,
                 capital: starting_capital,
             }),
@@ -165,139 +144,90 @@ mod tests {
     use crate::{
         performance::vol::VolumeDetails,
         types::common::{
-            SgBigInt, SgBytes, SgOrderbook, SgTradeEvent, SgTradeStructPartialOrder,
-            SgTradeVaultBalanceChange, SgTransaction, SgVaultBalanceChangeVault,
+            SgBigInt, SgBytes, SgOrderbook, SgTradeEvent, SgTradeEventTypename, SgTradeRef,
+            SgTradeStructPartialOrder, SgTradeVaultBalanceChange, SgTransaction,
+            SgVaultBalanceChangeVault,
         },
+        utils::float::*,
     };
     use alloy::primitives::{Address, B256};
 
-    #[test]
-    fn test_get_vaults_apy_ok() {
-        let tokens = get_tokens(SgBigInt(18.to_string()));
-        let trades = get_trades(tokens.clone(), SgBigInt("5000000000000000000".to_string()));
-        let [token1, token2] = tokens;
-        let [vault1, vault2] = get_vault_ids();
-        let vault_vol1 = VaultVolume {
-            id: vault1.to_string(),
-            token: token1.clone(),
+    fn vault_vol(id: B256, token: SgErc20, net_vol: Float) -> VaultVolume {
+        VaultVolume {
+            id: id.to_string(),
+            token,
             vol_details: VolumeDetails {
-                total_in: U256::ZERO,
-                total_out: U256::ZERO,
-                total_vol: U256::ZERO,
-                net_vol: U256::from_str("1000000000000000000").unwrap(),
+                total_in: F0,
+                total_out: F0,
+                total_vol: F0,
+                net_vol,
             },
-        };
-        let vault_vol2 = VaultVolume {
-            id: vault2.to_string(),
-            token: token2.clone(),
-            vol_details: VolumeDetails {
-                total_in: U256::ZERO,
-                total_out: U256::ZERO,
-                total_vol: U256::ZERO,
-                net_vol: U256::from_str("2000000000000000000").unwrap(),
-            },
-        };
-        let result =
-            get_vaults_apy(&trades, &[vault_vol1, vault_vol2], Some(1), Some(10000001)).unwrap();
-        let expected = vec![
-            VaultAPY {
-                id: vault1.to_string(),
-                token: token1.clone(),
-                apy_details: Some(APYDetails {
-                    start_time: 1,
-                    end_time: 10000001,
-                    net_vol: U256::from_str("1000000000000000000").unwrap(),
-                    capital: U256::from_str("5000000000000000000").unwrap(),
-                    // (1/5) / (10000001_end - 1_start / 31_536_00_year)
-                    apy: Some(U256::from_str("630720000000000000").unwrap()),
-                    is_neg: false,
-                }),
-            },
-            VaultAPY {
-                id: vault2.to_string(),
-                token: token2.clone(),
-                apy_details: Some(APYDetails {
-                    start_time: 1,
-                    end_time: 10000001,
-                    net_vol: U256::from_str("2000000000000000000").unwrap(),
-                    capital: U256::from_str("5000000000000000000").unwrap(),
-                    // (2/5) / ((10000001_end - 1_start) / 31_536_00_year)
-                    apy: Some(U256::from_str("1261440000000000000").unwrap()),
-                    is_neg: false,
-                }),
-            },
-        ];
-
-        assert_eq!(result, expected);
+        }
     }
 
     #[test]
-    fn test_get_vaults_apy_err() {
-        let tokens = get_tokens(SgBigInt("bad".to_string()));
-        let trades = get_trades(tokens.clone(), SgBigInt("2000000000000000000".to_string()));
-        let [token1, token2] = tokens;
+    fn test_get_vaults_apy_ok() {
+        let [token1, token2] = get_tokens();
+        let trades = get_trades(SgBytes(F5.as_hex()));
         let [vault1, vault2] = get_vault_ids();
+        let vols = [
+            vault_vol(vault1, token1.clone(), F1),
+            vault_vol(vault2, token2.clone(), F2),
+        ];
 
-        let zero = Float::default();
-        let one = Float::parse("1".to_string()).unwrap();
-        let two = Float::parse("2".to_string()).unwrap();
-
-        let vault_vol1 = VaultVolume {
-            id: vault1.to_string(),
-            token: token1.clone(),
-            vol_details: VolumeDetails {
-                total_in: zero,
-                total_out: zero,
-                total_vol: zero,
-                net_vol: one,
-            },
-        };
-        let vault_vol2 = VaultVolume {
-            id: vault2.to_string(),
-            token: token2.clone(),
-            vol_details: VolumeDetails {
-                total_in: zero,
-                total_out: zero,
-                total_vol: zero,
-                net_vol: two,
-            },
-        };
+        let result = get_vaults_apy(&trades, &vols, Some(1), Some(10000001)).unwrap();
+        assert_eq!(result.len(), 2);
 
-        let err = get_vaults_apy(&trades, &[vault_vol1, vault_vol2], Some(1), Some(10000001))
-            .unwrap_err();
+        assert_eq!(result[0].id, vault1.to_string());
+        assert_eq!(result[0].token, token1);
+        let details = result[0].apy_details.unwrap();
+        assert_eq!(details.start_time, 1);
+        assert_eq!(details.end_time, 10000001);
+        assert!(details.net_vol.eq(F1).unwrap());
+        assert!(details.capital.eq(F5).unwrap());
+        assert!(!details.is_neg);
+        // (1/5) / ((10000001_end - 1_start) / 31_536_000_year)
+        let expected = Float::parse("0.63072".to_string()).unwrap();
+        assert!(details.apy.unwrap().eq(expected).unwrap());
 
-        assert!(matches!(err, PerformanceError::ParseIntError(_)));
+        assert_eq!(result[1].id, vault2.to_string());
+        assert_eq!(result[1].token, token2);
+        let details = result[1].apy_details.unwrap();
+        assert!(details.net_vol.eq(F2).unwrap());
+        assert!(details.capital.eq(F5).unwrap());
+        // (2/5) / ((10000001_end - 1_start) / 31_536_000_year)
+        let expected = Float::parse("1.26144".to_string()).unwrap();
+        assert!(details.apy.unwrap().eq(expected).unwrap());
+    }
 
-        let tokens = get_tokens(SgBigInt("18".to_string()));
-        let trades = get_trades(tokens.clone(), SgBigInt("bad".to_string()));
-        let [token1, token2] = tokens;
+    #[test]
+    fn test_get_vaults_apy_zero_capital_and_no_trades() {
+        let [token1, token2] = get_tokens();
+        let trades = get_trades(SgBytes(F0.as_hex()));
         let [vault1, vault2] = get_vault_ids();
+        let vols = [
+            vault_vol(vault1, token1.clone(), F1),
+            vault_vol(B256::from_slice(&[0x33u8; 32]), token2, F2),
+        ];
 
-        let vault_vol1 = VaultVolume {
-            id: vault1.to_string(),
-            token: token1.clone(),
-            vol_details: VolumeDetails {
-                total_in: zero,
-                total_out: zero,
-                total_vol: zero,
-                net_vol: one,
-            },
-        };
-        let vault_vol2 = VaultVolume {
-            id: vault2.to_string(),
-            token: token2.clone(),
-            vol_details: VolumeDetails {
-                total_in: zero,
-                total_out: zero,
-                total_vol: zero,
-                net_vol: two,
-            },
-        };
+        let result = get_vaults_apy(&trades, &vols, Some(1), Some(10000001)).unwrap();
+        // zero starting capital has no apy
+        let details = result[0].apy_details.unwrap();
+        assert!(details.capital.is_zero().unwrap());
+        assert!(details.apy.is_none());
+        // a vault without trades has no apy details at all
+        assert!(result[1].apy_details.is_none());
+    }
 
-        let err = get_vaults_apy(&trades, &[vault_vol1, vault_vol2], Some(1), Some(10000001))
-            .unwrap_err();
+    #[test]
+    fn test_get_vaults_apy_err() {
+        let [token1, token2] = get_tokens();
+        let trades = get_trades(SgBytes("bad".to_string()));
+        let [vault1, vault2] = get_vault_ids();
+        let vols = [vault_vol(vault1, token1, F1), vault_vol(vault2, token2, F2)];
 
-        assert!(matches!(err, PerformanceError::ParseUnsignedError(_)));
+        let err = get_vaults_apy(&trades, &vols, Some(1), Some(10000001)).unwrap_err();
+        assert!(matches!(err, PerformanceError::FloatError(_)));
     }
 
     fn get_vault_ids() -> [B256; 2] {
@@ -307,7 +237,7 @@ mod tests {
         ]
     }
 
-    fn get_tokens(decimals: SgBigInt) -> [SgErc20; 2] {
+    fn get_tokens() -> [SgErc20; 2] {
         let token1_address = Address::from_slice(&[0x11u8; 20]);
         let token2_address = Address::from_slice(&[0x22u8; 20]);
         let token1 = SgErc20 {
@@ -315,93 +245,60 @@ mod tests {
             address: SgBytes(token1_address.to_string()),
             name: Some("Token1".to_string()),
             symbol: Some("Token1".to_string()),
-            decimals: Some(decimals.clone()),
+            decimals: Some(SgBigInt("18".to_string())),
         };
         let token2 = SgErc20 {
             id: SgBytes(token2_address.to_string()),
             address: SgBytes(token2_address.to_string()),
             name: Some("Token2".to_string()),
             symbol: Some("Token2".to_string()),
-            decimals: Some(decimals),
+            decimals: Some(SgBigInt("18".to_string())),
         };
         [token1, token2]
     }
 
-    fn get_trades(tokens: [SgErc20; 2], new_vault_balance: SgBigInt) -> Vec<SgTrade> {
+    fn balance_change(
+        vault_id: B256,
+        token: SgErc20,
+        amount: Float,
+        new_vault_balance: SgBytes,
+        timestamp: &str,
+    ) -> SgTradeVaultBalanceChange {
         let bytes = SgBytes("".to_string());
-        let bigint = SgBigInt("".to_string());
-        let [vault_id1, vault_id2] = get_vault_ids();
-        let [token1, token2] = tokens;
-
-        let two = Float::parse("2".to_string()).unwrap();
-        let two_str = serde_json::to_string(&two).unwrap();
-        let minus_two = (-two).unwrap();
-        let minus_two_str = serde_json::to_string(&minus_two).unwrap();
-        let five = Float::parse("5".to_string()).unwrap();
-        let five_str = serde_json::to_string(&five).unwrap();
-        let seven = Float::parse("7".to_string()).unwrap();
-        let seven_str = serde_json::to_string(&seven).unwrap();
-
-        let trade1 = SgTrade {
+        SgTradeVaultBalanceChange {
             id: bytes.clone(),
-            order: SgTradeStructPartialOrder {
+            __typename: "TradeVaultBalanceChange".to_string(),
+            amount: SgBytes(amount.as_hex()),
+            new_vault_balance,
+            old_vault_balance: bytes.clone(),
+            vault: SgVaultBalanceChangeVault {
                 id: bytes.clone(),
-                order_hash: bytes.clone(),
-            },
-            trade_event: SgTradeEvent {
-                sender: bytes.clone(),
-                transaction: SgTransaction {
-                    id: bytes.clone(),
-                    from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: bigint.clone(),
-                },
+                token,
+                vault_id: SgBytes(vault_id.to_string()),
             },
-            timestamp: SgBigInt("1".to_string()),
-            orderbook: SgOrderbook { id: bytes.clone() },
-            output_vault_balance_change: SgTradeVaultBalanceChange {
+            timestamp: SgBigInt(timestamp.to_string()),
+            transaction: SgTransaction {
                 id: bytes.clone(),
-                __typename: "TradeVaultBalanceChange".to_string(),
-                amount: SgBytes(minus_two_str.clone()),
-                new_vault_balance: SgBytes(two_str.clone()),
-                old_vault_balance: bigint.clone(),
-                vault: SgVaultBalanceChangeVault {
-                    id: bytes.clone(),
-                    token: token1.clone(),
-                    vault_id: SgBigInt(vault_id1.to_string()),
-                },
-                timestamp: SgBigInt("1".to_string()),
-                transaction: SgTransaction {
-                    id: bytes.clone(),
-                    from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: SgBigInt("1".to_string()),
-                },
-                orderbook: SgOrderbook { id: bytes.clone() },
+                from: bytes.clone(),
+                block_number: SgBigInt("".to_string()),
+                timestamp: SgBigInt(timestamp.to_string()),
             },
-            input_vault_balance_change: SgTradeVaultBalanceChange {
-                id: bytes.clone(),
-                __typename: "TradeVaultBalanceChange".to_string(),
-                amount: SgBytes(five_str.clone()),
-                new_vault_balance: SgBytes(two_str.clone()),
-                old_vault_balance: bigint.clone(),
-                vault: SgVaultBalanceChangeVault {
-                    id: bytes.clone(),
-                    token: token2.clone(),
-                    vault_id: SgBigInt(vault_id2.to_string()),
+            orderbook: SgOrderbook { id: bytes },
+            trade: SgTradeRef {
+                trade_event: SgTradeEventTypename {
+                    __typename: "TakeOrder".to_string(),
                 },
-                timestamp: SgBigInt("1".to_string()),
-                transaction: SgTransaction {
-                    id: bytes.clone(),
-                    from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: SgBigInt("1".to_string()),
-                },
-                orderbook: SgOrderbook { id: bytes.clone() },
             },
-        };
+        }
+    }
 
-        let trade2 = SgTrade {
+    fn trade(
+        timestamp: &str,
+        input: SgTradeVaultBalanceChange,
+        output: SgTradeVaultBalanceChange,
+    ) -> SgTrade {
+        let bytes = SgBytes("".to_string());
+        SgTrade {
             id: bytes.clone(),
             order: SgTradeStructPartialOrder {
                 id: bytes.clone(),
@@ -412,53 +309,33 @@ mod tests {
                 transaction: SgTransaction {
                     id: bytes.clone(),
                     from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: bigint.clone(),
-                },
-            },
-            timestamp: SgBigInt("2".to_string()),
-            orderbook: SgOrderbook { id: bytes.clone() },
-            output_vault_balance_change: SgTradeVaultBalanceChange {
-                id: bytes.clone(),
-                __typename: "TradeVaultBalanceChange".to_string(),
-                amount: SgBytes(minus_two_str.clone()),
-                new_vault_balance: new_vault_balance.clone(),
-                old_vault_balance: bytes.clone(),
-                vault: SgVaultBalanceChangeVault {
-                    id: bytes.clone(),
-                    token: token2.clone(),
-                    vault_id: SgBytes(vault_id2.to_string()),
-                },
-                timestamp: SgBigInt("2".to_string()),
-                transaction: SgTransaction {
-                    id: bytes.clone(),
-                    from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: SgBigInt("1".to_string()),
+                    block_number: SgBigInt("".to_string()),
+                    timestamp: SgBigInt(timestamp.to_string()),
                 },
-                orderbook: SgOrderbook { id: bytes.clone() },
             },
-            input_vault_balance_change: SgTradeVaultBalanceChange {
-                id: bytes.clone(),
-                __typename: "TradeVaultBalanceChange".to_string(),
-                new_vault_balance,
-                amount: SgBigInt(seven),
-                old_vault_balance: bigint.clone(),
-                vault: SgVaultBalanceChangeVault {
-                    id: bytes.clone(),
-                    token: token1.clone(),
-                    vault_id: SgBytes(vault_id1.to_string()),
-                },
-                timestamp: SgBigInt("2".to_string()),
-                transaction: SgTransaction {
-                    id: bytes.clone(),
-                    from: bytes.clone(),
-                    block_number: bigint.clone(),
-                    timestamp: SgBigInt("1".to_string()),
-                },
-                orderbook: SgOrderbook { id: bytes.clone() },
-            },
-        };
+            timestamp: SgBigInt(timestamp.to_string()),
+            orderbook: SgOrderbook { id: bytes },
+            input_vault_balance_change: input,
+            output_vault_balance_change: output,
+        }
+    }
+
+    /// Two trades in desc order, the latest one sets both vaults' balance
+    /// to `new_vault_balance`
+    fn get_trades(new_vault_balance: SgBytes) -> Vec<SgTrade> {
+        let [vault_id1, vault_id2] = get_vault_ids();
+        let [token1, token2] = get_tokens();
+
+        let trade1 = trade(
+            "1",
+            balance_change(vault_id2, token2.clone(), F5, SgBytes(F2.as_hex()), "1"),
+            balance_change(vault_id1, token1.clone(), NEG2, SgBytes(F2.as_hex()), "1"),
+        );
+        let trade2 = trade(
+            "2",
+            balance_change(vault_id1, token1, F7, new_vault_balance.clone(), "2"),
+            balance_change(vault_id2, token2, NEG2, new_vault_balance, "2"),
+        );
         vec![trade2, trade1]
     }
 }
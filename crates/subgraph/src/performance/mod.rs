@@ -1,26 +1,20 @@
-use rain_math_float::FloatError;
+use alloy::primitives::U256;
+use rain_math_float::{Float, FloatError};
 use rain_orderbook_math::MathError;
 use std::num::ParseIntError;
 use thiserror::Error;
 
-// TODO: APY related logic
-// use alloy::primitives::{ruint::ParseError, U256};
-// use chrono::TimeDelta;
-// use once_cell::sync::Lazy;
-// use rain_orderbook_math::ONE18;
-
-// TODO: APY related logic
-// pub mod apy;
-// mod order_performance;
+pub mod apy;
+mod order_performance;
 pub mod vol;
 
-// TODO: APY related logic
-// pub use order_performance::*;
+pub use order_performance::*;
+
+/// a year length in seconds
+pub const YEAR_SECONDS: u64 = 365 * 24 * 60 * 60;
 
-// TODO: APY related logic
-// /// a year length timestamp in seconds as 18 point decimals as U256
-// pub static YEAR18: Lazy<U256> =
-//     Lazy::new(|| U256::from(TimeDelta::days(365).num_seconds()).saturating_mul(ONE18));
+/// a day length in seconds
+pub const DAY_SECONDS: u64 = 24 * 60 * 60;
 
 #[derive(Error, Debug)]
 pub enum PerformanceError {
@@ -34,11 +28,13 @@ pub enum PerformanceError {
     DivByZero,
     #[error("Missing decimals in subgraph response")]
     MissingDecimals,
-    // TODO: APY related logic
-    // #[error(transparent)]
-    // ParseUnsignedError(#[from] ParseError),
-    // #[error("Found no trades")]
-    // NoTrades,
-    // #[error("Parsing error: {0}")]
-    // ParsingError(#[from] serde_json::Error),
+    #[error("Found no trades")]
+    NoTrades,
+}
+
+/// Ratio of the `start..end` timeframe to a year
+pub fn annual_rate(start: u64, end: u64) -> Result<Float, PerformanceError> {
+    let elapsed = Float::from_fixed_decimal(U256::from(end.saturating_sub(start)), 0)?;
+    let year = Float::from_fixed_decimal(U256::from(YEAR_SECONDS), 0)?;
+    Ok((elapsed / year)?)
 }
@@ -1,17 +1,16 @@
 use super::apy::APYDetails;
 use super::vol::VolumeDetails;
-use super::{PerformanceError, YEAR18};
+use super::{annual_rate, PerformanceError};
 use crate::performance::apy::{get_vaults_apy, TokenPair};
 use crate::{
     performance::vol::get_vaults_vol,
     types::common::{SgErc20, SgOrder, SgTrade},
 };
-use alloy::primitives::U256;
+use alloy::primitives::Address;
 use rain_math_float::Float;
-use rain_orderbook_math::{BigUintMath, ONE18};
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::str::FromStr;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
 
@@ -35,19 +34,19 @@ pub struct VaultPerformance {
 pub struct DenominatedPerformance {
     /// The denomination token
     pub token: SgErc20,
-    /// Order's APY raw value
+    /// Order's APY
     #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
-    pub apy: U256,
+    pub apy: Float,
     /// Determines if apy is negative or not
     pub apy_is_neg: bool,
-    /// Order's net vol raw value
+    /// Order's net vol
     #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
-    pub net_vol: U256,
+    pub net_vol: Float,
     /// Determines if net_vol is negative or not
     pub net_vol_is_neg: bool,
     /// Order's starting capital
     #[cfg_attr(target_family = "wasm", tsify(type = "string"))]
-    pub starting_capital: U256,
+    pub starting_capital: Float,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +89,20 @@ impl OrderPerformance {
         trades: &[SgTrade],
         start_timestamp: Option<u64>,
         end_timestamp: Option<u64>,
+    ) -> Result<OrderPerformance, PerformanceError> {
+        Self::measure_denominated(order, trades, None, start_timestamp, end_timestamp)
+    }
+
+    /// Same as [`OrderPerformance::measure`], but when `denomination` is set the
+    /// order's whole performance is only measured in that token, which must be
+    /// one of the order's io tokens. Otherwise the io token with the highest
+    /// net vol is picked.
+    pub fn measure_denominated(
+        order: &SgOrder,
+        trades: &[SgTrade],
+        denomination: Option<Address>,
+        start_timestamp: Option<u64>,
+        end_timestamp: Option<u64>,
     ) -> Result<OrderPerformance, PerformanceError> {
         // return early if there are no trades
         if trades.is_empty() {
@@ -114,31 +127,28 @@ impl OrderPerformance {
                     end_time = v.end_time;
                 }
             });
+            let vault_performance = VaultPerformance {
+                id: vault_apy.id.clone(),
+                token: vault_apy.token.clone(),
+                apy_details: vault_apy.apy_details,
+                vol_details: vault_vol.vol_details,
+            };
             if order
                 .inputs
                 .iter()
                 .any(|v| v.vault_id.0 == vault_apy.id && v.token == vault_apy.token)
             {
-                inputs.push(VaultPerformance {
-                    id: vault_apy.id.clone(),
-                    token: vault_apy.token.clone(),
-                    apy_details: vault_apy.apy_details,
-                    vol_details: vault_vol.vol_details,
-                });
+                inputs.push(vault_performance.clone());
             }
             if order
                 .outputs
                 .iter()
                 .any(|v| v.vault_id.0 == vault_apy.id && v.token == vault_apy.token)
             {
-                outputs.push(VaultPerformance {
-                    id: vault_apy.id.clone(),
-                    token: vault_apy.token.clone(),
-                    apy_details: vault_apy.apy_details,
-                    vol_details: vault_vol.vol_details,
-                });
+                outputs.push(vault_performance);
             }
         }
+
         let mut order_performance = OrderPerformance {
             order_id: order.id.0.clone(),
             order_hash: order.order_hash.0.clone(),
@@ -164,92 +174,63 @@ impl OrderPerformance {
         let mut tokens_vol_list: Vec<TokenBasedVol> = vec![];
         let mut token_denominated_performance = vec![];
         for token in &vaults_apy {
-            // skip if token is alreaedy processed
+            // skip if token is alreaedy processed or isn't the requested denomination
             if processed_tokens.contains(&&token.token) {
                 continue;
-            } else {
-                processed_tokens.push(&token.token);
             }
+            processed_tokens.push(&token.token);
+            if denomination.is_some_and(|address| !is_token(&token.token, address)) {
+                continue;
+            }
+
             let mut noway = false;
-            let mut net_vol_is_neg = false;
-            let mut net_vol_rate_is_neg = false;
-            let mut acc_capital = U256::ZERO;
-            let mut acc_net_vol = U256::ZERO;
-            let mut acc_annual_rate_vol = U256::ZERO;
+            let mut acc_capital = Float::default();
+            let mut acc_net_vol = Float::default();
+            let mut acc_annual_rate_vol = Float::default();
             let mut current_token_vol_list: Vec<TokenBasedVol> = vec![];
             for token_vault in &vaults_apy {
-                if let Some(apy_details) = token_vault.apy_details {
-                    // this vault's timeframe to year ratio
-                    let annual_rate = U256::from(apy_details.end_time - apy_details.start_time)
-                        .saturating_mul(ONE18)
-                        .div_18(YEAR18
-                        .map_err(PerformanceError::from)?;
-
-                    // sum up all token vaults' capitals and vols by using the direct ratio between the tokens
-                    if token_vault.token == token.token {
-                        acc_capital += apy_details.capital;
-                        (acc_net_vol, net_vol_is_neg) = accumulate(
-                            (apy_details.net_vol, apy_details.is_neg),
-                            (acc_net_vol, net_vol_is_neg),
-                        );
-
-                        let annual_rate_vol = apy_details
-                            .net_vol
-                            .div_18(annual_rate)
-                            .map_err(PerformanceError::from)?;
-                        (acc_annual_rate_vol, net_vol_rate_is_neg) = accumulate(
-                            (annual_rate_vol, apy_details.is_neg),
-                            (acc_annual_rate_vol, net_vol_rate_is_neg),
-                        );
-
-                        current_token_vol_list.push(TokenBasedVol {
-                            net_vol: apy_details.net_vol,
-                            is_neg: apy_details.is_neg,
-                            token: &token.token,
-                        });
-                    } else {
-                        let pair = TokenPair {
-                            input: token.token.clone(),
-                            output: token_vault.token.clone(),
-                        };
-                        // convert to current denomination by the direct pair ratio if it exists
-                        if let Some(Some(ratio)) = pair_ratio_map.get(&pair) {
-                            let capital_converted = apy_details
-                                .capital
-                                .mul_18(*ratio)
-                                .map_err(PerformanceError::from)?;
-                            acc_capital += capital_converted;
-
-                            let net_vol_converted = apy_details
-                                .net_vol
-                                .mul_18(*ratio)
-                                .map_err(PerformanceError::from)?;
-                            (acc_net_vol, net_vol_is_neg) = accumulate(
-                                (net_vol_converted, apy_details.is_neg),
-                                (acc_net_vol, net_vol_is_neg),
-                            );
-
-                            let annual_rate_vol_converted = net_vol_converted
-                                .div_18(annual_rate)
-                                .map_err(PerformanceError::from)?;
-                            (acc_annual_rate_vol, net_vol_rate_is_neg) = accumulate(
-                                (annual_rate_vol_converted, apy_details.is_neg),
-                                (acc_annual_rate_vol, net_vol_rate_is_neg),
-                            );
-
-                            current_token_vol_list.push(TokenBasedVol {
-                                net_vol: net_vol_converted,
-                                is_neg: apy_details.is_neg,
-                                token: &token_vault.token,
-                            });
-                        } else {
-                            // if found no way to convert (there were no direct ratio between the tokens),
-                            // break the loop and go to the next token and try that
+                let Some(apy_details) = token_vault.apy_details else {
+                    continue;
+                };
+                // this vault's timeframe to year ratio
+                let annual_rate = annual_rate(apy_details.start_time, apy_details.end_time)?;
+
+                // convert to current denomination by the direct pair ratio if it exists
+                let ratio = if token_vault.token == token.token {
+                    None
+                } else {
+                    let pair = TokenPair {
+                        input: token.token.clone(),
+                        output: token_vault.token.clone(),
+                    };
+                    match pair_ratio_map.get(&pair) {
+                        Some(Some(ratio)) => Some(*ratio),
+                        // if found no way to convert (there were no direct ratio between the tokens),
+                        // break the loop and go to the next token and try that
+                        _ => {
                             noway = true;
                             break;
                         }
                     }
+                };
+                let (capital, net_vol) = match ratio {
+                    Some(ratio) => (
+                        (apy_details.capital * ratio)?,
+                        (apy_details.net_vol * ratio)?,
+                    ),
+                    None => (apy_details.capital, apy_details.net_vol),
+                };
+
+                // sum up all token vaults' capitals and vols in the current denomination
+                acc_capital = (acc_capital + capital)?;
+                acc_net_vol = (acc_net_vol + net_vol)?;
+                if !annual_rate.is_zero()? {
+                    acc_annual_rate_vol = (acc_annual_rate_vol + (net_vol / annual_rate)?)?;
                 }
+                current_token_vol_list.push(TokenBasedVol {
+                    net_vol,
+                    token: &token_vault.token,
+                });
             }
 
             // for every success apy calc in a token denomination, gather them in an array,
@@ -257,41 +238,44 @@ impl OrderPerformance {
             // the order's io tokens, and will pick the one that its token had the highest
             // net vol amon all other vaults
             if !noway {
-                if let Ok(apy) = acc_annual_rate_vol.div_18(acc_capital) {
+                if !acc_capital.is_zero()? {
+                    let apy = (acc_annual_rate_vol / acc_capital)?;
                     token_denominated_performance.push(DenominatedPerformance {
                         apy,
-                        apy_is_neg: net_vol_rate_is_neg,
+                        apy_is_neg: apy.lt(Float::default())?,
                         token: token.token.clone(),
                         starting_capital: acc_capital,
                         net_vol: acc_net_vol,
-                        net_vol_is_neg,
+                        net_vol_is_neg: acc_net_vol.lt(Float::default())?,
                     });
                 }
-
                 // if we found a way to calculate apy in the current token denomination,
                 // we'll include all the tokens vaults net vol in this array to have a list
-                // of tokens net vols converted to current token
-                // later, sorting this array will give us the highest to lowest tokens net vols
-                // to pick the denomination from
+                // of tokens net vols converted to current token to pick the denomination from
                 if tokens_vol_list.is_empty() {
                     tokens_vol_list.extend(current_token_vol_list);
                 }
             }
         }
 
-        // after array is sorted, pick the denomination with highest net vol by
-        // iterating over tokens with highest vol to lowest and pick the first matching one
-        tokens_vol_list.sort();
-        for token in tokens_vol_list.iter().rev() {
-            if let Some(denominated_performance) = token_denominated_performance
+        // pick the denomination whose token had the highest net vol
+        let mut highest: Option<(Float, &DenominatedPerformance)> = None;
+        for token_vol in &tokens_vol_list {
+            let Some(denominated_performance) = token_denominated_performance
                 .iter()
-                .find(|&v| &v.token == token.token)
-            {
-                order_performance.denominated_performance = Some(denominated_performance.clone());
-                // return early as soon as a match is found
-                return Ok(order_performance);
+                .find(|v| &v.token == token_vol.token)
+            else {
+                continue;
+            };
+            let is_higher = match &highest {
+                Some((net_vol, _)) => token_vol.net_vol.gt(*net_vol)?,
+                None => true,
+            };
+            if is_higher {
+                highest = Some((token_vol.net_vol, denominated_performance));
             }
         }
+        order_performance.denominated_performance = highest.map(|(_, v)| v.clone());
 
         Ok(order_performance)
     }
@@ -303,8 +287,8 @@ impl OrderPerformance {
 pub fn get_order_pairs_ratio(
     order: &SgOrder,
     trades: &[SgTrade],
-) -> HashMap<TokenPair, Option<U256>> {
-    let mut pair_ratio_map: HashMap<TokenPair, Option<U256>> = HashMap::new();
+) -> HashMap<TokenPair, Option<Float>> {
+    let mut pair_ratio_map: HashMap<TokenPair, Option<Float>> = HashMap::new();
     for input in &order.inputs {
         for output in &order.outputs {
             let pair_as_key = TokenPair {
@@ -358,317 +342,54 @@ pub fn get_order_pairs_ratio(
     pair_ratio_map
 }
 
-fn accumulate(new_val: (U256, bool), old_val: (U256, bool)) -> (U256, bool) {
-    let mut acc = old_val.0;
-    let mut sign = old_val.1;
-    if new_val.1 == sign {
-        acc += new_val.0;
-    } else if sign {
-        if new_val.0 >= acc {
-            sign = false;
-            acc = new_val.0 - acc;
-        } else {
-            acc -= new_val.0;
-        }
-    } else if acc >= new_val.0 {
-        acc -= new_val.0;
-    } else {
-        sign = true;
-        acc = new_val.0 - acc;
-    }
-    (acc, sign)
+fn is_token(token: &SgErc20, address: Address) -> bool {
+    Address::from_str(&token.address.0).is_ok_and(|v| v == address)
 }
 
-/// helper struct that provides sorting tokens based on a given net vol
+/// helper struct that tracks a token vault's net vol in a denomination
 #[derive(Debug, Clone)]
 struct TokenBasedVol<'a> {
     token: &'a SgErc20,
     net_vol: Float,
-    is_neg: bool,
-}
-
-impl<'a> Ord for TokenBasedVol<'a> {
-    fn clamp(self, _min: Self, _max: Self) -> Self
-    where
-        Self: Sized,
-        Self: PartialOrd,
-    {
-        self
-    }
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.is_neg == other.is_neg {
-            match self.net_vol.cmp(&other.net_vol) {
-                Ordering::Greater => {
-                    if self.is_neg {
-                        Ordering::Less
-                    } else {
-                        Ordering::Greater
-                    }
-                }
-                Ordering::Less => {
-                    if self.is_neg {
-                        Ordering::Greater
-                    } else {
-                        Ordering::Less
-                    }
-                }
-                Ordering::Equal => Ordering::Equal,
-            }
-        } else if self.is_neg {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
-    }
-    fn min(self, other: Self) -> Self
-    where
-        Self: Sized,
-    {
-        match self.cmp(&other) {
-            Ordering::Greater => other,
-            Ordering::Less => self,
-            Ordering::Equal => self,
-        }
-    }
-    fn max(self, other: Self) -> Self
-    where
-        Self: Sized,
-    {
-        match self.cmp(&other) {
-            Ordering::Greater => self,
-            Ordering::Less => other,
-            Ordering::Equal => self,
-        }
-    }
-}
-impl<'a> PartialOrd for TokenBasedVol<'a> {
-    fn ge(&self, other: &Self) -> bool {
-        !matches!(self.cmp(other), Ordering::Less)
-    }
-    fn le(&self, other: &Self) -> bool {
-        !matches!(self.cmp(other), Ordering::Greater)
-    }
-    fn gt(&self, other: &Self) -> bool {
-        matches!(self.cmp(other), Ordering::Greater)
-    }
-    fn lt(&self, other: &Self) -> bool {
-        matches!(self.cmp(other), Ordering::Less)
-    }
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::*;
     use super::*;
     use crate::types::common::{
-        SgBigInt, SgBytes, SgOrder, SgOrderbook, SgTradeEvent, SgTradeStructPartialOrder,
-        SgTradeVaultBalanceChange, SgTransaction, SgVault, SgVaultBalanceChangeVault,
+        SgBigInt, SgBytes, SgOrder, SgOrderbook, SgTradeEvent, SgTradeEventTypename, SgTradeRef,
+        SgTradeStructPartialOrder, SgTradeVaultBalanceChange, SgTransaction, SgVault,
+        SgVaultBalanceChangeVault,
     };
-    use alloy::primitives::{Address, B256};
-    use std::str::FromStr;
-
-    #[test]
-    fn test_token_based_vol_ord_parial_ord() {
-        let token_address = Address::random();
-        let token = SgErc20 {
-            id: SgBytes(token_address.to_string()),
-            address: SgBytes(token_address.to_string()),
-            name: Some("Token".to_string()),
-            symbol: Some("Token".to_string()),
-            decimals: Some(SgBigInt(6.to_string())),
-        };
-
-        // positive == positive
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: false,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: false,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Equal));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Equal)));
-        assert_eq!(a.clone().min(b.clone()), a);
-        assert_eq!(a.clone().max(b.clone()), a);
-        assert!(!a.gt(&b));
-        assert!(a.ge(&b));
-        assert!(a.le(&b));
-        assert!(!a.lt(&b));
-
-        // negative == negative
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: true,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: true,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Equal));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Equal)));
-        assert_eq!(a.clone().min(b.clone()), a);
-        assert_eq!(a.clone().max(b.clone()), a);
-        assert!(!a.gt(&b));
-        assert!(a.ge(&b));
-        assert!(a.le(&b));
-        assert!(!a.lt(&b));
-
-        // positive > positive
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(2),
-            is_neg: false,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: false,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Greater));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Greater)));
-        assert_eq!(a.clone().min(b.clone()), b);
-        assert_eq!(a.clone().max(b.clone()), a);
-        assert!(a.gt(&b));
-        assert!(a.ge(&b));
-        assert!(!a.le(&b));
-        assert!(!a.lt(&b));
-
-        // positive < positive
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: false,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(2),
-            is_neg: false,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Less));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Less)));
-        assert_eq!(a.clone().min(b.clone()), a);
-        assert_eq!(a.clone().max(b.clone()), b);
-        assert!(!a.gt(&b));
-        assert!(!a.ge(&b));
-        assert!(a.le(&b));
-        assert!(a.lt(&b));
-
-        // negative > negative
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: true,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(2),
-            is_neg: true,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Greater));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Greater)));
-        assert_eq!(a.clone().min(b.clone()), b);
-        assert_eq!(a.clone().max(b.clone()), a);
-        assert!(a.gt(&b));
-        assert!(a.ge(&b));
-        assert!(!a.le(&b));
-        assert!(!a.lt(&b));
-
-        // negative < negative
-        let a = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(2),
-            is_neg: true,
-        };
-        let b = TokenBasedVol {
-            token: &token,
-            net_vol: U256::from(1),
-            is_neg: true,
-        };
-        assert!(matches!(a.cmp(&b), Ordering::Less));
-        assert!(matches!(a.partial_cmp(&b), Some(Ordering::Less)));
-        assert_eq!(a.clone().min(b.clone()), a);
-        assert_eq!(a.clone().max(b.clone()), b);
-        assert!(!a.gt(&b));
-        assert!(!a.ge(&b));
-        assert!(a.le(&b));
-        assert!(a.lt(&b));
-    }
-
-    #[test]
-    fn test_accumulate() {
-        // both positive
-        let new_val = (U256::from(1), false);
-        let old_val = (U256::from(2), false);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(3), false);
-        assert_eq!(result, expected);
-
-        // both negative
-        let new_val = (U256::from(1), true);
-        let old_val = (U256::from(2), true);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(3), true);
-        assert_eq!(result, expected);
-
-        // negative < positive
-        let new_val = (U256::from(1), true);
-        let old_val = (U256::from(2), false);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(1), false);
-        assert_eq!(result, expected);
-
-        // negative > positive
-        let new_val = (U256::from(2), true);
-        let old_val = (U256::from(1), false);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(1), true);
-        assert_eq!(result, expected);
-
-        // positive < negative
-        let new_val = (U256::from(1), false);
-        let old_val = (U256::from(2), true);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(1), true);
-        assert_eq!(result, expected);
-
-        // positive > negative
-        let new_val = (U256::from(2), false);
-        let old_val = (U256::from(1), true);
-        let result = accumulate(new_val, old_val);
-        let expected = (U256::from(1), false);
-        assert_eq!(result, expected);
-    }
+    use crate::utils::float::*;
+    use alloy::primitives::B256;
 
     #[test]
     fn test_get_pairs_ratio() {
         let trades = get_trades();
         let [token1, token2] = get_tokens();
         let result = get_order_pairs_ratio(&get_order(), &trades);
-        let mut expected = HashMap::new();
-        expected.insert(
-            TokenPair {
-                input: token2.clone(),
-                output: token1.clone(),
-            },
-            Some(U256::from_str("285714285714285714").unwrap()),
-        );
-        expected.insert(
-            TokenPair {
+        assert_eq!(result.len(), 2);
+
+        // latest trade is 7 token1 in for 2 token2 out
+        let ratio = result
+            .get(&TokenPair {
                 input: token1.clone(),
                 output: token2.clone(),
-            },
-            Some(U256::from_str("3500000000000000000").unwrap()),
-        );
-
-        assert_eq!(result, expected);
+            })
+            .unwrap()
+            .unwrap();
+        assert!(ratio.eq(Float::parse("3.5".to_string()).unwrap()).unwrap());
+
+        let ratio = result
+            .get(&TokenPair {
+                input: token2,
+                output: token1,
+            })
+            .unwrap()
+            .unwrap();
+        let expected = (F2 / F7).unwrap();
+        assert!(ratio.eq(expected).unwrap());
     }
 
     #[test]
@@ -678,23 +399,21 @@ mod test {
         trades[0].input_vault_balance_change.amount = SgBytes("abcd".to_string());
         let [token1, token2] = get_tokens();
         let result = get_order_pairs_ratio(&get_order(), &trades);
-        let mut expected = HashMap::new();
-        expected.insert(
-            TokenPair {
+        assert_eq!(result.len(), 2);
+        assert!(result
+            .get(&TokenPair {
                 input: token2.clone(),
                 output: token1.clone(),
-            },
-            None,
-        );
-        expected.insert(
-            TokenPair {
-                input: token1.clone(),
-                output: token2.clone(),
-            },
-            None,
-        );
-
-        assert_eq!(result, expected);
+            })
+            .unwrap()
+            .is_none());
+        assert!(result
+            .get(&TokenPair {
+                input: token1,
+                output: token2,
+            })
+            .unwrap()
+            .is_none());
     }
 
     #[test]
@@ -704,62 +423,88 @@ mod test {
         let [token1, token2] = get_tokens();
         let [vault1, vault2] = get_vault_ids();
 
-        let token1_perf = VaultPerformance {
-            id: vault1.to_string(),
-            token: token1.clone(),
-            apy_details: Some(APYDetails {
-                start_time: 1,
-                end_time: 10000001,
-                net_vol: F5,
-                capital: F5,
-                apy: Some(Float::parse("3.1536".to_string()).unwrap()),
-                is_neg: false,
-            }),
-            vol_details: VolumeDetails {
-                net_vol: U256::from_str("5000000000000000000").unwrap(),
-                total_in: U256::from_str("7000000000000000000").unwrap(),
-                total_out: U256::from_str("2000000000000000000").unwrap(),
-                total_vol: U256::from_str("9000000000000000000").unwrap(),
-            },
-        };
-        let token2_perf = VaultPerformance {
-            id: vault2.to_string(),
-            token: token2.clone(),
-            apy_details: Some(APYDetails {
-                start_time: 1,
-                end_time: 10000001,
-                net_vol: U256::from_str("3000000000000000000").unwrap(),
-                capital: U256::from_str("5000000000000000000").unwrap(),
-                apy: Some(U256::from_str("1892160000000000000").unwrap()),
-                is_neg: false,
-            }),
-            vol_details: VolumeDetails {
-                net_vol: U256::from_str("3000000000000000000").unwrap(),
-                total_in: U256::from_str("5000000000000000000").unwrap(),
-                total_out: U256::from_str("2000000000000000000").unwrap(),
-                total_vol: U256::from_str("7000000000000000000").unwrap(),
-            },
-        };
         let result = OrderPerformance::measure(&order, &trades, Some(1), Some(10000001)).unwrap();
-        let expected = OrderPerformance {
-            order_id: "order-id".to_string(),
-            order_hash: "".to_string(),
-            orderbook: "".to_string(),
-            start_time: 1,
-            end_time: 10000001,
-            inputs_vaults: vec![token1_perf.clone(), token2_perf.clone()],
-            outputs_vaults: vec![token1_perf.clone(), token2_perf.clone()],
-            denominated_performance: Some(DenominatedPerformance {
-                apy: U256::from_str("2172479999999999999").unwrap(),
-                apy_is_neg: false,
-                token: token2,
-                net_vol: U256::from_str("4428571428571428570").unwrap(),
-                starting_capital: U256::from_str("6428571428571428570").unwrap(),
-                net_vol_is_neg: false,
-            }),
-        };
+        assert_eq!(result.order_id, "order-id");
+        assert_eq!(result.start_time, 1);
+        assert_eq!(result.end_time, 10000001);
+        assert_eq!(result.inputs_vaults.len(), 2);
+        assert_eq!(result.outputs_vaults.len(), 2);
+
+        let token1_perf = &result.inputs_vaults[0];
+        assert_eq!(token1_perf.id, vault1.to_string());
+        assert_eq!(token1_perf.token, token1);
+        assert!(token1_perf.vol_details.net_vol.eq(F5).unwrap());
+        assert!(token1_perf.vol_details.total_in.eq(F7).unwrap());
+        assert!(token1_perf.vol_details.total_out.eq(F2).unwrap());
+        let apy_details = token1_perf.apy_details.unwrap();
+        assert!(apy_details.capital.eq(F5).unwrap());
+        // (5/5) / ((10000001_end - 1_start) / 31_536_000_year)
+        let expected = Float::parse("3.1536".to_string()).unwrap();
+        assert!(apy_details.apy.unwrap().eq(expected).unwrap());
+
+        let token2_perf = &result.inputs_vaults[1];
+        assert_eq!(token2_perf.id, vault2.to_string());
+        assert_eq!(token2_perf.token, token2);
+        assert!(token2_perf.vol_details.net_vol.eq(F3).unwrap());
+        let apy_details = token2_perf.apy_details.unwrap();
+        assert!(apy_details.capital.eq(F5).unwrap());
+        // (3/5) / ((10000001_end - 1_start) / 31_536_000_year)
+        let expected = Float::parse("1.89216".to_string()).unwrap();
+        assert!(apy_details.apy.unwrap().eq(expected).unwrap());
+
+        // in token2 denomination token1's 5 net vol is worth 5 * 2/7 while in
+        // token1 denomination token2's 3 net vol is worth 10.5, so token2 wins
+        let to_token2 = (F2 / F7).unwrap();
+        let denominated = result.denominated_performance.unwrap();
+        assert_eq!(denominated.token, token2);
+        let expected_capital = ((F5 * to_token2).unwrap() + F5).unwrap();
+        assert!(denominated.starting_capital.eq(expected_capital).unwrap());
+        let expected_net_vol = ((F5 * to_token2).unwrap() + F3).unwrap();
+        assert!(denominated.net_vol.eq(expected_net_vol).unwrap());
+        assert!(!denominated.net_vol_is_neg);
+        assert!(!denominated.apy_is_neg);
+    }
+
+    #[test]
+    fn test_measure_order_performance_in_denomination() {
+        let order = get_order();
+        let trades = get_trades();
+        let [token1, _] = get_tokens();
+        let token1_address = Address::from_str(&token1.address.0).unwrap();
+
+        let result = OrderPerformance::measure_denominated(
+            &order,
+            &trades,
+            Some(token1_address),
+            Some(1),
+            Some(10000001),
+        )
+        .unwrap();
+        let denominated = result.denominated_performance.unwrap();
+        assert_eq!(denominated.token, token1);
+        // token2's 5 capital and 3 net vol at 3.5 token1 per token2
+        let expected_capital = Float::parse("22.5".to_string()).unwrap();
+        assert!(denominated.starting_capital.eq(expected_capital).unwrap());
+        let expected_net_vol = Float::parse("15.5".to_string()).unwrap();
+        assert!(denominated.net_vol.eq(expected_net_vol).unwrap());
+
+        // not one of the order's tokens
+        let result = OrderPerformance::measure_denominated(
+            &order,
+            &trades,
+            Some(Address::repeat_byte(0x99)),
+            Some(1),
+            Some(10000001),
+        )
+        .unwrap();
+        assert!(result.denominated_performance.is_none());
+        assert_eq!(result.inputs_vaults.len(), 2);
+    }
 
-        assert_eq!(result, expected);
+    #[test]
+    fn test_measure_order_performance_no_trades() {
+        let err = OrderPerformance::measure(&get_order(), &[], None, None).unwrap_err();
+        assert!(matches!(err, PerformanceError::NoTrades));
     }
 
     fn get_vault_ids() -> [B256; 2] {
@@ -830,7 +575,7 @@ mod test {
                 id: SgBytes("".to_string()),
             },
             active: true,
-            timestamp_added: SgBytes("".to_string()),
+            timestamp_added: SgBigInt("".to_string()),
             meta: None,
             add_events: vec![],
             trades: vec![],
@@ -864,7 +609,7 @@ mod test {
             output_vault_balance_change: SgTradeVaultBalanceChange {
                 id: bytes.clone(),
                 __typename: "TradeVaultBalanceChange".to_string(),
-                amount: SgBytes(F5.as_hex()),
+                amount: SgBytes(NEG2.as_hex()),
                 new_vault_balance: SgBytes(F2.as_hex()),
                 old_vault_balance: bytes.clone(),
                 vault: SgVaultBalanceChangeVault {
@@ -880,6 +625,11 @@ mod test {
                     timestamp: SgBigInt("1".to_string()),
                 },
                 orderbook: SgOrderbook { id: bytes.clone() },
+                trade: SgTradeRef {
+                    trade_event: SgTradeEventTypename {
+                        __typename: "TakeOrder".to_string(),
+                    },
+                },
             },
             input_vault_balance_change: SgTradeVaultBalanceChange {
                 id: bytes.clone(),
@@ -900,6 +650,11 @@ mod test {
                     timestamp: SgBigInt("1".to_string()),
                 },
                 orderbook: SgOrderbook { id: bytes.clone() },
+                trade: SgTradeRef {
+                    trade_event: SgTradeEventTypename {
+                        __typename: "TakeOrder".to_string(),
+                    },
+                },
             },
         };
 
@@ -939,6 +694,11 @@ mod test {
                     timestamp: SgBigInt("1".to_string()),
                 },
                 orderbook: SgOrderbook { id: bytes.clone() },
+                trade: SgTradeRef {
+                    trade_event: SgTradeEventTypename {
+                        __typename: "TakeOrder".to_string(),
+                    },
+                },
             },
             input_vault_balance_change: SgTradeVaultBalanceChange {
                 id: bytes.clone(),
@@ -959,6 +719,11 @@ mod test {
                     timestamp: SgBigInt("1".to_string()),
                 },
                 orderbook: SgOrderbook { id: bytes.clone() },
+                trade: SgTradeRef {
+                    trade_event: SgTradeEventTypename {
+                        __typename: "TakeOrder".to_string(),
+                    },
+                },
             },
         };
         vec![trade2, trade1]
@@ -0,0 +1,34 @@
+use super::common::*;
+use crate::schema;
+use serde::Serialize;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_utils::{impl_wasm_traits, prelude::*};
+
+#[derive(cynic::QueryFragment, Debug, Clone, Serialize)]
+#[cynic(graphql_type = "_Block_")]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+pub struct SgMetaBlock {
+    pub hash: Option<SgBytes>,
+    pub number: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug, Clone, Serialize)]
+#[cynic(graphql_type = "_Meta_")]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+pub struct SgMeta {
+    pub block: SgMetaBlock,
+}
+
+#[derive(cynic::QueryFragment, Debug, Serialize)]
+#[cynic(graphql_type = "Query")]
+#[cfg_attr(target_family = "wasm", derive(Tsify))]
+pub struct SgMetaQuery {
+    #[cynic(rename = "_meta")]
+    pub meta: Option<SgMeta>,
+}
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(SgMetaBlock);
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(SgMeta);
+#[cfg(target_family = "wasm")]
+impl_wasm_traits!(SgMetaQuery);
@@ -1,6 +1,7 @@
 pub mod add_order;
 pub mod common;
 mod impls;
+pub mod meta;
 pub mod order;
 pub mod order_detail_traits;
 pub mod order_trade;